@@ -0,0 +1,2 @@
+-- Track whether a meeting's stored transcripts had PII redacted
+ALTER TABLE meetings ADD COLUMN transcripts_redacted INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Store the language detected by the ASR provider when transcribing with auto-detection
+ALTER TABLE meetings ADD COLUMN detected_language TEXT;
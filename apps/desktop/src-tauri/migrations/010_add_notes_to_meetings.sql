@@ -0,0 +1,2 @@
+-- User-authored notes for a meeting, kept separate from generated insights
+ALTER TABLE meetings ADD COLUMN notes TEXT;
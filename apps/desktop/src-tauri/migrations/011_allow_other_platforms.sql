@@ -0,0 +1,54 @@
+-- Drop the CHECK constraint on meetings.platform so platforms other than
+-- Teams, Zoom and Meet (e.g. "webex", "in-person") can be stored.
+--
+-- SQLite can't drop a constraint in place, so the table is rebuilt. Foreign keys
+-- are disabled while migrations run, which keeps the rows in participants,
+-- transcripts and insights from being cascade-deleted by the DROP TABLE.
+
+CREATE TABLE meetings_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    platform TEXT NOT NULL CHECK(platform <> ''),
+    title TEXT,
+    start_time INTEGER NOT NULL,
+    end_time INTEGER,
+    participant_count INTEGER,
+    created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    audio_file_path TEXT,
+    language TEXT DEFAULT 'en',
+    transcripts_redacted INTEGER NOT NULL DEFAULT 0,
+    detected_language TEXT,
+    notes TEXT
+);
+
+INSERT INTO meetings_new (
+    id, platform, title, start_time, end_time, participant_count, created_at,
+    audio_file_path, language, transcripts_redacted, detected_language, notes
+)
+SELECT
+    id, platform, title, start_time, end_time, participant_count, created_at,
+    audio_file_path, language, transcripts_redacted, detected_language, notes
+FROM meetings;
+
+DROP TABLE meetings;
+ALTER TABLE meetings_new RENAME TO meetings;
+
+-- Indexes and FTS triggers were dropped with the old table
+CREATE INDEX idx_meetings_start_time ON meetings(start_time);
+CREATE INDEX idx_meetings_platform ON meetings(platform);
+CREATE INDEX idx_meetings_audio_file ON meetings(audio_file_path) WHERE audio_file_path IS NOT NULL;
+
+CREATE TRIGGER meetings_ai AFTER INSERT ON meetings BEGIN
+    INSERT INTO meetings_fts(rowid, title, platform)
+    VALUES (new.id, COALESCE(new.title, ''), new.platform);
+END;
+
+CREATE TRIGGER meetings_ad AFTER DELETE ON meetings BEGIN
+    DELETE FROM meetings_fts WHERE rowid = old.id;
+END;
+
+CREATE TRIGGER meetings_au AFTER UPDATE ON meetings BEGIN
+    UPDATE meetings_fts
+    SET title = COALESCE(new.title, ''),
+        platform = new.platform
+    WHERE rowid = new.id;
+END;
@@ -0,0 +1,3 @@
+-- Milliseconds of leading silence trimmed from the saved recording, added back to
+-- transcript timestamps so they stay relative to the start of the meeting
+ALTER TABLE meetings ADD COLUMN audio_offset_ms INTEGER NOT NULL DEFAULT 0;
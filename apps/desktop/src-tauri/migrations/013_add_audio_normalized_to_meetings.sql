@@ -0,0 +1,2 @@
+-- Whether the saved recording was amplified by loudness normalization
+ALTER TABLE meetings ADD COLUMN audio_normalized INTEGER NOT NULL DEFAULT 0;
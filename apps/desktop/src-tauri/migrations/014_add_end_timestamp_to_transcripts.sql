@@ -0,0 +1,3 @@
+-- End of each transcript segment in milliseconds into the meeting.
+-- NULL for segments stored before end times were recorded.
+ALTER TABLE transcripts ADD COLUMN end_timestamp_ms INTEGER;
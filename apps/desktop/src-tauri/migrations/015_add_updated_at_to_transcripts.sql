@@ -0,0 +1,2 @@
+-- Set when a transcript's text is corrected by hand; NULL for unedited segments
+ALTER TABLE transcripts ADD COLUMN updated_at INTEGER;
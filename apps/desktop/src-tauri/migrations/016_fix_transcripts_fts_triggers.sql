@@ -0,0 +1,25 @@
+-- Fix the transcripts_fts sync triggers
+--
+-- transcripts_fts is an external-content table, so FTS5 looks up the indexed values of
+-- a row it is asked to update or delete in `transcripts`. In AFTER triggers that row
+-- already holds the new values (or is gone), so the old tokens were never removed and
+-- updating a transcript's text could corrupt the index. Old values must be passed
+-- explicitly with the 'delete' command instead.
+
+DROP TRIGGER transcripts_ad;
+DROP TRIGGER transcripts_au;
+
+CREATE TRIGGER transcripts_ad AFTER DELETE ON transcripts BEGIN
+    INSERT INTO transcripts_fts(transcripts_fts, rowid, text, speaker_label, meeting_id, timestamp_ms)
+    VALUES ('delete', old.id, old.text, old.speaker_label, old.meeting_id, old.timestamp_ms);
+END;
+
+CREATE TRIGGER transcripts_au AFTER UPDATE ON transcripts BEGIN
+    INSERT INTO transcripts_fts(transcripts_fts, rowid, text, speaker_label, meeting_id, timestamp_ms)
+    VALUES ('delete', old.id, old.text, old.speaker_label, old.meeting_id, old.timestamp_ms);
+    INSERT INTO transcripts_fts(rowid, text, speaker_label, meeting_id, timestamp_ms)
+    VALUES (new.id, new.text, new.speaker_label, new.meeting_id, new.timestamp_ms);
+END;
+
+-- Drop whatever stale entries the old triggers left behind
+INSERT INTO transcripts_fts(transcripts_fts) VALUES ('rebuild');
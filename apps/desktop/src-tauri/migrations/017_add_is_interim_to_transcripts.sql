@@ -0,0 +1,3 @@
+-- Streaming segments stored before the provider finalized them; replaced in place by
+-- the final segment covering the same time range
+ALTER TABLE transcripts ADD COLUMN is_interim INTEGER NOT NULL DEFAULT 0;
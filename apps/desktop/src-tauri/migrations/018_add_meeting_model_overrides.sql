@@ -0,0 +1,9 @@
+-- Per-meeting ASR/LLM provider and model, preferred over the active service configs
+CREATE TABLE IF NOT EXISTS meeting_model_overrides (
+    meeting_id INTEGER PRIMARY KEY REFERENCES meetings(id) ON DELETE CASCADE,
+    asr_provider TEXT,
+    asr_model TEXT,
+    llm_provider TEXT,
+    llm_model TEXT,
+    updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
@@ -0,0 +1,2 @@
+-- Overall confidence (0.0 to 1.0) the ASR provider reported for the meeting's transcript
+ALTER TABLE meetings ADD COLUMN transcript_confidence REAL;
@@ -0,0 +1,2 @@
+-- Outcome of saving the meeting's recording ('recorded' or 'no_audio'); NULL for older meetings
+ALTER TABLE meetings ADD COLUMN recording_status TEXT;
@@ -0,0 +1,3 @@
+-- Language of each transcript segment as reported by the ASR provider (e.g. "en").
+-- NULL when the provider reports no per-segment language, and for older segments.
+ALTER TABLE transcripts ADD COLUMN language TEXT;
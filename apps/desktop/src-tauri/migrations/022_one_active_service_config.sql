@@ -0,0 +1,13 @@
+-- At most one service config per service type may be active.
+-- Where several are active already, keep the most recently updated one.
+UPDATE service_configs SET is_active = 0
+WHERE is_active = 1
+  AND id != (
+    SELECT s.id FROM service_configs s
+    WHERE s.service_type = service_configs.service_type AND s.is_active = 1
+    ORDER BY s.updated_at DESC, s.id DESC
+    LIMIT 1
+  );
+
+CREATE UNIQUE INDEX idx_service_configs_one_active
+ON service_configs(service_type) WHERE is_active = 1;
@@ -0,0 +1,7 @@
+-- Application-wide settings that don't belong to a service config (e.g. the HTTP proxy)
+-- Values are stored as text; structured settings are JSON
+CREATE TABLE IF NOT EXISTS app_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
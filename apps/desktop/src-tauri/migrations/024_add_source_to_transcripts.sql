@@ -0,0 +1,4 @@
+-- How each transcript segment was produced: 'batch' (file transcription) or 'streaming'
+-- (live captions). Re-transcribing replaces only batch rows, so live captions are kept.
+-- Older rows can't be told apart and are treated as batch.
+ALTER TABLE transcripts ADD COLUMN source TEXT NOT NULL DEFAULT 'batch';
@@ -0,0 +1,4 @@
+-- Format of the saved recording file
+ALTER TABLE meetings ADD COLUMN audio_sample_rate INTEGER;
+ALTER TABLE meetings ADD COLUMN audio_channels INTEGER;
+ALTER TABLE meetings ADD COLUMN audio_bits_per_sample INTEGER;
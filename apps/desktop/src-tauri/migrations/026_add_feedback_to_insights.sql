@@ -0,0 +1,28 @@
+-- Whether the user accepted (1) or rejected (0) a generated insight; NULL until rated
+ALTER TABLE insights ADD COLUMN feedback INTEGER;
+
+-- Fix the insights_fts sync triggers
+--
+-- Like transcripts_fts before migration 016, insights_fts is an external-content
+-- table whose delete and update triggers relied on FTS5 reading the old values from
+-- `insights`, where they are already gone. Any UPDATE of an insight, including
+-- rating it, could corrupt the index. Old values are now passed with the 'delete'
+-- command, and updates only touch the index when an indexed column changes.
+
+DROP TRIGGER insights_ad;
+DROP TRIGGER insights_au;
+
+CREATE TRIGGER insights_ad AFTER DELETE ON insights BEGIN
+    INSERT INTO insights_fts(insights_fts, rowid, content, type, meeting_id)
+    VALUES ('delete', old.id, old.content, old.type, old.meeting_id);
+END;
+
+CREATE TRIGGER insights_au AFTER UPDATE OF content, type, meeting_id ON insights BEGIN
+    INSERT INTO insights_fts(insights_fts, rowid, content, type, meeting_id)
+    VALUES ('delete', old.id, old.content, old.type, old.meeting_id);
+    INSERT INTO insights_fts(rowid, content, type, meeting_id)
+    VALUES (new.id, new.content, new.type, new.meeting_id);
+END;
+
+-- Drop whatever stale entries the old triggers left behind
+INSERT INTO insights_fts(insights_fts) VALUES ('rebuild');
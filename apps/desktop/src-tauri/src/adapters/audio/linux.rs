@@ -4,7 +4,10 @@
 //! Monitor sources allow non-intrusive capture of audio playing through the system.
 
 use crate::error::{AppError, Result};
-use crate::ports::audio::{AudioBuffer, AudioCapturePort, AudioFormat};
+use crate::ports::audio::{
+    AudioBuffer, AudioCapturePort, AudioDeviceInfo, AudioFormat, DeviceKind, DeviceSelection,
+    DEFAULT_DEVICE_ID,
+};
 use async_trait::async_trait;
 use libpulse_binding::context::{Context, FlagSet as ContextFlagSet};
 use libpulse_binding::mainloop::threaded::Mainloop;
@@ -16,16 +19,20 @@ use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+/// (name, description) of each PulseAudio device
+type PulseDevices = Vec<(String, String)>;
+
 /// Linux PulseAudio capture implementation
 ///
 /// Captures system audio output using PulseAudio monitor sources.
 /// Uses @DEFAULT_MONITOR@ to capture what's playing through the speakers.
 ///
-/// Audio format: 44100 Hz, 2 channels (stereo), 16-bit signed little-endian
+/// Audio format: 44100 Hz, 2 channels (stereo), 16-bit signed little-endian, unless
+/// `start_capture` is given a preferred format
 pub struct PulseAudioCapture {
     is_capturing: Arc<Mutex<bool>>,
     audio_buffer: Arc<Mutex<Vec<f32>>>,
-    /// Audio format - placeholder until capture starts, then set to 44.1kHz stereo 16-bit
+    /// Audio format - placeholder until capture starts, then set to the capture spec
     format: AudioFormat,
     capture_handle: Option<tokio::task::JoinHandle<()>>,
     /// Current audio level for visual feedback (0.0 to 1.0)
@@ -36,7 +43,7 @@ impl PulseAudioCapture {
     /// Creates a new PulseAudio capture instance
     ///
     /// The format field is initialized to a default placeholder.
-    /// Actual format (44.1kHz stereo 16-bit) is set when `start_capture()` is called.
+    /// Actual format (44.1kHz stereo 16-bit by default) is set when `start_capture()` is called.
     pub fn new() -> Self {
         Self {
             is_capturing: Arc::new(Mutex::new(false)),
@@ -71,6 +78,179 @@ impl PulseAudioCapture {
         // In practice, the device selection should pass the actual device name, not just the index
         Ok(format!("device_{}", device_index))
     }
+
+    /// PulseAudio source that records what the selected speaker plays
+    fn speaker_source(selection: &DeviceSelection) -> Result<String> {
+        match selection {
+            DeviceSelection::Default => Self::get_device_name_by_index(0),
+            DeviceSelection::Index(index) => Self::get_device_name_by_index(*index),
+            // Every sink has a monitor source named after it
+            DeviceSelection::Id(sink) => Ok(format!("{}.monitor", sink)),
+        }
+    }
+
+    /// PulseAudio source for the selected microphone
+    ///
+    /// Index selections can't be resolved to a source name, so they use the default input.
+    fn microphone_source(selection: &DeviceSelection) -> String {
+        match selection {
+            DeviceSelection::Id(source) => source.clone(),
+            DeviceSelection::Default | DeviceSelection::Index(_) => "@DEFAULT_SOURCE@".to_string(),
+        }
+    }
+
+    /// Sample spec for a capture stream
+    ///
+    /// PulseAudio converts to whatever spec the stream asks for, so a preferred 16-bit
+    /// format is used as is. Other bit depths and invalid formats get the default
+    /// 44.1 kHz stereo spec.
+    fn capture_spec(preferred_format: Option<&AudioFormat>) -> Spec {
+        let default_spec = Spec {
+            format: Format::S16le, // 16-bit signed little-endian
+            channels: 2,           // Stereo
+            rate: 44100,           // 44.1 kHz
+        };
+        let Some(preferred) = preferred_format else {
+            return default_spec;
+        };
+
+        let spec = Spec {
+            format: Format::S16le,
+            channels: u8::try_from(preferred.channels).unwrap_or(0),
+            rate: preferred.sample_rate,
+        };
+        let valid = (1..=Spec::RATE_MAX).contains(&spec.rate)
+            && (1..=Spec::CHANNELS_MAX).contains(&spec.channels);
+        if preferred.bits_per_sample == 16 && valid {
+            spec
+        } else {
+            log::warn!(
+                "Can't capture in {}, using {} Hz stereo 16-bit",
+                preferred,
+                default_spec.rate
+            );
+            default_spec
+        }
+    }
+
+    /// Names and descriptions of the sinks and of the non-monitor sources
+    fn enumerate_sinks_and_sources() -> Result<(PulseDevices, PulseDevices)> {
+        let mut mainloop = Mainloop::new().ok_or_else(|| {
+            AppError::AudioCapture("Failed to create PulseAudio mainloop".to_string())
+        })?;
+
+        let mut context =
+            Context::new(&mainloop, "Meet-Scribe Device Enumeration").ok_or_else(|| {
+                AppError::AudioCapture("Failed to create PulseAudio context".to_string())
+            })?;
+
+        context
+            .connect(None, ContextFlagSet::NOFLAGS, None)
+            .map_err(|e| {
+                AppError::AudioCapture(format!("Failed to connect to PulseAudio: {}", e))
+            })?;
+
+        mainloop.lock();
+        mainloop
+            .start()
+            .map_err(|e| AppError::AudioCapture(format!("Failed to start mainloop: {}", e)))?;
+
+        // Wait for context to be ready
+        loop {
+            match context.get_state() {
+                libpulse_binding::context::State::Ready => break,
+                libpulse_binding::context::State::Failed
+                | libpulse_binding::context::State::Terminated => {
+                    mainloop.unlock();
+                    mainloop.stop();
+                    return Err(AppError::AudioCapture(
+                        "PulseAudio context failed".to_string(),
+                    ));
+                }
+                _ => {
+                    mainloop.unlock();
+                    std::thread::sleep(Duration::from_millis(10));
+                    mainloop.lock();
+                }
+            }
+        }
+
+        let sinks: Rc<RefCell<PulseDevices>> = Rc::new(RefCell::new(Vec::new()));
+        let sources: Rc<RefCell<PulseDevices>> = Rc::new(RefCell::new(Vec::new()));
+        let done = Rc::new(RefCell::new(false));
+
+        let sinks_clone = Rc::clone(&sinks);
+        let done_sinks = Rc::clone(&done);
+        context
+            .introspect()
+            .get_sink_info_list(move |result| match result {
+                libpulse_binding::callbacks::ListResult::Item(sink_info) => {
+                    if let Some(name) = sink_info.name.as_ref().map(|n| n.to_string()) {
+                        let description = sink_info
+                            .description
+                            .as_ref()
+                            .map(|d| d.to_string())
+                            .unwrap_or_else(|| name.clone());
+                        sinks_clone.borrow_mut().push((name, description));
+                    }
+                }
+                libpulse_binding::callbacks::ListResult::End => {
+                    *done_sinks.borrow_mut() = true;
+                }
+                libpulse_binding::callbacks::ListResult::Error => {
+                    log::error!("Error enumerating sinks");
+                    *done_sinks.borrow_mut() = true;
+                }
+            });
+
+        mainloop.unlock();
+        while !*done.borrow() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        mainloop.lock();
+        *done.borrow_mut() = false;
+
+        let sources_clone = Rc::clone(&sources);
+        let done_sources = Rc::clone(&done);
+        context
+            .introspect()
+            .get_source_info_list(move |result| match result {
+                libpulse_binding::callbacks::ListResult::Item(source_info) => {
+                    // Skip monitor sources (they're for capturing output, not real microphones)
+                    if source_info.monitor_of_sink.is_none() {
+                        if let Some(name) = source_info.name.as_ref().map(|n| n.to_string()) {
+                            let description = source_info
+                                .description
+                                .as_ref()
+                                .map(|d| d.to_string())
+                                .unwrap_or_else(|| name.clone());
+                            sources_clone.borrow_mut().push((name, description));
+                        }
+                    }
+                }
+                libpulse_binding::callbacks::ListResult::End => {
+                    *done_sources.borrow_mut() = true;
+                }
+                libpulse_binding::callbacks::ListResult::Error => {
+                    log::error!("Error enumerating sources");
+                    *done_sources.borrow_mut() = true;
+                }
+            });
+
+        mainloop.unlock();
+        while !*done.borrow() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        mainloop.lock();
+
+        mainloop.unlock();
+        mainloop.stop();
+        context.disconnect();
+
+        let sinks = sinks.borrow().clone();
+        let sources = sources.borrow().clone();
+        Ok((sinks, sources))
+    }
 }
 
 impl Default for PulseAudioCapture {
@@ -443,7 +623,49 @@ impl AudioCapturePort for PulseAudioCapture {
         .map_err(|e| AppError::AudioCapture(format!("Task join error: {}", e)))?
     }
 
-    async fn start_capture(&mut self, device_name: Option<String>) -> Result<()> {
+    async fn list_devices_detailed(&self) -> Result<Vec<AudioDeviceInfo>> {
+        tokio::task::spawn_blocking(|| {
+            let (sinks, sources) = Self::enumerate_sinks_and_sources()?;
+
+            // Indices match list_speaker_devices and list_microphone_devices
+            let mut devices = vec![AudioDeviceInfo {
+                index: 0,
+                id: DEFAULT_DEVICE_ID.to_string(),
+                name: "Default Monitor".to_string(),
+                kind: DeviceKind::Speaker,
+            }];
+            devices.extend(
+                sinks
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (id, name))| AudioDeviceInfo {
+                        index: i + 1,
+                        id,
+                        name,
+                        kind: DeviceKind::Speaker,
+                    }),
+            );
+            devices.extend(sources.into_iter().enumerate().map(|(i, (id, name))| {
+                AudioDeviceInfo {
+                    index: i,
+                    id,
+                    name,
+                    kind: DeviceKind::Microphone,
+                }
+            }));
+
+            log::info!("Found {} audio devices on Linux", devices.len());
+            Ok(devices)
+        })
+        .await
+        .map_err(|e| AppError::AudioCapture(format!("Task join error: {}", e)))?
+    }
+
+    async fn start_capture(
+        &mut self,
+        device_name: Option<String>,
+        preferred_format: Option<AudioFormat>,
+    ) -> Result<()> {
         {
             let mut is_capturing = self.is_capturing.lock().unwrap();
             if *is_capturing {
@@ -458,35 +680,27 @@ impl AudioCapturePort for PulseAudioCapture {
         let is_capturing_clone = Arc::clone(&self.is_capturing);
         let audio_buffer_clone = Arc::clone(&self.audio_buffer);
 
-        // Parse device index from device name
-        // Device name format: "0: Device Name (Type)" or "1: Device Name (Type)"
-        let device_index = device_name
-            .as_ref()
-            .and_then(|name| {
-                // Extract index from "N: Device Name" format
-                name.split(':').next()?.trim().parse::<usize>().ok()
-            })
-            .unwrap_or(0); // Default to index 0 if parsing fails
+        // Device name format: "N: Device Name (Type)" or a sink name from list_devices_detailed
+        let selection = device_name
+            .as_deref()
+            .map(DeviceSelection::parse)
+            .unwrap_or(DeviceSelection::Default);
 
-        log::info!("Using audio device index: {}", device_index);
+        log::info!("Using audio device: {:?}", selection);
 
         // Determine which device to use for capture
         // Default to system monitor source if not specified
-        let device = Self::get_device_name_by_index(device_index)?;
+        let device = Self::speaker_source(&selection)?;
 
         // Store format info to be updated after detection
         let format_info = Arc::new(Mutex::new(AudioFormat::default()));
         let format_info_clone = Arc::clone(&format_info);
 
+        // Set up PulseAudio sample specification
+        let spec = Self::capture_spec(preferred_format.as_ref());
+
         // Spawn background task for audio capture
         let handle = tokio::task::spawn_blocking(move || {
-            // Set up PulseAudio sample specification
-            let spec = Spec {
-                format: Format::S16le, // 16-bit signed little-endian
-                channels: 2,           // Stereo
-                rate: 44100,           // 44.1 kHz
-            };
-
             // Store the format
             *format_info_clone.lock().unwrap() = AudioFormat {
                 sample_rate: spec.rate,
@@ -566,7 +780,7 @@ impl AudioCapturePort for PulseAudioCapture {
         self.capture_handle = Some(handle);
 
         // Wait for format initialization to complete
-        // Format is set to 44100 Hz, stereo, 16-bit in the background thread
+        // Format is set from the sample spec in the background thread
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
         // Update our format from the initialized format
@@ -585,7 +799,16 @@ impl AudioCapturePort for PulseAudioCapture {
         &mut self,
         speaker_device: Option<String>,
         microphone_device: Option<String>,
+        process_name: Option<String>,
+        preferred_format: Option<AudioFormat>,
     ) -> Result<()> {
+        if let Some(name) = &process_name {
+            log::warn!(
+                "Capturing {} on its own isn't supported on Linux, capturing the speaker",
+                name
+            );
+        }
+
         {
             let mut is_capturing = self.is_capturing.lock().unwrap();
             if *is_capturing {
@@ -600,18 +823,23 @@ impl AudioCapturePort for PulseAudioCapture {
         let audio_buffer_clone = Arc::clone(&self.audio_buffer);
         let current_level_clone = Arc::clone(&self.current_level);
 
-        // Speaker: index 0 → @DEFAULT_MONITOR@ (loopback of default speaker output)
-        let speaker_index = speaker_device
-            .as_ref()
-            .and_then(|name| name.split(':').next()?.trim().parse::<usize>().ok())
-            .unwrap_or(0);
-        let speaker_pa_device = Self::get_device_name_by_index(speaker_index)?;
+        // Speaker: default or index 0 → @DEFAULT_MONITOR@ (loopback of default speaker output)
+        let speaker_selection = speaker_device
+            .as_deref()
+            .map(DeviceSelection::parse)
+            .unwrap_or(DeviceSelection::Default);
+        let speaker_pa_device = Self::speaker_source(&speaker_selection)?;
 
-        // Mic: use @DEFAULT_SOURCE@ (PulseAudio default input / microphone).
-        // Both streams request stereo 44100 S16le — PulseAudio converts internally,
+        // Mic: a source name selects that source, otherwise @DEFAULT_SOURCE@.
+        // Both streams request the same spec — PulseAudio converts internally,
         // so both buffers always have the same channel layout and can be mixed directly.
         // TODO: resolve the actual PulseAudio source name when a specific mic index is provided.
-        let mic_pa_device = "@DEFAULT_SOURCE@".to_string();
+        let mic_pa_device = Self::microphone_source(
+            &microphone_device
+                .as_deref()
+                .map(DeviceSelection::parse)
+                .unwrap_or(DeviceSelection::Default),
+        );
 
         log::info!(
             "Linux dual capture: speaker={}, mic={}",
@@ -619,11 +847,12 @@ impl AudioCapturePort for PulseAudioCapture {
             mic_pa_device
         );
 
+        let spec = Self::capture_spec(preferred_format.as_ref());
         let format_info = Arc::new(Mutex::new(AudioFormat::default()));
         let format_info_clone = Arc::clone(&format_info);
         *format_info_clone.lock().unwrap() = AudioFormat {
-            sample_rate: 44100,
-            channels: 2,
+            sample_rate: spec.rate,
+            channels: u16::from(spec.channels),
             bits_per_sample: 16,
         };
 
@@ -638,11 +867,6 @@ impl AudioCapturePort for PulseAudioCapture {
 
             // Speaker capture thread — creates its own Simple connection inside
             let spk_thread = std::thread::spawn(move || {
-                let spec = Spec {
-                    format: Format::S16le,
-                    channels: 2,
-                    rate: 44100,
-                };
                 let simple = match Simple::new(
                     None,
                     "Meet-Scribe",
@@ -689,14 +913,9 @@ impl AudioCapturePort for PulseAudioCapture {
             });
 
             // Mic capture thread — creates its own Simple connection inside.
-            // Requests stereo so PulseAudio upmixes mono hardware internally;
-            // both buffers are always stereo, eliminating any channel mismatch in the mixer.
+            // Requests the speaker's spec so PulseAudio converts the mic internally;
+            // both buffers share one layout, eliminating any channel mismatch in the mixer.
             let mic_thread = std::thread::spawn(move || {
-                let spec = Spec {
-                    format: Format::S16le,
-                    channels: 2,
-                    rate: 44100,
-                };
                 let simple = match Simple::new(
                     None,
                     "Meet-Scribe",
@@ -743,7 +962,7 @@ impl AudioCapturePort for PulseAudioCapture {
             });
 
             // Mixer loop: drain both buffers every 50 ms and mix.
-            // Both streams are stereo so samples interleave identically — no channel mismatch.
+            // Both streams share one spec so samples interleave identically — no channel mismatch.
             while *is_capturing_clone.lock().unwrap() {
                 std::thread::sleep(Duration::from_millis(50));
 
@@ -847,6 +1066,7 @@ impl AudioCapturePort for PulseAudioCapture {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ports::transcription::STREAMING_AUDIO_FORMAT;
 
     #[test]
     fn test_new_pulseaudio_capture() {
@@ -859,7 +1079,7 @@ mod tests {
         let capture = PulseAudioCapture::new();
         let format = capture.get_format();
         // Before capture starts, format is the default placeholder
-        // Actual format is set during start_capture() to: 44100 Hz, stereo, 16-bit
+        // Actual format is set during start_capture() to 44100 Hz, stereo, 16-bit by default
         assert_eq!(format.sample_rate, 16000); // Placeholder before capture
         assert_eq!(format.channels, 1); // Placeholder before capture
         assert_eq!(format.bits_per_sample, 16); // Placeholder before capture
@@ -893,4 +1113,61 @@ mod tests {
         assert!((converted[1] - 0.5).abs() < 0.001);
         assert!((converted[2] + 0.5).abs() < 0.001);
     }
+
+    #[test]
+    fn test_pulse_sources_for_selection() {
+        assert_eq!(
+            PulseAudioCapture::speaker_source(&DeviceSelection::Default).unwrap(),
+            "@DEFAULT_MONITOR@"
+        );
+        assert_eq!(
+            PulseAudioCapture::speaker_source(&DeviceSelection::parse(
+                "alsa_output.usb-Logitech_Webcam-02.analog-stereo"
+            ))
+            .unwrap(),
+            "alsa_output.usb-Logitech_Webcam-02.analog-stereo.monitor"
+        );
+
+        assert_eq!(
+            PulseAudioCapture::microphone_source(&DeviceSelection::parse(
+                "alsa_input.pci-0000_00_1f.3.analog-stereo"
+            )),
+            "alsa_input.pci-0000_00_1f.3.analog-stereo"
+        );
+        assert_eq!(
+            PulseAudioCapture::microphone_source(&DeviceSelection::parse("1: Webcam Mic")),
+            "@DEFAULT_SOURCE@"
+        );
+    }
+
+    #[test]
+    fn test_capture_spec_uses_preferred_format() {
+        // Compared field by field; Spec's PartialEq calls into libpulse
+        let fields = |spec: Spec| (spec.format, spec.channels, spec.rate);
+
+        let spec = PulseAudioCapture::capture_spec(Some(&STREAMING_AUDIO_FORMAT));
+        assert_eq!(fields(spec), (Format::S16le, 1, 16000));
+
+        let default_spec = fields(PulseAudioCapture::capture_spec(None));
+        assert_eq!(default_spec, (Format::S16le, 2, 44100));
+
+        // Formats PulseAudio can't deliver as 16-bit samples fall back to the default
+        for unsupported in [
+            AudioFormat {
+                sample_rate: 16000,
+                channels: 1,
+                bits_per_sample: 32,
+            },
+            AudioFormat {
+                sample_rate: 0,
+                channels: 1,
+                bits_per_sample: 16,
+            },
+        ] {
+            assert_eq!(
+                fields(PulseAudioCapture::capture_spec(Some(&unsupported))),
+                default_spec
+            );
+        }
+    }
 }
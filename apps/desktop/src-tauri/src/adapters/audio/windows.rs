@@ -4,18 +4,34 @@
 //! This allows capturing audio playing through the system without being intrusive.
 
 use crate::error::{AppError, Result};
-use crate::ports::audio::{AudioBuffer, AudioCapturePort, AudioFormat};
+use crate::ports::audio::{
+    check_bit_depth, AudioBuffer, AudioCapturePort, AudioDeviceInfo, AudioFormat,
+    CaptureStreamKind, CaptureStreamParams, CaptureTiming, DeviceKind, DeviceSelection,
+    DEFAULT_DEVICE_ID,
+};
 use async_trait::async_trait;
+use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use windows::core::Interface;
+use windows::core::{implement, Interface};
+use windows::Win32::Foundation::CloseHandle;
 use windows::Win32::Media::Audio::{
-    eCapture, eConsole, eRender, IAudioCaptureClient, IAudioClient, IMMDevice, IMMDeviceEnumerator,
-    IMMEndpoint, MMDeviceEnumerator, AUDCLNT_BUFFERFLAGS_SILENT, AUDCLNT_SHAREMODE_SHARED,
-    AUDCLNT_STREAMFLAGS_LOOPBACK, WAVEFORMATEX, WAVEFORMATEXTENSIBLE,
+    eCapture, eConsole, eRender, ActivateAudioInterfaceAsync, EDataFlow,
+    IActivateAudioInterfaceAsyncOperation, IActivateAudioInterfaceCompletionHandler,
+    IActivateAudioInterfaceCompletionHandler_Impl, IAudioCaptureClient, IAudioClient, IMMDevice,
+    IMMDeviceEnumerator, IMMEndpoint, MMDeviceEnumerator, AUDCLNT_BUFFERFLAGS_SILENT,
+    AUDCLNT_SHAREMODE_SHARED, AUDCLNT_STREAMFLAGS_AUTOCONVERTPCM, AUDCLNT_STREAMFLAGS_LOOPBACK,
+    AUDCLNT_STREAMFLAGS_SRC_DEFAULT_QUALITY, AUDIOCLIENT_ACTIVATION_PARAMS,
+    AUDIOCLIENT_ACTIVATION_PARAMS_0, AUDIOCLIENT_ACTIVATION_TYPE_PROCESS_LOOPBACK,
+    AUDIOCLIENT_PROCESS_LOOPBACK_PARAMS, PROCESS_LOOPBACK_MODE_INCLUDE_TARGET_PROCESS_TREE,
+    VIRTUAL_AUDIO_DEVICE_PROCESS_LOOPBACK, WAVEFORMATEX, WAVEFORMATEXTENSIBLE, WAVE_FORMAT_PCM,
 };
 use windows::Win32::System::Com::{
-    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_MULTITHREADED,
+    CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_ALL,
+    COINIT_MULTITHREADED,
+};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
 };
 
 // Only import Property Store related items when not in test mode
@@ -24,6 +40,235 @@ use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
 #[cfg(not(test))]
 use windows::Win32::System::Com::STGM_READ;
 
+/// Keeps COM initialized on the current thread until dropped
+///
+/// COM initialization is per thread, so the guard is neither `Send` nor `Sync`.
+/// Create it before any COM interface in the same scope: locals drop in reverse
+/// order, so the interfaces are released before `CoUninitialize` runs.
+struct ComGuard {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ComGuard {
+    fn new() -> Result<Self> {
+        unsafe {
+            CoInitializeEx(None, COINIT_MULTITHREADED)
+                .ok()
+                .map_err(|e| AppError::AudioCapture(format!("Failed to initialize COM: {}", e)))?;
+        }
+        Ok(Self {
+            _not_send: PhantomData,
+        })
+    }
+}
+
+impl Drop for ComGuard {
+    fn drop(&mut self) {
+        unsafe { CoUninitialize() };
+    }
+}
+
+/// How long to wait for Windows to activate a process loopback client
+const PROCESS_LOOPBACK_ACTIVATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Format requested from a process loopback client when no format is preferred
+///
+/// Process loopback clients have no mix format of their own, so one has to be asked for.
+const PROCESS_LOOPBACK_FORMAT: AudioFormat = AudioFormat {
+    sample_rate: 48000,
+    channels: 2,
+    bits_per_sample: 16,
+};
+
+/// `VT_BLOB` variant type
+const VT_BLOB: u16 = 65;
+
+/// A running process, as listed by a Toolhelp snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProcessEntry {
+    id: u32,
+    parent_id: u32,
+    exe_name: String,
+}
+
+/// Whether `exe_name` is the executable the user means by `name`
+///
+/// Case-insensitive, and the ".exe" suffix is optional: "zoom" matches "Zoom.exe".
+fn matches_process_name(exe_name: &str, name: &str) -> bool {
+    let strip = |s: &str| {
+        let lower = s.trim().to_lowercase();
+        lower
+            .strip_suffix(".exe")
+            .map(str::to_string)
+            .unwrap_or(lower)
+    };
+    strip(exe_name) == strip(name)
+}
+
+/// Pick the process whose tree should be captured for `name`
+///
+/// Apps like Teams run many processes with the same name and play audio from a
+/// child, so the first one whose parent isn't also a match is used: capturing
+/// its tree includes every other match.
+fn find_root_process(processes: &[ProcessEntry], name: &str) -> Option<u32> {
+    let matching: Vec<&ProcessEntry> = processes
+        .iter()
+        .filter(|p| matches_process_name(&p.exe_name, name))
+        .collect();
+    matching
+        .iter()
+        .find(|p| !matching.iter().any(|parent| parent.id == p.parent_id))
+        .or(matching.first())
+        .map(|p| p.id)
+}
+
+/// List running processes
+fn list_processes() -> Result<Vec<ProcessEntry>> {
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0).map_err(|e| {
+            AppError::AudioCapture(format!("Failed to list running processes: {}", e))
+        })?;
+
+        let mut processes = Vec::new();
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut next = Process32FirstW(snapshot, &mut entry);
+        while next.is_ok() {
+            let len = entry
+                .szExeFile
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(entry.szExeFile.len());
+            processes.push(ProcessEntry {
+                id: entry.th32ProcessID,
+                parent_id: entry.th32ParentProcessID,
+                exe_name: String::from_utf16_lossy(&entry.szExeFile[..len]),
+            });
+            next = Process32NextW(snapshot, &mut entry);
+        }
+
+        let _ = CloseHandle(snapshot);
+        Ok(processes)
+    }
+}
+
+/// WASAPI stream flags for opening a capture stream
+fn stream_flags(params: CaptureStreamParams) -> u32 {
+    let mut flags = 0;
+    if params.loopback {
+        flags |= AUDCLNT_STREAMFLAGS_LOOPBACK;
+    }
+    if params.auto_convert {
+        flags |= AUDCLNT_STREAMFLAGS_AUTOCONVERTPCM | AUDCLNT_STREAMFLAGS_SRC_DEFAULT_QUALITY;
+    }
+    flags
+}
+
+/// Activation parameters asking for the audio of `process_id` and its children
+fn process_loopback_params(process_id: u32) -> AUDIOCLIENT_ACTIVATION_PARAMS {
+    AUDIOCLIENT_ACTIVATION_PARAMS {
+        ActivationType: AUDIOCLIENT_ACTIVATION_TYPE_PROCESS_LOOPBACK,
+        Anonymous: AUDIOCLIENT_ACTIVATION_PARAMS_0 {
+            ProcessLoopbackParams: AUDIOCLIENT_PROCESS_LOOPBACK_PARAMS {
+                TargetProcessId: process_id,
+                ProcessLoopbackMode: PROCESS_LOOPBACK_MODE_INCLUDE_TARGET_PROCESS_TREE,
+            },
+        },
+    }
+}
+
+/// A `VT_BLOB` PROPVARIANT borrowing its data
+///
+/// Laid out like PROPVARIANT, as in `get_device_friendly_name`. The windows crate's
+/// PROPVARIANT would free the blob with `PropVariantClear` when dropped, which must
+/// not happen to borrowed data.
+#[repr(C)]
+struct BlobPropVariant<'a> {
+    vt: u16,
+    _reserved: [u16; 3],
+    cb_size: u32,
+    blob_data: *const u8,
+    _data: PhantomData<&'a ()>,
+}
+
+impl<'a> BlobPropVariant<'a> {
+    fn new<T>(data: &'a T) -> Self {
+        Self {
+            vt: VT_BLOB,
+            _reserved: [0; 3],
+            cb_size: std::mem::size_of::<T>() as u32,
+            blob_data: data as *const T as *const u8,
+            _data: PhantomData,
+        }
+    }
+
+    fn as_propvariant(&self) -> *const windows::core::PROPVARIANT {
+        self as *const Self as *const windows::core::PROPVARIANT
+    }
+}
+
+/// Signals when `ActivateAudioInterfaceAsync` has finished
+#[implement(IActivateAudioInterfaceCompletionHandler)]
+struct ActivationHandler {
+    done: std::sync::mpsc::SyncSender<()>,
+}
+
+impl IActivateAudioInterfaceCompletionHandler_Impl for ActivationHandler_Impl {
+    fn ActivateCompleted(
+        &self,
+        _operation: Option<&IActivateAudioInterfaceAsyncOperation>,
+    ) -> windows::core::Result<()> {
+        let _ = self.done.send(());
+        Ok(())
+    }
+}
+
+/// Activate an audio client that captures only `process_id` and its child processes
+///
+/// Needs Windows 10 build 20348 or later. COM must be initialized.
+fn activate_process_loopback(process_id: u32) -> Result<IAudioClient> {
+    let params = process_loopback_params(process_id);
+    let variant = BlobPropVariant::new(&params);
+    let (done_tx, done_rx) = std::sync::mpsc::sync_channel(1);
+    let handler: IActivateAudioInterfaceCompletionHandler =
+        ActivationHandler { done: done_tx }.into();
+
+    unsafe {
+        let operation = ActivateAudioInterfaceAsync(
+            VIRTUAL_AUDIO_DEVICE_PROCESS_LOOPBACK,
+            &IAudioClient::IID,
+            Some(variant.as_propvariant()),
+            &handler,
+        )
+        .map_err(|e| {
+            AppError::AudioCapture(format!("Failed to request process loopback: {}", e))
+        })?;
+
+        done_rx
+            .recv_timeout(PROCESS_LOOPBACK_ACTIVATION_TIMEOUT)
+            .map_err(|_| {
+                AppError::AudioCapture("Process loopback activation timed out".to_string())
+            })?;
+
+        let mut activate_result = windows::core::HRESULT(0);
+        let mut activated: Option<windows::core::IUnknown> = None;
+        operation
+            .GetActivateResult(&mut activate_result, &mut activated)
+            .and_then(|()| activate_result.ok())
+            .map_err(|e| {
+                AppError::AudioCapture(format!("Failed to activate process loopback: {}", e))
+            })?;
+        activated
+            .ok_or_else(|| {
+                AppError::AudioCapture("Process loopback returned no audio client".to_string())
+            })?
+            .cast::<IAudioClient>()
+            .map_err(|e| AppError::AudioCapture(format!("Process loopback client: {}", e)))
+    }
+}
+
 /// Windows WASAPI audio capture implementation
 ///
 /// Captures system audio output using WASAPI loopback mode.
@@ -41,6 +286,8 @@ pub struct WasapiAudioCapture {
     mic_capture_handle: Option<tokio::task::JoinHandle<()>>,
     /// Current audio level (0.0 to 1.0) for visual feedback
     current_level: Arc<Mutex<f32>>,
+    /// Device buffer length and capture loop polling interval
+    capture_timing: CaptureTiming,
 }
 
 impl WasapiAudioCapture {
@@ -56,17 +303,74 @@ impl WasapiAudioCapture {
             capture_handle: None,
             mic_capture_handle: None,
             current_level: Arc::new(Mutex::new(0.0)),
+            capture_timing: CaptureTiming::default(),
         }
     }
 
-    /// Initialize COM for the current thread
-    fn init_com() -> Result<()> {
+    /// Run `f` with COM initialized on the current thread
+    ///
+    /// Every COM interface created inside `f` is released when `f` returns, before
+    /// COM is uninitialized.
+    fn with_com<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let _com = ComGuard::new()?;
+        f()
+    }
+
+    /// Create a device enumerator (COM must be initialized)
+    fn create_enumerator() -> Result<IMMDeviceEnumerator> {
         unsafe {
-            CoInitializeEx(None, COINIT_MULTITHREADED)
-                .ok()
-                .map_err(|e| AppError::AudioCapture(format!("Failed to initialize COM: {}", e)))?;
+            CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL).map_err(|e| {
+                AppError::AudioCapture(format!("Failed to create device enumerator: {}", e))
+            })
+        }
+    }
+
+    /// Count and friendly names of the active endpoints for one direction
+    ///
+    /// Names are keyed by collection index. `kind` ("speaker" or "microphone") is only
+    /// used in messages. Endpoints that can't be opened are skipped with a warning, so
+    /// indices may have gaps.
+    fn active_endpoint_names(
+        enumerator: &IMMDeviceEnumerator,
+        data_flow: EDataFlow,
+        kind: &str,
+    ) -> Result<(u32, Vec<(u32, String)>)> {
+        use windows::Win32::Media::Audio::DEVICE_STATE_ACTIVE;
+
+        unsafe {
+            let collection = enumerator
+                .EnumAudioEndpoints(data_flow, DEVICE_STATE_ACTIVE)
+                .map_err(|e| {
+                    AppError::AudioCapture(format!("Failed to enumerate {} endpoints: {}", kind, e))
+                })?;
+
+            let count = collection.GetCount().map_err(|e| {
+                AppError::AudioCapture(format!("Failed to get {} device count: {}", kind, e))
+            })?;
+
+            let mut names = Vec::new();
+            for i in 0..count {
+                match collection.Item(i) {
+                    Ok(device) => names.push((i, Self::get_device_friendly_name(&device, i))),
+                    Err(e) => log::warn!("Failed to get {} device {}: {}", kind, i, e),
+                }
+            }
+            Ok((count, names))
+        }
+    }
+
+    /// Entry for the default speaker, listed first with index 0
+    fn default_speaker_entry(fallback: &str) -> String {
+        match Self::get_default_device() {
+            Ok(device) => {
+                let name = Self::get_device_friendly_name(&device, 0);
+                format!("0: {} (Default Speaker)", name)
+            }
+            Err(e) => {
+                log::warn!("Failed to get default device: {}", e);
+                format!("0: {}", fallback)
+            }
         }
-        Ok(())
     }
 
     /// Get the default audio render device (loopback capture)
@@ -178,6 +482,109 @@ impl WasapiAudioCapture {
         }
     }
 
+    /// Get an audio device by its endpoint ID (see [`Self::endpoint_id`])
+    ///
+    /// Fails if the device is unplugged or disabled.
+    fn get_device_by_id(id: &str) -> Result<IMMDevice> {
+        use windows::Win32::Media::Audio::DEVICE_STATE_ACTIVE;
+
+        unsafe {
+            let enumerator = Self::create_enumerator()?;
+            let device = enumerator
+                .GetDevice(&windows::core::HSTRING::from(id))
+                .map_err(|e| {
+                    AppError::AudioCapture(format!("Audio device {} not found: {}", id, e))
+                })?;
+
+            let state = device.GetState().map_err(|e| {
+                AppError::AudioCapture(format!("Failed to get state of audio device {}: {}", id, e))
+            })?;
+            if state != DEVICE_STATE_ACTIVE {
+                return Err(AppError::AudioCapture(format!(
+                    "Audio device {} is not connected",
+                    id
+                )));
+            }
+            Ok(device)
+        }
+    }
+
+    /// Resolve a speaker selection; index 0 and "default" are the default device
+    fn speaker_device(selection: &DeviceSelection) -> Result<IMMDevice> {
+        match selection {
+            DeviceSelection::Default => Self::get_default_device(),
+            DeviceSelection::Index(index) => Self::get_device_by_index(*index),
+            DeviceSelection::Id(id) => Self::get_device_by_id(id),
+        }
+    }
+
+    /// Resolve a microphone selection; "default" is the default input device
+    fn microphone_device(selection: &DeviceSelection) -> Result<IMMDevice> {
+        match selection {
+            DeviceSelection::Default => unsafe {
+                Self::create_enumerator()?
+                    .GetDefaultAudioEndpoint(eCapture, eConsole)
+                    .map_err(|e| {
+                        AppError::AudioCapture(format!("Failed to get default microphone: {}", e))
+                    })
+            },
+            DeviceSelection::Index(index) => Self::get_microphone_by_index(*index),
+            DeviceSelection::Id(id) => Self::get_device_by_id(id),
+        }
+    }
+
+    /// Stable endpoint ID of a device, e.g. "{0.0.0.00000000}.{GUID}"
+    ///
+    /// Unlike collection indices, the ID doesn't change when devices are plugged in or out.
+    fn endpoint_id(device: &IMMDevice) -> Option<String> {
+        unsafe {
+            let id = device.GetId().ok()?;
+            let result = id.to_string().ok();
+            CoTaskMemFree(Some(id.0 as *const _));
+            result
+        }
+    }
+
+    /// ID, index and friendly name of the active endpoints for one direction
+    ///
+    /// Indices are collection indices, as in [`Self::active_endpoint_names`]. Endpoints
+    /// whose ID can't be read are skipped with a warning.
+    fn active_endpoints(
+        enumerator: &IMMDeviceEnumerator,
+        data_flow: EDataFlow,
+        kind: &str,
+    ) -> Result<Vec<(u32, String, String)>> {
+        use windows::Win32::Media::Audio::DEVICE_STATE_ACTIVE;
+
+        unsafe {
+            let collection = enumerator
+                .EnumAudioEndpoints(data_flow, DEVICE_STATE_ACTIVE)
+                .map_err(|e| {
+                    AppError::AudioCapture(format!("Failed to enumerate {} endpoints: {}", kind, e))
+                })?;
+
+            let count = collection.GetCount().map_err(|e| {
+                AppError::AudioCapture(format!("Failed to get {} device count: {}", kind, e))
+            })?;
+
+            let mut endpoints = Vec::new();
+            for i in 0..count {
+                let device = match collection.Item(i) {
+                    Ok(device) => device,
+                    Err(e) => {
+                        log::warn!("Failed to get {} device {}: {}", kind, i, e);
+                        continue;
+                    }
+                };
+                match Self::endpoint_id(&device) {
+                    Some(id) => endpoints.push((i, id, Self::get_device_friendly_name(&device, i))),
+                    None => log::warn!("Failed to get ID of {} device {}", kind, i),
+                }
+            }
+            Ok(endpoints)
+        }
+    }
+
     /// Get friendly name for an audio device
     ///
     /// Retrieves the user-friendly device name using Windows Property Store
@@ -307,6 +714,7 @@ impl WasapiAudioCapture {
     /// Returns: (WAVEFORMATEX, sample_rate, bits_per_sample, is_float)
     fn initialize_audio_client(
         audio_client: &IAudioClient,
+        timing: CaptureTiming,
     ) -> Result<(WAVEFORMATEX, u32, u16, bool)> {
         unsafe {
             // Get the device's mix format (auto-detected from system)
@@ -323,15 +731,33 @@ impl WasapiAudioCapture {
             let mix_format = *mix_format_ptr;
             let sample_rate = mix_format.nSamplesPerSec; // Actual system sample rate
             let bits_per_sample = mix_format.wBitsPerSample; // Actual bit depth
+            let n_channels = mix_format.nChannels;
             let is_float = Self::detect_is_float_format(mix_format_ptr);
+            log::info!(
+                "Loopback format: {} Hz, {} ch, {} bit, {}",
+                sample_rate,
+                n_channels,
+                bits_per_sample,
+                if is_float {
+                    "IEEE float"
+                } else {
+                    "PCM integer"
+                }
+            );
+
+            // Samples of any other depth would be dropped, recording silence
+            if let Err(e) = check_bit_depth(bits_per_sample) {
+                windows::Win32::System::Com::CoTaskMemFree(Some(mix_format_ptr as *const _));
+                return Err(e);
+            }
 
             // Initialize the audio client for loopback capture
-            let buffer_duration = 10_000_000; // 1 second in 100-nanosecond units
+            let params = timing.stream_params(CaptureStreamKind::Loopback);
             audio_client
                 .Initialize(
                     AUDCLNT_SHAREMODE_SHARED,
-                    AUDCLNT_STREAMFLAGS_LOOPBACK,
-                    buffer_duration,
+                    stream_flags(params),
+                    params.buffer_duration_hns,
                     0,
                     mix_format_ptr,
                     None,
@@ -347,11 +773,99 @@ impl WasapiAudioCapture {
         }
     }
 
+    /// Initialize the audio client for a converted stream in a requested format
+    ///
+    /// Shared-mode WASAPI converts from the mix format itself when asked to
+    /// (`AUTOCONVERTPCM`), so the audio doesn't need resampling later. Only 16-bit PCM
+    /// can be requested. `kind` is `ConvertedLoopback` or `ConvertedMicrophone`. On error
+    /// the client is left uninitialized, so the caller can still initialize it with the
+    /// mix format.
+    ///
+    /// Returns: (WAVEFORMATEX, sample_rate, bits_per_sample, is_float)
+    fn initialize_audio_client_with_format(
+        audio_client: &IAudioClient,
+        timing: CaptureTiming,
+        kind: CaptureStreamKind,
+        preferred: &AudioFormat,
+    ) -> Result<(WAVEFORMATEX, u32, u16, bool)> {
+        if preferred.bits_per_sample != 16
+            || preferred.sample_rate == 0
+            || !(1..=8).contains(&preferred.channels)
+        {
+            return Err(AppError::AudioCapture(format!(
+                "{} can't be requested from WASAPI",
+                preferred
+            )));
+        }
+
+        let block_align = preferred.channels * 2;
+        let format = WAVEFORMATEX {
+            wFormatTag: WAVE_FORMAT_PCM as u16,
+            nChannels: preferred.channels,
+            nSamplesPerSec: preferred.sample_rate,
+            nAvgBytesPerSec: preferred.sample_rate * u32::from(block_align),
+            nBlockAlign: block_align,
+            wBitsPerSample: 16,
+            cbSize: 0,
+        };
+
+        let params = timing.stream_params(kind);
+        unsafe {
+            audio_client
+                .Initialize(
+                    AUDCLNT_SHAREMODE_SHARED,
+                    stream_flags(params),
+                    params.buffer_duration_hns,
+                    0,
+                    &format,
+                    None,
+                )
+                .map_err(|e| {
+                    AppError::AudioCapture(format!("Failed to initialize audio client: {}", e))
+                })?;
+        }
+
+        log::info!("{:?} capture converted to {}", kind, preferred);
+        Ok((format, preferred.sample_rate, 16, false))
+    }
+
+    /// Activate and initialize a loopback client capturing only `process_name`
+    ///
+    /// Keeps music or notifications playing in other apps out of the recording.
+    /// Fails when the process isn't running or process loopback isn't available
+    /// (it needs Windows 10 build 20348 or later).
+    ///
+    /// Returns: (client, (WAVEFORMATEX, sample_rate, bits_per_sample, is_float))
+    fn initialize_process_client(
+        process_name: &str,
+        timing: CaptureTiming,
+        preferred_format: Option<&AudioFormat>,
+    ) -> Result<(IAudioClient, (WAVEFORMATEX, u32, u16, bool))> {
+        let process_id = find_root_process(&list_processes()?, process_name).ok_or_else(|| {
+            AppError::NotFound(format!("No running process named {}", process_name))
+        })?;
+        log::info!(
+            "Capturing audio of {} (process {})",
+            process_name,
+            process_id
+        );
+
+        let audio_client = activate_process_loopback(process_id)?;
+        let format = Self::initialize_audio_client_with_format(
+            &audio_client,
+            timing,
+            CaptureStreamKind::ConvertedLoopback,
+            preferred_format.unwrap_or(&PROCESS_LOOPBACK_FORMAT),
+        )?;
+        Ok((audio_client, format))
+    }
+
     /// Initialize the audio client for microphone capture (non-loopback)
     ///
     /// Similar to initialize_audio_client but doesn't use loopback mode
     fn initialize_microphone_client(
         audio_client: &IAudioClient,
+        timing: CaptureTiming,
     ) -> Result<(WAVEFORMATEX, u32, u16, bool)> {
         unsafe {
             // Get the device's mix format (auto-detected from system)
@@ -385,13 +899,18 @@ impl WasapiAudioCapture {
                 }
             );
 
+            if let Err(e) = check_bit_depth(bits_per_sample) {
+                windows::Win32::System::Com::CoTaskMemFree(Some(mix_format_ptr as *const _));
+                return Err(e);
+            }
+
             // Initialize the audio client for normal (non-loopback) capture
-            let buffer_duration = 10_000_000; // 1 second in 100-nanosecond units
+            let params = timing.stream_params(CaptureStreamKind::Microphone);
             audio_client
                 .Initialize(
                     AUDCLNT_SHAREMODE_SHARED,
-                    0, // No special flags (normal capture, not loopback)
-                    buffer_duration,
+                    stream_flags(params),
+                    params.buffer_duration_hns,
                     0,
                     mix_format_ptr,
                     None,
@@ -506,12 +1025,13 @@ impl WasapiAudioCapture {
         mean_square.sqrt()
     }
 
-    /// Perform the actual audio capture loop
+    /// Perform the actual audio capture loop, reading the buffer every `poll_interval`
     fn capture_loop(
         audio_client: IAudioClient,
         capture_client: IAudioCaptureClient,
         format: WAVEFORMATEX,
         is_float: bool,
+        poll_interval: Duration,
         is_capturing: Arc<Mutex<bool>>,
         audio_buffer: Arc<Mutex<Vec<f32>>>,
         current_level: Arc<Mutex<f32>>,
@@ -532,7 +1052,7 @@ impl WasapiAudioCapture {
             // Capture loop
             while *is_capturing.lock().unwrap() {
                 // Sleep a bit to avoid busy-waiting
-                std::thread::sleep(Duration::from_millis(10));
+                std::thread::sleep(poll_interval);
 
                 // Get the next packet of data
                 let packet_length = match capture_client.GetNextPacketSize() {
@@ -612,97 +1132,29 @@ impl Default for WasapiAudioCapture {
 #[async_trait]
 impl AudioCapturePort for WasapiAudioCapture {
     async fn list_devices(&self) -> Result<Vec<String>> {
-        use windows::Win32::Media::Audio::DEVICE_STATE_ACTIVE;
-
         tokio::task::spawn_blocking(|| {
-            unsafe {
-                // Initialize COM for this thread
-                if let Err(e) = Self::init_com() {
-                    return Err(e);
+            Self::with_com(|| {
+                let enumerator = Self::create_enumerator()?;
+
+                // Default speaker device first
+                let mut devices = vec![Self::default_speaker_entry(
+                    "Default Communication Device (Speaker)",
+                )];
+
+                let (speaker_count, speakers) =
+                    Self::active_endpoint_names(&enumerator, eRender, "speaker")?;
+                for (i, name) in speakers {
+                    devices.push(format!("{}: {} (Speaker)", i + 1, name));
                 }
 
-                let enumerator: IMMDeviceEnumerator =
-                    CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL).map_err(|e| {
-                        CoUninitialize();
-                        AppError::AudioCapture(format!("Failed to create device enumerator: {}", e))
-                    })?;
-
-                let mut devices = Vec::new();
-
-                // Get default speaker device first
-                match Self::get_default_device() {
-                    Ok(device) => {
-                        let name = Self::get_device_friendly_name(&device, 0);
-                        devices.push(format!("0: {} (Default Speaker)", name));
-                    }
-                    Err(e) => {
-                        log::warn!("Failed to get default device: {}", e);
-                        devices.push("0: Default Communication Device (Speaker)".to_string());
-                    }
-                }
-
-                // Enumerate all speaker (render) devices
-                let speaker_collection = enumerator
-                    .EnumAudioEndpoints(eRender, DEVICE_STATE_ACTIVE)
-                    .map_err(|e| {
-                        CoUninitialize();
-                        AppError::AudioCapture(format!(
-                            "Failed to enumerate speaker endpoints: {}",
-                            e
-                        ))
-                    })?;
-
-                let speaker_count = speaker_collection.GetCount().map_err(|e| {
-                    CoUninitialize();
-                    AppError::AudioCapture(format!("Failed to get speaker device count: {}", e))
-                })?;
-
-                // Add all speaker devices with their friendly names
-                for i in 0..speaker_count {
-                    match speaker_collection.Item(i) {
-                        Ok(device) => {
-                            let name = Self::get_device_friendly_name(&device, i);
-                            devices.push(format!("{}: {} (Speaker)", i + 1, name));
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to get speaker device {}: {}", i, e);
-                        }
-                    }
-                }
-
-                // Enumerate all microphone (capture) devices
-                let mic_collection = enumerator
-                    .EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE)
-                    .map_err(|e| {
-                        CoUninitialize();
-                        AppError::AudioCapture(format!(
-                            "Failed to enumerate microphone endpoints: {}",
-                            e
-                        ))
-                    })?;
-
-                let mic_count = mic_collection.GetCount().map_err(|e| {
-                    CoUninitialize();
-                    AppError::AudioCapture(format!("Failed to get microphone device count: {}", e))
-                })?;
-
-                // Add all microphone devices with their friendly names
-                // Use offset starting after speaker devices
+                // Microphones are numbered after the speaker devices
+                let (mic_count, microphones) =
+                    Self::active_endpoint_names(&enumerator, eCapture, "microphone")?;
                 let mic_offset = speaker_count + 1;
-                for i in 0..mic_count {
-                    match mic_collection.Item(i) {
-                        Ok(device) => {
-                            let name = Self::get_device_friendly_name(&device, i);
-                            devices.push(format!("{}: {} (Microphone)", mic_offset + i, name));
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to get microphone device {}: {}", i, e);
-                        }
-                    }
+                for (i, name) in microphones {
+                    devices.push(format!("{}: {} (Microphone)", mic_offset + i, name));
                 }
 
-                CoUninitialize();
-
                 log::info!(
                     "Found {} audio devices ({} speakers, {} microphones)",
                     devices.len(),
@@ -710,135 +1162,99 @@ impl AudioCapturePort for WasapiAudioCapture {
                     mic_count
                 );
                 Ok(devices)
-            }
+            })
         })
         .await
         .map_err(|e| AppError::AudioCapture(format!("Task join error: {}", e)))?
     }
 
     async fn list_speaker_devices(&self) -> Result<Vec<String>> {
-        use windows::Win32::Media::Audio::DEVICE_STATE_ACTIVE;
-
         tokio::task::spawn_blocking(|| {
-            unsafe {
-                if let Err(e) = Self::init_com() {
-                    return Err(e);
-                }
-
-                let enumerator: IMMDeviceEnumerator =
-                    CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL).map_err(|e| {
-                        CoUninitialize();
-                        AppError::AudioCapture(format!("Failed to create device enumerator: {}", e))
-                    })?;
-
-                let mut devices = Vec::new();
-
-                // Get default speaker device first
-                match Self::get_default_device() {
-                    Ok(device) => {
-                        let name = Self::get_device_friendly_name(&device, 0);
-                        devices.push(format!("0: {} (Default Speaker)", name));
-                    }
-                    Err(e) => {
-                        log::warn!("Failed to get default device: {}", e);
-                        devices.push("0: Default Communication Device".to_string());
-                    }
-                }
+            Self::with_com(|| {
+                let enumerator = Self::create_enumerator()?;
 
-                // Enumerate all speaker (render) devices
-                let speaker_collection = enumerator
-                    .EnumAudioEndpoints(eRender, DEVICE_STATE_ACTIVE)
-                    .map_err(|e| {
-                        CoUninitialize();
-                        AppError::AudioCapture(format!(
-                            "Failed to enumerate speaker endpoints: {}",
-                            e
-                        ))
-                    })?;
+                // Default speaker device first
+                let mut devices = vec![Self::default_speaker_entry("Default Communication Device")];
 
-                let speaker_count = speaker_collection.GetCount().map_err(|e| {
-                    CoUninitialize();
-                    AppError::AudioCapture(format!("Failed to get speaker device count: {}", e))
-                })?;
-
-                // Add all speaker devices with their friendly names
-                for i in 0..speaker_count {
-                    match speaker_collection.Item(i) {
-                        Ok(device) => {
-                            let name = Self::get_device_friendly_name(&device, i);
-                            devices.push(format!("{}: {}", i + 1, name));
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to get speaker device {}: {}", i, e);
-                        }
-                    }
+                let (_, speakers) = Self::active_endpoint_names(&enumerator, eRender, "speaker")?;
+                for (i, name) in speakers {
+                    devices.push(format!("{}: {}", i + 1, name));
                 }
 
-                CoUninitialize();
                 log::info!("Found {} speaker devices", devices.len());
                 Ok(devices)
-            }
+            })
         })
         .await
         .map_err(|e| AppError::AudioCapture(format!("Task join error: {}", e)))?
     }
 
     async fn list_microphone_devices(&self) -> Result<Vec<String>> {
-        use windows::Win32::Media::Audio::DEVICE_STATE_ACTIVE;
-
         tokio::task::spawn_blocking(|| {
-            unsafe {
-                if let Err(e) = Self::init_com() {
-                    return Err(e);
-                }
+            Self::with_com(|| {
+                let enumerator = Self::create_enumerator()?;
 
-                let enumerator: IMMDeviceEnumerator =
-                    CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL).map_err(|e| {
-                        CoUninitialize();
-                        AppError::AudioCapture(format!("Failed to create device enumerator: {}", e))
-                    })?;
+                let (_, microphones) =
+                    Self::active_endpoint_names(&enumerator, eCapture, "microphone")?;
+                let devices: Vec<String> = microphones
+                    .into_iter()
+                    .map(|(i, name)| format!("{}: {}", i, name))
+                    .collect();
 
-                let mut devices = Vec::new();
-
-                // Enumerate all microphone (capture) devices
-                let mic_collection = enumerator
-                    .EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE)
-                    .map_err(|e| {
-                        CoUninitialize();
-                        AppError::AudioCapture(format!(
-                            "Failed to enumerate microphone endpoints: {}",
-                            e
-                        ))
-                    })?;
-
-                let mic_count = mic_collection.GetCount().map_err(|e| {
-                    CoUninitialize();
-                    AppError::AudioCapture(format!("Failed to get microphone device count: {}", e))
-                })?;
+                log::info!("Found {} microphone devices", devices.len());
+                Ok(devices)
+            })
+        })
+        .await
+        .map_err(|e| AppError::AudioCapture(format!("Task join error: {}", e)))?
+    }
 
-                // Add all microphone devices with their friendly names
-                for i in 0..mic_count {
-                    match mic_collection.Item(i) {
-                        Ok(device) => {
-                            let name = Self::get_device_friendly_name(&device, i);
-                            devices.push(format!("{}: {}", i, name));
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to get microphone device {}: {}", i, e);
-                        }
-                    }
+    async fn list_devices_detailed(&self) -> Result<Vec<AudioDeviceInfo>> {
+        tokio::task::spawn_blocking(|| {
+            Self::with_com(|| {
+                let enumerator = Self::create_enumerator()?;
+
+                // Indices match list_speaker_devices and list_microphone_devices
+                let default_name = Self::get_default_device()
+                    .map(|device| Self::get_device_friendly_name(&device, 0))
+                    .unwrap_or_else(|_| "Default Communication Device".to_string());
+                let mut devices = vec![AudioDeviceInfo {
+                    index: 0,
+                    id: DEFAULT_DEVICE_ID.to_string(),
+                    name: format!("{} (Default Speaker)", default_name),
+                    kind: DeviceKind::Speaker,
+                }];
+
+                for (i, id, name) in Self::active_endpoints(&enumerator, eRender, "speaker")? {
+                    devices.push(AudioDeviceInfo {
+                        index: i as usize + 1,
+                        id,
+                        name,
+                        kind: DeviceKind::Speaker,
+                    });
+                }
+                for (i, id, name) in Self::active_endpoints(&enumerator, eCapture, "microphone")? {
+                    devices.push(AudioDeviceInfo {
+                        index: i as usize,
+                        id,
+                        name,
+                        kind: DeviceKind::Microphone,
+                    });
                 }
 
-                CoUninitialize();
-                log::info!("Found {} microphone devices", devices.len());
+                log::info!("Found {} audio devices", devices.len());
                 Ok(devices)
-            }
+            })
         })
         .await
         .map_err(|e| AppError::AudioCapture(format!("Task join error: {}", e)))?
     }
 
-    async fn start_capture(&mut self, device_name: Option<String>) -> Result<()> {
+    async fn start_capture(
+        &mut self,
+        device_name: Option<String>,
+        preferred_format: Option<AudioFormat>,
+    ) -> Result<()> {
         {
             let mut is_capturing = self.is_capturing.lock().unwrap();
             if *is_capturing {
@@ -851,6 +1267,7 @@ impl AudioCapturePort for WasapiAudioCapture {
         } // Drop is_capturing guard here
 
         let is_capturing_clone = Arc::clone(&self.is_capturing);
+        let timing = self.capture_timing;
         let audio_buffer_clone = Arc::clone(&self.audio_buffer);
         let current_level_clone = Arc::clone(&self.current_level);
 
@@ -860,32 +1277,30 @@ impl AudioCapturePort for WasapiAudioCapture {
 
         // Spawn background task for audio capture
         let handle = tokio::task::spawn_blocking(move || {
-            // Initialize COM for this thread
-            if let Err(e) = Self::init_com() {
-                log::error!("Failed to initialize COM: {}", e);
-                *is_capturing_clone.lock().unwrap() = false;
-                return;
-            }
+            // Initialize COM for this thread; uninitialized when the guard drops
+            let _com = match ComGuard::new() {
+                Ok(guard) => guard,
+                Err(e) => {
+                    log::error!("Failed to initialize COM: {}", e);
+                    *is_capturing_clone.lock().unwrap() = false;
+                    return;
+                }
+            };
 
             // Get the audio device (specific device or default)
-            // Device name format: "0: Default Audio Output" or "1: Audio Device 1"
-            let device_index = device_name
-                .and_then(|name| {
-                    // Extract index from "N: Device Name" format
-                    name.split(':').next()?.trim().parse::<usize>().ok()
-                })
-                .unwrap_or(0); // Default to index 0 if parsing fails
+            // Device name format: "0: Default Audio Output", "1: Audio Device 1" or an endpoint ID
+            let selection = device_name
+                .as_deref()
+                .map(DeviceSelection::parse)
+                .unwrap_or(DeviceSelection::Default);
 
-            log::info!("Using audio device index: {}", device_index);
+            log::info!("Using audio device: {:?}", selection);
 
-            let device = match Self::get_device_by_index(device_index) {
+            let device = match Self::speaker_device(&selection) {
                 Ok(d) => d,
                 Err(e) => {
-                    log::error!("Failed to get device at index {}: {}", device_index, e);
+                    log::error!("Failed to get device {:?}: {}", selection, e);
                     *is_capturing_clone.lock().unwrap() = false;
-                    unsafe {
-                        CoUninitialize();
-                    }
                     return;
                 }
             };
@@ -897,27 +1312,37 @@ impl AudioCapturePort for WasapiAudioCapture {
                     Err(e) => {
                         log::error!("Failed to activate audio client: {}", e);
                         *is_capturing_clone.lock().unwrap() = false;
-                        unsafe {
-                            CoUninitialize();
-                        }
                         return;
                     }
                 };
 
-            // Initialize the audio client and get the actual device format
-            // This is where the format is detected from the WASAPI device
-            let (format, sample_rate, bits_per_sample, is_float) =
-                match Self::initialize_audio_client(&audio_client) {
-                    Ok(f) => f,
-                    Err(e) => {
-                        log::error!("Failed to initialize audio client: {}", e);
-                        *is_capturing_clone.lock().unwrap() = false;
-                        unsafe {
-                            CoUninitialize();
-                        }
-                        return;
-                    }
-                };
+            // Initialize the audio client in the preferred format if one was requested,
+            // otherwise (or if that fails) in the format detected from the WASAPI device
+            let initialized = match preferred_format.as_ref() {
+                Some(preferred) => Self::initialize_audio_client_with_format(
+                    &audio_client,
+                    timing,
+                    CaptureStreamKind::ConvertedLoopback,
+                    preferred,
+                )
+                .or_else(|e| {
+                    log::warn!(
+                        "Can't capture in {}, using the device format: {}",
+                        preferred,
+                        e
+                    );
+                    Self::initialize_audio_client(&audio_client, timing)
+                }),
+                None => Self::initialize_audio_client(&audio_client, timing),
+            };
+            let (format, sample_rate, bits_per_sample, is_float) = match initialized {
+                Ok(f) => f,
+                Err(e) => {
+                    log::error!("Failed to initialize audio client: {}", e);
+                    *is_capturing_clone.lock().unwrap() = false;
+                    return;
+                }
+            };
 
             // IMPORTANT: Update format with actual detected values from the device
             // This replaces the default placeholder values with the real audio format
@@ -935,9 +1360,6 @@ impl AudioCapturePort for WasapiAudioCapture {
                     Err(e) => {
                         log::error!("Failed to get capture client: {}", e);
                         *is_capturing_clone.lock().unwrap() = false;
-                        unsafe {
-                            CoUninitialize();
-                        }
                         return;
                     }
                 };
@@ -956,14 +1378,11 @@ impl AudioCapturePort for WasapiAudioCapture {
                 capture_client,
                 format,
                 is_float,
+                timing.poll_interval(),
                 is_capturing_clone,
                 audio_buffer_clone,
                 current_level_clone,
             );
-
-            unsafe {
-                CoUninitialize();
-            }
         });
 
         self.capture_handle = Some(handle);
@@ -989,6 +1408,8 @@ impl AudioCapturePort for WasapiAudioCapture {
         &mut self,
         speaker_device: Option<String>,
         microphone_device: Option<String>,
+        process_name: Option<String>,
+        preferred_format: Option<AudioFormat>,
     ) -> Result<()> {
         {
             let mut is_capturing = self.is_capturing.lock().unwrap();
@@ -1001,33 +1422,34 @@ impl AudioCapturePort for WasapiAudioCapture {
         }
 
         let is_capturing_clone = Arc::clone(&self.is_capturing);
+        let timing = self.capture_timing;
         // Note: audio_buffer is not used directly in dual-capture - speaker_buffer and mic_buffer
         // are used instead, and the mixer thread writes to self.audio_buffer
 
         // Store format info to be updated after detection
-        // This will be updated by whichever capture thread starts first
-        let format_info = Arc::new(Mutex::new(AudioFormat::default()));
+        // Set by the speaker thread, or by the microphone thread when there's no speaker
+        let format_info: Arc<Mutex<Option<AudioFormat>>> = Arc::new(Mutex::new(None));
 
-        // Parse device indices
+        // Parse device selections ("N: Name" entries or endpoint IDs)
         log::info!("Speaker device string: {:?}", speaker_device);
         log::info!("Microphone device string: {:?}", microphone_device);
 
-        let speaker_index = speaker_device
-            .as_ref()
-            .and_then(|name| name.split(':').next()?.trim().parse::<usize>().ok());
-
-        let microphone_index = microphone_device
-            .as_ref()
-            .and_then(|name| name.split(':').next()?.trim().parse::<usize>().ok());
+        // A process target still needs a speaker to fall back on
+        let speaker_selection = speaker_device
+            .as_deref()
+            .map(DeviceSelection::parse)
+            .or_else(|| process_name.as_ref().map(|_| DeviceSelection::Default));
+        let microphone_selection = microphone_device.as_deref().map(DeviceSelection::parse);
 
         log::info!(
-            "Starting dual-capture: speaker index {:?}, microphone index {:?}",
-            speaker_index,
-            microphone_index
+            "Starting dual-capture: speaker {:?}, microphone {:?}, process {:?}",
+            speaker_selection,
+            microphone_selection,
+            process_name
         );
 
         // If both devices are None, return error
-        if speaker_index.is_none() && microphone_index.is_none() {
+        if speaker_selection.is_none() && microphone_selection.is_none() {
             *self.is_capturing.lock().unwrap() = false;
             return Err(AppError::AudioCapture(
                 "At least one device (speaker or microphone) must be specified".to_string(),
@@ -1041,9 +1463,12 @@ impl AudioCapturePort for WasapiAudioCapture {
         let speaker_is_capturing = Arc::clone(&is_capturing_clone);
         let speaker_level = Arc::clone(&self.current_level);
         let speaker_format_info = Arc::clone(&format_info);
+        let speaker_preferred = preferred_format.clone();
+        // The speaker's format is the master one whenever a speaker is captured
+        let speaker_sets_format = speaker_selection.is_some();
 
         // Channel and handle for speaker initialization (only created if speaker is specified)
-        let (speaker_handle, speaker_init_rx) = if let Some(spk_idx) = speaker_index {
+        let (speaker_handle, speaker_init_rx) = if let Some(speaker_selection) = speaker_selection {
             let (speaker_init_tx, speaker_init_rx) = tokio::sync::oneshot::channel::<Result<()>>();
             let handle = tokio::task::spawn_blocking(move || {
                 // Helper to send error and cleanup
@@ -1051,60 +1476,93 @@ impl AudioCapturePort for WasapiAudioCapture {
                     let _ = tx.send(Err(AppError::AudioCapture(msg)));
                 };
 
-                if let Err(e) = Self::init_com() {
-                    log::error!("Failed to initialize COM for speaker capture: {}", e);
-                    *speaker_is_capturing.lock().unwrap() = false;
-                    send_error(speaker_init_tx, format!("Failed to initialize COM: {}", e));
-                    return;
-                }
-
-                let device = match Self::get_device_by_index(spk_idx) {
-                    Ok(d) => d,
+                // Uninitializes COM when the thread's work is done, on every return path
+                let _com = match ComGuard::new() {
+                    Ok(guard) => guard,
                     Err(e) => {
-                        log::error!("Failed to get speaker device: {}", e);
+                        log::error!("Failed to initialize COM for speaker capture: {}", e);
                         *speaker_is_capturing.lock().unwrap() = false;
-                        send_error(
-                            speaker_init_tx,
-                            format!("Failed to get speaker device: {}", e),
-                        );
-                        unsafe {
-                            CoUninitialize();
-                        }
+                        send_error(speaker_init_tx, format!("Failed to initialize COM: {}", e));
                         return;
                     }
                 };
 
-                let audio_client: IAudioClient =
-                    match unsafe { device.Activate::<IAudioClient>(CLSCTX_ALL, None) } {
-                        Ok(client) => client,
-                        Err(e) => {
-                            log::error!("Failed to activate speaker audio client: {}", e);
-                            *speaker_is_capturing.lock().unwrap() = false;
-                            send_error(
-                                speaker_init_tx,
-                                format!("Failed to activate speaker audio client: {}", e),
-                            );
-                            unsafe {
-                                CoUninitialize();
-                            }
-                            return;
-                        }
-                    };
-
-                let (format, sample_rate, bits_per_sample, is_float) =
-                    match Self::initialize_audio_client(&audio_client) {
-                        Ok(f) => f,
-                        Err(e) => {
-                            log::error!("Failed to initialize speaker audio client: {}", e);
-                            *speaker_is_capturing.lock().unwrap() = false;
-                            send_error(
-                                speaker_init_tx,
-                                format!("Failed to initialize speaker audio client: {}", e),
+                // Capture only the meeting app when it's running, otherwise the speaker
+                let process_client = process_name.as_deref().and_then(|name| {
+                    Self::initialize_process_client(name, timing, speaker_preferred.as_ref())
+                        .map_err(|e| {
+                            log::warn!(
+                                "Can't capture {} on its own, capturing the speaker: {}",
+                                name,
+                                e
                             );
-                            unsafe {
-                                CoUninitialize();
+                        })
+                        .ok()
+                });
+
+                let (audio_client, (format, sample_rate, bits_per_sample, is_float)) =
+                    match process_client {
+                        Some(client) => client,
+                        None => {
+                            let device = match Self::speaker_device(&speaker_selection) {
+                                Ok(d) => d,
+                                Err(e) => {
+                                    log::error!("Failed to get speaker device: {}", e);
+                                    *speaker_is_capturing.lock().unwrap() = false;
+                                    send_error(
+                                        speaker_init_tx,
+                                        format!("Failed to get speaker device: {}", e),
+                                    );
+                                    return;
+                                }
+                            };
+
+                            let audio_client: IAudioClient = match unsafe {
+                                device.Activate::<IAudioClient>(CLSCTX_ALL, None)
+                            } {
+                                Ok(client) => client,
+                                Err(e) => {
+                                    log::error!("Failed to activate speaker audio client: {}", e);
+                                    *speaker_is_capturing.lock().unwrap() = false;
+                                    send_error(
+                                        speaker_init_tx,
+                                        format!("Failed to activate speaker audio client: {}", e),
+                                    );
+                                    return;
+                                }
+                            };
+
+                            // The preferred format if one was requested and the
+                            // device can deliver it, otherwise the device's mix format
+                            let initialized = match speaker_preferred.as_ref() {
+                                Some(preferred) => Self::initialize_audio_client_with_format(
+                                    &audio_client,
+                                    timing,
+                                    CaptureStreamKind::ConvertedLoopback,
+                                    preferred,
+                                )
+                                .or_else(|e| {
+                                    log::warn!(
+                                        "Can't capture the speaker in {}, using its format: {}",
+                                        preferred,
+                                        e
+                                    );
+                                    Self::initialize_audio_client(&audio_client, timing)
+                                }),
+                                None => Self::initialize_audio_client(&audio_client, timing),
+                            };
+                            match initialized {
+                                Ok(f) => (audio_client, f),
+                                Err(e) => {
+                                    log::error!("Failed to initialize speaker audio client: {}", e);
+                                    *speaker_is_capturing.lock().unwrap() = false;
+                                    send_error(
+                                        speaker_init_tx,
+                                        format!("Failed to initialize speaker audio client: {}", e),
+                                    );
+                                    return;
+                                }
                             }
-                            return;
                         }
                     };
 
@@ -1118,9 +1576,6 @@ impl AudioCapturePort for WasapiAudioCapture {
                                 speaker_init_tx,
                                 format!("Failed to get speaker capture client: {}", e),
                             );
-                            unsafe {
-                                CoUninitialize();
-                            }
                             return;
                         }
                     };
@@ -1130,11 +1585,11 @@ impl AudioCapturePort for WasapiAudioCapture {
 
                 // Update the shared format info with speaker format
                 // Speaker format is typically the master format for loopback capture
-                *speaker_format_info.lock().unwrap() = AudioFormat {
+                *speaker_format_info.lock().unwrap() = Some(AudioFormat {
                     sample_rate,
                     channels,
                     bits_per_sample,
-                };
+                });
 
                 log::info!(
                     "Speaker capture initialized: {} Hz, {} channels, {} bits",
@@ -1151,14 +1606,11 @@ impl AudioCapturePort for WasapiAudioCapture {
                     capture_client,
                     format,
                     is_float,
+                    timing.poll_interval(),
                     speaker_is_capturing,
                     speaker_buffer_clone,
                     speaker_level,
                 );
-
-                unsafe {
-                    CoUninitialize();
-                }
             });
             (Some(handle), Some(speaker_init_rx))
         } else {
@@ -1177,7 +1629,7 @@ impl AudioCapturePort for WasapiAudioCapture {
         let mic_channels_info_clone = Arc::clone(&mic_channels_info);
 
         // Channel and handle for microphone initialization (only created if microphone is specified)
-        let (mic_handle, mic_init_rx) = if let Some(mic_idx) = microphone_index {
+        let (mic_handle, mic_init_rx) = if let Some(microphone_selection) = microphone_selection {
             let (mic_init_tx, mic_init_rx) = tokio::sync::oneshot::channel::<Result<()>>();
             let handle = tokio::task::spawn_blocking(move || {
                 // Helper to send error and cleanup
@@ -1185,14 +1637,18 @@ impl AudioCapturePort for WasapiAudioCapture {
                     let _ = tx.send(Err(AppError::AudioCapture(msg)));
                 };
 
-                if let Err(e) = Self::init_com() {
-                    log::error!("Failed to initialize COM for microphone capture: {}", e);
-                    *mic_is_capturing.lock().unwrap() = false;
-                    send_error(mic_init_tx, format!("Failed to initialize COM: {}", e));
-                    return;
-                }
+                // Uninitializes COM when the thread's work is done, on every return path
+                let _com = match ComGuard::new() {
+                    Ok(guard) => guard,
+                    Err(e) => {
+                        log::error!("Failed to initialize COM for microphone capture: {}", e);
+                        *mic_is_capturing.lock().unwrap() = false;
+                        send_error(mic_init_tx, format!("Failed to initialize COM: {}", e));
+                        return;
+                    }
+                };
 
-                let device = match Self::get_microphone_by_index(mic_idx) {
+                let device = match Self::microphone_device(&microphone_selection) {
                     Ok(d) => d,
                     Err(e) => {
                         log::error!("Failed to get microphone device: {}", e);
@@ -1201,9 +1657,6 @@ impl AudioCapturePort for WasapiAudioCapture {
                             mic_init_tx,
                             format!("Failed to get microphone device: {}", e),
                         );
-                        unsafe {
-                            CoUninitialize();
-                        }
                         return;
                     }
                 };
@@ -1218,29 +1671,40 @@ impl AudioCapturePort for WasapiAudioCapture {
                                 mic_init_tx,
                                 format!("Failed to activate microphone audio client: {}", e),
                             );
-                            unsafe {
-                                CoUninitialize();
-                            }
                             return;
                         }
                     };
 
-                let (format, sample_rate, bits_per_sample, is_float) =
-                    match Self::initialize_microphone_client(&audio_client) {
-                        Ok(f) => f,
-                        Err(e) => {
-                            log::error!("Failed to initialize microphone audio client: {}", e);
-                            *mic_is_capturing.lock().unwrap() = false;
-                            send_error(
-                                mic_init_tx,
-                                format!("Failed to initialize microphone audio client: {}", e),
-                            );
-                            unsafe {
-                                CoUninitialize();
-                            }
-                            return;
-                        }
-                    };
+                // Converting the mic to the speaker's format keeps the mix aligned
+                let initialized = match preferred_format.as_ref() {
+                    Some(preferred) => Self::initialize_audio_client_with_format(
+                        &audio_client,
+                        timing,
+                        CaptureStreamKind::ConvertedMicrophone,
+                        preferred,
+                    )
+                    .or_else(|e| {
+                        log::warn!(
+                            "Can't capture the microphone in {}, using its format: {}",
+                            preferred,
+                            e
+                        );
+                        Self::initialize_microphone_client(&audio_client, timing)
+                    }),
+                    None => Self::initialize_microphone_client(&audio_client, timing),
+                };
+                let (format, sample_rate, bits_per_sample, is_float) = match initialized {
+                    Ok(f) => f,
+                    Err(e) => {
+                        log::error!("Failed to initialize microphone audio client: {}", e);
+                        *mic_is_capturing.lock().unwrap() = false;
+                        send_error(
+                            mic_init_tx,
+                            format!("Failed to initialize microphone audio client: {}", e),
+                        );
+                        return;
+                    }
+                };
 
                 // Update format from microphone only if speaker format not already set
                 // Speaker format takes priority as it's the loopback (system audio) source
@@ -1251,18 +1715,18 @@ impl AudioCapturePort for WasapiAudioCapture {
 
                 {
                     let mut fmt = format_info_clone2.lock().unwrap();
-                    // Only update if still at default (speaker didn't set it)
-                    if fmt.sample_rate == 16000 && fmt.channels == 1 {
-                        *fmt = AudioFormat {
+                    // Only update if no speaker is captured to set it
+                    if !speaker_sets_format {
+                        *fmt = Some(AudioFormat {
                             sample_rate,
                             channels,
                             bits_per_sample,
-                        };
+                        });
                         log::info!("Using microphone format as master (no speaker specified)");
                     } else {
                         log::info!(
-                            "Speaker format already set ({} Hz, {} ch), microphone format ignored ({} Hz, {} ch)",
-                            fmt.sample_rate, fmt.channels, sample_rate, channels
+                            "Speaker format is the master, microphone format ignored ({} Hz, {} ch)",
+                            sample_rate, channels
                         );
                     }
                 }
@@ -1277,9 +1741,6 @@ impl AudioCapturePort for WasapiAudioCapture {
                                 mic_init_tx,
                                 format!("Failed to get microphone capture client: {}", e),
                             );
-                            unsafe {
-                                CoUninitialize();
-                            }
                             return;
                         }
                     };
@@ -1299,14 +1760,11 @@ impl AudioCapturePort for WasapiAudioCapture {
                     capture_client,
                     format,
                     is_float,
+                    timing.poll_interval(),
                     mic_is_capturing,
                     mic_buffer_clone,
                     mic_level,
                 );
-
-                unsafe {
-                    CoUninitialize();
-                }
             });
             (Some(handle), Some(mic_init_rx))
         } else {
@@ -1367,19 +1825,15 @@ impl AudioCapturePort for WasapiAudioCapture {
             ));
         }
 
-        // Get the detected format
-        self.format = format_info.lock().unwrap().clone();
-
-        // Verify format was properly detected (not still at default)
-        if self.format.sample_rate == 16000 && self.format.channels == 1 {
-            log::error!(
-                "Format still at default after capture start - initialization may have failed"
-            );
+        // Get the detected format, which is missing if initialization failed
+        let Some(format) = format_info.lock().unwrap().clone() else {
+            log::error!("Format not detected after capture start - initialization may have failed");
             *self.is_capturing.lock().unwrap() = false;
             return Err(AppError::AudioCapture(
                 "Audio format detection failed - capture may not be working correctly".to_string(),
             ));
-        }
+        };
+        self.format = format;
 
         // Spawn mixer thread to combine both audio streams
         let is_capturing_mixer = Arc::clone(&self.is_capturing);
@@ -1510,6 +1964,10 @@ impl AudioCapturePort for WasapiAudioCapture {
     fn get_current_level(&self) -> f32 {
         *self.current_level.lock().unwrap()
     }
+
+    fn set_capture_timing(&mut self, timing: CaptureTiming) {
+        self.capture_timing = timing;
+    }
 }
 
 impl WasapiAudioCapture {
@@ -1552,10 +2010,10 @@ impl WasapiAudioCapture {
             IAudioRenderClient, AUDCLNT_BUFFERFLAGS_SILENT, AUDCLNT_SHAREMODE_SHARED,
         };
 
-        unsafe {
-            // Initialize COM for this thread
-            Self::init_com()?;
+        // Initialize COM for this thread; uninitialized on every return path
+        let _com = ComGuard::new()?;
 
+        unsafe {
             // Get the playback device (render endpoint)
             let device = Self::get_device_by_index(device_index)?;
 
@@ -1708,8 +2166,6 @@ impl WasapiAudioCapture {
             // Free the mix format
             windows::Win32::System::Com::CoTaskMemFree(Some(mix_format_ptr as *const _));
 
-            CoUninitialize();
-
             Ok(())
         }
     }
@@ -1725,6 +2181,38 @@ mod tests {
         assert!(!capture.is_capturing());
     }
 
+    #[test]
+    fn test_com_guard_uninitializes_on_drop() {
+        use windows::Win32::Foundation::{S_FALSE, S_OK};
+
+        // CoInitializeEx returns S_FALSE while COM is already initialized on the
+        // thread and S_OK once every earlier initialization has been undone
+        unsafe {
+            {
+                let _com = ComGuard::new().unwrap();
+                assert_eq!(CoInitializeEx(None, COINIT_MULTITHREADED), S_FALSE);
+                CoUninitialize();
+            }
+            assert_eq!(CoInitializeEx(None, COINIT_MULTITHREADED), S_OK);
+            CoUninitialize();
+        }
+    }
+
+    #[test]
+    fn test_with_com_uninitializes_on_error() {
+        use windows::Win32::Foundation::S_OK;
+
+        let result: Result<()> = WasapiAudioCapture::with_com(|| {
+            Err(AppError::AudioCapture("enumeration failed".to_string()))
+        });
+        assert!(result.is_err());
+
+        unsafe {
+            assert_eq!(CoInitializeEx(None, COINIT_MULTITHREADED), S_OK);
+            CoUninitialize();
+        }
+    }
+
     #[test]
     fn test_default_format() {
         let capture = WasapiAudioCapture::new();
@@ -1783,4 +2271,77 @@ mod tests {
         assert!((samples[0] - 0.0).abs() < 0.001);
         assert!((samples[1] - 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_process_loopback_params() {
+        let params = process_loopback_params(4242);
+        assert_eq!(
+            params.ActivationType,
+            AUDIOCLIENT_ACTIVATION_TYPE_PROCESS_LOOPBACK
+        );
+        let loopback = unsafe { params.Anonymous.ProcessLoopbackParams };
+        assert_eq!(loopback.TargetProcessId, 4242);
+        assert_eq!(
+            loopback.ProcessLoopbackMode,
+            PROCESS_LOOPBACK_MODE_INCLUDE_TARGET_PROCESS_TREE
+        );
+
+        // Passed as a VT_BLOB PROPVARIANT pointing at the parameters
+        let variant = BlobPropVariant::new(&params);
+        assert_eq!(
+            std::mem::size_of::<BlobPropVariant>(),
+            std::mem::size_of::<windows::core::PROPVARIANT>()
+        );
+        assert_eq!(variant.vt, VT_BLOB);
+        assert_eq!(
+            variant.cb_size as usize,
+            std::mem::size_of::<AUDIOCLIENT_ACTIVATION_PARAMS>()
+        );
+        assert_eq!(variant.blob_data, &params as *const _ as *const u8);
+    }
+
+    #[test]
+    fn test_stream_flags() {
+        let timing = CaptureTiming::default();
+        assert_eq!(
+            stream_flags(timing.stream_params(CaptureStreamKind::Loopback)),
+            AUDCLNT_STREAMFLAGS_LOOPBACK
+        );
+        assert_eq!(
+            stream_flags(timing.stream_params(CaptureStreamKind::ConvertedLoopback)),
+            AUDCLNT_STREAMFLAGS_LOOPBACK
+                | AUDCLNT_STREAMFLAGS_AUTOCONVERTPCM
+                | AUDCLNT_STREAMFLAGS_SRC_DEFAULT_QUALITY
+        );
+        assert_eq!(
+            stream_flags(timing.stream_params(CaptureStreamKind::Microphone)),
+            0
+        );
+        assert_eq!(
+            stream_flags(timing.stream_params(CaptureStreamKind::ConvertedMicrophone)),
+            AUDCLNT_STREAMFLAGS_AUTOCONVERTPCM | AUDCLNT_STREAMFLAGS_SRC_DEFAULT_QUALITY
+        );
+    }
+
+    #[test]
+    fn test_find_root_process() {
+        let process = |id, parent_id, exe_name: &str| ProcessEntry {
+            id,
+            parent_id,
+            exe_name: exe_name.to_string(),
+        };
+        let processes = vec![
+            process(10, 1, "explorer.exe"),
+            process(21, 20, "ms-teams.exe"),
+            process(20, 10, "ms-teams.exe"),
+            process(30, 10, "Zoom.exe"),
+            process(40, 10, "Spotify.exe"),
+        ];
+
+        assert_eq!(find_root_process(&processes, "zoom"), Some(30));
+        assert_eq!(find_root_process(&processes, "ZOOM.EXE"), Some(30));
+        // The parent of the Teams processes, whose tree includes the child
+        assert_eq!(find_root_process(&processes, "ms-teams"), Some(20));
+        assert_eq!(find_root_process(&processes, "slack"), None);
+    }
 }
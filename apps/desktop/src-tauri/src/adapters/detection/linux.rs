@@ -270,6 +270,7 @@ for i in range(desktop.get_child_count()):
                 Platform::Teams => "teams",
                 Platform::Zoom => "zoom",
                 Platform::Meet => "meet",
+                Platform::Other(name) => name.as_str(),
             }
         );
 
@@ -364,6 +365,7 @@ for i in range(desktop.get_child_count()):
                     return false;
                 }
             }
+            Platform::Other(_) => {}
         }
 
         name.chars().any(|c| c.is_alphabetic())
@@ -303,6 +303,7 @@ impl UiAutomationDetector {
                     return false;
                 }
             }
+            Platform::Other(_) => {}
         }
 
         // Must have letters and reasonable number of spaces
@@ -12,55 +12,192 @@ use crate::ports::transcription::{
     StreamingSession, StreamingTranscriptionCallback, TranscriptionConfig, TranscriptionResult,
     TranscriptionSegment, TranscriptionServicePort,
 };
+use crate::utils::http::{check_api_key, shared_client};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use tokio::fs::File;
-use tokio::io::AsyncReadExt;
+
+use super::{
+    channel_speaker_label, filter_low_confidence, normalize_speaker_labels, open_audio_upload,
+    redaction_policies, save_raw_response, UPLOAD_AUDIO_FORMATS,
+};
 
 const ASSEMBLYAI_API_BASE: &str = "https://api.assemblyai.com/v2";
+/// Largest file the upload endpoint accepts (2.2 GB)
+const MAX_UPLOAD_BYTES: u64 = 2_200_000_000;
 const POLL_INTERVAL_MS: u64 = 3000; // Poll every 3 seconds
 const MAX_POLL_ATTEMPTS: u32 = 200; // Max 10 minutes (200 * 3s)
+/// Upper bound for `max_poll_attempts` from settings
+const MAX_POLL_ATTEMPTS_LIMIT: u32 = 100_000;
+/// Growth of the poll interval per attempt; fixed unless `poll_backoff_factor` is set
+const POLL_BACKOFF_FACTOR: f64 = 1.0;
+/// Cap for the grown interval when backoff is enabled
+const MAX_POLL_INTERVAL_MS: u64 = 15000;
+/// Largest exponent applied to the backoff factor; beyond it every delay is at the cap
+const MAX_BACKOFF_EXPONENT: u32 = 64;
+
+/// PII policies redacted when PII redaction is enabled without an explicit policy list
+const DEFAULT_PII_POLICIES: &[&str] = &[
+    "person_name",
+    "email_address",
+    "phone_number",
+    "us_social_security_number",
+];
+
+/// How often and for how long to poll for a finished transcript
+///
+/// Configurable through the ASR service settings (`poll_interval_ms`, `max_poll_attempts`,
+/// `poll_backoff_factor`, `max_poll_interval_ms`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollingConfig {
+    /// Delay before the first poll
+    pub interval: Duration,
+    pub max_attempts: u32,
+    /// Multiplier applied to the delay after each poll (1.0 keeps it fixed)
+    pub backoff_factor: f64,
+    /// Upper bound for the grown delay
+    pub max_interval: Duration,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(POLL_INTERVAL_MS),
+            max_attempts: MAX_POLL_ATTEMPTS,
+            backoff_factor: POLL_BACKOFF_FACTOR,
+            max_interval: Duration::from_millis(MAX_POLL_INTERVAL_MS),
+        }
+    }
+}
+
+impl PollingConfig {
+    /// Read polling overrides from the ASR service settings, keeping defaults for unset keys
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        let defaults = Self::default();
+        let u64_setting = |key: &str| settings.get(key).and_then(|v| v.as_u64());
+
+        let interval = u64_setting("poll_interval_ms")
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.interval);
+
+        Self {
+            interval,
+            max_attempts: u64_setting("max_poll_attempts")
+                .filter(|n| *n > 0)
+                .map(|n| n.min(u64::from(MAX_POLL_ATTEMPTS_LIMIT)) as u32)
+                .unwrap_or(defaults.max_attempts),
+            backoff_factor: settings
+                .get("poll_backoff_factor")
+                .and_then(|v| v.as_f64())
+                .filter(|f| *f >= 1.0)
+                .unwrap_or(defaults.backoff_factor),
+            max_interval: u64_setting("max_poll_interval_ms")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.max_interval)
+                .max(interval),
+        }
+    }
+
+    /// Delay before poll `attempt` (0-based)
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let exponent = attempt.min(MAX_BACKOFF_EXPONENT) as i32;
+        let grown = self.interval.as_secs_f64() * self.backoff_factor.powi(exponent);
+        if grown >= self.max_interval.as_secs_f64() {
+            return self.max_interval;
+        }
+        Duration::from_secs_f64(grown)
+    }
+
+    /// Total time spent waiting if every attempt is used
+    ///
+    /// The delays grow geometrically for the first `growing` attempts and then stay
+    /// at the cap, so the total is a geometric sum plus the capped remainder.
+    pub fn deadline(&self) -> Duration {
+        let interval = self.interval.as_secs_f64();
+        let cap = self.max_interval.as_secs_f64();
+        if self.backoff_factor <= 1.0 || interval >= cap {
+            return self
+                .interval
+                .min(self.max_interval)
+                .saturating_mul(self.max_attempts);
+        }
+
+        let factor = self.backoff_factor;
+        let growing = ((cap / interval).ln() / factor.ln()).ceil();
+        let growing = growing.clamp(0.0, f64::from(self.max_attempts.min(MAX_BACKOFF_EXPONENT)));
+        let geometric = interval * (factor.powf(growing) - 1.0) / (factor - 1.0);
+        let geometric = Duration::try_from_secs_f64(geometric).unwrap_or(Duration::MAX);
+
+        let capped = self
+            .max_interval
+            .saturating_mul(self.max_attempts - growing as u32);
+        geometric.saturating_add(capped)
+    }
+}
 
 /// AssemblyAI service implementation
 pub struct AssemblyAIService {
     client: Client,
     api_key: String,
+    api_base: String,
+    polling: PollingConfig,
 }
 
 impl AssemblyAIService {
     /// Create a new AssemblyAI service with the given API key
     pub fn new(api_key: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(60))
-            .build()
-            .expect("Failed to create HTTP client");
+        let client = shared_client(Duration::from_secs(60));
+
+        Self {
+            client,
+            api_key,
+            api_base: ASSEMBLYAI_API_BASE.to_string(),
+            polling: PollingConfig::default(),
+        }
+    }
+
+    /// Point the service at a different API host (used by tests)
+    #[cfg(test)]
+    fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.to_string();
+        self
+    }
 
-        Self { client, api_key }
+    /// Override how the service polls for finished transcripts
+    pub fn with_polling(mut self, polling: PollingConfig) -> Self {
+        self.polling = polling;
+        self
+    }
+
+    /// Check that the API key is accepted by listing the most recent transcript
+    pub async fn verify_api_key(&self) -> Result<()> {
+        check_api_key(
+            self.client
+                .get(format!("{}/transcript?limit=1", self.api_base))
+                .header("authorization", &self.api_key),
+            "AssemblyAI",
+            AppError::Transcription,
+        )
+        .await
     }
 
     /// Upload audio file to AssemblyAI and get the upload URL
     async fn upload_file(&self, audio_path: &str) -> Result<String> {
         log::info!("Uploading audio file to AssemblyAI: {}", audio_path);
 
-        // Read the audio file
-        let mut file = File::open(audio_path)
-            .await
-            .map_err(|e| AppError::Transcription(format!("Failed to open audio file: {}", e)))?;
-
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .await
-            .map_err(|e| AppError::Transcription(format!("Failed to read audio file: {}", e)))?;
+        // Stream the file from disk rather than buffering it
+        let upload = open_audio_upload(audio_path).await?;
 
         // Upload to AssemblyAI
         let response = self
             .client
-            .post(format!("{}/upload", ASSEMBLYAI_API_BASE))
+            .post(format!("{}/upload", self.api_base))
             .header("authorization", &self.api_key)
             .header("content-type", "application/octet-stream")
-            .body(buffer)
+            .header("content-length", upload.size)
+            .body(upload.body)
             .send()
             .await
             .map_err(|e| AppError::Transcription(format!("Upload request failed: {}", e)))?;
@@ -89,17 +226,11 @@ impl AssemblyAIService {
     ) -> Result<String> {
         log::info!("Submitting transcription request to AssemblyAI");
 
-        let request_body = TranscriptionRequest {
-            audio_url: audio_url.to_string(),
-            speaker_labels: config.enable_diarization,
-            speakers_expected: config.num_speakers,
-            language_code: config.language.clone(),
-            speech_model: config.model.clone(),
-        };
+        let request_body = TranscriptionRequest::from_config(audio_url, config);
 
         let response = self
             .client
-            .post(format!("{}/transcript", ASSEMBLYAI_API_BASE))
+            .post(format!("{}/transcript", self.api_base))
             .header("authorization", &self.api_key)
             .header("content-type", "application/json")
             .json(&request_body)
@@ -124,19 +255,29 @@ impl AssemblyAIService {
     }
 
     /// Poll for transcription completion
-    async fn poll_transcription(&self, transcript_id: &str) -> Result<TranscriptionResult> {
-        log::info!("Polling for transcription completion: {}", transcript_id);
+    ///
+    /// The completed response is saved to `raw_response_path` when one is given.
+    async fn poll_transcription(
+        &self,
+        transcript_id: &str,
+        raw_response_path: Option<&str>,
+    ) -> Result<TranscriptionResult> {
+        let polling = &self.polling;
+        let deadline = polling.deadline();
+        let started = std::time::Instant::now();
+        log::info!(
+            "Polling for transcription completion: {} (deadline {}s)",
+            transcript_id,
+            deadline.as_secs()
+        );
 
-        for attempt in 1..=MAX_POLL_ATTEMPTS {
+        for attempt in 1..=polling.max_attempts {
             // Wait before polling
-            tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+            tokio::time::sleep(polling.delay_for(attempt - 1)).await;
 
             let response = self
                 .client
-                .get(format!(
-                    "{}/transcript/{}",
-                    ASSEMBLYAI_API_BASE, transcript_id
-                ))
+                .get(format!("{}/transcript/{}", self.api_base, transcript_id))
                 .header("authorization", &self.api_key)
                 .send()
                 .await
@@ -150,13 +291,18 @@ impl AssemblyAIService {
                 )));
             }
 
-            let transcript_response: TranscriptResponse = response.json().await.map_err(|e| {
-                AppError::Transcription(format!("Failed to parse poll response: {}", e))
+            let body = response.text().await.map_err(|e| {
+                AppError::Transcription(format!("Failed to read poll response: {}", e))
             })?;
+            let transcript_response: TranscriptResponse =
+                serde_json::from_str(&body).map_err(|e| {
+                    AppError::Transcription(format!("Failed to parse poll response: {}", e))
+                })?;
 
             match transcript_response.status.as_str() {
                 "completed" => {
                     log::info!("Transcription completed successfully");
+                    save_raw_response(raw_response_path, &body).await;
                     return self.parse_transcript_response(transcript_response);
                 }
                 "error" => {
@@ -167,10 +313,11 @@ impl AssemblyAIService {
                 }
                 "queued" | "processing" => {
                     log::debug!(
-                        "Transcription status: {} (attempt {}/{})",
+                        "Transcription status: {} (attempt {}/{}, ~{}s remaining)",
                         transcript_response.status,
                         attempt,
-                        MAX_POLL_ATTEMPTS
+                        polling.max_attempts,
+                        deadline.saturating_sub(started.elapsed()).as_secs()
                     );
                     continue;
                 }
@@ -181,9 +328,11 @@ impl AssemblyAIService {
             }
         }
 
-        Err(AppError::Transcription(
-            "Transcription timeout: exceeded maximum polling attempts".to_string(),
-        ))
+        Err(AppError::Transcription(format!(
+            "Transcription timeout: not finished after {} polls ({}s)",
+            polling.max_attempts,
+            started.elapsed().as_secs()
+        )))
     }
 
     /// Parse AssemblyAI response into our TranscriptionResult format
@@ -199,11 +348,12 @@ impl AssemblyAIService {
             utterances
                 .into_iter()
                 .map(|utt| TranscriptionSegment {
+                    speaker_label: Some(utterance_speaker_label(&utt)),
                     text: utt.text,
                     start_ms: utt.start,
                     end_ms: utt.end,
-                    speaker_label: Some(format!("Speaker {}", utt.speaker)),
                     confidence: Some(utt.confidence),
+                    language: utt.language_code,
                 })
                 .collect()
         } else {
@@ -214,6 +364,7 @@ impl AssemblyAIService {
                 end_ms: response.audio_duration.unwrap_or(0),
                 speaker_label: None,
                 confidence,
+                language: None,
             }]
         };
 
@@ -221,6 +372,9 @@ impl AssemblyAIService {
             text,
             segments,
             confidence,
+            detected_language: response.language_code,
+            model_info: None,
+            request_id: Some(response.id),
         })
     }
 }
@@ -241,7 +395,11 @@ impl TranscriptionServicePort for AssemblyAIService {
         let transcript_id = self.submit_transcription(&audio_url, config).await?;
 
         // Step 3: Poll for completion
-        let result = self.poll_transcription(&transcript_id).await?;
+        let mut result = self
+            .poll_transcription(&transcript_id, config.raw_response_path.as_deref())
+            .await?;
+        filter_low_confidence(&mut result, config);
+        normalize_speaker_labels(&mut result, config);
 
         log::info!(
             "AssemblyAI transcription complete: {} segments, {} chars",
@@ -332,6 +490,26 @@ impl TranscriptionServicePort for AssemblyAIService {
     fn supports_streaming(&self) -> bool {
         false // TODO: Implement AssemblyAI streaming support
     }
+
+    fn supports_pii_redaction(&self) -> bool {
+        true
+    }
+
+    fn supports_diarization(&self) -> bool {
+        true
+    }
+
+    fn supports_language_detection(&self) -> bool {
+        true
+    }
+
+    fn supported_audio_formats(&self) -> &'static [&'static str] {
+        UPLOAD_AUDIO_FORMATS
+    }
+
+    fn max_upload_bytes(&self) -> Option<u64> {
+        Some(MAX_UPLOAD_BYTES)
+    }
 }
 
 // ===== API Request/Response Types =====
@@ -343,10 +521,55 @@ struct TranscriptionRequest {
     speaker_labels: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     speakers_expected: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    multichannel: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     language_code: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    language_detection: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     speech_model: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    redact_pii: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redact_pii_policies: Option<Vec<String>>,
+}
+
+impl TranscriptionRequest {
+    fn from_config(audio_url: &str, config: &TranscriptionConfig) -> Self {
+        // AssemblyAI requires a policy list whenever redaction is enabled. The app's
+        // categories are AssemblyAI policy names, and its other policies pass through.
+        let redact_pii_policies =
+            redaction_policies(config, |category| Some(category), DEFAULT_PII_POLICIES);
+
+        // Multichannel speakers are labeled by channel, so diarization is left off
+        let speaker_labels = config.enable_diarization && !config.multichannel;
+
+        Self {
+            audio_url: audio_url.to_string(),
+            speaker_labels,
+            speakers_expected: config.num_speakers.filter(|_| speaker_labels),
+            multichannel: config.multichannel,
+            language_code: config.language.clone(),
+            // No language means auto-detect
+            language_detection: config.language.is_none(),
+            speech_model: config.model.clone(),
+            redact_pii: config.enable_pii_redaction,
+            redact_pii_policies,
+        }
+    }
+}
+
+/// Speaker label for an utterance, by channel in multichannel transcripts
+fn utterance_speaker_label(utterance: &Utterance) -> String {
+    match utterance
+        .channel
+        .as_deref()
+        .and_then(|channel| channel.parse::<usize>().ok())
+    {
+        Some(channel) if channel > 0 => channel_speaker_label(channel - 1),
+        _ => format!("Speaker {}", utterance.speaker),
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -361,6 +584,7 @@ struct TranscriptResponse {
     text: Option<String>,
     confidence: Option<f32>,
     audio_duration: Option<i64>,
+    language_code: Option<String>,
     utterances: Option<Vec<Utterance>>,
     error: Option<String>,
 }
@@ -372,6 +596,10 @@ struct Utterance {
     end: i64,
     confidence: f32,
     speaker: String,
+    /// 1-based audio channel, reported for multichannel transcripts
+    channel: Option<String>,
+    /// Only reported when code switching is enabled with language detection
+    language_code: Option<String>,
 }
 
 #[cfg(test)]
@@ -385,9 +613,247 @@ mod tests {
         assert!(service.is_configured());
     }
 
+    #[tokio::test]
+    async fn test_upload_streams_file() {
+        use std::io::Write;
+
+        let (api_base, server) = super::super::test_server::serve_once(
+            r#"{ "upload_url": "https://cdn.assemblyai.com/upload/abc" }"#,
+        )
+        .await;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&vec![1u8; 3 * 1024 * 1024 + 17]).unwrap();
+
+        let service = AssemblyAIService::new("test_api_key".to_string()).with_api_base(&api_base);
+        let upload_url = service
+            .upload_file(file.path().to_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(upload_url, "https://cdn.assemblyai.com/upload/abc");
+
+        let request = server.await.unwrap();
+        assert!(request.head.starts_with("post /upload "));
+        assert_eq!(request.body_len, 3 * 1024 * 1024 + 17);
+    }
+
+    #[test]
+    fn test_polling_deadline_without_backoff() {
+        let polling = PollingConfig::from_settings(&serde_json::json!({
+            "poll_interval_ms": 2000,
+            "max_poll_attempts": 30,
+            "poll_backoff_factor": 1.0
+        }));
+
+        assert_eq!(polling.delay_for(0), Duration::from_secs(2));
+        assert_eq!(polling.delay_for(29), Duration::from_secs(2));
+        assert_eq!(polling.deadline(), Duration::from_secs(2) * 30);
+    }
+
+    #[test]
+    fn test_polling_defaults_keep_fixed_interval() {
+        let polling = PollingConfig::from_settings(&serde_json::Value::Null);
+        assert_eq!(polling, PollingConfig::default());
+        assert_eq!(polling.delay_for(0), Duration::from_secs(3));
+        assert_eq!(
+            polling.delay_for(MAX_POLL_ATTEMPTS - 1),
+            Duration::from_secs(3)
+        );
+        // The same 10 minutes as before polling became configurable
+        assert_eq!(polling.deadline(), Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_polling_backoff() {
+        let polling = PollingConfig::from_settings(&serde_json::json!({
+            "poll_backoff_factor": 1.05,
+        }));
+
+        // Grows each attempt but never past the cap
+        assert!(polling.delay_for(10) > polling.delay_for(9));
+        assert_eq!(
+            polling.delay_for(MAX_POLL_ATTEMPTS - 1),
+            Duration::from_millis(MAX_POLL_INTERVAL_MS)
+        );
+
+        // The closed form matches adding up every delay
+        let summed: Duration = (0..polling.max_attempts)
+            .map(|attempt| polling.delay_for(attempt))
+            .sum();
+        let difference = polling.deadline().abs_diff(summed);
+        assert!(
+            difference < Duration::from_millis(MAX_POLL_INTERVAL_MS),
+            "{:?}",
+            difference
+        );
+    }
+
+    #[test]
+    fn test_polling_deadline_saturates() {
+        let polling = PollingConfig::from_settings(&serde_json::json!({
+            "poll_interval_ms": u64::MAX,
+            "max_poll_attempts": u64::MAX,
+            "poll_backoff_factor": 1e300,
+        }));
+        assert_eq!(polling.max_attempts, MAX_POLL_ATTEMPTS_LIMIT);
+        assert_eq!(polling.delay_for(u32::MAX), polling.max_interval);
+        assert_eq!(polling.deadline(), Duration::MAX);
+
+        // A huge factor jumps straight to the cap without overflowing
+        let polling = PollingConfig::from_settings(&serde_json::json!({
+            "poll_backoff_factor": 1e300,
+            "max_poll_attempts": 10,
+        }));
+        assert_eq!(
+            polling.delay_for(5),
+            Duration::from_millis(MAX_POLL_INTERVAL_MS)
+        );
+        assert!(polling.deadline() <= Duration::from_millis(MAX_POLL_INTERVAL_MS) * 10);
+    }
+
     #[test]
     fn test_assemblyai_service_not_configured() {
         let service = AssemblyAIService::new("".to_string());
         assert!(!service.is_configured());
     }
+
+    #[test]
+    fn test_request_pii_redaction() {
+        let config = TranscriptionConfig {
+            enable_pii_redaction: true,
+            ..Default::default()
+        };
+        let body = serde_json::to_value(TranscriptionRequest::from_config("url", &config)).unwrap();
+        assert_eq!(body["redact_pii"], true);
+        assert_eq!(
+            body["redact_pii_policies"].as_array().unwrap().len(),
+            DEFAULT_PII_POLICIES.len()
+        );
+
+        let config = TranscriptionConfig {
+            enable_pii_redaction: true,
+            pii_policies: Some(vec!["email_address".to_string()]),
+            ..Default::default()
+        };
+        let body = serde_json::to_value(TranscriptionRequest::from_config("url", &config)).unwrap();
+        assert_eq!(
+            body["redact_pii_policies"],
+            serde_json::json!(["email_address"])
+        );
+
+        // AssemblyAI rejects redaction without policies
+        let config = TranscriptionConfig {
+            enable_pii_redaction: true,
+            pii_policies: Some(Vec::new()),
+            ..Default::default()
+        };
+        let body = serde_json::to_value(TranscriptionRequest::from_config("url", &config)).unwrap();
+        assert_eq!(
+            body["redact_pii_policies"].as_array().unwrap().len(),
+            DEFAULT_PII_POLICIES.len()
+        );
+
+        let body = serde_json::to_value(TranscriptionRequest::from_config(
+            "url",
+            &TranscriptionConfig::default(),
+        ))
+        .unwrap();
+        assert!(body.get("redact_pii").is_none());
+        assert!(body.get("redact_pii_policies").is_none());
+    }
+
+    #[test]
+    fn test_request_language_detection() {
+        let body = serde_json::to_value(TranscriptionRequest::from_config(
+            "url",
+            &TranscriptionConfig::default(),
+        ))
+        .unwrap();
+        assert_eq!(body["language_code"], "en");
+        assert!(body.get("language_detection").is_none());
+
+        let config = TranscriptionConfig {
+            language: None,
+            ..Default::default()
+        };
+        let body = serde_json::to_value(TranscriptionRequest::from_config("url", &config)).unwrap();
+        assert!(body.get("language_code").is_none());
+        assert_eq!(body["language_detection"], true);
+    }
+
+    #[test]
+    fn test_parse_utterance_languages() {
+        let service = AssemblyAIService::new("test_api_key".to_string());
+        let response: TranscriptResponse = serde_json::from_str(
+            r#"{
+                "id": "transcript-1",
+                "status": "completed",
+                "text": "Good morning. Buenos días.",
+                "confidence": 0.9,
+                "language_code": "en",
+                "utterances": [
+                    { "text": "Good morning.", "start": 0, "end": 900, "confidence": 0.92, "speaker": "A", "language_code": "en" },
+                    { "text": "Buenos días.", "start": 1200, "end": 2100, "confidence": 0.88, "speaker": "B", "language_code": "es" },
+                    { "text": "Okay.", "start": 2500, "end": 2800, "confidence": 0.8, "speaker": "A" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let result = service.parse_transcript_response(response).unwrap();
+        let languages: Vec<_> = result
+            .segments
+            .iter()
+            .map(|s| s.language.as_deref())
+            .collect();
+        assert_eq!(languages, vec![Some("en"), Some("es"), None]);
+        assert_eq!(result.detected_language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_request_multichannel() {
+        let config = TranscriptionConfig {
+            multichannel: true,
+            num_speakers: Some(3),
+            ..Default::default()
+        };
+        let body = serde_json::to_value(TranscriptionRequest::from_config("url", &config)).unwrap();
+        assert_eq!(body["multichannel"], true);
+        // Channels stand in for diarized speakers
+        assert!(body.get("speaker_labels").is_none());
+        assert!(body.get("speakers_expected").is_none());
+
+        let body = serde_json::to_value(TranscriptionRequest::from_config(
+            "url",
+            &TranscriptionConfig::default(),
+        ))
+        .unwrap();
+        assert!(body.get("multichannel").is_none());
+        assert_eq!(body["speaker_labels"], true);
+    }
+
+    #[test]
+    fn test_parse_multichannel_utterances() {
+        let service = AssemblyAIService::new("test_api_key".to_string());
+        let response: TranscriptResponse = serde_json::from_str(
+            r#"{
+                "id": "transcript-2",
+                "status": "completed",
+                "text": "Can you hear me? Yes, loud and clear.",
+                "utterances": [
+                    { "text": "Can you hear me?", "start": 0, "end": 1000, "confidence": 0.9, "speaker": "1", "channel": "1" },
+                    { "text": "Yes, loud and clear.", "start": 1400, "end": 2600, "confidence": 0.9, "speaker": "2", "channel": "2" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let result = service.parse_transcript_response(response).unwrap();
+        let labels: Vec<_> = result
+            .segments
+            .iter()
+            .map(|s| s.speaker_label.as_deref())
+            .collect();
+        assert_eq!(labels, vec![Some("Channel 1"), Some("Channel 2")]);
+    }
 }
@@ -5,33 +5,150 @@
 
 use crate::error::{AppError, Result};
 use crate::ports::transcription::{
-    StreamingSession, StreamingTranscriptionCallback, TranscriptionConfig, TranscriptionResult,
-    TranscriptionSegment, TranscriptionServicePort,
+    ModelInfo, StreamingSession, StreamingTranscriptionCallback, TranscriptionConfig,
+    TranscriptionResult, TranscriptionSegment, TranscriptionServicePort,
 };
+use crate::utils::http::{check_api_key, shared_client};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::time::Duration;
-use tokio::fs::File;
-use tokio::io::AsyncReadExt;
+
+use super::{
+    audio_content_type, audio_file_content_type, channel_speaker_label, dominant_language,
+    filter_low_confidence, normalize_speaker_labels, open_audio_upload, redaction_policies,
+    save_raw_response, split_into_sentences, UPLOAD_AUDIO_FORMATS,
+};
+use crate::utils::wav::read_wav_header;
 
 const DEEPGRAM_API_BASE: &str = "https://api.deepgram.com/v1";
 
+/// Deepgram rejects pre-recorded audio files over 2 GB
+const MAX_UPLOAD_BYTES: u64 = 2_000_000_000;
+
+/// Query parameters for Deepgram's optional features, read from `additional_settings`
+///
+/// - `smart_format` (bool): format numbers, dates, currency and so on
+/// - `keyterms` (list of strings): boost recognition of names and jargon; Nova-3 only
+///
+/// Used by both the batch and the live `/listen` URLs. Values are URL-encoded.
+pub(super) fn feature_params(config: &TranscriptionConfig) -> Vec<(&'static str, String)> {
+    let Some(settings) = config.additional_settings.as_ref() else {
+        return Vec::new();
+    };
+
+    let mut params = Vec::new();
+    if let Some(smart_format) = settings.get("smart_format").and_then(|s| s.as_bool()) {
+        params.push(("smart_format", smart_format.to_string()));
+    }
+    if let Some(keyterms) = settings.get("keyterms").and_then(|k| k.as_array()) {
+        params.extend(
+            keyterms
+                .iter()
+                .filter_map(|term| term.as_str())
+                .map(str::trim)
+                .filter(|term| !term.is_empty())
+                .map(|term| ("keyterm", encode_query_value(term))),
+        );
+    }
+    params
+}
+
+/// Deepgram's `redact` value for one of the app's PII categories
+///
+/// Deepgram redacts by broad class rather than by entity: `pii` covers names, emails
+/// and phone numbers, `ssn` social security numbers and `pci` card numbers. Deepgram's
+/// own values are accepted as they are.
+fn deepgram_redaction(category: &str) -> Option<&str> {
+    match category {
+        "person_name" | "email_address" | "phone_number" | "pii" => Some("pii"),
+        "us_social_security_number" | "ssn" => Some("ssn"),
+        "credit_card_number" | "pci" => Some("pci"),
+        "numbers" => Some("numbers"),
+        _ => None,
+    }
+}
+
+/// `redact` query parameters for the configured PII redaction
+///
+/// Used by both the batch and the live `/listen` URLs.
+pub(super) fn redact_params(config: &TranscriptionConfig) -> Vec<(&'static str, String)> {
+    redaction_policies(config, deepgram_redaction, &["pii"])
+        .unwrap_or_default()
+        .into_iter()
+        .map(|name| ("redact", name))
+        .collect()
+}
+
+/// Percent-encode a query parameter value
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Reject a model that Deepgram only offers for batch transcription
+///
+/// Models missing from `models` (e.g. non-English ones, which `list_models` leaves out)
+/// are allowed; Deepgram reports the error itself if they can't stream.
+pub(super) fn check_streaming_model(model: &str, models: &[DeepgramModel]) -> Result<()> {
+    match models
+        .iter()
+        .find(|m| m.canonical_name == model || m.name == model)
+    {
+        Some(found) if !found.streaming => Err(AppError::InvalidInput(format!(
+            "Deepgram model {} only supports batch transcription; choose a streaming model for live transcription",
+            model
+        ))),
+        _ => Ok(()),
+    }
+}
+
 /// Deepgram service implementation
 pub struct DeepgramService {
     client: Client,
     api_key: String,
+    api_base: String,
 }
 
 impl DeepgramService {
     /// Create a new Deepgram service with the given API key
     pub fn new(api_key: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(300)) // Longer timeout for large files
-            .build()
-            .expect("Failed to create HTTP client");
+        let client = shared_client(Duration::from_secs(300)); // Longer timeout for large files
 
-        Self { client, api_key }
+        Self {
+            client,
+            api_key,
+            api_base: DEEPGRAM_API_BASE.to_string(),
+        }
+    }
+
+    /// Point the service at a different API host (used by tests)
+    #[cfg(test)]
+    fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.to_string();
+        self
+    }
+
+    /// Check that the API key is accepted by listing the account's projects
+    ///
+    /// The models endpoint doesn't require authentication, so it can't be used for this.
+    pub async fn verify_api_key(&self) -> Result<()> {
+        check_api_key(
+            self.client
+                .get(format!("{}/projects", self.api_base))
+                .header("authorization", format!("Token {}", self.api_key)),
+            "Deepgram",
+            AppError::Transcription,
+        )
+        .await
     }
 
     /// Fetch available models from Deepgram API
@@ -39,7 +156,7 @@ impl DeepgramService {
     pub async fn list_models(&self) -> Result<Vec<DeepgramModel>> {
         log::info!("Fetching Deepgram models from API (English only, exclude outdated)");
 
-        let url = format!("{}/models?include_outdated=false", DEEPGRAM_API_BASE);
+        let url = format!("{}/models?include_outdated=false", self.api_base);
 
         let response = self
             .client
@@ -79,93 +196,88 @@ impl DeepgramService {
             .collect())
     }
 
-    /// Transcribe audio file with diarization
-    async fn transcribe_with_diarization(
-        &self,
-        audio_path: &str,
-        config: &TranscriptionConfig,
-    ) -> Result<TranscriptionResult> {
-        log::info!("Transcribing with Deepgram: {}", audio_path);
-
-        // Read the audio file
-        let mut file = File::open(audio_path)
-            .await
-            .map_err(|e| AppError::Transcription(format!("Failed to open audio file: {}", e)))?;
-
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .await
-            .map_err(|e| AppError::Transcription(format!("Failed to read audio file: {}", e)))?;
-
-        // Log WAV file details
-        if buffer.len() > 44 {
-            // WAV header is 44 bytes - check if this looks like a valid WAV
-            let is_wav = &buffer[0..4] == b"RIFF" && &buffer[8..12] == b"WAVE";
-            println!(
-                ">>> WAV file check: is_valid_wav={}, total_bytes={}",
-                is_wav,
-                buffer.len()
-            );
-
-            if is_wav {
-                // Parse basic WAV info
-                let audio_format = u16::from_le_bytes([buffer[20], buffer[21]]);
-                let num_channels = u16::from_le_bytes([buffer[22], buffer[23]]);
-                let sample_rate =
-                    u32::from_le_bytes([buffer[24], buffer[25], buffer[26], buffer[27]]);
-                let bits_per_sample = u16::from_le_bytes([buffer[34], buffer[35]]);
-
-                println!(">>> WAV format: audio_format={}, channels={}, sample_rate={}, bits_per_sample={}",
-                    audio_format, num_channels, sample_rate, bits_per_sample);
-            } else {
-                println!("!!! WARNING: File doesn't have valid WAV header!");
-            }
-        }
-
-        // Build query parameters
-        let mut url = format!("{}/listen", DEEPGRAM_API_BASE);
-
+    /// Build the `/listen` URL with query parameters for a transcription config
+    fn listen_url(&self, config: &TranscriptionConfig) -> String {
         // Use model from config, or default to nova-2-meeting
         let model = config.model.as_deref().unwrap_or("nova-2-meeting");
 
         let mut params = vec![
-            ("model", model),
-            ("punctuate", "true"),
-            (
-                "diarize",
-                if config.enable_diarization {
-                    "true"
-                } else {
-                    "false"
-                },
-            ),
-            ("utterances", "true"),
+            ("model", model.to_string()),
+            ("punctuate", "true".to_string()),
+            ("diarize", config.enable_diarization.to_string()),
+            ("utterances", "true".to_string()),
         ];
 
-        if let Some(lang) = &config.language {
-            params.push(("language", lang));
+        // Expected speaker count, a hint for the diarizer
+        if config.enable_diarization {
+            if let Some(num_speakers) = config.num_speakers {
+                params.push(("num_speakers", num_speakers.to_string()));
+            }
+        }
+
+        if config.multichannel {
+            params.push(("multichannel", "true".to_string()));
         }
 
+        // No language means auto-detect
+        match &config.language {
+            Some(lang) => params.push(("language", lang.clone())),
+            None => params.push(("detect_language", "true".to_string())),
+        }
+
+        params.extend(redact_params(config));
+        params.extend(feature_params(config));
+
         let query_string = params
             .iter()
             .map(|(k, v)| format!("{}={}", k, v))
             .collect::<Vec<_>>()
             .join("&");
 
-        url = format!("{}?{}", url, query_string);
+        format!("{}/listen?{}", self.api_base, query_string)
+    }
+
+    /// Transcribe audio file with diarization
+    async fn transcribe_with_diarization(
+        &self,
+        audio_path: &str,
+        config: &TranscriptionConfig,
+    ) -> Result<TranscriptionResult> {
+        log::info!("Transcribing with Deepgram: {}", audio_path);
+
+        // Stream the file from disk; only the header is read up front
+        let upload = open_audio_upload(audio_path).await?;
+
+        // Log WAV file details
+        let content_type = audio_file_content_type(audio_path);
+        if content_type == "audio/wav" {
+            match read_wav_header(&upload.header) {
+                Ok(wav) => log::debug!(
+                    "WAV format: audio_format={}, channels={}, sample_rate={}, bits_per_sample={}, total_bytes={}",
+                    wav.audio_format, wav.channels, wav.sample_rate, wav.bits_per_sample, upload.size
+                ),
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+
+        let url = self.listen_url(config);
 
-        println!(">>> Sending request to Deepgram API: {}", url);
-        println!(">>> Audio file size: {} bytes", buffer.len());
-        log::info!("Sending request to Deepgram API: {}", url);
-        log::info!("Audio file size: {} bytes", buffer.len());
+        // The query string carries request options, so the full URL is only logged at trace level
+        log::debug!(
+            "Sending {} bytes to Deepgram API at {}/listen",
+            upload.size,
+            self.api_base
+        );
+        log::trace!("Deepgram request URL: {}", url);
 
         // Send request
         let response = self
             .client
             .post(&url)
             .header("authorization", format!("Token {}", self.api_key))
-            .header("content-type", "audio/wav")
-            .body(buffer)
+            .header("content-type", content_type)
+            .header("content-length", upload.size)
+            .body(upload.body)
             .send()
             .await
             .map_err(|e| {
@@ -174,62 +286,150 @@ impl DeepgramService {
             })?;
 
         let status = response.status();
-        println!(">>> Deepgram API response status: {}", status);
-        log::info!("Deepgram API response status: {}", status);
+        log::debug!("Deepgram API response status: {}", status);
 
         if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            println!("!!! Deepgram API error ({}): {}", status, error_text);
-            log::error!("Deepgram API error response: {}", error_text);
+            log::error!("Deepgram API error ({}): {}", status, error_text);
             return Err(AppError::Transcription(format!(
                 "Deepgram API error ({}): {}",
                 status, error_text
             )));
         }
 
-        let deepgram_response: DeepgramResponse = response.json().await.map_err(|e| {
-            println!("!!! Failed to parse Deepgram JSON response: {}", e);
+        let body = response.text().await.map_err(|e| {
+            AppError::Transcription(format!("Failed to read Deepgram response: {}", e))
+        })?;
+        save_raw_response(config.raw_response_path.as_deref(), &body).await;
+
+        let deepgram_response: DeepgramResponse = serde_json::from_str(&body).map_err(|e| {
             log::error!("Failed to parse Deepgram JSON response: {}", e);
             AppError::Transcription(format!("Failed to parse Deepgram response: {}", e))
         })?;
 
-        println!(">>> Successfully parsed Deepgram JSON response");
-        println!(">>> Channels: {}", deepgram_response.results.channels.len());
+        log::trace!(
+            "Parsed Deepgram response with {} channels",
+            deepgram_response.results.channels.len()
+        );
 
-        let result = self.parse_deepgram_response(deepgram_response)?;
-        println!(">>> Parsed into {} segments", result.segments.len());
-        println!(">>> Transcript text length: {} chars", result.text.len());
+        let result =
+            self.parse_deepgram_response(deepgram_response, config.sentence_segmentation)?;
+        log::debug!(
+            "Deepgram returned {} segments, {} chars of text",
+            result.segments.len(),
+            result.text.len()
+        );
 
         Ok(result)
     }
 
     /// Parse Deepgram response into our TranscriptionResult format
-    fn parse_deepgram_response(&self, response: DeepgramResponse) -> Result<TranscriptionResult> {
-        let channel = response.results.channels.get(0).ok_or_else(|| {
-            AppError::Transcription("No channels in Deepgram response".to_string())
-        })?;
-
-        let alternative = channel.alternatives.get(0).ok_or_else(|| {
-            AppError::Transcription("No alternatives in Deepgram response".to_string())
-        })?;
+    ///
+    /// Multichannel responses contain one entry per audio channel. Segments from each
+    /// channel are labeled by channel (see `channel_speaker_label`) and merged in time order.
+    ///
+    /// With `sentence_segmentation`, a transcript without utterances or words is split
+    /// into sentences instead of kept as one segment.
+    fn parse_deepgram_response(
+        &self,
+        response: DeepgramResponse,
+        sentence_segmentation: bool,
+    ) -> Result<TranscriptionResult> {
+        let channels = &response.results.channels;
+        if channels.is_empty() {
+            return Err(AppError::Transcription(
+                "No channels in Deepgram response".to_string(),
+            ));
+        }
 
-        let text = alternative.transcript.clone();
-        let confidence = Some(alternative.confidence);
+        let multichannel = channels.len() > 1;
+        let detected_language = channels[0]
+            .detected_language
+            .clone()
+            .or_else(|| response.metadata.detected_language.clone());
 
-        println!(">>> Transcript text from Deepgram: {} chars", text.len());
-        println!(">>> Has utterances: {}", alternative.utterances.is_some());
-        println!(">>> Has words: {}", alternative.words.is_some());
+        if let Some(ref lang) = detected_language {
+            log::info!("Deepgram detected language: {}", lang);
+        }
 
-        if let Some(ref utterances) = alternative.utterances {
-            println!(">>> Utterances count: {}", utterances.len());
+        let model_info = response.metadata.primary_model();
+        if let Some(ref request_id) = response.metadata.request_id {
+            log::info!(
+                "Deepgram request {} (model: {})",
+                request_id,
+                model_info.as_ref().map_or("unknown", |m| m.name.as_str())
+            );
         }
-        if let Some(ref words) = alternative.words {
-            println!(">>> Words count: {}", words.len());
+
+        let mut segments = Vec::new();
+        let mut transcripts = Vec::new();
+        let mut confidence_sum = 0.0;
+
+        for (index, channel) in channels.iter().enumerate() {
+            let alternative = channel.alternatives.first().ok_or_else(|| {
+                AppError::Transcription("No alternatives in Deepgram response".to_string())
+            })?;
+
+            let mut channel_segments = Self::parse_alternative_segments(
+                alternative,
+                response.metadata.duration,
+                sentence_segmentation,
+            );
+
+            if multichannel {
+                let label = channel_speaker_label(index);
+                for segment in &mut channel_segments {
+                    segment.speaker_label = Some(label.clone());
+                }
+            }
+
+            transcripts.push(alternative.transcript.clone());
+            confidence_sum += alternative.confidence;
+            segments.extend(channel_segments);
         }
 
+        let text = if multichannel {
+            // Interleave channels in time order
+            segments.sort_by_key(|segment| segment.start_ms);
+            segments
+                .iter()
+                .map(|segment| segment.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            transcripts.remove(0)
+        };
+        let confidence = Some(confidence_sum / channels.len() as f32);
+
+        Ok(TranscriptionResult {
+            text,
+            segments,
+            confidence,
+            detected_language,
+            model_info,
+            request_id: response.metadata.request_id,
+        })
+    }
+
+    /// Build segments for a single channel alternative
+    ///
+    /// Prefers utterances, then words grouped by speaker, then a single segment
+    /// spanning the whole recording, split into sentences with `sentence_segmentation`.
+    fn parse_alternative_segments(
+        alternative: &Alternative,
+        duration: f64,
+        sentence_segmentation: bool,
+    ) -> Vec<TranscriptionSegment> {
+        log::trace!(
+            "Deepgram alternative: {} chars, utterances={:?}, words={:?}",
+            alternative.transcript.len(),
+            alternative.utterances.as_ref().map(Vec::len),
+            alternative.words.as_ref().map(Vec::len)
+        );
+
         // Parse utterances with speaker labels
-        let segments = if let Some(utterances) = &alternative.utterances {
-            println!(">>> Using utterances for segments");
+        if let Some(utterances) = &alternative.utterances {
+            log::trace!("Using utterances for segments");
             utterances
                 .iter()
                 .map(|utt| TranscriptionSegment {
@@ -238,10 +438,11 @@ impl DeepgramService {
                     end_ms: (utt.end * 1000.0) as i64,
                     speaker_label: Some(format!("Speaker {}", utt.speaker)),
                     confidence: Some(utt.confidence),
+                    language: utt.language(),
                 })
                 .collect()
         } else if let Some(words) = &alternative.words {
-            println!(">>> Using words fallback for segments");
+            log::trace!("Using words fallback for segments");
             // Fallback: group words by speaker if utterances not available
             let mut segments = Vec::new();
             let mut current_speaker = None;
@@ -250,6 +451,7 @@ impl DeepgramService {
             let mut current_end = 0i64;
             let mut word_count = 0;
             let mut confidence_sum = 0.0;
+            let mut current_languages = Vec::new();
 
             for word in words {
                 let word_speaker = word.speaker.unwrap_or(0);
@@ -267,6 +469,7 @@ impl DeepgramService {
                             } else {
                                 None
                             },
+                            language: dominant_language(current_languages.drain(..)),
                         });
                     }
 
@@ -277,6 +480,7 @@ impl DeepgramService {
                     current_end = (word.end * 1000.0) as i64;
                     word_count = 1;
                     confidence_sum = word.confidence;
+                    current_languages.push(word.language.as_deref());
                 } else {
                     // Same speaker - append word
                     current_text.push(' ');
@@ -284,6 +488,7 @@ impl DeepgramService {
                     current_end = (word.end * 1000.0) as i64;
                     word_count += 1;
                     confidence_sum += word.confidence;
+                    current_languages.push(word.language.as_deref());
                 }
             }
 
@@ -299,34 +504,36 @@ impl DeepgramService {
                     } else {
                         None
                     },
+                    language: dominant_language(current_languages),
                 });
             }
 
             segments
         } else {
-            println!(">>> No utterances or words - using fallback single segment");
+            log::trace!("No utterances or words - using fallback single segment");
             // No diarization - single segment
-            if text.is_empty() {
-                println!("!!! WARNING: Transcript text is empty!");
+            if alternative.transcript.is_empty() {
+                log::warn!("Deepgram returned an empty transcript");
                 vec![]
             } else {
-                vec![TranscriptionSegment {
-                    text: text.clone(),
+                let segment = TranscriptionSegment {
+                    text: alternative.transcript.clone(),
                     start_ms: 0,
-                    end_ms: (response.metadata.duration * 1000.0) as i64,
+                    end_ms: (duration * 1000.0) as i64,
                     speaker_label: None,
-                    confidence,
-                }]
+                    confidence: Some(alternative.confidence),
+                    language: alternative
+                        .languages
+                        .as_ref()
+                        .and_then(|languages| languages.first().cloned()),
+                };
+                if sentence_segmentation {
+                    split_into_sentences(segment)
+                } else {
+                    vec![segment]
+                }
             }
-        };
-
-        println!(">>> Final segments count: {}", segments.len());
-
-        Ok(TranscriptionResult {
-            text,
-            segments,
-            confidence,
-        })
+        }
     }
 }
 
@@ -339,7 +546,8 @@ impl TranscriptionServicePort for DeepgramService {
     ) -> Result<TranscriptionResult> {
         log::info!("Starting Deepgram transcription for: {}", audio_path);
 
-        let result = self.transcribe_with_diarization(audio_path, config).await?;
+        let mut result = self.transcribe_with_diarization(audio_path, config).await?;
+        filter_low_confidence(&mut result, config);
 
         log::info!(
             "Deepgram transcription complete: {} segments, {} chars",
@@ -362,45 +570,9 @@ impl TranscriptionServicePort for DeepgramService {
             format
         );
 
-        // Build query parameters
-        let mut url = format!("{}/listen", DEEPGRAM_API_BASE);
-
-        // Use model from config, or default to nova-2-meeting
-        let model = config.model.as_deref().unwrap_or("nova-2-meeting");
-
-        let mut params = vec![
-            ("model", model),
-            ("punctuate", "true"),
-            (
-                "diarize",
-                if config.enable_diarization {
-                    "true"
-                } else {
-                    "false"
-                },
-            ),
-            ("utterances", "true"),
-        ];
-
-        if let Some(lang) = &config.language {
-            params.push(("language", lang));
-        }
-
-        let query_string = params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("&");
-
-        url = format!("{}?{}", url, query_string);
+        let url = self.listen_url(config);
 
-        // Determine content type
-        let content_type = match format {
-            "wav" => "audio/wav",
-            "mp3" => "audio/mpeg",
-            "flac" => "audio/flac",
-            _ => "audio/wav", // Default
-        };
+        let content_type = audio_content_type(format);
 
         // Send request
         let response = self
@@ -422,11 +594,20 @@ impl TranscriptionServicePort for DeepgramService {
             )));
         }
 
-        let deepgram_response: DeepgramResponse = response.json().await.map_err(|e| {
+        let body = response.text().await.map_err(|e| {
+            AppError::Transcription(format!("Failed to read Deepgram response: {}", e))
+        })?;
+        save_raw_response(config.raw_response_path.as_deref(), &body).await;
+
+        let deepgram_response: DeepgramResponse = serde_json::from_str(&body).map_err(|e| {
             AppError::Transcription(format!("Failed to parse Deepgram response: {}", e))
         })?;
 
-        self.parse_deepgram_response(deepgram_response)
+        let mut result =
+            self.parse_deepgram_response(deepgram_response, config.sentence_segmentation)?;
+        filter_low_confidence(&mut result, config);
+        normalize_speaker_labels(&mut result, config);
+        Ok(result)
     }
 
     async fn start_streaming(
@@ -439,6 +620,14 @@ impl TranscriptionServicePort for DeepgramService {
         // Import the streaming module
         use super::deepgram_streaming::DeepgramStreamingSession;
 
+        if let Some(model) = &config.model {
+            match self.list_models().await {
+                Ok(models) => check_streaming_model(model, &models)?,
+                // Let Deepgram decide rather than block streaming on the models endpoint
+                Err(e) => log::warn!("Could not check that {} supports streaming: {}", model, e),
+            }
+        }
+
         let session = DeepgramStreamingSession::new(self.api_key.clone(), config, callback).await?;
 
         Ok(Box::new(session))
@@ -455,6 +644,26 @@ impl TranscriptionServicePort for DeepgramService {
     fn supports_streaming(&self) -> bool {
         true // Deepgram supports streaming
     }
+
+    fn supports_pii_redaction(&self) -> bool {
+        true
+    }
+
+    fn supports_diarization(&self) -> bool {
+        true
+    }
+
+    fn supports_language_detection(&self) -> bool {
+        true
+    }
+
+    fn supported_audio_formats(&self) -> &'static [&'static str] {
+        UPLOAD_AUDIO_FORMATS
+    }
+
+    fn max_upload_bytes(&self) -> Option<u64> {
+        Some(MAX_UPLOAD_BYTES)
+    }
 }
 
 // ===== API Response Types =====
@@ -490,7 +699,38 @@ struct DeepgramResponse {
 
 #[derive(Debug, Deserialize)]
 struct Metadata {
+    #[serde(default)]
     duration: f64,
+    request_id: Option<String>,
+    /// Some responses report the language here rather than per channel
+    detected_language: Option<String>,
+    /// IDs of the models used, keys into `model_info`
+    #[serde(default)]
+    models: Vec<String>,
+    #[serde(default)]
+    model_info: HashMap<String, DeepgramModelInfo>,
+}
+
+impl Metadata {
+    /// Details of the first model listed for the request
+    fn primary_model(&self) -> Option<ModelInfo> {
+        let info = match self.models.first() {
+            Some(id) => self.model_info.get(id),
+            None => self.model_info.values().next(),
+        }?;
+        Some(ModelInfo {
+            name: info.name.clone()?,
+            version: info.version.clone(),
+            arch: info.arch.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramModelInfo {
+    name: Option<String>,
+    version: Option<String>,
+    arch: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -501,6 +741,8 @@ struct Results {
 #[derive(Debug, Deserialize)]
 struct Channel {
     alternatives: Vec<Alternative>,
+    /// Only present when `detect_language=true` was requested
+    detected_language: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -509,6 +751,8 @@ struct Alternative {
     confidence: f32,
     words: Option<Vec<Word>>,
     utterances: Option<Vec<Utterance>>,
+    /// Languages heard, most common first; only reported for multilingual requests
+    languages: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -518,6 +762,8 @@ struct Word {
     end: f64,
     confidence: f32,
     speaker: Option<u32>,
+    /// Only reported for multilingual (code-switching) requests
+    language: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -527,6 +773,25 @@ struct Utterance {
     end: f64,
     confidence: f32,
     speaker: u32,
+    languages: Option<Vec<String>>,
+    words: Option<Vec<Word>>,
+}
+
+impl Utterance {
+    /// The utterance's reported language, else the most common among its words
+    fn language(&self) -> Option<String> {
+        self.languages
+            .as_ref()
+            .and_then(|languages| languages.first().cloned())
+            .or_else(|| {
+                dominant_language(
+                    self.words
+                        .iter()
+                        .flatten()
+                        .map(|word| word.language.as_deref()),
+                )
+            })
+    }
 }
 
 #[cfg(test)]
@@ -545,4 +810,438 @@ mod tests {
         let service = DeepgramService::new("".to_string());
         assert!(!service.is_configured());
     }
+
+    #[test]
+    fn test_listen_url_includes_expected_speakers() {
+        let service = DeepgramService::new("key".to_string());
+
+        let url = service.listen_url(&TranscriptionConfig::default());
+        assert!(url.starts_with("https://api.deepgram.com/v1/listen?model=nova-2-meeting&"));
+        assert!(url.contains("diarize=true"));
+        assert!(!url.contains("num_speakers"));
+
+        let mut config = TranscriptionConfig {
+            num_speakers: Some(3),
+            ..Default::default()
+        };
+        assert!(service.listen_url(&config).contains("&num_speakers=3&"));
+
+        // The count only matters to the diarizer
+        config.enable_diarization = false;
+        let url = service.listen_url(&config);
+        assert!(url.contains("diarize=false"));
+        assert!(!url.contains("num_speakers"));
+    }
+
+    #[test]
+    fn test_listen_url_includes_feature_settings() {
+        let service = DeepgramService::new("key".to_string());
+        let config = TranscriptionConfig {
+            model: Some("nova-3".to_string()),
+            additional_settings: Some(serde_json::json!({
+                "smart_format": false,
+                "keyterms": ["Q3 OKRs", "Zürich"],
+            })),
+            ..Default::default()
+        };
+
+        let url = service.listen_url(&config);
+        assert!(url.contains("&smart_format=false"));
+        assert!(url.contains("&keyterm=Q3%20OKRs&keyterm=Z%C3%BCrich"));
+    }
+
+    #[test]
+    fn test_listen_url_maps_pii_categories() {
+        let service = DeepgramService::new("key".to_string());
+        let mut config = TranscriptionConfig {
+            enable_pii_redaction: true,
+            ..Default::default()
+        };
+        let url = service.listen_url(&config);
+        assert!(url.contains("&redact=pii"));
+        assert_eq!(url.matches("redact=").count(), 1);
+
+        // App categories become Deepgram's classes, each requested once
+        config.pii_policies = Some(
+            [
+                "person_name",
+                "email_address",
+                "us_social_security_number",
+                "credit_card_number",
+                "date_of_birth",
+            ]
+            .iter()
+            .map(|c| c.to_string())
+            .collect(),
+        );
+        let url = service.listen_url(&config);
+        assert!(url.contains("&redact=pii&redact=ssn&redact=pci"));
+        assert_eq!(url.matches("redact=").count(), 3);
+        assert!(!url.contains("email_address"));
+
+        // Nothing Deepgram can redact falls back to its default class
+        config.pii_policies = Some(vec!["driver_license".to_string()]);
+        let url = service.listen_url(&config);
+        assert!(url.contains("&redact=pii"));
+        assert_eq!(url.matches("redact=").count(), 1);
+
+        config.enable_pii_redaction = false;
+        assert!(!service.listen_url(&config).contains("redact="));
+    }
+
+    #[test]
+    fn test_check_streaming_model() {
+        let model = |name: &str, streaming: bool| DeepgramModel {
+            name: name.to_string(),
+            canonical_name: name.to_string(),
+            architecture: "nova-3".to_string(),
+            languages: vec!["en".to_string()],
+            version: "2024-12-20.0".to_string(),
+            uuid: String::new(),
+            batch: true,
+            streaming,
+            formatted_output: true,
+        };
+        let models = [model("nova-3", true), model("nova-3-batch-only", false)];
+
+        assert!(check_streaming_model("nova-3", &models).is_ok());
+        let err = check_streaming_model("nova-3-batch-only", &models).unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)));
+        assert!(err.to_string().contains("only supports batch"));
+        // Unlisted models are left to Deepgram
+        assert!(check_streaming_model("whisper-large", &models).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_streams_large_file() {
+        use std::io::Write;
+
+        let (api_base, server) = super::super::test_server::serve_once(
+            r#"{
+                "metadata": { "duration": 1.0 },
+                "results": {
+                    "channels": [{
+                        "alternatives": [{ "transcript": "hello", "confidence": 0.9 }]
+                    }]
+                }
+            }"#,
+        )
+        .await;
+
+        // 64 MiB of audio written in 1 MiB chunks
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let chunk = vec![0u8; 1024 * 1024];
+        for _ in 0..64 {
+            file.write_all(&chunk).unwrap();
+        }
+        let size = 64 * chunk.len() as u64;
+
+        let service = DeepgramService::new("test_api_key".to_string()).with_api_base(&api_base);
+        let result = service
+            .transcribe_file(
+                file.path().to_str().unwrap(),
+                &TranscriptionConfig::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.text, "hello");
+
+        let request = server.await.unwrap();
+        assert!(request.head.starts_with("post /listen?"));
+        assert!(request.head.contains(&format!("content-length: {}", size)));
+        assert_eq!(request.body_len, size);
+    }
+
+    #[tokio::test]
+    async fn test_raw_response_saved_in_debug_mode() {
+        let body = r#"{
+                "metadata": { "duration": 1.0 },
+                "results": {
+                    "channels": [{
+                        "alternatives": [{ "transcript": "hello", "confidence": 0.9 }]
+                    }]
+                }
+            }"#;
+        let (api_base, server) = super::super::test_server::serve_once(body).await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let raw_path = dir.path().join("meeting_1_deepgram.json");
+        let config = TranscriptionConfig {
+            raw_response_path: Some(raw_path.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+
+        let service = DeepgramService::new("test_api_key".to_string()).with_api_base(&api_base);
+        let result = service
+            .transcribe_bytes(b"RIFF", "wav", &config)
+            .await
+            .unwrap();
+        assert_eq!(result.text, "hello");
+        server.await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&raw_path).unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn test_opus_bytes_sent_as_ogg() {
+        let body = r#"{
+                "metadata": { "duration": 1.0 },
+                "results": {
+                    "channels": [{
+                        "alternatives": [{ "transcript": "hello", "confidence": 0.9 }]
+                    }]
+                }
+            }"#;
+        let (api_base, server) = super::super::test_server::serve_once(body).await;
+
+        let service = DeepgramService::new("test_api_key".to_string()).with_api_base(&api_base);
+        service
+            .transcribe_bytes(b"OggS", "opus", &TranscriptionConfig::default())
+            .await
+            .unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.head.contains("content-type: audio/ogg"));
+        assert_eq!(request.body_len, 4);
+    }
+
+    #[test]
+    fn test_parse_detected_language() {
+        let service = DeepgramService::new("test_api_key".to_string());
+        let response: DeepgramResponse = serde_json::from_str(
+            r#"{
+                "metadata": { "duration": 2.5 },
+                "results": {
+                    "channels": [{
+                        "detected_language": "es",
+                        "alternatives": [{
+                            "transcript": "hola a todos",
+                            "confidence": 0.97
+                        }]
+                    }]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let result = service.parse_deepgram_response(response, false).unwrap();
+        assert_eq!(result.detected_language.as_deref(), Some("es"));
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.segments[0].end_ms, 2500);
+    }
+
+    #[test]
+    fn test_parse_segment_languages() {
+        let service = DeepgramService::new("test_api_key".to_string());
+        let response: DeepgramResponse = serde_json::from_str(
+            r#"{
+                "metadata": { "duration": 4.0 },
+                "results": {
+                    "channels": [{
+                        "alternatives": [{
+                            "transcript": "let's start empecemos ya",
+                            "confidence": 0.93,
+                            "languages": ["en", "es"],
+                            "words": [
+                                { "word": "let's", "start": 0.0, "end": 0.4, "confidence": 0.95, "speaker": 0, "language": "en" },
+                                { "word": "start", "start": 0.4, "end": 0.9, "confidence": 0.94, "speaker": 0, "language": "en" },
+                                { "word": "empecemos", "start": 1.5, "end": 2.2, "confidence": 0.92, "speaker": 1, "language": "es" },
+                                { "word": "ya", "start": 2.2, "end": 2.5, "confidence": 0.91, "speaker": 1, "language": "es" }
+                            ],
+                            "utterances": [
+                                { "transcript": "let's start", "start": 0.0, "end": 0.9, "confidence": 0.94, "speaker": 0, "languages": ["en"] },
+                                {
+                                    "transcript": "empecemos ya", "start": 1.5, "end": 2.5, "confidence": 0.91, "speaker": 1,
+                                    "words": [
+                                        { "word": "empecemos", "start": 1.5, "end": 2.2, "confidence": 0.92, "speaker": 1, "language": "es" },
+                                        { "word": "ya", "start": 2.2, "end": 2.5, "confidence": 0.91, "speaker": 1, "language": "es" }
+                                    ]
+                                }
+                            ]
+                        }]
+                    }]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let result = service.parse_deepgram_response(response, false).unwrap();
+        let languages: Vec<_> = result
+            .segments
+            .iter()
+            .map(|s| s.language.as_deref())
+            .collect();
+        assert_eq!(languages, vec![Some("en"), Some("es")]);
+
+        // Without utterances, the language comes from the grouped words
+        let alternative: Alternative = serde_json::from_str(
+            r#"{
+                "transcript": "hello hola",
+                "confidence": 0.9,
+                "words": [
+                    { "word": "hello", "start": 0.0, "end": 0.5, "confidence": 0.9, "speaker": 0, "language": "en" },
+                    { "word": "hola", "start": 1.0, "end": 1.5, "confidence": 0.9, "speaker": 1, "language": "es" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let segments = DeepgramService::parse_alternative_segments(&alternative, 1.5, false);
+        assert_eq!(segments[0].language.as_deref(), Some("en"));
+        assert_eq!(segments[1].language.as_deref(), Some("es"));
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let service = DeepgramService::new("test_api_key".to_string());
+        let response: DeepgramResponse = serde_json::from_str(
+            r#"{
+                "metadata": {
+                    "request_id": "a1b2c3d4-0000-4000-8000-000000000000",
+                    "duration": 1.5,
+                    "channels": 1,
+                    "models": ["30089e05-99d1-4376-b32e-c263170674af"],
+                    "model_info": {
+                        "30089e05-99d1-4376-b32e-c263170674af": {
+                            "name": "general-nova-3",
+                            "version": "2024-12-20.0",
+                            "arch": "nova-3"
+                        }
+                    }
+                },
+                "results": {
+                    "channels": [{
+                        "alternatives": [{ "transcript": "hello", "confidence": 0.9 }]
+                    }]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let result = service.parse_deepgram_response(response, false).unwrap();
+        assert_eq!(
+            result.request_id.as_deref(),
+            Some("a1b2c3d4-0000-4000-8000-000000000000")
+        );
+        assert_eq!(
+            result.model_info,
+            Some(ModelInfo {
+                name: "general-nova-3".to_string(),
+                version: Some("2024-12-20.0".to_string()),
+                arch: Some("nova-3".to_string()),
+            })
+        );
+
+        // Missing metadata fields are tolerated
+        let response: DeepgramResponse = serde_json::from_str(
+            r#"{
+                "metadata": {},
+                "results": {
+                    "channels": [{
+                        "alternatives": [{ "transcript": "hello", "confidence": 0.9 }]
+                    }]
+                }
+            }"#,
+        )
+        .unwrap();
+        let result = service.parse_deepgram_response(response, false).unwrap();
+        assert_eq!(result.request_id, None);
+        assert_eq!(result.model_info, None);
+    }
+
+    #[test]
+    fn test_sentence_segmentation_of_unsegmented_transcript() {
+        let service = DeepgramService::new("test_api_key".to_string());
+        let parse = |sentence_segmentation| {
+            let response: DeepgramResponse = serde_json::from_str(
+                r#"{
+                    "metadata": { "duration": 3.1 },
+                    "results": {
+                        "channels": [{
+                            "alternatives": [{
+                                "transcript": "Hello there. How are you? Fine!",
+                                "confidence": 0.8
+                            }]
+                        }]
+                    }
+                }"#,
+            )
+            .unwrap();
+            service
+                .parse_deepgram_response(response, sentence_segmentation)
+                .unwrap()
+        };
+
+        // Off by default: one block for the whole recording
+        let result = parse(false);
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.segments[0].end_ms, 3100);
+
+        // 31 characters over 3.1 seconds: 100 ms per character
+        let result = parse(true);
+        let spans: Vec<_> = result
+            .segments
+            .iter()
+            .map(|s| (s.text.as_str(), s.start_ms, s.end_ms))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("Hello there.", 0, 1200),
+                ("How are you?", 1200, 2500),
+                ("Fine!", 2500, 3100),
+            ]
+        );
+        assert!(result.segments.iter().all(|s| s.confidence == Some(0.8)));
+        assert_eq!(result.text, "Hello there. How are you? Fine!");
+    }
+
+    #[test]
+    fn test_parse_multichannel_response() {
+        let service = DeepgramService::new("test_api_key".to_string());
+        let response: DeepgramResponse = serde_json::from_str(
+            r#"{
+                "metadata": { "duration": 6.0 },
+                "results": {
+                    "channels": [
+                        {
+                            "alternatives": [{
+                                "transcript": "hi everyone let's start",
+                                "confidence": 0.9,
+                                "words": [
+                                    { "word": "hi", "start": 0.0, "end": 0.4, "confidence": 0.9 },
+                                    { "word": "everyone", "start": 0.4, "end": 1.0, "confidence": 0.9 },
+                                    { "word": "let's", "start": 4.0, "end": 4.3, "confidence": 0.9 },
+                                    { "word": "start", "start": 4.3, "end": 4.8, "confidence": 0.9 }
+                                ]
+                            }]
+                        },
+                        {
+                            "alternatives": [{
+                                "transcript": "hello",
+                                "confidence": 0.7,
+                                "words": [
+                                    { "word": "hello", "start": 2.0, "end": 2.5, "confidence": 0.7 }
+                                ]
+                            }]
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let result = service.parse_deepgram_response(response, false).unwrap();
+
+        // Both channels are parsed and each segment is labeled by its channel
+        let labels: Vec<_> = result
+            .segments
+            .iter()
+            .map(|s| s.speaker_label.as_deref().unwrap())
+            .collect();
+        assert_eq!(labels, vec!["Channel 1", "Channel 2"]);
+        assert_eq!(result.segments[0].start_ms, 0);
+        assert_eq!(result.segments[1].start_ms, 2000);
+        assert_eq!(result.text, "hi everyone let's start hello");
+        assert!((result.confidence.unwrap() - 0.8).abs() < 0.001);
+    }
 }
@@ -3,228 +3,778 @@
 //! Implements real-time transcription with speaker diarization using Deepgram's WebSocket API.
 //! Reference: https://developers.deepgram.com/docs/live-streaming-audio
 
+use super::deepgram::{feature_params, redact_params};
 use crate::error::{AppError, Result};
 use crate::ports::transcription::{
     StreamingSession, StreamingTranscriptionCallback, TranscriptionConfig, TranscriptionSegment,
+    STREAMING_AUDIO_FORMAT,
 };
+use crate::utils::opus::OggOpusEncoder;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 const DEEPGRAM_STREAMING_URL: &str = "wss://api.deepgram.com/v1/listen";
 
+/// Bytes of audio per millisecond for the stream format (linear16, 16 kHz, mono)
+const BYTES_PER_MS: u64 = 32;
+
+/// Number of recently sent chunks kept for replay after a reconnect
+///
+/// Audio that was in flight when the socket dropped may never have been
+/// transcribed, so the tail of the stream is re-sent on the new connection.
+const REPLAY_WINDOW_CHUNKS: usize = 20;
+
+/// How often a KeepAlive message is sent to hold the connection open
+///
+/// Deepgram closes a live connection that receives no data for about 10 seconds, which
+/// happens whenever capture delivers nothing during a long silence.
+pub const DEFAULT_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long `close` waits for the final results after asking Deepgram to end the stream
+const CLOSE_STREAM_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Deepgram control messages
+const KEEPALIVE_MESSAGE: &str = r#"{"type":"KeepAlive"}"#;
+/// Transcribe all audio received so far, keeping the connection open
+const FINALIZE_MESSAGE: &str = r#"{"type":"Finalize"}"#;
+/// Transcribe all audio received so far, send the final results and close the connection
+const CLOSE_STREAM_MESSAGE: &str = r#"{"type":"CloseStream"}"#;
+
+type WsStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+type WsSink = futures_util::stream::SplitSink<WsStream, Message>;
+type WsSource = futures_util::stream::SplitStream<WsStream>;
+
+/// Backoff policy for re-establishing a dropped WebSocket
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Maximum reconnect attempts before giving up
+    pub max_retries: u32,
+    /// Delay before the first attempt; doubled for each subsequent attempt
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between attempts
+    pub max_delay: Duration,
+    /// Maximum chunks queued while disconnected; the oldest are dropped beyond this
+    pub max_pending_chunks: usize,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            max_pending_chunks: 500,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before reconnect attempt `attempt` (0-based), or None once retries are exhausted
+    pub fn delay_for(&self, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        let factor = 2u32.saturating_pow(attempt);
+        Some(
+            self.initial_delay
+                .saturating_mul(factor)
+                .min(self.max_delay),
+        )
+    }
+}
+
+/// Retry `connect` with exponential backoff until it succeeds or the policy is exhausted
+///
+/// Returns the last connection error when every attempt fails.
+async fn reconnect_with_backoff<T, F, Fut>(policy: &ReconnectPolicy, mut connect: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 0;
+    let mut last_error = AppError::Transcription("No reconnect attempts allowed".to_string());
+
+    while let Some(delay) = policy.delay_for(attempt) {
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+
+        match connect().await {
+            Ok(connection) => {
+                log::info!("Reconnected to Deepgram after {} attempt(s)", attempt);
+                return Ok(connection);
+            }
+            Err(e) => {
+                log::warn!(
+                    "Deepgram reconnect attempt {}/{} failed: {}",
+                    attempt,
+                    policy.max_retries,
+                    e
+                );
+                last_error = e;
+            }
+        }
+    }
+
+    Err(last_error)
+}
+
+/// Build the Deepgram live transcription URL for a config
+fn build_streaming_url(config: &TranscriptionConfig) -> String {
+    let model = config.model.as_deref().unwrap_or("nova-2-meeting");
+
+    let mut url = format!("{}?model={}", DEEPGRAM_STREAMING_URL, model);
+
+    // Add diarization if enabled
+    if config.enable_diarization {
+        url.push_str("&diarize=true");
+    }
+
+    // Add utterances for better segmentation
+    url.push_str("&utterances=true");
+
+    // Add punctuation
+    url.push_str("&punctuate=true");
+
+    // Add interim results for real-time feedback
+    url.push_str("&interim_results=true");
+
+    // Add language if specified
+    if let Some(lang) = &config.language {
+        url.push_str(&format!("&language={}", lang));
+    }
+
+    // PII redaction, smart_format and keyterms, shared with batch requests
+    for (name, value) in redact_params(config)
+        .into_iter()
+        .chain(feature_params(config))
+    {
+        url.push_str(&format!("&{}={}", name, value));
+    }
+
+    // Add encoding and sample rate (Deepgram expects these)
+    url.push_str(&format!(
+        "&encoding={}&sample_rate={}&channels={}",
+        stream_encoding(config).name(),
+        STREAMING_AUDIO_FORMAT.sample_rate,
+        STREAMING_AUDIO_FORMAT.channels
+    ));
+
+    url
+}
+
+/// Encoding of the audio sent over the socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamEncoding {
+    /// The linear16 chunks are sent as they are
+    Linear16,
+    /// Chunks are encoded to an Ogg Opus stream, about a tenth of the bandwidth
+    Opus,
+}
+
+impl StreamEncoding {
+    /// Value of Deepgram's `encoding` parameter
+    fn name(self) -> &'static str {
+        match self {
+            StreamEncoding::Linear16 => "linear16",
+            StreamEncoding::Opus => "opus",
+        }
+    }
+}
+
+/// Stream encoding from `additional_settings.streaming_encoding`; linear16 unless "opus"
+fn stream_encoding(config: &TranscriptionConfig) -> StreamEncoding {
+    let setting = config
+        .additional_settings
+        .as_ref()
+        .and_then(|settings| settings.get("streaming_encoding")?.as_str());
+    match setting {
+        Some(value) if value.eq_ignore_ascii_case("opus") => StreamEncoding::Opus,
+        _ => StreamEncoding::Linear16,
+    }
+}
+
+/// Turns the linear16 chunks callers send into the bytes put on the wire
+///
+/// Each connection is a separate Ogg stream, so the Opus encoder is restarted
+/// (headers and all) after a reconnect.
+struct WireEncoder {
+    encoding: StreamEncoding,
+    opus: Option<OggOpusEncoder>,
+}
+
+impl WireEncoder {
+    fn new(encoding: StreamEncoding) -> Result<Self> {
+        let mut encoder = Self {
+            encoding,
+            opus: None,
+        };
+        encoder.restart()?;
+        Ok(encoder)
+    }
+
+    /// Start a new stream for a new connection
+    fn restart(&mut self) -> Result<()> {
+        self.opus = match self.encoding {
+            StreamEncoding::Linear16 => None,
+            StreamEncoding::Opus => Some(OggOpusEncoder::new(
+                STREAMING_AUDIO_FORMAT.sample_rate,
+                STREAMING_AUDIO_FORMAT.channels,
+            )?),
+        };
+        Ok(())
+    }
+
+    /// Bytes to send for a linear16 chunk; empty while Opus waits for a full frame
+    fn encode(&mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+        match self.opus.as_mut() {
+            None => Ok(chunk.to_vec()),
+            Some(opus) => {
+                let samples: Vec<i16> = chunk
+                    .chunks_exact(2)
+                    .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                    .collect();
+                opus.encode(&samples)
+            }
+        }
+    }
+
+    /// Bytes that end the stream: the last buffered audio and the end-of-stream page
+    fn finish(&mut self) -> Result<Vec<u8>> {
+        match self.opus.take() {
+            None => Ok(Vec::new()),
+            Some(opus) => opus.finish(),
+        }
+    }
+}
+
+/// Keepalive interval from `additional_settings.keepalive_interval_secs`, if set
+fn keepalive_interval(config: &TranscriptionConfig) -> Duration {
+    config
+        .additional_settings
+        .as_ref()
+        .and_then(|settings| settings.get("keepalive_interval_secs")?.as_u64())
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_KEEPALIVE_INTERVAL)
+}
+
+/// Open an authenticated WebSocket to Deepgram
+async fn connect(url: &str, api_key: &str) -> Result<WsStream> {
+    let request = tokio_tungstenite::tungstenite::http::Request::builder()
+        .uri(url)
+        .header("Authorization", format!("Token {}", api_key))
+        .body(())
+        .map_err(|e| AppError::Transcription(format!("Failed to build request: {}", e)))?;
+
+    let (ws_stream, _) = connect_async(request)
+        .await
+        .map_err(|e| AppError::Transcription(format!("WebSocket connection failed: {}", e)))?;
+
+    Ok(ws_stream)
+}
+
+/// Audio sent on the current connection, used to replay and re-align after a reconnect
+#[derive(Default)]
+struct SentAudio {
+    /// Most recent chunks, oldest first
+    recent: VecDeque<Vec<u8>>,
+    /// Total bytes sent over the lifetime of the session
+    total_bytes: u64,
+}
+
+impl SentAudio {
+    fn record(&mut self, chunk: &[u8]) {
+        if self.recent.len() == REPLAY_WINDOW_CHUNKS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(chunk.to_vec());
+        self.total_bytes += chunk.len() as u64;
+    }
+
+    /// Stream position (ms) where the replayed window starts
+    fn replay_start_ms(&self) -> i64 {
+        let replay_bytes: u64 = self.recent.iter().map(|c| c.len() as u64).sum();
+        (self.total_bytes.saturating_sub(replay_bytes) / BYTES_PER_MS) as i64
+    }
+}
+
+/// Bounded queue of audio received while the socket is reconnecting
+struct PendingChunks {
+    chunks: VecDeque<Vec<u8>>,
+    max_chunks: usize,
+    dropped: usize,
+}
+
+impl PendingChunks {
+    fn new(max_chunks: usize) -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            max_chunks,
+            dropped: 0,
+        }
+    }
+
+    /// Queue a chunk, discarding the oldest one if the queue is full
+    fn push(&mut self, chunk: &[u8]) {
+        if self.max_chunks == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.chunks.len() == self.max_chunks {
+            self.chunks.pop_front();
+            self.dropped += 1;
+        }
+        self.chunks.push_back(chunk.to_vec());
+    }
+
+    /// Take all queued chunks, oldest first, along with how many were dropped
+    fn drain(&mut self) -> (Vec<Vec<u8>>, usize) {
+        let dropped = std::mem::take(&mut self.dropped);
+        (self.chunks.drain(..).collect(), dropped)
+    }
+
+    fn len(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+/// State shared between the session handle and its receiver task
+struct SharedState {
+    ws_sender: Mutex<Option<WsSink>>,
+    is_active: Mutex<bool>,
+    sent_audio: Mutex<SentAudio>,
+    /// Audio waiting for the socket to come back; a std mutex so `pending_chunks` can stay sync
+    pending: std::sync::Mutex<PendingChunks>,
+    /// Only used while holding `ws_sender`, so bytes go out in the order they were encoded
+    wire: std::sync::Mutex<WireEncoder>,
+}
+
+impl SharedState {
+    /// Encode a linear16 chunk for the current connection
+    fn wire_bytes(&self, chunk: &[u8]) -> Result<Vec<u8>> {
+        self.wire
+            .lock()
+            .map_err(|_| AppError::Transcription("Audio encoder poisoned".to_string()))?
+            .encode(chunk)
+    }
+}
+
 /// Deepgram streaming session
 pub struct DeepgramStreamingSession {
-    /// WebSocket write sink
-    ws_sender: Arc<
-        Mutex<
-            Option<
-                futures_util::stream::SplitSink<
-                    tokio_tungstenite::WebSocketStream<
-                        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-                    >,
-                    Message,
-                >,
-            >,
-        >,
-    >,
-
-    /// Session active status
-    is_active: Arc<Mutex<bool>>,
+    shared: Arc<SharedState>,
 
     /// Handle to the receiver task
     receiver_task: Option<tokio::task::JoinHandle<()>>,
+
+    /// Handle to the task sending KeepAlive messages
+    keepalive_task: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl DeepgramStreamingSession {
     /// Create a new Deepgram streaming session
+    ///
+    /// The keepalive interval can be set with `additional_settings.keepalive_interval_secs`,
+    /// and `additional_settings.streaming_encoding = "opus"` sends Opus instead of linear16.
     pub async fn new(
         api_key: String,
         config: &TranscriptionConfig,
         callback: Box<dyn StreamingTranscriptionCallback>,
+    ) -> Result<Self> {
+        Self::with_reconnect_policy(api_key, config, callback, ReconnectPolicy::default()).await
+    }
+
+    /// Create a new Deepgram streaming session with a custom reconnect policy
+    pub async fn with_reconnect_policy(
+        api_key: String,
+        config: &TranscriptionConfig,
+        callback: Box<dyn StreamingTranscriptionCallback>,
+        policy: ReconnectPolicy,
+    ) -> Result<Self> {
+        let interval = keepalive_interval(config);
+        Self::with_options(api_key, config, callback, policy, interval).await
+    }
+
+    /// Create a new Deepgram streaming session with a custom reconnect policy and
+    /// keepalive interval
+    pub async fn with_options(
+        api_key: String,
+        config: &TranscriptionConfig,
+        callback: Box<dyn StreamingTranscriptionCallback>,
+        policy: ReconnectPolicy,
+        keepalive_interval: Duration,
     ) -> Result<Self> {
         log::info!("Starting Deepgram streaming session");
 
-        // Build WebSocket URL with query parameters
-        let model = config.model.as_deref().unwrap_or("nova-2-meeting");
+        let url = build_streaming_url(config);
+        let wire = WireEncoder::new(stream_encoding(config))?;
+        log::debug!("Connecting to Deepgram WebSocket: {}", url);
 
-        let mut url = format!("{}?model={}", DEEPGRAM_STREAMING_URL, model);
+        let ws_stream = connect(&url, &api_key).await?;
+        log::info!("Connected to Deepgram WebSocket");
 
-        // Add diarization if enabled
-        if config.enable_diarization {
-            url.push_str("&diarize=true");
-        }
+        // Split the WebSocket into sender and receiver
+        let (write, read) = ws_stream.split();
 
-        // Add utterances for better segmentation
-        url.push_str("&utterances=true");
+        let shared = Arc::new(SharedState {
+            ws_sender: Mutex::new(Some(write)),
+            is_active: Mutex::new(true),
+            sent_audio: Mutex::new(SentAudio::default()),
+            pending: std::sync::Mutex::new(PendingChunks::new(policy.max_pending_chunks)),
+            wire: std::sync::Mutex::new(wire),
+        });
 
-        // Add punctuation
-        url.push_str("&punctuate=true");
+        // Spawn a task to receive messages, reconnecting if the socket drops mid-session
+        let receiver_task = tokio::spawn(run_receiver(
+            read,
+            Arc::clone(&shared),
+            callback,
+            url,
+            api_key,
+            policy,
+        ));
 
-        // Add interim results for real-time feedback
-        url.push_str("&interim_results=true");
+        // Keep the connection open through silences where no audio is sent
+        let keepalive_shared = Arc::clone(&shared);
+        let keepalive_task = tokio::spawn(async move {
+            run_keepalive(&keepalive_shared.is_active, keepalive_interval, || {
+                send_keepalive(&keepalive_shared)
+            })
+            .await;
+        });
 
-        // Add language if specified
-        if let Some(lang) = &config.language {
-            url.push_str(&format!("&language={}", lang));
-        }
+        Ok(Self {
+            shared,
+            receiver_task: Some(receiver_task),
+            keepalive_task: Some(keepalive_task),
+        })
+    }
+}
 
-        // Add encoding and sample rate (Deepgram expects these)
-        url.push_str("&encoding=linear16&sample_rate=16000&channels=1");
+/// Call `send_keepalive` every `interval` until the session is no longer active
+async fn run_keepalive<F, Fut>(is_active: &Mutex<bool>, interval: Duration, mut send_keepalive: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately; the connection was just opened
+    ticker.tick().await;
 
-        log::info!("Connecting to Deepgram WebSocket: {}", url);
+    loop {
+        ticker.tick().await;
+        if !*is_active.lock().await {
+            log::debug!("Stopping Deepgram keepalive");
+            return;
+        }
+        send_keepalive().await;
+    }
+}
 
-        // Create authorization header
-        let request = tokio_tungstenite::tungstenite::http::Request::builder()
-            .uri(&url)
-            .header("Authorization", format!("Token {}", api_key))
-            .body(())
-            .map_err(|e| AppError::Transcription(format!("Failed to build request: {}", e)))?;
+/// Send a KeepAlive message on the current connection
+///
+/// Skipped while reconnecting; the receiver task will install a new sender.
+async fn send_keepalive(shared: &SharedState) {
+    if let Some(ws) = shared.ws_sender.lock().await.as_mut() {
+        if let Err(e) = ws.send(Message::Text(KEEPALIVE_MESSAGE.to_string())).await {
+            log::warn!("Failed to send Deepgram keepalive: {}", e);
+        }
+    }
+}
 
-        // Connect to WebSocket
-        let (ws_stream, _) = connect_async(request)
+/// End a stream without losing audio Deepgram hasn't transcribed yet
+///
+/// Sends CloseStream so the server flushes its buffer, lets the receiver task forward the
+/// final results until the server closes the connection (or `timeout` passes), and only
+/// then sends our own Close frame.
+async fn finish_stream<S>(
+    mut sink: Option<S>,
+    receiver: Option<tokio::task::JoinHandle<()>>,
+    timeout: Duration,
+) where
+    S: futures_util::Sink<Message> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    if let Some(ws) = sink.as_mut() {
+        if let Err(e) = ws
+            .send(Message::Text(CLOSE_STREAM_MESSAGE.to_string()))
             .await
-            .map_err(|e| AppError::Transcription(format!("WebSocket connection failed: {}", e)))?;
+        {
+            log::warn!("Failed to send CloseStream to Deepgram: {}", e);
+        }
+    }
 
-        log::info!("Connected to Deepgram WebSocket");
+    if let Some(mut task) = receiver {
+        if tokio::time::timeout(timeout, &mut task).await.is_err() {
+            log::warn!(
+                "Deepgram did not finish the stream within {:?}; the last transcript may be incomplete",
+                timeout
+            );
+            task.abort();
+        }
+    }
 
-        // Split the WebSocket into sender and receiver
-        let (write, mut read) = ws_stream.split();
+    if let Some(mut ws) = sink {
+        let _ = ws.send(Message::Close(None)).await;
+        let _ = ws.close().await;
+    }
+}
 
-        let ws_sender = Arc::new(Mutex::new(Some(write)));
-        let is_active = Arc::new(Mutex::new(true));
+/// Encode a linear16 chunk and send it on `ws`
+///
+/// Nothing is sent while the Opus encoder is still waiting for a full frame.
+async fn send_chunk(ws: &mut WsSink, shared: &SharedState, chunk: &[u8]) -> Result<()> {
+    let bytes = shared.wire_bytes(chunk)?;
+    if bytes.is_empty() {
+        return Ok(());
+    }
+    ws.send(Message::Binary(bytes))
+        .await
+        .map_err(|e| AppError::Transcription(format!("Failed to send audio: {}", e)))
+}
 
-        // Spawn a task to receive messages from the WebSocket
-        let is_active_clone = Arc::clone(&is_active);
-        let receiver_task = tokio::spawn(async move {
-            while let Some(message) = read.next().await {
-                match message {
-                    Ok(Message::Text(text)) => {
-                        log::debug!("Received Deepgram message: {}", text);
-
-                        // Parse the Deepgram response
-                        match serde_json::from_str::<DeepgramStreamingResponse>(&text) {
-                            Ok(response) => {
-                                // Check if this is a final transcript or interim
-                                let is_final = response.is_final.unwrap_or(false);
-
-                                // Process the response
-                                if let Some(ref channel_data) = response.channel {
-                                    if let Some(alternative) = channel_data.alternatives.first() {
-                                        // Extract segment information from alternative
-                                        if !alternative.transcript.is_empty() {
-                                            let segment = TranscriptionSegment {
-                                                text: alternative.transcript.clone(),
-                                                start_ms: (response.start.unwrap_or(0.0) * 1000.0)
-                                                    as i64,
-                                                end_ms: ((response.start.unwrap_or(0.0)
-                                                    + response.duration.unwrap_or(0.0))
-                                                    * 1000.0)
-                                                    as i64,
-                                                speaker_label: None, // Will be populated from utterances if available
-                                                confidence: Some(alternative.confidence),
-                                            };
-
-                                            if is_final {
-                                                callback.on_transcript(segment).await;
-                                            } else {
-                                                callback.on_interim_transcript(segment).await;
-                                            }
-                                        }
-
-                                        // Process utterances (for diarization)
-                                        if let Some(ref utterances) = alternative.utterances {
-                                            for utterance in utterances {
-                                                let segment = TranscriptionSegment {
-                                                    text: utterance.transcript.clone(),
-                                                    start_ms: (utterance.start * 1000.0) as i64,
-                                                    end_ms: (utterance.end * 1000.0) as i64,
-                                                    speaker_label: Some(format!(
-                                                        "Speaker {}",
-                                                        utterance.speaker
-                                                    )),
-                                                    confidence: Some(utterance.confidence),
-                                                };
-
-                                                callback.on_transcript(segment).await;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                log::error!("Failed to parse Deepgram response: {}", e);
+/// Why a connection stopped delivering messages
+enum Disconnect {
+    Closed,
+    Error(String),
+}
+
+/// Forward transcripts from one connection until it closes or errors
+async fn read_until_disconnect(
+    read: &mut WsSource,
+    callback: &dyn StreamingTranscriptionCallback,
+    offset_ms: i64,
+) -> Disconnect {
+    while let Some(message) = read.next().await {
+        match message {
+            Ok(Message::Text(text)) => {
+                log::debug!("Received Deepgram message: {}", text);
+
+                // Parse the Deepgram response
+                match serde_json::from_str::<DeepgramStreamingResponse>(&text) {
+                    Ok(response) => {
+                        // Check if this is a final transcript or interim
+                        let is_final = response.is_final.unwrap_or(false);
+
+                        for mut segment in build_segments(&response) {
+                            // Timestamps restart at zero on each connection
+                            segment.start_ms += offset_ms;
+                            segment.end_ms += offset_ms;
+
+                            if is_final {
+                                callback.on_transcript(segment).await;
+                            } else {
+                                callback.on_interim_transcript(segment).await;
                             }
                         }
                     }
-                    Ok(Message::Close(_)) => {
-                        log::info!("Deepgram WebSocket closed");
-                        *is_active_clone.lock().await = false;
-                        callback.on_close().await;
+                    Err(e) => {
+                        log::error!("Failed to parse Deepgram response: {}", e);
+                    }
+                }
+            }
+            Ok(Message::Close(_)) => return Disconnect::Closed,
+            Err(e) => return Disconnect::Error(e.to_string()),
+            _ => {}
+        }
+    }
+    Disconnect::Closed
+}
+
+/// Receive loop for the whole session, re-establishing the socket when it drops
+async fn run_receiver(
+    mut read: WsSource,
+    shared: Arc<SharedState>,
+    callback: Box<dyn StreamingTranscriptionCallback>,
+    url: String,
+    api_key: String,
+    policy: ReconnectPolicy,
+) {
+    let mut offset_ms = 0;
+
+    loop {
+        let disconnect = read_until_disconnect(&mut read, callback.as_ref(), offset_ms).await;
+
+        // A close we initiated ends the session normally
+        if !*shared.is_active.lock().await {
+            log::info!("Deepgram WebSocket closed");
+            callback.on_close().await;
+            return;
+        }
+
+        match &disconnect {
+            Disconnect::Closed => log::warn!("Deepgram WebSocket closed unexpectedly"),
+            Disconnect::Error(e) => log::warn!("Deepgram WebSocket error: {}", e),
+        }
+
+        // Stop forwarding audio to the dead socket while reconnecting
+        shared.ws_sender.lock().await.take();
+
+        match reconnect_with_backoff(&policy, || connect(&url, &api_key)).await {
+            Ok(ws_stream) => {
+                let (mut write, new_read) = ws_stream.split();
+
+                // Hold the sender slot so send_audio keeps queueing until the backlog is out
+                let mut sender = shared.ws_sender.lock().await;
+                let mut sent_audio = shared.sent_audio.lock().await;
+
+                // The new connection is a new stream; Opus starts over with its headers
+                if let Err(e) = shared.wire.lock().map(|mut wire| wire.restart()) {
+                    log::warn!("Failed to restart the audio encoder: {}", e);
+                }
+
+                // Replay the tail of the stream that may have been lost in the drop
+                offset_ms = sent_audio.replay_start_ms();
+                for chunk in &sent_audio.recent {
+                    if let Err(e) = send_chunk(&mut write, &shared, chunk).await {
+                        log::warn!("Failed to replay buffered audio: {}", e);
                         break;
                     }
-                    Err(e) => {
-                        log::error!("WebSocket error: {}", e);
-                        callback.on_error(e.to_string()).await;
-                        *is_active_clone.lock().await = false;
+                }
+                log::info!(
+                    "Replayed {} buffered chunks after reconnect",
+                    sent_audio.recent.len()
+                );
+
+                // Then forward the audio that arrived while we were disconnected
+                let (pending, dropped) = match shared.pending.lock() {
+                    Ok(mut pending) => pending.drain(),
+                    Err(_) => (Vec::new(), 0),
+                };
+                if dropped > 0 {
+                    log::warn!(
+                        "Dropped {} audio chunks while reconnecting (queue full)",
+                        dropped
+                    );
+                }
+                for chunk in &pending {
+                    if let Err(e) = send_chunk(&mut write, &shared, chunk).await {
+                        log::warn!("Failed to flush queued audio: {}", e);
                         break;
                     }
-                    _ => {}
+                    sent_audio.record(chunk);
                 }
-            }
-        });
+                log::info!("Flushed {} queued chunks after reconnect", pending.len());
+                drop(sent_audio);
 
-        Ok(Self {
-            ws_sender,
-            is_active,
-            receiver_task: Some(receiver_task),
-        })
+                *sender = Some(write);
+                drop(sender);
+                read = new_read;
+            }
+            Err(e) => {
+                let reason = match disconnect {
+                    Disconnect::Closed => "connection closed".to_string(),
+                    Disconnect::Error(e) => e,
+                };
+                log::error!(
+                    "Giving up on Deepgram streaming after {} reconnect attempts: {}",
+                    policy.max_retries,
+                    e
+                );
+                *shared.is_active.lock().await = false;
+                callback
+                    .on_error(format!(
+                        "Live transcription disconnected ({}) and could not reconnect: {}",
+                        reason, e
+                    ))
+                    .await;
+                callback.on_close().await;
+                return;
+            }
+        }
     }
 }
 
 #[async_trait]
 impl StreamingSession for DeepgramStreamingSession {
     async fn send_audio(&mut self, audio_chunk: &[u8]) -> Result<()> {
-        let mut sender = self.ws_sender.lock().await;
+        let mut sender = self.shared.ws_sender.lock().await;
 
-        if let Some(ws) = sender.as_mut() {
-            ws.send(Message::Binary(audio_chunk.to_vec()))
-                .await
-                .map_err(|e| AppError::Transcription(format!("Failed to send audio: {}", e)))?;
-            Ok(())
-        } else {
-            Err(AppError::Transcription(
+        if !*self.shared.is_active.lock().await {
+            return Err(AppError::Transcription(
                 "WebSocket connection is closed".to_string(),
-            ))
+            ));
         }
+
+        if let Some(ws) = sender.as_mut() {
+            match send_chunk(ws, &self.shared, audio_chunk).await {
+                Ok(()) => {
+                    self.shared.sent_audio.lock().await.record(audio_chunk);
+                    return Ok(());
+                }
+                // The receiver task notices the drop and reconnects; keep the chunk until then
+                Err(e) => log::warn!("{}, queueing until reconnect", e),
+            }
+        }
+
+        self.shared
+            .pending
+            .lock()
+            .map_err(|_| AppError::Transcription("Pending audio queue poisoned".to_string()))?
+            .push(audio_chunk);
+        Ok(())
     }
 
     async fn flush(&mut self) -> Result<()> {
-        // Deepgram automatically processes all buffered audio when the connection closes
-        // We can optionally send a flush message, but it's not required
         log::info!("Flushing Deepgram streaming session");
-        Ok(())
+
+        let mut sender = self.shared.ws_sender.lock().await;
+        match sender.as_mut() {
+            Some(ws) => ws
+                .send(Message::Text(FINALIZE_MESSAGE.to_string()))
+                .await
+                .map_err(|e| AppError::Transcription(format!("Failed to send Finalize: {}", e))),
+            None => {
+                // Audio queued during a reconnect is sent once the new connection is up
+                log::debug!("Deepgram is reconnecting; nothing to finalize yet");
+                Ok(())
+            }
+        }
     }
 
     async fn close(&mut self) -> Result<()> {
         log::info!("Closing Deepgram streaming session");
 
-        *self.is_active.lock().await = false;
+        *self.shared.is_active.lock().await = false;
 
-        // Send close frame
-        let mut sender = self.ws_sender.lock().await;
-        if let Some(mut ws) = sender.take() {
-            let _ = ws.send(Message::Close(None)).await;
-            let _ = ws.close().await;
+        if let Some(task) = self.keepalive_task.take() {
+            task.abort();
         }
 
-        // Wait for receiver task to complete
-        if let Some(task) = self.receiver_task.take() {
-            let _ = task.await;
+        let pending = self.pending_chunks();
+        if pending > 0 {
+            log::warn!(
+                "Closing with {} queued audio chunks that were never sent",
+                pending
+            );
+        }
+
+        // Let Deepgram send the last results before the socket is torn down
+        let mut sender = self.shared.ws_sender.lock().await.take();
+        if let Some(ws) = sender.as_mut() {
+            // Audio Opus is still holding, and the end of the Ogg stream
+            let tail = match self.shared.wire.lock() {
+                Ok(mut wire) => wire.finish(),
+                Err(_) => Ok(Vec::new()),
+            };
+            match tail {
+                Ok(tail) if !tail.is_empty() => {
+                    if let Err(e) = ws.send(Message::Binary(tail)).await {
+                        log::warn!("Failed to send the end of the audio stream: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Failed to finish the audio stream: {}", e),
+            }
         }
+        finish_stream(sender, self.receiver_task.take(), CLOSE_STREAM_TIMEOUT).await;
 
         Ok(())
     }
@@ -232,11 +782,20 @@ impl StreamingSession for DeepgramStreamingSession {
     fn is_active(&self) -> bool {
         // We need to use try_lock here since this is a sync method
         // In a real-world scenario, you might want to use a different pattern
-        self.is_active
+        self.shared
+            .is_active
             .try_lock()
             .map(|guard| *guard)
             .unwrap_or(false)
     }
+
+    fn pending_chunks(&self) -> usize {
+        self.shared
+            .pending
+            .lock()
+            .map(|pending| pending.len())
+            .unwrap_or(0)
+    }
 }
 
 impl Drop for DeepgramStreamingSession {
@@ -245,6 +804,9 @@ impl Drop for DeepgramStreamingSession {
         if let Some(task) = self.receiver_task.take() {
             task.abort();
         }
+        if let Some(task) = self.keepalive_task.take() {
+            task.abort();
+        }
     }
 }
 
@@ -269,14 +831,480 @@ struct Channel {
 struct Alternative {
     transcript: String,
     confidence: f32,
-    utterances: Option<Vec<Utterance>>,
+    words: Option<Vec<Word>>,
 }
 
+/// Word-level result; the live API reports diarization per word, not per utterance
 #[derive(Debug, Deserialize)]
-struct Utterance {
-    transcript: String,
+struct Word {
+    word: String,
+    punctuated_word: Option<String>,
     start: f64,
     end: f64,
     confidence: f32,
-    speaker: u32,
+    speaker: Option<u32>,
+    /// Only reported for multilingual (code-switching) requests
+    language: Option<String>,
+}
+
+/// Build transcript segments from a streaming response
+///
+/// Consecutive words from the same speaker are grouped into one segment. When the
+/// response carries no word-level speakers (diarization disabled), the whole
+/// alternative becomes a single unlabeled segment.
+fn build_segments(response: &DeepgramStreamingResponse) -> Vec<TranscriptionSegment> {
+    let Some(alternative) = response
+        .channel
+        .as_ref()
+        .and_then(|channel| channel.alternatives.first())
+    else {
+        return Vec::new();
+    };
+
+    if alternative.transcript.is_empty() {
+        return Vec::new();
+    }
+
+    let words = alternative.words.as_deref().unwrap_or_default();
+    if !words.iter().any(|w| w.speaker.is_some()) {
+        let start = response.start.unwrap_or(0.0);
+        return vec![TranscriptionSegment {
+            text: alternative.transcript.clone(),
+            start_ms: (start * 1000.0) as i64,
+            end_ms: ((start + response.duration.unwrap_or(0.0)) * 1000.0) as i64,
+            speaker_label: None,
+            confidence: Some(alternative.confidence),
+            language: super::dominant_language(words.iter().map(|w| w.language.as_deref())),
+        }];
+    }
+
+    let mut segments = Vec::new();
+    let mut current: Option<(Option<u32>, Vec<&Word>)> = None;
+
+    for word in words {
+        match current.as_mut() {
+            Some((speaker, group)) if *speaker == word.speaker => group.push(word),
+            _ => {
+                if let Some((speaker, group)) = current.take() {
+                    segments.push(segment_from_words(speaker, &group));
+                }
+                current = Some((word.speaker, vec![word]));
+            }
+        }
+    }
+
+    if let Some((speaker, group)) = current {
+        segments.push(segment_from_words(speaker, &group));
+    }
+
+    segments
+}
+
+/// Build a single segment from a run of same-speaker words
+fn segment_from_words(speaker: Option<u32>, words: &[&Word]) -> TranscriptionSegment {
+    let text = words
+        .iter()
+        .map(|w| w.punctuated_word.as_deref().unwrap_or(&w.word))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let confidence = words.iter().map(|w| w.confidence).sum::<f32>() / words.len() as f32;
+
+    TranscriptionSegment {
+        text,
+        start_ms: (words[0].start * 1000.0) as i64,
+        end_ms: (words[words.len() - 1].end * 1000.0) as i64,
+        speaker_label: speaker.map(|s| format!("Speaker {}", s)),
+        confidence: Some(confidence),
+        language: super::dominant_language(words.iter().map(|w| w.language.as_deref())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy(max_retries: u32) -> ReconnectPolicy {
+        ReconnectPolicy {
+            max_retries,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            ..ReconnectPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_reconnect_policy_backoff() {
+        let policy = ReconnectPolicy::default();
+        assert_eq!(policy.delay_for(0), Some(Duration::from_millis(500)));
+        assert_eq!(policy.delay_for(1), Some(Duration::from_secs(1)));
+        assert_eq!(policy.delay_for(3), Some(Duration::from_secs(4)));
+        // Capped at max_delay
+        assert_eq!(policy.delay_for(4), Some(Duration::from_secs(8)));
+        // Exhausted
+        assert_eq!(policy.delay_for(5), None);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_succeeds_after_transient_failures() {
+        // Fake transport that refuses the first two connection attempts
+        let attempts = AtomicU32::new(0);
+        let result = reconnect_with_backoff(&fast_policy(5), || async {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt < 3 {
+                Err(AppError::Transcription("connection refused".to_string()))
+            } else {
+                Ok(attempt)
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_gives_up_after_max_retries() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = reconnect_with_backoff(&fast_policy(3), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(AppError::Transcription("network unreachable".to_string()))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_stops_after_close() {
+        let is_active = Arc::new(Mutex::new(true));
+        let sent = Arc::new(AtomicU32::new(0));
+
+        let task = tokio::spawn({
+            let is_active = Arc::clone(&is_active);
+            let sent = Arc::clone(&sent);
+            async move {
+                run_keepalive(&is_active, Duration::from_secs(5), || {
+                    sent.fetch_add(1, Ordering::SeqCst);
+                    async {}
+                })
+                .await;
+            }
+        });
+
+        // Nothing is sent until the first interval elapses
+        tokio::time::sleep(Duration::from_secs(4)).await;
+        assert_eq!(sent.load(Ordering::SeqCst), 0);
+
+        tokio::time::sleep(Duration::from_secs(12)).await;
+        assert_eq!(sent.load(Ordering::SeqCst), 3);
+
+        *is_active.lock().await = false;
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert_eq!(sent.load(Ordering::SeqCst), 3);
+        assert!(task.is_finished());
+    }
+
+    /// Sink that records the control messages and frames sent to it
+    fn recording_sink(
+        events: Arc<std::sync::Mutex<Vec<String>>>,
+    ) -> std::pin::Pin<Box<impl futures_util::Sink<Message, Error = std::convert::Infallible>>>
+    {
+        Box::pin(futures_util::sink::unfold(
+            (),
+            move |(), message: Message| {
+                let events = Arc::clone(&events);
+                async move {
+                    let event = match message {
+                        Message::Text(text) => text,
+                        Message::Close(_) => "close frame".to_string(),
+                        other => format!("{:?}", other),
+                    };
+                    events.lock().unwrap().push(event);
+                    Ok(())
+                }
+            },
+        ))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_finish_stream_waits_for_final_results() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        // Receiver that forwards the last transcript shortly after CloseStream
+        let receiver = tokio::spawn({
+            let events = Arc::clone(&events);
+            async move {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                events.lock().unwrap().push("final transcript".to_string());
+            }
+        });
+
+        finish_stream(
+            Some(recording_sink(Arc::clone(&events))),
+            Some(receiver),
+            Duration::from_secs(5),
+        )
+        .await;
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                CLOSE_STREAM_MESSAGE.to_string(),
+                "final transcript".to_string(),
+                "close frame".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_finish_stream_gives_up_after_timeout() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let receiver = tokio::spawn(std::future::pending::<()>());
+
+        let started = tokio::time::Instant::now();
+        finish_stream(
+            Some(recording_sink(Arc::clone(&events))),
+            Some(receiver),
+            Duration::from_secs(5),
+        )
+        .await;
+
+        assert_eq!(started.elapsed(), Duration::from_secs(5));
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![CLOSE_STREAM_MESSAGE.to_string(), "close frame".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_keepalive_interval_from_settings() {
+        let config = TranscriptionConfig::default();
+        assert_eq!(keepalive_interval(&config), DEFAULT_KEEPALIVE_INTERVAL);
+
+        let config = TranscriptionConfig {
+            additional_settings: Some(serde_json::json!({ "keepalive_interval_secs": 3 })),
+            ..Default::default()
+        };
+        assert_eq!(keepalive_interval(&config), Duration::from_secs(3));
+
+        // Zero would spin, so it falls back to the default
+        let config = TranscriptionConfig {
+            additional_settings: Some(serde_json::json!({ "keepalive_interval_secs": 0 })),
+            ..Default::default()
+        };
+        assert_eq!(keepalive_interval(&config), DEFAULT_KEEPALIVE_INTERVAL);
+    }
+
+    #[test]
+    fn test_pending_chunks_bounded() {
+        let mut pending = PendingChunks::new(3);
+        for i in 0..5u8 {
+            pending.push(&[i]);
+        }
+        assert_eq!(pending.len(), 3);
+
+        // Oldest chunks are dropped first and the rest flush in order
+        let (chunks, dropped) = pending.drain();
+        assert_eq!(chunks, vec![vec![2], vec![3], vec![4]]);
+        assert_eq!(dropped, 2);
+        assert_eq!(pending.len(), 0);
+        assert_eq!(pending.drain().1, 0);
+    }
+
+    #[test]
+    fn test_sent_audio_replay_window() {
+        let mut sent = SentAudio::default();
+        for _ in 0..(REPLAY_WINDOW_CHUNKS + 5) {
+            sent.record(&[0u8; 3200]); // 100ms of audio
+        }
+
+        assert_eq!(sent.recent.len(), REPLAY_WINDOW_CHUNKS);
+        // Replay starts after the 5 chunks that fell out of the window
+        assert_eq!(sent.replay_start_ms(), 500);
+    }
+
+    #[test]
+    fn test_build_segments_groups_words_by_speaker() {
+        // Captured from a live session with diarize=true&punctuate=true
+        let frame = r#"{
+            "type": "Results",
+            "channel_index": [0, 1],
+            "duration": 3.1,
+            "start": 12.5,
+            "is_final": true,
+            "speech_final": true,
+            "channel": {
+                "alternatives": [{
+                    "transcript": "can you hear me yes loud and clear",
+                    "confidence": 0.97,
+                    "words": [
+                        { "word": "can", "start": 12.5, "end": 12.7, "confidence": 0.99, "speaker": 0, "speaker_confidence": 0.8, "punctuated_word": "Can" },
+                        { "word": "you", "start": 12.7, "end": 12.8, "confidence": 0.98, "speaker": 0, "speaker_confidence": 0.8, "punctuated_word": "you" },
+                        { "word": "hear", "start": 12.8, "end": 13.0, "confidence": 0.99, "speaker": 0, "speaker_confidence": 0.8, "punctuated_word": "hear" },
+                        { "word": "me", "start": 13.0, "end": 13.3, "confidence": 0.97, "speaker": 0, "speaker_confidence": 0.8, "punctuated_word": "me?" },
+                        { "word": "yes", "start": 14.0, "end": 14.2, "confidence": 0.95, "speaker": 1, "speaker_confidence": 0.6, "punctuated_word": "Yes," },
+                        { "word": "loud", "start": 14.2, "end": 14.5, "confidence": 0.96, "speaker": 1, "speaker_confidence": 0.6, "punctuated_word": "loud" },
+                        { "word": "and", "start": 14.5, "end": 14.6, "confidence": 0.99, "speaker": 1, "speaker_confidence": 0.6, "punctuated_word": "and" },
+                        { "word": "clear", "start": 14.6, "end": 15.6, "confidence": 0.98, "speaker": 1, "speaker_confidence": 0.6, "punctuated_word": "clear." }
+                    ]
+                }]
+            },
+            "metadata": { "request_id": "5f1c7c4e", "model_info": { "name": "2-meeting-nova" } }
+        }"#;
+
+        let response: DeepgramStreamingResponse = serde_json::from_str(frame).unwrap();
+        let segments = build_segments(&response);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Can you hear me?");
+        assert_eq!(segments[0].speaker_label.as_deref(), Some("Speaker 0"));
+        assert_eq!(segments[0].start_ms, 12500);
+        assert_eq!(segments[0].end_ms, 13300);
+        assert_eq!(segments[1].text, "Yes, loud and clear.");
+        assert_eq!(segments[1].speaker_label.as_deref(), Some("Speaker 1"));
+        assert_eq!(segments[1].start_ms, 14000);
+        assert_eq!(segments[1].end_ms, 15600);
+    }
+
+    #[test]
+    fn test_build_segments_without_speakers() {
+        let frame = r#"{
+            "type": "Results",
+            "start": 1.0,
+            "duration": 2.0,
+            "is_final": false,
+            "channel": {
+                "alternatives": [{
+                    "transcript": "good morning",
+                    "confidence": 0.9,
+                    "words": [
+                        { "word": "good", "start": 1.0, "end": 1.4, "confidence": 0.9 },
+                        { "word": "morning", "start": 1.4, "end": 2.0, "confidence": 0.9 }
+                    ]
+                }]
+            }
+        }"#;
+
+        let response: DeepgramStreamingResponse = serde_json::from_str(frame).unwrap();
+        let segments = build_segments(&response);
+
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "good morning");
+        assert!(segments[0].speaker_label.is_none());
+        assert_eq!(segments[0].start_ms, 1000);
+        assert_eq!(segments[0].end_ms, 3000);
+    }
+
+    #[test]
+    fn test_build_segments_empty_transcript() {
+        let frame = r#"{
+            "type": "Results",
+            "is_final": true,
+            "channel": { "alternatives": [{ "transcript": "", "confidence": 0.0, "words": [] }] }
+        }"#;
+
+        let response: DeepgramStreamingResponse = serde_json::from_str(frame).unwrap();
+        assert!(build_segments(&response).is_empty());
+    }
+
+    #[test]
+    fn test_streaming_url_for_nova_3() {
+        let config = TranscriptionConfig {
+            model: Some("nova-3".to_string()),
+            additional_settings: Some(serde_json::json!({
+                "smart_format": true,
+                "keyterms": ["Kubernetes", "Acme Corp", " "],
+            })),
+            ..Default::default()
+        };
+        let url = build_streaming_url(&config);
+
+        assert!(url.starts_with("wss://api.deepgram.com/v1/listen?model=nova-3&"));
+        assert!(url.contains("&smart_format=true"));
+        assert!(url.contains("&keyterm=Kubernetes&keyterm=Acme%20Corp&"));
+        assert_eq!(url.matches("keyterm=").count(), 2);
+        assert!(url.ends_with("&encoding=linear16&sample_rate=16000&channels=1"));
+
+        // Without settings only the base parameters are sent
+        let url = build_streaming_url(&TranscriptionConfig::default());
+        assert!(url.starts_with("wss://api.deepgram.com/v1/listen?model=nova-2-meeting&"));
+        assert!(!url.contains("smart_format"));
+        assert!(!url.contains("keyterm"));
+    }
+
+    #[test]
+    fn test_streaming_url_maps_pii_categories() {
+        let config = TranscriptionConfig {
+            enable_pii_redaction: true,
+            pii_policies: Some(vec![
+                "phone_number".to_string(),
+                "us_social_security_number".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let url = build_streaming_url(&config);
+        assert!(url.contains("&redact=pii&redact=ssn&"));
+        assert!(!url.contains("phone_number"));
+    }
+
+    #[test]
+    fn test_streaming_url_negotiates_opus() {
+        let config = TranscriptionConfig {
+            additional_settings: Some(serde_json::json!({ "streaming_encoding": "opus" })),
+            ..Default::default()
+        };
+        assert_eq!(stream_encoding(&config), StreamEncoding::Opus);
+        assert!(
+            build_streaming_url(&config).ends_with("&encoding=opus&sample_rate=16000&channels=1")
+        );
+
+        let config = TranscriptionConfig {
+            additional_settings: Some(serde_json::json!({ "streaming_encoding": "flac" })),
+            ..Default::default()
+        };
+        assert_eq!(stream_encoding(&config), StreamEncoding::Linear16);
+    }
+
+    /// 20 ms of linear16 audio, a full Opus frame
+    fn linear16_frame() -> Vec<u8> {
+        (0..320i16).flat_map(|i| (i * 50).to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_wire_encoder_passes_linear16_through() {
+        let mut wire = WireEncoder::new(StreamEncoding::Linear16).unwrap();
+        let chunk = linear16_frame();
+        assert_eq!(wire.encode(&chunk).unwrap(), chunk);
+        assert!(wire.finish().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_wire_encoder_sends_ogg_opus() {
+        let mut wire = WireEncoder::new(StreamEncoding::Opus).unwrap();
+        let chunk = linear16_frame();
+
+        // Half a frame: only the headers are ready
+        let first = wire.encode(&chunk[..320]).unwrap();
+        assert!(first.starts_with(b"OggS"));
+        assert!(first.windows(8).any(|w| w == b"OpusHead"));
+        // The rest of the frame is encoded, far smaller than the PCM
+        let page = wire.encode(&chunk[320..]).unwrap();
+        assert!(page.starts_with(b"OggS"));
+        assert!(page.len() < chunk.len() / 2);
+
+        // A reconnect starts a new stream, headers first
+        wire.restart().unwrap();
+        let restarted = wire.encode(&chunk).unwrap();
+        assert!(restarted.windows(8).any(|w| w == b"OpusHead"));
+
+        // Finishing flushes the encoder and marks the end of the stream
+        let tail = wire.finish().unwrap();
+        assert!(tail.starts_with(b"OggS"));
+        // The last page has the end-of-stream flag (0x04) in its header type
+        let last_page = tail.windows(4).rposition(|w| w == b"OggS").unwrap();
+        assert_eq!(tail[last_page + 5] & 0x04, 0x04);
+        assert!(wire.finish().unwrap().is_empty());
+    }
 }
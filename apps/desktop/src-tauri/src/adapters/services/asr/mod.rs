@@ -7,24 +7,335 @@
 pub mod assemblyai;
 pub mod deepgram;
 mod deepgram_streaming;
+#[cfg(test)]
+mod test_server;
 
 pub use assemblyai::AssemblyAIService;
 pub use deepgram::DeepgramService;
 
-use crate::adapters::storage::SqliteStorage;
 use crate::error::{AppError, Result};
 use crate::ports::storage::StoragePort;
-use crate::ports::transcription::TranscriptionServicePort;
-use crate::utils::keychain::KeychainManager;
-use keyring::Entry;
+use crate::ports::transcription::{
+    AsrCapabilities, StreamingSession, StreamingTranscriptionCallback, TranscriptionConfig,
+    TranscriptionResult, TranscriptionSegment, TranscriptionServiceFactory,
+    TranscriptionServicePort,
+};
+use crate::utils::keychain::KeychainPort;
+use crate::utils::rate_limit::{self, RateLimiter};
+use crate::utils::wav::WAV_HEADER_LEN;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+/// An audio file opened as a streamed request body
+pub(crate) struct AudioUpload {
+    pub body: reqwest::Body,
+    /// Size of the file in bytes, sent as `Content-Length`
+    pub size: u64,
+    /// Up to the first [`WAV_HEADER_LEN`] bytes, for format sanity checks
+    pub header: Vec<u8>,
+}
+
+/// Open an audio file for upload without reading it into memory
+///
+/// Recordings can run to several GB, so the body is streamed from disk in chunks.
+pub(crate) async fn open_audio_upload(audio_path: &str) -> Result<AudioUpload> {
+    let mut file = File::open(audio_path)
+        .await
+        .map_err(|e| AppError::Transcription(format!("Failed to open audio file: {}", e)))?;
+
+    let size = file
+        .metadata()
+        .await
+        .map_err(|e| AppError::Transcription(format!("Failed to read audio file: {}", e)))?
+        .len();
+
+    let mut header = Vec::with_capacity(WAV_HEADER_LEN);
+    (&mut file)
+        .take(WAV_HEADER_LEN as u64)
+        .read_to_end(&mut header)
+        .await
+        .map_err(|e| AppError::Transcription(format!("Failed to read audio file: {}", e)))?;
+    file.seek(SeekFrom::Start(0))
+        .await
+        .map_err(|e| AppError::Transcription(format!("Failed to read audio file: {}", e)))?;
+
+    Ok(AudioUpload {
+        body: reqwest::Body::wrap_stream(ReaderStream::new(file)),
+        size,
+        header,
+    })
+}
+
+/// Reject a recording too large for the provider before uploading any of it
+///
+/// Providers answer an oversized upload with an opaque error, often only after most of
+/// the file has been sent.
+pub fn check_upload_size(service: &dyn TranscriptionServicePort, size: u64) -> Result<()> {
+    match service.max_upload_bytes() {
+        Some(limit) if size > limit => Err(AppError::InvalidInput(format!(
+            "The recording is {} but {} accepts at most {} per file. Split the recording into shorter parts and transcribe each one.",
+            format_size(size),
+            service.provider_name(),
+            format_size(limit)
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Byte count in decimal units, e.g. "2.2 GB"
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["bytes", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} bytes", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Formats `audio_content_type` knows, all of which the hosted providers accept
+pub(crate) const UPLOAD_AUDIO_FORMATS: &[&str] =
+    &["wav", "mp3", "flac", "opus", "ogg", "webm", "m4a"];
+
+/// MIME type for an audio format name or file extension
+///
+/// `opus` and `ogg` are Opus in an Ogg container, the only way Opus is stored on disk.
+/// Unknown formats are sent as WAV, which is what recordings are saved as by default.
+pub(crate) fn audio_content_type(format: &str) -> &'static str {
+    match format.to_ascii_lowercase().as_str() {
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "opus" | "ogg" => "audio/ogg",
+        "webm" => "audio/webm",
+        "m4a" | "mp4" => "audio/mp4",
+        _ => "audio/wav",
+    }
+}
+
+/// Provider names to request for the configured PII redaction; None when it is off
+///
+/// `TranscriptionConfig::pii_policies` holds the app's categories; `vendor_name` maps
+/// each one to the provider's own name, and categories it doesn't know are skipped
+/// with a warning. Without a configured list, or when none of its categories map, the
+/// provider's `defaults` are used, so enabled redaction always applies some policy.
+/// Several categories can share a provider name, so duplicates are removed.
+pub(crate) fn redaction_policies(
+    config: &TranscriptionConfig,
+    vendor_name: impl Fn(&str) -> Option<&str>,
+    defaults: &[&str],
+) -> Option<Vec<String>> {
+    if !config.enable_pii_redaction {
+        return None;
+    }
+    let Some(categories) = &config.pii_policies else {
+        return Some(defaults.iter().map(|name| name.to_string()).collect());
+    };
+
+    let mut names: Vec<String> = Vec::new();
+    for category in categories {
+        match vendor_name(category) {
+            Some(name) if !names.iter().any(|n| n == name) => names.push(name.to_string()),
+            Some(_) => {}
+            None => log::warn!(
+                "PII category {:?} isn't supported by this provider",
+                category
+            ),
+        }
+    }
+    if names.is_empty() {
+        log::warn!(
+            "No requested PII category is supported by this provider, redacting its defaults"
+        );
+        return Some(defaults.iter().map(|name| name.to_string()).collect());
+    }
+    Some(names)
+}
+
+/// MIME type for an audio file, from its extension
+pub(crate) fn audio_file_content_type(audio_path: &str) -> &'static str {
+    audio_content_type(
+        Path::new(audio_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default(),
+    )
+}
+
+/// Write a provider's raw response body to `path` when debug mode set one
+///
+/// Failures are logged rather than returned so a debugging aid can't fail a transcription.
+pub(crate) async fn save_raw_response(path: Option<&str>, body: &str) {
+    let Some(path) = path else {
+        return;
+    };
+    match tokio::fs::write(path, body).await {
+        Ok(()) => log::info!("Saved raw ASR response to {}", path),
+        Err(e) => log::warn!("Failed to save raw ASR response to {}: {}", path, e),
+    }
+}
+
+/// Speaker label for a channel in multichannel audio, numbered from 1
+///
+/// Recordings mix the microphone and the system output into every channel, so a
+/// channel says nothing about who is speaking and is labeled only by its number.
+pub(crate) fn channel_speaker_label(channel_index: usize) -> String {
+    format!("Channel {}", channel_index + 1)
+}
+
+/// Relabel diarized speakers by first appearance when `normalize_speaker_labels` is set
+///
+/// Whoever speaks first becomes "Speaker 0", the next new voice "Speaker 1", and so on.
+/// Channel labels from multichannel transcription are already stable and are left alone,
+/// as are segments without a speaker.
+pub(crate) fn normalize_speaker_labels(
+    result: &mut TranscriptionResult,
+    config: &TranscriptionConfig,
+) {
+    if !config.normalize_speaker_labels || config.multichannel {
+        return;
+    }
+
+    let mut first_seen: Vec<(&str, i64)> = Vec::new();
+    for segment in &result.segments {
+        let Some(label) = segment.speaker_label.as_deref() else {
+            continue;
+        };
+        match first_seen.iter_mut().find(|(seen, _)| *seen == label) {
+            Some((_, start_ms)) => *start_ms = (*start_ms).min(segment.start_ms),
+            None => first_seen.push((label, segment.start_ms)),
+        }
+    }
+    // Stable sort keeps list order for speakers starting at the same time
+    first_seen.sort_by_key(|&(_, start_ms)| start_ms);
+
+    let renames: HashMap<String, String> = first_seen
+        .iter()
+        .enumerate()
+        .map(|(index, (label, _))| (label.to_string(), format!("Speaker {}", index)))
+        .collect();
+    for segment in &mut result.segments {
+        if let Some(label) = segment.speaker_label.as_mut() {
+            if let Some(renamed) = renames.get(label.as_str()) {
+                *label = renamed.clone();
+            }
+        }
+    }
+}
+
+/// Split a segment holding a whole transcript into one segment per sentence
+///
+/// Sentences end at `.`, `!` or `?` (and their full-width forms) followed by
+/// whitespace. Without word timings the times are estimates: the segment's span is
+/// shared out in proportion to where each sentence falls in the text, by character.
+/// Speaker, confidence and language carry over to every sentence.
+pub(crate) fn split_into_sentences(segment: TranscriptionSegment) -> Vec<TranscriptionSegment> {
+    let chars: Vec<char> = segment.text.chars().collect();
+    let total = chars.len() as i64;
+    if total == 0 {
+        return vec![segment];
+    }
+
+    // Character offsets where each sentence starts, after any whitespace
+    let mut starts = vec![0usize];
+    for (index, pair) in chars.windows(2).enumerate() {
+        if matches!(pair[0], '.' | '!' | '?' | '。' | '！' | '？') && pair[1].is_whitespace() {
+            starts.push(index + 1);
+        }
+    }
+
+    let duration_ms = segment.end_ms - segment.start_ms;
+    let time_at = |offset: usize| segment.start_ms + duration_ms * offset as i64 / total;
+    let bounds: Vec<usize> = starts.iter().copied().chain([chars.len()]).collect();
+    bounds
+        .windows(2)
+        .filter_map(|pair| {
+            let text: String = chars[pair[0]..pair[1]].iter().collect();
+            let text = text.trim();
+            (!text.is_empty()).then(|| TranscriptionSegment {
+                text: text.to_string(),
+                start_ms: time_at(pair[0]),
+                end_ms: time_at(pair[1]),
+                speaker_label: segment.speaker_label.clone(),
+                confidence: segment.confidence,
+                language: segment.language.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Drop segments below the configured `min_confidence`
+///
+/// Segments without a confidence score are kept. When anything is dropped, the full
+/// transcript text is rebuilt from the remaining segments so the two stay consistent.
+pub(crate) fn filter_low_confidence(
+    result: &mut TranscriptionResult,
+    config: &TranscriptionConfig,
+) {
+    let Some(min_confidence) = config.min_confidence else {
+        return;
+    };
+
+    let before = result.segments.len();
+    result
+        .segments
+        .retain(|segment| segment.confidence.is_none_or(|c| c >= min_confidence));
+
+    let dropped = before - result.segments.len();
+    if dropped > 0 {
+        log::info!(
+            "Dropped {} of {} segments below confidence {}",
+            dropped,
+            before,
+            min_confidence
+        );
+        result.text = result
+            .segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+}
+
+/// Most common language among a segment's words, as reported per word by the provider
+///
+/// Words without a language are ignored; a tie goes to the language heard first.
+pub(crate) fn dominant_language<'a>(
+    languages: impl IntoIterator<Item = Option<&'a str>>,
+) -> Option<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for language in languages.into_iter().flatten() {
+        match counts.iter_mut().find(|(l, _)| *l == language) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((language, 1)),
+        }
+    }
+    counts
+        .iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(language, _)| language.to_string())
+}
 
 /// Get the active ASR service based on service configuration
 ///
 /// Queries the database for the active ASR provider and creates the appropriate service
-/// with the API key from the keychain.
+/// with the API key from the keychain (stored under the `asr_{provider}` account).
 pub async fn get_active_asr_service(
-    storage: &SqliteStorage,
-    _keychain: &KeychainManager,
+    storage: &dyn StoragePort,
+    keychain: &dyn KeychainPort,
+    factory: &dyn TranscriptionServiceFactory,
 ) -> Result<Box<dyn TranscriptionServicePort>> {
     // Query for active ASR service
     let configs = storage.list_service_configs().await?;
@@ -33,21 +344,518 @@ pub async fn get_active_asr_service(
         .find(|c| c.service_type.to_string() == "asr" && c.is_active)
         .ok_or_else(|| AppError::Config("No active ASR service configured".to_string()))?;
 
+    create_asr_service(
+        keychain,
+        factory,
+        &asr_config.provider,
+        asr_config.settings.as_deref(),
+    )
+}
+
+/// Create the ASR service for a provider with the API key from the keychain
+///
+/// `settings` is the provider's saved settings JSON, if it has a service config.
+pub fn create_asr_service(
+    keychain: &dyn KeychainPort,
+    factory: &dyn TranscriptionServiceFactory,
+    provider: &str,
+    settings: Option<&str>,
+) -> Result<Box<dyn TranscriptionServicePort>> {
     // Get API key from keychain
-    let keychain_key = format!("asr_{}", asr_config.provider);
-    let entry = Entry::new("com.srprasanna.meet-scribe", &keychain_key)
-        .map_err(|e| AppError::Config(format!("Failed to access keychain: {}", e)))?;
-    let api_key = entry
-        .get_password()
+    let api_key = keychain
+        .get_api_key("asr", provider)
         .map_err(|e| AppError::Config(format!("ASR API key not found: {}", e)))?;
 
-    // Create appropriate service instance
-    match asr_config.provider.as_str() {
-        "assemblyai" => Ok(Box::new(AssemblyAIService::new(api_key))),
+    factory.create(provider, api_key, settings)
+}
+
+/// Builds the real provider adapters
+pub struct ProviderAsrFactory;
+
+impl TranscriptionServiceFactory for ProviderAsrFactory {
+    fn create(
+        &self,
+        provider: &str,
+        api_key: String,
+        settings: Option<&str>,
+    ) -> Result<Box<dyn TranscriptionServicePort>> {
+        let service = build_asr_service(provider, api_key, settings)?;
+        Ok(Box::new(RateLimitedAsrService {
+            limiter: rate_limit::limiter(provider),
+            inner: service,
+        }))
+    }
+}
+
+/// Holds each provider request to the provider's rate limits
+///
+/// A streaming session counts as one request while it connects; once open it is
+/// not limited.
+struct RateLimitedAsrService {
+    inner: Box<dyn TranscriptionServicePort>,
+    limiter: Arc<RateLimiter>,
+}
+
+#[async_trait]
+impl TranscriptionServicePort for RateLimitedAsrService {
+    async fn transcribe_file(
+        &self,
+        audio_path: &str,
+        config: &TranscriptionConfig,
+    ) -> Result<TranscriptionResult> {
+        let _permit = self.limiter.acquire().await;
+        self.inner.transcribe_file(audio_path, config).await
+    }
+
+    async fn transcribe_bytes(
+        &self,
+        audio_data: &[u8],
+        format: &str,
+        config: &TranscriptionConfig,
+    ) -> Result<TranscriptionResult> {
+        let _permit = self.limiter.acquire().await;
+        self.inner
+            .transcribe_bytes(audio_data, format, config)
+            .await
+    }
+
+    async fn start_streaming(
+        &self,
+        config: &TranscriptionConfig,
+        callback: Box<dyn StreamingTranscriptionCallback>,
+    ) -> Result<Box<dyn StreamingSession>> {
+        let _permit = self.limiter.acquire().await;
+        self.inner.start_streaming(config, callback).await
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    fn is_configured(&self) -> bool {
+        self.inner.is_configured()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn supports_pii_redaction(&self) -> bool {
+        self.inner.supports_pii_redaction()
+    }
+
+    fn supports_diarization(&self) -> bool {
+        self.inner.supports_diarization()
+    }
+
+    fn supports_language_detection(&self) -> bool {
+        self.inner.supports_language_detection()
+    }
+
+    fn supported_audio_formats(&self) -> &'static [&'static str] {
+        self.inner.supported_audio_formats()
+    }
+
+    fn max_upload_bytes(&self) -> Option<u64> {
+        self.inner.max_upload_bytes()
+    }
+}
+
+/// Describe what a provider supports; no API key is needed
+pub fn asr_capabilities(provider: &str) -> Result<AsrCapabilities> {
+    let service = build_asr_service(provider, String::new(), None)?;
+    Ok(AsrCapabilities::of(provider, service.as_ref()))
+}
+
+/// Create the service instance for a provider
+fn build_asr_service(
+    provider: &str,
+    api_key: String,
+    settings: Option<&str>,
+) -> Result<Box<dyn TranscriptionServicePort>> {
+    match provider {
+        "assemblyai" => {
+            let settings = settings
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or(serde_json::Value::Null);
+            Ok(Box::new(AssemblyAIService::new(api_key).with_polling(
+                assemblyai::PollingConfig::from_settings(&settings),
+            )))
+        }
         "deepgram" => Ok(Box::new(DeepgramService::new(api_key))),
         _ => Err(AppError::Config(format!(
             "Unknown ASR provider: {}",
-            asr_config.provider
+            provider
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ServiceConfig, ServiceType};
+    use crate::ports::mocks::MockStorage;
+    use crate::ports::transcription::TranscriptionSegment;
+    use crate::utils::keychain::MockKeychain;
+
+    async fn activate(storage: &MockStorage, service_type: ServiceType, provider: &str) {
+        let mut config = ServiceConfig::new(service_type, provider.to_string());
+        config.is_active = true;
+        storage.save_service_config(&config).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_active_assemblyai_service() {
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        activate(&storage, ServiceType::Asr, "assemblyai").await;
+        keychain
+            .save_api_key("asr", "assemblyai", "assemblyai-key")
+            .unwrap();
+
+        let service = get_active_asr_service(&storage, &keychain, &ProviderAsrFactory)
+            .await
+            .unwrap();
+        assert_eq!(service.provider_name(), "AssemblyAI");
+        assert!(service.is_configured());
+    }
+
+    #[tokio::test]
+    async fn test_active_deepgram_service() {
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        activate(&storage, ServiceType::Asr, "deepgram").await;
+        keychain
+            .save_api_key("asr", "deepgram", "deepgram-key")
+            .unwrap();
+
+        let service = get_active_asr_service(&storage, &keychain, &ProviderAsrFactory)
+            .await
+            .unwrap();
+        assert_eq!(service.provider_name(), "Deepgram");
+    }
+
+    #[tokio::test]
+    async fn test_no_active_asr_config() {
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        // An active LLM config must not be picked up as the ASR service
+        activate(&storage, ServiceType::Llm, "openai").await;
+
+        let result = get_active_asr_service(&storage, &keychain, &ProviderAsrFactory).await;
+        assert!(matches!(result, Err(AppError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_missing_api_key() {
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        activate(&storage, ServiceType::Asr, "deepgram").await;
+
+        let result = get_active_asr_service(&storage, &keychain, &ProviderAsrFactory).await;
+        assert!(matches!(result, Err(AppError::Config(_))));
+    }
+
+    fn segment(text: &str, confidence: Option<f32>) -> TranscriptionSegment {
+        TranscriptionSegment {
+            text: text.to_string(),
+            start_ms: 0,
+            end_ms: 1000,
+            speaker_label: None,
+            confidence,
+            language: None,
+        }
+    }
+
+    fn mixed_confidence_result() -> TranscriptionResult {
+        TranscriptionResult {
+            text: "hello uh there world".to_string(),
+            segments: vec![
+                segment("hello", Some(0.95)),
+                segment("uh", Some(0.3)),
+                segment("there", None),
+                segment("world", Some(0.6)),
+            ],
+            confidence: Some(0.7),
+            detected_language: None,
+            model_info: None,
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_low_confidence() {
+        let mut result = mixed_confidence_result();
+        let config = TranscriptionConfig {
+            min_confidence: Some(0.6),
+            ..Default::default()
+        };
+
+        filter_low_confidence(&mut result, &config);
+
+        let texts: Vec<&str> = result.segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["hello", "there", "world"]);
+        assert_eq!(result.text, "hello there world");
+    }
+
+    #[test]
+    fn test_filter_low_confidence_disabled_by_default() {
+        let mut result = mixed_confidence_result();
+
+        filter_low_confidence(&mut result, &TranscriptionConfig::default());
+
+        assert_eq!(result.segments.len(), 4);
+        assert_eq!(result.text, "hello uh there world");
+    }
+
+    fn diarized_result(labels: &[(Option<&str>, i64)]) -> TranscriptionResult {
+        TranscriptionResult {
+            text: String::new(),
+            segments: labels
+                .iter()
+                .map(|&(label, start_ms)| TranscriptionSegment {
+                    start_ms,
+                    end_ms: start_ms + 500,
+                    speaker_label: label.map(String::from),
+                    ..segment("text", None)
+                })
+                .collect(),
+            confidence: None,
+            detected_language: None,
+            model_info: None,
+            request_id: None,
+        }
+    }
+
+    fn speaker_labels(result: &TranscriptionResult) -> Vec<Option<&str>> {
+        result
+            .segments
+            .iter()
+            .map(|s| s.speaker_label.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize_speaker_labels_by_first_appearance() {
+        // Segments aren't necessarily in time order
+        let mut result = diarized_result(&[
+            (Some("Speaker 2"), 1000),
+            (Some("Speaker 1"), 0),
+            (None, 1500),
+            (Some("Speaker 0"), 2000),
+            (Some("Speaker 1"), 3000),
+        ]);
+        let config = TranscriptionConfig {
+            normalize_speaker_labels: true,
+            ..Default::default()
+        };
+
+        normalize_speaker_labels(&mut result, &config);
+
+        assert_eq!(
+            speaker_labels(&result),
+            vec![
+                Some("Speaker 1"),
+                Some("Speaker 0"),
+                None,
+                Some("Speaker 2"),
+                Some("Speaker 0"),
+            ]
+        );
+
+        // AssemblyAI's letter labels are renumbered the same way
+        let mut result = diarized_result(&[(Some("Speaker B"), 0), (Some("Speaker A"), 500)]);
+        normalize_speaker_labels(&mut result, &config);
+        assert_eq!(
+            speaker_labels(&result),
+            vec![Some("Speaker 0"), Some("Speaker 1")]
+        );
+    }
+
+    #[test]
+    fn test_normalize_speaker_labels_disabled_or_multichannel() {
+        let labels = [(Some("Speaker 1"), 0), (Some("Speaker 0"), 500)];
+
+        let mut result = diarized_result(&labels);
+        normalize_speaker_labels(&mut result, &TranscriptionConfig::default());
+        assert_eq!(
+            speaker_labels(&result),
+            vec![Some("Speaker 1"), Some("Speaker 0")]
+        );
+
+        let mut result = diarized_result(&labels);
+        let config = TranscriptionConfig {
+            normalize_speaker_labels: true,
+            multichannel: true,
+            ..Default::default()
+        };
+        normalize_speaker_labels(&mut result, &config);
+        assert_eq!(
+            speaker_labels(&result),
+            vec![Some("Speaker 1"), Some("Speaker 0")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_provider() {
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        activate(&storage, ServiceType::Asr, "whisper").await;
+        keychain.save_api_key("asr", "whisper", "key").unwrap();
+
+        let result = get_active_asr_service(&storage, &keychain, &ProviderAsrFactory).await;
+        assert!(matches!(result, Err(AppError::Config(_))));
+    }
+
+    #[test]
+    fn test_audio_content_type() {
+        assert_eq!(audio_content_type("wav"), "audio/wav");
+        assert_eq!(audio_content_type("opus"), "audio/ogg");
+        assert_eq!(audio_content_type("OGG"), "audio/ogg");
+        assert_eq!(audio_content_type("mp3"), "audio/mpeg");
+        assert_eq!(audio_content_type("flac"), "audio/flac");
+        assert_eq!(audio_content_type("unknown"), "audio/wav");
+
+        assert_eq!(
+            audio_file_content_type("/recordings/meeting_1.opus"),
+            "audio/ogg"
+        );
+        assert_eq!(
+            audio_file_content_type("/recordings/meeting_1.wav"),
+            "audio/wav"
+        );
+        assert_eq!(
+            audio_file_content_type("/recordings/meeting_1"),
+            "audio/wav"
+        );
+    }
+
+    #[test]
+    fn test_split_into_sentences_estimates_times_by_position() {
+        // 40 characters over 10 seconds: 250 ms per character, offset by the start
+        let segment = TranscriptionSegment {
+            text: "One two three. Four five six seven?  End".to_string(),
+            start_ms: 5_000,
+            end_ms: 15_000,
+            speaker_label: Some("Speaker 1".to_string()),
+            confidence: Some(0.7),
+            language: Some("en".to_string()),
+        };
+        let sentences = split_into_sentences(segment.clone());
+        let spans: Vec<_> = sentences
+            .iter()
+            .map(|s| (s.text.as_str(), s.start_ms, s.end_ms))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("One two three.", 5_000, 8_500),
+                ("Four five six seven?", 8_500, 13_750),
+                ("End", 13_750, 15_000),
+            ]
+        );
+        assert!(sentences
+            .iter()
+            .all(|s| s.speaker_label.as_deref() == Some("Speaker 1") && s.confidence == Some(0.7)));
+
+        // Decimals and text without sentence breaks stay whole
+        let single = TranscriptionSegment {
+            text: "Revenue grew 3.5 percent".to_string(),
+            ..segment
+        };
+        let kept = split_into_sentences(single);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(
+            (kept[0].text.as_str(), kept[0].start_ms, kept[0].end_ms),
+            ("Revenue grew 3.5 percent", 5_000, 15_000)
+        );
+    }
+
+    #[test]
+    fn test_check_upload_size() {
+        // 2.4 GB is over both providers' limits, 1.5 GB under them
+        for provider in ["deepgram", "assemblyai"] {
+            let service = build_asr_service(provider, String::new(), None).unwrap();
+            assert!(check_upload_size(service.as_ref(), 1_500_000_000).is_ok());
+            let error = check_upload_size(service.as_ref(), 2_400_000_000).unwrap_err();
+            assert!(matches!(error, AppError::InvalidInput(_)));
+            assert!(error.to_string().contains("The recording is 2.4 GB"));
+        }
+        assert_eq!(
+            asr_capabilities("assemblyai").unwrap().max_upload_bytes,
+            Some(2_200_000_000)
+        );
+
+        assert_eq!(format_size(512), "512 bytes");
+        assert_eq!(format_size(1_500), "1.5 KB");
+        assert_eq!(format_size(2_000_000_000), "2.0 GB");
+    }
+
+    #[test]
+    fn test_asr_capabilities() {
+        let deepgram = asr_capabilities("deepgram").unwrap();
+        assert!(deepgram.supports_streaming);
+        assert!(deepgram.supports_diarization);
+        assert!(deepgram
+            .supported_audio_formats
+            .contains(&"wav".to_string()));
+
+        let assemblyai = asr_capabilities("assemblyai").unwrap();
+        assert!(!assemblyai.supports_streaming);
+        assert!(assemblyai.supports_language_detection);
+
+        assert!(asr_capabilities("whisper").is_err());
+    }
+
+    #[test]
+    fn test_redaction_policies() {
+        fn vendor_name(category: &str) -> Option<&str> {
+            match category {
+                "person_name" | "email_address" => Some("pii"),
+                "us_social_security_number" => Some("ssn"),
+                _ => None,
+            }
+        }
+
+        // Off unless redaction is enabled
+        let mut config = TranscriptionConfig {
+            pii_policies: Some(vec!["person_name".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(redaction_policies(&config, vendor_name, &["pii"]), None);
+
+        // Mapped to vendor names, deduplicated, unknown categories dropped
+        config.enable_pii_redaction = true;
+        config.pii_policies = Some(
+            [
+                "person_name",
+                "email_address",
+                "date_of_birth",
+                "us_social_security_number",
+            ]
+            .iter()
+            .map(|c| c.to_string())
+            .collect(),
+        );
+        assert_eq!(
+            redaction_policies(&config, vendor_name, &["pii"]),
+            Some(vec!["pii".to_string(), "ssn".to_string()])
+        );
+
+        // No list: the provider's defaults
+        config.pii_policies = None;
+        assert_eq!(
+            redaction_policies(&config, vendor_name, &["pii"]),
+            Some(vec!["pii".to_string()])
+        );
+
+        // Nothing mappable (or an empty list) still redacts, with the defaults
+        for categories in [vec!["driver_license".to_string()], Vec::new()] {
+            config.pii_policies = Some(categories);
+            assert_eq!(
+                redaction_policies(&config, vendor_name, &["pii"]),
+                Some(vec!["pii".to_string()])
+            );
+        }
+    }
+}
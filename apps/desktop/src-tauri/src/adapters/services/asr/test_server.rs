@@ -0,0 +1,67 @@
+//! Minimal HTTP endpoint for exercising ASR adapters in tests
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// What the endpoint saw of a request
+pub struct ReceivedRequest {
+    /// Request line and headers, lowercased
+    pub head: String,
+    /// Number of body bytes read (the body itself is discarded)
+    pub body_len: u64,
+}
+
+/// Accept a single request and answer it with `200 OK` and a JSON body
+///
+/// Returns the base URL to point the adapter at and a handle that resolves once
+/// the request has been read.
+pub async fn serve_once(response_json: &'static str) -> (String, JoinHandle<ReceivedRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut head = Vec::new();
+
+        // Read until the end of the headers, keeping any body bytes that came along
+        let mut body_len = loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            assert!(n > 0, "connection closed before headers were complete");
+            head.extend_from_slice(&buf[..n]);
+            if let Some(end) = head.windows(4).position(|w| w == b"\r\n\r\n") {
+                let body_start = end + 4;
+                let already_read = (head.len() - body_start) as u64;
+                head.truncate(end);
+                break already_read;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&head).to_lowercase();
+        let content_length: u64 = head
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .expect("request has no content-length")
+            .trim()
+            .parse()
+            .unwrap();
+
+        while body_len < content_length {
+            let n = socket.read(&mut buf).await.unwrap();
+            assert!(n > 0, "connection closed before the body was complete");
+            body_len += n as u64;
+        }
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            response_json.len(),
+            response_json
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+
+        ReceivedRequest { head, body_len }
+    });
+
+    (base_url, handle)
+}
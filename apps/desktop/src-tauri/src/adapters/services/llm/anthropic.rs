@@ -3,9 +3,12 @@
 //! Implements the LlmServicePort for Anthropic's API (Claude models)
 //! Supports dynamic model fetching and customizable prompts.
 
+use super::{apply_model_overrides, Completion};
 use crate::domain::models::InsightType;
+use crate::domain::models::ModelOverride;
 use crate::error::{AppError, Result};
 use crate::ports::llm::{GeneratedInsight, InsightRequest, LlmConfig, LlmServicePort, ModelInfo};
+use crate::utils::http::shared_client;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -18,6 +21,7 @@ const ANTHROPIC_API_VERSION: &str = "2023-06-01";
 pub struct AnthropicService {
     client: Client,
     api_key: String,
+    model_overrides: Vec<ModelOverride>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +41,16 @@ struct AnthropicModelsResponse {
     last_id: Option<String>,
 }
 
+/// Assistant prefill used in JSON mode
+///
+/// Anthropic has no `response_format` option; starting the assistant turn with `{`
+/// makes the model continue with a JSON object. The prefill is not echoed back, so it
+/// is prepended to the returned text.
+const JSON_PREFILL: &str = "{";
+
+/// Stop reason when `max_tokens` was reached
+const STOP_REASON_MAX_TOKENS: &str = "max_tokens";
+
 #[derive(Debug, Serialize)]
 struct MessagesRequest {
     model: String,
@@ -46,6 +60,29 @@ struct MessagesRequest {
     temperature: Option<f32>,
 }
 
+impl MessagesRequest {
+    fn new(prompt: String, config: &LlmConfig) -> Self {
+        let mut messages = vec![Message {
+            role: "user".to_string(),
+            content: prompt,
+        }];
+        if config.json_mode {
+            messages.push(Message {
+                role: "assistant".to_string(),
+                content: JSON_PREFILL.to_string(),
+            });
+        }
+
+        Self {
+            model: config.model.clone(),
+            messages,
+            // Anthropic requires max_tokens to be specified
+            max_tokens: config.max_tokens.unwrap_or(4096),
+            temperature: config.temperature,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Message {
     role: String,
@@ -81,12 +118,19 @@ struct Usage {
 impl AnthropicService {
     /// Create a new Anthropic service with the given API key
     pub fn new(api_key: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(120))
-            .build()
-            .expect("Failed to create HTTP client");
+        let client = shared_client(Duration::from_secs(120));
 
-        Self { client, api_key }
+        Self {
+            client,
+            api_key,
+            model_overrides: Vec::new(),
+        }
+    }
+
+    /// Use the user's context window overrides for models listed by `fetch_available_models`
+    pub fn with_model_overrides(mut self, model_overrides: Vec<ModelOverride>) -> Self {
+        self.model_overrides = model_overrides;
+        self
     }
 
     /// Fetch available models from Anthropic API
@@ -126,27 +170,14 @@ impl AnthropicService {
         transcript: &str,
         context: Option<&str>,
         config: &LlmConfig,
-    ) -> Result<String> {
+    ) -> Result<Completion> {
         // Replace placeholders in prompt
         let context_str = context.unwrap_or("");
         let formatted_prompt = prompt
             .replace("{transcript}", transcript)
             .replace("{context}", context_str);
 
-        let messages = vec![Message {
-            role: "user".to_string(),
-            content: formatted_prompt,
-        }];
-
-        // Anthropic requires max_tokens to be specified
-        let max_tokens = config.max_tokens.unwrap_or(4096);
-
-        let request_body = MessagesRequest {
-            model: config.model.clone(),
-            messages,
-            max_tokens,
-            temperature: config.temperature,
-        };
+        let request_body = MessagesRequest::new(formatted_prompt, config);
 
         log::info!(
             "Calling Anthropic messages API with model: {}",
@@ -176,19 +207,27 @@ impl AnthropicService {
             AppError::LlmService(format!("Failed to parse messages response: {}", e))
         })?;
 
-        if messages_response.content.is_empty() {
-            return Err(AppError::LlmService(
-                "No content blocks returned".to_string(),
-            ));
+        let text = messages_response
+            .content
+            .into_iter()
+            .next()
+            .map(|block| block.text)
+            .unwrap_or_default();
+        let mut completion = Completion::new(
+            "Anthropic",
+            text,
+            messages_response.stop_reason.as_deref(),
+            STOP_REASON_MAX_TOKENS,
+        )?;
+        if config.json_mode {
+            completion.content.insert_str(0, JSON_PREFILL);
         }
-
-        let content = messages_response.content[0].text.clone();
         log::info!(
             "Anthropic completion successful, generated {} characters",
-            content.len()
+            completion.content.len()
         );
 
-        Ok(content)
+        Ok(completion)
     }
 
     /// Get estimated context window for a model
@@ -246,7 +285,7 @@ impl LlmServicePort for AnthropicService {
                 crate::domain::PromptTemplates::for_type(insight_type).to_string()
             };
 
-            let content = self
+            let completion = self
                 .generate_with_prompt(
                     &prompt,
                     &request.transcript,
@@ -257,8 +296,8 @@ impl LlmServicePort for AnthropicService {
 
             insights.push(GeneratedInsight {
                 insight_type: insight_type.clone(),
-                content,
-                metadata: None,
+                metadata: completion.metadata(),
+                content: completion.content,
             });
         }
 
@@ -278,14 +317,16 @@ impl LlmServicePort for AnthropicService {
             crate::domain::PromptTemplates::summary().to_string()
         };
 
-        self.generate_with_prompt(&prompt, transcript, context, config)
-            .await
+        Ok(self
+            .generate_with_prompt(&prompt, transcript, context, config)
+            .await?
+            .content)
     }
 
     async fn fetch_available_models(&self) -> Result<Vec<ModelInfo>> {
-        let models = self.list_models().await?;
-
-        Ok(models
+        let mut models: Vec<ModelInfo> = self
+            .list_models()
+            .await?
             .into_iter()
             .map(|m| {
                 let (context_window, is_fallback) = Self::get_context_window(&m.id);
@@ -297,7 +338,10 @@ impl LlmServicePort for AnthropicService {
                     is_fallback_context_window: if is_fallback { Some(true) } else { None },
                 }
             })
-            .collect())
+            .collect();
+
+        apply_model_overrides(&mut models, &self.model_overrides);
+        Ok(models)
     }
 
     fn provider_name(&self) -> &str {
@@ -350,4 +394,21 @@ mod tests {
             (100000, true)
         );
     }
+
+    #[test]
+    fn test_json_mode_prefills_assistant_turn() {
+        let request = MessagesRequest::new("Summarize".to_string(), &LlmConfig::default());
+        assert_eq!(request.messages.len(), 1);
+
+        let config = LlmConfig {
+            json_mode: true,
+            ..Default::default()
+        };
+        let body =
+            serde_json::to_value(MessagesRequest::new("Summarize".to_string(), &config)).unwrap();
+        assert_eq!(
+            body["messages"][1],
+            serde_json::json!({ "role": "assistant", "content": "{" })
+        );
+    }
 }
@@ -0,0 +1,315 @@
+//! Azure OpenAI LLM service adapter
+//!
+//! Implements the LlmServicePort for Azure OpenAI Service. Azure uses the same
+//! chat completion body as OpenAI, but requests are routed to a deployment on the
+//! customer's resource and authenticated with an `api-key` header:
+//! `{endpoint}/openai/deployments/{deployment}/chat/completions?api-version=...`
+
+use super::openai::{
+    ChatCompletionRequest, ChatCompletionResponse, ChatMessage, FINISH_REASON_LENGTH,
+};
+use super::{apply_model_overrides, Completion};
+use crate::domain::models::ModelOverride;
+use crate::error::{AppError, Result};
+use crate::ports::llm::{GeneratedInsight, InsightRequest, LlmConfig, LlmServicePort, ModelInfo};
+use crate::utils::http::{check_api_key, shared_client};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::time::Duration;
+
+/// Provider ID for Azure OpenAI, whose endpoint and deployment live in the service settings
+pub const AZURE_OPENAI_PROVIDER: &str = "azure-openai";
+
+/// API version used when the service settings don't specify one
+pub const DEFAULT_API_VERSION: &str = "2024-06-01";
+
+/// Context window assumed for deployments (Azure doesn't report it per deployment)
+const DEFAULT_CONTEXT_WINDOW: usize = 128000;
+
+/// Azure OpenAI service implementation
+pub struct AzureOpenAIService {
+    client: Client,
+    api_key: String,
+    endpoint: String,
+    deployment: Option<String>,
+    api_version: String,
+    model_overrides: Vec<ModelOverride>,
+}
+
+impl AzureOpenAIService {
+    /// Create a new Azure OpenAI service
+    ///
+    /// `endpoint` is the resource URL (e.g. `https://my-resource.openai.azure.com`).
+    /// When `deployment` is None, the model name from each request is used as the deployment.
+    pub fn new(
+        api_key: String,
+        endpoint: String,
+        deployment: Option<String>,
+        api_version: Option<String>,
+    ) -> Self {
+        let client = shared_client(Duration::from_secs(120));
+
+        Self {
+            client,
+            api_key,
+            endpoint: endpoint.trim().trim_end_matches('/').to_string(),
+            deployment: deployment.filter(|d| !d.trim().is_empty()),
+            api_version: api_version
+                .filter(|v| !v.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_API_VERSION.to_string()),
+            model_overrides: Vec::new(),
+        }
+    }
+
+    /// Use the user's context window override for the deployment instead of the default
+    pub fn with_model_overrides(mut self, model_overrides: Vec<ModelOverride>) -> Self {
+        self.model_overrides = model_overrides;
+        self
+    }
+
+    /// Create a service from the `settings` JSON of the `azure-openai` service config
+    ///
+    /// Expects `endpoint` and optionally `deployment` and `api_version`.
+    pub fn from_settings(api_key: String, settings: &serde_json::Value) -> Result<Self> {
+        let get = |key: &str| {
+            settings
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+
+        let endpoint = get("endpoint")
+            .filter(|e| !e.trim().is_empty())
+            .ok_or_else(|| {
+                AppError::Config("Azure OpenAI endpoint is not configured".to_string())
+            })?;
+
+        Ok(Self::new(
+            api_key,
+            endpoint,
+            get("deployment"),
+            get("api_version"),
+        ))
+    }
+
+    /// Build the chat completions URL for a deployment
+    fn chat_completions_url(&self, deployment: &str) -> String {
+        format!(
+            "{}/openai/deployments/{}/chat/completions?api-version={}",
+            self.endpoint, deployment, self.api_version
+        )
+    }
+
+    /// Check that the endpoint accepts the API key by listing the resource's models
+    pub async fn verify_api_key(&self) -> Result<()> {
+        check_api_key(
+            self.client
+                .get(format!(
+                    "{}/openai/models?api-version={}",
+                    self.endpoint, self.api_version
+                ))
+                .header("api-key", &self.api_key),
+            "Azure OpenAI",
+            AppError::LlmService,
+        )
+        .await
+    }
+
+    /// Generate text using the deployment's chat completion API
+    async fn generate_with_prompt(
+        &self,
+        prompt: &str,
+        transcript: &str,
+        context: Option<&str>,
+        config: &LlmConfig,
+    ) -> Result<Completion> {
+        let context_str = context.unwrap_or("");
+        let formatted_prompt = prompt
+            .replace("{transcript}", transcript)
+            .replace("{context}", context_str);
+
+        let deployment = self.deployment.as_deref().unwrap_or(&config.model);
+
+        let request_body = ChatCompletionRequest::new(
+            vec![ChatMessage {
+                role: "user".to_string(),
+                content: formatted_prompt,
+            }],
+            config,
+        );
+
+        log::info!(
+            "Calling Azure OpenAI chat completion with deployment: {}",
+            deployment
+        );
+
+        let response = self
+            .client
+            .post(self.chat_completions_url(deployment))
+            .header("api-key", &self.api_key)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| AppError::LlmService(format!("Chat completion request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::LlmService(format!(
+                "Chat completion failed: {}",
+                error_text
+            )));
+        }
+
+        let completion_response: ChatCompletionResponse = response.json().await.map_err(|e| {
+            AppError::LlmService(format!("Failed to parse completion response: {}", e))
+        })?;
+
+        let choice = completion_response
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::LlmService("No completion choices returned".to_string()))?;
+
+        let completion = Completion::new(
+            "Azure OpenAI",
+            choice.message.content,
+            choice.finish_reason.as_deref(),
+            FINISH_REASON_LENGTH,
+        )?;
+        log::info!(
+            "Azure OpenAI completion successful, generated {} characters",
+            completion.content.len()
+        );
+
+        Ok(completion)
+    }
+}
+
+#[async_trait]
+impl LlmServicePort for AzureOpenAIService {
+    async fn generate_insights(
+        &self,
+        request: &InsightRequest,
+        config: &LlmConfig,
+        prompt_template: Option<&str>,
+    ) -> Result<Vec<GeneratedInsight>> {
+        let mut insights = Vec::new();
+
+        for insight_type in &request.insight_types {
+            let prompt = if let Some(template) = prompt_template {
+                template.to_string()
+            } else {
+                crate::domain::PromptTemplates::for_type(insight_type).to_string()
+            };
+
+            let completion = self
+                .generate_with_prompt(
+                    &prompt,
+                    &request.transcript,
+                    request.context.as_deref(),
+                    config,
+                )
+                .await?;
+
+            insights.push(GeneratedInsight {
+                insight_type: insight_type.clone(),
+                metadata: completion.metadata(),
+                content: completion.content,
+            });
+        }
+
+        Ok(insights)
+    }
+
+    async fn generate_summary(
+        &self,
+        transcript: &str,
+        context: Option<&str>,
+        config: &LlmConfig,
+        prompt_template: Option<&str>,
+    ) -> Result<String> {
+        let prompt = if let Some(template) = prompt_template {
+            template.to_string()
+        } else {
+            crate::domain::PromptTemplates::summary().to_string()
+        };
+
+        Ok(self
+            .generate_with_prompt(&prompt, transcript, context, config)
+            .await?
+            .content)
+    }
+
+    /// Azure deployments are created by the customer, so the configured deployment is
+    /// the only "model" available
+    async fn fetch_available_models(&self) -> Result<Vec<ModelInfo>> {
+        let deployment = self.deployment.clone().ok_or_else(|| {
+            AppError::Config("Azure OpenAI deployment is not configured".to_string())
+        })?;
+
+        let mut models = vec![ModelInfo {
+            id: deployment.clone(),
+            name: deployment,
+            provider: "azure-openai".to_string(),
+            context_window: DEFAULT_CONTEXT_WINDOW,
+            is_fallback_context_window: Some(true),
+        }];
+        apply_model_overrides(&mut models, &self.model_overrides);
+        Ok(models)
+    }
+
+    fn provider_name(&self) -> &str {
+        "azure-openai"
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.api_key.is_empty() && !self.endpoint.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_azure_openai_service_creation() {
+        let service = AzureOpenAIService::new(
+            "test_api_key".to_string(),
+            "https://my-resource.openai.azure.com".to_string(),
+            Some("gpt-4o".to_string()),
+            None,
+        );
+        assert_eq!(service.provider_name(), "azure-openai");
+        assert!(service.is_configured());
+        assert_eq!(service.api_version, DEFAULT_API_VERSION);
+    }
+
+    #[test]
+    fn test_chat_completions_url() {
+        let service = AzureOpenAIService::new(
+            "key".to_string(),
+            "https://my-resource.openai.azure.com/".to_string(),
+            None,
+            Some("2024-02-01".to_string()),
+        );
+        assert_eq!(
+            service.chat_completions_url("gpt-4o"),
+            "https://my-resource.openai.azure.com/openai/deployments/gpt-4o/chat/completions?api-version=2024-02-01"
+        );
+    }
+
+    #[test]
+    fn test_from_settings() {
+        let settings = serde_json::json!({
+            "endpoint": "https://my-resource.openai.azure.com",
+            "deployment": "insights",
+            "api_version": "2024-06-01",
+        });
+        let service = AzureOpenAIService::from_settings("key".to_string(), &settings).unwrap();
+        assert_eq!(service.deployment.as_deref(), Some("insights"));
+
+        let missing_endpoint = serde_json::json!({ "deployment": "insights" });
+        assert!(AzureOpenAIService::from_settings("key".to_string(), &missing_endpoint).is_err());
+    }
+}
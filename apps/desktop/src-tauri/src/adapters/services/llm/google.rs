@@ -3,9 +3,12 @@
 //! Implements the LlmServicePort for Google's Gemini API
 //! Supports dynamic model fetching and customizable prompts.
 
+use super::{apply_model_overrides, Completion};
 use crate::domain::models::InsightType;
+use crate::domain::models::ModelOverride;
 use crate::error::{AppError, Result};
 use crate::ports::llm::{GeneratedInsight, InsightRequest, LlmConfig, LlmServicePort, ModelInfo};
+use crate::utils::http::shared_client;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -13,10 +16,14 @@ use std::time::Duration;
 
 const GOOGLE_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
 
+/// Candidate finish reason when `maxOutputTokens` was reached
+const FINISH_REASON_MAX_TOKENS: &str = "MAX_TOKENS";
+
 /// Google Gemini service implementation
 pub struct GoogleService {
     client: Client,
     api_key: String,
+    model_overrides: Vec<ModelOverride>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -63,6 +70,19 @@ struct GenerationConfig {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_output_tokens: Option<u32>,
+    /// "application/json" in JSON mode; omitted for plain text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<&'static str>,
+}
+
+impl GenerationConfig {
+    fn new(config: &LlmConfig) -> Self {
+        Self {
+            temperature: config.temperature,
+            max_output_tokens: config.max_tokens,
+            response_mime_type: config.json_mode.then_some("application/json"),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -82,6 +102,8 @@ struct Candidate {
 
 #[derive(Debug, Deserialize)]
 struct ResponseContent {
+    /// Absent when generation stopped before producing any text
+    #[serde(default)]
     parts: Vec<ResponsePart>,
     role: String,
 }
@@ -104,12 +126,19 @@ struct UsageMetadata {
 impl GoogleService {
     /// Create a new Google Gemini service with the given API key
     pub fn new(api_key: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(120))
-            .build()
-            .expect("Failed to create HTTP client");
+        let client = shared_client(Duration::from_secs(120));
+
+        Self {
+            client,
+            api_key,
+            model_overrides: Vec::new(),
+        }
+    }
 
-        Self { client, api_key }
+    /// Use the user's context window overrides for models listed by `fetch_available_models`
+    pub fn with_model_overrides(mut self, model_overrides: Vec<ModelOverride>) -> Self {
+        self.model_overrides = model_overrides;
+        self
     }
 
     /// Fetch available models from Google API
@@ -159,7 +188,7 @@ impl GoogleService {
         transcript: &str,
         context: Option<&str>,
         config: &LlmConfig,
-    ) -> Result<String> {
+    ) -> Result<Completion> {
         // Replace placeholders in prompt
         let context_str = context.unwrap_or("");
         let formatted_prompt = prompt
@@ -172,10 +201,7 @@ impl GoogleService {
             }],
         }];
 
-        let generation_config = Some(GenerationConfig {
-            temperature: config.temperature,
-            max_output_tokens: config.max_tokens,
-        });
+        let generation_config = Some(GenerationConfig::new(config));
 
         let request_body = GenerateContentRequest {
             contents,
@@ -216,23 +242,31 @@ impl GoogleService {
             AppError::LlmService(format!("Failed to parse content response: {}", e))
         })?;
 
-        if content_response.candidates.is_empty() {
-            return Err(AppError::LlmService("No candidates returned".to_string()));
-        }
-
-        if content_response.candidates[0].content.parts.is_empty() {
-            return Err(AppError::LlmService(
-                "No content parts in response".to_string(),
-            ));
-        }
+        let candidate = content_response
+            .candidates
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::LlmService("No candidates returned".to_string()))?;
 
-        let content = content_response.candidates[0].content.parts[0].text.clone();
+        let text = candidate
+            .content
+            .parts
+            .into_iter()
+            .next()
+            .map(|part| part.text)
+            .unwrap_or_default();
+        let completion = Completion::new(
+            "Google",
+            text,
+            candidate.finish_reason.as_deref(),
+            FINISH_REASON_MAX_TOKENS,
+        )?;
         log::info!(
             "Google completion successful, generated {} characters",
-            content.len()
+            completion.content.len()
         );
 
-        Ok(content)
+        Ok(completion)
     }
 
     /// Get estimated context window for a model
@@ -273,7 +307,7 @@ impl LlmServicePort for GoogleService {
                 crate::domain::PromptTemplates::for_type(insight_type).to_string()
             };
 
-            let content = self
+            let completion = self
                 .generate_with_prompt(
                     &prompt,
                     &request.transcript,
@@ -284,8 +318,8 @@ impl LlmServicePort for GoogleService {
 
             insights.push(GeneratedInsight {
                 insight_type: insight_type.clone(),
-                content,
-                metadata: None,
+                metadata: completion.metadata(),
+                content: completion.content,
             });
         }
 
@@ -305,14 +339,16 @@ impl LlmServicePort for GoogleService {
             crate::domain::PromptTemplates::summary().to_string()
         };
 
-        self.generate_with_prompt(&prompt, transcript, context, config)
-            .await
+        Ok(self
+            .generate_with_prompt(&prompt, transcript, context, config)
+            .await?
+            .content)
     }
 
     async fn fetch_available_models(&self) -> Result<Vec<ModelInfo>> {
-        let models = self.list_models().await?;
-
-        Ok(models
+        let mut models: Vec<ModelInfo> = self
+            .list_models()
+            .await?
             .into_iter()
             .map(|m| {
                 // Extract just the model name from "models/gemini-pro" format
@@ -331,7 +367,10 @@ impl LlmServicePort for GoogleService {
                     is_fallback_context_window: if is_fallback { Some(true) } else { None },
                 }
             })
-            .collect())
+            .collect();
+
+        apply_model_overrides(&mut models, &self.model_overrides);
+        Ok(models)
     }
 
     fn provider_name(&self) -> &str {
@@ -378,4 +417,17 @@ mod tests {
             100000
         );
     }
+
+    #[test]
+    fn test_response_mime_type_only_in_json_mode() {
+        let body = serde_json::to_value(GenerationConfig::new(&LlmConfig::default())).unwrap();
+        assert!(body.get("response_mime_type").is_none());
+
+        let config = LlmConfig {
+            json_mode: true,
+            ..Default::default()
+        };
+        let body = serde_json::to_value(GenerationConfig::new(&config)).unwrap();
+        assert_eq!(body["response_mime_type"], "application/json");
+    }
 }
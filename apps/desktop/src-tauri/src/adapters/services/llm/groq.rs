@@ -4,9 +4,13 @@
 //! Uses OpenAI-compatible API for easy integration
 //! Supports dynamic model fetching and customizable prompts.
 
+use super::openai::{ResponseFormat, FINISH_REASON_LENGTH};
+use super::{apply_model_overrides, Completion};
 use crate::domain::models::InsightType;
+use crate::domain::models::ModelOverride;
 use crate::error::{AppError, Result};
 use crate::ports::llm::{GeneratedInsight, InsightRequest, LlmConfig, LlmServicePort, ModelInfo};
+use crate::utils::http::shared_client;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -18,6 +22,7 @@ const GROQ_API_BASE: &str = "https://api.groq.com/openai/v1";
 pub struct GroqService {
     client: Client,
     api_key: String,
+    model_overrides: Vec<ModelOverride>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,6 +49,20 @@ struct ChatCompletionRequest {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+}
+
+impl ChatCompletionRequest {
+    fn new(messages: Vec<ChatMessage>, config: &LlmConfig) -> Self {
+        Self {
+            model: config.model.clone(),
+            messages,
+            temperature: config.temperature,
+            max_tokens: config.max_tokens,
+            response_format: ResponseFormat::for_config(config),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -71,12 +90,19 @@ struct ChatChoice {
 impl GroqService {
     /// Create a new Groq service with the given API key
     pub fn new(api_key: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(120))
-            .build()
-            .expect("Failed to create HTTP client");
+        let client = shared_client(Duration::from_secs(120));
+
+        Self {
+            client,
+            api_key,
+            model_overrides: Vec::new(),
+        }
+    }
 
-        Self { client, api_key }
+    /// Use the user's context window overrides for models listed by `fetch_available_models`
+    pub fn with_model_overrides(mut self, model_overrides: Vec<ModelOverride>) -> Self {
+        self.model_overrides = model_overrides;
+        self
     }
 
     /// Fetch available models from Groq API
@@ -122,7 +148,7 @@ impl GroqService {
         transcript: &str,
         context: Option<&str>,
         config: &LlmConfig,
-    ) -> Result<String> {
+    ) -> Result<Completion> {
         // Replace placeholders in prompt
         let context_str = context.unwrap_or("");
         let formatted_prompt = prompt
@@ -134,12 +160,7 @@ impl GroqService {
             content: formatted_prompt,
         }];
 
-        let request_body = ChatCompletionRequest {
-            model: config.model.clone(),
-            messages,
-            temperature: config.temperature,
-            max_tokens: config.max_tokens,
-        };
+        let request_body = ChatCompletionRequest::new(messages, config);
 
         log::info!("Calling Groq chat completion with model: {}", config.model);
 
@@ -165,19 +186,24 @@ impl GroqService {
             AppError::LlmService(format!("Failed to parse completion response: {}", e))
         })?;
 
-        if completion_response.choices.is_empty() {
-            return Err(AppError::LlmService(
-                "No completion choices returned".to_string(),
-            ));
-        }
-
-        let content = completion_response.choices[0].message.content.clone();
+        let choice = completion_response
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::LlmService("No completion choices returned".to_string()))?;
+
+        let completion = Completion::new(
+            "Groq",
+            choice.message.content,
+            choice.finish_reason.as_deref(),
+            FINISH_REASON_LENGTH,
+        )?;
         log::info!(
             "Groq completion successful, generated {} characters",
-            content.len()
+            completion.content.len()
         );
 
-        Ok(content)
+        Ok(completion)
     }
 
     /// Get estimated context window for a model
@@ -220,7 +246,7 @@ impl LlmServicePort for GroqService {
                 crate::domain::PromptTemplates::for_type(insight_type).to_string()
             };
 
-            let content = self
+            let completion = self
                 .generate_with_prompt(
                     &prompt,
                     &request.transcript,
@@ -231,8 +257,8 @@ impl LlmServicePort for GroqService {
 
             insights.push(GeneratedInsight {
                 insight_type: insight_type.clone(),
-                content,
-                metadata: None,
+                metadata: completion.metadata(),
+                content: completion.content,
             });
         }
 
@@ -252,14 +278,16 @@ impl LlmServicePort for GroqService {
             crate::domain::PromptTemplates::summary().to_string()
         };
 
-        self.generate_with_prompt(&prompt, transcript, context, config)
-            .await
+        Ok(self
+            .generate_with_prompt(&prompt, transcript, context, config)
+            .await?
+            .content)
     }
 
     async fn fetch_available_models(&self) -> Result<Vec<ModelInfo>> {
-        let models = self.list_models().await?;
-
-        Ok(models
+        let mut models: Vec<ModelInfo> = self
+            .list_models()
+            .await?
             .into_iter()
             .map(|m| {
                 let is_fallback = m.context_window.is_none();
@@ -271,7 +299,10 @@ impl LlmServicePort for GroqService {
                     is_fallback_context_window: if is_fallback { Some(true) } else { None },
                 }
             })
-            .collect())
+            .collect();
+
+        apply_model_overrides(&mut models, &self.model_overrides);
+        Ok(models)
     }
 
     fn provider_name(&self) -> &str {
@@ -321,4 +352,28 @@ mod tests {
             131072
         );
     }
+
+    #[test]
+    fn test_response_format_only_in_json_mode() {
+        let messages = || {
+            vec![ChatMessage {
+                role: "user".to_string(),
+                content: "Summarize".to_string(),
+            }]
+        };
+
+        let body = serde_json::to_value(ChatCompletionRequest::new(
+            messages(),
+            &LlmConfig::default(),
+        ))
+        .unwrap();
+        assert!(body.get("response_format").is_none());
+
+        let config = LlmConfig {
+            json_mode: true,
+            ..Default::default()
+        };
+        let body = serde_json::to_value(ChatCompletionRequest::new(messages(), &config)).unwrap();
+        assert_eq!(body["response_format"]["type"], "json_object");
+    }
 }
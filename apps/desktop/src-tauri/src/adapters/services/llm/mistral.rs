@@ -0,0 +1,377 @@
+//! Mistral LLM service adapter
+//!
+//! Implements the LlmServicePort for Mistral's La Plateforme API
+//! The chat completion API is OpenAI-compatible, so the OpenAI request/response types are reused.
+//! Supports dynamic model fetching and customizable prompts.
+
+use super::openai::{
+    ChatCompletionRequest, ChatCompletionResponse, ChatMessage, FINISH_REASON_LENGTH,
+};
+use super::{apply_model_overrides, Completion};
+use crate::domain::models::ModelOverride;
+use crate::error::{AppError, Result};
+use crate::ports::llm::{GeneratedInsight, InsightRequest, LlmConfig, LlmServicePort, ModelInfo};
+use crate::utils::http::shared_client;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+const MISTRAL_API_BASE: &str = "https://api.mistral.ai/v1";
+
+/// Mistral service implementation
+pub struct MistralService {
+    client: Client,
+    api_key: String,
+    model_overrides: Vec<ModelOverride>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MistralModel {
+    id: String,
+    name: Option<String>,
+    max_context_length: Option<u32>,
+    capabilities: Option<MistralCapabilities>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MistralCapabilities {
+    #[serde(default)]
+    completion_chat: bool,
+}
+
+impl MistralModel {
+    /// Whether the model supports chat completions (embedding, OCR and moderation
+    /// models don't); models without capability info are assumed to
+    fn is_chat_model(&self) -> bool {
+        self.capabilities.as_ref().is_none_or(|c| c.completion_chat)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MistralModelsResponse {
+    data: Vec<MistralModel>,
+}
+
+impl MistralService {
+    /// Create a new Mistral service with the given API key
+    pub fn new(api_key: String) -> Self {
+        let client = shared_client(Duration::from_secs(120));
+
+        Self {
+            client,
+            api_key,
+            model_overrides: Vec::new(),
+        }
+    }
+
+    /// Use the user's context window overrides for models listed by `fetch_available_models`
+    pub fn with_model_overrides(mut self, model_overrides: Vec<ModelOverride>) -> Self {
+        self.model_overrides = model_overrides;
+        self
+    }
+
+    /// Fetch available models from Mistral API
+    async fn list_models(&self) -> Result<Vec<MistralModel>> {
+        log::info!("Fetching available models from Mistral");
+
+        let response = self
+            .client
+            .get(format!("{}/models", MISTRAL_API_BASE))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send()
+            .await
+            .map_err(|e| AppError::LlmService(format!("Failed to fetch models: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::LlmService(format!(
+                "Failed to fetch models: {}",
+                error_text
+            )));
+        }
+
+        let models_response: MistralModelsResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::LlmService(format!("Failed to parse models response: {}", e)))?;
+
+        let chat_models: Vec<MistralModel> = models_response
+            .data
+            .into_iter()
+            .filter(MistralModel::is_chat_model)
+            .collect();
+
+        log::info!("Found {} Mistral chat models", chat_models.len());
+        Ok(chat_models)
+    }
+
+    /// Generate text using chat completion API (OpenAI-compatible)
+    async fn generate_with_prompt(
+        &self,
+        prompt: &str,
+        transcript: &str,
+        context: Option<&str>,
+        config: &LlmConfig,
+    ) -> Result<Completion> {
+        // Replace placeholders in prompt
+        let context_str = context.unwrap_or("");
+        let formatted_prompt = prompt
+            .replace("{transcript}", transcript)
+            .replace("{context}", context_str);
+
+        let request_body = ChatCompletionRequest::new(
+            vec![ChatMessage {
+                role: "user".to_string(),
+                content: formatted_prompt,
+            }],
+            config,
+        );
+
+        log::info!(
+            "Calling Mistral chat completion with model: {}",
+            config.model
+        );
+
+        let response = self
+            .client
+            .post(format!("{}/chat/completions", MISTRAL_API_BASE))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| AppError::LlmService(format!("Chat completion request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::LlmService(format!(
+                "Chat completion failed: {}",
+                error_text
+            )));
+        }
+
+        let completion_response: ChatCompletionResponse = response.json().await.map_err(|e| {
+            AppError::LlmService(format!("Failed to parse completion response: {}", e))
+        })?;
+
+        let choice = completion_response
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::LlmService("No completion choices returned".to_string()))?;
+
+        let completion = Completion::new(
+            "Mistral",
+            choice.message.content,
+            choice.finish_reason.as_deref(),
+            FINISH_REASON_LENGTH,
+        )?;
+        log::info!(
+            "Mistral completion successful, generated {} characters",
+            completion.content.len()
+        );
+
+        Ok(completion)
+    }
+
+    /// Get estimated context window for a model
+    /// Returns (context_window, is_fallback)
+    fn get_context_window(model_id: &str, api_context_length: Option<u32>) -> (usize, bool) {
+        // Use API-provided context length if available
+        if let Some(length) = api_context_length {
+            return (length as usize, false);
+        }
+
+        // Fall back to known context windows for common models
+        if model_id.contains("codestral") {
+            (256000, false) // 256k tokens
+        } else if model_id.contains("mistral-large")
+            || model_id.contains("mistral-medium")
+            || model_id.contains("mistral-small")
+            || model_id.contains("ministral")
+            || model_id.contains("pixtral")
+            || model_id.contains("mistral-nemo")
+        {
+            (131072, false) // 128k tokens
+        } else if model_id.contains("mixtral-8x22b") {
+            (65536, false) // 64k tokens
+        } else if model_id.contains("mixtral-8x7b") || model_id.contains("mistral-7b") {
+            (32768, false) // 32k tokens
+        } else {
+            // Unknown model - use conservative fallback
+            log::warn!(
+                "Unknown Mistral model '{}' - using fallback context window of 32768 tokens. \
+                Consider configuring a custom context window for this model in settings.",
+                model_id
+            );
+            (32768, true)
+        }
+    }
+}
+
+#[async_trait]
+impl LlmServicePort for MistralService {
+    async fn generate_insights(
+        &self,
+        request: &InsightRequest,
+        config: &LlmConfig,
+        prompt_template: Option<&str>,
+    ) -> Result<Vec<GeneratedInsight>> {
+        let mut insights = Vec::new();
+
+        for insight_type in &request.insight_types {
+            // Use custom prompt or fall back to default
+            let prompt = if let Some(template) = prompt_template {
+                template.to_string()
+            } else {
+                crate::domain::PromptTemplates::for_type(insight_type).to_string()
+            };
+
+            let completion = self
+                .generate_with_prompt(
+                    &prompt,
+                    &request.transcript,
+                    request.context.as_deref(),
+                    config,
+                )
+                .await?;
+
+            insights.push(GeneratedInsight {
+                insight_type: insight_type.clone(),
+                metadata: completion.metadata(),
+                content: completion.content,
+            });
+        }
+
+        Ok(insights)
+    }
+
+    async fn generate_summary(
+        &self,
+        transcript: &str,
+        context: Option<&str>,
+        config: &LlmConfig,
+        prompt_template: Option<&str>,
+    ) -> Result<String> {
+        let prompt = if let Some(template) = prompt_template {
+            template.to_string()
+        } else {
+            crate::domain::PromptTemplates::summary().to_string()
+        };
+
+        Ok(self
+            .generate_with_prompt(&prompt, transcript, context, config)
+            .await?
+            .content)
+    }
+
+    async fn fetch_available_models(&self) -> Result<Vec<ModelInfo>> {
+        let mut models: Vec<ModelInfo> = self
+            .list_models()
+            .await?
+            .into_iter()
+            .map(|m| {
+                let (context_window, is_fallback) =
+                    Self::get_context_window(&m.id, m.max_context_length);
+                ModelInfo {
+                    name: m.name.unwrap_or_else(|| m.id.clone()),
+                    id: m.id,
+                    provider: "mistral".to_string(),
+                    context_window,
+                    is_fallback_context_window: if is_fallback { Some(true) } else { None },
+                }
+            })
+            .collect();
+
+        apply_model_overrides(&mut models, &self.model_overrides);
+        Ok(models)
+    }
+
+    fn provider_name(&self) -> &str {
+        "mistral"
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mistral_service_creation() {
+        let service = MistralService::new("test_api_key".to_string());
+        assert_eq!(service.provider_name(), "mistral");
+        assert!(service.is_configured());
+    }
+
+    #[test]
+    fn test_mistral_service_not_configured() {
+        let service = MistralService::new("".to_string());
+        assert!(!service.is_configured());
+    }
+
+    #[test]
+    fn test_context_window_estimation() {
+        assert_eq!(
+            MistralService::get_context_window("mistral-large-latest", None),
+            (131072, false)
+        );
+        assert_eq!(
+            MistralService::get_context_window("codestral-2501", None),
+            (256000, false)
+        );
+        assert_eq!(
+            MistralService::get_context_window("open-mixtral-8x22b", None),
+            (65536, false)
+        );
+        assert_eq!(
+            MistralService::get_context_window("open-mistral-7b", None),
+            (32768, false)
+        );
+
+        // Test with explicit context length from API
+        assert_eq!(
+            MistralService::get_context_window("mistral-small-latest", Some(32000)),
+            (32000, false)
+        );
+
+        // Test fallback for unknown model
+        assert_eq!(
+            MistralService::get_context_window("magistral-future", None),
+            (32768, true)
+        );
+    }
+
+    #[test]
+    fn test_models_response_filters_non_chat_models() {
+        let response: MistralModelsResponse = serde_json::from_value(serde_json::json!({
+            "object": "list",
+            "data": [
+                {
+                    "id": "mistral-large-latest",
+                    "name": "mistral-large-2411",
+                    "max_context_length": 131072,
+                    "capabilities": { "completion_chat": true }
+                },
+                {
+                    "id": "mistral-embed",
+                    "capabilities": { "completion_chat": false }
+                },
+                { "id": "open-mistral-7b" }
+            ]
+        }))
+        .unwrap();
+
+        let chat: Vec<_> = response
+            .data
+            .iter()
+            .filter(|m| m.is_chat_model())
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(chat, vec!["mistral-large-latest", "open-mistral-7b"]);
+    }
+}
@@ -2,16 +2,261 @@
 //!
 //! Implementations of the LlmServicePort trait for various providers:
 //! - OpenAI (GPT-4, GPT-3.5-turbo)
+//! - Azure OpenAI (deployment-based OpenAI models)
 //! - Anthropic (Claude)
 //! - Google (Gemini)
 //! - Groq (Llama, Mixtral, Gemma)
+//! - Mistral (La Plateforme)
 
 pub mod anthropic;
+pub mod azure_openai;
 pub mod google;
 pub mod groq;
+pub mod mistral;
 pub mod openai;
 
 pub use anthropic::AnthropicService;
+pub use azure_openai::AzureOpenAIService;
 pub use google::GoogleService;
 pub use groq::GroqService;
+pub use mistral::MistralService;
 pub use openai::OpenAIService;
+
+use crate::domain::models::ModelOverride;
+use crate::error::{AppError, Result};
+use crate::ports::llm::ModelInfo;
+use crate::ports::llm::{
+    GeneratedInsight, InsightRequest, LlmConfig, LlmServiceFactory, LlmServicePort,
+};
+use crate::utils::rate_limit::{self, RateLimiter};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Replace the context windows of listed models with the user's overrides
+///
+/// An overridden window is no longer an estimate, so the fallback flag is cleared.
+pub(crate) fn apply_model_overrides(models: &mut [ModelInfo], overrides: &[ModelOverride]) {
+    for model in models.iter_mut() {
+        let context_window = overrides
+            .iter()
+            .find(|o| o.provider == model.provider && o.model_id == model.id)
+            .and_then(|o| o.context_window);
+        if let Some(context_window) = context_window {
+            model.context_window = context_window;
+            model.is_fallback_context_window = None;
+        }
+    }
+}
+
+/// Builds the real provider adapters
+pub struct ProviderLlmFactory;
+
+impl LlmServiceFactory for ProviderLlmFactory {
+    fn create(
+        &self,
+        provider: &str,
+        api_key: String,
+        settings: Option<&serde_json::Value>,
+    ) -> Result<Box<dyn LlmServicePort>> {
+        let service: Box<dyn LlmServicePort> = match provider {
+            "openai" => Box::new(OpenAIService::new(api_key)),
+            "anthropic" => Box::new(AnthropicService::new(api_key)),
+            "google" => Box::new(GoogleService::new(api_key)),
+            "groq" => Box::new(GroqService::new(api_key)),
+            "mistral" => Box::new(MistralService::new(api_key)),
+            azure_openai::AZURE_OPENAI_PROVIDER => Box::new(AzureOpenAIService::from_settings(
+                api_key,
+                settings.unwrap_or(&serde_json::Value::Null),
+            )?),
+            _ => {
+                return Err(AppError::InvalidInput(format!(
+                    "Unknown provider: {}",
+                    provider
+                )))
+            }
+        };
+        Ok(Box::new(RateLimitedLlmService {
+            limiter: rate_limit::limiter(provider),
+            inner: service,
+        }))
+    }
+}
+
+/// Holds each provider request to the provider's rate limits
+struct RateLimitedLlmService {
+    inner: Box<dyn LlmServicePort>,
+    limiter: Arc<RateLimiter>,
+}
+
+#[async_trait]
+impl LlmServicePort for RateLimitedLlmService {
+    async fn generate_insights(
+        &self,
+        request: &InsightRequest,
+        config: &LlmConfig,
+        prompt_template: Option<&str>,
+    ) -> Result<Vec<GeneratedInsight>> {
+        let _permit = self.limiter.acquire().await;
+        self.inner
+            .generate_insights(request, config, prompt_template)
+            .await
+    }
+
+    async fn generate_summary(
+        &self,
+        transcript: &str,
+        context: Option<&str>,
+        config: &LlmConfig,
+        prompt_template: Option<&str>,
+    ) -> Result<String> {
+        let _permit = self.limiter.acquire().await;
+        self.inner
+            .generate_summary(transcript, context, config, prompt_template)
+            .await
+    }
+
+    async fn fetch_available_models(&self) -> Result<Vec<ModelInfo>> {
+        let _permit = self.limiter.acquire().await;
+        self.inner.fetch_available_models().await
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    fn is_configured(&self) -> bool {
+        self.inner.is_configured()
+    }
+}
+
+/// Text returned by a provider's generation endpoint
+pub(crate) struct Completion {
+    pub(crate) content: String,
+    /// Whether generation stopped at the token limit, leaving the content cut off
+    pub(crate) truncated: bool,
+}
+
+impl Completion {
+    /// Build a completion from the provider's text and finish/stop reason
+    ///
+    /// `length_reason` is the provider's finish reason for hitting the token limit
+    /// (e.g. "length" for OpenAI). Empty output is an error, with a hint to raise
+    /// `max_tokens` when the limit was the cause.
+    pub(crate) fn new(
+        provider: &str,
+        content: String,
+        finish_reason: Option<&str>,
+        length_reason: &str,
+    ) -> Result<Self> {
+        let truncated = finish_reason == Some(length_reason);
+
+        if content.trim().is_empty() {
+            return Err(AppError::LlmService(if truncated {
+                format!(
+                    "{} reached the token limit before producing any output; increase max_tokens and try again",
+                    provider
+                )
+            } else {
+                format!(
+                    "{} returned an empty response (finish reason: {})",
+                    provider,
+                    finish_reason.unwrap_or("none")
+                )
+            }));
+        }
+
+        if truncated {
+            log::warn!(
+                "{} response was cut off at the token limit after {} characters",
+                provider,
+                content.len()
+            );
+        }
+
+        Ok(Self { content, truncated })
+    }
+
+    /// Insight metadata flagging a truncated response so callers can retry with a
+    /// higher `max_tokens`
+    pub(crate) fn metadata(&self) -> Option<serde_json::Value> {
+        self.truncated
+            .then(|| serde_json::json!({ "truncated": true }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completion_flags_truncation() {
+        let completion = Completion::new(
+            "OpenAI",
+            "The meeting".to_string(),
+            Some("length"),
+            "length",
+        )
+        .unwrap();
+        assert!(completion.truncated);
+        assert_eq!(
+            completion.metadata(),
+            Some(serde_json::json!({ "truncated": true }))
+        );
+
+        let completion =
+            Completion::new("OpenAI", "Done.".to_string(), Some("stop"), "length").unwrap();
+        assert!(!completion.truncated);
+        assert_eq!(completion.metadata(), None);
+    }
+
+    #[test]
+    fn test_override_replaces_fallback_context_window() {
+        let model = |id: &str, provider: &str| ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            provider: provider.to_string(),
+            context_window: 100_000,
+            is_fallback_context_window: Some(true),
+        };
+        let mut models = vec![
+            model("claude-sonnet-4-5", "anthropic"),
+            model("claude-opus-4-1", "anthropic"),
+            model("claude-sonnet-4-5", "openai"),
+        ];
+        let overrides = vec![
+            ModelOverride::new("anthropic".to_string(), "claude-sonnet-4-5".to_string())
+                .with_context_window(200_000),
+            // Notes alone leave the window alone
+            ModelOverride::new("anthropic".to_string(), "claude-opus-4-1".to_string())
+                .with_notes("Check the docs".to_string()),
+        ];
+
+        apply_model_overrides(&mut models, &overrides);
+        assert_eq!(models[0].context_window, 200_000);
+        assert_eq!(models[0].is_fallback_context_window, None);
+        assert_eq!(models[1].context_window, 100_000);
+        assert_eq!(models[1].is_fallback_context_window, Some(true));
+        // Overrides only apply to their own provider
+        assert_eq!(models[2].context_window, 100_000);
+    }
+
+    #[test]
+    fn test_completion_rejects_empty_content() {
+        let err = Completion::new(
+            "Anthropic",
+            " ".to_string(),
+            Some("max_tokens"),
+            "max_tokens",
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("increase max_tokens"));
+
+        let err = Completion::new("Google", String::new(), Some("SAFETY"), "MAX_TOKENS")
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .contains("empty response (finish reason: SAFETY)"));
+    }
+}
@@ -3,9 +3,12 @@
 //! Implements the LlmServicePort for OpenAI's API (GPT-4, GPT-3.5-turbo, etc.)
 //! Supports dynamic model fetching and customizable prompts.
 
+use super::{apply_model_overrides, Completion};
 use crate::domain::models::InsightType;
+use crate::domain::models::ModelOverride;
 use crate::error::{AppError, Result};
 use crate::ports::llm::{GeneratedInsight, InsightRequest, LlmConfig, LlmServicePort, ModelInfo};
+use crate::utils::http::shared_client;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -13,10 +16,14 @@ use std::time::Duration;
 
 const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
 
+/// Chat completion finish reason when `max_tokens` was reached
+pub(crate) const FINISH_REASON_LENGTH: &str = "length";
+
 /// OpenAI service implementation
 pub struct OpenAIService {
     client: Client,
     api_key: String,
+    model_overrides: Vec<ModelOverride>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,47 +39,86 @@ struct OpenAIModelsResponse {
     data: Vec<OpenAIModel>,
 }
 
+// Chat completion types are shared with the Azure OpenAI adapter, which uses the same body shape
+
 #[derive(Debug, Serialize)]
-struct ChatCompletionRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
+pub(crate) struct ChatCompletionRequest {
+    pub(crate) model: String,
+    pub(crate) messages: Vec<ChatMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    temperature: Option<f32>,
+    pub(crate) temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    max_tokens: Option<u32>,
+    pub(crate) max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) response_format: Option<ResponseFormat>,
+}
+
+impl ChatCompletionRequest {
+    pub(crate) fn new(messages: Vec<ChatMessage>, config: &LlmConfig) -> Self {
+        Self {
+            model: config.model.clone(),
+            messages,
+            temperature: config.temperature,
+            max_tokens: config.max_tokens,
+            response_format: ResponseFormat::for_config(config),
+        }
+    }
+}
+
+/// `response_format` of an OpenAI-compatible chat completion request
+#[derive(Debug, Serialize)]
+pub(crate) struct ResponseFormat {
+    #[serde(rename = "type")]
+    format_type: &'static str,
+}
+
+impl ResponseFormat {
+    /// JSON object mode when `json_mode` is set, otherwise the provider default (text)
+    pub(crate) fn for_config(config: &LlmConfig) -> Option<Self> {
+        config.json_mode.then_some(Self {
+            format_type: "json_object",
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct ChatMessage {
-    role: String,
-    content: String,
+pub(crate) struct ChatMessage {
+    pub(crate) role: String,
+    pub(crate) content: String,
 }
 
 #[derive(Debug, Deserialize)]
-struct ChatCompletionResponse {
+pub(crate) struct ChatCompletionResponse {
     id: String,
     object: String,
     created: u64,
     model: String,
-    choices: Vec<ChatChoice>,
+    pub(crate) choices: Vec<ChatChoice>,
 }
 
 #[derive(Debug, Deserialize)]
-struct ChatChoice {
+pub(crate) struct ChatChoice {
     index: u32,
-    message: ChatMessage,
-    finish_reason: Option<String>,
+    pub(crate) message: ChatMessage,
+    pub(crate) finish_reason: Option<String>,
 }
 
 impl OpenAIService {
     /// Create a new OpenAI service with the given API key
     pub fn new(api_key: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(120))
-            .build()
-            .expect("Failed to create HTTP client");
+        let client = shared_client(Duration::from_secs(120));
+
+        Self {
+            client,
+            api_key,
+            model_overrides: Vec::new(),
+        }
+    }
 
-        Self { client, api_key }
+    /// Use the user's context window overrides for models listed by `fetch_available_models`
+    pub fn with_model_overrides(mut self, model_overrides: Vec<ModelOverride>) -> Self {
+        self.model_overrides = model_overrides;
+        self
     }
 
     /// Fetch available models from OpenAI API
@@ -111,7 +157,7 @@ impl OpenAIService {
         transcript: &str,
         context: Option<&str>,
         config: &LlmConfig,
-    ) -> Result<String> {
+    ) -> Result<Completion> {
         // Replace placeholders in prompt
         let context_str = context.unwrap_or("");
         let formatted_prompt = prompt
@@ -123,12 +169,7 @@ impl OpenAIService {
             content: formatted_prompt,
         }];
 
-        let request_body = ChatCompletionRequest {
-            model: config.model.clone(),
-            messages,
-            temperature: config.temperature,
-            max_tokens: config.max_tokens,
-        };
+        let request_body = ChatCompletionRequest::new(messages, config);
 
         log::info!(
             "Calling OpenAI chat completion with model: {}",
@@ -157,19 +198,24 @@ impl OpenAIService {
             AppError::LlmService(format!("Failed to parse completion response: {}", e))
         })?;
 
-        if completion_response.choices.is_empty() {
-            return Err(AppError::LlmService(
-                "No completion choices returned".to_string(),
-            ));
-        }
-
-        let content = completion_response.choices[0].message.content.clone();
+        let choice = completion_response
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::LlmService("No completion choices returned".to_string()))?;
+
+        let completion = Completion::new(
+            "OpenAI",
+            choice.message.content,
+            choice.finish_reason.as_deref(),
+            FINISH_REASON_LENGTH,
+        )?;
         log::info!(
             "OpenAI completion successful, generated {} characters",
-            content.len()
+            completion.content.len()
         );
 
-        Ok(content)
+        Ok(completion)
     }
 
     /// Get estimated context window for a model
@@ -226,7 +272,7 @@ impl LlmServicePort for OpenAIService {
                 crate::domain::PromptTemplates::for_type(insight_type).to_string()
             };
 
-            let content = self
+            let completion = self
                 .generate_with_prompt(
                     &prompt,
                     &request.transcript,
@@ -237,8 +283,8 @@ impl LlmServicePort for OpenAIService {
 
             insights.push(GeneratedInsight {
                 insight_type: insight_type.clone(),
-                content,
-                metadata: None,
+                metadata: completion.metadata(),
+                content: completion.content,
             });
         }
 
@@ -258,14 +304,16 @@ impl LlmServicePort for OpenAIService {
             crate::domain::PromptTemplates::summary().to_string()
         };
 
-        self.generate_with_prompt(&prompt, transcript, context, config)
-            .await
+        Ok(self
+            .generate_with_prompt(&prompt, transcript, context, config)
+            .await?
+            .content)
     }
 
     async fn fetch_available_models(&self) -> Result<Vec<ModelInfo>> {
-        let models = self.list_models().await?;
-
-        Ok(models
+        let mut models: Vec<ModelInfo> = self
+            .list_models()
+            .await?
             .into_iter()
             .map(|m| {
                 let (context_window, is_fallback) = Self::get_context_window(&m.id);
@@ -277,7 +325,10 @@ impl LlmServicePort for OpenAIService {
                     is_fallback_context_window: if is_fallback { Some(true) } else { None },
                 }
             })
-            .collect())
+            .collect();
+
+        apply_model_overrides(&mut models, &self.model_overrides);
+        Ok(models)
     }
 
     fn provider_name(&self) -> &str {
@@ -323,4 +374,31 @@ mod tests {
             (4096, true)
         );
     }
+
+    #[test]
+    fn test_response_format_only_in_json_mode() {
+        let messages = || {
+            vec![ChatMessage {
+                role: "user".to_string(),
+                content: "Summarize".to_string(),
+            }]
+        };
+
+        let body = serde_json::to_value(ChatCompletionRequest::new(
+            messages(),
+            &LlmConfig::default(),
+        ))
+        .unwrap();
+        assert!(body.get("response_format").is_none());
+
+        let config = LlmConfig {
+            json_mode: true,
+            ..Default::default()
+        };
+        let body = serde_json::to_value(ChatCompletionRequest::new(messages(), &config)).unwrap();
+        assert_eq!(
+            body["response_format"],
+            serde_json::json!({ "type": "json_object" })
+        );
+    }
 }
@@ -2,16 +2,20 @@
 ///
 /// Implements StoragePort for SQLite database operations.
 use crate::domain::models::{
-    Insight, InsightSearchResult, InsightType, Meeting, Participant, Platform, SearchResults,
-    ServiceConfig, ServiceType, Transcript, TranscriptSearchResult,
+    Insight, InsightSearchResult, InsightType, Meeting, MeetingModelOverride, ModelOverride,
+    Participant, Platform, RecordingStatus, SavedRecording, SearchResults, ServiceConfig,
+    ServiceType, Transcript, TranscriptSearchResult, TranscriptSource,
 };
 use crate::error::{AppError, Result};
 use crate::ports::storage::StoragePort;
 use async_trait::async_trait;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+/// Name of the user's custom row in `prompt_templates` (one per insight type)
+const CUSTOM_PROMPT_TEMPLATE_NAME: &str = "Custom";
+
 /// SQLite storage implementation
 pub struct SqliteStorage {
     conn: Arc<Mutex<Connection>>,
@@ -25,6 +29,17 @@ impl SqliteStorage {
         // Enable foreign keys
         conn.execute("PRAGMA foreign_keys = ON", [])?;
 
+        // Write-ahead logging keeps the database intact if the app crashes mid-write and
+        // makes commits cheaper, since NORMAL sync is then safe. All access still goes
+        // through the one connection below, so reads don't run alongside writes.
+        // In-memory databases stay in "memory" journal mode.
+        let journal_mode: String =
+            conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+        log::debug!("SQLite journal mode: {}", journal_mode);
+        // NORMAL is durable across application crashes in WAL mode; only an OS crash
+        // or power loss can roll back the most recent commits
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
         })
@@ -52,15 +67,177 @@ impl SqliteStorage {
             M::up(include_str!(
                 "../../../migrations/007_add_language_to_meetings.sql"
             )),
+            M::up(include_str!(
+                "../../../migrations/008_add_transcripts_redacted_to_meetings.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/009_add_detected_language_to_meetings.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/010_add_notes_to_meetings.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/011_allow_other_platforms.sql"
+            ))
+            .foreign_key_check(),
+            M::up(include_str!(
+                "../../../migrations/012_add_audio_offset_to_meetings.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/013_add_audio_normalized_to_meetings.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/014_add_end_timestamp_to_transcripts.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/015_add_updated_at_to_transcripts.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/016_fix_transcripts_fts_triggers.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/017_add_is_interim_to_transcripts.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/018_add_meeting_model_overrides.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/019_add_transcript_confidence_to_meetings.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/020_add_recording_status_to_meetings.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/021_add_language_to_transcripts.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/022_one_active_service_config.sql"
+            )),
+            M::up(include_str!("../../../migrations/023_add_app_settings.sql")),
+            M::up(include_str!(
+                "../../../migrations/024_add_source_to_transcripts.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/025_add_audio_format_to_meetings.sql"
+            )),
+            M::up(include_str!(
+                "../../../migrations/026_add_feedback_to_insights.sql"
+            )),
         ]);
 
         let mut conn = self.conn.lock().unwrap();
-        migrations.to_latest(&mut conn).map_err(|e| {
+
+        // Table rebuilds drop and recreate parent tables; with foreign keys on, the
+        // DROP would cascade to child rows. The pragma is a no-op inside a
+        // transaction, so it has to be toggled around the whole run.
+        conn.execute("PRAGMA foreign_keys = OFF", [])?;
+        let migrated = migrations.to_latest(&mut conn);
+        conn.execute("PRAGMA foreign_keys = ON", [])?;
+        migrated.map_err(|e| {
             AppError::Database(rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
         })?;
 
         Ok(())
     }
+
+    /// Load a meeting's transcripts, optionally skipping low-confidence segments or
+    /// keeping only one source
+    ///
+    /// Segments with no confidence score are always included.
+    fn query_transcripts(
+        &self,
+        meeting_id: i64,
+        min_confidence: Option<f32>,
+        source: Option<TranscriptSource>,
+    ) -> Result<Vec<Transcript>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.meeting_id, t.participant_id, p.name, t.speaker_label, t.timestamp_ms, t.end_timestamp_ms, t.text, t.confidence, t.created_at, t.updated_at, t.language, t.source
+             FROM transcripts t
+             LEFT JOIN participants p ON t.participant_id = p.id
+             WHERE t.meeting_id = ?1
+               AND (?2 IS NULL OR t.confidence IS NULL OR t.confidence >= ?2)
+               AND (?3 IS NULL OR t.source = ?3)
+             ORDER BY t.timestamp_ms",
+        )?;
+
+        let source = source.map(|s| s.to_string());
+        let rows = stmt.query_map(params![meeting_id, min_confidence, source], |row| {
+            Ok(Transcript {
+                id: Some(row.get(0)?),
+                meeting_id: row.get(1)?,
+                participant_id: row.get(2)?,
+                participant_name: row.get(3)?,
+                speaker_label: row.get(4)?,
+                timestamp_ms: row.get(5)?,
+                end_timestamp_ms: row.get(6)?,
+                text: row.get(7)?,
+                confidence: row.get(8)?,
+                language: row.get(11)?,
+                source: row.get::<_, String>(12)?.parse().unwrap_or_default(),
+                created_at: row.get(9)?,
+                updated_at: row.get(10)?,
+            })
+        })?;
+
+        let mut transcripts = Vec::new();
+        for transcript_result in rows {
+            transcripts.push(transcript_result?);
+        }
+
+        Ok(transcripts)
+    }
+
+    /// Map an `id, meeting_id, type, content, metadata, created_at, feedback` row to an
+    /// insight
+    fn insight_from_row(row: &rusqlite::Row) -> rusqlite::Result<Insight> {
+        let type_str: String = row.get(2)?;
+        let insight_type = match type_str.as_str() {
+            "summary" => InsightType::Summary,
+            "action_item" => InsightType::ActionItem,
+            "key_point" => InsightType::KeyPoint,
+            "decision" => InsightType::Decision,
+            _ => InsightType::Summary,
+        };
+
+        Ok(Insight {
+            id: Some(row.get(0)?),
+            meeting_id: row.get(1)?,
+            insight_type,
+            content: row.get(3)?,
+            metadata: row.get(4)?,
+            created_at: row.get(5)?,
+            feedback: row.get(6)?,
+        })
+    }
+
+    /// Insert transcripts on an open connection or transaction, returning their IDs in order
+    fn insert_transcripts(conn: &Connection, transcripts: &[Transcript]) -> Result<Vec<i64>> {
+        let mut stmt = conn.prepare(
+            "INSERT INTO transcripts (meeting_id, participant_id, speaker_label, timestamp_ms, end_timestamp_ms, text, confidence, created_at, language, updated_at, source)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        )?;
+
+        let mut ids = Vec::with_capacity(transcripts.len());
+        for transcript in transcripts {
+            stmt.execute(params![
+                transcript.meeting_id,
+                transcript.participant_id,
+                transcript.speaker_label,
+                transcript.timestamp_ms,
+                transcript.end_timestamp_ms,
+                transcript.text,
+                transcript.confidence,
+                transcript.created_at,
+                transcript.language,
+                transcript.updated_at,
+                transcript.source.to_string(),
+            ])?;
+            ids.push(conn.last_insert_rowid());
+        }
+
+        Ok(ids)
+    }
 }
 
 #[async_trait]
@@ -68,8 +245,8 @@ impl StoragePort for SqliteStorage {
     async fn create_meeting(&self, meeting: &Meeting) -> Result<i64> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT INTO meetings (platform, title, language, start_time, end_time, participant_count, audio_file_path, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO meetings (platform, title, language, start_time, end_time, participant_count, audio_file_path, transcripts_redacted, detected_language, notes, audio_offset_ms, audio_normalized, created_at, transcript_confidence, recording_status, audio_sample_rate, audio_channels, audio_bits_per_sample)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
             params![
                 meeting.platform.to_string(),
                 meeting.title,
@@ -78,7 +255,17 @@ impl StoragePort for SqliteStorage {
                 meeting.end_time,
                 meeting.participant_count,
                 meeting.audio_file_path,
+                meeting.transcripts_redacted,
+                meeting.detected_language,
+                meeting.notes,
+                meeting.audio_offset_ms,
+                meeting.audio_normalized,
                 meeting.created_at,
+                meeting.transcript_confidence,
+                meeting.recording_status.map(|s| s.to_string()),
+                meeting.audio_sample_rate,
+                meeting.audio_channels,
+                meeting.audio_bits_per_sample,
             ],
         )?;
         Ok(conn.last_insert_rowid())
@@ -87,7 +274,7 @@ impl StoragePort for SqliteStorage {
     async fn get_meeting(&self, id: i64) -> Result<Option<Meeting>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, platform, title, language, start_time, end_time, participant_count, audio_file_path, created_at
+            "SELECT id, platform, title, language, start_time, end_time, participant_count, audio_file_path, transcripts_redacted, detected_language, notes, audio_offset_ms, audio_normalized, created_at, transcript_confidence, recording_status, audio_sample_rate, audio_channels, audio_bits_per_sample
              FROM meetings WHERE id = ?1",
         )?;
 
@@ -95,12 +282,7 @@ impl StoragePort for SqliteStorage {
 
         if let Some(row) = rows.next()? {
             let platform_str: String = row.get(1)?;
-            let platform = match platform_str.as_str() {
-                "teams" => Platform::Teams,
-                "zoom" => Platform::Zoom,
-                "meet" => Platform::Meet,
-                _ => return Err(AppError::Database(rusqlite::Error::InvalidQuery)),
-            };
+            let Ok(platform) = platform_str.parse::<Platform>();
 
             Ok(Some(Meeting {
                 id: Some(row.get(0)?),
@@ -111,7 +293,19 @@ impl StoragePort for SqliteStorage {
                 end_time: row.get(5)?,
                 participant_count: row.get(6)?,
                 audio_file_path: row.get(7)?,
-                created_at: row.get(8)?,
+                transcripts_redacted: row.get(8)?,
+                detected_language: row.get(9)?,
+                notes: row.get(10)?,
+                audio_offset_ms: row.get(11)?,
+                audio_normalized: row.get(12)?,
+                created_at: row.get(13)?,
+                transcript_confidence: row.get(14)?,
+                recording_status: row
+                    .get::<_, Option<String>>(15)?
+                    .and_then(|s| s.parse().ok()),
+                audio_sample_rate: row.get(16)?,
+                audio_channels: row.get(17)?,
+                audio_bits_per_sample: row.get(18)?,
             }))
         } else {
             Ok(None)
@@ -121,19 +315,14 @@ impl StoragePort for SqliteStorage {
     async fn list_meetings(&self, limit: Option<i32>, offset: Option<i32>) -> Result<Vec<Meeting>> {
         let conn = self.conn.lock().unwrap();
         let query = format!(
-            "SELECT id, platform, title, language, start_time, end_time, participant_count, audio_file_path, created_at
+            "SELECT id, platform, title, language, start_time, end_time, participant_count, audio_file_path, transcripts_redacted, detected_language, notes, audio_offset_ms, audio_normalized, created_at, transcript_confidence, recording_status, audio_sample_rate, audio_channels, audio_bits_per_sample
              FROM meetings ORDER BY start_time DESC LIMIT ?1 OFFSET ?2"
         );
 
         let mut stmt = conn.prepare(&query)?;
         let rows = stmt.query_map(params![limit.unwrap_or(100), offset.unwrap_or(0)], |row| {
             let platform_str: String = row.get(1)?;
-            let platform = match platform_str.as_str() {
-                "teams" => Platform::Teams,
-                "zoom" => Platform::Zoom,
-                "meet" => Platform::Meet,
-                _ => Platform::Teams, // Default fallback
-            };
+            let Ok(platform) = platform_str.parse::<Platform>();
 
             Ok(Meeting {
                 id: Some(row.get(0)?),
@@ -144,7 +333,19 @@ impl StoragePort for SqliteStorage {
                 end_time: row.get(5)?,
                 participant_count: row.get(6)?,
                 audio_file_path: row.get(7)?,
-                created_at: row.get(8)?,
+                transcripts_redacted: row.get(8)?,
+                detected_language: row.get(9)?,
+                notes: row.get(10)?,
+                audio_offset_ms: row.get(11)?,
+                audio_normalized: row.get(12)?,
+                created_at: row.get(13)?,
+                transcript_confidence: row.get(14)?,
+                recording_status: row
+                    .get::<_, Option<String>>(15)?
+                    .and_then(|s| s.parse().ok()),
+                audio_sample_rate: row.get(16)?,
+                audio_channels: row.get(17)?,
+                audio_bits_per_sample: row.get(18)?,
             })
         })?;
 
@@ -160,7 +361,10 @@ impl StoragePort for SqliteStorage {
         let conn = self.conn.lock().unwrap();
         conn.execute(
             "UPDATE meetings SET platform = ?1, title = ?2, language = ?3, start_time = ?4, end_time = ?5,
-             participant_count = ?6, audio_file_path = ?7 WHERE id = ?8",
+             participant_count = ?6, audio_file_path = ?7, transcripts_redacted = ?8,
+             detected_language = ?9, notes = ?10, audio_offset_ms = ?11,
+             audio_normalized = ?12, transcript_confidence = ?13, recording_status = ?14,
+             audio_sample_rate = ?15, audio_channels = ?16, audio_bits_per_sample = ?17 WHERE id = ?18",
             params![
                 meeting.platform.to_string(),
                 meeting.title,
@@ -169,18 +373,159 @@ impl StoragePort for SqliteStorage {
                 meeting.end_time,
                 meeting.participant_count,
                 meeting.audio_file_path,
+                meeting.transcripts_redacted,
+                meeting.detected_language,
+                meeting.notes,
+                meeting.audio_offset_ms,
+                meeting.audio_normalized,
+                meeting.transcript_confidence,
+                meeting.recording_status.map(|s| s.to_string()),
+                meeting.audio_sample_rate,
+                meeting.audio_channels,
+                meeting.audio_bits_per_sample,
                 meeting.id,
             ],
         )?;
         Ok(())
     }
 
+    async fn update_meeting_notes(&self, id: i64, notes: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE meetings SET notes = ?1 WHERE id = ?2",
+            params![notes, id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Meeting {} not found", id)));
+        }
+        Ok(())
+    }
+
+    async fn set_meeting_end_time(&self, id: i64, end_time: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE meetings SET end_time = ?1 WHERE id = ?2",
+            params![end_time, id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Meeting {} not found", id)));
+        }
+        Ok(())
+    }
+
+    async fn set_meeting_recording(&self, id: i64, recording: &SavedRecording) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE meetings SET audio_file_path = ?1, audio_offset_ms = ?2, audio_normalized = ?3,
+             recording_status = ?4, audio_sample_rate = ?5, audio_channels = ?6,
+             audio_bits_per_sample = ?7 WHERE id = ?8",
+            params![
+                recording.audio_file_path,
+                recording.audio_offset_ms,
+                recording.audio_normalized,
+                RecordingStatus::Recorded.to_string(),
+                recording.sample_rate,
+                recording.channels,
+                recording.bits_per_sample,
+                id,
+            ],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Meeting {} not found", id)));
+        }
+        Ok(())
+    }
+
+    async fn set_recording_status(&self, id: i64, status: RecordingStatus) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE meetings SET recording_status = ?1 WHERE id = ?2",
+            params![status.to_string(), id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Meeting {} not found", id)));
+        }
+        Ok(())
+    }
+
     async fn delete_meeting(&self, id: i64) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute("DELETE FROM meetings WHERE id = ?1", params![id])?;
         Ok(())
     }
 
+    async fn get_meeting_model_override(
+        &self,
+        meeting_id: i64,
+    ) -> Result<Option<MeetingModelOverride>> {
+        let conn = self.conn.lock().unwrap();
+        let model_override = conn
+            .query_row(
+                "SELECT meeting_id, asr_provider, asr_model, llm_provider, llm_model
+                 FROM meeting_model_overrides WHERE meeting_id = ?1",
+                params![meeting_id],
+                |row| {
+                    Ok(MeetingModelOverride {
+                        meeting_id: row.get(0)?,
+                        asr_provider: row.get(1)?,
+                        asr_model: row.get(2)?,
+                        llm_provider: row.get(3)?,
+                        llm_model: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(model_override)
+    }
+
+    async fn set_meeting_model_override(
+        &self,
+        model_override: &MeetingModelOverride,
+    ) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let meeting_id = model_override.meeting_id;
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM meetings WHERE id = ?1)",
+            params![meeting_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound(format!(
+                "Meeting {} not found",
+                meeting_id
+            )));
+        }
+
+        if model_override.is_empty() {
+            conn.execute(
+                "DELETE FROM meeting_model_overrides WHERE meeting_id = ?1",
+                params![meeting_id],
+            )?;
+            return Ok(());
+        }
+
+        conn.execute(
+            "INSERT INTO meeting_model_overrides
+                (meeting_id, asr_provider, asr_model, llm_provider, llm_model, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(meeting_id) DO UPDATE SET
+                asr_provider = excluded.asr_provider,
+                asr_model = excluded.asr_model,
+                llm_provider = excluded.llm_provider,
+                llm_model = excluded.llm_model,
+                updated_at = excluded.updated_at",
+            params![
+                meeting_id,
+                model_override.asr_provider,
+                model_override.asr_model,
+                model_override.llm_provider,
+                model_override.llm_model,
+                chrono::Utc::now().timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+
     async fn create_participant(&self, participant: &Participant) -> Result<i64> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
@@ -196,6 +541,87 @@ impl StoragePort for SqliteStorage {
         Ok(conn.last_insert_rowid())
     }
 
+    async fn set_participants(&self, meeting_id: i64, roster: &[Participant]) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().unwrap();
+        let tx = conn.unchecked_transaction()?;
+
+        let existing = {
+            let mut stmt = tx.prepare(
+                "SELECT id, meeting_id, name, email, speaker_label FROM participants
+                 WHERE meeting_id = ?1 ORDER BY id",
+            )?;
+            let rows = stmt.query_map(params![meeting_id], |row| {
+                Ok(Participant {
+                    id: Some(row.get(0)?),
+                    meeting_id: row.get(1)?,
+                    name: row.get(2)?,
+                    email: row.get(3)?,
+                    speaker_label: row.get(4)?,
+                })
+            })?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()?
+        };
+
+        let mut ids = Vec::with_capacity(roster.len());
+        for (entry, matched) in roster
+            .iter()
+            .zip(Participant::match_roster(&existing, roster))
+        {
+            match matched {
+                Some(id) => {
+                    tx.execute(
+                        "UPDATE participants SET name = ?1, email = COALESCE(?2, email) WHERE id = ?3",
+                        params![entry.name, entry.email, id],
+                    )?;
+                    ids.push(id);
+                }
+                None => {
+                    tx.execute(
+                        "INSERT INTO participants (meeting_id, name, email) VALUES (?1, ?2, ?3)",
+                        params![meeting_id, entry.name, entry.email],
+                    )?;
+                    ids.push(tx.last_insert_rowid());
+                }
+            }
+        }
+
+        for participant in &existing {
+            let id = participant.id.unwrap_or_default();
+            if participant.speaker_label.is_none() && !ids.contains(&id) {
+                tx.execute("DELETE FROM participants WHERE id = ?1", params![id])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(ids)
+    }
+
+    async fn create_participants_batch(&self, participants: &[Participant]) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().unwrap();
+        let mut ids = Vec::new();
+
+        let tx = conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO participants (meeting_id, name, email, speaker_label)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+
+            for participant in participants {
+                stmt.execute(params![
+                    participant.meeting_id,
+                    participant.name,
+                    participant.email,
+                    participant.speaker_label,
+                ])?;
+                ids.push(tx.last_insert_rowid());
+            }
+        }
+        tx.commit()?;
+
+        Ok(ids)
+    }
+
     async fn get_participants(&self, meeting_id: i64) -> Result<Vec<Participant>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
@@ -244,93 +670,186 @@ impl StoragePort for SqliteStorage {
     async fn create_transcript(&self, transcript: &Transcript) -> Result<i64> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT INTO transcripts (meeting_id, participant_id, speaker_label, timestamp_ms, text, confidence, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO transcripts (meeting_id, participant_id, speaker_label, timestamp_ms, end_timestamp_ms, text, confidence, created_at, language, updated_at, source)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 transcript.meeting_id,
                 transcript.participant_id,
                 transcript.speaker_label,
                 transcript.timestamp_ms,
+                transcript.end_timestamp_ms,
                 transcript.text,
                 transcript.confidence,
                 transcript.created_at,
+                transcript.language,
+                transcript.updated_at,
+                transcript.source.to_string(),
             ],
         )?;
         Ok(conn.last_insert_rowid())
     }
 
     async fn get_transcripts(&self, meeting_id: i64) -> Result<Vec<Transcript>> {
+        self.query_transcripts(meeting_id, None, None)
+    }
+
+    async fn get_transcripts_by_source(
+        &self,
+        meeting_id: i64,
+        source: TranscriptSource,
+    ) -> Result<Vec<Transcript>> {
+        self.query_transcripts(meeting_id, None, Some(source))
+    }
+
+    async fn get_confident_transcripts(
+        &self,
+        meeting_id: i64,
+        min_confidence: f32,
+    ) -> Result<Vec<Transcript>> {
+        self.query_transcripts(meeting_id, Some(min_confidence), None)
+    }
+
+    async fn update_transcript_text(&self, transcript_id: i64, text: &str) -> Result<()> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT t.id, t.meeting_id, t.participant_id, p.name, t.speaker_label, t.timestamp_ms, t.text, t.confidence, t.created_at
-             FROM transcripts t
-             LEFT JOIN participants p ON t.participant_id = p.id
-             WHERE t.meeting_id = ?1
-             ORDER BY t.timestamp_ms",
+        let updated = conn.execute(
+            "UPDATE transcripts SET text = ?1, updated_at = ?2 WHERE id = ?3",
+            params![text, chrono::Utc::now().timestamp(), transcript_id],
         )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!(
+                "Transcript {} not found",
+                transcript_id
+            )));
+        }
+        Ok(())
+    }
 
-        let rows = stmt.query_map(params![meeting_id], |row| {
-            Ok(Transcript {
-                id: Some(row.get(0)?),
-                meeting_id: row.get(1)?,
-                participant_id: row.get(2)?,
-                participant_name: row.get(3)?,
-                speaker_label: row.get(4)?,
-                timestamp_ms: row.get(5)?,
-                text: row.get(6)?,
-                confidence: row.get(7)?,
-                created_at: row.get(8)?,
-            })
-        })?;
+    async fn create_transcripts_batch(&self, transcripts: &[Transcript]) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().unwrap();
 
-        let mut transcripts = Vec::new();
-        for transcript_result in rows {
-            transcripts.push(transcript_result?);
-        }
+        let tx = conn.unchecked_transaction()?;
+        let ids = Self::insert_transcripts(&tx, transcripts)?;
+        tx.commit()?;
 
-        Ok(transcripts)
+        Ok(ids)
     }
 
-    async fn create_transcripts_batch(&self, transcripts: &[Transcript]) -> Result<Vec<i64>> {
+    async fn replace_transcripts(
+        &self,
+        meeting_id: i64,
+        transcripts: &[Transcript],
+    ) -> Result<Vec<i64>> {
+        if let Some(other) = transcripts.iter().find(|t| t.meeting_id != meeting_id) {
+            return Err(AppError::InvalidInput(format!(
+                "Transcript for meeting {} cannot replace transcripts of meeting {}",
+                other.meeting_id, meeting_id
+            )));
+        }
+
         let conn = self.conn.lock().unwrap();
-        let mut ids = Vec::new();
 
         let tx = conn.unchecked_transaction()?;
-        {
-            let mut stmt = tx.prepare(
-                "INSERT INTO transcripts (meeting_id, participant_id, speaker_label, timestamp_ms, text, confidence, created_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            )?;
+        tx.execute(
+            "DELETE FROM transcripts WHERE meeting_id = ?1 AND source = ?2",
+            params![meeting_id, TranscriptSource::Batch.to_string()],
+        )?;
+        let ids = Self::insert_transcripts(&tx, transcripts)?;
+        tx.commit()?;
 
-            for transcript in transcripts {
-                stmt.execute(params![
+        Ok(ids)
+    }
+
+    async fn upsert_transcript_segment(
+        &self,
+        transcript: &Transcript,
+        is_final: bool,
+    ) -> Result<i64> {
+        let start = transcript.timestamp_ms;
+        let end = transcript.end_timestamp_ms.unwrap_or(start);
+
+        let conn = self.conn.lock().unwrap();
+        let tx = conn.unchecked_transaction()?;
+
+        // Interim rows whose [start, end) range overlaps this segment
+        let overlapping: Vec<i64> = tx
+            .prepare(
+                "SELECT id FROM transcripts
+                 WHERE meeting_id = ?1 AND is_interim = 1 AND source = ?4
+                   AND ((timestamp_ms < ?3 AND COALESCE(end_timestamp_ms, timestamp_ms) > ?2)
+                        OR timestamp_ms = ?2)
+                 ORDER BY id",
+            )?
+            .query_map(
+                params![
                     transcript.meeting_id,
-                    transcript.participant_id,
-                    transcript.speaker_label,
-                    transcript.timestamp_ms,
-                    transcript.text,
-                    transcript.confidence,
-                    transcript.created_at,
-                ])?;
-                ids.push(tx.last_insert_rowid());
+                    start,
+                    end,
+                    transcript.source.to_string()
+                ],
+                |row| row.get(0),
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let id = match overlapping.split_first() {
+            Some((&id, stale)) => {
+                tx.execute(
+                    "UPDATE transcripts SET participant_id = ?1, speaker_label = ?2, timestamp_ms = ?3, end_timestamp_ms = ?4, text = ?5, confidence = ?6, is_interim = ?7, language = ?8 WHERE id = ?9",
+                    params![
+                        transcript.participant_id,
+                        transcript.speaker_label,
+                        transcript.timestamp_ms,
+                        transcript.end_timestamp_ms,
+                        transcript.text,
+                        transcript.confidence,
+                        !is_final,
+                        transcript.language,
+                        id,
+                    ],
+                )?;
+                for stale_id in stale {
+                    tx.execute("DELETE FROM transcripts WHERE id = ?1", params![stale_id])?;
+                }
+                id
             }
-        }
+            None => {
+                tx.execute(
+                    "INSERT INTO transcripts (meeting_id, participant_id, speaker_label, timestamp_ms, end_timestamp_ms, text, confidence, created_at, is_interim, language, source)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                    params![
+                        transcript.meeting_id,
+                        transcript.participant_id,
+                        transcript.speaker_label,
+                        transcript.timestamp_ms,
+                        transcript.end_timestamp_ms,
+                        transcript.text,
+                        transcript.confidence,
+                        transcript.created_at,
+                        !is_final,
+                        transcript.language,
+                        transcript.source.to_string(),
+                    ],
+                )?;
+                tx.last_insert_rowid()
+            }
+        };
         tx.commit()?;
 
-        Ok(ids)
+        Ok(id)
     }
 
     async fn update_transcript(&self, transcript: &Transcript) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "UPDATE transcripts SET meeting_id = ?1, participant_id = ?2, speaker_label = ?3, timestamp_ms = ?4, text = ?5, confidence = ?6 WHERE id = ?7",
+            "UPDATE transcripts SET meeting_id = ?1, participant_id = ?2, speaker_label = ?3, timestamp_ms = ?4, end_timestamp_ms = ?5, text = ?6, confidence = ?7, language = ?8 WHERE id = ?9",
             params![
                 transcript.meeting_id,
                 transcript.participant_id,
                 transcript.speaker_label,
                 transcript.timestamp_ms,
+                transcript.end_timestamp_ms,
                 transcript.text,
                 transcript.confidence,
+                transcript.language,
                 transcript.id,
             ],
         )?;
@@ -354,17 +873,164 @@ impl StoragePort for SqliteStorage {
         Ok(updated)
     }
 
+    /// Merge speaker labels in a single transaction
+    ///
+    /// If the target label is linked to a participant, the merged transcripts are linked
+    /// to it and the source participant is removed. If only the source label is linked,
+    /// its participant is carried over to the target label.
+    async fn merge_speaker_labels(
+        &self,
+        meeting_id: i64,
+        from_label: &str,
+        into_label: &str,
+    ) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let tx = conn.unchecked_transaction()?;
+
+        let find_participant = |label: &str| -> rusqlite::Result<Option<i64>> {
+            tx.query_row(
+                "SELECT id FROM participants WHERE meeting_id = ?1 AND speaker_label = ?2",
+                params![meeting_id, label],
+                |row| row.get(0),
+            )
+            .optional()
+        };
+        let from_participant = find_participant(from_label)?;
+        let into_participant = find_participant(into_label)?;
+
+        match (from_participant, into_participant) {
+            (from_id, Some(into_id)) => {
+                tx.execute(
+                    "UPDATE transcripts SET participant_id = ?1
+                     WHERE meeting_id = ?2 AND speaker_label = ?3",
+                    params![into_id, meeting_id, from_label],
+                )?;
+                if let Some(from_id) = from_id {
+                    tx.execute("DELETE FROM participants WHERE id = ?1", params![from_id])?;
+                }
+            }
+            (Some(from_id), None) => {
+                tx.execute(
+                    "UPDATE participants SET speaker_label = ?1 WHERE id = ?2",
+                    params![into_label, from_id],
+                )?;
+                tx.execute(
+                    "UPDATE transcripts SET participant_id = ?1
+                     WHERE meeting_id = ?2 AND speaker_label = ?3",
+                    params![from_id, meeting_id, into_label],
+                )?;
+            }
+            (None, None) => {}
+        }
+
+        let updated = tx.execute(
+            "UPDATE transcripts SET speaker_label = ?1
+             WHERE meeting_id = ?2 AND speaker_label = ?3",
+            params![into_label, meeting_id, from_label],
+        )?;
+
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    async fn assign_speakers(
+        &self,
+        meeting_id: i64,
+        assignments: &[(String, String)],
+    ) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let tx = conn.unchecked_transaction()?;
+
+        let mut missing_labels = Vec::new();
+        for (speaker_label, _) in assignments {
+            let exists: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM transcripts WHERE meeting_id = ?1 AND speaker_label = ?2)",
+                params![meeting_id, speaker_label],
+                |row| row.get(0),
+            )?;
+            if !exists {
+                missing_labels.push(speaker_label.as_str());
+            }
+        }
+        if !missing_labels.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Speaker labels not found in meeting {}: {}",
+                meeting_id,
+                missing_labels.join(", ")
+            )));
+        }
+
+        let mut linked = 0;
+        for (speaker_label, participant_name) in assignments {
+            let existing: Option<i64> = tx
+                .query_row(
+                    "SELECT id FROM participants WHERE meeting_id = ?1 AND speaker_label = ?2",
+                    params![meeting_id, speaker_label],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            // Otherwise link an attendee from the meeting's roster with the same name
+            let roster_match: Option<i64> = match existing {
+                Some(_) => None,
+                None => tx
+                    .query_row(
+                        "SELECT id FROM participants
+                         WHERE meeting_id = ?1 AND speaker_label IS NULL AND name = ?2 COLLATE NOCASE
+                         ORDER BY id LIMIT 1",
+                        params![meeting_id, participant_name],
+                        |row| row.get(0),
+                    )
+                    .optional()?,
+            };
+
+            let participant_id = match (existing, roster_match) {
+                (Some(id), _) => {
+                    tx.execute(
+                        "UPDATE participants SET name = ?1 WHERE id = ?2",
+                        params![participant_name, id],
+                    )?;
+                    id
+                }
+                (None, Some(id)) => {
+                    tx.execute(
+                        "UPDATE participants SET speaker_label = ?1 WHERE id = ?2",
+                        params![speaker_label, id],
+                    )?;
+                    id
+                }
+                (None, None) => {
+                    tx.execute(
+                        "INSERT INTO participants (meeting_id, name, speaker_label)
+                         VALUES (?1, ?2, ?3)",
+                        params![meeting_id, participant_name, speaker_label],
+                    )?;
+                    tx.last_insert_rowid()
+                }
+            };
+
+            linked += tx.execute(
+                "UPDATE transcripts SET participant_id = ?1
+                 WHERE meeting_id = ?2 AND speaker_label = ?3",
+                params![participant_id, meeting_id, speaker_label],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(linked)
+    }
+
     async fn create_insight(&self, insight: &Insight) -> Result<i64> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT INTO insights (meeting_id, type, content, metadata, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO insights (meeting_id, type, content, metadata, created_at, feedback)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![
                 insight.meeting_id,
                 insight.insight_type.to_string(),
                 insight.content,
                 insight.metadata,
                 insight.created_at,
+                insight.feedback,
             ],
         )?;
         Ok(conn.last_insert_rowid())
@@ -373,29 +1039,11 @@ impl StoragePort for SqliteStorage {
     async fn get_insights(&self, meeting_id: i64) -> Result<Vec<Insight>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, meeting_id, type, content, metadata, created_at
+            "SELECT id, meeting_id, type, content, metadata, created_at, feedback
              FROM insights WHERE meeting_id = ?1",
         )?;
 
-        let rows = stmt.query_map(params![meeting_id], |row| {
-            let type_str: String = row.get(2)?;
-            let insight_type = match type_str.as_str() {
-                "summary" => InsightType::Summary,
-                "action_item" => InsightType::ActionItem,
-                "key_point" => InsightType::KeyPoint,
-                "decision" => InsightType::Decision,
-                _ => InsightType::Summary,
-            };
-
-            Ok(Insight {
-                id: Some(row.get(0)?),
-                meeting_id: row.get(1)?,
-                insight_type,
-                content: row.get(3)?,
-                metadata: row.get(4)?,
-                created_at: row.get(5)?,
-            })
-        })?;
+        let rows = stmt.query_map(params![meeting_id], Self::insight_from_row)?;
 
         let mut insights = Vec::new();
         for insight_result in rows {
@@ -405,6 +1053,34 @@ impl StoragePort for SqliteStorage {
         Ok(insights)
     }
 
+    async fn get_insight(&self, insight_id: i64) -> Result<Option<Insight>> {
+        let conn = self.conn.lock().unwrap();
+        let insight = conn
+            .query_row(
+                "SELECT id, meeting_id, type, content, metadata, created_at, feedback
+                 FROM insights WHERE id = ?1",
+                params![insight_id],
+                Self::insight_from_row,
+            )
+            .optional()?;
+        Ok(insight)
+    }
+
+    async fn delete_transcript(&self, transcript_id: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn.execute(
+            "DELETE FROM transcripts WHERE id = ?1",
+            params![transcript_id],
+        )?;
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!(
+                "Transcript {} not found",
+                transcript_id
+            )));
+        }
+        Ok(())
+    }
+
     async fn delete_transcripts(&self, meeting_id: i64) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
@@ -423,6 +1099,21 @@ impl StoragePort for SqliteStorage {
         Ok(())
     }
 
+    async fn set_insight_feedback(&self, insight_id: i64, feedback: Option<bool>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE insights SET feedback = ?1 WHERE id = ?2",
+            params![feedback, insight_id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!(
+                "Insight {} not found",
+                insight_id
+            )));
+        }
+        Ok(())
+    }
+
     async fn delete_insights(&self, meeting_id: i64) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
@@ -432,11 +1123,96 @@ impl StoragePort for SqliteStorage {
         Ok(())
     }
 
+    async fn save_prompt_template(&self, insight_type: &InsightType, template: &str) -> Result<()> {
+        // Custom templates are the non-default rows; the seeded defaults from migration 003
+        // are superseded by the built-in `PromptTemplates` and left untouched
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO prompt_templates (insight_type, name, prompt_text, is_default, is_active, updated_at)
+             VALUES (?1, ?2, ?3, 0, 1, ?4)
+             ON CONFLICT(insight_type, name) DO UPDATE SET
+                prompt_text = excluded.prompt_text,
+                is_active = 1,
+                updated_at = excluded.updated_at",
+            params![
+                insight_type.to_string(),
+                CUSTOM_PROMPT_TEMPLATE_NAME,
+                template,
+                chrono::Utc::now().timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn get_prompt_template(&self, insight_type: &InsightType) -> Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        let template = conn
+            .query_row(
+                "SELECT prompt_text FROM prompt_templates
+                 WHERE insight_type = ?1 AND is_default = 0 AND is_active = 1
+                 ORDER BY updated_at DESC LIMIT 1",
+                params![insight_type.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(template)
+    }
+
+    async fn reset_prompt_template(&self, insight_type: &InsightType) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM prompt_templates WHERE insight_type = ?1 AND is_default = 0",
+            params![insight_type.to_string()],
+        )?;
+        Ok(())
+    }
+
+    async fn get_app_setting(&self, key: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        let value = conn
+            .query_row(
+                "SELECT value FROM app_settings WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    async fn set_app_setting(&self, key: &str, value: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        match value {
+            Some(value) => conn.execute(
+                "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET
+                    value = excluded.value,
+                    updated_at = excluded.updated_at",
+                params![key, value, chrono::Utc::now().timestamp()],
+            )?,
+            None => conn.execute("DELETE FROM app_settings WHERE key = ?1", params![key])?,
+        };
+        Ok(())
+    }
+
     async fn save_service_config(&self, config: &ServiceConfig) -> Result<i64> {
         let conn = self.conn.lock().unwrap();
+        let tx = conn.unchecked_transaction()?;
+
+        // Activating a provider deactivates the others of its type
+        if config.is_active {
+            tx.execute(
+                "UPDATE service_configs SET is_active = 0, updated_at = ?1
+                 WHERE service_type = ?2 AND provider != ?3 AND is_active = 1",
+                params![
+                    chrono::Utc::now().timestamp(),
+                    config.service_type.to_string(),
+                    config.provider,
+                ],
+            )?;
+        }
 
         // Try to update first
-        let rows_updated = conn.execute(
+        let rows_updated = tx.execute(
             "UPDATE service_configs SET is_active = ?1, settings = ?2, updated_at = ?3
              WHERE service_type = ?4 AND provider = ?5",
             params![
@@ -448,9 +1224,9 @@ impl StoragePort for SqliteStorage {
             ],
         )?;
 
-        if rows_updated == 0 {
+        let id = if rows_updated == 0 {
             // Insert if doesn't exist
-            conn.execute(
+            tx.execute(
                 "INSERT INTO service_configs (service_type, provider, is_active, settings, created_at, updated_at)
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
                 params![
@@ -462,18 +1238,18 @@ impl StoragePort for SqliteStorage {
                     config.updated_at,
                 ],
             )?;
-            Ok(conn.last_insert_rowid())
+            tx.last_insert_rowid()
         } else {
             // Return the ID of the updated row
-            let mut stmt = conn.prepare(
+            tx.query_row(
                 "SELECT id FROM service_configs WHERE service_type = ?1 AND provider = ?2",
-            )?;
-            let id: i64 = stmt.query_row(
                 params![config.service_type.to_string(), config.provider],
                 |row| row.get(0),
-            )?;
-            Ok(id)
-        }
+            )?
+        };
+
+        tx.commit()?;
+        Ok(id)
     }
 
     async fn get_service_config(
@@ -576,6 +1352,26 @@ impl StoragePort for SqliteStorage {
         Ok(configs)
     }
 
+    async fn get_model_overrides(&self, provider: &str) -> Result<Vec<ModelOverride>> {
+        let config = self.get_service_config("llm", provider).await?;
+        Ok(ModelOverride::list_from_settings(
+            provider,
+            config.as_ref().and_then(|c| c.settings.as_deref()),
+        ))
+    }
+
+    async fn save_model_override(&self, model_override: &ModelOverride) -> Result<()> {
+        let mut config = self
+            .get_service_config("llm", &model_override.provider)
+            .await?
+            .unwrap_or_else(|| {
+                ServiceConfig::new(ServiceType::Llm, model_override.provider.clone())
+            });
+        config.settings = Some(model_override.save_to_settings(config.settings.as_deref())?);
+        self.save_service_config(&config).await?;
+        Ok(())
+    }
+
     /// Search across all searchable entities using FTS5
     async fn search_all(&self, query: &str, limit: Option<i32>) -> Result<SearchResults> {
         let search_limit = limit.unwrap_or(50);
@@ -611,9 +1407,9 @@ impl StoragePort for SqliteStorage {
         let sql = r#"
             SELECT
                 t.id, t.meeting_id, t.participant_id, p.name as participant_name,
-                t.speaker_label, t.timestamp_ms, t.text, t.confidence, t.created_at,
-                m.title as meeting_title, m.platform as meeting_platform,
-                bm25(transcripts_fts) as rank
+                t.speaker_label, t.timestamp_ms, t.end_timestamp_ms, t.text, t.confidence,
+                t.created_at, t.updated_at, m.title as meeting_title, m.platform as meeting_platform,
+                bm25(transcripts_fts) as rank, t.language, t.source
             FROM transcripts_fts
             INNER JOIN transcripts t ON transcripts_fts.rowid = t.id
             INNER JOIN meetings m ON t.meeting_id = m.id
@@ -625,13 +1421,8 @@ impl StoragePort for SqliteStorage {
 
         let mut stmt = conn.prepare(sql)?;
         let rows = stmt.query_map(params![query, search_limit], |row| {
-            let platform_str: String = row.get(10)?;
-            let platform = match platform_str.as_str() {
-                "teams" => Platform::Teams,
-                "zoom" => Platform::Zoom,
-                "meet" => Platform::Meet,
-                _ => Platform::Meet,
-            };
+            let platform_str: String = row.get(12)?;
+            let Ok(platform) = platform_str.parse::<Platform>();
 
             Ok(TranscriptSearchResult {
                 transcript: Transcript {
@@ -641,13 +1432,17 @@ impl StoragePort for SqliteStorage {
                     participant_name: row.get(3)?,
                     speaker_label: row.get(4)?,
                     timestamp_ms: row.get(5)?,
-                    text: row.get(6)?,
-                    confidence: row.get(7)?,
-                    created_at: row.get(8)?,
+                    end_timestamp_ms: row.get(6)?,
+                    text: row.get(7)?,
+                    confidence: row.get(8)?,
+                    language: row.get(14)?,
+                    source: row.get::<_, String>(15)?.parse().unwrap_or_default(),
+                    created_at: row.get(9)?,
+                    updated_at: row.get(10)?,
                 },
-                meeting_title: row.get(9)?,
+                meeting_title: row.get(11)?,
                 meeting_platform: platform.to_string(),
-                rank: row.get(11)?,
+                rank: row.get(13)?,
             })
         })?;
 
@@ -675,7 +1470,7 @@ impl StoragePort for SqliteStorage {
 
         let sql = r#"
             SELECT
-                i.id, i.meeting_id, i.type, i.content, i.metadata, i.created_at,
+                i.id, i.meeting_id, i.type, i.content, i.metadata, i.created_at, i.feedback,
                 m.title as meeting_title, m.platform as meeting_platform,
                 bm25(insights_fts) as rank
             FROM insights_fts
@@ -697,13 +1492,8 @@ impl StoragePort for SqliteStorage {
                 _ => InsightType::Summary,
             };
 
-            let platform_str: String = row.get(7)?;
-            let platform = match platform_str.as_str() {
-                "teams" => Platform::Teams,
-                "zoom" => Platform::Zoom,
-                "meet" => Platform::Meet,
-                _ => Platform::Meet,
-            };
+            let platform_str: String = row.get(8)?;
+            let Ok(platform) = platform_str.parse::<Platform>();
 
             Ok(InsightSearchResult {
                 insight: Insight {
@@ -713,10 +1503,11 @@ impl StoragePort for SqliteStorage {
                     content: row.get(3)?,
                     metadata: row.get(4)?,
                     created_at: row.get(5)?,
+                    feedback: row.get(6)?,
                 },
-                meeting_title: row.get(6)?,
+                meeting_title: row.get(7)?,
                 meeting_platform: platform.to_string(),
-                rank: row.get(8)?,
+                rank: row.get(9)?,
             })
         })?;
 
@@ -741,7 +1532,10 @@ impl StoragePort for SqliteStorage {
         let sql = r#"
             SELECT
                 m.id, m.platform, m.title, m.language, m.start_time, m.end_time,
-                m.participant_count, m.audio_file_path, m.created_at
+                m.participant_count, m.audio_file_path, m.transcripts_redacted,
+                m.detected_language, m.notes, m.audio_offset_ms, m.audio_normalized, m.created_at,
+                m.transcript_confidence, m.recording_status, m.audio_sample_rate, m.audio_channels,
+                m.audio_bits_per_sample
             FROM meetings_fts
             INNER JOIN meetings m ON meetings_fts.rowid = m.id
             WHERE meetings_fts MATCH ?1
@@ -752,12 +1546,7 @@ impl StoragePort for SqliteStorage {
         let mut stmt = conn.prepare(sql)?;
         let rows = stmt.query_map(params![query, search_limit], |row| {
             let platform_str: String = row.get(1)?;
-            let platform = match platform_str.as_str() {
-                "teams" => Platform::Teams,
-                "zoom" => Platform::Zoom,
-                "meet" => Platform::Meet,
-                _ => Platform::Meet,
-            };
+            let Ok(platform) = platform_str.parse::<Platform>();
 
             Ok(Meeting {
                 id: Some(row.get(0)?),
@@ -768,7 +1557,19 @@ impl StoragePort for SqliteStorage {
                 end_time: row.get(5)?,
                 participant_count: row.get(6)?,
                 audio_file_path: row.get(7)?,
-                created_at: row.get(8)?,
+                transcripts_redacted: row.get(8)?,
+                detected_language: row.get(9)?,
+                notes: row.get(10)?,
+                audio_offset_ms: row.get(11)?,
+                audio_normalized: row.get(12)?,
+                created_at: row.get(13)?,
+                transcript_confidence: row.get(14)?,
+                recording_status: row
+                    .get::<_, Option<String>>(15)?
+                    .and_then(|s| s.parse().ok()),
+                audio_sample_rate: row.get(16)?,
+                audio_channels: row.get(17)?,
+                audio_bits_per_sample: row.get(18)?,
             })
         })?;
 
@@ -780,3 +1581,1160 @@ impl StoragePort for SqliteStorage {
         Ok(results)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::RecordingStatus;
+
+    fn setup_storage() -> SqliteStorage {
+        let storage = SqliteStorage::new(PathBuf::from(":memory:")).unwrap();
+        storage.run_migrations().unwrap();
+        storage
+    }
+
+    fn speaker_transcript(meeting_id: i64, timestamp_ms: i64, label: &str) -> Transcript {
+        Transcript::with_speaker(
+            meeting_id,
+            timestamp_ms,
+            format!("{} at {}", label, timestamp_ms),
+            Some(0.9),
+            Some(label.to_string()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_merge_speaker_labels() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        storage
+            .create_transcripts_batch(&[
+                speaker_transcript(meeting_id, 0, "Speaker 0"),
+                speaker_transcript(meeting_id, 1000, "Speaker 2"),
+                speaker_transcript(meeting_id, 2000, "Speaker 0"),
+                speaker_transcript(meeting_id, 3000, "Speaker 2"),
+                speaker_transcript(meeting_id, 4000, "Speaker 2"),
+                speaker_transcript(meeting_id, 5000, "Speaker 1"),
+            ])
+            .await
+            .unwrap();
+
+        let mut participant = Participant::new(meeting_id, "Alice".to_string(), None);
+        participant.speaker_label = Some("Speaker 2".to_string());
+        let participant_id = storage.create_participant(&participant).await.unwrap();
+        storage
+            .update_transcripts_by_speaker_label(meeting_id, "Speaker 2", participant_id)
+            .await
+            .unwrap();
+
+        let merged = storage
+            .merge_speaker_labels(meeting_id, "Speaker 2", "Speaker 0")
+            .await
+            .unwrap();
+        assert_eq!(merged, 3);
+
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        let count_for = |label: &str| {
+            transcripts
+                .iter()
+                .filter(|t| t.speaker_label.as_deref() == Some(label))
+                .count()
+        };
+        assert_eq!(count_for("Speaker 0"), 5);
+        assert_eq!(count_for("Speaker 2"), 0);
+        assert_eq!(count_for("Speaker 1"), 1);
+
+        // The participant follows the surviving label and links every merged segment
+        let participants = storage.get_participants(meeting_id).await.unwrap();
+        assert_eq!(participants.len(), 1);
+        assert_eq!(participants[0].speaker_label.as_deref(), Some("Speaker 0"));
+        assert!(transcripts
+            .iter()
+            .filter(|t| t.speaker_label.as_deref() == Some("Speaker 0"))
+            .all(|t| t.participant_id == Some(participant_id)));
+    }
+
+    #[tokio::test]
+    async fn test_assign_speakers() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Teams, None, None))
+            .await
+            .unwrap();
+
+        storage
+            .create_transcripts_batch(&[
+                speaker_transcript(meeting_id, 0, "Speaker 0"),
+                speaker_transcript(meeting_id, 1000, "Speaker 1"),
+                speaker_transcript(meeting_id, 2000, "Speaker 0"),
+            ])
+            .await
+            .unwrap();
+
+        let mapping = vec![
+            ("Speaker 0".to_string(), "Alice".to_string()),
+            ("Speaker 1".to_string(), "Bob".to_string()),
+        ];
+
+        // Running twice must not create duplicate participants
+        for _ in 0..2 {
+            let linked = storage.assign_speakers(meeting_id, &mapping).await.unwrap();
+            assert_eq!(linked, 3);
+        }
+
+        let participants = storage.get_participants(meeting_id).await.unwrap();
+        assert_eq!(participants.len(), 2);
+
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        let names: Vec<_> = transcripts
+            .iter()
+            .map(|t| t.participant_name.as_deref())
+            .collect();
+        assert_eq!(names, vec![Some("Alice"), Some("Bob"), Some("Alice")]);
+    }
+
+    #[tokio::test]
+    async fn test_assign_speakers_rejects_unknown_label() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Teams, None, None))
+            .await
+            .unwrap();
+        storage
+            .create_transcript(&speaker_transcript(meeting_id, 0, "Speaker 0"))
+            .await
+            .unwrap();
+
+        let mapping = vec![
+            ("Speaker 0".to_string(), "Alice".to_string()),
+            ("Speaker 5".to_string(), "Bob".to_string()),
+        ];
+        let result = storage.assign_speakers(meeting_id, &mapping).await;
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+
+        // Nothing is written when validation fails
+        assert!(storage
+            .get_participants(meeting_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prompt_template_save_and_reset() {
+        let storage = setup_storage();
+        let summary = InsightType::Summary;
+
+        // Seeded defaults from the migration are not returned as custom templates
+        assert_eq!(storage.get_prompt_template(&summary).await.unwrap(), None);
+
+        storage
+            .save_prompt_template(&summary, "Summarize: {transcript}")
+            .await
+            .unwrap();
+        storage
+            .save_prompt_template(&summary, "Briefly summarize: {transcript}")
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get_prompt_template(&summary).await.unwrap(),
+            Some("Briefly summarize: {transcript}".to_string())
+        );
+        assert_eq!(
+            storage
+                .get_prompt_template(&InsightType::Decision)
+                .await
+                .unwrap(),
+            None
+        );
+
+        storage.reset_prompt_template(&summary).await.unwrap();
+        assert_eq!(storage.get_prompt_template(&summary).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_other_platform_round_trips() {
+        let storage = setup_storage();
+        let platform = Platform::Other("webex".to_string());
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(platform.clone(), None, None))
+            .await
+            .unwrap();
+
+        let meeting = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(meeting.platform, platform);
+
+        let meetings = storage.list_meetings(None, None).await.unwrap();
+        assert_eq!(meetings[0].platform, platform);
+
+        // FTS triggers were recreated with the rebuilt table
+        let found = storage.search_meetings("webex", None).await.unwrap();
+        assert_eq!(found[0].id, Some(meeting_id));
+    }
+
+    #[tokio::test]
+    async fn test_get_and_list_meetings_agree_on_platform() {
+        let storage = setup_storage();
+        for name in ["teams", "zoom", "meet", "webex"] {
+            let meeting_id = storage
+                .create_meeting(&Meeting::new(Platform::Teams, Some(name.to_string()), None))
+                .await
+                .unwrap();
+            // Write the raw string so parsing is exercised on read
+            storage
+                .conn
+                .lock()
+                .unwrap()
+                .execute(
+                    "UPDATE meetings SET platform = ?1 WHERE id = ?2",
+                    params![name, meeting_id],
+                )
+                .unwrap();
+        }
+
+        let listed = storage.list_meetings(None, None).await.unwrap();
+        assert_eq!(listed.len(), 4);
+        for meeting in listed {
+            let fetched = storage
+                .get_meeting(meeting.id.unwrap())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(fetched.platform, meeting.platform);
+            assert_eq!(meeting.platform.to_string(), meeting.title.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_meeting_notes() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        storage
+            .update_meeting_notes(meeting_id, Some("Follow up with legal"))
+            .await
+            .unwrap();
+        let meeting = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(meeting.notes.as_deref(), Some("Follow up with legal"));
+
+        storage
+            .update_meeting_notes(meeting_id, None)
+            .await
+            .unwrap();
+        let meeting = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(meeting.notes, None);
+
+        assert!(storage
+            .update_meeting_notes(meeting_id + 1, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ending_and_saving_a_meeting_keep_each_others_columns() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        // Either order leaves both the end time and the recording in place
+        let recording = SavedRecording {
+            audio_file_path: "/recordings/meeting_1.wav".to_string(),
+            audio_offset_ms: 120,
+            audio_normalized: true,
+            sample_rate: 16000,
+            channels: 1,
+            bits_per_sample: 16,
+        };
+        storage
+            .set_meeting_recording(meeting_id, &recording)
+            .await
+            .unwrap();
+        storage
+            .set_meeting_end_time(meeting_id, 1_700_000_000)
+            .await
+            .unwrap();
+
+        let meeting = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(meeting.end_time, Some(1_700_000_000));
+        assert_eq!(
+            meeting.audio_file_path.as_deref(),
+            Some("/recordings/meeting_1.wav")
+        );
+        assert_eq!(meeting.audio_offset_ms, 120);
+        assert!(meeting.audio_normalized);
+        assert_eq!(meeting.recording_status, Some(RecordingStatus::Recorded));
+        assert_eq!(meeting.audio_sample_rate, Some(16000));
+        assert_eq!(meeting.audio_channels, Some(1));
+        assert_eq!(meeting.audio_bits_per_sample, Some(16));
+
+        let empty_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        storage
+            .set_meeting_end_time(empty_id, 1_700_000_000)
+            .await
+            .unwrap();
+        storage
+            .set_recording_status(empty_id, RecordingStatus::NoAudio)
+            .await
+            .unwrap();
+        let meeting = storage.get_meeting(empty_id).await.unwrap().unwrap();
+        assert_eq!(meeting.end_time, Some(1_700_000_000));
+        assert_eq!(meeting.recording_status, Some(RecordingStatus::NoAudio));
+
+        assert!(storage
+            .set_recording_status(empty_id + 1, RecordingStatus::NoAudio)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_meeting_audio_processing() {
+        let storage = setup_storage();
+        let mut meeting = Meeting::new(Platform::Teams, None, None);
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+
+        let stored = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(stored.audio_offset_ms, 0);
+        assert!(!stored.audio_normalized);
+        assert_eq!(stored.recording_status, None);
+
+        meeting.id = Some(meeting_id);
+        meeting.audio_offset_ms = 1750;
+        meeting.audio_normalized = true;
+        meeting.recording_status = Some(RecordingStatus::NoAudio);
+        storage.update_meeting(&meeting).await.unwrap();
+
+        let stored = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(stored.audio_offset_ms, 1750);
+        assert!(stored.audio_normalized);
+        assert_eq!(stored.recording_status, Some(RecordingStatus::NoAudio));
+    }
+
+    #[tokio::test]
+    async fn test_meeting_audio_format_round_trip() {
+        let storage = setup_storage();
+        let mut meeting = Meeting::new(Platform::Zoom, None, None);
+        meeting.audio_sample_rate = Some(48000);
+        meeting.audio_channels = Some(2);
+        meeting.audio_bits_per_sample = Some(32);
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+
+        let stored = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(stored.audio_sample_rate, Some(48000));
+        assert_eq!(stored.audio_channels, Some(2));
+        assert_eq!(stored.audio_bits_per_sample, Some(32));
+
+        meeting.id = Some(meeting_id);
+        meeting.audio_sample_rate = Some(16000);
+        meeting.audio_channels = Some(1);
+        meeting.audio_bits_per_sample = Some(16);
+        storage.update_meeting(&meeting).await.unwrap();
+
+        let listed = storage.list_meetings(None, None).await.unwrap();
+        assert_eq!(listed[0].audio_sample_rate, Some(16000));
+        assert_eq!(listed[0].audio_channels, Some(1));
+        assert_eq!(listed[0].audio_bits_per_sample, Some(16));
+    }
+
+    #[tokio::test]
+    async fn test_transcript_end_timestamps_round_trip() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let mut with_end = speaker_transcript(meeting_id, 0, "Speaker 0");
+        with_end.end_timestamp_ms = Some(2400);
+        let without_end = speaker_transcript(meeting_id, 3000, "Speaker 1");
+        storage
+            .create_transcripts_batch(&[with_end, without_end])
+            .await
+            .unwrap();
+
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts[0].end_timestamp_ms, Some(2400));
+        assert_eq!(transcripts[1].end_timestamp_ms, None);
+
+        let mut updated = transcripts[1].clone();
+        updated.end_timestamp_ms = Some(3900);
+        storage.update_transcript(&updated).await.unwrap();
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts[1].end_timestamp_ms, Some(3900));
+    }
+
+    #[tokio::test]
+    async fn test_transcript_language_round_trip() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let mut spanish = speaker_transcript(meeting_id, 0, "Speaker 0");
+        spanish.language = Some("es".to_string());
+        let untagged = speaker_transcript(meeting_id, 1000, "Speaker 1");
+        storage
+            .create_transcripts_batch(&[spanish, untagged])
+            .await
+            .unwrap();
+
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts[0].language.as_deref(), Some("es"));
+        assert_eq!(transcripts[1].language, None);
+
+        let mut english = transcripts[1].clone();
+        english.language = Some("en".to_string());
+        storage.update_transcript(&english).await.unwrap();
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts[1].language.as_deref(), Some("en"));
+    }
+
+    #[tokio::test]
+    async fn test_get_insight_by_id() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let id = storage
+            .create_insight(&Insight::new(
+                meeting_id,
+                InsightType::ActionItem,
+                "Send the notes".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let insight = storage.get_insight(id).await.unwrap().unwrap();
+        assert_eq!(insight.id, Some(id));
+        assert_eq!(insight.meeting_id, meeting_id);
+        assert_eq!(insight.insight_type, InsightType::ActionItem);
+        assert_eq!(insight.content, "Send the notes");
+
+        assert!(storage.get_insight(id + 1).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_insight_feedback_round_trip() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        let id = storage
+            .create_insight(&Insight::new(
+                meeting_id,
+                InsightType::Decision,
+                "Move the launch to March".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get_insight(id).await.unwrap().unwrap().feedback,
+            None
+        );
+
+        storage.set_insight_feedback(id, Some(false)).await.unwrap();
+        let insights = storage.get_insights(meeting_id).await.unwrap();
+        assert_eq!(insights[0].feedback, Some(false));
+
+        storage.set_insight_feedback(id, Some(true)).await.unwrap();
+        assert_eq!(
+            storage.get_insight(id).await.unwrap().unwrap().feedback,
+            Some(true)
+        );
+        storage.set_insight_feedback(id, None).await.unwrap();
+        assert_eq!(
+            storage.get_insight(id).await.unwrap().unwrap().feedback,
+            None
+        );
+
+        assert!(matches!(
+            storage.set_insight_feedback(id + 1, Some(true)).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        // Rating and editing leave the search index intact
+        storage
+            .update_insight_content(id, "Move the launch to April")
+            .await
+            .unwrap();
+        let results = storage.search_insights("April", None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(storage
+            .search_insights("March", None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transcripts_filtered_by_source() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let live = Transcript {
+            source: TranscriptSource::Streaming,
+            end_timestamp_ms: Some(900),
+            ..Transcript::new(meeting_id, 0, "live caption".to_string(), None)
+        };
+        storage
+            .upsert_transcript_segment(&live, true)
+            .await
+            .unwrap();
+        storage
+            .create_transcripts_batch(&[speaker_transcript(meeting_id, 0, "Speaker 0")])
+            .await
+            .unwrap();
+
+        let streaming = storage
+            .get_transcripts_by_source(meeting_id, TranscriptSource::Streaming)
+            .await
+            .unwrap();
+        assert_eq!(streaming.len(), 1);
+        assert_eq!(streaming[0].text, "live caption");
+        let batch = storage
+            .get_transcripts_by_source(meeting_id, TranscriptSource::Batch)
+            .await
+            .unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].source, TranscriptSource::Batch);
+        assert_eq!(storage.get_transcripts(meeting_id).await.unwrap().len(), 2);
+
+        // Re-transcribing replaces the batch rows and keeps the live captions
+        let retranscribed = Transcript::new(meeting_id, 0, "better transcript".to_string(), None);
+        storage
+            .replace_transcripts(meeting_id, &[retranscribed])
+            .await
+            .unwrap();
+        let texts: Vec<String> = storage
+            .get_transcripts(meeting_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.text)
+            .collect();
+        assert_eq!(texts.len(), 2);
+        assert!(texts.contains(&"live caption".to_string()));
+        assert!(texts.contains(&"better transcript".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_confident_transcripts() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let segments = [
+            Transcript::new(meeting_id, 0, "clear".to_string(), Some(0.9)),
+            Transcript::new(meeting_id, 1000, "noise".to_string(), Some(0.2)),
+            Transcript::new(meeting_id, 2000, "unscored".to_string(), None),
+            Transcript::new(meeting_id, 3000, "borderline".to_string(), Some(0.5)),
+        ];
+        storage.create_transcripts_batch(&segments).await.unwrap();
+
+        let texts = |transcripts: Vec<Transcript>| {
+            transcripts.into_iter().map(|t| t.text).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            texts(
+                storage
+                    .get_confident_transcripts(meeting_id, 0.5)
+                    .await
+                    .unwrap()
+            ),
+            vec!["clear", "unscored", "borderline"]
+        );
+        assert_eq!(storage.get_transcripts(meeting_id).await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_create_participants_batch() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Teams, None, None))
+            .await
+            .unwrap();
+
+        let names = ["Alice", "Bob", "Carol"];
+        let participants: Vec<Participant> = names
+            .iter()
+            .map(|name| Participant::new(meeting_id, name.to_string(), None))
+            .collect();
+
+        let ids = storage
+            .create_participants_batch(&participants)
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let stored = storage.get_participants(meeting_id).await.unwrap();
+        for (id, name) in ids.iter().zip(names) {
+            let participant = stored.iter().find(|p| p.id == Some(*id)).unwrap();
+            assert_eq!(participant.name, name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_participants_batch_is_atomic() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Teams, None, None))
+            .await
+            .unwrap();
+
+        // The second participant references a meeting that doesn't exist
+        let participants = vec![
+            Participant::new(meeting_id, "Alice".to_string(), None),
+            Participant::new(meeting_id + 1, "Bob".to_string(), None),
+        ];
+
+        assert!(storage
+            .create_participants_batch(&participants)
+            .await
+            .is_err());
+        assert!(storage
+            .get_participants(meeting_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_transcript_text() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        let transcript_id = storage
+            .create_transcript(&Transcript::new(
+                meeting_id,
+                0,
+                "the quarterly numbers look grate".to_string(),
+                Some(0.8),
+            ))
+            .await
+            .unwrap();
+
+        let transcript = &storage.get_transcripts(meeting_id).await.unwrap()[0];
+        assert_eq!(transcript.updated_at, None);
+
+        let before_edit = chrono::Utc::now().timestamp();
+        storage
+            .update_transcript_text(transcript_id, "the quarterly numbers look great")
+            .await
+            .unwrap();
+
+        let transcript = &storage.get_transcripts(meeting_id).await.unwrap()[0];
+        assert_eq!(transcript.text, "the quarterly numbers look great");
+        assert!(transcript.updated_at.unwrap() >= before_edit);
+
+        // The search index follows the edit
+        let results = storage.search_transcripts("great", None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].transcript.updated_at, transcript.updated_at);
+
+        assert!(matches!(
+            storage.update_transcript_text(transcript_id + 1, "x").await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_single_transcript() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        let ids = storage
+            .create_transcripts_batch(&[
+                Transcript::new(meeting_id, 0, "welcome everyone".to_string(), None),
+                Transcript::new(meeting_id, 1000, "misheard gibberish".to_string(), None),
+                Transcript::new(meeting_id, 2000, "let's get started".to_string(), None),
+            ])
+            .await
+            .unwrap();
+
+        storage.delete_transcript(ids[1]).await.unwrap();
+
+        let remaining: Vec<(Option<i64>, String)> = storage
+            .get_transcripts(meeting_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.id, t.text))
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![
+                (Some(ids[0]), "welcome everyone".to_string()),
+                (Some(ids[2]), "let's get started".to_string()),
+            ]
+        );
+        assert!(storage
+            .search_transcripts("gibberish", None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(matches!(
+            storage.delete_transcript(ids[1]).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_replace_transcripts() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        let other_meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Teams, None, None))
+            .await
+            .unwrap();
+
+        let old = vec![
+            Transcript::new(meeting_id, 0, "old first".to_string(), None),
+            Transcript::new(meeting_id, 1000, "old second".to_string(), None),
+        ];
+        storage.create_transcripts_batch(&old).await.unwrap();
+        storage
+            .create_transcript(&Transcript::new(
+                other_meeting_id,
+                0,
+                "other meeting".to_string(),
+                None,
+            ))
+            .await
+            .unwrap();
+
+        let new = vec![Transcript::new(meeting_id, 0, "new".to_string(), Some(0.9))];
+        let ids = storage.replace_transcripts(meeting_id, &new).await.unwrap();
+        assert_eq!(ids.len(), 1);
+
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts.len(), 1);
+        assert_eq!(transcripts[0].id, Some(ids[0]));
+        assert_eq!(transcripts[0].text, "new");
+        assert_eq!(
+            storage
+                .get_transcripts(other_meeting_id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // A failed insert leaves the existing transcripts in place
+        let mut orphan = Transcript::new(meeting_id, 0, "broken".to_string(), None);
+        orphan.participant_id = Some(9999);
+        let failing = vec![
+            Transcript::new(meeting_id, 0, "ok".to_string(), None),
+            orphan,
+        ];
+        assert!(storage
+            .replace_transcripts(meeting_id, &failing)
+            .await
+            .is_err());
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts.len(), 1);
+        assert_eq!(transcripts[0].text, "new");
+
+        // Transcripts of another meeting are rejected
+        let wrong = vec![Transcript::new(other_meeting_id, 0, "x".to_string(), None)];
+        assert!(matches!(
+            storage.replace_transcripts(meeting_id, &wrong).await,
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_participants_then_link_speaker() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Teams, None, None))
+            .await
+            .unwrap();
+        storage
+            .create_transcript(&Transcript::with_speaker(
+                meeting_id,
+                0,
+                "Morning all".to_string(),
+                None,
+                Some("Speaker 0".to_string()),
+            ))
+            .await
+            .unwrap();
+
+        let roster = vec![
+            Participant::new(
+                meeting_id,
+                "Ana Lima".to_string(),
+                Some("ana@example.com".to_string()),
+            ),
+            Participant::new(meeting_id, "Ben Ortiz".to_string(), None),
+            Participant::new(meeting_id, "Dropped".to_string(), None),
+        ];
+        let ids = storage.set_participants(meeting_id, &roster).await.unwrap();
+        assert_eq!(ids.len(), 3);
+
+        let participants = storage.get_participants(meeting_id).await.unwrap();
+        assert_eq!(participants.len(), 3);
+        assert!(participants.iter().all(|p| p.speaker_label.is_none()));
+
+        // Assigning a speaker by name links the roster entry instead of adding a participant
+        storage
+            .assign_speakers(
+                meeting_id,
+                &[("Speaker 0".to_string(), "ana lima".to_string())],
+            )
+            .await
+            .unwrap();
+        let participants = storage.get_participants(meeting_id).await.unwrap();
+        let ana = participants.iter().find(|p| p.id == Some(ids[0])).unwrap();
+        assert_eq!(ana.speaker_label.as_deref(), Some("Speaker 0"));
+        assert_eq!(ana.email.as_deref(), Some("ana@example.com"));
+        assert_eq!(participants.len(), 3);
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts[0].participant_id, Some(ids[0]));
+
+        // Resetting the roster matches by email, keeps linked participants and drops the rest
+        let roster = vec![
+            Participant::new(
+                meeting_id,
+                "Ana L.".to_string(),
+                Some("ANA@example.com".to_string()),
+            ),
+            Participant::new(
+                meeting_id,
+                "Ben Ortiz".to_string(),
+                Some("ben@example.com".to_string()),
+            ),
+        ];
+        let new_ids = storage.set_participants(meeting_id, &roster).await.unwrap();
+        assert_eq!(new_ids, vec![ids[0], ids[1]]);
+
+        let participants = storage.get_participants(meeting_id).await.unwrap();
+        assert_eq!(participants.len(), 2);
+        let ana = participants.iter().find(|p| p.id == Some(ids[0])).unwrap();
+        assert_eq!(ana.name, "Ana L.");
+        assert_eq!(ana.speaker_label.as_deref(), Some("Speaker 0"));
+        let ben = participants.iter().find(|p| p.id == Some(ids[1])).unwrap();
+        assert_eq!(ben.email.as_deref(), Some("ben@example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_interim_then_final_segments() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let segment = |start: i64, end: i64, text: &str| Transcript {
+            end_timestamp_ms: Some(end),
+            ..Transcript::new(meeting_id, start, text.to_string(), None)
+        };
+
+        // Interims of one utterance grow over the same range and share a row
+        let first = storage
+            .upsert_transcript_segment(&segment(0, 800, "hel"), false)
+            .await
+            .unwrap();
+        let second = storage
+            .upsert_transcript_segment(&segment(0, 1500, "hello wor"), false)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+
+        // The final replaces the interim row instead of adding one
+        let final_id = storage
+            .upsert_transcript_segment(&segment(0, 2000, "hello world"), true)
+            .await
+            .unwrap();
+        assert_eq!(final_id, first);
+
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts.len(), 1);
+        assert_eq!(transcripts[0].text, "hello world");
+        assert_eq!(transcripts[0].end_timestamp_ms, Some(2000));
+
+        // The search index follows the replaced text
+        assert_eq!(
+            storage
+                .search_transcripts("world", None)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(storage
+            .search_transcripts("wor", None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // A stored final is never replaced, even by an overlapping segment
+        let next = storage
+            .upsert_transcript_segment(&segment(1900, 2600, "how are"), false)
+            .await
+            .unwrap();
+        assert_ne!(next, final_id);
+        let next_final = storage
+            .upsert_transcript_segment(&segment(2000, 3000, "how are you"), true)
+            .await
+            .unwrap();
+        assert_eq!(next_final, next);
+
+        let texts: Vec<String> = storage
+            .get_transcripts(meeting_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.text)
+            .collect();
+        assert_eq!(texts, vec!["hello world", "how are you"]);
+    }
+
+    #[tokio::test]
+    async fn test_meeting_model_override_roundtrip() {
+        let storage = setup_storage();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        assert!(storage
+            .get_meeting_model_override(meeting_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        let mut model_override = MeetingModelOverride {
+            meeting_id,
+            asr_provider: Some("assemblyai".to_string()),
+            llm_model: Some("gpt-4o-mini".to_string()),
+            ..Default::default()
+        };
+        storage
+            .set_meeting_model_override(&model_override)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage
+                .get_meeting_model_override(meeting_id)
+                .await
+                .unwrap(),
+            Some(model_override.clone())
+        );
+
+        // Setting again replaces the whole override
+        model_override.asr_provider = None;
+        storage
+            .set_meeting_model_override(&model_override)
+            .await
+            .unwrap();
+        let stored = storage
+            .get_meeting_model_override(meeting_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.asr_provider, None);
+        assert_eq!(stored.llm_model.as_deref(), Some("gpt-4o-mini"));
+
+        // Clearing every field removes it
+        storage
+            .set_meeting_model_override(&MeetingModelOverride {
+                meeting_id,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(storage
+            .get_meeting_model_override(meeting_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Unknown meetings are rejected, and deleting a meeting drops its override
+        assert!(storage
+            .set_meeting_model_override(&MeetingModelOverride {
+                meeting_id: meeting_id + 1,
+                asr_model: Some("nova-3".to_string()),
+                ..Default::default()
+            })
+            .await
+            .is_err());
+        storage
+            .set_meeting_model_override(&model_override)
+            .await
+            .unwrap();
+        storage.delete_meeting(meeting_id).await.unwrap();
+        assert!(storage
+            .get_meeting_model_override(meeting_id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_model_overrides_stored_in_service_settings() {
+        let storage = setup_storage();
+        assert!(storage
+            .get_model_overrides("groq")
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Creates an inactive config when the provider has none yet
+        storage
+            .save_model_override(
+                &ModelOverride::new("groq".to_string(), "kimi-k2".to_string())
+                    .with_context_window(131_072),
+            )
+            .await
+            .unwrap();
+        let config = storage
+            .get_service_config("llm", "groq")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!config.is_active);
+
+        // Keeps the provider's other settings
+        let config = ServiceConfig::new(ServiceType::Llm, "openai".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"model": "gpt-5"}"#.to_string()));
+        storage.save_service_config(&config).await.unwrap();
+        for context_window in [400_000, 272_000] {
+            storage
+                .save_model_override(
+                    &ModelOverride::new("openai".to_string(), "gpt-5".to_string())
+                        .with_context_window(context_window),
+                )
+                .await
+                .unwrap();
+        }
+
+        let overrides = storage.get_model_overrides("openai").await.unwrap();
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].context_window, Some(272_000));
+        let config = storage
+            .get_active_service_config("llm")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.model().as_deref(), Some("gpt-5"));
+    }
+
+    #[tokio::test]
+    async fn test_file_database_uses_wal() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("meet-scribe.db")).unwrap();
+        storage.run_migrations().unwrap();
+
+        {
+            let conn = storage.conn.lock().unwrap();
+            let journal_mode: String = conn
+                .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(journal_mode, "wal");
+            // NORMAL = 1
+            let synchronous: i64 = conn
+                .query_row("PRAGMA synchronous", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(synchronous, 1);
+        }
+
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        assert!(storage.get_meeting(meeting_id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_only_one_service_config_active_per_type() {
+        let storage = setup_storage();
+        let active_providers = |configs: Vec<ServiceConfig>, service_type: ServiceType| {
+            configs
+                .into_iter()
+                .filter(|c| c.service_type == service_type && c.is_active)
+                .map(|c| c.provider)
+                .collect::<Vec<_>>()
+        };
+
+        let llm = ServiceConfig::new(ServiceType::Llm, "openai".to_string()).with_active(true);
+        storage.save_service_config(&llm).await.unwrap();
+
+        for provider in ["deepgram", "assemblyai", "deepgram"] {
+            let config = ServiceConfig::new(ServiceType::Asr, provider.to_string())
+                .with_active(true)
+                .with_settings(Some(r#"{"model": "nova-3"}"#.to_string()));
+            storage.save_service_config(&config).await.unwrap();
+
+            let configs = storage.list_service_configs().await.unwrap();
+            assert_eq!(
+                active_providers(configs.clone(), ServiceType::Asr),
+                [provider]
+            );
+            // Other service types are untouched
+            assert_eq!(active_providers(configs, ServiceType::Llm), ["openai"]);
+            assert_eq!(
+                storage
+                    .get_active_service_config("asr")
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .provider,
+                provider
+            );
+        }
+
+        // The database itself refuses a second active config of a type
+        let conn = storage.conn.lock().unwrap();
+        let result = conn.execute(
+            "UPDATE service_configs SET is_active = 1 WHERE service_type = 'asr'",
+            [],
+        );
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_app_settings() {
+        let storage = setup_storage();
+        assert_eq!(storage.get_app_setting("proxy").await.unwrap(), None);
+
+        storage.set_app_setting("proxy", Some("a")).await.unwrap();
+        storage.set_app_setting("proxy", Some("b")).await.unwrap();
+        assert_eq!(
+            storage.get_app_setting("proxy").await.unwrap().as_deref(),
+            Some("b")
+        );
+
+        storage.set_app_setting("proxy", None).await.unwrap();
+        assert_eq!(storage.get_app_setting("proxy").await.unwrap(), None);
+    }
+}
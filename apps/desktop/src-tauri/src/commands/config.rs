@@ -1,10 +1,23 @@
 //! Configuration and API key management commands
 
+use crate::adapters::services::asr::{AssemblyAIService, DeepgramService};
+use crate::adapters::services::llm::{
+    AnthropicService, GoogleService, GroqService, MistralService, OpenAIService,
+};
+use crate::commands::llm::{azure_openai_service, AZURE_OPENAI_PROVIDER};
 use crate::domain::models::{ServiceConfig, ServiceType};
+use crate::error::{CommandError, CommandResult};
+use crate::ports::llm::LlmServicePort;
 use crate::ports::storage::StoragePort;
-use crate::utils::keychain::KeychainPort;
+use crate::utils::http::{self, ProxyConfig, PROXY_SETTING_KEY};
+use crate::utils::keychain::{KeyStatus, KeychainPort};
+use crate::utils::rate_limit::{self, RateLimits, RATE_LIMITS_SETTING_KEY};
 use crate::AppState;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How long a live API key test may take before it's reported as failed
+const API_KEY_TEST_TIMEOUT: Duration = Duration::from_secs(8);
 
 /// Request to save an API key
 #[derive(Debug, Deserialize)]
@@ -14,6 +27,13 @@ pub struct SaveApiKeyRequest {
     pub api_key: String,
 }
 
+/// Response after saving an API key
+#[derive(Debug, Serialize)]
+pub struct SaveApiKeyResponse {
+    /// Set when the key doesn't match the provider's usual format (the key is still saved)
+    pub format_warning: Option<String>,
+}
+
 /// Request to get an API key (returns masked version)
 #[derive(Debug, Deserialize)]
 pub struct GetApiKeyRequest {
@@ -28,6 +48,73 @@ pub struct ApiKeyStatus {
     pub masked_key: Option<String>, // Shows last 4 chars: "sk-...abc123"
 }
 
+/// Marker that precedes the visible tail of a masked key
+const MASK_MARKER: &str = "...";
+
+/// Mask a key so only its last 4 characters are visible
+fn mask_api_key(key: &str) -> String {
+    let char_count = key.chars().count();
+    if char_count > 4 {
+        let tail: String = key.chars().skip(char_count - 4).collect();
+        format!("{}{}", MASK_MARKER, tail)
+    } else {
+        MASK_MARKER.to_string()
+    }
+}
+
+/// Trim a key entered by the user and reject values that can't be a real key
+fn normalize_api_key(api_key: &str) -> CommandResult<String> {
+    let api_key = api_key.trim();
+
+    if api_key.is_empty() {
+        return Err(CommandError::invalid_input("API key cannot be empty"));
+    }
+
+    // Pasting the masked value from the settings screen back in would overwrite the real key
+    if api_key.contains(MASK_MARKER) || api_key.contains('\u{2026}') {
+        return Err(CommandError::invalid_input(
+            "This looks like a masked API key. Paste the full key from your provider instead",
+        ));
+    }
+
+    if api_key.chars().any(char::is_whitespace) {
+        return Err(CommandError::invalid_input(
+            "API key cannot contain spaces or line breaks",
+        ));
+    }
+
+    Ok(api_key.to_string())
+}
+
+/// Check a key against the provider's known prefix, if it has one
+fn api_key_format_warning(provider: &str, api_key: &str) -> Option<String> {
+    let expected_prefix = match provider {
+        "anthropic" => "sk-ant-",
+        "openai" => "sk-",
+        _ => return None,
+    };
+
+    let matches = api_key.starts_with(expected_prefix)
+        // Anthropic keys share OpenAI's prefix, so an OpenAI key must not be an Anthropic one
+        && !(provider == "openai" && api_key.starts_with("sk-ant-"));
+
+    if matches {
+        None
+    } else {
+        Some(format!(
+            "{} API keys usually start with \"{}\". Double-check the key if requests fail.",
+            provider, expected_prefix
+        ))
+    }
+}
+
+/// Result of a live API key test
+#[derive(Debug, Serialize)]
+pub struct ApiKeyTestResult {
+    pub ok: bool,
+    pub message: String,
+}
+
 /// Request to save service configuration
 #[derive(Debug, Deserialize)]
 pub struct SaveServiceConfigRequest {
@@ -48,20 +135,43 @@ pub struct ServiceConfigResponse {
     pub has_api_key: bool,
 }
 
+impl ServiceConfigResponse {
+    /// Combine a stored config with whether its provider has a key in the keychain
+    fn new(config: ServiceConfig, keychain: &dyn KeychainPort) -> Self {
+        let service_type = format!("{:?}", config.service_type).to_lowercase();
+        let has_api_key = keychain.has_api_key(&service_type, &config.provider);
+        Self {
+            id: config.id,
+            service_type,
+            provider: config.provider,
+            is_active: config.is_active,
+            settings: config.settings,
+            has_api_key,
+        }
+    }
+}
+
 /// Saves an API key to the OS keychain
 ///
 /// The API key is stored securely using platform-specific mechanisms:
 /// - Windows: Windows Credential Manager
 /// - Linux: Secret Service (GNOME Keyring, KWallet)
+///
+/// The key is trimmed before saving. Empty or masked values are rejected, and a key that
+/// doesn't match the provider's usual prefix is saved with a `format_warning`.
 #[tauri::command]
 pub async fn save_api_key(
     state: tauri::State<'_, AppState>,
     request: SaveApiKeyRequest,
-) -> Result<(), String> {
+) -> CommandResult<SaveApiKeyResponse> {
+    let api_key = normalize_api_key(&request.api_key)?;
+    let format_warning = api_key_format_warning(&request.provider, &api_key);
+
     state
         .keychain
-        .save_api_key(&request.service_type, &request.provider, &request.api_key)
-        .map_err(|e| e.to_string())
+        .save_api_key(&request.service_type, &request.provider, &api_key)?;
+
+    Ok(SaveApiKeyResponse { format_warning })
 }
 
 /// Checks if an API key exists and returns a masked version
@@ -73,22 +183,16 @@ pub async fn save_api_key(
 pub async fn get_api_key_status(
     state: tauri::State<'_, AppState>,
     request: GetApiKeyRequest,
-) -> Result<ApiKeyStatus, String> {
+) -> CommandResult<ApiKeyStatus> {
     match state
         .keychain
         .get_api_key(&request.service_type, &request.provider)
     {
         Ok(key) => {
             // Mask the key - show only last 4 characters
-            let masked = if key.len() > 4 {
-                format!("...{}", &key[key.len() - 4..])
-            } else {
-                "...".to_string()
-            };
-
             Ok(ApiKeyStatus {
                 has_key: true,
-                masked_key: Some(masked),
+                masked_key: Some(mask_api_key(&key)),
             })
         }
         Err(_) => Ok(ApiKeyStatus {
@@ -98,17 +202,135 @@ pub async fn get_api_key_status(
     }
 }
 
+/// Reports whether an API key is stored, missing, or unreadable
+///
+/// Unlike `get_api_key_status`, this tells a locked keychain (e.g. GNOME Keyring before
+/// login) apart from a key that was never saved, so the UI can ask the user to unlock it.
+#[tauri::command]
+pub async fn keychain_status(
+    state: tauri::State<'_, AppState>,
+    service_type: String,
+    provider: String,
+) -> CommandResult<KeyStatus> {
+    let status = state.keychain.key_status(&service_type, &provider);
+    if let KeyStatus::Error(message) = &status {
+        log::warn!(
+            "Keychain lookup failed for {}:{}: {}",
+            service_type,
+            provider,
+            message
+        );
+    }
+    Ok(status)
+}
+
+/// Make a cheap authenticated request to the provider with the given key
+async fn verify_api_key(
+    state: &AppState,
+    service_type: &str,
+    provider: &str,
+    api_key: String,
+) -> CommandResult<()> {
+    match (service_type, provider) {
+        ("asr", "deepgram") => DeepgramService::new(api_key).verify_api_key().await?,
+        ("asr", "assemblyai") => AssemblyAIService::new(api_key).verify_api_key().await?,
+        ("llm", "openai") => {
+            OpenAIService::new(api_key).fetch_available_models().await?;
+        }
+        ("llm", "anthropic") => {
+            AnthropicService::new(api_key)
+                .fetch_available_models()
+                .await?;
+        }
+        ("llm", "google") => {
+            GoogleService::new(api_key).fetch_available_models().await?;
+        }
+        ("llm", "groq") => {
+            GroqService::new(api_key).fetch_available_models().await?;
+        }
+        ("llm", "mistral") => {
+            MistralService::new(api_key)
+                .fetch_available_models()
+                .await?;
+        }
+        ("llm", AZURE_OPENAI_PROVIDER) => {
+            azure_openai_service(state, api_key)
+                .await?
+                .verify_api_key()
+                .await?
+        }
+        _ => {
+            return Err(CommandError::invalid_input(format!(
+                "Unknown provider: {}:{}",
+                service_type, provider
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+/// Tests the stored API key with a live request to the provider
+///
+/// Lists models for LLM providers, projects for Deepgram and recent transcripts for
+/// AssemblyAI. A rejected key or unreachable provider is reported as `ok: false` with
+/// the provider's message rather than as a command error.
+#[tauri::command]
+pub async fn test_api_key(
+    state: tauri::State<'_, AppState>,
+    service_type: String,
+    provider: String,
+) -> CommandResult<ApiKeyTestResult> {
+    let api_key = state.keychain.get_api_key(&service_type, &provider)?;
+
+    log::info!("Testing API key for {}:{}", service_type, provider);
+
+    let result = tokio::time::timeout(
+        API_KEY_TEST_TIMEOUT,
+        verify_api_key(&state, &service_type, &provider, api_key),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(())) => Ok(ApiKeyTestResult {
+            ok: true,
+            message: format!("API key for {} is valid", provider),
+        }),
+        Ok(Err(e)) if matches!(e.code.as_str(), "invalid_input" | "config") => Err(e),
+        Ok(Err(e)) => {
+            log::warn!(
+                "API key test failed for {}:{}: {}",
+                service_type,
+                provider,
+                e
+            );
+            Ok(ApiKeyTestResult {
+                ok: false,
+                message: e.message,
+            })
+        }
+        Err(_) => Ok(ApiKeyTestResult {
+            ok: false,
+            message: format!(
+                "{} did not respond within {} seconds",
+                provider,
+                API_KEY_TEST_TIMEOUT.as_secs()
+            ),
+        }),
+    }
+}
+
 /// Deletes an API key from the OS keychain
 #[tauri::command]
 pub async fn delete_api_key(
     state: tauri::State<'_, AppState>,
     service_type: String,
     provider: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     state
         .keychain
         .delete_api_key(&service_type, &provider)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Saves service configuration to the database
@@ -119,16 +341,16 @@ pub async fn delete_api_key(
 pub async fn save_service_config(
     state: tauri::State<'_, AppState>,
     request: SaveServiceConfigRequest,
-) -> Result<i64, String> {
+) -> CommandResult<i64> {
     // Parse service type
     let service_type = match request.service_type.as_str() {
         "asr" => ServiceType::Asr,
         "llm" => ServiceType::Llm,
         _ => {
-            return Err(format!(
+            return Err(CommandError::invalid_input(format!(
                 "Invalid service type: {}. Must be 'asr' or 'llm'",
                 request.service_type
-            ))
+            )))
         }
     };
 
@@ -142,7 +364,7 @@ pub async fn save_service_config(
         .storage
         .save_service_config(&config)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Gets a specific service configuration
@@ -151,29 +373,14 @@ pub async fn get_service_config(
     state: tauri::State<'_, AppState>,
     service_type: String,
     provider: String,
-) -> Result<Option<ServiceConfigResponse>, String> {
+) -> CommandResult<Option<ServiceConfigResponse>> {
     let config = state
         .storage
         .get_service_config(&service_type, &provider)
         .await
-        .map_err(|e| e.to_string())?;
-
-    match config {
-        Some(cfg) => {
-            // Check if API key exists
-            let has_api_key = state.keychain.has_api_key(&service_type, &provider);
-
-            Ok(Some(ServiceConfigResponse {
-                id: cfg.id,
-                service_type: format!("{:?}", cfg.service_type).to_lowercase(),
-                provider: cfg.provider,
-                is_active: cfg.is_active,
-                settings: cfg.settings,
-                has_api_key,
-            }))
-        }
-        None => Ok(None),
-    }
+        .map_err(CommandError::from)?;
+
+    Ok(config.map(|cfg| ServiceConfigResponse::new(cfg, state.keychain.as_ref())))
 }
 
 /// Gets the currently active service configuration for a service type
@@ -181,57 +388,48 @@ pub async fn get_service_config(
 pub async fn get_active_service_config(
     state: tauri::State<'_, AppState>,
     service_type: String,
-) -> Result<Option<ServiceConfigResponse>, String> {
+) -> CommandResult<Option<ServiceConfigResponse>> {
     let config = state
         .storage
         .get_active_service_config(&service_type)
         .await
-        .map_err(|e| e.to_string())?;
-
-    match config {
-        Some(cfg) => {
-            let has_api_key = state.keychain.has_api_key(&service_type, &cfg.provider);
-
-            Ok(Some(ServiceConfigResponse {
-                id: cfg.id,
-                service_type: format!("{:?}", cfg.service_type).to_lowercase(),
-                provider: cfg.provider,
-                is_active: cfg.is_active,
-                settings: cfg.settings,
-                has_api_key,
-            }))
-        }
-        None => Ok(None),
-    }
+        .map_err(CommandError::from)?;
+
+    Ok(config.map(|cfg| ServiceConfigResponse::new(cfg, state.keychain.as_ref())))
 }
 
 /// Lists all service configurations
 #[tauri::command]
 pub async fn list_service_configs(
     state: tauri::State<'_, AppState>,
-) -> Result<Vec<ServiceConfigResponse>, String> {
-    let configs = state
-        .storage
+) -> CommandResult<Vec<ServiceConfigResponse>> {
+    service_configs_with_keys(state.storage.as_ref(), state.keychain.as_ref()).await
+}
+
+/// Lists all service configurations, each with whether its provider has an API key
+///
+/// Lets the settings screen load every provider's state in one call instead of asking
+/// for each provider's key status separately.
+#[tauri::command]
+pub async fn list_service_configs_with_keys(
+    state: tauri::State<'_, AppState>,
+) -> CommandResult<Vec<ServiceConfigResponse>> {
+    service_configs_with_keys(state.storage.as_ref(), state.keychain.as_ref()).await
+}
+
+async fn service_configs_with_keys(
+    storage: &dyn StoragePort,
+    keychain: &dyn KeychainPort,
+) -> CommandResult<Vec<ServiceConfigResponse>> {
+    let configs = storage
         .list_service_configs()
         .await
-        .map_err(|e| e.to_string())?;
-
-    let mut responses = Vec::new();
-    for cfg in configs {
-        let service_type_str = format!("{:?}", cfg.service_type).to_lowercase();
-        let has_api_key = state.keychain.has_api_key(&service_type_str, &cfg.provider);
-
-        responses.push(ServiceConfigResponse {
-            id: cfg.id,
-            service_type: service_type_str,
-            provider: cfg.provider,
-            is_active: cfg.is_active,
-            settings: cfg.settings,
-            has_api_key,
-        });
-    }
+        .map_err(|e| CommandError::from(e).context("Failed to list service configs"))?;
 
-    Ok(responses)
+    Ok(configs
+        .into_iter()
+        .map(|cfg| ServiceConfigResponse::new(cfg, keychain))
+        .collect())
 }
 
 /// Activates a specific service configuration (deactivates others of same type)
@@ -240,73 +438,340 @@ pub async fn activate_service(
     state: tauri::State<'_, AppState>,
     service_type: String,
     provider: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     // Check if API key exists first
     if !state.keychain.has_api_key(&service_type, &provider) {
-        return Err(format!(
+        return Err(CommandError::config(format!(
             "Cannot activate service without API key. Please add an API key for {}:{}",
             service_type, provider
-        ));
+        )));
     }
 
-    // Get or create the configuration
-    let config = state
-        .storage
-        .get_service_config(&service_type, &provider)
+    activate_service_config(state.storage.as_ref(), &service_type, &provider).await
+}
+
+/// Make a provider the active one for its service type, creating its config if needed
+///
+/// Saving the active config deactivates the type's other providers in the same
+/// transaction, so exactly one stays active.
+async fn activate_service_config(
+    storage: &dyn StoragePort,
+    service_type: &str,
+    provider: &str,
+) -> CommandResult<()> {
+    let config = storage
+        .get_service_config(service_type, provider)
+        .await
+        .map_err(CommandError::from)?;
+
+    let config = match config {
+        Some(config) => config,
+        None => {
+            log::info!(
+                "Creating default configuration for {}:{}",
+                service_type,
+                provider
+            );
+
+            let service_type_enum = match service_type {
+                "asr" => ServiceType::Asr,
+                "llm" => ServiceType::Llm,
+                _ => {
+                    return Err(CommandError::invalid_input(format!(
+                        "Invalid service type: {}. Must be 'asr' or 'llm'",
+                        service_type
+                    )))
+                }
+            };
+            ServiceConfig::new(service_type_enum, provider.to_string())
+        }
+    };
+
+    storage
+        .save_service_config(&config.with_active(true))
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
-    // If config doesn't exist, create a default one
-    if config.is_none() {
-        log::info!(
-            "Creating default configuration for {}:{}",
-            service_type,
-            provider
+    Ok(())
+}
+
+/// Get the proxy used for provider requests
+///
+/// Empty when none is saved, in which case the proxy environment variables apply.
+#[tauri::command]
+pub async fn get_proxy_config() -> CommandResult<ProxyConfig> {
+    Ok(http::proxy_config())
+}
+
+/// Save the proxy used for provider requests and apply it from the next request on
+///
+/// Blank fields are cleared; saving an empty config falls back to the
+/// `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY` environment variables.
+#[tauri::command]
+pub async fn save_proxy_config(
+    state: tauri::State<'_, AppState>,
+    config: ProxyConfig,
+) -> CommandResult<ProxyConfig> {
+    let config = save_proxy_setting(state.storage.as_ref(), config).await?;
+    http::set_proxy_config(config.clone());
+    Ok(config)
+}
+
+/// Validate and persist a proxy config, returning it normalized
+async fn save_proxy_setting(
+    storage: &dyn StoragePort,
+    config: ProxyConfig,
+) -> CommandResult<ProxyConfig> {
+    let config = config.normalized()?;
+    let value = if config.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&config).map_err(crate::error::AppError::from)?)
+    };
+    storage
+        .set_app_setting(PROXY_SETTING_KEY, value.as_deref())
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to save proxy settings"))?;
+    Ok(config)
+}
+
+/// The proxy config saved in settings, empty if none was saved
+pub async fn saved_proxy_config(storage: &dyn StoragePort) -> crate::error::Result<ProxyConfig> {
+    match storage.get_app_setting(PROXY_SETTING_KEY).await? {
+        Some(saved) => Ok(serde_json::from_str(&saved)?),
+        None => Ok(ProxyConfig::default()),
+    }
+}
+
+/// Get the request limits applied to each provider
+///
+/// Providers without an entry allow 4 concurrent requests with no per-minute limit.
+#[tauri::command]
+pub async fn get_rate_limits() -> CommandResult<RateLimits> {
+    Ok(rate_limit::rate_limits())
+}
+
+/// Save the request limits for each provider, replacing any saved before
+///
+/// Applies to services created from now on, so from the next provider call.
+#[tauri::command]
+pub async fn save_rate_limits(
+    state: tauri::State<'_, AppState>,
+    limits: RateLimits,
+) -> CommandResult<RateLimits> {
+    save_rate_limits_setting(state.storage.as_ref(), &limits).await?;
+    rate_limit::set_rate_limits(limits.clone());
+    Ok(limits)
+}
+
+/// Validate and persist per-provider rate limits
+async fn save_rate_limits_setting(
+    storage: &dyn StoragePort,
+    limits: &RateLimits,
+) -> CommandResult<()> {
+    for (provider, config) in limits {
+        config.validate(provider)?;
+    }
+    let value = if limits.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(limits).map_err(crate::error::AppError::from)?)
+    };
+    storage
+        .set_app_setting(RATE_LIMITS_SETTING_KEY, value.as_deref())
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to save rate limits"))
+}
+
+/// The per-provider rate limits saved in settings, empty if none were saved
+pub async fn saved_rate_limits(storage: &dyn StoragePort) -> crate::error::Result<RateLimits> {
+    match storage.get_app_setting(RATE_LIMITS_SETTING_KEY).await? {
+        Some(saved) => Ok(serde_json::from_str(&saved)?),
+        None => Ok(RateLimits::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_api_key_trims_whitespace() {
+        assert_eq!(
+            normalize_api_key("  sk-test1234\n").unwrap(),
+            "sk-test1234".to_string()
         );
+    }
+
+    #[test]
+    fn test_normalize_api_key_rejects_empty() {
+        let error = normalize_api_key(" \t\n ").unwrap_err();
+        assert_eq!(error.code, "invalid_input");
+    }
 
-        let service_type_enum = match service_type.as_str() {
-            "asr" => ServiceType::Asr,
-            "llm" => ServiceType::Llm,
-            _ => {
-                return Err(format!(
-                    "Invalid service type: {}. Must be 'asr' or 'llm'",
-                    service_type
-                ))
-            }
-        };
-
-        let default_config =
-            ServiceConfig::new(service_type_enum, provider.clone()).with_active(false); // Will be activated below
-
-        state
-            .storage
-            .save_service_config(&default_config)
+    #[test]
+    fn test_normalize_api_key_rejects_masked_value() {
+        let masked = mask_api_key("sk-secret-abcd");
+        assert_eq!(masked, "...abcd");
+        assert!(normalize_api_key(&masked).is_err());
+        assert!(normalize_api_key("sk-...abcd").is_err());
+        assert!(normalize_api_key("sk-\u{2026}abcd").is_err());
+    }
+
+    #[test]
+    fn test_api_key_format_warning() {
+        assert!(api_key_format_warning("openai", "sk-proj-abc").is_none());
+        assert!(api_key_format_warning("openai", "sk-ant-abc").is_some());
+        assert!(api_key_format_warning("anthropic", "sk-ant-abc").is_none());
+        assert!(api_key_format_warning("anthropic", "sk-abc").is_some());
+        // Providers without a known prefix are never flagged
+        assert!(api_key_format_warning("deepgram", "0123456789abcdef").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_service_configs_with_keys() {
+        use crate::ports::mocks::MockStorage;
+        use crate::utils::keychain::MockKeychain;
+
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        keychain.save_api_key("asr", "deepgram", "dg-key").unwrap();
+        keychain.save_api_key("llm", "openai", "sk-key").unwrap();
+        for (service_type, provider) in [
+            (ServiceType::Asr, "deepgram"),
+            (ServiceType::Asr, "assemblyai"),
+            (ServiceType::Llm, "openai"),
+        ] {
+            storage
+                .save_service_config(&ServiceConfig::new(service_type, provider.to_string()))
+                .await
+                .unwrap();
+        }
+
+        let mut flags: Vec<_> = service_configs_with_keys(&storage, &keychain)
             .await
-            .map_err(|e| e.to_string())?;
+            .unwrap()
+            .into_iter()
+            .map(|cfg| (cfg.service_type, cfg.provider, cfg.has_api_key))
+            .collect();
+        flags.sort();
+        assert_eq!(
+            flags,
+            vec![
+                ("asr".to_string(), "assemblyai".to_string(), false),
+                ("asr".to_string(), "deepgram".to_string(), true),
+                ("llm".to_string(), "openai".to_string(), true),
+            ]
+        );
     }
 
-    // Deactivate all services of this type
-    let all_configs = state
-        .storage
-        .list_service_configs()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    for mut cfg in all_configs {
-        let cfg_type_str = format!("{:?}", cfg.service_type).to_lowercase();
-        if cfg_type_str == service_type {
-            if cfg.provider == provider {
-                cfg.is_active = true;
-            } else {
-                cfg.is_active = false;
-            }
-            state
-                .storage
-                .save_service_config(&cfg)
+    #[tokio::test]
+    async fn test_activate_service_config_switches_provider() {
+        use crate::ports::mocks::MockStorage;
+
+        let storage = MockStorage::new();
+        for provider in ["deepgram", "assemblyai"] {
+            storage
+                .save_service_config(&ServiceConfig::new(ServiceType::Asr, provider.to_string()))
+                .await
+                .unwrap();
+        }
+
+        for provider in ["deepgram", "assemblyai"] {
+            activate_service_config(&storage, "asr", provider)
+                .await
+                .unwrap();
+            let active: Vec<_> = storage
+                .list_service_configs()
                 .await
-                .map_err(|e| e.to_string())?;
+                .unwrap()
+                .into_iter()
+                .filter(|c| c.is_active)
+                .map(|c| c.provider)
+                .collect();
+            assert_eq!(active, [provider]);
         }
+
+        let err = activate_service_config(&storage, "tts", "deepgram")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "invalid_input");
     }
 
-    Ok(())
+    #[tokio::test]
+    async fn test_proxy_setting_round_trip() {
+        use crate::ports::mocks::MockStorage;
+
+        let storage = MockStorage::new();
+        assert!(saved_proxy_config(&storage).await.unwrap().is_empty());
+
+        let saved = save_proxy_setting(
+            &storage,
+            ProxyConfig {
+                url: Some(" http://proxy.corp.example:8080 ".to_string()),
+                no_proxy: Some("localhost,.corp.example".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.url.as_deref(), Some("http://proxy.corp.example:8080"));
+        assert_eq!(saved_proxy_config(&storage).await.unwrap(), saved);
+
+        // Clearing the proxy removes the setting
+        save_proxy_setting(&storage, ProxyConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get_app_setting(PROXY_SETTING_KEY).await.unwrap(),
+            None
+        );
+
+        let err = save_proxy_setting(
+            &storage,
+            ProxyConfig {
+                url: Some("http://proxy:not-a-port".to_string()),
+                no_proxy: None,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, "invalid_input");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits_setting_round_trip() {
+        use crate::ports::mocks::MockStorage;
+        use crate::utils::rate_limit::RateLimitConfig;
+
+        let storage = MockStorage::new();
+        assert!(saved_rate_limits(&storage).await.unwrap().is_empty());
+
+        let limits = RateLimits::from([(
+            "deepgram".to_string(),
+            RateLimitConfig {
+                max_concurrent: 2,
+                requests_per_minute: Some(30),
+            },
+        )]);
+        save_rate_limits_setting(&storage, &limits).await.unwrap();
+        assert_eq!(saved_rate_limits(&storage).await.unwrap(), limits);
+
+        let invalid = RateLimits::from([(
+            "openai".to_string(),
+            RateLimitConfig {
+                max_concurrent: 0,
+                requests_per_minute: None,
+            },
+        )]);
+        let err = save_rate_limits_setting(&storage, &invalid)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "invalid_input");
+        assert_eq!(saved_rate_limits(&storage).await.unwrap(), limits);
+
+        save_rate_limits_setting(&storage, &RateLimits::new())
+            .await
+            .unwrap();
+        assert!(saved_rate_limits(&storage).await.unwrap().is_empty());
+    }
 }
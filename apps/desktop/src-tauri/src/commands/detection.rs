@@ -4,6 +4,7 @@
 
 use crate::adapters::detection::create_detector;
 use crate::domain::models::{Participant, Platform};
+use crate::error::{CommandError, CommandResult};
 use crate::ports::detection::{
     DetectedMeeting, DetectedParticipant, DetectionConfig, DetectionResult, ParticipantDetectorPort,
 };
@@ -58,13 +59,13 @@ pub struct ImportParticipantsRequest {
 ///
 /// Scans for running instances of Teams, Zoom, and Google Meet.
 #[tauri::command]
-pub async fn list_active_meetings() -> Result<ActiveMeetingsResponse, String> {
+pub async fn list_active_meetings() -> CommandResult<ActiveMeetingsResponse> {
     let detector = create_detector();
 
     let meetings = detector
         .list_active_meetings()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     Ok(ActiveMeetingsResponse {
         meetings,
@@ -79,14 +80,14 @@ pub async fn list_active_meetings() -> Result<ActiveMeetingsResponse, String> {
 #[tauri::command]
 pub async fn detect_participants(
     request: DetectParticipantsRequest,
-) -> Result<DetectionResult, String> {
+) -> CommandResult<DetectionResult> {
     let detector = create_detector();
     let config = request.config.unwrap_or_default();
 
     detector
         .detect_participants(&request.meeting, &config)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Auto-detects participants from any running meeting
@@ -96,7 +97,7 @@ pub async fn detect_participants(
 #[tauri::command]
 pub async fn auto_detect_participants(
     request: AutoDetectRequest,
-) -> Result<AutoDetectResponse, String> {
+) -> CommandResult<AutoDetectResponse> {
     let detector = create_detector();
 
     let target_platform = request.target_platform.and_then(|p| match p.as_str() {
@@ -115,7 +116,7 @@ pub async fn auto_detect_participants(
     let result = detector
         .auto_detect(&config)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     Ok(AutoDetectResponse {
         result,
@@ -125,14 +126,14 @@ pub async fn auto_detect_participants(
 
 /// Checks if participant detection is available on this platform
 #[tauri::command]
-pub async fn is_detection_available() -> Result<bool, String> {
+pub async fn is_detection_available() -> CommandResult<bool> {
     let detector = create_detector();
     Ok(detector.is_available())
 }
 
 /// Gets information about the detection method being used
 #[tauri::command]
-pub async fn get_detection_info() -> Result<serde_json::Value, String> {
+pub async fn get_detection_info() -> CommandResult<serde_json::Value> {
     let detector = create_detector();
 
     Ok(serde_json::json!({
@@ -150,28 +151,24 @@ pub async fn get_detection_info() -> Result<serde_json::Value, String> {
 pub async fn import_detected_participants(
     state: tauri::State<'_, AppState>,
     request: ImportParticipantsRequest,
-) -> Result<Vec<i64>, String> {
-    let storage = &state.storage;
-    let mut created_ids = Vec::new();
-
-    for detected in request.participants {
-        let participant = Participant {
+) -> CommandResult<Vec<i64>> {
+    let participants: Vec<Participant> = request
+        .participants
+        .into_iter()
+        .map(|detected| Participant {
             id: None,
             meeting_id: request.meeting_id,
             name: detected.name,
             email: None,
             speaker_label: None, // Will be linked later during transcription
-        };
-
-        let id = storage
-            .create_participant(&participant)
-            .await
-            .map_err(|e| e.to_string())?;
-
-        created_ids.push(id);
-    }
+        })
+        .collect();
 
-    Ok(created_ids)
+    state
+        .storage
+        .create_participants_batch(&participants)
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Detects and imports participants in one step
@@ -183,7 +180,7 @@ pub async fn detect_and_import_participants(
     state: tauri::State<'_, AppState>,
     meeting_id: i64,
     target_platform: Option<String>,
-) -> Result<DetectionResult, String> {
+) -> CommandResult<DetectionResult> {
     // First, auto-detect participants
     let detector = create_detector();
 
@@ -203,24 +200,31 @@ pub async fn detect_and_import_participants(
     let result = detector
         .auto_detect(&config)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     let Some(detection_result) = result else {
-        return Err("No active meeting found".to_string());
+        return Err(CommandError::not_found("No active meeting found"));
     };
 
     // Import detected participants
-    let storage = &state.storage;
-    for detected in &detection_result.participants {
-        let participant = Participant {
+    let participants: Vec<Participant> = detection_result
+        .participants
+        .iter()
+        .map(|detected| Participant {
             id: None,
             meeting_id,
             name: detected.name.clone(),
             email: None,
             speaker_label: None,
-        };
+        })
+        .collect();
 
-        let _ = storage.create_participant(&participant).await;
+    if let Err(e) = state.storage.create_participants_batch(&participants).await {
+        log::warn!(
+            "Failed to import detected participants for meeting {}: {}",
+            meeting_id,
+            e
+        );
     }
 
     Ok(detection_result)
@@ -0,0 +1,415 @@
+//! Self-diagnostics command
+//!
+//! Runs a set of independent checks over the database, recordings directory, API keys,
+//! audio devices and provider connectivity so users can see why recording or
+//! transcription isn't working.
+
+use crate::domain::models::ServiceType;
+use crate::error::CommandResult;
+use crate::ports::audio::AudioCapturePort;
+use crate::ports::storage::StoragePort;
+use crate::utils::keychain::KeychainPort;
+use crate::AppState;
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+use tauri::Manager;
+
+/// How long a provider may take to answer the reachability probe
+const NETWORK_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Not broken, but likely to get in the way (e.g. nothing configured yet)
+    Warning,
+    Failed,
+}
+
+/// Result of one diagnostic check
+#[derive(Debug, Serialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl DiagnosticCheck {
+    fn new(name: &str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Report returned by `run_diagnostics`
+#[derive(Debug, Serialize)]
+pub struct DiagnosticsReport {
+    /// False if any check failed; warnings don't count
+    pub healthy: bool,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+/// Get the path of the application log file, for attaching to bug reports
+///
+/// Returns None if the log file couldn't be opened and logs only go to stderr.
+#[tauri::command]
+pub async fn get_log_path() -> CommandResult<Option<String>> {
+    Ok(crate::utils::logging::log_path().map(|path| path.to_string_lossy().into_owned()))
+}
+
+/// Run every diagnostic check and report the results
+///
+/// Each check is independent, so one failure doesn't hide the others.
+#[tauri::command]
+pub async fn run_diagnostics(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> CommandResult<DiagnosticsReport> {
+    log::info!("Running diagnostics");
+
+    let mut checks = vec![check_database(state.storage.as_ref()).await];
+
+    checks.push(match app.path().app_data_dir() {
+        Ok(app_data_dir) => check_recordings_dir(&app_data_dir.join("recordings")),
+        Err(e) => DiagnosticCheck::new(
+            "recordings_dir",
+            CheckStatus::Failed,
+            format!("Could not locate the app data directory: {}", e),
+        ),
+    });
+
+    {
+        let audio_capture = state.audio_capture.lock().await;
+        checks.push(check_audio_devices(&*audio_capture).await);
+    }
+
+    for service_type in [ServiceType::Asr, ServiceType::Llm] {
+        let provider = match active_provider(state.storage.as_ref(), &service_type).await {
+            Ok(provider) => provider,
+            Err(check) => {
+                checks.push(check);
+                continue;
+            }
+        };
+        checks.push(check_api_key(
+            state.keychain.as_ref(),
+            &service_type,
+            provider.as_deref(),
+        ));
+        if let Some(provider) = provider {
+            checks
+                .push(check_network(&service_type, &provider, provider_base_url(&provider)).await);
+        }
+    }
+
+    let healthy = checks.iter().all(|c| c.status != CheckStatus::Failed);
+    for check in checks.iter().filter(|c| c.status != CheckStatus::Ok) {
+        log::warn!(
+            "Diagnostic {} {:?}: {}",
+            check.name,
+            check.status,
+            check.message
+        );
+    }
+
+    Ok(DiagnosticsReport { healthy, checks })
+}
+
+/// Check that the database answers a simple query
+async fn check_database(storage: &dyn StoragePort) -> DiagnosticCheck {
+    match storage.list_meetings(Some(1), Some(0)).await {
+        Ok(_) => DiagnosticCheck::new("database", CheckStatus::Ok, "Database is reachable"),
+        Err(e) => DiagnosticCheck::new(
+            "database",
+            CheckStatus::Failed,
+            format!("Database query failed: {}", e),
+        ),
+    }
+}
+
+/// Check that recordings can be written by creating and removing a probe file
+fn check_recordings_dir(dir: &Path) -> DiagnosticCheck {
+    const NAME: &str = "recordings_dir";
+
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        return DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            format!("Cannot create {}: {}", dir.display(), e),
+        );
+    }
+
+    let probe = dir.join(".diagnostics_probe");
+    match std::fs::write(&probe, b"ok") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            DiagnosticCheck::new(
+                NAME,
+                CheckStatus::Ok,
+                format!("{} is writable", dir.display()),
+            )
+        }
+        Err(e) => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            format!("Cannot write to {}: {}", dir.display(), e),
+        ),
+    }
+}
+
+/// Check that speaker and microphone devices can be enumerated
+async fn check_audio_devices(audio_capture: &dyn AudioCapturePort) -> DiagnosticCheck {
+    const NAME: &str = "audio_devices";
+
+    let speakers = audio_capture.list_speaker_devices().await;
+    let microphones = audio_capture.list_microphone_devices().await;
+
+    match (speakers, microphones) {
+        (Err(e), _) => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            format!("Failed to list speaker devices: {}", e),
+        ),
+        (_, Err(e)) => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            format!("Failed to list microphone devices: {}", e),
+        ),
+        (Ok(speakers), Ok(_)) if speakers.is_empty() => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            "No speaker devices found; meeting audio cannot be captured",
+        ),
+        (Ok(speakers), Ok(microphones)) if microphones.is_empty() => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Warning,
+            format!(
+                "Found {} speaker device(s) but no microphone; only other participants will be recorded",
+                speakers.len()
+            ),
+        ),
+        (Ok(speakers), Ok(microphones)) => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            format!(
+                "Found {} speaker and {} microphone device(s)",
+                speakers.len(),
+                microphones.len()
+            ),
+        ),
+    }
+}
+
+/// Look up the active provider for a service type
+///
+/// A storage error is returned as a failed check for that service's API key.
+async fn active_provider(
+    storage: &dyn StoragePort,
+    service_type: &ServiceType,
+) -> std::result::Result<Option<String>, DiagnosticCheck> {
+    storage
+        .get_active_service_config(&service_type.to_string())
+        .await
+        .map(|config| config.map(|c| c.provider))
+        .map_err(|e| {
+            DiagnosticCheck::new(
+                &format!("{}_api_key", service_type),
+                CheckStatus::Failed,
+                format!(
+                    "Could not read the {} configuration: {}",
+                    label(service_type),
+                    e
+                ),
+            )
+        })
+}
+
+/// Check that the active provider for a service type has an API key in the keychain
+fn check_api_key(
+    keychain: &dyn KeychainPort,
+    service_type: &ServiceType,
+    provider: Option<&str>,
+) -> DiagnosticCheck {
+    let name = format!("{}_api_key", service_type);
+    let label = label(service_type);
+
+    let Some(provider) = provider else {
+        return DiagnosticCheck::new(
+            &name,
+            CheckStatus::Warning,
+            format!("No {} provider is active", label),
+        );
+    };
+
+    if keychain.has_api_key(&service_type.to_string(), provider) {
+        DiagnosticCheck::new(
+            &name,
+            CheckStatus::Ok,
+            format!("{} API key for {} is stored", label, provider),
+        )
+    } else {
+        DiagnosticCheck::new(
+            &name,
+            CheckStatus::Failed,
+            format!("No {} API key stored for {}", label, provider),
+        )
+    }
+}
+
+/// API host probed for a provider's reachability
+fn provider_base_url(provider: &str) -> Option<&'static str> {
+    match provider {
+        "deepgram" => Some("https://api.deepgram.com"),
+        "assemblyai" => Some("https://api.assemblyai.com"),
+        "openai" => Some("https://api.openai.com"),
+        "anthropic" => Some("https://api.anthropic.com"),
+        "google" => Some("https://generativelanguage.googleapis.com"),
+        "groq" => Some("https://api.groq.com"),
+        "mistral" => Some("https://api.mistral.ai"),
+        _ => None,
+    }
+}
+
+/// Check that a provider's API host answers at all
+///
+/// Any HTTP response counts as reachable; only connection failures and timeouts fail.
+/// Providers with per-user endpoints (e.g. Azure OpenAI) are not probed.
+async fn check_network(
+    service_type: &ServiceType,
+    provider: &str,
+    base_url: Option<&str>,
+) -> DiagnosticCheck {
+    let name = format!("{}_network", service_type);
+
+    let Some(base_url) = base_url else {
+        return DiagnosticCheck::new(
+            &name,
+            CheckStatus::Warning,
+            format!("Reachability of {} is not checked", provider),
+        );
+    };
+
+    let client = match crate::utils::http::client_builder(NETWORK_CHECK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            return DiagnosticCheck::new(
+                &name,
+                CheckStatus::Failed,
+                format!("Failed to create HTTP client: {}", e),
+            )
+        }
+    };
+
+    match client.head(base_url).send().await {
+        Ok(_) => DiagnosticCheck::new(&name, CheckStatus::Ok, format!("{} is reachable", provider)),
+        Err(e) if e.is_timeout() => DiagnosticCheck::new(
+            &name,
+            CheckStatus::Failed,
+            format!(
+                "{} did not respond within {} seconds",
+                provider,
+                NETWORK_CHECK_TIMEOUT.as_secs()
+            ),
+        ),
+        Err(e) => DiagnosticCheck::new(
+            &name,
+            CheckStatus::Failed,
+            format!("Cannot reach {}: {}", provider, e),
+        ),
+    }
+}
+
+/// Human-readable name of a service type
+fn label(service_type: &ServiceType) -> &'static str {
+    match service_type {
+        ServiceType::Asr => "transcription",
+        ServiceType::Llm => "LLM",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::ServiceConfig;
+    use crate::ports::mocks::MockStorage;
+    use crate::utils::keychain::MockKeychain;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_check_database() {
+        let storage = MockStorage::new();
+        assert_eq!(check_database(&storage).await.status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_check_recordings_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let recordings = dir.path().join("recordings");
+
+        let check = check_recordings_dir(&recordings);
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert!(recordings.is_dir());
+        assert_eq!(std::fs::read_dir(&recordings).unwrap().count(), 0);
+
+        // A file where the directory should be
+        let blocked = dir.path().join("blocked");
+        std::fs::write(&blocked, b"").unwrap();
+        assert_eq!(check_recordings_dir(&blocked).status, CheckStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_check_api_key() {
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+
+        let provider = active_provider(&storage, &ServiceType::Asr).await.unwrap();
+        let check = check_api_key(&keychain, &ServiceType::Asr, provider.as_deref());
+        assert_eq!(check.status, CheckStatus::Warning);
+
+        let config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string()).with_active(true);
+        storage.save_service_config(&config).await.unwrap();
+        let provider = active_provider(&storage, &ServiceType::Asr).await.unwrap();
+        let check = check_api_key(&keychain, &ServiceType::Asr, provider.as_deref());
+        assert_eq!(check.name, "asr_api_key");
+        assert_eq!(check.status, CheckStatus::Failed);
+
+        keychain.save_api_key("asr", "deepgram", "key").unwrap();
+        let check = check_api_key(&keychain, &ServiceType::Asr, provider.as_deref());
+        assert_eq!(check.status, CheckStatus::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_check_network() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+        });
+
+        // Any HTTP response means the host is reachable
+        let check = check_network(&ServiceType::Llm, "openai", Some(&base_url)).await;
+        assert_eq!(check.status, CheckStatus::Ok, "{}", check.message);
+
+        // Nothing listens on the port once the listener is gone
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let check = check_network(&ServiceType::Llm, "openai", Some(&closed_url)).await;
+        assert_eq!(check.status, CheckStatus::Failed);
+
+        let check = check_network(&ServiceType::Llm, "azure_openai", None).await;
+        assert_eq!(check.status, CheckStatus::Warning);
+    }
+}
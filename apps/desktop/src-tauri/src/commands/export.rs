@@ -1,10 +1,13 @@
+use crate::domain::models::{
+    Insight, InsightType, Meeting, Participant, RecordingStatus, Transcript,
+};
 /// Export commands for exporting meeting data to various formats
-use crate::domain::models::{Insight, InsightType, Meeting, Participant, Transcript};
+use crate::error::{AppError, CommandError, CommandResult};
 use crate::ports::storage::StoragePort;
 use crate::AppState;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
 use tauri::Manager;
 
 /// Export format
@@ -38,13 +41,33 @@ pub struct ExportResponse {
     pub size_bytes: u64,
 }
 
+/// Version of the [`MeetingExport`] document layout
+///
+/// Bump when a change would make older documents import incorrectly. Documents from a
+/// newer version are rejected rather than imported partially.
+pub const MEETING_EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// A complete meeting as a single JSON document, for backup and interop
+///
+/// Rows keep the IDs they had when exported; importing assigns new ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingExport {
+    pub schema_version: u32,
+    pub exported_at: i64,
+    pub meeting: Meeting,
+    pub participants: Vec<Participant>,
+    /// Ordered by `timestamp_ms`
+    pub transcripts: Vec<Transcript>,
+    pub insights: Vec<Insight>,
+}
+
 /// Export a meeting to Markdown or JSON format
 #[tauri::command]
 pub async fn export_meeting(
     app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     request: ExportRequest,
-) -> Result<ExportResponse, String> {
+) -> CommandResult<ExportResponse> {
     log::info!(
         "Exporting meeting {} to {:?} format",
         request.meeting_id,
@@ -56,15 +79,17 @@ pub async fn export_meeting(
         .storage
         .get_meeting(request.meeting_id)
         .await
-        .map_err(|e| format!("Failed to fetch meeting: {}", e))?
-        .ok_or_else(|| format!("Meeting with id {} not found", request.meeting_id))?;
+        .map_err(|e| CommandError::from(e).context("Failed to fetch meeting"))?
+        .ok_or_else(|| {
+            CommandError::not_found(format!("Meeting with id {} not found", request.meeting_id))
+        })?;
 
     // Fetch transcripts
     let transcripts = state
         .storage
         .get_transcripts(request.meeting_id)
         .await
-        .map_err(|e| format!("Failed to fetch transcripts: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to fetch transcripts"))?;
 
     // Fetch participants if requested
     let participants = if request.include_participants {
@@ -72,7 +97,7 @@ pub async fn export_meeting(
             .storage
             .get_participants(request.meeting_id)
             .await
-            .map_err(|e| format!("Failed to fetch participants: {}", e))?
+            .map_err(|e| CommandError::from(e).context("Failed to fetch participants"))?
     } else {
         Vec::new()
     };
@@ -83,7 +108,7 @@ pub async fn export_meeting(
             .storage
             .get_insights(request.meeting_id)
             .await
-            .map_err(|e| format!("Failed to fetch insights: {}", e))?
+            .map_err(|e| CommandError::from(e).context("Failed to fetch insights"))?
     } else {
         Vec::new()
     };
@@ -94,20 +119,22 @@ pub async fn export_meeting(
             format_meeting_as_markdown(&meeting, &transcripts, &participants, &insights)
         }
         ExportFormat::Json => {
-            format_meeting_as_json(&meeting, &transcripts, &participants, &insights)
-                .map_err(|e| format!("Failed to serialize JSON: {}", e))?
+            format_meeting_as_json(&meeting, &transcripts, &participants, &insights).map_err(
+                |e| CommandError::from(AppError::from(e)).context("Failed to serialize JSON"),
+            )?
         }
     };
 
     // Determine output path
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let app_data_dir = app.path().app_data_dir().map_err(|e| {
+        CommandError::from(AppError::Other(e.to_string()))
+            .context("Failed to get app data directory")
+    })?;
 
     let export_dir = app_data_dir.join("exports");
-    std::fs::create_dir_all(&export_dir)
-        .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+    std::fs::create_dir_all(&export_dir).map_err(|e| {
+        CommandError::from(AppError::from(e)).context("Failed to create exports directory")
+    })?;
 
     // Create file name
     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
@@ -115,16 +142,17 @@ pub async fn export_meeting(
         ExportFormat::Markdown => "md",
         ExportFormat::Json => "json",
     };
-    let file_name = format!("meeting_{}_{}.{}", request.meeting_id, timestamp, extension);
+    let file_name = export_file_name(&meeting, &timestamp.to_string(), extension);
     let file_path = export_dir.join(&file_name);
 
     // Write file
-    std::fs::write(&file_path, content)
-        .map_err(|e| format!("Failed to write export file: {}", e))?;
+    std::fs::write(&file_path, content).map_err(|e| {
+        CommandError::from(AppError::from(e)).context("Failed to write export file")
+    })?;
 
     // Get file size
     let size = std::fs::metadata(&file_path)
-        .map_err(|e| format!("Failed to get file metadata: {}", e))?
+        .map_err(|e| CommandError::from(AppError::from(e)).context("Failed to get file metadata"))?
         .len();
 
     log::info!("Successfully exported meeting to: {}", file_path.display());
@@ -136,6 +164,167 @@ pub async fn export_meeting(
     })
 }
 
+/// Export a meeting with its participants, transcripts and insights as one JSON document
+///
+/// The document can be re-imported with `import_meeting_json`, here or in another install.
+#[tauri::command]
+pub async fn export_meeting_json(
+    state: tauri::State<'_, AppState>,
+    meeting_id: i64,
+) -> CommandResult<MeetingExport> {
+    build_meeting_export(state.storage.as_ref(), meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to export meeting"))
+}
+
+/// Recreate a meeting from a document made by `export_meeting_json`
+///
+/// The meeting is added as a new meeting, without a recording; existing meetings are
+/// never overwritten. Returns the imported meeting.
+#[tauri::command]
+pub async fn import_meeting_json(
+    state: tauri::State<'_, AppState>,
+    document: serde_json::Value,
+) -> CommandResult<Meeting> {
+    let export = parse_meeting_export(document)?;
+    let meeting = import_meeting_export(state.storage.as_ref(), export)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to import meeting"))?;
+
+    log::info!("Imported meeting {:?} from JSON", meeting.id);
+    Ok(meeting)
+}
+
+/// Collect everything stored for a meeting into a [`MeetingExport`]
+async fn build_meeting_export(
+    storage: &dyn StoragePort,
+    meeting_id: i64,
+) -> crate::error::Result<MeetingExport> {
+    let meeting = storage
+        .get_meeting(meeting_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Meeting with id {} not found", meeting_id)))?;
+
+    Ok(MeetingExport {
+        schema_version: MEETING_EXPORT_SCHEMA_VERSION,
+        exported_at: Utc::now().timestamp(),
+        meeting,
+        participants: storage.get_participants(meeting_id).await?,
+        transcripts: storage.get_transcripts(meeting_id).await?,
+        insights: storage.get_insights(meeting_id).await?,
+    })
+}
+
+/// Check a document's schema version before deserializing the rest of it
+fn parse_meeting_export(document: serde_json::Value) -> CommandResult<MeetingExport> {
+    let version = document
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| {
+            CommandError::invalid_input("Not a meeting export: missing schema_version")
+        })?;
+    if version == 0 || version > u64::from(MEETING_EXPORT_SCHEMA_VERSION) {
+        return Err(CommandError::invalid_input(format!(
+            "Unsupported meeting export schema version {} (supported: up to {})",
+            version, MEETING_EXPORT_SCHEMA_VERSION
+        )));
+    }
+
+    serde_json::from_value(document)
+        .map_err(|e| CommandError::invalid_input(format!("Invalid meeting export: {}", e)))
+}
+
+/// Insert the rows of a [`MeetingExport`] as a new meeting
+///
+/// Transcript links to participants are remapped to the new participant IDs. The
+/// recording isn't part of an export, so the imported meeting has none. If any insert
+/// fails, the partly imported meeting is deleted again.
+async fn import_meeting_export(
+    storage: &dyn StoragePort,
+    export: MeetingExport,
+) -> crate::error::Result<Meeting> {
+    let mut meeting = export.meeting;
+    meeting.id = None;
+    // The path may be another meeting's recording, or any file at all, and deleting
+    // this meeting would delete it
+    meeting.audio_file_path = None;
+    if meeting.recording_status == Some(RecordingStatus::Recorded) {
+        meeting.recording_status = None;
+    }
+    meeting.audio_sample_rate = None;
+    meeting.audio_channels = None;
+    meeting.audio_bits_per_sample = None;
+
+    let meeting_id = storage.create_meeting(&meeting).await?;
+    meeting.id = Some(meeting_id);
+
+    let inserted = insert_meeting_rows(
+        storage,
+        meeting_id,
+        export.participants,
+        export.transcripts,
+        export.insights,
+    )
+    .await;
+    if let Err(e) = inserted {
+        if let Err(cleanup) = storage.delete_meeting(meeting_id).await {
+            log::error!(
+                "Failed to remove partly imported meeting {}: {}",
+                meeting_id,
+                cleanup
+            );
+        }
+        return Err(e);
+    }
+
+    Ok(meeting)
+}
+
+async fn insert_meeting_rows(
+    storage: &dyn StoragePort,
+    meeting_id: i64,
+    mut participants: Vec<Participant>,
+    mut transcripts: Vec<Transcript>,
+    insights: Vec<Insight>,
+) -> crate::error::Result<()> {
+    let old_ids: Vec<Option<i64>> = participants.iter().map(|p| p.id).collect();
+    for participant in &mut participants {
+        participant.id = None;
+        participant.meeting_id = meeting_id;
+    }
+    let new_ids = storage.create_participants_batch(&participants).await?;
+    let participant_ids: HashMap<i64, i64> = old_ids
+        .into_iter()
+        .zip(new_ids)
+        .filter_map(|(old, new)| Some((old?, new)))
+        .collect();
+
+    for transcript in &mut transcripts {
+        transcript.id = None;
+        transcript.meeting_id = meeting_id;
+        transcript.participant_id = transcript
+            .participant_id
+            .and_then(|id| participant_ids.get(&id).copied());
+    }
+    storage.create_transcripts_batch(&transcripts).await?;
+
+    for mut insight in insights {
+        insight.id = None;
+        insight.meeting_id = meeting_id;
+        storage.create_insight(&insight).await?;
+    }
+    Ok(())
+}
+
+/// Export file name, including a sanitized form of the title when there is one
+fn export_file_name(meeting: &Meeting, timestamp: &str, extension: &str) -> String {
+    let id = meeting.id.unwrap_or_default();
+    match meeting.file_name_title() {
+        Some(title) => format!("meeting_{}_{}_{}.{}", id, title, timestamp, extension),
+        None => format!("meeting_{}_{}.{}", id, timestamp, extension),
+    }
+}
+
 /// Format meeting data as Markdown
 fn format_meeting_as_markdown(
     meeting: &Meeting,
@@ -146,8 +335,11 @@ fn format_meeting_as_markdown(
     let mut output = String::new();
 
     // Header
-    let title = meeting.title.as_deref().unwrap_or("Untitled Meeting");
-    output.push_str(&format!("# Meeting: {} - {}\n\n", title, meeting.platform));
+    output.push_str(&format!(
+        "# Meeting: {} - {}\n\n",
+        meeting.display_title(),
+        meeting.platform
+    ));
 
     // Meeting metadata
     let start_time = DateTime::from_timestamp(meeting.start_time, 0)
@@ -181,6 +373,13 @@ fn format_meeting_as_markdown(
 
     output.push_str("\n---\n\n");
 
+    // Notes section
+    if let Some(notes) = &meeting.notes {
+        output.push_str("## Notes\n\n");
+        output.push_str(notes);
+        output.push_str("\n\n---\n\n");
+    }
+
     // Participants section
     if !participants.is_empty() {
         output.push_str("## Participants\n\n");
@@ -224,9 +423,16 @@ fn format_meeting_as_markdown(
                 .or(transcript.speaker_label.as_deref())
                 .unwrap_or("Unknown Speaker");
 
+            // Tag the language in mixed-language meetings
+            let language = transcript
+                .language
+                .as_deref()
+                .map(|language| format!(" ({})", language))
+                .unwrap_or_default();
+
             output.push_str(&format!(
-                "**[{}] {}:** {}\n\n",
-                timestamp, speaker, transcript.text
+                "**[{}] {}{}:** {}\n\n",
+                timestamp, speaker, language, transcript.text
             ));
         }
 
@@ -319,6 +525,7 @@ fn format_meeting_as_json(
             "duration_seconds": meeting.end_time.map(|end| end - meeting.start_time),
             "participant_count": meeting.participant_count,
             "audio_file_path": meeting.audio_file_path,
+            "notes": meeting.notes,
             "created_at": meeting.created_at,
         },
         "participants": participants,
@@ -337,3 +544,202 @@ fn format_meeting_as_json(
 
     serde_json::to_string_pretty(&export_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::SqliteStorage;
+    use crate::domain::models::Platform;
+    use std::path::{Path, PathBuf};
+
+    fn memory_storage() -> SqliteStorage {
+        let storage = SqliteStorage::new(PathBuf::from(":memory:")).unwrap();
+        storage.run_migrations().unwrap();
+        storage
+    }
+
+    /// Drop the row IDs, which are reassigned on import
+    fn without_ids(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => map
+                .into_iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "id" | "meeting_id" | "participant_id"))
+                .map(|(key, value)| (key, without_ids(value)))
+                .collect(),
+            serde_json::Value::Array(items) => items.into_iter().map(without_ids).collect(),
+            other => other,
+        }
+    }
+
+    fn rows(export: &MeetingExport) -> serde_json::Value {
+        without_ids(serde_json::json!({
+            "meeting": export.meeting,
+            "participants": export.participants,
+            "transcripts": export.transcripts,
+            "insights": export.insights,
+        }))
+    }
+
+    async fn populated_meeting(storage: &SqliteStorage, audio_path: &str) -> i64 {
+        // Offset the IDs from a fresh database's
+        storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let mut meeting = Meeting::new(
+            Platform::Other("webex".to_string()),
+            Some("Planning".to_string()),
+            Some("en".to_string()),
+        );
+        meeting.end_time = Some(meeting.start_time + 600);
+        meeting.participant_count = Some(2);
+        meeting.audio_file_path = Some(audio_path.to_string());
+        meeting.transcripts_redacted = true;
+        meeting.detected_language = Some("en".to_string());
+        meeting.notes = Some("Bring the roadmap".to_string());
+        meeting.audio_offset_ms = 250;
+        meeting.audio_normalized = true;
+        meeting.transcript_confidence = Some(0.9);
+        meeting.recording_status = Some(RecordingStatus::Recorded);
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+
+        let mut alice = Participant::new(meeting_id, "Alice".to_string(), None);
+        alice.email = Some("alice@example.com".to_string());
+        alice.speaker_label = Some("Speaker 0".to_string());
+        let bob = Participant::new(meeting_id, "Bob".to_string(), None);
+        let ids = storage
+            .create_participants_batch(&[alice, bob])
+            .await
+            .unwrap();
+
+        let mut first = Transcript::with_speaker(
+            meeting_id,
+            0,
+            "Let's start".to_string(),
+            Some(0.95),
+            Some("Speaker 0".to_string()),
+        );
+        first.participant_id = Some(ids[0]);
+        first.end_timestamp_ms = Some(1200);
+        first.language = Some("en".to_string());
+        first.updated_at = Some(first.created_at + 60);
+        let second = Transcript::new(meeting_id, 1500, "Sounds good".to_string(), None);
+        storage
+            .create_transcripts_batch(&[second, first])
+            .await
+            .unwrap();
+
+        let mut summary = Insight::new(
+            meeting_id,
+            InsightType::Summary,
+            "Roadmap planning".to_string(),
+        );
+        summary.metadata = Some(r#"{"model":"test"}"#.to_string());
+        storage.create_insight(&summary).await.unwrap();
+        storage
+            .create_insight(&Insight::new(
+                meeting_id,
+                InsightType::ActionItem,
+                "Alice to share the roadmap".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        meeting_id
+    }
+
+    #[tokio::test]
+    async fn test_meeting_json_round_trip() {
+        let audio = tempfile::NamedTempFile::new().unwrap();
+        let audio_path = audio.path().to_string_lossy().into_owned();
+        let source = memory_storage();
+        let meeting_id = populated_meeting(&source, &audio_path).await;
+
+        let export = build_meeting_export(&source, meeting_id).await.unwrap();
+        assert_eq!(export.schema_version, MEETING_EXPORT_SCHEMA_VERSION);
+        assert_eq!(export.transcripts[0].text, "Let's start");
+
+        // Through JSON text, as when the document is saved to a file
+        let document = serde_json::from_str(&serde_json::to_string(&export).unwrap()).unwrap();
+        let target = memory_storage();
+        let imported = import_meeting_export(&target, parse_meeting_export(document).unwrap())
+            .await
+            .unwrap();
+
+        let reexported = build_meeting_export(&target, imported.id.unwrap())
+            .await
+            .unwrap();
+        // Everything but the recording comes across
+        let mut expected = rows(&export);
+        expected["meeting"]["audio_file_path"] = serde_json::Value::Null;
+        expected["meeting"]["recording_status"] = serde_json::Value::Null;
+        assert_eq!(rows(&reexported), expected);
+
+        // The transcript is linked to the imported participant
+        let alice = &reexported.participants[0];
+        assert_eq!(reexported.transcripts[0].participant_id, alice.id);
+        assert_eq!(
+            reexported.transcripts[0].participant_name.as_deref(),
+            Some("Alice")
+        );
+        assert!(Path::new(&audio_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_import_never_takes_over_a_recording() {
+        let audio = tempfile::NamedTempFile::new().unwrap();
+        let audio_path = audio.path().to_string_lossy().into_owned();
+        let storage = memory_storage();
+        let meeting_id = populated_meeting(&storage, &audio_path).await;
+
+        // Importing an export on the same install must not share the original's file
+        let export = build_meeting_export(&storage, meeting_id).await.unwrap();
+        let imported = import_meeting_export(&storage, export).await.unwrap();
+        assert_ne!(imported.id, Some(meeting_id));
+        assert_eq!(imported.audio_file_path, None);
+        assert_eq!(imported.recording_status, None);
+
+        crate::commands::meeting::delete_meeting_with_audio(&storage, imported.id.unwrap())
+            .await
+            .unwrap();
+        assert!(Path::new(&audio_path).exists());
+    }
+
+    #[test]
+    fn test_parse_meeting_export_checks_schema_version() {
+        let missing = parse_meeting_export(serde_json::json!({ "meeting": {} })).unwrap_err();
+        assert_eq!(missing.code, "invalid_input");
+
+        let newer = parse_meeting_export(serde_json::json!({
+            "schema_version": MEETING_EXPORT_SCHEMA_VERSION + 1,
+        }))
+        .unwrap_err();
+        assert!(newer.message.contains("Unsupported"));
+    }
+
+    #[test]
+    fn test_export_file_name_stays_in_export_dir() {
+        let export_dir = std::path::Path::new("/data/exports");
+        let mut meeting = Meeting::new(Platform::Teams, Some("../../evil".to_string()), None);
+        meeting.id = Some(7);
+
+        let file_name = export_file_name(&meeting, "20250101_120000", "md");
+        assert_eq!(file_name, "meeting_7_evil_20250101_120000.md");
+        assert_eq!(export_dir.join(&file_name).parent(), Some(export_dir));
+
+        meeting.title = Some("Standup\nfollow-up/notes".to_string());
+        let file_name = export_file_name(&meeting, "20250101_120000", "json");
+        assert_eq!(
+            file_name,
+            "meeting_7_Standup-follow-up_notes_20250101_120000.json"
+        );
+        assert_eq!(export_dir.join(&file_name).parent(), Some(export_dir));
+
+        meeting.title = None;
+        assert_eq!(
+            export_file_name(&meeting, "20250101_120000", "md"),
+            "meeting_7_20250101_120000.md"
+        );
+    }
+}
@@ -6,10 +6,21 @@
 //! - Managing custom prompt templates
 //! - Generating insights from transcripts
 
-use crate::adapters::services::llm::{AnthropicService, GoogleService, GroqService, OpenAIService};
-use crate::domain::models::InsightType;
+use crate::adapters::services::llm::{
+    AnthropicService, AzureOpenAIService, GoogleService, GroqService, MistralService,
+    OpenAIService, ProviderLlmFactory,
+};
+use crate::domain::models::{
+    parse_offset, InsightType, MeetingModelOverride, ModelOverride, ServiceType, Transcript,
+};
+use crate::domain::prompts::{
+    validate_template, with_detail_level, with_target_language, with_time_references, DetailLevel,
+};
 use crate::domain::PromptTemplates;
-use crate::ports::llm::{InsightRequest, LlmConfig, LlmServicePort, ModelInfo};
+use crate::error::{AppError, CommandError, CommandResult};
+use crate::ports::llm::{
+    GeneratedInsight, InsightRequest, LlmConfig, LlmServiceFactory, LlmServicePort, ModelInfo,
+};
 use crate::utils::keychain::KeychainPort;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
@@ -18,7 +29,36 @@ use tauri::State;
 /// Request to fetch models from a specific provider
 #[derive(Debug, Deserialize)]
 pub struct FetchModelsRequest {
-    pub provider: String, // "openai", "anthropic", "google", "groq"
+    pub provider: String, // "openai", "azure-openai", "anthropic", "google", "groq", "mistral"
+}
+
+pub(crate) use crate::adapters::services::llm::azure_openai::AZURE_OPENAI_PROVIDER;
+
+/// Build an Azure OpenAI service from the stored `azure-openai` service configuration
+pub(crate) async fn azure_openai_service(
+    state: &AppState,
+    api_key: String,
+) -> CommandResult<AzureOpenAIService> {
+    let settings = azure_openai_settings(state.storage.as_ref()).await?;
+    AzureOpenAIService::from_settings(api_key, &settings).map_err(CommandError::from)
+}
+
+/// Settings of the stored `azure-openai` service configuration
+async fn azure_openai_settings(
+    storage: &dyn crate::ports::storage::StoragePort,
+) -> CommandResult<serde_json::Value> {
+    let service_config = storage
+        .get_service_config("llm", AZURE_OPENAI_PROVIDER)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get Azure OpenAI configuration"))?
+        .ok_or_else(|| CommandError::config("Azure OpenAI is not configured"))?;
+
+    match service_config.settings.as_deref() {
+        Some(settings) => serde_json::from_str(settings).map_err(|e| {
+            CommandError::from(AppError::from(e)).context("Invalid Azure OpenAI settings")
+        }),
+        None => Ok(serde_json::Value::Null),
+    }
 }
 
 /// Response containing available models
@@ -45,6 +85,15 @@ pub struct GenerateInsightsRequest {
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
     pub custom_prompt: Option<String>,
+    /// Request JSON output from the model (only for models that support it)
+    #[serde(default)]
+    pub json_mode: bool,
+    /// Language to write the insights in (e.g. "Spanish"); None keeps the model's choice
+    #[serde(default)]
+    pub target_language: Option<String>,
+    /// Length of the insights; sets `max_tokens` unless it is given
+    #[serde(default)]
+    pub detail_level: DetailLevel,
 }
 
 /// Response containing generated insights
@@ -57,6 +106,8 @@ pub struct GenerateInsightsResponse {
 pub struct InsightResponse {
     pub insight_type: InsightType,
     pub content: String,
+    /// `{"truncated": true}` when the response hit the token limit
+    pub metadata: Option<serde_json::Value>,
 }
 
 /// Request to get default prompts
@@ -82,47 +133,75 @@ pub struct PromptInfo {
 pub async fn fetch_llm_models(
     request: FetchModelsRequest,
     state: State<'_, AppState>,
-) -> Result<FetchModelsResponse, String> {
+) -> CommandResult<FetchModelsResponse> {
+    use crate::ports::storage::StoragePort;
+
     log::info!("Fetching models for provider: {}", request.provider);
 
     // Get API key from keychain
     let api_key = state
         .keychain
         .get_api_key("llm", &request.provider)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
+
+    // User-configured context windows replace the adapters' estimates
+    let overrides = state
+        .storage
+        .get_model_overrides(&request.provider)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get model overrides"))?;
 
     // Create service based on provider
     let models = match request.provider.as_str() {
         "openai" => {
-            let service = OpenAIService::new(api_key);
+            let service = OpenAIService::new(api_key).with_model_overrides(overrides);
             service
                 .fetch_available_models()
                 .await
-                .map_err(|e| e.to_string())?
+                .map_err(CommandError::from)?
         }
         "anthropic" => {
-            let service = AnthropicService::new(api_key);
+            let service = AnthropicService::new(api_key).with_model_overrides(overrides);
             service
                 .fetch_available_models()
                 .await
-                .map_err(|e| e.to_string())?
+                .map_err(CommandError::from)?
         }
         "google" => {
-            let service = GoogleService::new(api_key);
+            let service = GoogleService::new(api_key).with_model_overrides(overrides);
             service
                 .fetch_available_models()
                 .await
-                .map_err(|e| e.to_string())?
+                .map_err(CommandError::from)?
         }
         "groq" => {
-            let service = GroqService::new(api_key);
+            let service = GroqService::new(api_key).with_model_overrides(overrides);
+            service
+                .fetch_available_models()
+                .await
+                .map_err(CommandError::from)?
+        }
+        "mistral" => {
+            let service = MistralService::new(api_key).with_model_overrides(overrides);
+            service
+                .fetch_available_models()
+                .await
+                .map_err(CommandError::from)?
+        }
+        AZURE_OPENAI_PROVIDER => {
+            let service = azure_openai_service(&state, api_key)
+                .await?
+                .with_model_overrides(overrides);
             service
                 .fetch_available_models()
                 .await
-                .map_err(|e| e.to_string())?
+                .map_err(CommandError::from)?
         }
         _ => {
-            return Err(format!("Unknown provider: {}", request.provider));
+            return Err(CommandError::invalid_input(format!(
+                "Unknown provider: {}",
+                request.provider
+            )));
         }
     };
 
@@ -135,18 +214,72 @@ pub async fn fetch_llm_models(
     Ok(FetchModelsResponse { models })
 }
 
+/// Get the user's model overrides for an LLM provider
+#[tauri::command]
+pub async fn get_model_overrides(
+    provider: String,
+    state: State<'_, AppState>,
+) -> CommandResult<Vec<ModelOverride>> {
+    use crate::ports::storage::StoragePort;
+
+    state
+        .storage
+        .get_model_overrides(&provider)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get model overrides"))
+}
+
+/// Set the context window of a model, e.g. one too new for the app to know
+///
+/// The override is used in place of the estimated window when the provider's models
+/// are fetched. Saving again for the same model replaces the previous override.
+#[tauri::command]
+pub async fn save_model_override(
+    provider: String,
+    model_id: String,
+    context_window: Option<usize>,
+    notes: Option<String>,
+    state: State<'_, AppState>,
+) -> CommandResult<()> {
+    use crate::ports::storage::StoragePort;
+
+    let model_id = model_id.trim();
+    if model_id.is_empty() {
+        return Err(CommandError::invalid_input("Model ID cannot be empty"));
+    }
+    if context_window == Some(0) {
+        return Err(CommandError::invalid_input(
+            "Context window must be greater than zero",
+        ));
+    }
+
+    let mut model_override = ModelOverride::new(provider, model_id.to_string());
+    if let Some(context_window) = context_window {
+        model_override = model_override.with_context_window(context_window);
+    }
+    if let Some(notes) = notes.filter(|n| !n.trim().is_empty()) {
+        model_override = model_override.with_notes(notes);
+    }
+
+    state
+        .storage
+        .save_model_override(&model_override)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to save model override"))
+}
+
 /// Save API key for an LLM provider
 #[tauri::command]
 pub async fn save_llm_api_key(
     request: SaveApiKeyRequest,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     log::info!("Saving API key for provider: {}", request.provider);
 
     state
         .keychain
         .save_api_key("llm", &request.provider, &request.api_key)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     log::info!("API key saved successfully for {}", request.provider);
     Ok(())
@@ -157,7 +290,7 @@ pub async fn save_llm_api_key(
 pub async fn check_llm_api_key(
     provider: String,
     state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     log::info!("Checking API key for provider: {}", provider);
 
     match state.keychain.get_api_key("llm", &provider) {
@@ -168,16 +301,13 @@ pub async fn check_llm_api_key(
 
 /// Delete API key for a provider
 #[tauri::command]
-pub async fn delete_llm_api_key(
-    provider: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
+pub async fn delete_llm_api_key(provider: String, state: State<'_, AppState>) -> CommandResult<()> {
     log::info!("Deleting API key for provider: {}", provider);
 
     state
         .keychain
         .delete_api_key("llm", &provider)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     log::info!("API key deleted successfully for {}", provider);
     Ok(())
@@ -188,7 +318,7 @@ pub async fn delete_llm_api_key(
 pub async fn generate_insights(
     request: GenerateInsightsRequest,
     state: State<'_, AppState>,
-) -> Result<GenerateInsightsResponse, String> {
+) -> CommandResult<GenerateInsightsResponse> {
     log::info!(
         "Generating insights with provider: {}, model: {}",
         request.provider,
@@ -199,57 +329,56 @@ pub async fn generate_insights(
     let api_key = state
         .keychain
         .get_api_key("llm", &request.provider)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     // Create LLM config
     let config = LlmConfig {
         model: request.model.clone(),
         temperature: request.temperature,
-        max_tokens: request.max_tokens,
+        max_tokens: request
+            .max_tokens
+            .or(Some(request.detail_level.max_tokens())),
         additional_settings: None,
+        json_mode: request.json_mode,
     };
 
-    // Create insight request
-    let insight_request = InsightRequest {
-        transcript: request.transcript,
-        context: request.context,
-        insight_types: request.insight_types,
-    };
+    if let Some(custom_prompt) = &request.custom_prompt {
+        validate_template(custom_prompt)?;
+    }
+    let target_language = target_language(request.target_language.as_deref())?;
 
-    // Generate insights based on provider
-    let insights = match request.provider.as_str() {
-        "openai" => {
-            let service = OpenAIService::new(api_key);
-            service
-                .generate_insights(&insight_request, &config, request.custom_prompt.as_deref())
-                .await
-                .map_err(|e| e.to_string())?
-        }
-        "anthropic" => {
-            let service = AnthropicService::new(api_key);
-            service
-                .generate_insights(&insight_request, &config, request.custom_prompt.as_deref())
-                .await
-                .map_err(|e| e.to_string())?
-        }
-        "google" => {
-            let service = GoogleService::new(api_key);
-            service
-                .generate_insights(&insight_request, &config, request.custom_prompt.as_deref())
-                .await
-                .map_err(|e| e.to_string())?
-        }
-        "groq" => {
-            let service = GroqService::new(api_key);
+    let service = llm_service(
+        state.storage.as_ref(),
+        &ProviderLlmFactory,
+        &request.provider,
+        api_key,
+    )
+    .await?;
+
+    // One request per type, so a translation instruction can go on each type's prompt
+    let mut insights = Vec::new();
+    for insight_type in &request.insight_types {
+        let insight_request = InsightRequest {
+            transcript: request.transcript.clone(),
+            context: request.context.clone(),
+            insight_types: vec![insight_type.clone()],
+        };
+        let prompt = insight_prompt(
+            insight_type,
+            request.custom_prompt.as_deref(),
+            target_language,
+            request.detail_level,
+            false,
+        );
+        insights.extend(
             service
-                .generate_insights(&insight_request, &config, request.custom_prompt.as_deref())
+                .generate_insights(&insight_request, &config, prompt.as_deref())
                 .await
-                .map_err(|e| e.to_string())?
-        }
-        _ => {
-            return Err(format!("Unknown provider: {}", request.provider));
-        }
-    };
+                .map_err(CommandError::from)?
+                .into_iter()
+                .map(|insight| tag_language(insight, target_language)),
+        );
+    }
 
     log::info!("Successfully generated {} insights", insights.len());
 
@@ -259,6 +388,7 @@ pub async fn generate_insights(
             .map(|i| InsightResponse {
                 insight_type: i.insight_type,
                 content: i.content,
+                metadata: i.metadata,
             })
             .collect(),
     })
@@ -268,7 +398,7 @@ pub async fn generate_insights(
 #[tauri::command]
 pub async fn get_default_prompts(
     request: GetDefaultPromptsRequest,
-) -> Result<GetDefaultPromptsResponse, String> {
+) -> CommandResult<GetDefaultPromptsResponse> {
     log::info!("Getting default prompts");
 
     let prompts = if let Some(insight_type) = request.insight_type {
@@ -291,14 +421,96 @@ pub async fn get_default_prompts(
     Ok(GetDefaultPromptsResponse { prompts })
 }
 
+/// A prompt template for an insight type
+#[derive(Debug, Serialize)]
+pub struct PromptTemplateResponse {
+    pub insight_type: InsightType,
+    pub prompt: String,
+    /// False when the built-in default is in use
+    pub is_custom: bool,
+}
+
+/// Get the prompt template used for an insight type (custom if saved, else the default)
+#[tauri::command]
+pub async fn get_prompt_template(
+    insight_type: InsightType,
+    state: State<'_, AppState>,
+) -> CommandResult<PromptTemplateResponse> {
+    use crate::ports::storage::StoragePort;
+
+    let custom = state
+        .storage
+        .get_prompt_template(&insight_type)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get prompt template"))?;
+
+    Ok(match custom {
+        Some(prompt) => PromptTemplateResponse {
+            insight_type,
+            prompt,
+            is_custom: true,
+        },
+        None => PromptTemplateResponse {
+            prompt: PromptTemplates::for_type(&insight_type).to_string(),
+            insight_type,
+            is_custom: false,
+        },
+    })
+}
+
+/// Save a custom prompt template for an insight type
+///
+/// The template is checked with [`validate_template`] before it is stored.
+#[tauri::command]
+pub async fn save_prompt_template(
+    insight_type: InsightType,
+    template: String,
+    state: State<'_, AppState>,
+) -> CommandResult<()> {
+    use crate::ports::storage::StoragePort;
+
+    validate_template(&template)?;
+
+    log::info!("Saving custom prompt template for {}", insight_type);
+    state
+        .storage
+        .save_prompt_template(&insight_type, &template)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to save prompt template"))
+}
+
+/// Discard the custom prompt template for an insight type and return the default
+#[tauri::command]
+pub async fn reset_prompt_template(
+    insight_type: InsightType,
+    state: State<'_, AppState>,
+) -> CommandResult<PromptTemplateResponse> {
+    use crate::ports::storage::StoragePort;
+
+    log::info!("Resetting prompt template for {}", insight_type);
+    state
+        .storage
+        .reset_prompt_template(&insight_type)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to reset prompt template"))?;
+
+    Ok(PromptTemplateResponse {
+        prompt: PromptTemplates::for_type(&insight_type).to_string(),
+        insight_type,
+        is_custom: false,
+    })
+}
+
 /// List all supported LLM providers
 #[tauri::command]
-pub async fn list_llm_providers() -> Result<Vec<String>, String> {
+pub async fn list_llm_providers() -> CommandResult<Vec<String>> {
     Ok(vec![
         "openai".to_string(),
+        AZURE_OPENAI_PROVIDER.to_string(),
         "anthropic".to_string(),
         "google".to_string(),
         "groq".to_string(),
+        "mistral".to_string(),
     ])
 }
 
@@ -306,17 +518,46 @@ pub async fn list_llm_providers() -> Result<Vec<String>, String> {
 #[derive(Debug, Deserialize)]
 pub struct GenerateMeetingInsightsRequest {
     pub meeting_id: i64,
-    pub provider: String,
-    pub model: String,
+    /// Defaults to the meeting's override, then the active LLM service
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Defaults to the meeting's override, then the provider's saved model
+    #[serde(default)]
+    pub model: Option<String>,
     pub insight_types: Vec<InsightType>,
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
+    /// Request JSON output from the model (only for models that support it)
+    #[serde(default)]
+    pub json_mode: bool,
+    /// Background for the prompts' `{context}`, such as the agenda or the previous
+    /// meeting's summary. Defaults to the meeting's notes.
+    #[serde(default)]
+    pub context: Option<String>,
+    /// Language to write the insights in (e.g. "Spanish"); None keeps the model's choice
+    #[serde(default)]
+    pub target_language: Option<String>,
+    /// Length of the insights; sets `max_tokens` unless it is given
+    #[serde(default)]
+    pub detail_level: DetailLevel,
+    /// Prefix transcript lines with `[mm:ss]` so action items cite when they came up
+    #[serde(default)]
+    pub include_timestamps: bool,
 }
 
 /// Response containing stored insights
 #[derive(Debug, Serialize)]
 pub struct MeetingInsightsResponse {
     pub insights: Vec<StoredInsight>,
+    /// Types that failed or came back empty; retry them with `regenerate_insight_types`
+    pub failed: Vec<FailedInsight>,
+}
+
+/// An insight type that could not be generated
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedInsight {
+    pub insight_type: InsightType,
+    pub error: CommandError,
 }
 
 #[derive(Debug, Serialize)]
@@ -325,149 +566,710 @@ pub struct StoredInsight {
     pub meeting_id: i64,
     pub insight_type: InsightType,
     pub content: String,
+    /// `"truncated": true` when the response hit the token limit, and `"language"` when
+    /// a target language was requested
+    pub metadata: Option<serde_json::Value>,
     pub created_at: i64,
+    /// Whether the user accepted or rejected the insight, None until rated
+    pub feedback: Option<bool>,
+}
+
+impl From<crate::domain::models::Insight> for StoredInsight {
+    fn from(insight: crate::domain::models::Insight) -> Self {
+        Self {
+            id: insight.id.unwrap_or(0),
+            meeting_id: insight.meeting_id,
+            insight_type: insight.insight_type,
+            content: insight.content,
+            metadata: insight
+                .metadata
+                .as_deref()
+                .and_then(|m| serde_json::from_str(m).ok()),
+            created_at: insight.created_at,
+            feedback: insight.feedback,
+        }
+    }
 }
 
 /// Generate insights for a meeting and store them in the database
 #[tauri::command]
 pub async fn generate_meeting_insights(
+    app: tauri::AppHandle,
     request: GenerateMeetingInsightsRequest,
     state: State<'_, AppState>,
-) -> Result<MeetingInsightsResponse, String> {
-    use crate::domain::models::Insight;
+) -> CommandResult<MeetingInsightsResponse> {
     use crate::ports::storage::StoragePort;
+    use crate::utils::notification::{meeting_display_name, notify, summarize_error};
+
+    let result = generate_and_store_insights(
+        &request,
+        state.storage.as_ref(),
+        state.keychain.as_ref(),
+        &ProviderLlmFactory,
+    )
+    .await;
+
+    let title = match state.storage.get_meeting(request.meeting_id).await {
+        Ok(Some(meeting)) => meeting.title,
+        _ => None,
+    };
+    let meeting_name = meeting_display_name(title.as_deref(), request.meeting_id);
+    match &result {
+        Ok(response) if response.failed.is_empty() => notify(
+            &app,
+            "Insights ready",
+            &format!("Insights ready for {}", meeting_name),
+        ),
+        Ok(response) => notify(
+            &app,
+            "Some insights failed",
+            &format!(
+                "Could not generate {} for {}",
+                response
+                    .failed
+                    .iter()
+                    .map(|failed| failed.insight_type.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                meeting_name
+            ),
+        ),
+        Err(e) => notify(
+            &app,
+            "Insight generation failed",
+            &format!(
+                "Could not generate insights for {}: {}",
+                meeting_name,
+                summarize_error(&e.message)
+            ),
+        ),
+    }
+
+    result
+}
+
+/// Generate and store only the given insight types for a meeting
+///
+/// Meant for retrying the types a previous `generate_meeting_insights` call reported
+/// as failed, without paying again for the ones that succeeded. `request` is the
+/// original request, so the retry keeps its language, detail level and other options;
+/// only its insight types are replaced.
+#[tauri::command]
+pub async fn regenerate_insight_types(
+    request: GenerateMeetingInsightsRequest,
+    insight_types: Vec<InsightType>,
+    state: State<'_, AppState>,
+) -> CommandResult<MeetingInsightsResponse> {
+    if insight_types.is_empty() {
+        return Err(CommandError::invalid_input(
+            "No insight types to regenerate",
+        ));
+    }
+    log::info!(
+        "Regenerating {} insight types for meeting {}",
+        insight_types.len(),
+        request.meeting_id
+    );
+
+    let request = GenerateMeetingInsightsRequest {
+        insight_types,
+        ..request
+    };
+    generate_and_store_insights(
+        &request,
+        state.storage.as_ref(),
+        state.keychain.as_ref(),
+        &ProviderLlmFactory,
+    )
+    .await
+}
+
+/// Generate insights with the requested provider and persist them
+///
+/// A type that fails doesn't stop the others: what was generated is stored and the
+/// failures are listed in the response. Only when every type fails is it an error.
+pub(crate) async fn generate_and_store_insights(
+    request: &GenerateMeetingInsightsRequest,
+    storage: &dyn crate::ports::storage::StoragePort,
+    keychain: &dyn KeychainPort,
+    factory: &dyn LlmServiceFactory,
+) -> CommandResult<MeetingInsightsResponse> {
+    use crate::domain::models::Insight;
+
+    let target_language = target_language(request.target_language.as_deref())?;
+    let (provider, model) = insights_model(
+        storage,
+        request.meeting_id,
+        request.provider.as_deref(),
+        request.model.as_deref(),
+    )
+    .await?;
 
     log::info!(
         "Generating insights for meeting {} with provider: {}, model: {}",
         request.meeting_id,
-        request.provider,
-        request.model
+        provider,
+        model
     );
 
     // Get transcripts for the meeting
-    let transcripts = state
-        .storage
+    let transcripts = storage
         .get_transcripts(request.meeting_id)
         .await
-        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to get transcripts"))?;
 
     if transcripts.is_empty() {
-        return Err("No transcripts found for this meeting".to_string());
+        return Err(CommandError::not_found(
+            "No transcripts found for this meeting",
+        ));
     }
 
     // Reconstruct full transcript with speaker labels
-    let full_transcript = transcripts
-        .iter()
-        .map(|t| {
-            // Prefer participant_name over speaker_label
-            if let Some(name) = &t.participant_name {
-                format!("[{}]: {}", name, t.text)
-            } else if let Some(speaker) = &t.speaker_label {
-                format!("[{}]: {}", speaker, t.text)
-            } else {
-                t.text.clone()
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
+    let full_transcript = Transcript::reconstruct(&transcripts, request.include_timestamps);
+
+    let notes = storage
+        .get_meeting(request.meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get meeting"))?
+        .and_then(|meeting| meeting.notes);
+    let context = insight_context(request.context.as_deref(), notes.as_deref());
 
     // Get API key from keychain
-    let api_key = state
-        .keychain
-        .get_api_key("llm", &request.provider)
-        .map_err(|e| e.to_string())?;
+    let api_key = keychain
+        .get_api_key("llm", &provider)
+        .map_err(CommandError::from)?;
 
     // Create LLM config
     let config = LlmConfig {
-        model: request.model.clone(),
+        model,
         temperature: request.temperature,
-        max_tokens: request.max_tokens,
+        max_tokens: request
+            .max_tokens
+            .or(Some(request.detail_level.max_tokens())),
         additional_settings: None,
+        json_mode: request.json_mode,
     };
 
-    // Create insight request
-    let insight_request = InsightRequest {
-        transcript: full_transcript,
-        context: None,
-        insight_types: request.insight_types.clone(),
-    };
+    let service = llm_service(storage, factory, &provider, api_key).await?;
 
-    // Generate insights based on provider
-    let generated_insights = match request.provider.as_str() {
-        "openai" => {
-            let service = OpenAIService::new(api_key);
-            service
-                .generate_insights(&insight_request, &config, None)
-                .await
-                .map_err(|e| e.to_string())?
-        }
-        "anthropic" => {
-            let service = AnthropicService::new(api_key);
-            service
-                .generate_insights(&insight_request, &config, None)
-                .await
-                .map_err(|e| e.to_string())?
-        }
-        "google" => {
-            let service = GoogleService::new(api_key);
-            service
-                .generate_insights(&insight_request, &config, None)
-                .await
-                .map_err(|e| e.to_string())?
-        }
-        "groq" => {
-            let service = GroqService::new(api_key);
-            service
-                .generate_insights(&insight_request, &config, None)
-                .await
-                .map_err(|e| e.to_string())?
-        }
-        _ => {
-            return Err(format!("Unknown provider: {}", request.provider));
+    let (generated_insights, failed) = generate_insight_types(
+        storage,
+        service.as_ref(),
+        &full_transcript,
+        context.as_deref(),
+        &request.insight_types,
+        &config,
+        PromptOptions {
+            target_language,
+            detail_level: request.detail_level,
+            include_timestamps: request.include_timestamps,
+        },
+    )
+    .await?;
+
+    if generated_insights.is_empty() {
+        if let Some(first) = failed.first() {
+            return Err(first.error.clone());
         }
-    };
+    }
 
     // Store insights in database
     let mut stored_insights = Vec::new();
     for insight in generated_insights {
-        let domain_insight = Insight::new(
+        let mut domain_insight = Insight::new(
             request.meeting_id,
             insight.insight_type.clone(),
             insight.content.clone(),
         );
+        domain_insight.metadata = insight.metadata.as_ref().map(|m| m.to_string());
 
-        let id = state
-            .storage
+        let id = storage
             .create_insight(&domain_insight)
             .await
-            .map_err(|e| format!("Failed to store insight: {}", e))?;
+            .map_err(|e| CommandError::from(e).context("Failed to store insight"))?;
 
         stored_insights.push(StoredInsight {
             id,
             meeting_id: request.meeting_id,
             insight_type: insight.insight_type,
             content: insight.content,
+            metadata: insight.metadata,
             created_at: domain_insight.created_at,
+            feedback: None,
         });
     }
 
     log::info!(
-        "Successfully generated and stored {} insights for meeting {}",
+        "Successfully generated and stored {} insights for meeting {} ({} types failed)",
         stored_insights.len(),
-        request.meeting_id
+        request.meeting_id,
+        failed.len()
     );
 
     Ok(MeetingInsightsResponse {
         insights: stored_insights,
+        failed,
+    })
+}
+
+/// Context for a meeting's insight prompts
+///
+/// Context given with the request wins over the meeting's notes; blank text counts as
+/// none.
+fn insight_context(request_context: Option<&str>, notes: Option<&str>) -> Option<String> {
+    fn non_blank(text: Option<&str>) -> Option<&str> {
+        text.map(str::trim).filter(|t| !t.is_empty())
+    }
+    non_blank(request_context)
+        .or_else(|| non_blank(notes))
+        .map(str::to_string)
+}
+
+/// Instructions appended to the prompt of every insight type
+#[derive(Debug, Clone, Copy, Default)]
+struct PromptOptions<'a> {
+    target_language: Option<&'a str>,
+    detail_level: DetailLevel,
+    /// The transcript lines start with `[mm:ss]`, so action items can cite times
+    include_timestamps: bool,
+}
+
+/// Generate each insight type on its own so a custom template only applies to its type
+///
+/// Returns the generated insights and the types that failed. A provider error or an
+/// empty response only fails its own type; a storage error fails the whole call.
+async fn generate_insight_types(
+    storage: &dyn crate::ports::storage::StoragePort,
+    service: &dyn LlmServicePort,
+    transcript: &str,
+    context: Option<&str>,
+    insight_types: &[InsightType],
+    config: &LlmConfig,
+    options: PromptOptions<'_>,
+) -> CommandResult<(Vec<GeneratedInsight>, Vec<FailedInsight>)> {
+    let PromptOptions {
+        target_language,
+        detail_level,
+        include_timestamps,
+    } = options;
+    let mut generated_insights = Vec::new();
+    let mut failed = Vec::new();
+    for insight_type in insight_types {
+        let custom_template = storage
+            .get_prompt_template(insight_type)
+            .await
+            .map_err(|e| CommandError::from(e).context("Failed to get prompt template"))?;
+
+        let insight_request = InsightRequest {
+            transcript: transcript.to_string(),
+            context: context.map(str::to_string),
+            insight_types: vec![insight_type.clone()],
+        };
+
+        let prompt = insight_prompt(
+            insight_type,
+            custom_template.as_deref(),
+            target_language,
+            detail_level,
+            include_timestamps,
+        );
+
+        let result = service
+            .generate_insights(&insight_request, config, prompt.as_deref())
+            .await
+            .map_err(CommandError::from)
+            .and_then(|insights| {
+                let insights: Vec<_> = insights
+                    .into_iter()
+                    .filter(|insight| !insight.content.trim().is_empty())
+                    .collect();
+                if insights.is_empty() {
+                    return Err(CommandError::new(
+                        "llm",
+                        format!("The model returned an empty {}", insight_type),
+                    ));
+                }
+                Ok(insights)
+            });
+
+        match result {
+            Ok(insights) => generated_insights.extend(insights.into_iter().map(|insight| {
+                let insight = tag_language(insight, target_language);
+                if include_timestamps {
+                    tag_time_references(insight)
+                } else {
+                    insight
+                }
+            })),
+            Err(error) => {
+                log::warn!("Failed to generate {}: {}", insight_type, error.message);
+                failed.push(FailedInsight {
+                    insight_type: insight_type.clone(),
+                    error,
+                });
+            }
+        }
+    }
+    Ok((generated_insights, failed))
+}
+
+/// Trimmed target language, or None if blank
+fn target_language(language: Option<&str>) -> CommandResult<Option<&str>> {
+    let Some(language) = language.map(str::trim).filter(|l| !l.is_empty()) else {
+        return Ok(None);
+    };
+    // It goes into the prompt verbatim, so keep it to a plain language name or code
+    if language.len() > 50 || language.contains(['{', '}', '\n']) {
+        return Err(CommandError::invalid_input(format!(
+            "Invalid target language: {}",
+            language
+        )));
+    }
+    Ok(Some(language))
+}
+
+/// Prompt for one insight type, or None to let the provider use its default
+///
+/// With a target language, a non-standard detail level or timestamped action items the
+/// default template is spelled out so the instructions can be appended to it.
+fn insight_prompt(
+    insight_type: &InsightType,
+    template: Option<&str>,
+    target_language: Option<&str>,
+    detail_level: DetailLevel,
+    include_timestamps: bool,
+) -> Option<String> {
+    let cite_times = include_timestamps && *insight_type == InsightType::ActionItem;
+    if target_language.is_none() && detail_level.instruction().is_none() && !cite_times {
+        return template.map(str::to_string);
+    }
+    let template = template.unwrap_or_else(|| PromptTemplates::for_type(insight_type));
+    let mut prompt = with_detail_level(template, detail_level);
+    if cite_times {
+        prompt = with_time_references(&prompt);
+    }
+    Some(match target_language {
+        Some(language) => with_target_language(&prompt, language),
+        None => prompt,
+    })
+}
+
+/// Record the language an insight was written in on its metadata
+fn tag_language(mut insight: GeneratedInsight, target_language: Option<&str>) -> GeneratedInsight {
+    if let Some(language) = target_language {
+        let metadata = insight
+            .metadata
+            .get_or_insert_with(|| serde_json::json!({}));
+        if let Some(fields) = metadata.as_object_mut() {
+            fields.insert("language".to_string(), language.into());
+        }
+    }
+    insight
+}
+
+/// Record the `[mm:ss]` times an action item response cites on its metadata
+///
+/// Stored as `time_references_ms`, meeting offsets in the order they appear, so the
+/// UI can link each item back to the transcript. Other insight types are left as is.
+fn tag_time_references(mut insight: GeneratedInsight) -> GeneratedInsight {
+    if insight.insight_type != InsightType::ActionItem {
+        return insight;
+    }
+    let offsets: Vec<i64> = insight
+        .content
+        .split('[')
+        .skip(1)
+        .filter_map(|rest| parse_offset(&rest[..rest.find(']')?]))
+        .collect();
+    if offsets.is_empty() {
+        return insight;
+    }
+    let metadata = insight
+        .metadata
+        .get_or_insert_with(|| serde_json::json!({}));
+    if let Some(fields) = metadata.as_object_mut() {
+        fields.insert("time_references_ms".to_string(), offsets.into());
+    }
+    insight
+}
+
+/// Provider and model to generate a meeting's insights with
+///
+/// A provider or model given in the request is used as-is. Anything not given comes
+/// from the meeting's override, then the active LLM service config.
+async fn insights_model(
+    storage: &dyn crate::ports::storage::StoragePort,
+    meeting_id: i64,
+    provider: Option<&str>,
+    model: Option<&str>,
+) -> CommandResult<(String, String)> {
+    // An explicit provider makes the meeting's choices irrelevant
+    let mut llm_override = match provider {
+        Some(_) => MeetingModelOverride::default(),
+        None => storage
+            .get_meeting_model_override(meeting_id)
+            .await
+            .map_err(|e| CommandError::from(e).context("Failed to get model override"))?
+            .unwrap_or_default(),
+    };
+    if let Some(provider) = provider {
+        llm_override.llm_provider = Some(provider.to_string());
+    }
+    if let Some(model) = model {
+        llm_override.llm_model = Some(model.to_string());
+    }
+
+    let configs = storage
+        .list_service_configs()
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get service configs"))?;
+    let selection = MeetingModelOverride::resolve(Some(&llm_override), &ServiceType::Llm, &configs)
+        .ok_or_else(|| CommandError::config("No active LLM service configured"))?;
+    let model = selection.model.ok_or_else(|| {
+        CommandError::config(format!("No model selected for {}", selection.provider))
+    })?;
+
+    Ok((selection.provider, model))
+}
+
+/// Build the LLM service for a provider
+///
+/// Azure OpenAI's stored settings are loaded for it; other providers only need the key.
+async fn llm_service(
+    storage: &dyn crate::ports::storage::StoragePort,
+    factory: &dyn LlmServiceFactory,
+    provider: &str,
+    api_key: String,
+) -> CommandResult<Box<dyn LlmServicePort>> {
+    let settings = match provider {
+        AZURE_OPENAI_PROVIDER => Some(azure_openai_settings(storage).await?),
+        _ => None,
+    };
+    factory
+        .create(provider, api_key, settings.as_ref())
+        .map_err(CommandError::from)
+}
+
+/// Generate and store only a summary for a meeting
+///
+/// Calls the provider's `generate_summary` directly instead of going through the
+/// multi-type insights path. With `target_language` the summary is written in that
+/// language; `detail_level` (standard by default) sets its length.
+#[tauri::command]
+pub async fn generate_meeting_summary(
+    meeting_id: i64,
+    provider: String,
+    model: String,
+    target_language: Option<String>,
+    detail_level: Option<DetailLevel>,
+    state: State<'_, AppState>,
+) -> CommandResult<StoredInsight> {
+    use crate::domain::models::Insight;
+    use crate::ports::storage::StoragePort;
+
+    let target_language = self::target_language(target_language.as_deref())?;
+    let detail_level = detail_level.unwrap_or_default();
+
+    log::info!(
+        "Generating summary for meeting {} with provider: {}, model: {}",
+        meeting_id,
+        provider,
+        model
+    );
+
+    let transcripts = state
+        .storage
+        .get_transcripts(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get transcripts"))?;
+
+    if transcripts.is_empty() {
+        return Err(CommandError::not_found(
+            "No transcripts found for this meeting",
+        ));
+    }
+
+    let api_key = state.keychain.get_api_key("llm", &provider)?;
+    let service = llm_service(
+        state.storage.as_ref(),
+        &ProviderLlmFactory,
+        &provider,
+        api_key,
+    )
+    .await?;
+    let custom_template = state
+        .storage
+        .get_prompt_template(&InsightType::Summary)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get prompt template"))?;
+
+    let config = LlmConfig {
+        model,
+        temperature: None,
+        max_tokens: Some(detail_level.max_tokens()),
+        additional_settings: None,
+        json_mode: false,
+    };
+
+    let prompt = insight_prompt(
+        &InsightType::Summary,
+        custom_template.as_deref(),
+        target_language,
+        detail_level,
+        false,
+    );
+
+    let content = service
+        .generate_summary(
+            &Transcript::reconstruct(&transcripts, false),
+            None,
+            &config,
+            prompt.as_deref(),
+        )
+        .await?;
+
+    let metadata = target_language.map(|language| serde_json::json!({ "language": language }));
+    let mut insight = Insight::new(meeting_id, InsightType::Summary, content);
+    insight.metadata = metadata.as_ref().map(|m| m.to_string());
+    let id = state
+        .storage
+        .create_insight(&insight)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to store summary"))?;
+
+    log::info!("Stored summary {} for meeting {}", id, meeting_id);
+
+    Ok(StoredInsight {
+        id,
+        meeting_id,
+        insight_type: insight.insight_type,
+        content: insight.content,
+        metadata,
+        created_at: insight.created_at,
+        feedback: None,
     })
 }
 
+/// Name an untitled meeting with the active LLM
+///
+/// Returns the new title, or None when the meeting already has one or has no
+/// transcript yet. A title the user entered is never replaced.
+#[tauri::command]
+pub async fn generate_meeting_title(
+    meeting_id: i64,
+    state: State<'_, AppState>,
+) -> CommandResult<Option<String>> {
+    fill_meeting_title(
+        state.storage.as_ref(),
+        state.keychain.as_ref(),
+        &ProviderLlmFactory,
+        meeting_id,
+    )
+    .await
+}
+
+/// Generate and save a title for a meeting whose title is empty
+///
+/// The stored summary is used when there is one, since it is shorter and already
+/// picks out the main topics; otherwise the full transcript.
+pub(crate) async fn fill_meeting_title(
+    storage: &dyn crate::ports::storage::StoragePort,
+    keychain: &dyn KeychainPort,
+    factory: &dyn LlmServiceFactory,
+    meeting_id: i64,
+) -> CommandResult<Option<String>> {
+    let mut meeting = storage
+        .get_meeting(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get meeting"))?
+        .ok_or_else(|| CommandError::not_found(format!("Meeting {} not found", meeting_id)))?;
+    if meeting
+        .title
+        .as_deref()
+        .is_some_and(|title| !title.trim().is_empty())
+    {
+        return Ok(None);
+    }
+
+    let transcripts = storage
+        .get_transcripts(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get transcripts"))?;
+    if transcripts.is_empty() {
+        return Ok(None);
+    }
+    let summary = storage
+        .get_insights(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get insights"))?
+        .into_iter()
+        .rev()
+        .find(|insight| insight.insight_type == InsightType::Summary)
+        .map(|insight| insight.content);
+    let source = summary.unwrap_or_else(|| Transcript::reconstruct(&transcripts, false));
+
+    let (provider, model) = insights_model(storage, meeting_id, None, None).await?;
+    let api_key = keychain
+        .get_api_key("llm", &provider)
+        .map_err(CommandError::from)?;
+    let service = llm_service(storage, factory, &provider, api_key).await?;
+    let config = LlmConfig {
+        model,
+        temperature: None,
+        max_tokens: Some(64),
+        additional_settings: None,
+        json_mode: false,
+    };
+
+    let response = service
+        .generate_summary(&source, None, &config, Some(PromptTemplates::title()))
+        .await?;
+    let title = clean_title(&response)
+        .ok_or_else(|| CommandError::new("llm", "The LLM returned an empty title"))?;
+
+    meeting.title = Some(title.clone());
+    storage
+        .update_meeting(&meeting)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to save meeting title"))?;
+
+    log::info!("Titled meeting {} \"{}\"", meeting_id, title);
+    Ok(Some(title))
+}
+
+/// Reduce an LLM reply to a one-line title
+///
+/// Models tend to add a "Title:" label, markdown heading or quotes despite the prompt.
+fn clean_title(response: &str) -> Option<String> {
+    const MAX_CHARS: usize = 80;
+
+    let line = response
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    let line = line.trim_start_matches('#').trim_start();
+    let line = match line.split_once(':') {
+        Some((label, rest)) if label.trim().eq_ignore_ascii_case("title") => rest,
+        _ => line,
+    };
+    let title = line
+        .trim()
+        .trim_matches(|c| matches!(c, '"' | '\'' | '*' | '`'))
+        .trim_end_matches('.')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let title: String = title.chars().take(MAX_CHARS).collect();
+    let title = title.trim_end().to_string();
+    (!title.is_empty()).then_some(title)
+}
+
 /// Get stored insights for a meeting
 #[tauri::command]
 pub async fn get_meeting_insights(
     meeting_id: i64,
     state: State<'_, AppState>,
-) -> Result<MeetingInsightsResponse, String> {
+) -> CommandResult<MeetingInsightsResponse> {
     use crate::ports::storage::StoragePort;
 
     log::info!("Getting insights for meeting {}", meeting_id);
@@ -476,22 +1278,35 @@ pub async fn get_meeting_insights(
         .storage
         .get_insights(meeting_id)
         .await
-        .map_err(|e| format!("Failed to get insights: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to get insights"))?;
 
     Ok(MeetingInsightsResponse {
-        insights: insights
-            .into_iter()
-            .map(|i| StoredInsight {
-                id: i.id.unwrap_or(0),
-                meeting_id: i.meeting_id,
-                insight_type: i.insight_type,
-                content: i.content,
-                created_at: i.created_at,
-            })
-            .collect(),
+        insights: insights.into_iter().map(StoredInsight::from).collect(),
+        failed: Vec::new(),
     })
 }
 
+/// Get a single stored insight, e.g. to confirm an edit saved with `update_insight`
+#[tauri::command]
+pub async fn get_insight(
+    insight_id: i64,
+    state: State<'_, AppState>,
+) -> CommandResult<StoredInsight> {
+    find_insight(state.storage.as_ref(), insight_id).await
+}
+
+async fn find_insight(
+    storage: &dyn crate::ports::storage::StoragePort,
+    insight_id: i64,
+) -> CommandResult<StoredInsight> {
+    storage
+        .get_insight(insight_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get insight"))?
+        .map(StoredInsight::from)
+        .ok_or_else(|| CommandError::not_found(format!("Insight {} not found", insight_id)))
+}
+
 /// Update an existing insight's content
 ///
 /// This allows users to edit and refine AI-generated insights.
@@ -500,7 +1315,7 @@ pub async fn update_insight(
     insight_id: i64,
     content: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     use crate::ports::storage::StoragePort;
 
     log::info!("Updating insight {}", insight_id);
@@ -509,7 +1324,34 @@ pub async fn update_insight(
         .storage
         .update_insight_content(insight_id, &content)
         .await
-        .map_err(|e| format!("Failed to update insight: {}", e))
+        .map_err(|e| CommandError::from(e).context("Failed to update insight"))
+}
+
+/// Mark an insight as accepted or rejected
+///
+/// The rating is returned with the insight by `get_meeting_insights`, so prompts can
+/// later be tuned on what users kept. `accepted: null` clears it.
+#[tauri::command]
+pub async fn set_insight_feedback(
+    insight_id: i64,
+    accepted: Option<bool>,
+    state: State<'_, AppState>,
+) -> CommandResult<StoredInsight> {
+    rate_insight(state.storage.as_ref(), insight_id, accepted).await
+}
+
+async fn rate_insight(
+    storage: &dyn crate::ports::storage::StoragePort,
+    insight_id: i64,
+    accepted: Option<bool>,
+) -> CommandResult<StoredInsight> {
+    log::info!("Setting feedback on insight {}: {:?}", insight_id, accepted);
+
+    storage
+        .set_insight_feedback(insight_id, accepted)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to save insight feedback"))?;
+    find_insight(storage, insight_id).await
 }
 
 /// Delete all insights for a meeting
@@ -519,7 +1361,7 @@ pub async fn update_insight(
 pub async fn delete_meeting_insights(
     meeting_id: i64,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     use crate::ports::storage::StoragePort;
 
     log::info!("Deleting insights for meeting {}", meeting_id);
@@ -527,5 +1369,583 @@ pub async fn delete_meeting_insights(
         .storage
         .delete_insights(meeting_id)
         .await
-        .map_err(|e| format!("Failed to delete insights: {}", e))
+        .map_err(|e| CommandError::from(e).context("Failed to delete insights"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{Meeting, Platform, ServiceConfig};
+    use crate::ports::mocks::MockStorage;
+    use crate::ports::storage::StoragePort;
+    use std::sync::Mutex;
+
+    /// LLM stand-in that records the requests and prompt templates it was given
+    #[derive(Default)]
+    struct RecordingLlm {
+        requests: Mutex<Vec<InsightRequest>>,
+        prompts: Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LlmServicePort for RecordingLlm {
+        async fn generate_insights(
+            &self,
+            request: &InsightRequest,
+            _config: &LlmConfig,
+            prompt_template: Option<&str>,
+        ) -> crate::error::Result<Vec<GeneratedInsight>> {
+            self.requests.lock().unwrap().push(request.clone());
+            self.prompts
+                .lock()
+                .unwrap()
+                .push(prompt_template.map(str::to_string));
+            Ok(request
+                .insight_types
+                .iter()
+                .map(|insight_type| GeneratedInsight {
+                    insight_type: insight_type.clone(),
+                    content: "generated".to_string(),
+                    metadata: None,
+                })
+                .collect())
+        }
+
+        async fn generate_summary(
+            &self,
+            _transcript: &str,
+            _context: Option<&str>,
+            _config: &LlmConfig,
+            _prompt_template: Option<&str>,
+        ) -> crate::error::Result<String> {
+            Ok("generated".to_string())
+        }
+
+        async fn fetch_available_models(&self) -> crate::error::Result<Vec<ModelInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn provider_name(&self) -> &str {
+            "recording"
+        }
+
+        fn is_configured(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_insight_context_defaults_to_notes() {
+        assert_eq!(
+            insight_context(Some("Q3 planning agenda"), Some("notes")).as_deref(),
+            Some("Q3 planning agenda")
+        );
+        assert_eq!(
+            insight_context(None, Some(" Follow up with legal ")).as_deref(),
+            Some("Follow up with legal")
+        );
+        assert_eq!(
+            insight_context(Some("  "), Some("notes")).as_deref(),
+            Some("notes")
+        );
+        assert_eq!(insight_context(None, Some("")), None);
+    }
+
+    #[tokio::test]
+    async fn test_context_reaches_insight_request() {
+        let storage = MockStorage::new();
+        let llm = RecordingLlm::default();
+
+        let (insights, _) = generate_insight_types(
+            &storage,
+            &llm,
+            "[Speaker 0]: Let's review the budget",
+            Some("Agenda: budget review"),
+            &[InsightType::Summary, InsightType::ActionItem],
+            &LlmConfig::default(),
+            PromptOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(insights.len(), 2);
+
+        let requests = llm.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in requests.iter() {
+            assert_eq!(request.context.as_deref(), Some("Agenda: budget review"));
+            assert_eq!(request.transcript, "[Speaker 0]: Let's review the budget");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_target_language_instruction() {
+        let storage = MockStorage::new();
+        let instruction = "Write your entire response in Spanish";
+
+        // No target language: the provider's own default prompt is used
+        let llm = RecordingLlm::default();
+        let (insights, _) = generate_insight_types(
+            &storage,
+            &llm,
+            "[Speaker 0]: Let's review the budget",
+            None,
+            &[InsightType::Summary],
+            &LlmConfig::default(),
+            PromptOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(llm.prompts.lock().unwrap().as_slice(), &[None]);
+        assert!(insights[0].metadata.is_none());
+
+        // The instruction goes on the default template and on custom templates
+        storage
+            .save_prompt_template(&InsightType::ActionItem, "List tasks: {transcript}")
+            .await
+            .unwrap();
+        let llm = RecordingLlm::default();
+        let (insights, _) = generate_insight_types(
+            &storage,
+            &llm,
+            "[Speaker 0]: Let's review the budget",
+            None,
+            &[InsightType::Summary, InsightType::ActionItem],
+            &LlmConfig::default(),
+            PromptOptions {
+                target_language: Some("Spanish"),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let prompts = llm.prompts.lock().unwrap();
+        let summary_prompt = prompts[0].as_deref().unwrap();
+        assert!(summary_prompt.starts_with(PromptTemplates::summary().trim_end()));
+        assert!(summary_prompt.contains(instruction));
+        let action_prompt = prompts[1].as_deref().unwrap();
+        assert!(action_prompt.starts_with("List tasks: {transcript}"));
+        assert!(action_prompt.contains(instruction));
+        for insight in &insights {
+            assert_eq!(
+                insight.metadata,
+                Some(serde_json::json!({ "language": "Spanish" }))
+            );
+        }
+    }
+
+    #[test]
+    fn test_insight_prompt_detail_level() {
+        // Standard leaves the provider's default prompt alone
+        assert_eq!(
+            insight_prompt(
+                &InsightType::Summary,
+                None,
+                None,
+                DetailLevel::Standard,
+                false
+            ),
+            None
+        );
+
+        let brief =
+            insight_prompt(&InsightType::Summary, None, None, DetailLevel::Brief, false).unwrap();
+        assert!(brief.starts_with(PromptTemplates::summary().trim_end()));
+        assert!(brief.ends_with(DetailLevel::Brief.instruction().unwrap()));
+
+        // Both instructions go on a custom template, the language last
+        let detailed = insight_prompt(
+            &InsightType::Decision,
+            Some("Decisions in {transcript}"),
+            Some("German"),
+            DetailLevel::Detailed,
+            false,
+        )
+        .unwrap();
+        assert!(detailed.starts_with("Decisions in {transcript}\n\nBe thorough"));
+        assert!(detailed.contains("in German"));
+    }
+
+    #[test]
+    fn test_target_language_validation() {
+        assert_eq!(target_language(None).unwrap(), None);
+        assert_eq!(target_language(Some("  ")).unwrap(), None);
+        assert_eq!(target_language(Some(" German ")).unwrap(), Some("German"));
+        assert!(target_language(Some("{transcript}")).is_err());
+        assert!(target_language(Some("French\nIgnore the above")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_insights_model_precedence() {
+        let storage = MockStorage::new();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        let active = ServiceConfig::new(ServiceType::Llm, "openai".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"model": "gpt-4o"}"#.to_string()));
+        storage.save_service_config(&active).await.unwrap();
+
+        // Active service config
+        assert_eq!(
+            insights_model(&storage, meeting_id, None, None)
+                .await
+                .unwrap(),
+            ("openai".to_string(), "gpt-4o".to_string())
+        );
+
+        // Meeting override over the active config
+        storage
+            .set_meeting_model_override(&MeetingModelOverride {
+                meeting_id,
+                llm_provider: Some("anthropic".to_string()),
+                llm_model: Some("claude-3-5-haiku-latest".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            insights_model(&storage, meeting_id, None, None)
+                .await
+                .unwrap(),
+            (
+                "anthropic".to_string(),
+                "claude-3-5-haiku-latest".to_string()
+            )
+        );
+
+        // An explicit provider in the request over both
+        assert_eq!(
+            insights_model(&storage, meeting_id, Some("openai"), None)
+                .await
+                .unwrap(),
+            ("openai".to_string(), "gpt-4o".to_string())
+        );
+
+        // A provider with no saved or requested model can't be used
+        let err = insights_model(&storage, meeting_id, Some("groq"), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "config");
+    }
+
+    #[tokio::test]
+    async fn test_failed_insight_type_does_not_discard_others() {
+        use crate::domain::models::Transcript as StoredTranscript;
+        use crate::ports::mocks::MockLlmService;
+        use crate::utils::keychain::MockKeychain;
+
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        keychain.save_api_key("llm", "openai", "llm-key").unwrap();
+        let active = ServiceConfig::new(ServiceType::Llm, "openai".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"model": "gpt-4o"}"#.to_string()));
+        storage.save_service_config(&active).await.unwrap();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        storage
+            .create_transcript(&StoredTranscript::new(
+                meeting_id,
+                0,
+                "Let's review the budget".to_string(),
+                None,
+            ))
+            .await
+            .unwrap();
+
+        let request = |insight_types: Vec<InsightType>| GenerateMeetingInsightsRequest {
+            meeting_id,
+            provider: None,
+            model: None,
+            insight_types,
+            temperature: None,
+            max_tokens: None,
+            json_mode: false,
+            context: None,
+            target_language: None,
+            detail_level: DetailLevel::default(),
+            include_timestamps: false,
+        };
+
+        // Action items fail; the summary and key points are still stored
+        let llm = MockLlmService::new().failing_for(&[InsightType::ActionItem]);
+        let response = generate_and_store_insights(
+            &request(vec![
+                InsightType::Summary,
+                InsightType::ActionItem,
+                InsightType::KeyPoint,
+            ]),
+            &storage,
+            &keychain,
+            &llm,
+        )
+        .await
+        .unwrap();
+        let generated: Vec<_> = response.insights.iter().map(|i| &i.insight_type).collect();
+        assert_eq!(generated, [&InsightType::Summary, &InsightType::KeyPoint]);
+        assert_eq!(response.failed.len(), 1);
+        assert_eq!(response.failed[0].insight_type, InsightType::ActionItem);
+        assert_eq!(response.failed[0].error.code, "llm");
+        assert_eq!(storage.get_insights(meeting_id).await.unwrap().len(), 2);
+
+        // Retrying only the failed type doesn't call the model for the others
+        let llm = MockLlmService::new();
+        let response = generate_and_store_insights(
+            &request(vec![InsightType::ActionItem]),
+            &storage,
+            &keychain,
+            &llm,
+        )
+        .await
+        .unwrap();
+        assert!(response.failed.is_empty());
+        assert_eq!(llm.requests.lock().unwrap().len(), 1);
+        assert_eq!(storage.get_insights(meeting_id).await.unwrap().len(), 3);
+
+        // When every type fails nothing is stored and the call fails
+        let llm = MockLlmService::new().failing_for(&[InsightType::Decision]);
+        let err = generate_and_store_insights(
+            &request(vec![InsightType::Decision]),
+            &storage,
+            &keychain,
+            &llm,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, "llm");
+        assert_eq!(storage.get_insights(meeting_id).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_find_insight() {
+        use crate::domain::models::Insight;
+
+        let storage = MockStorage::new();
+        let mut insight = Insight::new(1, InsightType::Decision, "Ship on Friday".to_string());
+        insight.metadata = Some(r#"{"truncated":true}"#.to_string());
+        let id = storage.create_insight(&insight).await.unwrap();
+
+        let found = find_insight(&storage, id).await.unwrap();
+        assert_eq!(found.id, id);
+        assert_eq!(found.content, "Ship on Friday");
+        assert_eq!(
+            found.metadata,
+            Some(serde_json::json!({ "truncated": true }))
+        );
+
+        let missing = find_insight(&storage, id + 1).await.unwrap_err();
+        assert_eq!(missing.code, "not_found");
+    }
+
+    #[tokio::test]
+    async fn test_rate_insight() {
+        use crate::domain::models::Insight;
+
+        let storage = MockStorage::new();
+        let id = storage
+            .create_insight(&Insight::new(
+                1,
+                InsightType::ActionItem,
+                "Book the venue".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let rated = rate_insight(&storage, id, Some(true)).await.unwrap();
+        assert_eq!(rated.feedback, Some(true));
+        let stored = storage.get_insights(1).await.unwrap();
+        assert_eq!(StoredInsight::from(stored[0].clone()).feedback, Some(true));
+
+        let cleared = rate_insight(&storage, id, None).await.unwrap();
+        assert_eq!(cleared.feedback, None);
+
+        let missing = rate_insight(&storage, id + 1, Some(false))
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code, "not_found");
+    }
+
+    #[test]
+    fn test_clean_title() {
+        assert_eq!(
+            clean_title("Title: \"Q3 Budget Review.\"\n\nThe meeting covered...").as_deref(),
+            Some("Q3 Budget Review")
+        );
+        assert_eq!(
+            clean_title("\n## Hiring   Plan Sync").as_deref(),
+            Some("Hiring Plan Sync")
+        );
+        assert_eq!(clean_title("  \n \"\" "), None);
+        assert_eq!(
+            clean_title(&"word ".repeat(40)).unwrap().chars().count(),
+            79
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fill_meeting_title_only_when_empty() {
+        use crate::ports::mocks::MockLlmService;
+        use crate::utils::keychain::MockKeychain;
+
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        keychain.save_api_key("llm", "openai", "llm-key").unwrap();
+        let config = ServiceConfig::new(ServiceType::Llm, "openai".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"model": "gpt-4o"}"#.to_string()));
+        storage.save_service_config(&config).await.unwrap();
+
+        let mut ids = Vec::new();
+        for title in [None, Some("  "), Some("Budget review")] {
+            let meeting = Meeting::new(Platform::Zoom, title.map(str::to_string), None);
+            let id = storage.create_meeting(&meeting).await.unwrap();
+            let transcript = Transcript::new(id, 0, "Let's review the budget".to_string(), None);
+            storage.create_transcript(&transcript).await.unwrap();
+            ids.push(id);
+        }
+        let untranscribed = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let llm = MockLlmService::new();
+        let generated = MockLlmService::content_for(&InsightType::Summary);
+        for id in &ids[..2] {
+            let title = fill_meeting_title(&storage, &keychain, &llm, *id)
+                .await
+                .unwrap();
+            assert_eq!(title.as_deref(), Some(generated.as_str()));
+            let meeting = storage.get_meeting(*id).await.unwrap().unwrap();
+            assert_eq!(meeting.title.as_deref(), Some(generated.as_str()));
+        }
+
+        // The user's title is kept, and nothing is asked of the LLM without a transcript
+        let created = llm.created.lock().unwrap().len();
+        for id in [ids[2], untranscribed] {
+            assert_eq!(
+                fill_meeting_title(&storage, &keychain, &llm, id)
+                    .await
+                    .unwrap(),
+                None
+            );
+        }
+        assert_eq!(llm.created.lock().unwrap().len(), created);
+        let meeting = storage.get_meeting(ids[2]).await.unwrap().unwrap();
+        assert_eq!(meeting.title.as_deref(), Some("Budget review"));
+        let meeting = storage.get_meeting(untranscribed).await.unwrap().unwrap();
+        assert_eq!(meeting.title, None);
+    }
+
+    #[tokio::test]
+    async fn test_include_timestamps_in_transcript_and_action_prompt() {
+        use crate::ports::mocks::MockLlmService;
+        use crate::utils::keychain::MockKeychain;
+
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        keychain.save_api_key("llm", "openai", "llm-key").unwrap();
+        let config = ServiceConfig::new(ServiceType::Llm, "openai".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"model": "gpt-4o"}"#.to_string()));
+        storage.save_service_config(&config).await.unwrap();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        for (timestamp_ms, text) in [
+            (65_000, "Sam will send the deck"),
+            (3_725_000, "Thanks all"),
+        ] {
+            let transcript = Transcript::with_speaker(
+                meeting_id,
+                timestamp_ms,
+                text.to_string(),
+                None,
+                Some("Speaker 0".to_string()),
+            );
+            storage.create_transcript(&transcript).await.unwrap();
+        }
+
+        let llm = MockLlmService::new();
+        let request = GenerateMeetingInsightsRequest {
+            meeting_id,
+            provider: None,
+            model: None,
+            insight_types: vec![InsightType::ActionItem],
+            temperature: None,
+            max_tokens: None,
+            json_mode: false,
+            context: None,
+            target_language: None,
+            detail_level: DetailLevel::default(),
+            include_timestamps: true,
+        };
+        generate_and_store_insights(&request, &storage, &keychain, &llm)
+            .await
+            .unwrap();
+        assert_eq!(
+            llm.requests.lock().unwrap()[0].transcript,
+            "[01:05] [Speaker 0]: Sam will send the deck\n[1:02:05] [Speaker 0]: Thanks all"
+        );
+
+        // Only the action item prompt asks for times
+        let recording = RecordingLlm::default();
+        generate_insight_types(
+            &storage,
+            &recording,
+            "[01:05] [Speaker 0]: Sam will send the deck",
+            None,
+            &[InsightType::Summary, InsightType::ActionItem],
+            &LlmConfig::default(),
+            PromptOptions {
+                include_timestamps: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let prompts = recording.prompts.lock().unwrap();
+        assert_eq!(prompts[0], None);
+        assert_eq!(
+            prompts[1].as_deref(),
+            Some(with_time_references(PromptTemplates::action_items()).as_str())
+        );
+    }
+
+    #[test]
+    fn test_tag_time_references() {
+        let insight = |insight_type: InsightType, content: &str| GeneratedInsight {
+            insight_type,
+            content: content.to_string(),
+            metadata: Some(serde_json::json!({ "language": "German" })),
+        };
+
+        let tagged = tag_time_references(insight(
+            InsightType::ActionItem,
+            "- **[Sam]** - Send the deck `[01:05]`\n- **[Unassigned]** - Book a room `[1:02:05]`",
+        ));
+        assert_eq!(
+            tagged.metadata,
+            Some(serde_json::json!({
+                "language": "German",
+                "time_references_ms": [65_000, 3_725_000],
+            }))
+        );
+
+        // No times cited, or not an action item: metadata is untouched
+        let untagged =
+            tag_time_references(insight(InsightType::ActionItem, "- **[Sam]** - Send it"));
+        assert_eq!(
+            untagged.metadata,
+            Some(serde_json::json!({ "language": "German" }))
+        );
+        let summary = tag_time_references(insight(InsightType::Summary, "Agreed `[01:05]`"));
+        assert_eq!(
+            summary.metadata,
+            Some(serde_json::json!({ "language": "German" }))
+        );
+    }
 }
@@ -1,26 +1,95 @@
 //! Meeting and audio capture commands
 
-use crate::domain::models::{Meeting, Platform};
-use crate::ports::audio::AudioCapturePort;
+use crate::adapters::services::asr::{get_active_asr_service, ProviderAsrFactory};
+use crate::adapters::storage::SqliteStorage;
+use crate::commands::streaming::{close_active_session, StreamingTranscriptionState};
+use crate::domain::models::{
+    Meeting, MeetingModelOverride, Platform, RecordingStatus, SavedRecording,
+};
+use crate::error::{AppError, CommandError, CommandResult};
+use crate::ports::audio::{
+    AudioBuffer, AudioCapturePort, AudioDeviceInfo, AudioFormat, CaptureTiming,
+};
 use crate::ports::storage::StoragePort;
+use crate::ports::transcription::{TranscriptionServiceFactory, STREAMING_AUDIO_FORMAT};
+use crate::utils::audio::{normalize_loudness, trim_silence, NormalizeConfig, SilenceTrimConfig};
+use crate::utils::audio_file::{
+    opus_format, save_opus_file_as, save_wav_file, save_wav_file_as, RecordingFormat,
+    TRANSCRIPTION_WAV_FORMAT,
+};
+use crate::utils::flac::read_flac_file_header;
+use crate::utils::keychain::KeychainPort;
+use crate::utils::opus::read_opus_file_info;
+use crate::utils::wav::read_wav_file_header;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
 use std::sync::Arc;
-use tauri::Manager;
+use std::time::UNIX_EPOCH;
+use tauri::{Emitter, Manager};
+use tokio::sync::Mutex;
 
 /// Supported language codes for transcription (must match frontend LANGUAGES array)
 const SUPPORTED_LANGUAGES: &[&str] = &[
     "en", "es", "fr", "de", "it", "pt", "nl", "ja", "ko", "zh", "hi", "ru", "pl", "tr", "uk", "vi",
 ];
 
+/// Language code requesting automatic language detection by the ASR provider
+const AUTO_DETECT_LANGUAGE: &str = "auto";
+
+/// Recordings shorter than this are discarded instead of being saved for transcription
+const MIN_RECORDING_DURATION_MS: u64 = 1000;
+
+/// Event emitted when a stopped meeting captured no usable audio
+const MEETING_NO_AUDIO_EVENT: &str = "meeting-no-audio";
+
+/// Payload of the `meeting-no-audio` event
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingNoAudio {
+    pub meeting_id: i64,
+    pub message: String,
+}
+
+/// Event emitted once a stopped meeting's recording is saved and ready to transcribe
+const MEETING_AUDIO_READY_EVENT: &str = "meeting-audio-ready";
+
+/// Event emitted when a stopped meeting's recording could not be saved
+const MEETING_AUDIO_FAILED_EVENT: &str = "meeting-audio-failed";
+
+/// Payload of the `meeting-audio-ready` event
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingAudioReady {
+    pub meeting_id: i64,
+    /// Saved WAV file, as stored on the meeting
+    pub path: String,
+}
+
+/// Event emitted when `refresh_audio_devices` re-enumerates the audio devices
+const DEVICES_CHANGED_EVENT: &str = "devices-changed";
+
+/// Payload of the `devices-changed` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DevicesChanged {
+    pub devices: Vec<AudioDeviceInfo>,
+}
+
+/// Payload of the `meeting-audio-failed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingAudioFailed {
+    pub meeting_id: i64,
+    pub message: String,
+}
+
 /// Request to start a new meeting
 #[derive(Debug, Deserialize)]
 pub struct StartMeetingRequest {
-    pub platform: String, // "teams", "zoom", "meet"
+    pub platform: String, // "teams", "zoom", "meet", or any other platform name
     pub title: Option<String>,
-    pub language: Option<String>, // Language code for transcription (e.g., "en", "es", "fr")
-    pub speaker_device: Option<String>, // Speaker device (e.g., "0: Headset A18 (Speaker)")
-    pub microphone_device: Option<String>, // Microphone device (e.g., "1: Headset A18 (Microphone)")
+    pub language: Option<String>, // Language code for transcription (e.g., "en", "es", "fr"), or "auto"
+    pub speaker_device: Option<String>, // Speaker device ID from list_audio_devices_detailed, or "0: Headset A18 (Speaker)"
+    pub microphone_device: Option<String>, // Microphone device ID, or "1: Headset A18 (Microphone)"
+    pub process_name: Option<String>, // Meeting app to capture on its own, e.g. "zoom" or "ms-teams.exe" (Windows only)
 }
 
 /// Meeting status response
@@ -56,19 +125,14 @@ pub async fn start_meeting(
     app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     request: StartMeetingRequest,
-) -> Result<i64, String> {
+) -> CommandResult<i64> {
     log::info!("Starting meeting for platform: {}", request.platform);
 
-    // Parse platform
-    let platform = match request.platform.as_str() {
-        "teams" => Platform::Teams,
-        "zoom" => Platform::Zoom,
-        "meet" => Platform::Meet,
-        _ => return Err(format!("Invalid platform: {}", request.platform)),
-    };
+    let platform = parse_platform(&request.platform)?;
 
-    // Validate and normalize language code
+    // Validate and normalize language code ("auto" is stored as None for auto-detection)
     let language = match &request.language {
+        Some(lang) if lang == AUTO_DETECT_LANGUAGE => None,
         Some(lang) if SUPPORTED_LANGUAGES.contains(&lang.as_str()) => Some(lang.clone()),
         Some(lang) => {
             log::warn!("Invalid language code '{}', defaulting to 'en'", lang);
@@ -81,16 +145,29 @@ pub async fn start_meeting(
         .storage
         .create_meeting(&meeting)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     log::info!("Created meeting with ID: {}", meeting_id);
 
     // Start audio capture and wait for confirmation
     // This ensures we only store the meeting ID if audio capture actually started
     // Use dual-capture to capture both speaker output AND microphone input
+    let timing = capture_timing(state.storage.as_ref()).await;
+    let preferred_format = streaming_capture_format(
+        state.storage.as_ref(),
+        state.keychain.as_ref(),
+        &ProviderAsrFactory,
+    )
+    .await;
     let mut audio_capture = state.audio_capture.lock().await;
+    audio_capture.set_capture_timing(timing);
     match audio_capture
-        .start_dual_capture(request.speaker_device, request.microphone_device)
+        .start_dual_capture(
+            request.speaker_device,
+            request.microphone_device,
+            request.process_name.filter(|name| !name.trim().is_empty()),
+            preferred_format,
+        )
         .await
     {
         Ok(_) => {
@@ -102,10 +179,13 @@ pub async fn start_meeting(
             // Store current meeting ID only after successful audio capture
             *state.current_meeting_id.lock().await = Some(meeting_id);
 
-            // Update tray icon to show recording status
-            if let Some(tray) = app.tray_by_id("main") {
-                let _ = tray.set_tooltip(Some("Meet Scribe - Recording..."));
-            }
+            // Show elapsed recording time in the tray tooltip
+            crate::utils::tray::start_recording_ticker(
+                app.clone(),
+                &state.tray_ticker,
+                meeting.start_time,
+            )
+            .await;
 
             Ok(meeting_id)
         }
@@ -120,140 +200,318 @@ pub async fn start_meeting(
                 );
             }
 
-            Err(format!("Failed to start audio capture: {}", e))
+            Err(CommandError::from(e).context("Failed to start audio capture"))
         }
     }
 }
 
 /// Stop the current meeting and audio capture
+///
+/// The recording is saved in the background after this returns. `meeting-audio-ready`
+/// is emitted once it can be transcribed, or `meeting-audio-failed`/`meeting-no-audio`
+/// if there is nothing to transcribe.
 #[tauri::command]
 pub async fn stop_meeting(
     app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     meeting_id: i64,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     log::info!("Stopping meeting ID: {}", meeting_id);
 
     // Stop audio capture and save audio file in background
-    let audio_capture_arc = Arc::clone(&state.audio_capture);
-    let storage_arc = Arc::clone(&state.storage);
-    let app_clone = app.clone();
-
-    tokio::spawn(async move {
-        // Get the audio buffer BEFORE releasing the mutex
-        // This ensures we extract the data while holding the lock, then release it
-        // before doing slow file I/O operations
-        let buffer_result = {
-            let mut audio_capture = audio_capture_arc.lock().await;
-
-            // Stop capture
-            if let Err(e) = audio_capture.stop_capture().await {
-                log::error!("Failed to stop audio capture: {}", e);
-                return;
-            }
+    tokio::spawn(save_recording(
+        app.clone(),
+        Arc::clone(&state.audio_capture),
+        Arc::clone(&state.storage),
+        meeting_id,
+    ));
+
+    end_meeting(&app, &state, meeting_id).await
+}
 
-            // Get audio buffer - this is quick, just moving data
-            audio_capture.get_audio_buffer().await
-        }; // Mutex is released here, before slow file operations
-
-        // Now perform slow file I/O operations without holding the mutex
-        match buffer_result {
-            Ok(Some(buffer)) => {
-                // Get app data directory for secure storage
-                let app_data_dir = match app_clone.path().app_data_dir() {
-                    Ok(dir) => dir,
-                    Err(e) => {
-                        log::error!("Failed to get app data directory: {}", e);
-                        return;
-                    }
-                };
+/// Clear the current meeting and record its end time
+async fn end_meeting(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    meeting_id: i64,
+) -> CommandResult<()> {
+    // Clear current meeting ID
+    *state.current_meeting_id.lock().await = None;
+
+    // Stop the elapsed-time ticker and show idle status
+    crate::utils::tray::stop_recording_ticker(app, &state.tray_ticker).await;
+
+    // Only the end time is written: the recording may be saved at the same time
+    state
+        .storage
+        .set_meeting_end_time(meeting_id, chrono::Utc::now().timestamp())
+        .await
+        .map_err(CommandError::from)?;
+
+    log::info!("Meeting {} stopped", meeting_id);
+    Ok(())
+}
+
+/// Stop audio capture and save the recording for a meeting
+///
+/// Slow file I/O happens after the capture lock is released.
+async fn save_recording(
+    app: tauri::AppHandle,
+    audio_capture_arc: Arc<Mutex<crate::AudioCapture>>,
+    storage_arc: Arc<SqliteStorage>,
+    meeting_id: i64,
+) {
+    // Get the audio buffer BEFORE releasing the mutex
+    // This ensures we extract the data while holding the lock, then release it
+    // before doing slow file I/O operations
+    let buffer_result = {
+        let mut audio_capture = audio_capture_arc.lock().await;
+
+        // Stop capture
+        if let Err(e) = audio_capture.stop_capture().await {
+            log::error!("Failed to stop audio capture: {}", e);
+            emit_audio_failed(&app, meeting_id, &e);
+            return;
+        }
 
-                // Create audio recordings subdirectory with restricted permissions
-                let audio_dir = app_data_dir.join("recordings");
-                if let Err(e) = std::fs::create_dir_all(&audio_dir) {
-                    log::error!("Failed to create recordings directory: {}", e);
-                    return;
+        // Get audio buffer - this is quick, just moving data
+        audio_capture.get_audio_buffer().await
+    }; // Mutex is released here, before slow file operations
+
+    // Now perform slow file I/O operations without holding the mutex
+    match buffer_result {
+        Ok(Some(buffer)) if recording_status(Some(&buffer)) == RecordingStatus::Recorded => {
+            let saved = match app.path().app_data_dir() {
+                Ok(app_data_dir) => {
+                    store_recording(
+                        storage_arc.as_ref(),
+                        &app_data_dir.join("recordings"),
+                        meeting_id,
+                        buffer,
+                    )
+                    .await
                 }
+                Err(e) => Err(AppError::Other(format!(
+                    "Failed to get app data directory: {}",
+                    e
+                ))),
+            };
 
-                // Save audio file with meeting ID for uniqueness
-                let audio_file = audio_dir.join(format!("meeting_{}.wav", meeting_id));
-
-                // File I/O happens here - potentially slow, but mutex is NOT held
-                match crate::utils::audio_file::save_wav_file(&buffer, &audio_file) {
-                    Ok(samples_written) => {
-                        log::info!(
-                            "Saved {} samples to secure location: {}",
-                            samples_written,
-                            audio_file.display()
-                        );
-
-                        // Store audio file path in database
-                        let file_path_str = audio_file.to_string_lossy().to_string();
-                        match storage_arc.get_meeting(meeting_id).await {
-                            Ok(Some(mut meeting)) => {
-                                meeting.audio_file_path = Some(file_path_str);
-                                if let Err(e) = storage_arc.update_meeting(&meeting).await {
-                                    log::error!(
-                                        "Failed to update meeting with audio file path: {}",
-                                        e
-                                    );
-                                }
-                            }
-                            Ok(None) => {
-                                log::error!("Meeting {} not found", meeting_id);
-                            }
-                            Err(e) => {
-                                log::error!("Failed to get meeting: {}", e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        log::error!("Failed to save audio file: {}", e);
+            match saved {
+                Ok(path) => {
+                    let payload = MeetingAudioReady { meeting_id, path };
+                    if let Err(e) = app.emit_to("main", MEETING_AUDIO_READY_EVENT, payload) {
+                        log::warn!("Failed to emit {} event: {}", MEETING_AUDIO_READY_EVENT, e);
                     }
                 }
+                Err(e) => {
+                    log::error!("Failed to save recording for meeting {}: {}", meeting_id, e);
+                    emit_audio_failed(&app, meeting_id, &e);
+                }
             }
-            Ok(None) => {
-                log::warn!("No audio buffer to save");
+        }
+        Ok(buffer) => {
+            log::warn!(
+                "Meeting {} captured {} ms of audio, not saving a recording",
+                meeting_id,
+                buffer.as_ref().map_or(0, buffer_duration_ms)
+            );
+            if let Err(e) = mark_no_audio(storage_arc.as_ref(), meeting_id).await {
+                log::error!(
+                    "Failed to mark meeting {} as having no audio: {}",
+                    meeting_id,
+                    e
+                );
             }
-            Err(e) => {
-                log::error!("Failed to get audio buffer: {}", e);
+
+            let payload = MeetingNoAudio {
+                meeting_id,
+                message: "No audio was recorded for this meeting, so there is nothing to transcribe. Check your audio devices and record for at least a second.".to_string(),
+            };
+            if let Err(e) = app.emit_to("main", MEETING_NO_AUDIO_EVENT, payload) {
+                log::warn!("Failed to emit {} event: {}", MEETING_NO_AUDIO_EVENT, e);
             }
         }
-    });
+        Err(e) => {
+            log::error!("Failed to get audio buffer: {}", e);
+            emit_audio_failed(&app, meeting_id, &e);
+        }
+    }
+}
 
-    // Clear current meeting ID
-    *state.current_meeting_id.lock().await = None;
+/// Tell the UI a stopped meeting's recording was lost
+fn emit_audio_failed(app: &tauri::AppHandle, meeting_id: i64, error: &AppError) {
+    let payload = MeetingAudioFailed {
+        meeting_id,
+        message: format!("The recording could not be saved: {}", error),
+    };
+    if let Err(e) = app.emit_to("main", MEETING_AUDIO_FAILED_EVENT, payload) {
+        log::warn!("Failed to emit {} event: {}", MEETING_AUDIO_FAILED_EVENT, e);
+    }
+}
 
-    // Update meeting end time
-    let mut meeting = state
-        .storage
-        .get_meeting(meeting_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+/// Process a captured buffer, write it to `audio_dir` and record the file on the meeting
+///
+/// Returns the saved file's path once the meeting row points at it.
+async fn store_recording(
+    storage: &dyn StoragePort,
+    audio_dir: &Path,
+    meeting_id: i64,
+    mut buffer: AudioBuffer,
+) -> crate::error::Result<String> {
+    let processing = recording_processing(storage).await;
+
+    // Drop dead air from before people joined and after they left. The
+    // leading amount is kept so transcript timestamps still line up with
+    // the start of the meeting.
+    let mut audio_offset_ms = 0;
+    if processing.trim_silence {
+        let trimmed = trim_silence(&mut buffer, &SilenceTrimConfig::default());
+        log::info!(
+            "Trimmed {} ms of leading and {} ms of trailing silence from meeting {}",
+            trimmed.leading_ms,
+            trimmed.trailing_ms,
+            meeting_id
+        );
+        audio_offset_ms = trimmed.leading_ms;
+    }
 
-    meeting.end();
+    // Bring quiet recordings up to a level the ASR services handle well
+    let mut audio_normalized = false;
+    if processing.normalize_audio {
+        match normalize_loudness(&mut buffer, &NormalizeConfig::default()) {
+            Some(gain) => {
+                log::info!(
+                    "Normalized meeting {} audio with gain {:.2}",
+                    meeting_id,
+                    gain
+                );
+                audio_normalized = true;
+            }
+            None => log::debug!("Meeting {} audio needs no normalization", meeting_id),
+        }
+    }
 
-    state
-        .storage
-        .update_meeting(&meeting)
-        .await
-        .map_err(|e| e.to_string())?;
+    // Create audio recordings subdirectory with restricted permissions
+    std::fs::create_dir_all(audio_dir)?;
+
+    // Save audio file with meeting ID for uniqueness
+    let audio_file = audio_dir.join(format!(
+        "meeting_{}.{}",
+        meeting_id,
+        processing.format.extension()
+    ));
+    // Either the native capture format, at full quality, or the smaller format
+    // ASR services need
+    let target_format = if processing.optimize_for_transcription {
+        log::info!(
+            "Saving meeting {} audio as {} instead of {}",
+            meeting_id,
+            TRANSCRIPTION_WAV_FORMAT,
+            buffer.format
+        );
+        TRANSCRIPTION_WAV_FORMAT
+    } else {
+        buffer.format.clone()
+    };
+    let samples_written = match processing.format {
+        RecordingFormat::Opus => save_opus_file_as(&buffer, &audio_file, &target_format)?,
+        RecordingFormat::Wav if processing.optimize_for_transcription => {
+            save_wav_file_as(&buffer, &audio_file, &target_format)?
+        }
+        RecordingFormat::Wav => save_wav_file(&buffer, &audio_file)?,
+    };
+    // What the file holds: WAV is always 16-bit PCM, and Opus only supports some formats
+    let written_format = match processing.format {
+        RecordingFormat::Opus => opus_format(&target_format),
+        RecordingFormat::Wav => AudioFormat {
+            bits_per_sample: 16,
+            ..target_format
+        },
+    };
+    log::info!(
+        "Saved {} samples to secure location: {}",
+        samples_written,
+        audio_file.display()
+    );
 
-    // Update tray icon to show idle status
-    if let Some(tray) = app.tray_by_id("main") {
-        let _ = tray.set_tooltip(Some("Meet Scribe - Idle"));
+    // Store audio file path in database; `end_meeting` may be writing the same row
+    let file_path_str = audio_file.to_string_lossy().to_string();
+    storage
+        .set_meeting_recording(
+            meeting_id,
+            &SavedRecording {
+                audio_file_path: file_path_str.clone(),
+                audio_offset_ms,
+                audio_normalized,
+                sample_rate: written_format.sample_rate,
+                channels: written_format.channels,
+                bits_per_sample: written_format.bits_per_sample,
+            },
+        )
+        .await?;
+
+    Ok(file_path_str)
+}
+
+/// What `stop_all` shut down
+#[derive(Debug, Default, Serialize)]
+pub struct StopAllResult {
+    /// Meeting that was recording, if any
+    pub meeting_id: Option<i64>,
+    /// Whether a streaming transcription session was closed
+    pub streaming_closed: bool,
+}
+
+/// Stop everything that is running: streaming, the active meeting and audio capture
+///
+/// Unlike `stop_meeting`, the recording is saved before this returns, so it is safe
+/// to exit right after. Does nothing when nothing is active.
+#[tauri::command]
+pub async fn stop_all(app: tauri::AppHandle) -> CommandResult<StopAllResult> {
+    shutdown(&app).await
+}
+
+/// Shared by the `stop_all` command, the tray's Quit item and app exit
+pub async fn shutdown(app: &tauri::AppHandle) -> CommandResult<StopAllResult> {
+    let state = app.state::<AppState>();
+    let streaming_state = app.state::<StreamingTranscriptionState>();
+    let mut result = StopAllResult::default();
+
+    // Close streaming first so the final audio is transcribed before capture stops
+    match close_active_session(&streaming_state).await {
+        Ok(closed) => result.streaming_closed = closed,
+        Err(e) => log::error!("Failed to close streaming session: {}", e),
     }
 
-    log::info!("Meeting {} stopped", meeting_id);
-    Ok(())
+    let current_meeting_id = *state.current_meeting_id.lock().await;
+    if let Some(meeting_id) = current_meeting_id {
+        log::info!("Stopping meeting {} before shutdown", meeting_id);
+        save_recording(
+            app.clone(),
+            Arc::clone(&state.audio_capture),
+            Arc::clone(&state.storage),
+            meeting_id,
+        )
+        .await;
+        end_meeting(app, &state, meeting_id).await?;
+        result.meeting_id = Some(meeting_id);
+    } else {
+        // An audio device test may still be capturing
+        let mut audio_capture = state.audio_capture.lock().await;
+        if audio_capture.is_capturing() {
+            if let Err(e) = audio_capture.stop_capture().await {
+                log::error!("Failed to stop audio capture: {}", e);
+            }
+        }
+    }
+
+    Ok(result)
 }
 
 /// Get current meeting status
 #[tauri::command]
-pub async fn get_meeting_status(
-    state: tauri::State<'_, AppState>,
-) -> Result<MeetingStatus, String> {
+pub async fn get_meeting_status(state: tauri::State<'_, AppState>) -> CommandResult<MeetingStatus> {
     let current_meeting_id = *state.current_meeting_id.lock().await;
 
     if let Some(meeting_id) = current_meeting_id {
@@ -262,7 +520,7 @@ pub async fn get_meeting_status(
             .storage
             .get_meeting(meeting_id)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(CommandError::from)?;
 
         if let Some(meeting) = meeting {
             // Calculate duration
@@ -306,7 +564,7 @@ pub async fn get_meeting_status(
 #[tauri::command]
 pub async fn get_audio_capture_status(
     state: tauri::State<'_, AppState>,
-) -> Result<AudioCaptureStatus, String> {
+) -> CommandResult<AudioCaptureStatus> {
     let audio_capture = state.audio_capture.lock().await;
     let is_capturing = audio_capture.is_capturing();
     let format = audio_capture.get_format();
@@ -328,36 +586,85 @@ pub async fn get_audio_capture_status(
 
 /// List available audio devices (deprecated - use list_speaker_devices and list_microphone_devices)
 #[tauri::command]
-pub async fn list_audio_devices(state: tauri::State<'_, AppState>) -> Result<Vec<String>, String> {
+pub async fn list_audio_devices(state: tauri::State<'_, AppState>) -> CommandResult<Vec<String>> {
     let audio_capture = state.audio_capture.lock().await;
     audio_capture
         .list_devices()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// List available speaker devices
 #[tauri::command]
-pub async fn list_speaker_devices(
-    state: tauri::State<'_, AppState>,
-) -> Result<Vec<String>, String> {
+pub async fn list_speaker_devices(state: tauri::State<'_, AppState>) -> CommandResult<Vec<String>> {
     let audio_capture = state.audio_capture.lock().await;
     audio_capture
         .list_speaker_devices()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// List available microphone devices
 #[tauri::command]
 pub async fn list_microphone_devices(
     state: tauri::State<'_, AppState>,
-) -> Result<Vec<String>, String> {
+) -> CommandResult<Vec<String>> {
     let audio_capture = state.audio_capture.lock().await;
     audio_capture
         .list_microphone_devices()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+/// List speaker and microphone devices with their stable device IDs
+///
+/// The ID (or "default") can be passed as `speaker_device` / `microphone_device` when
+/// starting a meeting, and keeps selecting the same device when others are plugged in.
+#[tauri::command]
+pub async fn list_audio_devices_detailed(
+    state: tauri::State<'_, AppState>,
+) -> CommandResult<Vec<AudioDeviceInfo>> {
+    let audio_capture = state.audio_capture.lock().await;
+    audio_capture
+        .list_devices_detailed()
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Re-enumerate the audio devices and tell every listener about the new list
+///
+/// Devices plugged in since the settings were opened show up without reopening
+/// them; the list is also sent as a `devices-changed` event so other views
+/// showing devices update too.
+#[tauri::command]
+pub async fn refresh_audio_devices(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> CommandResult<Vec<AudioDeviceInfo>> {
+    let audio_capture = state.audio_capture.lock().await;
+    refresh_devices(&*audio_capture, &|payload| {
+        if let Err(e) = app.emit_to("main", DEVICES_CHANGED_EVENT, payload) {
+            log::warn!("Failed to emit {} event: {}", DEVICES_CHANGED_EVENT, e);
+        }
+    })
+    .await
+}
+
+/// List the devices `audio_capture` sees now and pass them to `emit`
+async fn refresh_devices(
+    audio_capture: &dyn AudioCapturePort,
+    emit: &(dyn Fn(DevicesChanged) + Send + Sync),
+) -> CommandResult<Vec<AudioDeviceInfo>> {
+    let devices = audio_capture
+        .list_devices_detailed()
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to list audio devices"))?;
+    log::info!("Refreshed audio devices: {} found", devices.len());
+
+    emit(DevicesChanged {
+        devices: devices.clone(),
+    });
+    Ok(devices)
 }
 
 /// Get meeting history
@@ -365,12 +672,12 @@ pub async fn list_microphone_devices(
 pub async fn get_meeting_history(
     state: tauri::State<'_, AppState>,
     limit: Option<i64>,
-) -> Result<Vec<Meeting>, String> {
+) -> CommandResult<Vec<Meeting>> {
     let meetings = state
         .storage
         .list_meetings(Some(limit.unwrap_or(50) as i32), Some(0))
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     Ok(meetings)
 }
@@ -380,55 +687,457 @@ pub async fn get_meeting_history(
 pub async fn get_meeting(
     state: tauri::State<'_, AppState>,
     meeting_id: i64,
-) -> Result<Meeting, String> {
+) -> CommandResult<Meeting> {
     state
         .storage
         .get_meeting(meeting_id)
         .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))
+        .map_err(CommandError::from)?
+        .ok_or_else(|| CommandError::not_found(format!("Meeting not found: {}", meeting_id)))
 }
 
-/// Delete a meeting
+/// Format and length of a meeting's recording, for playback
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AudioInfo {
+    pub path: String,
+    /// Container format, "wav", "flac" or "opus"
+    pub format: String,
+    /// None when the header doesn't record the length
+    pub duration_ms: Option<i64>,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits_per_sample: u16,
+    pub file_size: u64,
+}
+
+/// Get the duration and format of a meeting's recording
+///
+/// Only the WAV or FLAC header is read, so this is cheap enough to call before
+/// loading a player. Opus files are scanned for their last page, which holds the
+/// length.
 #[tauri::command]
-pub async fn delete_meeting(
+pub async fn get_audio_info(
     state: tauri::State<'_, AppState>,
     meeting_id: i64,
-) -> Result<(), String> {
-    // First, get the meeting to retrieve the audio file path
+) -> CommandResult<AudioInfo> {
     let meeting = state
         .storage
         .get_meeting(meeting_id)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?
+        .ok_or_else(|| CommandError::not_found(format!("Meeting not found: {}", meeting_id)))?;
+    let audio_path = meeting.audio_file_path.ok_or_else(|| {
+        CommandError::not_found(format!("Meeting {} has no recording", meeting_id))
+    })?;
+
+    audio_info(Path::new(&audio_path)).map_err(CommandError::from)
+}
+
+/// Read the header of the WAV, FLAC or Opus file at `path`
+pub(crate) fn audio_info(path: &Path) -> crate::error::Result<AudioInfo> {
+    let file_size = std::fs::metadata(path)
+        .map_err(|_| AppError::NotFound(format!("Recording file not found: {}", path.display())))?
+        .len();
+
+    let mut magic = [0u8; 4];
+    std::fs::File::open(path)?
+        .read_exact(&mut magic)
+        .map_err(|_| {
+            AppError::InvalidInput(format!("Recording file is too short: {}", path.display()))
+        })?;
+
+    let (format, duration_ms, sample_rate, channels, bits_per_sample) = match &magic {
+        b"RIFF" => {
+            let header = read_wav_file_header(path)?;
+            (
+                "wav",
+                header.duration_ms(),
+                header.sample_rate,
+                header.channels,
+                header.bits_per_sample,
+            )
+        }
+        b"fLaC" => {
+            let info = read_flac_file_header(path)?;
+            (
+                "flac",
+                info.duration_ms(),
+                info.sample_rate,
+                u16::from(info.channels),
+                u16::from(info.bits_per_sample),
+            )
+        }
+        b"OggS" => {
+            let info = read_opus_file_info(path)?;
+            (
+                "opus",
+                info.duration_ms(),
+                info.input_sample_rate,
+                u16::from(info.channels),
+                // Opus has no bit depth; this is what decoders produce
+                16,
+            )
+        }
+        _ => {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported audio format (expected WAV, FLAC or Opus): {}",
+                path.display()
+            )))
+        }
+    };
+
+    Ok(AudioInfo {
+        path: path.to_string_lossy().into_owned(),
+        format: format.to_string(),
+        duration_ms,
+        sample_rate,
+        channels,
+        bits_per_sample,
+        file_size,
+    })
+}
+
+/// Set the user-authored notes of a meeting
+///
+/// Blank notes clear the field.
+#[tauri::command]
+pub async fn update_meeting_notes(
+    state: tauri::State<'_, AppState>,
+    meeting_id: i64,
+    notes: Option<String>,
+) -> CommandResult<()> {
+    let notes = notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|notes| !notes.is_empty());
+
+    state
+        .storage
+        .update_meeting_notes(meeting_id, notes)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to update meeting notes"))
+}
+
+/// Get the ASR/LLM provider and model chosen for a meeting, if any
+#[tauri::command]
+pub async fn get_meeting_model_override(
+    state: tauri::State<'_, AppState>,
+    meeting_id: i64,
+) -> CommandResult<Option<MeetingModelOverride>> {
+    state
+        .storage
+        .get_meeting_model_override(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get model override"))
+}
+
+/// Choose the ASR/LLM provider and model for a single meeting
+///
+/// Set fields take precedence over the active service configs when the meeting is
+/// transcribed or summarized. Blank fields fall back to the active config; clearing
+/// every field removes the override.
+#[tauri::command]
+pub async fn set_meeting_model_override(
+    state: tauri::State<'_, AppState>,
+    meeting_id: i64,
+    asr_provider: Option<String>,
+    asr_model: Option<String>,
+    llm_provider: Option<String>,
+    llm_model: Option<String>,
+) -> CommandResult<()> {
+    let clean = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let model_override = MeetingModelOverride {
+        meeting_id,
+        asr_provider: clean(asr_provider),
+        asr_model: clean(asr_model),
+        llm_provider: clean(llm_provider),
+        llm_model: clean(llm_model),
+    };
+
+    state
+        .storage
+        .set_meeting_model_override(&model_override)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to set model override"))
+}
+
+/// Import an audio file recorded elsewhere as a finished meeting
+///
+/// The file is copied into the recordings directory, so the meeting can be transcribed
+/// and summarized like a captured one. No audio capture is involved.
+///
+/// # Arguments
+/// * `path` - WAV file to import (other formats are rejected)
+/// * `platform` - Platform name, e.g. "zoom" or "in-person"
+/// * `title` - Optional meeting title
+#[tauri::command]
+pub async fn import_meeting(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    platform: String,
+    title: Option<String>,
+) -> CommandResult<Meeting> {
+    let platform = parse_platform(&platform)?;
+    let title = title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+
+    let recordings_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| {
+            CommandError::from(AppError::Other(e.to_string()))
+                .context("Failed to get app data directory")
+        })?
+        .join("recordings");
+
+    let meeting = import_audio_file(
+        state.storage.as_ref(),
+        Path::new(&path),
+        &recordings_dir,
+        platform,
+        title,
+    )
+    .await
+    .map_err(|e| CommandError::from(e).context("Failed to import meeting"))?;
+
+    log::info!("Imported {} as meeting {:?}", path, meeting.id);
+    Ok(meeting)
+}
 
-    // If meeting exists and has an audio file, delete it
-    if let Some(meeting) = meeting {
-        if let Some(audio_file_path) = meeting.audio_file_path {
-            // Delete the audio file from disk
-            if let Err(e) = std::fs::remove_file(&audio_file_path) {
+/// Parse a platform name; unrecognized names (e.g. "webex", "in-person") are kept as `Other`
+fn parse_platform(name: &str) -> CommandResult<Platform> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return Err(CommandError::invalid_input("Platform is required"));
+    }
+    Ok(Platform::from(name))
+}
+
+/// Create a finished meeting from an audio file, copying it to `recordings_dir`
+///
+/// The meeting ends at the file's modification time (when the recording was last
+/// written) and starts one recording length earlier.
+async fn import_audio_file(
+    storage: &dyn StoragePort,
+    source: &Path,
+    recordings_dir: &Path,
+    platform: Platform,
+    title: Option<String>,
+) -> crate::error::Result<Meeting> {
+    if !source.is_file() {
+        return Err(AppError::NotFound(format!(
+            "Audio file not found: {}",
+            source.display()
+        )));
+    }
+    let header = read_wav_file_header(source).map_err(|e| {
+        AppError::InvalidInput(format!(
+            "Only WAV recordings can be imported ({}): {}",
+            source.display(),
+            e
+        ))
+    })?;
+
+    let end_time = std::fs::metadata(source)?
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|since_epoch| since_epoch.as_secs() as i64)
+        .unwrap_or_else(|| chrono::Utc::now().timestamp());
+    let duration_secs = header.duration_ms().unwrap_or(0) / 1000;
+
+    // Language is left to the ASR provider's auto-detection
+    let mut meeting = Meeting::new(platform, title, None);
+    meeting.start_time = end_time - duration_secs;
+    meeting.end_time = Some(end_time);
+    let meeting_id = storage.create_meeting(&meeting).await?;
+    meeting.id = Some(meeting_id);
+
+    let destination = recordings_dir.join(format!("meeting_{}.wav", meeting_id));
+    let copied = match tokio::fs::create_dir_all(recordings_dir).await {
+        Ok(()) => tokio::fs::copy(source, &destination).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = copied {
+        // Don't leave a meeting behind without its recording
+        let _ = tokio::fs::remove_file(&destination).await;
+        storage.delete_meeting(meeting_id).await?;
+        return Err(e.into());
+    }
+
+    meeting.audio_file_path = Some(destination.to_string_lossy().into_owned());
+    meeting.recording_status = Some(RecordingStatus::Recorded);
+    meeting.audio_sample_rate = Some(header.sample_rate);
+    meeting.audio_channels = Some(header.channels);
+    meeting.audio_bits_per_sample = Some(header.bits_per_sample);
+    storage.update_meeting(&meeting).await?;
+    Ok(meeting)
+}
+
+/// Delete a meeting
+///
+/// Removes the meeting's recording from disk before deleting the row; transcripts,
+/// participants and insights are removed with the row by `ON DELETE CASCADE`.
+#[tauri::command]
+pub async fn delete_meeting(
+    state: tauri::State<'_, AppState>,
+    meeting_id: i64,
+) -> CommandResult<()> {
+    delete_meeting_with_audio(state.storage.as_ref(), meeting_id).await?;
+    Ok(())
+}
+
+/// Optional processing applied to a recording before it is saved
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RecordingProcessing {
+    trim_silence: bool,
+    normalize_audio: bool,
+    /// Save 16 kHz mono instead of the capture format, for smaller uploads
+    optimize_for_transcription: bool,
+    /// File format the recording is saved in
+    format: RecordingFormat,
+}
+
+/// Settings JSON of the active ASR service config, which also holds recording options
+async fn active_asr_settings(storage: &dyn StoragePort) -> Option<serde_json::Value> {
+    match storage.get_active_service_config("asr").await {
+        Ok(config) => config
+            .and_then(|c| c.settings)
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok()),
+        Err(e) => {
+            log::warn!("Failed to read ASR service config: {}", e);
+            None
+        }
+    }
+}
+
+/// Read the recording processing flags from the active ASR service config
+///
+/// All are off unless the settings JSON sets `trim_silence` / `normalize_audio` /
+/// `optimize_for_transcription` to true. `recording_format` is "wav" (the default)
+/// or "opus".
+async fn recording_processing(storage: &dyn StoragePort) -> RecordingProcessing {
+    let Some(settings) = active_asr_settings(storage).await else {
+        return RecordingProcessing::default();
+    };
+
+    let flag = |key: &str| settings.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+    RecordingProcessing {
+        trim_silence: flag("trim_silence"),
+        normalize_audio: flag("normalize_audio"),
+        optimize_for_transcription: flag("optimize_for_transcription"),
+        format: match settings.get("recording_format").and_then(|v| v.as_str()) {
+            Some(value) => RecordingFormat::from_setting(value).unwrap_or_else(|| {
+                log::warn!("Unknown recording_format {:?}, saving as WAV", value);
+                RecordingFormat::Wav
+            }),
+            None => RecordingFormat::Wav,
+        },
+    }
+}
+
+/// Length of a captured buffer in milliseconds
+fn buffer_duration_ms(buffer: &AudioBuffer) -> u64 {
+    let frame_rate = buffer.format.sample_rate as u64 * buffer.format.channels.max(1) as u64;
+    if frame_rate == 0 {
+        return 0;
+    }
+    buffer.samples.len() as u64 * 1000 / frame_rate
+}
+
+/// Whether a stopped capture is worth saving, given the buffer it produced (if any)
+fn recording_status(buffer: Option<&AudioBuffer>) -> RecordingStatus {
+    match buffer {
+        Some(buffer) if buffer_duration_ms(buffer) >= MIN_RECORDING_DURATION_MS => {
+            RecordingStatus::Recorded
+        }
+        _ => RecordingStatus::NoAudio,
+    }
+}
+
+/// Record that a meeting has no recording to transcribe
+async fn mark_no_audio(storage: &dyn StoragePort, meeting_id: i64) -> crate::error::Result<()> {
+    storage
+        .set_recording_status(meeting_id, RecordingStatus::NoAudio)
+        .await
+}
+
+/// Read the capture buffer and polling interval from the active ASR service config
+///
+/// Out-of-range values are logged and the defaults used, so a bad setting can't
+/// prevent a meeting from being recorded.
+async fn capture_timing(storage: &dyn StoragePort) -> CaptureTiming {
+    let Some(settings) = active_asr_settings(storage).await else {
+        return CaptureTiming::default();
+    };
+    CaptureTiming::from_settings(&settings).unwrap_or_else(|e| {
+        log::warn!("Ignoring capture timing settings: {}", e);
+        CaptureTiming::default()
+    })
+}
+
+/// Format to capture a meeting in, so a live transcript needs no resampling
+///
+/// The streaming format when the active ASR service can stream, otherwise None so the
+/// devices' own format is recorded.
+async fn streaming_capture_format(
+    storage: &dyn StoragePort,
+    keychain: &dyn KeychainPort,
+    factory: &dyn TranscriptionServiceFactory,
+) -> Option<AudioFormat> {
+    match get_active_asr_service(storage, keychain, factory).await {
+        Ok(service) => service
+            .supports_streaming()
+            .then_some(STREAMING_AUDIO_FORMAT),
+        Err(e) => {
+            log::debug!("No streaming format to capture in: {}", e);
+            None
+        }
+    }
+}
+
+/// Delete a meeting and its audio file, returning the bytes freed on disk
+pub(crate) async fn delete_meeting_with_audio(
+    storage: &dyn StoragePort,
+    meeting_id: i64,
+) -> crate::error::Result<u64> {
+    // First, get the meeting to retrieve the audio file path
+    let meeting = storage.get_meeting(meeting_id).await?;
+
+    let mut bytes_freed = 0;
+    if let Some(audio_file_path) = meeting.and_then(|m| m.audio_file_path) {
+        match crate::utils::audio_file::remove_audio_file(&audio_file_path) {
+            Ok(bytes) => {
+                bytes_freed = bytes;
+                log::info!(
+                    "Deleted audio file {} ({} bytes freed)",
+                    audio_file_path,
+                    bytes
+                );
+            }
+            Err(e) => {
+                // Continue with database deletion even if file deletion fails
                 log::warn!(
                     "Failed to delete audio file for meeting {}: {}. File path: {}",
                     meeting_id,
                     e,
                     audio_file_path
                 );
-                // Continue with database deletion even if file deletion fails
-            } else {
-                log::info!("Deleted audio file: {}", audio_file_path);
             }
         }
     }
 
     // Delete the meeting record from the database
-    state
-        .storage
-        .delete_meeting(meeting_id)
-        .await
-        .map_err(|e| e.to_string())?;
+    storage.delete_meeting(meeting_id).await?;
 
     log::info!("Deleted meeting: {}", meeting_id);
-    Ok(())
+    Ok(bytes_freed)
 }
 
 /// Test speaker capture (loopback)
@@ -439,7 +1148,7 @@ pub async fn delete_meeting(
 pub async fn test_speaker_capture(
     state: tauri::State<'_, AppState>,
     device_index: usize,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     log::info!("Testing speaker capture on device index: {}", device_index);
 
     // Get device list to find the device name
@@ -447,15 +1156,15 @@ pub async fn test_speaker_capture(
     let devices = audio_capture
         .list_speaker_devices()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     drop(audio_capture);
 
     if device_index >= devices.len() {
-        return Err(format!(
+        return Err(CommandError::invalid_input(format!(
             "Speaker device index {} out of range (total: {})",
             device_index,
             devices.len()
-        ));
+        )));
     }
 
     let device_name = devices[device_index].clone();
@@ -464,9 +1173,9 @@ pub async fn test_speaker_capture(
     // Start capture on speaker device only (loopback mode)
     let mut audio_capture = state.audio_capture.lock().await;
     audio_capture
-        .start_capture(Some(device_name))
+        .start_capture(Some(device_name), None)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     log::info!("Speaker capture test started successfully");
     Ok(())
@@ -480,7 +1189,7 @@ pub async fn test_speaker_capture(
 pub async fn test_microphone_capture(
     state: tauri::State<'_, AppState>,
     device_index: usize,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     log::info!(
         "Testing microphone capture on device index: {}",
         device_index
@@ -491,15 +1200,15 @@ pub async fn test_microphone_capture(
     let devices = audio_capture
         .list_microphone_devices()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     drop(audio_capture);
 
     if device_index >= devices.len() {
-        return Err(format!(
+        return Err(CommandError::invalid_input(format!(
             "Microphone device index {} out of range (total: {})",
             device_index,
             devices.len()
-        ));
+        )));
     }
 
     let device_name = devices[device_index].clone();
@@ -509,9 +1218,9 @@ pub async fn test_microphone_capture(
     // This will capture just the microphone
     let mut audio_capture = state.audio_capture.lock().await;
     audio_capture
-        .start_dual_capture(None, Some(device_name))
+        .start_dual_capture(None, Some(device_name), None, None)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     log::info!("Microphone capture test started successfully");
     Ok(())
@@ -521,14 +1230,14 @@ pub async fn test_microphone_capture(
 ///
 /// Stops any ongoing audio capture test.
 #[tauri::command]
-pub async fn stop_audio_test(state: tauri::State<'_, AppState>) -> Result<(), String> {
+pub async fn stop_audio_test(state: tauri::State<'_, AppState>) -> CommandResult<()> {
     log::info!("Stopping audio test");
 
     let mut audio_capture = state.audio_capture.lock().await;
     audio_capture
         .stop_capture()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     log::info!("Audio test stopped successfully");
     Ok(())
@@ -537,7 +1246,7 @@ pub async fn stop_audio_test(state: tauri::State<'_, AppState>) -> Result<(), St
 ///
 /// Returns the current audio level as a percentage (0-100)
 #[tauri::command]
-pub async fn get_current_audio_level(state: tauri::State<'_, AppState>) -> Result<f32, String> {
+pub async fn get_current_audio_level(state: tauri::State<'_, AppState>) -> CommandResult<f32> {
     let audio_capture = state.audio_capture.lock().await;
 
     if !audio_capture.is_capturing() {
@@ -560,7 +1269,7 @@ pub async fn get_current_audio_level(state: tauri::State<'_, AppState>) -> Resul
 /// This allows users to verify their speaker is working and at the correct volume
 #[cfg(target_os = "windows")]
 #[tauri::command]
-pub async fn play_test_tone(device_index: usize) -> Result<(), String> {
+pub async fn play_test_tone(device_index: usize) -> CommandResult<()> {
     use crate::adapters::audio::WasapiAudioCapture;
 
     log::info!(
@@ -578,7 +1287,7 @@ pub async fn play_test_tone(device_index: usize) -> Result<(), String> {
     // Play the tone through the selected speaker
     WasapiAudioCapture::play_audio(device_index, samples, sample_rate)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     log::info!("Test tone playback completed");
     Ok(())
@@ -587,6 +1296,461 @@ pub async fn play_test_tone(device_index: usize) -> Result<(), String> {
 /// Play test tone - Linux placeholder
 #[cfg(not(target_os = "windows"))]
 #[tauri::command]
-pub async fn play_test_tone(_device_index: usize) -> Result<(), String> {
-    Err("Test tone playback not yet implemented for this platform".to_string())
+pub async fn play_test_tone(_device_index: usize) -> CommandResult<()> {
+    Err(CommandError::unsupported(
+        "Test tone playback not yet implemented for this platform",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ServiceConfig, ServiceType};
+    use crate::ports::audio::DeviceKind;
+    use crate::ports::mocks::{MockAudioCapture, MockStorage, MockTranscriptionService};
+    use crate::ports::transcription::TranscriptionSegment;
+    use crate::utils::keychain::MockKeychain;
+    use tempfile::tempdir;
+
+    /// Write a silent 16 kHz mono WAV file of the given length
+    fn write_wav(path: &Path, seconds: u32) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for _ in 0..16000 * seconds {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_imported_meeting_is_transcribable() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("external recording.wav");
+        write_wav(&source, 3);
+        let recordings_dir = dir.path().join("recordings");
+
+        let storage = MockStorage::new();
+        let meeting = import_audio_file(
+            &storage,
+            &source,
+            &recordings_dir,
+            Platform::from("in-person".to_string()),
+            Some("Offsite".to_string()),
+        )
+        .await
+        .unwrap();
+
+        let meeting_id = meeting.id.unwrap();
+        let stored = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        let audio_path = stored.audio_file_path.clone().unwrap();
+        assert_eq!(
+            Path::new(&audio_path),
+            recordings_dir.join(format!("meeting_{}.wav", meeting_id))
+        );
+        assert!(source.exists(), "the original file is copied, not moved");
+        assert_eq!(stored.end_time.unwrap() - stored.start_time, 3);
+        assert_eq!(stored.language, None);
+
+        // Same path the transcription command takes for a captured meeting
+        let keychain = MockKeychain::new();
+        keychain.save_api_key("asr", "deepgram", "asr-key").unwrap();
+        let config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string()).with_active(true);
+        storage.save_service_config(&config).await.unwrap();
+        let asr = MockTranscriptionService::new(vec![TranscriptionSegment {
+            text: "imported meeting".to_string(),
+            start_ms: 0,
+            end_ms: 3000,
+            speaker_label: Some("Speaker 0".to_string()),
+            confidence: Some(0.9),
+            language: None,
+        }]);
+
+        let prepared = match crate::commands::transcription::prepare_transcription(
+            &storage, &keychain, &asr, meeting_id, None, None,
+        )
+        .await
+        {
+            Ok(prepared) => prepared,
+            Err(e) => panic!("imported meeting can't be transcribed: {}", e.message),
+        };
+        assert_eq!(prepared.audio_file_path, audio_path);
+        let stored_count = crate::commands::transcription::transcribe_and_store(
+            &storage,
+            prepared.asr_service.as_ref(),
+            meeting_id,
+            &prepared.audio_file_path,
+            &prepared.config,
+            prepared.audio_offset_ms,
+            false,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("transcribing the imported meeting failed"));
+        assert_eq!(stored_count, 1);
+        assert_eq!(*asr.transcribed_files.lock().unwrap(), vec![audio_path]);
+
+        let stored_transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(stored_transcripts.len(), 1);
+        assert_eq!(stored_transcripts[0].text, "imported meeting");
+        assert_eq!(stored_transcripts[0].end_timestamp_ms, Some(3000));
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_non_wav_file() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("notes.mp3");
+        std::fs::write(&source, b"ID3\x03\x00 not a wav file").unwrap();
+        let recordings_dir = dir.path().join("recordings");
+
+        let storage = MockStorage::new();
+        let error = import_audio_file(&storage, &source, &recordings_dir, Platform::Zoom, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::InvalidInput(_)));
+        assert!(storage.list_meetings(None, None).await.unwrap().is_empty());
+
+        let missing = dir.path().join("missing.wav");
+        assert!(matches!(
+            import_audio_file(&storage, &missing, &recordings_dir, Platform::Zoom, None).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_meeting_removes_audio_file() {
+        let dir = tempdir().unwrap();
+        let audio_path = dir.path().join("meeting_1.wav");
+        std::fs::write(&audio_path, [0u8; 256]).unwrap();
+
+        let storage = MockStorage::new();
+        let mut meeting = Meeting::new(Platform::Zoom, None, None);
+        meeting.audio_file_path = Some(audio_path.to_string_lossy().to_string());
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+
+        let bytes_freed = delete_meeting_with_audio(&storage, meeting_id)
+            .await
+            .unwrap();
+
+        assert_eq!(bytes_freed, 256);
+        assert!(!audio_path.exists());
+        assert!(storage.get_meeting(meeting_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_meeting_with_missing_audio_file() {
+        let dir = tempdir().unwrap();
+        let storage = MockStorage::new();
+        let mut meeting = Meeting::new(Platform::Teams, None, None);
+        meeting.audio_file_path = Some(dir.path().join("gone.wav").to_string_lossy().to_string());
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+
+        assert_eq!(
+            delete_meeting_with_audio(&storage, meeting_id)
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(storage.get_meeting(meeting_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recording_processing_from_asr_settings() {
+        let storage = MockStorage::new();
+        assert_eq!(
+            recording_processing(&storage).await,
+            RecordingProcessing::default()
+        );
+
+        let mut config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"model":"nova-2"}"#.to_string()));
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(
+            recording_processing(&storage).await,
+            RecordingProcessing::default()
+        );
+
+        config.settings = Some(r#"{"model":"nova-2","trim_silence":true}"#.to_string());
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(
+            recording_processing(&storage).await,
+            RecordingProcessing {
+                trim_silence: true,
+                normalize_audio: false,
+                optimize_for_transcription: false,
+                format: RecordingFormat::Wav,
+            }
+        );
+
+        config.settings = Some(r#"{"normalize_audio":true}"#.to_string());
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(
+            recording_processing(&storage).await,
+            RecordingProcessing {
+                trim_silence: false,
+                normalize_audio: true,
+                optimize_for_transcription: false,
+                format: RecordingFormat::Wav,
+            }
+        );
+
+        config.settings = Some(r#"{"optimize_for_transcription":true}"#.to_string());
+        storage.save_service_config(&config).await.unwrap();
+        assert!(
+            recording_processing(&storage)
+                .await
+                .optimize_for_transcription
+        );
+
+        config.settings = Some(r#"{"recording_format":"opus"}"#.to_string());
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(
+            recording_processing(&storage).await.format,
+            RecordingFormat::Opus
+        );
+
+        // An unknown format falls back to WAV
+        config.settings = Some(r#"{"recording_format":"mp3"}"#.to_string());
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(
+            recording_processing(&storage).await.format,
+            RecordingFormat::Wav
+        );
+    }
+
+    fn buffer(seconds: f32, sample_rate: u32, channels: u16) -> AudioBuffer {
+        let len = (seconds * sample_rate as f32) as usize * channels as usize;
+        AudioBuffer {
+            samples: vec![0.1; len],
+            format: crate::ports::audio::AudioFormat {
+                sample_rate,
+                channels,
+                bits_per_sample: 32,
+            },
+        }
+    }
+
+    #[test]
+    fn test_empty_or_short_capture_has_no_audio() {
+        assert_eq!(
+            recording_status(Some(&buffer(0.0, 48000, 2))),
+            RecordingStatus::NoAudio
+        );
+        assert_eq!(recording_status(None), RecordingStatus::NoAudio);
+        assert_eq!(
+            recording_status(Some(&buffer(0.5, 48000, 2))),
+            RecordingStatus::NoAudio
+        );
+        assert_eq!(
+            recording_status(Some(&buffer(1.0, 16000, 1))),
+            RecordingStatus::Recorded
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_recording_updates_meeting() {
+        let dir = tempdir().unwrap();
+        let storage = MockStorage::new();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let audio_dir = dir.path().join("recordings");
+        let path = store_recording(&storage, &audio_dir, meeting_id, buffer(1.0, 16000, 1))
+            .await
+            .unwrap();
+
+        assert!(Path::new(&path).exists());
+        let stored = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(stored.audio_file_path.as_deref(), Some(path.as_str()));
+        assert_eq!(stored.recording_status, Some(RecordingStatus::Recorded));
+        assert_eq!(stored.audio_sample_rate, Some(16000));
+        assert_eq!(stored.audio_channels, Some(1));
+        // Captured as 32-bit float, saved as 16-bit PCM
+        assert_eq!(stored.audio_bits_per_sample, Some(16));
+
+        assert!(matches!(
+            store_recording(&storage, &audio_dir, meeting_id + 1, buffer(1.0, 16000, 1)).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mark_no_audio() {
+        let storage = MockStorage::new();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        mark_no_audio(&storage, meeting_id).await.unwrap();
+        let stored = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(stored.recording_status, Some(RecordingStatus::NoAudio));
+
+        assert!(matches!(
+            mark_no_audio(&storage, meeting_id + 1).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_capture_timing_from_asr_settings() {
+        let storage = MockStorage::new();
+        assert_eq!(capture_timing(&storage).await, CaptureTiming::default());
+
+        let mut config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string())
+            .with_active(true)
+            .with_settings(Some(
+                r#"{"capture_buffer_ms":100,"capture_poll_ms":5}"#.to_string(),
+            ));
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(
+            capture_timing(&storage).await,
+            CaptureTiming::new(100, 5).unwrap()
+        );
+
+        // Out of bounds falls back to the defaults
+        config.settings = Some(r#"{"capture_buffer_ms":5}"#.to_string());
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(capture_timing(&storage).await, CaptureTiming::default());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_capture_format_follows_asr_service() {
+        let storage = MockStorage::new();
+        let keychain = MockKeychain::new();
+        let batch_only = MockTranscriptionService::new(vec![]);
+        let streaming = MockTranscriptionService::new(vec![]).with_streaming();
+
+        // No active ASR service
+        assert_eq!(
+            streaming_capture_format(&storage, &keychain, &streaming).await,
+            None
+        );
+
+        let config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string()).with_active(true);
+        storage.save_service_config(&config).await.unwrap();
+        keychain.save_api_key("asr", "deepgram", "asr-key").unwrap();
+        assert_eq!(
+            streaming_capture_format(&storage, &keychain, &streaming).await,
+            Some(STREAMING_AUDIO_FORMAT)
+        );
+        assert_eq!(
+            streaming_capture_format(&storage, &keychain, &batch_only).await,
+            None
+        );
+    }
+
+    #[test]
+    fn test_audio_info_reads_wav_header() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("meeting.wav");
+        write_wav(&path, 3);
+
+        let info = audio_info(&path).unwrap();
+        assert_eq!(info.format, "wav");
+        assert_eq!(info.duration_ms, Some(3000));
+        assert_eq!(info.sample_rate, 16000);
+        assert_eq!(info.channels, 1);
+        assert_eq!(info.bits_per_sample, 16);
+        assert_eq!(info.file_size, 44 + 3 * 16000 * 2);
+    }
+
+    #[test]
+    fn test_audio_info_errors() {
+        let dir = tempdir().unwrap();
+
+        let missing = dir.path().join("missing.wav");
+        let error = audio_info(&missing).unwrap_err();
+        assert!(matches!(&error, AppError::NotFound(m) if m.contains("missing.wav")));
+
+        let text = dir.path().join("notes.txt");
+        std::fs::write(&text, "not audio").unwrap();
+        assert!(matches!(audio_info(&text), Err(AppError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_devices_emits_current_list() {
+        let audio_capture = MockAudioCapture::new();
+        audio_capture.plug_in("default", "Default speaker", DeviceKind::Speaker);
+        let emitted = std::sync::Mutex::new(Vec::new());
+        let emit = |payload| emitted.lock().unwrap().push(payload);
+
+        let devices = refresh_devices(&audio_capture, &emit).await.unwrap();
+        assert_eq!(devices.len(), 1);
+
+        // A headset connected afterwards is picked up by the next refresh
+        audio_capture.plug_in("usb-headset", "USB Headset", DeviceKind::Microphone);
+        let devices = refresh_devices(&audio_capture, &emit).await.unwrap();
+
+        let emitted = emitted.into_inner().unwrap();
+        assert_eq!(emitted.len(), 2);
+        assert_eq!(emitted[1], DevicesChanged { devices });
+        assert_eq!(emitted[1].devices[1].id, "usb-headset");
+    }
+
+    #[tokio::test]
+    async fn test_store_recording_optimized_for_transcription() {
+        let dir = tempdir().unwrap();
+        let storage = MockStorage::new();
+        let config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"optimize_for_transcription":true}"#.to_string()));
+        storage.save_service_config(&config).await.unwrap();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let path = store_recording(&storage, dir.path(), meeting_id, buffer(2.0, 48000, 2))
+            .await
+            .unwrap();
+
+        let header = read_wav_file_header(Path::new(&path)).unwrap();
+        assert_eq!(header.sample_rate, 16000);
+        assert_eq!(header.channels, 1);
+        assert_eq!(header.duration_ms(), Some(2000));
+
+        // The meeting records the format that was written, not the capture format
+        let stored = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(stored.audio_sample_rate, Some(16000));
+        assert_eq!(stored.audio_channels, Some(1));
+        assert_eq!(stored.audio_bits_per_sample, Some(16));
+    }
+
+    #[tokio::test]
+    async fn test_store_recording_as_opus() {
+        let dir = tempdir().unwrap();
+        let storage = MockStorage::new();
+        let config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"recording_format":"opus"}"#.to_string()));
+        storage.save_service_config(&config).await.unwrap();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+
+        let path = store_recording(&storage, dir.path(), meeting_id, buffer(2.0, 48000, 2))
+            .await
+            .unwrap();
+        assert!(path.ends_with(&format!("meeting_{}.opus", meeting_id)));
+
+        let info = audio_info(Path::new(&path)).unwrap();
+        assert_eq!(info.format, "opus");
+        assert_eq!(info.duration_ms, Some(2000));
+        assert_eq!(info.sample_rate, 48000);
+        assert_eq!(info.channels, 2);
+        // Far smaller than the 384 kB the same audio takes as WAV
+        assert!(info.file_size < 48000 * 2 * 2 / 4);
+
+        let stored = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(stored.audio_sample_rate, Some(48000));
+        assert_eq!(stored.audio_channels, Some(2));
+    }
 }
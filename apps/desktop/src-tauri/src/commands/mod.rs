@@ -1,10 +1,13 @@
 /// Tauri IPC command modules
 pub mod config;
 pub mod detection;
+pub mod diagnostics;
 pub mod export;
 pub mod llm;
 pub mod meeting;
 pub mod participant;
+pub mod processing;
 pub mod search;
+pub mod storage;
 pub mod streaming;
 pub mod transcription;
@@ -1,5 +1,7 @@
-/// Participant management commands
-use crate::domain::models::Participant;
+//! Participant management commands
+
+use crate::domain::models::{Participant, Transcript};
+use crate::error::{CommandError, CommandResult};
 use crate::ports::storage::StoragePort;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
@@ -22,6 +24,20 @@ pub struct ParticipantInfo {
     pub email: Option<String>,
 }
 
+/// Talk-time analytics for a single speaker
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerTalkTime {
+    pub speaker_label: String,
+    pub participant_id: Option<i64>,
+    pub participant_name: Option<String>,
+    pub duration_ms: i64,
+    pub word_count: usize,
+    pub percentage: f64, // Share of the meeting's duration spent speaking (0-100)
+}
+
+/// Estimated speaking rate used when a segment's duration cannot be bounded (~150 wpm)
+const ESTIMATED_MS_PER_WORD: i64 = 400;
+
 /// Request to link a speaker to a participant
 #[derive(Debug, Deserialize)]
 pub struct LinkSpeakerRequest {
@@ -31,12 +47,19 @@ pub struct LinkSpeakerRequest {
     pub participant_email: Option<String>,
 }
 
+/// A known attendee, e.g. from a calendar invite
+#[derive(Debug, Deserialize)]
+pub struct RosterEntry {
+    pub name: String,
+    pub email: Option<String>,
+}
+
 /// Get summary of all speakers in a meeting with sample transcripts
 #[tauri::command]
 pub async fn get_speaker_summary(
     meeting_id: i64,
     state: State<'_, AppState>,
-) -> Result<Vec<SpeakerSummary>, String> {
+) -> CommandResult<Vec<SpeakerSummary>> {
     log::info!("Getting speaker summary for meeting {}", meeting_id);
 
     // Get all transcripts for the meeting
@@ -44,14 +67,14 @@ pub async fn get_speaker_summary(
         .storage
         .get_transcripts(meeting_id)
         .await
-        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to get transcripts"))?;
 
     // Get all participants for the meeting
     let participants = state
         .storage
         .get_participants(meeting_id)
         .await
-        .map_err(|e| format!("Failed to get participants: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to get participants"))?;
 
     // Group transcripts by speaker_label
     let mut speaker_map: std::collections::HashMap<String, Vec<String>> =
@@ -101,12 +124,120 @@ pub async fn get_speaker_summary(
     Ok(summaries)
 }
 
+/// Get talk-time analytics for each speaker in a meeting
+/// Returns speakers sorted by spoken duration, longest first
+#[tauri::command]
+pub async fn get_talk_time(
+    meeting_id: i64,
+    state: State<'_, AppState>,
+) -> CommandResult<Vec<SpeakerTalkTime>> {
+    log::info!("Computing talk time for meeting {}", meeting_id);
+
+    let meeting = state
+        .storage
+        .get_meeting(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get meeting"))?
+        .ok_or_else(|| CommandError::not_found(format!("Meeting {} not found", meeting_id)))?;
+
+    let transcripts = state
+        .storage
+        .get_transcripts(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get transcripts"))?;
+
+    let meeting_duration_ms = meeting
+        .end_time
+        .map(|end_time| (end_time - meeting.start_time) * 1000);
+
+    Ok(compute_talk_time(&transcripts, meeting_duration_ms))
+}
+
+/// Aggregate transcript segments into per-speaker talk time
+///
+/// Segments with a stored end timestamp use it directly. Older segments only have a start
+/// timestamp, so they are assumed to last until the next segment starts; the final one runs
+/// until the end of the meeting when known, otherwise its duration is estimated from its
+/// word count.
+///
+/// Percentages are of the meeting's duration, so silence counts against every speaker.
+/// Without a known duration the meeting is taken to end with its last segment.
+fn compute_talk_time(
+    transcripts: &[Transcript],
+    meeting_duration_ms: Option<i64>,
+) -> Vec<SpeakerTalkTime> {
+    let mut ordered: Vec<&Transcript> = transcripts.iter().collect();
+    ordered.sort_by_key(|t| t.timestamp_ms);
+
+    let mut stats: Vec<SpeakerTalkTime> = Vec::new();
+    let mut last_end_ms = 0;
+    for (index, transcript) in ordered.iter().enumerate() {
+        let word_count = transcript.text.split_whitespace().count();
+        let end_ms = match (transcript.end_timestamp_ms, ordered.get(index + 1)) {
+            (Some(end), _) => end,
+            (None, Some(next)) => next.timestamp_ms,
+            (None, None) => meeting_duration_ms
+                .filter(|&end| end > transcript.timestamp_ms)
+                .unwrap_or(transcript.timestamp_ms + word_count as i64 * ESTIMATED_MS_PER_WORD),
+        };
+        let duration_ms = (end_ms - transcript.timestamp_ms).max(0);
+        last_end_ms = last_end_ms.max(end_ms);
+
+        // Segments without a diarization label are attributed to their linked participant
+        let Some(speaker_label) = transcript
+            .speaker_label
+            .clone()
+            .or_else(|| transcript.participant_name.clone())
+        else {
+            continue;
+        };
+
+        let entry = match stats.iter().position(|s| s.speaker_label == speaker_label) {
+            Some(position) => &mut stats[position],
+            None => {
+                stats.push(SpeakerTalkTime {
+                    speaker_label,
+                    participant_id: None,
+                    participant_name: None,
+                    duration_ms: 0,
+                    word_count: 0,
+                    percentage: 0.0,
+                });
+                stats.last_mut().unwrap()
+            }
+        };
+        entry.duration_ms += duration_ms;
+        entry.word_count += word_count;
+        if entry.participant_id.is_none() {
+            entry.participant_id = transcript.participant_id;
+            entry.participant_name = transcript.participant_name.clone();
+        }
+    }
+
+    let meeting_ms = meeting_duration_ms
+        .filter(|&duration| duration > 0)
+        .unwrap_or(last_end_ms);
+    if meeting_ms > 0 {
+        for entry in stats.iter_mut() {
+            // Overlapping speech can add up to more than the meeting
+            entry.percentage = (entry.duration_ms as f64 / meeting_ms as f64 * 100.0).min(100.0);
+        }
+    }
+
+    stats.sort_by(|a, b| {
+        b.duration_ms
+            .cmp(&a.duration_ms)
+            .then_with(|| a.speaker_label.cmp(&b.speaker_label))
+    });
+    stats
+}
+
 /// Link a speaker label to a participant (create or update)
 #[tauri::command]
 pub async fn link_speaker_to_participant(
     request: LinkSpeakerRequest,
     state: State<'_, AppState>,
-) -> Result<i64, String> {
+) -> CommandResult<i64> {
     log::info!(
         "Linking speaker '{}' to participant '{}' for meeting {}",
         request.speaker_label,
@@ -119,7 +250,7 @@ pub async fn link_speaker_to_participant(
         .storage
         .get_participants(request.meeting_id)
         .await
-        .map_err(|e| format!("Failed to get participants: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to get participants"))?;
 
     let existing_participant = participants
         .iter()
@@ -135,7 +266,7 @@ pub async fn link_speaker_to_participant(
             .storage
             .update_participant(&updated)
             .await
-            .map_err(|e| format!("Failed to update participant: {}", e))?;
+            .map_err(|e| CommandError::from(e).context("Failed to update participant"))?;
 
         existing.id.unwrap_or(0)
     } else {
@@ -152,7 +283,7 @@ pub async fn link_speaker_to_participant(
             .storage
             .create_participant(&participant)
             .await
-            .map_err(|e| format!("Failed to create participant: {}", e))?
+            .map_err(|e| CommandError::from(e).context("Failed to create participant"))?
     };
 
     // Batch update all transcripts with this speaker_label to link to the participant
@@ -165,7 +296,7 @@ pub async fn link_speaker_to_participant(
             participant_id,
         )
         .await
-        .map_err(|e| format!("Failed to update transcripts: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to update transcripts"))?;
 
     log::info!(
         "Successfully linked {} transcripts to participant {} (ID: {}) for meeting {}",
@@ -178,13 +309,67 @@ pub async fn link_speaker_to_participant(
     Ok(participant_id)
 }
 
+/// Assign participant names to several speaker labels at once
+/// Each entry maps a speaker label (e.g. "Speaker 0") to a participant name. Re-running with
+/// the same mapping is a no-op. Returns the number of transcripts linked.
+#[tauri::command]
+pub async fn assign_speakers(
+    meeting_id: i64,
+    mapping: Vec<(String, String)>,
+    state: State<'_, AppState>,
+) -> CommandResult<usize> {
+    let mut assignments: Vec<(String, String)> = Vec::with_capacity(mapping.len());
+    for (speaker_label, participant_name) in mapping {
+        let speaker_label = speaker_label.trim().to_string();
+        let participant_name = participant_name.trim().to_string();
+
+        if speaker_label.is_empty() || participant_name.is_empty() {
+            return Err(CommandError::invalid_input(
+                "Speaker labels and participant names cannot be empty",
+            ));
+        }
+        if assignments.iter().any(|(label, _)| *label == speaker_label) {
+            return Err(CommandError::invalid_input(format!(
+                "Speaker '{}' is assigned more than once",
+                speaker_label
+            )));
+        }
+        assignments.push((speaker_label, participant_name));
+    }
+
+    if assignments.is_empty() {
+        return Ok(0);
+    }
+
+    log::info!(
+        "Assigning {} speakers for meeting {}",
+        assignments.len(),
+        meeting_id
+    );
+
+    let linked_count = state
+        .storage
+        .assign_speakers(meeting_id, &assignments)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to assign speakers"))?;
+
+    log::info!(
+        "Linked {} transcripts to {} participants for meeting {}",
+        linked_count,
+        assignments.len(),
+        meeting_id
+    );
+
+    Ok(linked_count)
+}
+
 /// Unlink a speaker from a participant (remove mapping)
 #[tauri::command]
 pub async fn unlink_speaker(
     meeting_id: i64,
     speaker_label: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     log::info!(
         "Unlinking speaker '{}' for meeting {}",
         speaker_label,
@@ -196,7 +381,7 @@ pub async fn unlink_speaker(
         .storage
         .get_transcripts(meeting_id)
         .await
-        .map_err(|e| format!("Failed to get transcripts: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to get transcripts"))?;
 
     let mut updated_count = 0;
     for mut transcript in transcripts {
@@ -206,7 +391,7 @@ pub async fn unlink_speaker(
                 .storage
                 .update_transcript(&transcript)
                 .await
-                .map_err(|e| format!("Failed to update transcript: {}", e))?;
+                .map_err(|e| CommandError::from(e).context("Failed to update transcript"))?;
             updated_count += 1;
         }
     }
@@ -216,7 +401,7 @@ pub async fn unlink_speaker(
         .storage
         .get_participants(meeting_id)
         .await
-        .map_err(|e| format!("Failed to get participants: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to get participants"))?;
 
     if let Some(participant) = participants
         .iter()
@@ -227,7 +412,7 @@ pub async fn unlink_speaker(
                 .storage
                 .delete_participant(id)
                 .await
-                .map_err(|e| format!("Failed to delete participant: {}", e))?;
+                .map_err(|e| CommandError::from(e).context("Failed to delete participant"))?;
         }
     }
 
@@ -240,13 +425,123 @@ pub async fn unlink_speaker(
     Ok(())
 }
 
+/// Merge two speaker labels that belong to the same person
+/// All transcripts labeled `from_label` are relabeled to `into_label`, and any participant
+/// link is carried over to the surviving label. Returns the number of transcripts relabeled.
+#[tauri::command]
+pub async fn merge_speakers(
+    meeting_id: i64,
+    from_label: String,
+    into_label: String,
+    state: State<'_, AppState>,
+) -> CommandResult<usize> {
+    let from_label = from_label.trim();
+    let into_label = into_label.trim();
+
+    if from_label.is_empty() || into_label.is_empty() {
+        return Err(CommandError::invalid_input(
+            "Speaker labels cannot be empty",
+        ));
+    }
+    if from_label == into_label {
+        return Err(CommandError::invalid_input(
+            "Cannot merge a speaker into itself",
+        ));
+    }
+
+    log::info!(
+        "Merging speaker '{}' into '{}' for meeting {}",
+        from_label,
+        into_label,
+        meeting_id
+    );
+
+    let merged_count = state
+        .storage
+        .merge_speaker_labels(meeting_id, from_label, into_label)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to merge speakers"))?;
+
+    log::info!(
+        "Merged {} transcripts from '{}' into '{}'",
+        merged_count,
+        from_label,
+        into_label
+    );
+
+    Ok(merged_count)
+}
+
+/// Set the roster of known attendees for a meeting
+/// Can be called before or after recording. Attendees are stored without a speaker label
+/// until a diarized speaker is assigned to them by name; participants already linked to a
+/// speaker are kept. Returns the meeting's participants after the update.
+#[tauri::command]
+pub async fn set_participants(
+    meeting_id: i64,
+    participants: Vec<RosterEntry>,
+    state: State<'_, AppState>,
+) -> CommandResult<Vec<Participant>> {
+    let mut roster: Vec<Participant> = Vec::with_capacity(participants.len());
+    for entry in participants {
+        let name = entry.name.trim().to_string();
+        if name.is_empty() {
+            return Err(CommandError::invalid_input(
+                "Participant names cannot be empty",
+            ));
+        }
+        let email = entry
+            .email
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty());
+        if let Some(email) = &email {
+            if roster.iter().any(|p| {
+                p.email
+                    .as_deref()
+                    .is_some_and(|e| e.eq_ignore_ascii_case(email))
+            }) {
+                return Err(CommandError::invalid_input(format!(
+                    "Email '{}' is listed more than once",
+                    email
+                )));
+            }
+        }
+        roster.push(Participant::new(meeting_id, name, email));
+    }
+
+    state
+        .storage
+        .get_meeting(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get meeting"))?
+        .ok_or_else(|| CommandError::not_found(format!("Meeting {} not found", meeting_id)))?;
+
+    log::info!(
+        "Setting {} participants for meeting {}",
+        roster.len(),
+        meeting_id
+    );
+
+    state
+        .storage
+        .set_participants(meeting_id, &roster)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to set participants"))?;
+
+    state
+        .storage
+        .get_participants(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get participants"))
+}
+
 /// Delete all participants for a meeting
 /// This is useful when regenerating transcripts to start fresh
 #[tauri::command]
 pub async fn delete_meeting_participants(
     meeting_id: i64,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     log::info!("Deleting all participants for meeting {}", meeting_id);
 
     // Get all participants for this meeting
@@ -254,18 +549,16 @@ pub async fn delete_meeting_participants(
         .storage
         .get_participants(meeting_id)
         .await
-        .map_err(|e| format!("Failed to get participants: {}", e))?;
+        .map_err(|e| CommandError::from(e).context("Failed to get participants"))?;
 
     let participant_count = participants.len();
 
     // Delete each participant
     for participant in participants {
         if let Some(id) = participant.id {
-            state
-                .storage
-                .delete_participant(id)
-                .await
-                .map_err(|e| format!("Failed to delete participant {}: {}", id, e))?;
+            state.storage.delete_participant(id).await.map_err(|e| {
+                CommandError::from(e).context(&format!("Failed to delete participant {}", id))
+            })?;
         }
     }
 
@@ -277,3 +570,96 @@ pub async fn delete_meeting_participants(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(timestamp_ms: i64, label: &str, text: &str) -> Transcript {
+        Transcript::with_speaker(
+            1,
+            timestamp_ms,
+            text.to_string(),
+            None,
+            Some(label.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_talk_time_estimates_from_next_segment() {
+        let transcripts = vec![
+            segment(0, "Speaker 0", "hello everyone"),
+            segment(4000, "Speaker 1", "hi"),
+            segment(5000, "Speaker 0", "let's get started then"),
+        ];
+
+        let stats = compute_talk_time(&transcripts, Some(10_000));
+        assert_eq!(stats.len(), 2);
+
+        assert_eq!(stats[0].speaker_label, "Speaker 0");
+        assert_eq!(stats[0].duration_ms, 9000);
+        assert_eq!(stats[0].word_count, 6);
+        assert!((stats[0].percentage - 90.0).abs() < 1e-9);
+
+        assert_eq!(stats[1].speaker_label, "Speaker 1");
+        assert_eq!(stats[1].duration_ms, 1000);
+        assert_eq!(stats[1].word_count, 1);
+        assert!((stats[1].percentage - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_talk_time_last_segment_without_meeting_end() {
+        let transcripts = vec![
+            segment(0, "Speaker 0", "short intro"),
+            segment(1000, "Speaker 1", "one two three four five"),
+        ];
+
+        let stats = compute_talk_time(&transcripts, None);
+        assert_eq!(stats[0].speaker_label, "Speaker 1");
+        assert_eq!(stats[0].duration_ms, 5 * ESTIMATED_MS_PER_WORD);
+        assert_eq!(stats[1].duration_ms, 1000);
+    }
+
+    #[test]
+    fn test_talk_time_uses_stored_end_timestamps() {
+        let mut first = segment(0, "Speaker 0", "hello everyone");
+        first.end_timestamp_ms = Some(1500);
+        let mut second = segment(4000, "Speaker 1", "hi");
+        second.end_timestamp_ms = Some(4500);
+        // Older row without an end time falls back to the meeting end
+        let third = segment(5000, "Speaker 0", "let's get started then");
+
+        let stats = compute_talk_time(&[first, second, third], Some(10_000));
+
+        assert_eq!(stats[0].speaker_label, "Speaker 0");
+        assert_eq!(stats[0].duration_ms, 1500 + 5000);
+        assert_eq!(stats[1].speaker_label, "Speaker 1");
+        assert_eq!(stats[1].duration_ms, 500);
+
+        // Silence between segments isn't anyone's share of the meeting
+        assert!((stats[0].percentage - 65.0).abs() < 1e-9);
+        assert!((stats[1].percentage - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_talk_time_percentage_of_meeting() {
+        let mut first = segment(0, "Speaker 0", "hello everyone");
+        first.end_timestamp_ms = Some(3000);
+        let mut second = segment(3000, "Speaker 1", "hi");
+        second.end_timestamp_ms = Some(4000);
+
+        let stats = compute_talk_time(&[first.clone(), second.clone()], Some(20_000));
+        assert!((stats[0].percentage - 15.0).abs() < 1e-9);
+        assert!((stats[1].percentage - 5.0).abs() < 1e-9);
+
+        // Without a meeting end, the meeting lasts until the last segment ends
+        let stats = compute_talk_time(&[first, second], None);
+        assert!((stats[0].percentage - 75.0).abs() < 1e-9);
+        assert!((stats[1].percentage - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_talk_time_empty() {
+        assert!(compute_talk_time(&[], Some(1000)).is_empty());
+    }
+}
@@ -0,0 +1,360 @@
+//! One-shot meeting processing
+//!
+//! Chains transcription and insight generation for a finished meeting, so the UI can
+//! run both with one command instead of starting transcription, waiting for it and
+//! then asking for insights.
+
+use crate::adapters::services::asr::ProviderAsrFactory;
+use crate::adapters::services::llm::ProviderLlmFactory;
+use crate::commands::llm::{
+    generate_and_store_insights, GenerateMeetingInsightsRequest, MeetingInsightsResponse,
+};
+use crate::commands::transcription::{
+    finish_transcription, prepare_transcription, run_cancellable, transcribe_and_store,
+    validate_num_speakers, TranscriptionFailure, TranscriptionGuard, TranscriptionState,
+};
+use crate::error::{CommandError, CommandResult};
+use crate::ports::llm::LlmServiceFactory;
+use crate::ports::storage::StoragePort;
+use crate::ports::transcription::{TranscriptionConfig, TranscriptionServiceFactory};
+use crate::utils::keychain::KeychainPort;
+use crate::utils::notification::notify;
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{Emitter, State};
+
+/// Event emitted as `process_meeting` starts, finishes or fails each stage
+const PROCESSING_PROGRESS_EVENT: &str = "meeting-processing-progress";
+
+/// A step of `process_meeting`, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingStage {
+    Transcription,
+    Insights,
+}
+
+impl std::fmt::Display for ProcessingStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ProcessingStage::Transcription => "Transcription",
+            ProcessingStage::Insights => "Insight generation",
+        })
+    }
+}
+
+/// Where a stage has got to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageStatus {
+    Started,
+    Completed,
+    Failed,
+}
+
+/// Payload of the `meeting-processing-progress` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProcessingProgress {
+    pub meeting_id: i64,
+    pub stage: ProcessingStage,
+    pub status: StageStatus,
+    /// Why the stage failed, set with [`StageStatus::Failed`]
+    pub message: Option<String>,
+}
+
+/// Where `process_meeting_with` reports what it is doing
+pub(crate) struct ProcessingReporter<'a> {
+    /// Receives each stage as it starts and ends
+    pub on_progress: &'a (dyn Fn(ProcessingProgress) + Send + Sync),
+    /// Shows the user a notification, given its title and body
+    pub notify: &'a (dyn Fn(&str, &str) + Send + Sync),
+}
+
+/// Transcribe a meeting's recording, then generate and store its insights
+///
+/// `meeting-processing-progress` events report each stage as it starts and ends.
+/// Insights are only requested once the transcript is stored and non-empty; an
+/// error's message starts with the stage that failed, and its code is the
+/// underlying error's. `llm_request.meeting_id` must match `meeting_id`.
+///
+/// The job can be stopped with `cancel_transcription`, which makes this return a
+/// `cancelled` error.
+#[tauri::command]
+pub async fn process_meeting(
+    app: tauri::AppHandle,
+    meeting_id: i64,
+    asr_config: Option<TranscriptionConfig>,
+    llm_request: GenerateMeetingInsightsRequest,
+    state: State<'_, TranscriptionState>,
+) -> CommandResult<MeetingInsightsResponse> {
+    log::info!("Processing meeting {}", meeting_id);
+
+    if llm_request.meeting_id != meeting_id {
+        return Err(CommandError::invalid_input(format!(
+            "Insights request is for meeting {} but meeting {} is being processed",
+            llm_request.meeting_id, meeting_id
+        )));
+    }
+    validate_num_speakers(asr_config.as_ref().and_then(|c| c.num_speakers))?;
+
+    // Held for both stages so the meeting isn't transcribed twice at once
+    let guard = TranscriptionGuard::claim(&state.active_transcriptions, meeting_id)?;
+
+    let storage = Arc::clone(&state.storage);
+    let keychain = Arc::clone(&state.keychain);
+    run_cancellable(guard, async move {
+        process_meeting_with(
+            storage.as_ref(),
+            keychain.as_ref(),
+            &ProviderAsrFactory,
+            &ProviderLlmFactory,
+            asr_config,
+            &llm_request,
+            &ProcessingReporter {
+                on_progress: &|progress| {
+                    if let Err(e) = app.emit_to("main", PROCESSING_PROGRESS_EVENT, progress) {
+                        log::warn!("Failed to emit {} event: {}", PROCESSING_PROGRESS_EVENT, e);
+                    }
+                },
+                notify: &|title, body| notify(&app, title, body),
+            },
+        )
+        .await
+    })
+    .await
+}
+
+/// Run both stages for `llm_request.meeting_id`, reporting progress to `reporter`
+///
+/// The transcript replaces any the meeting already has, so running it again doesn't
+/// duplicate segments. Once it is stored the meeting is finished like any other
+/// transcription: `reporter.notify` is told the outcome and an untitled meeting gets a title.
+pub(crate) async fn process_meeting_with(
+    storage: &dyn StoragePort,
+    keychain: &dyn KeychainPort,
+    asr_factory: &dyn TranscriptionServiceFactory,
+    llm_factory: &dyn LlmServiceFactory,
+    asr_config: Option<TranscriptionConfig>,
+    llm_request: &GenerateMeetingInsightsRequest,
+    reporter: &ProcessingReporter<'_>,
+) -> CommandResult<MeetingInsightsResponse> {
+    let meeting_id = llm_request.meeting_id;
+    let report = |stage, status, message: Option<&str>| {
+        (reporter.on_progress)(ProcessingProgress {
+            meeting_id,
+            stage,
+            status,
+            message: message.map(str::to_string),
+        })
+    };
+    let fail = |stage, error: CommandError| {
+        let error = error.context(&format!("{} failed", stage));
+        report(stage, StageStatus::Failed, Some(&error.message));
+        error
+    };
+
+    report(ProcessingStage::Transcription, StageStatus::Started, None);
+    let prepared =
+        prepare_transcription(storage, keychain, asr_factory, meeting_id, asr_config, None)
+            .await
+            .map_err(|e| fail(ProcessingStage::Transcription, e))?;
+    let outcome = transcribe_and_store(
+        storage,
+        prepared.asr_service.as_ref(),
+        meeting_id,
+        &prepared.audio_file_path,
+        &prepared.config,
+        prepared.audio_offset_ms,
+        true,
+    )
+    .await;
+    finish_transcription(
+        storage,
+        keychain,
+        llm_factory,
+        meeting_id,
+        &prepared.meeting_name,
+        &outcome,
+        reporter.notify,
+    )
+    .await;
+    let stored = outcome
+        .map_err(|failure| match failure {
+            TranscriptionFailure::Transcribe(e) => CommandError::from(e),
+            TranscriptionFailure::Store(e) => {
+                CommandError::from(e).context("Failed to store transcripts")
+            }
+        })
+        .map_err(|e| fail(ProcessingStage::Transcription, e))?;
+
+    if stored == 0 {
+        return Err(fail(
+            ProcessingStage::Transcription,
+            CommandError::not_found("No speech was found in the recording"),
+        ));
+    }
+    report(ProcessingStage::Transcription, StageStatus::Completed, None);
+
+    report(ProcessingStage::Insights, StageStatus::Started, None);
+    let response = generate_and_store_insights(llm_request, storage, keychain, llm_factory)
+        .await
+        .map_err(|e| fail(ProcessingStage::Insights, e))?;
+    report(ProcessingStage::Insights, StageStatus::Completed, None);
+
+    log::info!(
+        "Processed meeting {}: {} transcript segments, {} insights",
+        meeting_id,
+        stored,
+        response.insights.len()
+    );
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::SqliteStorage;
+    use crate::domain::models::{InsightType, Meeting, Platform, ServiceConfig, ServiceType};
+    use crate::ports::mocks::{MockLlmService, MockTranscriptionService};
+    use crate::ports::transcription::TranscriptionSegment;
+    use crate::utils::keychain::MockKeychain;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    async fn setup() -> (SqliteStorage, MockKeychain, i64) {
+        let storage = SqliteStorage::new(PathBuf::from(":memory:")).unwrap();
+        storage.run_migrations().unwrap();
+        let keychain = MockKeychain::new();
+        keychain.save_api_key("asr", "deepgram", "asr-key").unwrap();
+        keychain.save_api_key("llm", "openai", "llm-key").unwrap();
+        for (service_type, provider, settings) in [
+            (ServiceType::Asr, "deepgram", None),
+            (ServiceType::Llm, "openai", Some(r#"{"model": "gpt-4o"}"#)),
+        ] {
+            let config = ServiceConfig::new(service_type, provider.to_string())
+                .with_active(true)
+                .with_settings(settings.map(str::to_string));
+            storage.save_service_config(&config).await.unwrap();
+        }
+
+        let mut meeting = Meeting::new(Platform::Zoom, Some("Budget review".to_string()), None);
+        meeting.audio_file_path = Some("/recordings/meeting_1.wav".to_string());
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+        (storage, keychain, meeting_id)
+    }
+
+    fn insights_request(meeting_id: i64) -> GenerateMeetingInsightsRequest {
+        GenerateMeetingInsightsRequest {
+            meeting_id,
+            provider: None,
+            model: None,
+            insight_types: vec![InsightType::Summary],
+            temperature: None,
+            max_tokens: None,
+            json_mode: false,
+            context: None,
+            target_language: None,
+            detail_level: Default::default(),
+            include_timestamps: false,
+        }
+    }
+
+    fn stages(progress: &Mutex<Vec<ProcessingProgress>>) -> Vec<(ProcessingStage, StageStatus)> {
+        progress
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|p| (p.stage, p.status))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_process_meeting_transcribes_then_summarizes() {
+        let (storage, keychain, meeting_id) = setup().await;
+        let asr = MockTranscriptionService::new(vec![TranscriptionSegment {
+            text: "Let's review the budget.".to_string(),
+            start_ms: 0,
+            end_ms: 1200,
+            speaker_label: Some("Speaker 0".to_string()),
+            confidence: Some(0.9),
+            language: None,
+        }]);
+        let llm = MockLlmService::new();
+        let progress = Mutex::new(Vec::new());
+        let notices = Mutex::new(Vec::new());
+
+        let response = process_meeting_with(
+            &storage,
+            &keychain,
+            &asr,
+            &llm,
+            None,
+            &insights_request(meeting_id),
+            &ProcessingReporter {
+                on_progress: &|p| progress.lock().unwrap().push(p),
+                notify: &|title, _| notices.lock().unwrap().push(title.to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.insights.len(), 1);
+        assert_eq!(storage.get_transcripts(meeting_id).await.unwrap().len(), 1);
+        assert!(llm.requests.lock().unwrap()[0]
+            .transcript
+            .contains("Let's review the budget."));
+        assert_eq!(
+            stages(&progress),
+            vec![
+                (ProcessingStage::Transcription, StageStatus::Started),
+                (ProcessingStage::Transcription, StageStatus::Completed),
+                (ProcessingStage::Insights, StageStatus::Started),
+                (ProcessingStage::Insights, StageStatus::Completed),
+            ]
+        );
+        assert_eq!(*notices.lock().unwrap(), vec!["Transcription complete"]);
+    }
+
+    #[tokio::test]
+    async fn test_process_meeting_stops_when_transcription_fails() {
+        let (storage, keychain, meeting_id) = setup().await;
+        let asr = MockTranscriptionService::new(Vec::new()).failing("provider unavailable");
+        let llm = MockLlmService::new();
+        let progress = Mutex::new(Vec::new());
+        let notices = Mutex::new(Vec::new());
+
+        let error = process_meeting_with(
+            &storage,
+            &keychain,
+            &asr,
+            &llm,
+            None,
+            &insights_request(meeting_id),
+            &ProcessingReporter {
+                on_progress: &|p| progress.lock().unwrap().push(p),
+                notify: &|title, _| notices.lock().unwrap().push(title.to_string()),
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.message.starts_with("Transcription failed: "));
+        assert!(error.message.contains("provider unavailable"));
+        assert_eq!(
+            stages(&progress),
+            vec![
+                (ProcessingStage::Transcription, StageStatus::Started),
+                (ProcessingStage::Transcription, StageStatus::Failed),
+            ]
+        );
+        assert_eq!(
+            progress.lock().unwrap()[1].message.as_deref(),
+            Some(error.message.as_str())
+        );
+        assert_eq!(*notices.lock().unwrap(), vec!["Transcription failed"]);
+
+        // The LLM is never reached
+        assert!(llm.created.lock().unwrap().is_empty());
+        assert!(storage.get_insights(meeting_id).await.unwrap().is_empty());
+    }
+}
@@ -1,5 +1,6 @@
-/// Search commands using FTS5 full-text search
 use crate::domain::models::{InsightSearchResult, Meeting, SearchResults, TranscriptSearchResult};
+/// Search commands using FTS5 full-text search
+use crate::error::{CommandError, CommandResult};
 use crate::ports::storage::StoragePort;
 use crate::AppState;
 use serde::Deserialize;
@@ -21,18 +22,18 @@ pub async fn search_all(
     state: tauri::State<'_, AppState>,
     query: String,
     limit: Option<i32>,
-) -> Result<SearchResults, String> {
+) -> CommandResult<SearchResults> {
     log::info!("Searching all entities for: '{}'", query);
 
     if query.trim().is_empty() {
-        return Err("Search query cannot be empty".to_string());
+        return Err(CommandError::invalid_input("Search query cannot be empty"));
     }
 
     state
         .storage
         .search_all(&query, limit)
         .await
-        .map_err(|e| format!("Search failed: {}", e))
+        .map_err(|e| CommandError::from(e).context("Search failed"))
 }
 
 /// Search only transcripts
@@ -41,18 +42,18 @@ pub async fn search_transcripts(
     state: tauri::State<'_, AppState>,
     query: String,
     limit: Option<i32>,
-) -> Result<Vec<TranscriptSearchResult>, String> {
+) -> CommandResult<Vec<TranscriptSearchResult>> {
     log::info!("Searching transcripts for: '{}'", query);
 
     if query.trim().is_empty() {
-        return Err("Search query cannot be empty".to_string());
+        return Err(CommandError::invalid_input("Search query cannot be empty"));
     }
 
     state
         .storage
         .search_transcripts(&query, limit)
         .await
-        .map_err(|e| format!("Transcript search failed: {}", e))
+        .map_err(|e| CommandError::from(e).context("Transcript search failed"))
 }
 
 /// Search only insights
@@ -61,18 +62,18 @@ pub async fn search_insights(
     state: tauri::State<'_, AppState>,
     query: String,
     limit: Option<i32>,
-) -> Result<Vec<InsightSearchResult>, String> {
+) -> CommandResult<Vec<InsightSearchResult>> {
     log::info!("Searching insights for: '{}'", query);
 
     if query.trim().is_empty() {
-        return Err("Search query cannot be empty".to_string());
+        return Err(CommandError::invalid_input("Search query cannot be empty"));
     }
 
     state
         .storage
         .search_insights(&query, limit)
         .await
-        .map_err(|e| format!("Insight search failed: {}", e))
+        .map_err(|e| CommandError::from(e).context("Insight search failed"))
 }
 
 /// Search meetings by title and platform (includes all meetings, even those without titles)
@@ -81,16 +82,16 @@ pub async fn search_meetings(
     state: tauri::State<'_, AppState>,
     query: String,
     limit: Option<i32>,
-) -> Result<Vec<Meeting>, String> {
+) -> CommandResult<Vec<Meeting>> {
     log::info!("Searching meetings for: '{}'", query);
 
     if query.trim().is_empty() {
-        return Err("Search query cannot be empty".to_string());
+        return Err(CommandError::invalid_input("Search query cannot be empty"));
     }
 
     state
         .storage
         .search_meetings(&query, limit)
         .await
-        .map_err(|e| format!("Meeting search failed: {}", e))
+        .map_err(|e| CommandError::from(e).context("Meeting search failed"))
 }
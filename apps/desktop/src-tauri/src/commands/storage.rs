@@ -0,0 +1,381 @@
+//! Disk usage and recording cleanup commands
+//!
+//! Recordings live in `{app_data_dir}/recordings` as `meeting_{id}.wav`. Cleanup removes
+//! audio only; transcripts and insights stay in the database.
+
+use crate::commands::transcription::{
+    ActiveTranscriptions, TranscriptionGuard, TranscriptionState,
+};
+use crate::error::{AppError, CommandError, CommandResult, Result};
+use crate::ports::storage::StoragePort;
+use crate::utils::audio_file::remove_audio_file;
+use crate::AppState;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+/// Database file name in the app data directory
+const DATABASE_FILE: &str = "meet-scribe.db";
+
+/// SQLite side files that count towards the database size
+const DATABASE_SIDE_FILE_SUFFIXES: &[&str] = &["-wal", "-shm", "-journal"];
+
+/// Disk space used by one meeting's recordings
+#[derive(Debug, Serialize)]
+pub struct MeetingStorageUsage {
+    pub meeting_id: i64,
+    pub title: Option<String>,
+    pub bytes: u64,
+    pub files: Vec<String>,
+}
+
+/// Disk usage report for the app data directory
+#[derive(Debug, Serialize)]
+pub struct StorageUsage {
+    pub database_bytes: u64,
+    pub recordings_bytes: u64,
+    /// Files in the recordings directory that don't belong to any meeting
+    pub unassigned_recordings_bytes: u64,
+    pub total_bytes: u64,
+    /// Per-meeting usage, largest first
+    pub meetings: Vec<MeetingStorageUsage>,
+}
+
+/// A recording removed by cleanup
+#[derive(Debug, Serialize)]
+pub struct FreedRecording {
+    pub meeting_id: i64,
+    pub path: String,
+    pub bytes: u64,
+}
+
+/// Result of a recordings cleanup
+#[derive(Debug, Serialize)]
+pub struct CleanupResult {
+    pub freed_files: Vec<FreedRecording>,
+    pub bytes_freed: u64,
+    /// Old meetings left alone because they are being recorded or transcribed
+    pub skipped_meeting_ids: Vec<i64>,
+}
+
+/// Report disk usage of the database and recordings, per meeting and in total
+#[tauri::command]
+pub async fn get_storage_usage(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> CommandResult<StorageUsage> {
+    let app_data_dir = app_data_dir(&app)?;
+
+    let database_path = app_data_dir.join(DATABASE_FILE);
+    let mut database_files = vec![database_path.clone()];
+    for suffix in DATABASE_SIDE_FILE_SUFFIXES {
+        database_files.push(PathBuf::from(format!(
+            "{}{}",
+            database_path.display(),
+            suffix
+        )));
+    }
+
+    Ok(compute_storage_usage(
+        state.storage.as_ref(),
+        &app_data_dir.join("recordings"),
+        &database_files,
+    )
+    .await?)
+}
+
+/// Delete recordings of meetings that started more than `older_than_days` days ago
+///
+/// Transcripts are kept and the meeting's `audio_file_path` is cleared. Meetings that are
+/// currently being recorded or transcribed are skipped.
+#[tauri::command]
+pub async fn cleanup_recordings(
+    state: tauri::State<'_, AppState>,
+    transcription_state: tauri::State<'_, TranscriptionState>,
+    older_than_days: u32,
+) -> CommandResult<CleanupResult> {
+    let cutoff = chrono::Utc::now().timestamp() - i64::from(older_than_days) * 24 * 60 * 60;
+    let recording_meeting_id = *state.current_meeting_id.lock().await;
+
+    let result = cleanup_old_recordings(
+        state.storage.as_ref(),
+        &transcription_state.active_transcriptions,
+        recording_meeting_id,
+        cutoff,
+    )
+    .await?;
+
+    log::info!(
+        "Recording cleanup freed {} bytes across {} files",
+        result.bytes_freed,
+        result.freed_files.len()
+    );
+    Ok(result)
+}
+
+fn app_data_dir(app: &tauri::AppHandle) -> CommandResult<PathBuf> {
+    app.path().app_data_dir().map_err(|e| {
+        CommandError::from(AppError::Other(e.to_string()))
+            .context("Failed to get app data directory")
+    })
+}
+
+/// Parse the meeting ID from a recording file name like `meeting_42.wav` or `meeting_42_001.wav`
+fn meeting_id_from_file_name(file_name: &str) -> Option<i64> {
+    let rest = file_name.strip_prefix("meeting_")?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// Size of each file directly inside `dir`; a missing directory has no files
+fn list_files(dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push((entry.path(), metadata.len()));
+        }
+    }
+    Ok(files)
+}
+
+async fn compute_storage_usage(
+    storage: &dyn StoragePort,
+    recordings_dir: &Path,
+    database_files: &[PathBuf],
+) -> Result<StorageUsage> {
+    let database_bytes = database_files
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    let meetings = storage.list_meetings(Some(i32::MAX), None).await?;
+    let meeting_titles: HashMap<i64, Option<String>> = meetings
+        .iter()
+        .filter_map(|m| m.id.map(|id| (id, m.title.clone())))
+        .collect();
+    // Prefer the path stored on the meeting; fall back to the file naming convention
+    let meeting_by_path: HashMap<PathBuf, i64> = meetings
+        .iter()
+        .filter_map(|m| Some((PathBuf::from(m.audio_file_path.as_ref()?), m.id?)))
+        .collect();
+
+    let mut recordings_bytes = 0;
+    let mut unassigned_recordings_bytes = 0;
+    let mut per_meeting: BTreeMap<i64, MeetingStorageUsage> = BTreeMap::new();
+
+    for (path, bytes) in list_files(recordings_dir)? {
+        recordings_bytes += bytes;
+
+        let meeting_id = meeting_by_path.get(&path).copied().or_else(|| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(meeting_id_from_file_name)
+                .filter(|id| meeting_titles.contains_key(id))
+        });
+
+        match meeting_id {
+            Some(meeting_id) => {
+                let usage = per_meeting
+                    .entry(meeting_id)
+                    .or_insert_with(|| MeetingStorageUsage {
+                        meeting_id,
+                        title: meeting_titles.get(&meeting_id).cloned().flatten(),
+                        bytes: 0,
+                        files: Vec::new(),
+                    });
+                usage.bytes += bytes;
+                usage.files.push(path.to_string_lossy().to_string());
+            }
+            None => unassigned_recordings_bytes += bytes,
+        }
+    }
+
+    let mut meetings: Vec<MeetingStorageUsage> = per_meeting.into_values().collect();
+    meetings.sort_by_key(|m| std::cmp::Reverse(m.bytes));
+
+    Ok(StorageUsage {
+        database_bytes,
+        recordings_bytes,
+        unassigned_recordings_bytes,
+        total_bytes: database_bytes + recordings_bytes,
+        meetings,
+    })
+}
+
+async fn cleanup_old_recordings(
+    storage: &dyn StoragePort,
+    active_transcriptions: &ActiveTranscriptions,
+    recording_meeting_id: Option<i64>,
+    cutoff: i64,
+) -> Result<CleanupResult> {
+    let meetings = storage.list_meetings(Some(i32::MAX), None).await?;
+
+    let mut freed_files = Vec::new();
+    let mut skipped_meeting_ids = Vec::new();
+
+    for mut meeting in meetings {
+        let Some(meeting_id) = meeting.id else {
+            continue;
+        };
+        if meeting.start_time >= cutoff {
+            continue;
+        }
+        let Some(audio_file_path) = meeting.audio_file_path.clone() else {
+            continue;
+        };
+
+        if recording_meeting_id == Some(meeting_id) {
+            skipped_meeting_ids.push(meeting_id);
+            continue;
+        }
+
+        // Holding the guard also stops a transcription from starting while the file is removed
+        let Some(_guard) = TranscriptionGuard::acquire(active_transcriptions, meeting_id) else {
+            log::info!(
+                "Skipping recording cleanup for meeting {}: transcription in progress",
+                meeting_id
+            );
+            skipped_meeting_ids.push(meeting_id);
+            continue;
+        };
+
+        let bytes = match remove_audio_file(&audio_file_path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::warn!(
+                    "Failed to delete recording {} for meeting {}: {}",
+                    audio_file_path,
+                    meeting_id,
+                    e
+                );
+                continue;
+            }
+        };
+
+        meeting.audio_file_path = None;
+        storage.update_meeting(&meeting).await?;
+
+        if bytes > 0 {
+            freed_files.push(FreedRecording {
+                meeting_id,
+                path: audio_file_path,
+                bytes,
+            });
+        }
+    }
+
+    let bytes_freed = freed_files.iter().map(|f| f.bytes).sum();
+    Ok(CleanupResult {
+        freed_files,
+        bytes_freed,
+        skipped_meeting_ids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{Meeting, Platform};
+    use crate::ports::mocks::MockStorage;
+    use tempfile::tempdir;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    async fn meeting_with_recording(
+        storage: &MockStorage,
+        dir: &Path,
+        start_time: i64,
+        bytes: usize,
+    ) -> (i64, PathBuf) {
+        let mut meeting = Meeting::new(Platform::Zoom, None, None);
+        meeting.start_time = start_time;
+        let id = storage.create_meeting(&meeting).await.unwrap();
+
+        let path = dir.join(format!("meeting_{}.wav", id));
+        std::fs::write(&path, vec![0u8; bytes]).unwrap();
+        meeting.id = Some(id);
+        meeting.audio_file_path = Some(path.to_string_lossy().to_string());
+        storage.update_meeting(&meeting).await.unwrap();
+
+        (id, path)
+    }
+
+    #[test]
+    fn test_meeting_id_from_file_name() {
+        assert_eq!(meeting_id_from_file_name("meeting_42.wav"), Some(42));
+        assert_eq!(meeting_id_from_file_name("meeting_7_001.wav"), Some(7));
+        assert_eq!(meeting_id_from_file_name("notes.txt"), None);
+        assert_eq!(meeting_id_from_file_name("meeting_.wav"), None);
+    }
+
+    #[tokio::test]
+    async fn test_compute_storage_usage() {
+        let dir = tempdir().unwrap();
+        let recordings_dir = dir.path().join("recordings");
+        std::fs::create_dir(&recordings_dir).unwrap();
+        let database_path = dir.path().join(DATABASE_FILE);
+        std::fs::write(&database_path, [0u8; 50]).unwrap();
+
+        let storage = MockStorage::new();
+        let now = chrono::Utc::now().timestamp();
+        let (small_id, _) = meeting_with_recording(&storage, &recordings_dir, now, 100).await;
+        let (large_id, _) = meeting_with_recording(&storage, &recordings_dir, now, 300).await;
+        std::fs::write(recordings_dir.join("stray.wav"), [0u8; 10]).unwrap();
+
+        let usage = compute_storage_usage(&storage, &recordings_dir, &[database_path])
+            .await
+            .unwrap();
+
+        assert_eq!(usage.database_bytes, 50);
+        assert_eq!(usage.recordings_bytes, 410);
+        assert_eq!(usage.unassigned_recordings_bytes, 10);
+        assert_eq!(usage.total_bytes, 460);
+        let ids: Vec<i64> = usage.meetings.iter().map(|m| m.meeting_id).collect();
+        assert_eq!(ids, vec![large_id, small_id]);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_old_recordings() {
+        let dir = tempdir().unwrap();
+        let storage = MockStorage::new();
+        let active: ActiveTranscriptions = Default::default();
+        let now = chrono::Utc::now().timestamp();
+
+        let (old_id, old_path) =
+            meeting_with_recording(&storage, dir.path(), now - 40 * DAY, 100).await;
+        let (recent_id, recent_path) =
+            meeting_with_recording(&storage, dir.path(), now - DAY, 100).await;
+        let (busy_id, busy_path) =
+            meeting_with_recording(&storage, dir.path(), now - 40 * DAY, 100).await;
+
+        let _busy = TranscriptionGuard::acquire(&active, busy_id).unwrap();
+        let result = cleanup_old_recordings(&storage, &active, None, now - 30 * DAY)
+            .await
+            .unwrap();
+
+        assert_eq!(result.bytes_freed, 100);
+        assert_eq!(result.freed_files.len(), 1);
+        assert_eq!(result.freed_files[0].meeting_id, old_id);
+        assert_eq!(result.skipped_meeting_ids, vec![busy_id]);
+
+        assert!(!old_path.exists());
+        assert!(recent_path.exists());
+        assert!(busy_path.exists());
+
+        // The meeting row is kept but no longer points at the deleted file
+        let old_meeting = storage.get_meeting(old_id).await.unwrap().unwrap();
+        assert!(old_meeting.audio_file_path.is_none());
+        let recent_meeting = storage.get_meeting(recent_id).await.unwrap().unwrap();
+        assert!(recent_meeting.audio_file_path.is_some());
+    }
+}
@@ -3,16 +3,21 @@
 //! Provides real-time transcription capabilities during active meetings.
 
 use crate::adapters::services::asr;
-use crate::domain::models::Transcript;
+use crate::commands::transcription::apply_asr_settings;
+use crate::domain::models::{Transcript, TranscriptSource};
+use crate::error::{CommandError, CommandResult};
+use crate::ports::audio::{AudioCapturePort, AudioFormat};
 use crate::ports::storage::StoragePort;
 use crate::ports::transcription::{
     StreamingSession, StreamingTranscriptionCallback, TranscriptionConfig, TranscriptionSegment,
+    STREAMING_AUDIO_FORMAT,
 };
 use crate::AppState;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant, SystemTime};
 use tauri::Emitter;
 use tokio::sync::Mutex;
 
@@ -34,24 +39,270 @@ impl StreamingTranscriptionState {
     }
 }
 
+/// Event emitted for each interim (still changing) transcript segment
+const STREAMING_INTERIM_EVENT: &str = "streaming-interim";
+
+/// Event emitted for each final transcript segment
+const STREAMING_FINAL_EVENT: &str = "streaming-final";
+
+/// Event emitted when the capture format differs from what the streaming session expects
+const STREAMING_FORMAT_WARNING_EVENT: &str = "streaming-format-warning";
+
+/// Payload of the `streaming-format-warning` event
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamingFormatWarning {
+    pub meeting_id: i64,
+    pub capture_format: AudioFormat,
+    pub expected_format: AudioFormat,
+    pub message: String,
+}
+
+/// Payload of the `streaming-interim` and `streaming-final` events
+///
+/// Interim events for an utterance share its `utterance_id` with the final event
+/// that settles it, so the UI should replace the caption with that ID rather than
+/// append a new one.
+///
+/// Events pass through a [`SegmentReorderBuffer`], so `segment.start_ms` does not
+/// decrease from one event to the next. The one exception is a final that arrives
+/// after later speech was already shown; it is still sent so its text isn't lost.
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamingSegmentEvent {
+    pub meeting_id: i64,
+    /// Position of this event in the session, counting interims and finals together
+    pub sequence: u64,
+    pub utterance_id: u64,
+    /// ID of the stored transcript row, if storing succeeded
+    ///
+    /// Interims of an utterance and its final share the row, which the final overwrites.
+    pub transcript_id: Option<i64>,
+    pub segment: TranscriptionSegment,
+}
+
+/// Most segments held back for reordering before the earliest is sent
+const REORDER_WINDOW: usize = 8;
+
+/// Longest a segment is held back waiting for earlier ones
+const MAX_HOLD: Duration = Duration::from_millis(300);
+
+/// How often held segments are checked during a pause in speech
+const RELEASE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A segment event ready to be emitted
+#[derive(Debug, Clone)]
+pub(crate) struct ReleasedSegment {
+    pub event: &'static str,
+    pub payload: StreamingSegmentEvent,
+}
+
+/// A segment waiting in the reorder buffer
+#[derive(Debug)]
+struct HeldSegment {
+    is_final: bool,
+    payload: StreamingSegmentEvent,
+    received: Instant,
+}
+
+impl HeldSegment {
+    fn order_key(&self) -> (i64, i64) {
+        (self.payload.segment.start_ms, self.payload.segment.end_ms)
+    }
+}
+
+/// Puts streamed segments back in `start_ms` order before they reach the UI
+///
+/// Providers can deliver interim and final frames out of order, which makes the live
+/// caption jump backward. Segments are held until either [`REORDER_WINDOW`] newer
+/// ones have arrived or they have waited [`MAX_HOLD`], then released earliest first
+/// and numbered. Interims that a final already covers are dropped, whether they are
+/// still held or arrive afterward.
+#[derive(Debug, Default)]
+pub(crate) struct SegmentReorderBuffer {
+    /// Held segments, sorted by start then end time
+    held: Vec<HeldSegment>,
+    /// Start of the last segment released
+    released_start_ms: Option<i64>,
+    /// End of the latest final seen; interims starting before it are stale
+    finalized_until_ms: Option<i64>,
+    next_sequence: u64,
+}
+
+impl SegmentReorderBuffer {
+    /// Add a segment and return whatever is now ready to be sent
+    pub fn push(
+        &mut self,
+        is_final: bool,
+        payload: StreamingSegmentEvent,
+        now: Instant,
+    ) -> Vec<ReleasedSegment> {
+        let start_ms = payload.segment.start_ms;
+        let mut released = Vec::new();
+
+        if is_final {
+            let end_ms = payload.segment.end_ms;
+            self.held
+                .retain(|held| held.is_final || !covers(start_ms, end_ms, &held.payload));
+            self.finalized_until_ms = Some(
+                self.finalized_until_ms
+                    .map_or(end_ms, |until| until.max(end_ms)),
+            );
+        } else if self
+            .finalized_until_ms
+            .is_some_and(|until| start_ms < until)
+        {
+            log::debug!("Dropping interim at {} ms superseded by a final", start_ms);
+            return released;
+        }
+
+        if self.released_start_ms.is_some_and(|last| start_ms < last) {
+            if !is_final {
+                log::debug!("Dropping interim at {} ms that arrived too late", start_ms);
+                return released;
+            }
+            log::warn!(
+                "Final segment at {} ms arrived after later speech was sent",
+                start_ms
+            );
+            released.push(self.release(HeldSegment {
+                is_final,
+                payload,
+                received: now,
+            }));
+            return released;
+        }
+
+        let held = HeldSegment {
+            is_final,
+            payload,
+            received: now,
+        };
+        let index = self
+            .held
+            .partition_point(|other| other.order_key() <= held.order_key());
+        self.held.insert(index, held);
+
+        while self.held.len() > REORDER_WINDOW {
+            let earliest = self.held.remove(0);
+            released.push(self.release(earliest));
+        }
+        released.extend(self.release_due(now));
+        released
+    }
+
+    /// Release segments that have been held for [`MAX_HOLD`]
+    ///
+    /// Anything held ahead of an overdue segment goes with it, keeping the order.
+    pub fn release_due(&mut self, now: Instant) -> Vec<ReleasedSegment> {
+        let due = self
+            .held
+            .iter()
+            .rposition(|held| now.duration_since(held.received) >= MAX_HOLD)
+            .map_or(0, |index| index + 1);
+        self.held
+            .drain(..due)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|held| self.release(held))
+            .collect()
+    }
+
+    /// Release everything still held, e.g. when the session closes
+    pub fn flush(&mut self) -> Vec<ReleasedSegment> {
+        std::mem::take(&mut self.held)
+            .into_iter()
+            .map(|held| self.release(held))
+            .collect()
+    }
+
+    fn release(&mut self, held: HeldSegment) -> ReleasedSegment {
+        let mut payload = held.payload;
+        payload.sequence = self.next_sequence;
+        self.next_sequence += 1;
+        let start_ms = payload.segment.start_ms;
+        self.released_start_ms = Some(
+            self.released_start_ms
+                .map_or(start_ms, |last| last.max(start_ms)),
+        );
+        ReleasedSegment {
+            event: if held.is_final {
+                STREAMING_FINAL_EVENT
+            } else {
+                STREAMING_INTERIM_EVENT
+            },
+            payload,
+        }
+    }
+}
+
+/// Whether a final spanning `start_ms..end_ms` settles the speech of an interim
+fn covers(start_ms: i64, end_ms: i64, interim: &StreamingSegmentEvent) -> bool {
+    (start_ms..end_ms).contains(&interim.segment.start_ms)
+}
+
+/// Emits segment events in order through a shared [`SegmentReorderBuffer`]
+struct SegmentEmitter {
+    app_handle: tauri::AppHandle,
+    buffer: std::sync::Mutex<SegmentReorderBuffer>,
+}
+
+impl SegmentEmitter {
+    fn push(&self, is_final: bool, payload: StreamingSegmentEvent) {
+        let released = self
+            .buffer
+            .lock()
+            .unwrap()
+            .push(is_final, payload, Instant::now());
+        self.emit(released);
+    }
+
+    fn release_due(&self) {
+        let released = self.buffer.lock().unwrap().release_due(Instant::now());
+        self.emit(released);
+    }
+
+    fn flush(&self) {
+        let released = self.buffer.lock().unwrap().flush();
+        self.emit(released);
+    }
+
+    fn emit(&self, released: Vec<ReleasedSegment>) {
+        for ReleasedSegment { event, payload } in released {
+            if let Err(e) = self.app_handle.emit_to("main", event, payload) {
+                log::warn!("Failed to emit {} event: {}", event, e);
+            }
+        }
+    }
+
+    /// Keep releasing held segments while speech pauses, until the session's
+    /// callback is dropped
+    fn spawn_release_task(emitter: Weak<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RELEASE_INTERVAL);
+            loop {
+                interval.tick().await;
+                match emitter.upgrade() {
+                    Some(emitter) => emitter.release_due(),
+                    None => break,
+                }
+            }
+        });
+    }
+}
+
 /// Tauri event callback for streaming transcription
 /// This sends transcript segments to the frontend via Tauri events
 struct TauriStreamingCallback {
     app_handle: tauri::AppHandle,
     meeting_id: i64,
     storage: Arc<dyn StoragePort>,
+    /// Utterance the next interim/final segment belongs to; advanced by each final
+    utterance_id: AtomicU64,
+    emitter: Arc<SegmentEmitter>,
 }
 
-#[async_trait]
-impl StreamingTranscriptionCallback for TauriStreamingCallback {
-    async fn on_transcript(&self, segment: TranscriptionSegment) {
-        log::info!(
-            "Received final transcript: {} chars, speaker: {:?}",
-            segment.text.len(),
-            segment.speaker_label
-        );
-
-        // Store transcript in database
+impl TauriStreamingCallback {
+    /// Store a segment, replacing the stored interim versions of the same speech
+    async fn store_segment(&self, segment: &TranscriptionSegment, is_final: bool) -> Option<i64> {
         let transcript = Transcript {
             id: None,
             meeting_id: self.meeting_id,
@@ -59,39 +310,79 @@ impl StreamingTranscriptionCallback for TauriStreamingCallback {
             participant_name: None, // Will be populated when speaker is linked to participant
             speaker_label: segment.speaker_label.clone(),
             timestamp_ms: segment.start_ms,
+            end_timestamp_ms: Some(segment.end_ms),
             text: segment.text.clone(),
             confidence: segment.confidence,
+            language: segment.language.clone(),
+            source: TranscriptSource::Streaming,
             created_at: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64,
+            updated_at: None,
         };
 
-        match self.storage.create_transcript(&transcript).await {
+        match self
+            .storage
+            .upsert_transcript_segment(&transcript, is_final)
+            .await
+        {
             Ok(id) => {
-                log::debug!("Stored transcript with ID: {}", id);
-
-                // Emit event to frontend with the stored transcript
-                let mut stored_transcript = transcript;
-                stored_transcript.id = Some(id);
-
-                let _ = self
-                    .app_handle
-                    .emit_to("main", "streaming-transcript", stored_transcript);
+                log::debug!("Stored transcript with ID: {} (final: {})", id, is_final);
+                Some(id)
             }
             Err(e) => {
                 log::error!("Failed to store transcript: {}", e);
+                None
             }
         }
     }
 
+    fn emit_segment(
+        &self,
+        is_final: bool,
+        utterance_id: u64,
+        transcript_id: Option<i64>,
+        segment: TranscriptionSegment,
+    ) {
+        // Numbered when the reorder buffer releases it
+        let payload = StreamingSegmentEvent {
+            meeting_id: self.meeting_id,
+            sequence: 0,
+            utterance_id,
+            transcript_id,
+            segment,
+        };
+        self.emitter.push(is_final, payload);
+    }
+}
+
+#[async_trait]
+impl StreamingTranscriptionCallback for TauriStreamingCallback {
+    async fn on_transcript(&self, segment: TranscriptionSegment) {
+        log::info!(
+            "Received final transcript: {} chars, speaker: {:?}",
+            segment.text.len(),
+            segment.speaker_label
+        );
+
+        // This final settles the current utterance; later interims start the next one
+        let utterance_id = self.utterance_id.fetch_add(1, Ordering::SeqCst);
+
+        // Overwrites the interim rows stored for this utterance
+        let transcript_id = self.store_segment(&segment, true).await;
+
+        // Emit even if storing failed so the live caption still firms up
+        self.emit_segment(true, utterance_id, transcript_id, segment);
+    }
+
     async fn on_interim_transcript(&self, segment: TranscriptionSegment) {
         log::debug!("Received interim transcript: {} chars", segment.text.len());
 
-        // Emit interim transcripts to frontend (not stored in DB)
-        let _ = self
-            .app_handle
-            .emit_to("main", "streaming-transcript-interim", segment);
+        // Stored as interim so the text survives a crash before the final arrives
+        let transcript_id = self.store_segment(&segment, false).await;
+        let utterance_id = self.utterance_id.load(Ordering::SeqCst);
+        self.emit_segment(false, utterance_id, transcript_id, segment);
     }
 
     async fn on_error(&self, error: String) {
@@ -105,6 +396,7 @@ impl StreamingTranscriptionCallback for TauriStreamingCallback {
     async fn on_close(&self) {
         log::info!("Streaming transcription closed");
 
+        self.emitter.flush();
         let _ = self
             .app_handle
             .emit_to("main", "streaming-transcription-closed", ());
@@ -119,7 +411,7 @@ pub async fn start_streaming_transcription(
     streaming_state: tauri::State<'_, StreamingTranscriptionState>,
     meeting_id: i64,
     config: Option<TranscriptionConfig>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     log::info!(
         "Starting streaming transcription for meeting {}",
         meeting_id
@@ -128,7 +420,9 @@ pub async fn start_streaming_transcription(
     // Check if there's already an active session
     let mut active_session = streaming_state.active_session.lock().await;
     if active_session.is_some() {
-        return Err("Streaming transcription already active".to_string());
+        return Err(CommandError::invalid_state(
+            "Streaming transcription already active",
+        ));
     }
 
     // Load transcription config if not provided
@@ -146,6 +440,7 @@ pub async fn start_streaming_transcription(
                             if let Some(model) = settings.get("model").and_then(|m| m.as_str()) {
                                 default_config.model = Some(model.to_string());
                             }
+                            apply_asr_settings(&mut default_config, settings);
                         }
                         Err(e) => {
                             log::warn!("Failed to parse service config settings: {}", e);
@@ -154,10 +449,10 @@ pub async fn start_streaming_transcription(
                 }
             }
             Ok(None) => {
-                return Err("No active ASR service configured".to_string());
+                return Err(CommandError::config("No active ASR service configured"));
             }
             Err(e) => {
-                return Err(format!("Failed to get ASR service config: {}", e));
+                return Err(CommandError::from(e).context("Failed to get ASR service config"));
             }
         }
 
@@ -165,86 +460,140 @@ pub async fn start_streaming_transcription(
     };
 
     // Get the active ASR service
-    let asr_service = asr::get_active_asr_service(&state.storage, &state.keychain)
-        .await
-        .map_err(|e| e.to_string())?;
+    let asr_service = asr::get_active_asr_service(
+        state.storage.as_ref(),
+        state.keychain.as_ref(),
+        &asr::ProviderAsrFactory,
+    )
+    .await
+    .map_err(CommandError::from)?;
 
     // Check if streaming is supported
     if !asr_service.supports_streaming() {
-        return Err(format!(
+        return Err(CommandError::unsupported(format!(
             "{} does not support streaming transcription",
             asr_service.provider_name()
-        ));
+        )));
     }
 
     // Create callback that emits Tauri events
+    let emitter = Arc::new(SegmentEmitter {
+        app_handle: app.clone(),
+        buffer: std::sync::Mutex::new(SegmentReorderBuffer::default()),
+    });
+    SegmentEmitter::spawn_release_task(Arc::downgrade(&emitter));
     let callback = Box::new(TauriStreamingCallback {
         app_handle: app.clone(),
         meeting_id,
         storage: Arc::clone(&state.storage) as Arc<dyn StoragePort>,
+        utterance_id: AtomicU64::new(0),
+        emitter,
     });
 
     // Start streaming session
     let session = asr_service
         .start_streaming(&transcription_config, callback)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
 
     // Store the session
     *active_session = Some(session);
     *streaming_state.meeting_id.lock().await = Some(meeting_id);
 
+    // Audio isn't resampled before it's sent, so a different capture format hurts accuracy
+    let capture_format = state.audio_capture.lock().await.get_format();
+    if let Some(message) = format_mismatch(&capture_format, &STREAMING_AUDIO_FORMAT) {
+        log::warn!("{}", message);
+        let warning = StreamingFormatWarning {
+            meeting_id,
+            capture_format,
+            expected_format: STREAMING_AUDIO_FORMAT,
+            message,
+        };
+        if let Err(e) = app.emit_to("main", STREAMING_FORMAT_WARNING_EVENT, warning) {
+            log::warn!(
+                "Failed to emit {} event: {}",
+                STREAMING_FORMAT_WARNING_EVENT,
+                e
+            );
+        }
+    }
+
     log::info!("Streaming transcription started for meeting {}", meeting_id);
 
     Ok(())
 }
 
+/// Describe how the capture format differs from the streaming format, or None if it matches
+fn format_mismatch(capture: &AudioFormat, expected: &AudioFormat) -> Option<String> {
+    (capture != expected).then(|| {
+        format!(
+            "Audio is captured as {} but streaming transcription expects {}; live transcripts may be inaccurate",
+            capture, expected
+        )
+    })
+}
+
 /// Stop streaming transcription
 #[tauri::command]
 pub async fn stop_streaming_transcription(
     streaming_state: tauri::State<'_, StreamingTranscriptionState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     log::info!("Stopping streaming transcription");
 
-    let mut active_session = streaming_state.active_session.lock().await;
-
-    if let Some(mut session) = active_session.take() {
-        session
-            .flush()
-            .await
-            .map_err(|e| format!("Failed to flush session: {}", e))?;
-
-        session
-            .close()
-            .await
-            .map_err(|e| format!("Failed to close session: {}", e))?;
-
-        *streaming_state.meeting_id.lock().await = None;
-
+    if close_active_session(&streaming_state).await? {
         log::info!("Streaming transcription stopped");
         Ok(())
     } else {
-        Err("No active streaming transcription session".to_string())
+        Err(CommandError::invalid_state(
+            "No active streaming transcription session",
+        ))
     }
 }
 
+/// Flush and close the active streaming session
+///
+/// Returns false when there was no session to close.
+pub async fn close_active_session(
+    streaming_state: &StreamingTranscriptionState,
+) -> CommandResult<bool> {
+    let Some(mut session) = streaming_state.active_session.lock().await.take() else {
+        return Ok(false);
+    };
+
+    session
+        .flush()
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to flush session"))?;
+
+    session
+        .close()
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to close session"))?;
+
+    *streaming_state.meeting_id.lock().await = None;
+    Ok(true)
+}
+
 /// Send audio chunk to the streaming transcription session
 #[tauri::command]
 pub async fn send_audio_chunk(
     streaming_state: tauri::State<'_, StreamingTranscriptionState>,
     audio_chunk: Vec<u8>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let mut active_session = streaming_state.active_session.lock().await;
 
     if let Some(session) = active_session.as_mut() {
         session
             .send_audio(&audio_chunk)
             .await
-            .map_err(|e| format!("Failed to send audio chunk: {}", e))?;
+            .map_err(|e| CommandError::from(e).context("Failed to send audio chunk"))?;
 
         Ok(())
     } else {
-        Err("No active streaming transcription session".to_string())
+        Err(CommandError::invalid_state(
+            "No active streaming transcription session",
+        ))
     }
 }
 
@@ -252,13 +601,17 @@ pub async fn send_audio_chunk(
 #[tauri::command]
 pub async fn get_streaming_transcription_status(
     streaming_state: tauri::State<'_, StreamingTranscriptionState>,
-) -> Result<StreamingTranscriptionStatus, String> {
+) -> CommandResult<StreamingTranscriptionStatus> {
     let active_session = streaming_state.active_session.lock().await;
     let meeting_id = streaming_state.meeting_id.lock().await;
 
     Ok(StreamingTranscriptionStatus {
         is_active: active_session.is_some(),
         meeting_id: *meeting_id,
+        pending_chunks: active_session
+            .as_ref()
+            .map(|session| session.pending_chunks())
+            .unwrap_or(0),
     })
 }
 
@@ -267,4 +620,178 @@ pub async fn get_streaming_transcription_status(
 pub struct StreamingTranscriptionStatus {
     pub is_active: bool,
     pub meeting_id: Option<i64>,
+    /// Audio chunks waiting for the provider connection to recover
+    pub pending_chunks: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use std::sync::atomic::AtomicBool;
+
+    /// Session that records whether it was flushed and closed
+    struct FakeSession {
+        closed: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl StreamingSession for FakeSession {
+        async fn send_audio(&mut self, _audio_chunk: &[u8]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            self.closed.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn is_active(&self) -> bool {
+            !self.closed.load(Ordering::SeqCst)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_close_active_session_is_idempotent() {
+        let state = StreamingTranscriptionState::new();
+        assert!(!close_active_session(&state).await.unwrap());
+
+        let closed = Arc::new(AtomicBool::new(false));
+        *state.active_session.lock().await = Some(Box::new(FakeSession {
+            closed: Arc::clone(&closed),
+        }));
+        *state.meeting_id.lock().await = Some(7);
+
+        assert!(close_active_session(&state).await.unwrap());
+        assert!(closed.load(Ordering::SeqCst));
+        assert!(state.meeting_id.lock().await.is_none());
+
+        assert!(!close_active_session(&state).await.unwrap());
+    }
+
+    #[test]
+    fn test_format_mismatch() {
+        assert_eq!(
+            format_mismatch(&STREAMING_AUDIO_FORMAT, &STREAMING_AUDIO_FORMAT),
+            None
+        );
+
+        // WASAPI's usual mix format
+        let capture = AudioFormat {
+            sample_rate: 48000,
+            channels: 2,
+            bits_per_sample: 32,
+        };
+        let message = format_mismatch(&capture, &STREAMING_AUDIO_FORMAT).unwrap();
+        assert!(message.contains("48000 Hz stereo 32-bit"));
+        assert!(message.contains("expects 16000 Hz mono 16-bit"));
+
+        // Any single difference counts
+        let stereo = AudioFormat {
+            channels: 2,
+            ..STREAMING_AUDIO_FORMAT
+        };
+        assert!(format_mismatch(&stereo, &STREAMING_AUDIO_FORMAT).is_some());
+    }
+
+    fn segment_event(start_ms: i64, end_ms: i64) -> StreamingSegmentEvent {
+        StreamingSegmentEvent {
+            meeting_id: 1,
+            sequence: 0,
+            utterance_id: 0,
+            transcript_id: None,
+            segment: TranscriptionSegment {
+                text: format!("{}-{}", start_ms, end_ms),
+                start_ms,
+                end_ms,
+                speaker_label: None,
+                confidence: None,
+                language: None,
+            },
+        }
+    }
+
+    fn summarize(released: &[ReleasedSegment]) -> Vec<(u64, &'static str, i64, i64)> {
+        released
+            .iter()
+            .map(|r| {
+                (
+                    r.payload.sequence,
+                    r.event,
+                    r.payload.segment.start_ms,
+                    r.payload.segment.end_ms,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reorder_buffer_sorts_shuffled_frames() {
+        let now = Instant::now();
+        let mut buffer = SegmentReorderBuffer::default();
+
+        // Three utterances' frames, delivered shuffled
+        let frames = [
+            (false, 1200, 1500),
+            (true, 0, 1000),
+            (false, 0, 400),
+            (false, 2500, 2800),
+            (true, 1200, 2000),
+            (false, 0, 800),
+        ];
+        let mut released = Vec::new();
+        for (is_final, start_ms, end_ms) in frames {
+            released.extend(buffer.push(is_final, segment_event(start_ms, end_ms), now));
+        }
+        assert!(released.is_empty());
+        released.extend(buffer.flush());
+
+        // Interims the finals cover are gone, held or late
+        assert_eq!(
+            summarize(&released),
+            vec![
+                (0, STREAMING_FINAL_EVENT, 0, 1000),
+                (1, STREAMING_FINAL_EVENT, 1200, 2000),
+                (2, STREAMING_INTERIM_EVENT, 2500, 2800),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reorder_buffer_releases_by_window_and_age() {
+        let now = Instant::now();
+        let mut buffer = SegmentReorderBuffer::default();
+
+        for i in (0..REORDER_WINDOW as i64).rev() {
+            let released = buffer.push(false, segment_event(i * 100 + 100, i * 100 + 150), now);
+            assert!(released.is_empty());
+        }
+        // One past the window sends the earliest
+        let released = buffer.push(false, segment_event(0, 50), now);
+        assert_eq!(
+            summarize(&released),
+            vec![(0, STREAMING_INTERIM_EVENT, 0, 50)]
+        );
+
+        // Too late for its place: an interim is dropped, a final still goes out
+        assert!(buffer.push(false, segment_event(-100, -50), now).is_empty());
+        let late = buffer.push(true, segment_event(-100, 50), now);
+        assert_eq!(summarize(&late), vec![(1, STREAMING_FINAL_EVENT, -100, 50)]);
+
+        // Held segments go out in order once they've waited long enough
+        assert!(buffer.release_due(now + MAX_HOLD / 2).is_empty());
+        let released = buffer.release_due(now + MAX_HOLD);
+        assert_eq!(released.len(), REORDER_WINDOW);
+        let starts: Vec<i64> = released
+            .iter()
+            .map(|r| r.payload.segment.start_ms)
+            .collect();
+        assert!(starts.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(released.last().unwrap().payload.sequence, 9);
+        assert!(buffer.flush().is_empty());
+    }
 }
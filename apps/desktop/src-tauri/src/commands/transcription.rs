@@ -1,22 +1,173 @@
-/// Transcription-related Tauri commands
-///
-/// Provides IPC commands for triggering and managing transcription operations.
-use crate::adapters::services::asr::get_active_asr_service;
+//! Transcription-related Tauri commands
+//!
+//! Provides IPC commands for triggering and managing transcription operations.
+
+use crate::adapters::services::asr::{
+    asr_capabilities, check_upload_size, create_asr_service, get_active_asr_service,
+    ProviderAsrFactory,
+};
+use crate::adapters::services::llm::ProviderLlmFactory;
 use crate::adapters::storage::SqliteStorage;
-use crate::domain::models::Transcript;
+use crate::commands::llm::fill_meeting_title;
+use crate::domain::models::{
+    MeetingModelOverride, ModelSelection, RecordingStatus, ServiceType, Transcript,
+    TranscriptQuality, TranscriptSource,
+};
+use crate::domain::pricing;
+use crate::error::{AppError, CommandError, CommandResult};
+use crate::ports::llm::LlmServiceFactory;
 use crate::ports::storage::StoragePort;
-use crate::ports::transcription::TranscriptionConfig;
-use crate::utils::keychain::{KeychainManager, KeychainPort};
-use std::sync::Arc;
+use crate::ports::transcription::{
+    AsrCapabilities, TranscriptionConfig, TranscriptionSegment, TranscriptionServiceFactory,
+    TranscriptionServicePort,
+};
+use crate::utils::keychain::{KeyStatus, KeychainManager, KeychainPort};
+use crate::utils::notification::{meeting_display_name, notify, summarize_error};
+use crate::utils::redaction::redact_pii;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tauri::State;
-use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
+
+/// An in-flight transcription job
+pub struct ActiveTranscription {
+    /// Identifies the job that owns this entry, so a cancelled task finishing late
+    /// cannot clear the entry of a retry for the same meeting
+    token: u64,
+    /// Aborts the background task, set once the task has been spawned
+    handle: Option<AbortHandle>,
+}
+
+/// In-flight transcription jobs keyed by meeting ID
+pub type ActiveTranscriptions = Arc<Mutex<HashMap<i64, ActiveTranscription>>>;
+
+static NEXT_TRANSCRIPTION_TOKEN: AtomicU64 = AtomicU64::new(1);
 
 /// Application state for transcription operations
 pub struct TranscriptionState {
     pub storage: Arc<SqliteStorage>,
     pub keychain: Arc<KeychainManager>,
-    /// Current transcription status: None, Some(meeting_id) if in progress
-    pub current_transcription: Arc<Mutex<Option<i64>>>,
+    /// Meetings currently being transcribed (one job per meeting, any number of meetings)
+    pub active_transcriptions: ActiveTranscriptions,
+}
+
+/// Marks a meeting as transcribing for as long as the guard is alive
+///
+/// Dropping the guard (including on early returns or a panicking task) clears the mark.
+pub(crate) struct TranscriptionGuard {
+    active: ActiveTranscriptions,
+    meeting_id: i64,
+    token: u64,
+}
+
+impl TranscriptionGuard {
+    /// Claim `meeting_id`, or return `None` if it is already being transcribed
+    pub(crate) fn acquire(active: &ActiveTranscriptions, meeting_id: i64) -> Option<Self> {
+        let mut jobs = active.lock().unwrap();
+        if jobs.contains_key(&meeting_id) {
+            return None;
+        }
+        let token = NEXT_TRANSCRIPTION_TOKEN.fetch_add(1, Ordering::Relaxed);
+        jobs.insert(
+            meeting_id,
+            ActiveTranscription {
+                token,
+                handle: None,
+            },
+        );
+        Some(Self {
+            active: Arc::clone(active),
+            meeting_id,
+            token,
+        })
+    }
+
+    /// Like [`acquire`](Self::acquire), with the error to return when the meeting is busy
+    pub(crate) fn claim(active: &ActiveTranscriptions, meeting_id: i64) -> CommandResult<Self> {
+        let guard = Self::acquire(active, meeting_id).ok_or_else(|| {
+            log::warn!(
+                "Transcription already in progress for meeting {}",
+                meeting_id
+            );
+            CommandError::invalid_state(format!(
+                "A transcription is already in progress for meeting {}",
+                meeting_id
+            ))
+        })?;
+        log::info!("Marked meeting {} as transcribing", meeting_id);
+        Ok(guard)
+    }
+}
+
+impl Drop for TranscriptionGuard {
+    fn drop(&mut self) {
+        let mut jobs = self.active.lock().unwrap();
+        if jobs.get(&self.meeting_id).map(|job| job.token) == Some(self.token) {
+            jobs.remove(&self.meeting_id);
+        }
+    }
+}
+
+/// Record the spawned task for a job so it can be cancelled
+///
+/// If the task already finished (and its guard removed the entry) this is a no-op.
+fn attach_handle(active: &ActiveTranscriptions, meeting_id: i64, token: u64, handle: AbortHandle) {
+    if let Some(job) = active.lock().unwrap().get_mut(&meeting_id) {
+        if job.token == token {
+            job.handle = Some(handle);
+        }
+    }
+}
+
+/// Abort the transcription job for a meeting and clear its in-progress marker
+///
+/// Returns `false` if no transcription was running for the meeting.
+fn abort_transcription(active: &ActiveTranscriptions, meeting_id: i64) -> bool {
+    match active.lock().unwrap().remove(&meeting_id) {
+        Some(job) => {
+            if let Some(handle) = job.handle {
+                handle.abort();
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Run a job on its own task, registered so `cancel_transcription` can abort it
+///
+/// The task holds `guard` until it ends. If the job is cancelled the error code is
+/// `cancelled`.
+pub(crate) async fn run_cancellable<T: Send + 'static>(
+    guard: TranscriptionGuard,
+    job: impl Future<Output = CommandResult<T>> + Send + 'static,
+) -> CommandResult<T> {
+    let active = Arc::clone(&guard.active);
+    let (meeting_id, token) = (guard.meeting_id, guard.token);
+    let handle = tokio::spawn(async move {
+        let _guard = guard;
+        job.await
+    });
+    attach_handle(&active, meeting_id, token, handle.abort_handle());
+
+    match handle.await {
+        Ok(result) => result,
+        Err(e) if e.is_cancelled() => Err(CommandError::new(
+            "cancelled",
+            format!("Processing of meeting {} was cancelled", meeting_id),
+        )),
+        Err(e) => Err(CommandError::new(
+            "internal",
+            format!(
+                "Processing of meeting {} stopped unexpectedly: {}",
+                meeting_id, e
+            ),
+        )),
+    }
 }
 
 /// Start transcription for a completed meeting
@@ -27,57 +178,266 @@ pub struct TranscriptionState {
 /// # Arguments
 /// * `meeting_id` - The ID of the meeting to transcribe
 /// * `config` - Optional transcription configuration (uses defaults if None)
+/// * `num_speakers` - Expected number of speakers, overriding the config's value
 ///
 /// # Returns
 /// * `Ok(())` if transcription started successfully
-/// * `Err(String)` if there's an error
+/// * `Err(CommandError)` if there's an error
 #[tauri::command]
 pub async fn start_transcription(
+    app: tauri::AppHandle,
     meeting_id: i64,
     config: Option<TranscriptionConfig>,
+    num_speakers: Option<u32>,
     state: State<'_, TranscriptionState>,
-) -> Result<(), String> {
-    println!(
-        "\n>>> START_TRANSCRIPTION COMMAND CALLED for meeting {}",
-        meeting_id
-    );
-    use std::io::Write;
-    let _ = std::io::stdout().flush();
-
+) -> CommandResult<()> {
     log::info!("Starting transcription for meeting {}", meeting_id);
+    spawn_transcription(app, meeting_id, config, num_speakers, &state, false).await
+}
 
-    // Check if a transcription is already in progress
-    let mut current = state.current_transcription.lock().await;
-    if current.is_some() {
-        println!(
-            "!!! Transcription already in progress for meeting {:?}",
-            *current
-        );
-        log::warn!(
-            "Transcription already in progress for meeting {:?}",
-            *current
+/// Re-transcribe a meeting, replacing its existing transcripts
+///
+/// Unlike calling `delete_transcripts` followed by `start_transcription`, the old
+/// transcripts stay in place until the new result is ready and are then swapped out in a
+/// single transaction. If transcription fails, the existing transcripts are left intact.
+///
+/// # Arguments
+/// * `meeting_id` - The ID of the meeting to re-transcribe
+/// * `config` - Optional transcription configuration, e.g. with a different model
+/// * `num_speakers` - Expected number of speakers, overriding the config's value
+///
+/// # Returns
+/// * `Ok(())` if re-transcription started successfully
+/// * `Err(CommandError)` if there's an error
+#[tauri::command]
+pub async fn retranscribe(
+    app: tauri::AppHandle,
+    meeting_id: i64,
+    config: Option<TranscriptionConfig>,
+    num_speakers: Option<u32>,
+    state: State<'_, TranscriptionState>,
+) -> CommandResult<()> {
+    log::info!("Re-transcribing meeting {}", meeting_id);
+    spawn_transcription(app, meeting_id, config, num_speakers, &state, true).await
+}
+
+/// Convert ASR segments into transcript rows of a meeting
+///
+/// Segment times are shifted by `audio_offset_ms`, the silence trimmed from the start of
+/// the recording, so they line up with the meeting. With `redact`, PII is removed from the
+/// text locally.
+pub(crate) fn segments_to_transcripts(
+    meeting_id: i64,
+    segments: Vec<TranscriptionSegment>,
+    audio_offset_ms: i64,
+    redact: bool,
+) -> Vec<Transcript> {
+    let now = chrono::Utc::now().timestamp();
+    segments
+        .into_iter()
+        .map(|segment| Transcript {
+            id: None,
+            meeting_id,
+            participant_id: None,
+            participant_name: None,
+            speaker_label: segment.speaker_label, // Diarization speaker label
+            timestamp_ms: segment.start_ms + audio_offset_ms,
+            end_timestamp_ms: Some(segment.end_ms + audio_offset_ms),
+            text: if redact {
+                redact_pii(&segment.text)
+            } else {
+                segment.text
+            },
+            confidence: segment.confidence,
+            language: segment.language,
+            source: TranscriptSource::Batch,
+            created_at: now,
+            updated_at: None,
+        })
+        .collect()
+}
+
+/// File the raw ASR response for a meeting is saved to in debug mode
+///
+/// Sits next to the recording as `meeting_{id}_{provider}.json`.
+pub fn raw_response_path(audio_file_path: &str, meeting_id: i64, provider: &str) -> PathBuf {
+    Path::new(audio_file_path).with_file_name(format!("meeting_{}_{}.json", meeting_id, provider))
+}
+
+/// ASR provider, model and settings for a meeting
+///
+/// The meeting's override comes first, then the active ASR service config.
+async fn asr_selection(
+    storage: &dyn StoragePort,
+    meeting_id: i64,
+) -> crate::error::Result<ModelSelection> {
+    let meeting_override = storage.get_meeting_model_override(meeting_id).await?;
+    let configs = storage.list_service_configs().await?;
+    MeetingModelOverride::resolve(meeting_override.as_ref(), &ServiceType::Asr, &configs)
+        .ok_or_else(|| {
+            crate::error::AppError::Config("No active ASR service configured".to_string())
+        })
+}
+
+/// Provider a meeting is transcribed with if the active ASR service's
+/// `save_raw_response` setting is on
+///
+/// Off unless enabled explicitly, since raw responses hold the full transcript. The
+/// provider is the one the meeting resolves to (its override first), which is the one
+/// whose response gets saved.
+async fn raw_response_provider(storage: &dyn StoragePort, meeting_id: i64) -> Option<String> {
+    let config = storage.get_active_service_config("asr").await.ok()??;
+    let enabled = config
+        .settings
+        .as_deref()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+        .and_then(|settings| settings.get("save_raw_response")?.as_bool())
+        .unwrap_or(false);
+    if !enabled {
+        return None;
+    }
+    asr_selection(storage, meeting_id)
+        .await
+        .ok()
+        .map(|selection| selection.provider)
+}
+
+/// Get the path of a meeting's saved raw ASR response
+///
+/// Responses are only saved while the ASR service's `save_raw_response` debug setting is
+/// enabled, so they can be attached to bug reports.
+///
+/// # Returns
+/// * `Ok(Some(path))` if a raw response was saved for this meeting and provider
+/// * `Ok(None)` if there is none
+#[tauri::command]
+pub async fn get_raw_response_path(
+    meeting_id: i64,
+    provider: String,
+    state: State<'_, TranscriptionState>,
+) -> CommandResult<Option<String>> {
+    // The provider becomes part of a file name
+    if provider.is_empty()
+        || !provider
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(CommandError::invalid_input(format!(
+            "Invalid provider: {}",
+            provider
+        )));
+    }
+
+    let meeting = state
+        .storage
+        .get_meeting(meeting_id)
+        .await?
+        .ok_or_else(|| CommandError::not_found(format!("Meeting {} not found", meeting_id)))?;
+
+    Ok(meeting
+        .audio_file_path
+        .map(|audio| raw_response_path(&audio, meeting_id, &provider))
+        .filter(|path| path.exists())
+        .map(|path| path.to_string_lossy().into_owned()))
+}
+
+/// Apply an ASR service's saved settings to a transcription config
+///
+/// The settings are also passed through as `additional_settings`, where adapters read
+/// their provider-specific options (e.g. Deepgram's `smart_format` and `keyterms`).
+/// Shared by batch and streaming transcription so both honor the same settings.
+pub(crate) fn apply_asr_settings(config: &mut TranscriptionConfig, settings: serde_json::Value) {
+    if let Some(redact) = settings.get("pii_redaction").and_then(|r| r.as_bool()) {
+        config.enable_pii_redaction = redact;
+    }
+
+    if let Some(multichannel) = settings.get("multichannel").and_then(|m| m.as_bool()) {
+        config.multichannel = multichannel;
+    }
+
+    if let Some(min_confidence) = settings.get("min_confidence").and_then(|c| c.as_f64()) {
+        config.min_confidence = Some(min_confidence as f32);
+    }
+
+    if let Some(normalize) = settings
+        .get("normalize_speaker_labels")
+        .and_then(|n| n.as_bool())
+    {
+        config.normalize_speaker_labels = normalize;
+    }
+
+    if let Some(split) = settings
+        .get("sentence_segmentation")
+        .and_then(|s| s.as_bool())
+    {
+        config.sentence_segmentation = split;
+    }
+
+    // A count of zero in saved settings means "unknown"
+    if let Some(count) = settings
+        .get("num_speakers")
+        .and_then(|n| n.as_u64())
+        .filter(|&n| n > 0)
+    {
+        config.num_speakers = u32::try_from(count).ok();
+    }
+
+    if let Some(policies) = settings.get("pii_policies").and_then(|p| p.as_array()) {
+        config.pii_policies = Some(
+            policies
+                .iter()
+                .filter_map(|p| p.as_str().map(String::from))
+                .collect(),
         );
-        return Err("A transcription is already in progress".to_string());
     }
 
-    // Mark this meeting as being transcribed
-    *current = Some(meeting_id);
-    drop(current); // Release lock
-    log::info!("Marked meeting {} as transcribing", meeting_id);
+    config.additional_settings = Some(settings);
+}
+
+/// Reject an expected speaker count of zero
+pub(crate) fn validate_num_speakers(num_speakers: Option<u32>) -> CommandResult<()> {
+    if num_speakers == Some(0) {
+        return Err(CommandError::invalid_input(
+            "Number of speakers must be at least 1",
+        ));
+    }
+    Ok(())
+}
+
+/// A meeting's recording with the ASR service and config to transcribe it with
+pub(crate) struct PreparedTranscription {
+    pub meeting_name: String,
+    pub audio_file_path: String,
+    /// Silence trimmed from the start of the recording, added back to segment times
+    pub audio_offset_ms: i64,
+    pub asr_service: Box<dyn TranscriptionServicePort>,
+    pub config: TranscriptionConfig,
+}
 
+/// Look up a meeting's recording and set up the ASR service to transcribe it
+///
+/// The meeting's own provider and model win over the active ASR service, and without
+/// a `config` the provider's saved settings are used.
+pub(crate) async fn prepare_transcription(
+    storage: &dyn StoragePort,
+    keychain: &dyn KeychainPort,
+    factory: &dyn TranscriptionServiceFactory,
+    meeting_id: i64,
+    config: Option<TranscriptionConfig>,
+    num_speakers: Option<u32>,
+) -> CommandResult<PreparedTranscription> {
     // Get the meeting details
     log::info!("Fetching meeting {} from database", meeting_id);
-    let meeting = state
-        .storage
+    let meeting = storage
         .get_meeting(meeting_id)
         .await
         .map_err(|e| {
             log::error!("Failed to get meeting {}: {}", meeting_id, e);
-            format!("Failed to get meeting: {}", e)
+            CommandError::from(e).context("Failed to get meeting")
         })?
         .ok_or_else(|| {
             log::error!("Meeting {} not found in database", meeting_id);
-            format!("Meeting {} not found", meeting_id)
+            CommandError::not_found(format!("Meeting {} not found", meeting_id))
         })?;
 
     log::info!(
@@ -87,6 +447,16 @@ pub async fn start_transcription(
         meeting.audio_file_path
     );
 
+    let meeting_name = meeting_display_name(meeting.title.as_deref(), meeting_id);
+    // Silence trimmed from the start of the recording, added back to segment times
+    let audio_offset_ms = meeting.audio_offset_ms;
+
+    if meeting.recording_status == Some(RecordingStatus::NoAudio) {
+        return Err(CommandError::invalid_state(
+            "No audio was recorded for this meeting, so there is nothing to transcribe.",
+        ));
+    }
+
     // Check if audio file exists
     let audio_file_path = meeting
         .audio_file_path
@@ -95,7 +465,7 @@ pub async fn start_transcription(
             log::error!("This usually means the audio file hasn't been saved yet, or audio recording failed");
             log::error!("Meeting details: platform={}, start_time={}, end_time={:?}",
                 meeting.platform, meeting.start_time, meeting.end_time);
-            "Meeting has no audio file. The audio may still be processing, or recording may have failed. Please wait a moment and try again.".to_string()
+            CommandError::not_found("Meeting has no audio file. The audio may still be processing, or recording may have failed. Please wait a moment and try again.")
         })?;
 
     log::info!(
@@ -104,40 +474,55 @@ pub async fn start_transcription(
         audio_file_path
     );
 
-    // Get the active ASR service
-    println!(">>> Getting active ASR service");
-    log::info!("Getting active ASR service");
-    let asr_service = get_active_asr_service(&state.storage, &state.keychain)
-        .await
-        .map_err(|e| {
-            println!("!!! Failed to get ASR service: {}", e);
-            log::error!("Failed to get ASR service: {}", e);
-            format!("Failed to get ASR service: {}", e)
-        })?;
+    // The meeting's own provider and model, if set, win over the active ASR service
+    log::info!("Getting ASR service for meeting {}", meeting_id);
+    let selection = asr_selection(storage, meeting_id).await.map_err(|e| {
+        log::error!("Failed to select ASR service: {}", e);
+        CommandError::from(e).context("Failed to get ASR service")
+    })?;
+    let asr_service = create_asr_service(
+        keychain,
+        factory,
+        &selection.provider,
+        selection
+            .config
+            .as_ref()
+            .and_then(|c| c.settings.as_deref()),
+    )
+    .map_err(|e| {
+        log::error!("Failed to get ASR service: {}", e);
+        CommandError::from(e).context("Failed to get ASR service")
+    })?;
+
+    log::info!(
+        "ASR service: {}, model: {:?}",
+        asr_service.provider_name(),
+        selection.model
+    );
 
-    println!(">>> Active ASR service: {}", asr_service.provider_name());
-    log::info!("Active ASR service: {}", asr_service.provider_name());
+    // A missing file is reported when the job runs; only the size is checked here
+    if let Ok(metadata) = std::fs::metadata(&audio_file_path) {
+        check_upload_size(asr_service.as_ref(), metadata.len())?;
+    }
 
     // Use provided config or load from active service configuration
-    let transcription_config = if let Some(cfg) = config {
-        println!(">>> Using provided config: model={:?}", cfg.model);
+    let mut transcription_config = if let Some(cfg) = config {
         log::info!("Using provided config: model={:?}", cfg.model);
         cfg
     } else {
-        println!(">>> No config provided, loading from service configuration");
         log::info!("No config provided, loading from service configuration");
 
-        // Load model from active service configuration
-        let mut default_config = TranscriptionConfig::default();
+        // The meeting's language is used; None means auto-detect
+        let mut default_config = TranscriptionConfig {
+            model: selection.model.clone(),
+            language: meeting.language.clone(),
+            ..Default::default()
+        };
 
-        match state.storage.get_active_service_config("asr").await {
-            Ok(Some(service_config)) => {
-                println!(
-                    ">>> Found active ASR service config: provider={}, settings={:?}",
-                    service_config.provider, service_config.settings
-                );
-                log::info!(
-                    "Found active ASR service config: provider={}, settings={:?}",
+        match selection.config {
+            Some(service_config) => {
+                log::debug!(
+                    "Found ASR service config: provider={}, settings={:?}",
                     service_config.provider,
                     service_config.settings
                 );
@@ -145,160 +530,358 @@ pub async fn start_transcription(
                 if let Some(settings_str) = service_config.settings {
                     match serde_json::from_str::<serde_json::Value>(&settings_str) {
                         Ok(settings) => {
-                            println!(">>> Parsed settings JSON: {:?}", settings);
-                            log::info!("Parsed settings JSON: {:?}", settings);
-
-                            if let Some(model) = settings.get("model").and_then(|m| m.as_str()) {
-                                default_config.model = Some(model.to_string());
-                                println!(">>> Using model from service config: {}", model);
-                                log::info!("Using model from service config: {}", model);
-                            } else {
-                                println!("!!! No model field found in settings");
-                                log::warn!("No model field found in settings");
-                            }
+                            log::debug!("Parsed settings JSON: {:?}", settings);
+                            apply_asr_settings(&mut default_config, settings);
                         }
                         Err(e) => {
-                            println!("!!! Failed to parse settings JSON: {}", e);
                             log::error!("Failed to parse settings JSON: {}", e);
                         }
                     }
                 } else {
-                    println!("!!! Active service config has no settings");
-                    log::warn!("Active service config has no settings");
+                    log::warn!("ASR service config has no settings");
                 }
             }
-            Ok(None) => {
-                println!("!!! No active ASR service configuration found");
-                log::warn!("No active ASR service configuration found");
-            }
-            Err(e) => {
-                println!("!!! Failed to get active ASR service config: {}", e);
-                log::error!("Failed to get active ASR service config: {}", e);
+            None => {
+                log::warn!(
+                    "No saved configuration for ASR provider {}",
+                    selection.provider
+                );
             }
         }
 
         default_config
     };
+    if num_speakers.is_some() {
+        transcription_config.num_speakers = num_speakers;
+    }
+
+    // Debug mode: keep the provider's raw response next to the recording
+    if let Some(provider) = raw_response_provider(storage, meeting_id).await {
+        let path = raw_response_path(&audio_file_path, meeting_id, &provider);
+        log::info!("Raw ASR response will be saved to {}", path.display());
+        transcription_config.raw_response_path = Some(path.to_string_lossy().into_owned());
+    }
+
+    Ok(PreparedTranscription {
+        meeting_name,
+        audio_file_path,
+        audio_offset_ms,
+        asr_service,
+        config: transcription_config,
+    })
+}
+
+/// Start a background transcription job for a meeting
+///
+/// With `replace_existing` the meeting's current transcripts are swapped for the new ones
+/// atomically once transcription succeeds; otherwise the new segments are appended.
+async fn spawn_transcription(
+    app: tauri::AppHandle,
+    meeting_id: i64,
+    config: Option<TranscriptionConfig>,
+    num_speakers: Option<u32>,
+    state: &TranscriptionState,
+    replace_existing: bool,
+) -> CommandResult<()> {
+    validate_num_speakers(num_speakers)?;
+    validate_num_speakers(config.as_ref().and_then(|c| c.num_speakers))?;
+
+    // Reject a second transcription of the same meeting; other meetings may run concurrently
+    let guard = TranscriptionGuard::claim(&state.active_transcriptions, meeting_id)?;
+
+    let PreparedTranscription {
+        meeting_name,
+        audio_file_path,
+        audio_offset_ms,
+        asr_service,
+        config: transcription_config,
+    } = prepare_transcription(
+        state.storage.as_ref(),
+        state.keychain.as_ref(),
+        &ProviderAsrFactory,
+        meeting_id,
+        config,
+        num_speakers,
+    )
+    .await?;
 
     // Clone state for the background task
     let storage = Arc::clone(&state.storage);
-    let current_transcription = Arc::clone(&state.current_transcription);
+    let keychain = Arc::clone(&state.keychain);
+    let active_transcriptions = Arc::clone(&state.active_transcriptions);
+    let token = guard.token;
 
-    println!(">>> About to spawn background transcription task");
-    let _ = std::io::stdout().flush();
+    log::debug!("Spawning background transcription task");
 
     // Spawn transcription task in background
-    tokio::spawn(async move {
-        // Print to both logger and stdout to ensure visibility
-        println!("=== TRANSCRIPTION BACKGROUND TASK STARTED ===");
-        println!("Transcribing audio file: {}", audio_file_path);
+    let handle = tokio::spawn(async move {
+        // Held until the task finishes so the meeting stays marked as transcribing
+        let _guard = guard;
+
         log::info!("=== TRANSCRIPTION BACKGROUND TASK STARTED ===");
         log::info!("Transcribing audio file: {}", audio_file_path);
         log::info!(
-            "Transcription config: diarization={}, language={:?}, model={:?}",
-            transcription_config.enable_diarization,
-            transcription_config.language,
-            transcription_config.model
-        );
-        println!(
-            ">>> Transcription config: diarization={}, language={:?}, model={:?}",
+            "Transcription config: diarization={}, num_speakers={:?}, language={:?}, model={:?}",
             transcription_config.enable_diarization,
+            transcription_config.num_speakers,
             transcription_config.language,
             transcription_config.model
         );
 
-        // Force flush logs to console
-        use std::io::Write;
-        let _ = std::io::stdout().flush();
-        let _ = std::io::stderr().flush();
-
         // Check if audio file exists
         if !std::path::Path::new(&audio_file_path).exists() {
             log::error!("Audio file not found: {}", audio_file_path);
-            *current_transcription.lock().await = None;
+            notify(
+                &app,
+                "Transcription failed",
+                &format!("Audio file for {} was not found", meeting_name),
+            );
             return;
         }
 
-        // Perform transcription
-        let result = match asr_service
-            .transcribe_file(&audio_file_path, &transcription_config)
-            .await
-        {
-            Ok(result) => {
-                log::info!("Transcription API call successful");
-                result
-            }
-            Err(e) => {
-                println!("!!! TRANSCRIPTION FAILED: {}", e);
-                println!("!!! Error details: {:?}", e);
-                log::error!("Transcription failed: {}", e);
-                log::error!("Error details: {:?}", e);
-                let _ = std::io::stdout().flush();
-                let _ = std::io::stderr().flush();
-                *current_transcription.lock().await = None;
-                return;
+        let outcome = transcribe_and_store(
+            storage.as_ref(),
+            asr_service.as_ref(),
+            meeting_id,
+            &audio_file_path,
+            &transcription_config,
+            audio_offset_ms,
+            replace_existing,
+        )
+        .await;
+        finish_transcription(
+            storage.as_ref(),
+            keychain.as_ref(),
+            &ProviderLlmFactory,
+            meeting_id,
+            &meeting_name,
+            &outcome,
+            &|title, body| notify(&app, title, body),
+        )
+        .await;
+    });
+    attach_handle(
+        &active_transcriptions,
+        meeting_id,
+        token,
+        handle.abort_handle(),
+    );
+
+    Ok(())
+}
+
+/// Tell the user how a transcription job ended, then title the meeting if it has none
+///
+/// Titling only runs once the transcript is stored and is skipped without an LLM.
+pub(crate) async fn finish_transcription(
+    storage: &dyn StoragePort,
+    keychain: &dyn KeychainPort,
+    llm_factory: &dyn LlmServiceFactory,
+    meeting_id: i64,
+    meeting_name: &str,
+    outcome: &Result<usize, TranscriptionFailure>,
+    notify: &(dyn Fn(&str, &str) + Send + Sync),
+) {
+    match outcome {
+        Ok(_) => {
+            notify(
+                "Transcription complete",
+                &format!("Transcript ready for {}", meeting_name),
+            );
+            // Name meetings started without a title; skipped without an LLM
+            if let Err(e) = fill_meeting_title(storage, keychain, llm_factory, meeting_id).await {
+                log::info!(
+                    "No title generated for meeting {}: {}",
+                    meeting_id,
+                    e.message
+                );
             }
-        };
+        }
+        Err(TranscriptionFailure::Transcribe(e)) => notify(
+            "Transcription failed",
+            &format!(
+                "Could not transcribe {}: {}",
+                meeting_name,
+                summarize_error(&e.to_string())
+            ),
+        ),
+        Err(TranscriptionFailure::Store(e)) => notify(
+            "Transcription failed",
+            &format!(
+                "Could not save transcript for {}: {}",
+                meeting_name,
+                summarize_error(&e.to_string())
+            ),
+        ),
+    }
+}
+
+/// Why a transcription job failed, for the notification shown to the user
+#[derive(Debug)]
+pub(crate) enum TranscriptionFailure {
+    /// The provider could not transcribe the recording
+    Transcribe(AppError),
+    /// The transcript could not be saved
+    Store(AppError),
+}
+
+/// Transcribe a meeting's recording and store the transcript
+///
+/// Once the transcript is stored, redaction, detected language and confidence are
+/// recorded on the meeting. Returns the number of segments stored.
+pub(crate) async fn transcribe_and_store(
+    storage: &dyn StoragePort,
+    asr_service: &dyn TranscriptionServicePort,
+    meeting_id: i64,
+    audio_file_path: &str,
+    transcription_config: &TranscriptionConfig,
+    audio_offset_ms: i64,
+    replace_existing: bool,
+) -> Result<usize, TranscriptionFailure> {
+    // Perform transcription
+    let result = match asr_service
+        .transcribe_file(audio_file_path, transcription_config)
+        .await
+    {
+        Ok(result) => {
+            log::info!("Transcription API call successful");
+            result
+        }
+        Err(e) => {
+            log::error!("Transcription failed: {}", e);
+            log::error!("Error details: {:?}", e);
+            return Err(TranscriptionFailure::Transcribe(e));
+        }
+    };
 
-        // Convert TranscriptionSegments to Transcript domain models
-        println!(
-            ">>> Converting {} segments to Transcript models",
-            result.segments.len()
+    // Providers without native redaction get a local regex pass before storage
+    let redact_locally =
+        transcription_config.enable_pii_redaction && !asr_service.supports_pii_redaction();
+    if redact_locally {
+        log::info!(
+            "{} has no native PII redaction, redacting transcripts locally",
+            asr_service.provider_name()
         );
-        let now = chrono::Utc::now().timestamp();
-        let transcripts: Vec<Transcript> = result
-            .segments
-            .into_iter()
-            .map(|segment| Transcript {
-                id: None,
-                meeting_id,
-                participant_id: None,
-                participant_name: None,
-                speaker_label: segment.speaker_label, // Diarization speaker label
-                timestamp_ms: segment.start_ms,
-                text: segment.text,
-                confidence: segment.confidence,
-                created_at: now,
-            })
-            .collect();
+    }
 
-        println!(">>> Converted {} transcript segments", transcripts.len());
+    // Convert TranscriptionSegments to Transcript domain models
+    log::debug!(
+        "Converting {} segments to Transcript models",
+        result.segments.len()
+    );
+    if let Some(ref request_id) = result.request_id {
         log::info!(
-            "Transcription complete: {} segments for meeting {}",
-            transcripts.len(),
-            meeting_id
+            "{} request ID for meeting {}: {}",
+            asr_service.provider_name(),
+            meeting_id,
+            request_id
         );
+    }
+    let detected_language = result.detected_language.clone();
+    let confidence = result.confidence;
+    let transcripts =
+        segments_to_transcripts(meeting_id, result.segments, audio_offset_ms, redact_locally);
+
+    log::info!(
+        "Transcription complete: {} segments for meeting {}",
+        transcripts.len(),
+        meeting_id
+    );
+
+    // Store transcripts in batch
+    log::debug!("Storing {} transcripts in database", transcripts.len());
+    let stored = if replace_existing {
+        storage.replace_transcripts(meeting_id, &transcripts).await
+    } else {
+        storage.create_transcripts_batch(&transcripts).await
+    };
+    if let Err(e) = stored {
+        log::error!("Failed to store transcripts: {}", e);
+        return Err(TranscriptionFailure::Store(e));
+    }
+    log::info!("Transcripts stored successfully");
 
-        // Store transcripts in batch
-        println!(">>> Storing {} transcripts in database", transcripts.len());
-        if let Err(e) = storage.create_transcripts_batch(&transcripts).await {
-            println!("!!! Failed to store transcripts: {}", e);
-            log::error!("Failed to store transcripts: {}", e);
-        } else {
-            println!(">>> Transcripts stored successfully!");
-            log::info!("Transcripts stored successfully");
+    // Record redaction, detected language and confidence on the meeting
+    // A replaced transcript is only redacted if the new run was
+    if replace_existing
+        || transcription_config.enable_pii_redaction
+        || detected_language.is_some()
+        || confidence.is_some()
+    {
+        match storage.get_meeting(meeting_id).await {
+            Ok(Some(mut meeting)) => {
+                // Enabled redaction always applies some policy: the provider falls back
+                // to its defaults when none of the requested categories map
+                if replace_existing || transcription_config.enable_pii_redaction {
+                    meeting.transcripts_redacted = transcription_config.enable_pii_redaction;
+                }
+                if detected_language.is_some() {
+                    meeting.detected_language = detected_language;
+                }
+                // A new transcript's score replaces the old one even if it has none
+                if replace_existing || confidence.is_some() {
+                    meeting.transcript_confidence = confidence;
+                }
+                if let Err(e) = storage.update_meeting(&meeting).await {
+                    log::error!("Failed to update meeting {}: {}", meeting_id, e);
+                }
+            }
+            Ok(None) => log::warn!("Meeting {} no longer exists", meeting_id),
+            Err(e) => log::error!("Failed to get meeting {}: {}", meeting_id, e),
         }
+    }
 
-        // Clear current transcription
-        *current_transcription.lock().await = None;
-    });
+    Ok(transcripts.len())
+}
 
-    Ok(())
+/// Cancel an in-progress transcription
+///
+/// Aborts the background task (including any pending provider request or polling loop)
+/// and clears the in-progress marker so the meeting can be transcribed again.
+///
+/// # Arguments
+/// * `meeting_id` - The ID of the meeting whose transcription should be cancelled
+///
+/// # Returns
+/// * `Ok(())` if a running transcription was cancelled
+/// * `Err(CommandError)` if no transcription is in progress for the meeting
+#[tauri::command]
+pub async fn cancel_transcription(
+    meeting_id: i64,
+    state: State<'_, TranscriptionState>,
+) -> CommandResult<()> {
+    log::info!("Cancelling transcription for meeting {}", meeting_id);
+
+    if abort_transcription(&state.active_transcriptions, meeting_id) {
+        log::info!("Transcription for meeting {} cancelled", meeting_id);
+        Ok(())
+    } else {
+        Err(CommandError::invalid_state(format!(
+            "No transcription in progress for meeting {}",
+            meeting_id
+        )))
+    }
 }
 
 /// Get transcription status
 ///
-/// Returns the current transcription status and progress.
+/// Returns the meetings that currently have a transcription in progress.
 ///
 /// # Returns
-/// * `Some(meeting_id)` if a transcription is in progress
-/// * `None` if no transcription is running
+/// * Sorted list of meeting IDs being transcribed (empty if none are running)
 #[tauri::command]
 pub async fn get_transcription_status(
     state: State<'_, TranscriptionState>,
-) -> Result<Option<i64>, String> {
-    let current = state.current_transcription.lock().await;
-    Ok(*current)
+) -> CommandResult<Vec<i64>> {
+    let mut active: Vec<i64> = state
+        .active_transcriptions
+        .lock()
+        .unwrap()
+        .keys()
+        .copied()
+        .collect();
+    active.sort_unstable();
+    Ok(active)
 }
 
 /// Get transcripts for a meeting
@@ -307,20 +890,124 @@ pub async fn get_transcription_status(
 ///
 /// # Arguments
 /// * `meeting_id` - The ID of the meeting
+/// * `min_confidence` - Optional threshold; segments scored below it are left out
+/// * `source` - Optional; only segments from batch transcription or only live captions
 ///
 /// # Returns
 /// * `Ok(Vec<Transcript>)` - List of transcript segments ordered by timestamp
-/// * `Err(String)` if there's an error
+/// * `Err(CommandError)` if there's an error
 #[tauri::command]
 pub async fn get_transcripts(
     meeting_id: i64,
+    min_confidence: Option<f32>,
+    source: Option<TranscriptSource>,
     state: State<'_, TranscriptionState>,
-) -> Result<Vec<Transcript>, String> {
-    state
+) -> CommandResult<Vec<Transcript>> {
+    load_transcripts(state.storage.as_ref(), meeting_id, min_confidence, source)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get transcripts"))
+}
+
+/// A meeting's transcripts, filtered by confidence and source when given
+async fn load_transcripts(
+    storage: &dyn StoragePort,
+    meeting_id: i64,
+    min_confidence: Option<f32>,
+    source: Option<TranscriptSource>,
+) -> crate::error::Result<Vec<Transcript>> {
+    match (min_confidence, source) {
+        (Some(min_confidence), None) => {
+            storage
+                .get_confident_transcripts(meeting_id, min_confidence)
+                .await
+        }
+        (None, None) => storage.get_transcripts(meeting_id).await,
+        (min_confidence, Some(source)) => {
+            let mut transcripts = storage
+                .get_transcripts_by_source(meeting_id, source)
+                .await?;
+            if let Some(min_confidence) = min_confidence {
+                transcripts.retain(|t| t.confidence.is_none_or(|c| c >= min_confidence));
+            }
+            Ok(transcripts)
+        }
+    }
+}
+
+/// Get the overall and per-speaker confidence of a meeting's transcript
+///
+/// A low score (`is_low`) suggests re-recording or transcribing with a better model.
+///
+/// # Arguments
+/// * `meeting_id` - The ID of the meeting
+///
+/// # Returns
+/// * `Ok(TranscriptQuality)` averaged over the stored segments
+/// * `Err(CommandError)` if the meeting doesn't exist
+#[tauri::command]
+pub async fn get_transcript_quality(
+    meeting_id: i64,
+    state: State<'_, TranscriptionState>,
+) -> CommandResult<TranscriptQuality> {
+    let meeting = state
+        .storage
+        .get_meeting(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get meeting"))?
+        .ok_or_else(|| CommandError::not_found(format!("Meeting {} not found", meeting_id)))?;
+    let transcripts = state
         .storage
         .get_transcripts(meeting_id)
         .await
-        .map_err(|e| format!("Failed to get transcripts: {}", e))
+        .map_err(|e| CommandError::from(e).context("Failed to get transcripts"))?;
+
+    Ok(TranscriptQuality::from_segments(
+        &transcripts,
+        meeting.transcript_confidence,
+    ))
+}
+
+/// Correct the text of a transcript segment
+///
+/// Records the edit time in `updated_at` so corrected segments can be told apart
+/// from the ASR output.
+#[tauri::command]
+pub async fn edit_transcript(
+    transcript_id: i64,
+    text: String,
+    state: State<'_, TranscriptionState>,
+) -> CommandResult<()> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(CommandError::invalid_input(
+            "Transcript text cannot be empty",
+        ));
+    }
+
+    log::info!("Editing transcript {}", transcript_id);
+
+    state
+        .storage
+        .update_transcript_text(transcript_id, text)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to edit transcript"))
+}
+
+/// Delete a single transcript segment, e.g. one that was misheard
+///
+/// The rest of the transcript is kept. Talk time and exports handle the gap it leaves.
+#[tauri::command]
+pub async fn delete_transcript(
+    transcript_id: i64,
+    state: State<'_, TranscriptionState>,
+) -> CommandResult<()> {
+    log::info!("Deleting transcript {}", transcript_id);
+
+    state
+        .storage
+        .delete_transcript(transcript_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to delete transcript"))
 }
 
 /// Check if transcription is available
@@ -330,17 +1017,136 @@ pub async fn get_transcripts(
 /// # Returns
 /// * `Ok(true)` if an ASR service is configured
 /// * `Ok(false)` if no ASR service is configured
-/// * `Err(String)` if there's an error checking configuration
+/// * `Err` with code `keychain_locked` if the API key can't be read until the keychain is unlocked
+/// * `Err(CommandError)` if there's an error checking configuration
 #[tauri::command]
 pub async fn is_transcription_available(
     state: State<'_, TranscriptionState>,
-) -> Result<bool, String> {
-    match get_active_asr_service(&state.storage, &state.keychain).await {
+) -> CommandResult<bool> {
+    match get_active_asr_service(
+        state.storage.as_ref(),
+        state.keychain.as_ref(),
+        &ProviderAsrFactory,
+    )
+    .await
+    {
         Ok(service) => Ok(service.is_configured()),
-        Err(_) => Ok(false),
+        Err(_) => {
+            // A locked keychain hides a saved key; report it instead of "not configured"
+            let active = state.storage.get_active_service_config("asr").await?;
+            match active.map(|config| state.keychain.key_status("asr", &config.provider)) {
+                Some(KeyStatus::Locked) => Err(CommandError::new(
+                    "keychain_locked",
+                    "The system keychain is locked. Please unlock it to use your saved API key.",
+                )),
+                _ => Ok(false),
+            }
+        }
     }
 }
 
+/// Where the duration of a transcription estimate came from
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DurationSource {
+    /// The recording's WAV header
+    AudioFile,
+    /// The meeting's start and end times (recording missing or unreadable)
+    MeetingTimes,
+}
+
+/// Estimated cost and time of transcribing a meeting with the active ASR service
+#[derive(Debug, Serialize)]
+pub struct TranscriptionEstimate {
+    pub provider: String,
+    /// Configured model, or the provider's default
+    pub model: Option<String>,
+    pub duration_ms: i64,
+    pub duration_source: DurationSource,
+    /// None when the model isn't in the price table
+    pub usd_per_minute: Option<f64>,
+    pub estimated_cost_usd: Option<f64>,
+    /// Rough processing time after upload, in seconds
+    pub estimated_processing_secs: Option<u64>,
+}
+
+/// Estimate the cost and duration of transcribing a meeting
+///
+/// Uses the recording's length (or the meeting's start and end times if the file can't
+/// be read) with the list price of the provider and model the meeting would be
+/// transcribed with: its override, then the active ASR service. No API call is made.
+///
+/// # Arguments
+/// * `meeting_id` - The ID of the meeting to estimate
+///
+/// # Returns
+/// * `Ok(TranscriptionEstimate)` with the estimated cost and processing time
+/// * `Err(CommandError)` if the meeting, its duration or the active ASR config is missing
+#[tauri::command]
+pub async fn estimate_transcription(
+    meeting_id: i64,
+    state: State<'_, TranscriptionState>,
+) -> CommandResult<TranscriptionEstimate> {
+    transcription_estimate(state.storage.as_ref(), meeting_id).await
+}
+
+async fn transcription_estimate(
+    storage: &dyn StoragePort,
+    meeting_id: i64,
+) -> CommandResult<TranscriptionEstimate> {
+    let meeting = storage
+        .get_meeting(meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get meeting"))?
+        .ok_or_else(|| CommandError::not_found(format!("Meeting {} not found", meeting_id)))?;
+
+    let file_duration_ms = meeting.audio_file_path.as_deref().and_then(|path| {
+        match crate::commands::meeting::audio_info(Path::new(path)) {
+            Ok(info) => info.duration_ms.filter(|&ms| ms > 0),
+            Err(e) => {
+                log::warn!("Could not read recording header for {}: {}", path, e);
+                None
+            }
+        }
+    });
+    let (duration_ms, duration_source) = match file_duration_ms {
+        Some(ms) => (ms, DurationSource::AudioFile),
+        None => {
+            let end_time = meeting.end_time.ok_or_else(|| {
+                CommandError::invalid_state(
+                    "Meeting has no readable recording and hasn't ended, so its length is unknown",
+                )
+            })?;
+            (
+                (end_time - meeting.start_time).max(0) * 1000,
+                DurationSource::MeetingTimes,
+            )
+        }
+    };
+
+    let selection = asr_selection(storage, meeting_id)
+        .await
+        .map_err(|e| CommandError::from(e).context("Failed to get ASR service config"))?;
+    let provider = selection.provider;
+    let model = selection
+        .model
+        .or_else(|| pricing::default_asr_model(&provider).map(String::from));
+
+    let usd_per_minute =
+        pricing::asr_price(&provider, model.as_deref()).map(|price| price.usd_per_minute);
+
+    Ok(TranscriptionEstimate {
+        estimated_cost_usd: usd_per_minute
+            .map(|rate| pricing::estimate_cost_usd(duration_ms, rate)),
+        estimated_processing_secs: pricing::estimate_processing_secs(&provider, duration_ms),
+        provider,
+        model,
+        duration_ms,
+        duration_source,
+        usd_per_minute,
+    })
+}
+
 /// Delete all transcripts for a meeting
 ///
 /// This allows regenerating transcripts by first deleting existing ones.
@@ -348,7 +1154,7 @@ pub async fn is_transcription_available(
 pub async fn delete_transcripts(
     meeting_id: i64,
     state: State<'_, TranscriptionState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     use crate::ports::storage::StoragePort;
 
     log::info!("Deleting transcripts for meeting {}", meeting_id);
@@ -356,7 +1162,7 @@ pub async fn delete_transcripts(
         .storage
         .delete_transcripts(meeting_id)
         .await
-        .map_err(|e| format!("Failed to delete transcripts: {}", e))
+        .map_err(|e| CommandError::from(e).context("Failed to delete transcripts"))
 }
 
 /// Fetch available models from an ASR provider
@@ -371,7 +1177,7 @@ pub async fn delete_transcripts(
 pub async fn fetch_asr_models(
     provider: String,
     state: State<'_, TranscriptionState>,
-) -> Result<Vec<serde_json::Value>, String> {
+) -> CommandResult<Vec<serde_json::Value>> {
     log::info!("Fetching ASR models for provider: {}", provider);
 
     match provider.as_str() {
@@ -380,17 +1186,17 @@ pub async fn fetch_asr_models(
             let api_key = state
                 .keychain
                 .get_api_key("asr", &provider)
-                .map_err(|e| format!("Failed to get API key: {}", e))?;
+                .map_err(|e| CommandError::from(e).context("Failed to get API key"))?;
 
             if api_key.is_empty() {
-                return Err("Deepgram API key not configured".to_string());
+                return Err(CommandError::config("Deepgram API key not configured"));
             }
 
             let service = crate::adapters::services::asr::deepgram::DeepgramService::new(api_key);
             let models = service
                 .list_models()
                 .await
-                .map_err(|e| format!("Failed to fetch Deepgram models: {}", e))?;
+                .map_err(|e| CommandError::from(e).context("Failed to fetch Deepgram models"))?;
 
             // Deduplicate models by canonical_name (keep the first occurrence)
             let mut seen = std::collections::HashSet::new();
@@ -437,10 +1243,22 @@ pub async fn fetch_asr_models(
                 }),
             ])
         }
-        _ => Err(format!("Unknown ASR provider: {}", provider)),
+        _ => Err(CommandError::invalid_input(format!(
+            "Unknown ASR provider: {}",
+            provider
+        ))),
     }
 }
 
+/// Get what an ASR provider supports (streaming, diarization, audio formats, ...)
+///
+/// Works without an API key, so the settings page can describe providers before
+/// they are configured.
+#[tauri::command]
+pub async fn get_asr_capabilities(provider: String) -> CommandResult<AsrCapabilities> {
+    asr_capabilities(&provider).map_err(|e| CommandError::invalid_input(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -452,4 +1270,396 @@ mod tests {
         assert_eq!(config.language, Some("en".to_string()));
         assert!(config.num_speakers.is_none());
     }
+
+    #[test]
+    fn test_raw_response_path_next_to_recording() {
+        let path = raw_response_path("/data/recordings/meeting_7.wav", 7, "deepgram");
+        assert_eq!(
+            path,
+            PathBuf::from("/data/recordings/meeting_7_deepgram.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_raw_response_provider_off_by_default() {
+        use crate::domain::models::{Meeting, Platform, ServiceConfig, ServiceType};
+        use crate::ports::mocks::MockStorage;
+
+        let storage = MockStorage::new();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        let mut config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string());
+        config.is_active = true;
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(raw_response_provider(&storage, meeting_id).await, None);
+
+        config.settings = Some(r#"{"save_raw_response": true}"#.to_string());
+        storage.save_service_config(&config).await.unwrap();
+        assert_eq!(
+            raw_response_provider(&storage, meeting_id).await.as_deref(),
+            Some("deepgram")
+        );
+
+        // The file is named after the provider the meeting is transcribed with
+        storage
+            .set_meeting_model_override(&MeetingModelOverride {
+                meeting_id,
+                asr_provider: Some("assemblyai".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            raw_response_provider(&storage, meeting_id).await.as_deref(),
+            Some("assemblyai")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_asr_selection_prefers_meeting_override() {
+        use crate::domain::models::{Meeting, Platform, ServiceConfig};
+        use crate::ports::mocks::MockStorage;
+
+        let storage = MockStorage::new();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Zoom, None, None))
+            .await
+            .unwrap();
+        assert!(asr_selection(&storage, meeting_id).await.is_err());
+
+        let active = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"model": "nova-3"}"#.to_string()));
+        storage.save_service_config(&active).await.unwrap();
+        let selection = asr_selection(&storage, meeting_id).await.unwrap();
+        assert_eq!(selection.provider, "deepgram");
+        assert_eq!(selection.model.as_deref(), Some("nova-3"));
+
+        storage
+            .set_meeting_model_override(&MeetingModelOverride {
+                meeting_id,
+                asr_provider: Some("assemblyai".to_string()),
+                asr_model: Some("slam-1".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let selection = asr_selection(&storage, meeting_id).await.unwrap();
+        assert_eq!(selection.provider, "assemblyai");
+        assert_eq!(selection.model.as_deref(), Some("slam-1"));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_uses_meeting_override() {
+        use crate::domain::models::{Meeting, Platform, ServiceConfig};
+        use crate::ports::mocks::MockStorage;
+
+        let storage = MockStorage::new();
+        let mut meeting = Meeting::new(Platform::Zoom, None, None);
+        meeting.end_time = Some(meeting.start_time + 600);
+        meeting.audio_file_path = Some("/nonexistent/meeting.opus".to_string());
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+        let active = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string())
+            .with_active(true)
+            .with_settings(Some(r#"{"model": "nova-3"}"#.to_string()));
+        storage.save_service_config(&active).await.unwrap();
+        storage
+            .set_meeting_model_override(&MeetingModelOverride {
+                meeting_id,
+                asr_provider: Some("assemblyai".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        // An unreadable recording falls back to the meeting's times
+        let estimate = transcription_estimate(&storage, meeting_id).await.unwrap();
+        assert_eq!(estimate.provider, "assemblyai");
+        assert_eq!(estimate.model.as_deref(), Some("best"));
+        assert_eq!(estimate.duration_ms, 600_000);
+        assert!(matches!(
+            estimate.duration_source,
+            DurationSource::MeetingTimes
+        ));
+    }
+
+    #[test]
+    fn test_validate_num_speakers() {
+        assert!(validate_num_speakers(None).is_ok());
+        assert!(validate_num_speakers(Some(3)).is_ok());
+        assert_eq!(
+            validate_num_speakers(Some(0)).unwrap_err().code,
+            "invalid_input"
+        );
+    }
+
+    #[test]
+    fn test_transcription_guard_per_meeting() {
+        let active = ActiveTranscriptions::default();
+
+        let first = TranscriptionGuard::acquire(&active, 1).expect("meeting 1 should be free");
+        assert!(
+            TranscriptionGuard::acquire(&active, 1).is_none(),
+            "Duplicate transcription of the same meeting must be rejected"
+        );
+
+        let second = TranscriptionGuard::acquire(&active, 2);
+        assert!(
+            second.is_some(),
+            "Other meetings may transcribe concurrently"
+        );
+        assert_eq!(active.lock().unwrap().len(), 2);
+
+        drop(first);
+        assert!(!active.lock().unwrap().contains_key(&1));
+        assert!(TranscriptionGuard::acquire(&active, 1).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_transcription_allows_retry() {
+        let active = ActiveTranscriptions::default();
+
+        let guard = TranscriptionGuard::acquire(&active, 1).unwrap();
+        let token = guard.token;
+        let handle = tokio::spawn(async move {
+            let _guard = guard;
+            std::future::pending::<()>().await;
+        });
+        attach_handle(&active, 1, token, handle.abort_handle());
+
+        assert!(abort_transcription(&active, 1));
+        assert!(!abort_transcription(&active, 1), "Nothing left to cancel");
+
+        // A retry started before the aborted task is dropped must keep its marker
+        let retry = TranscriptionGuard::acquire(&active, 1).expect("retry should not be blocked");
+        tokio::task::yield_now().await;
+        assert!(active.lock().unwrap().contains_key(&1));
+
+        drop(retry);
+        assert!(active.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_cancellable_can_be_cancelled() {
+        let active = ActiveTranscriptions::default();
+
+        let guard = TranscriptionGuard::acquire(&active, 1).unwrap();
+        let job = tokio::spawn(run_cancellable(
+            guard,
+            std::future::pending::<CommandResult<()>>(),
+        ));
+        while active.lock().unwrap()[&1].handle.is_none() {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(abort_transcription(&active, 1));
+        let error = job.await.unwrap().unwrap_err();
+        assert_eq!(error.code, "cancelled");
+        assert!(active.lock().unwrap().is_empty());
+
+        // Jobs that finish hand back their result and clear the marker
+        let guard = TranscriptionGuard::acquire(&active, 2).unwrap();
+        assert_eq!(run_cancellable(guard, async { Ok(3) }).await.unwrap(), 3);
+        assert!(active.lock().unwrap().is_empty());
+    }
+
+    fn mock_segment(speaker: &str, start_ms: i64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            text: text.to_string(),
+            start_ms,
+            end_ms: start_ms + 1000,
+            speaker_label: Some(speaker.to_string()),
+            confidence: Some(0.9),
+            language: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_store_and_summarize_with_mock_services() {
+        use crate::commands::llm::{generate_and_store_insights, GenerateMeetingInsightsRequest};
+        use crate::domain::models::{InsightType, Meeting, Platform, ServiceConfig};
+        use crate::ports::mocks::{MockLlmService, MockTranscriptionService};
+        use crate::utils::keychain::MockKeychain;
+
+        let storage = SqliteStorage::new(PathBuf::from(":memory:")).unwrap();
+        storage.run_migrations().unwrap();
+        let keychain = MockKeychain::new();
+        keychain.save_api_key("asr", "deepgram", "asr-key").unwrap();
+        keychain.save_api_key("llm", "openai", "llm-key").unwrap();
+        for (service_type, provider, settings) in [
+            (ServiceType::Asr, "deepgram", None),
+            (ServiceType::Llm, "openai", Some(r#"{"model": "gpt-4o"}"#)),
+        ] {
+            let config = ServiceConfig::new(service_type, provider.to_string())
+                .with_active(true)
+                .with_settings(settings.map(str::to_string));
+            storage.save_service_config(&config).await.unwrap();
+        }
+
+        let mut meeting = Meeting::new(Platform::Zoom, Some("Budget review".to_string()), None);
+        meeting.audio_file_path = Some("/recordings/meeting_1.wav".to_string());
+        meeting.audio_offset_ms = 500;
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+
+        // Transcribe with the mock standing in for the active provider
+        let asr = MockTranscriptionService::new(vec![
+            mock_segment("Speaker 0", 0, "Let's review the budget."),
+            mock_segment("Speaker 1", 1500, "Marketing is over by ten percent."),
+        ])
+        .with_detection("en", 0.92);
+        let service = get_active_asr_service(&storage, &keychain, &asr)
+            .await
+            .unwrap();
+        assert_eq!(
+            asr.created.lock().unwrap().as_slice(),
+            &[("deepgram".to_string(), "asr-key".to_string())]
+        );
+
+        let stored = transcribe_and_store(
+            &storage,
+            service.as_ref(),
+            meeting_id,
+            "/recordings/meeting_1.wav",
+            &TranscriptionConfig::default(),
+            meeting.audio_offset_ms,
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stored, 2);
+        assert_eq!(
+            asr.transcribed_files.lock().unwrap().as_slice(),
+            &["/recordings/meeting_1.wav".to_string()]
+        );
+
+        let transcripts = storage.get_transcripts(meeting_id).await.unwrap();
+        assert_eq!(transcripts.len(), 2);
+        assert_eq!(transcripts[0].timestamp_ms, 500);
+        assert_eq!(transcripts[1].speaker_label.as_deref(), Some("Speaker 1"));
+        let meeting = storage.get_meeting(meeting_id).await.unwrap().unwrap();
+        assert_eq!(meeting.detected_language.as_deref(), Some("en"));
+        assert_eq!(meeting.transcript_confidence, Some(0.92));
+
+        // Summarize the stored transcript with the mock LLM
+        let llm = MockLlmService::new();
+        let request = GenerateMeetingInsightsRequest {
+            meeting_id,
+            provider: None,
+            model: None,
+            insight_types: vec![InsightType::Summary, InsightType::ActionItem],
+            temperature: None,
+            max_tokens: None,
+            json_mode: false,
+            context: None,
+            target_language: None,
+            detail_level: Default::default(),
+            include_timestamps: false,
+        };
+        let response = generate_and_store_insights(&request, &storage, &keychain, &llm)
+            .await
+            .unwrap();
+        assert_eq!(response.insights.len(), 2);
+        assert_eq!(
+            llm.created.lock().unwrap().as_slice(),
+            &[("openai".to_string(), "llm-key".to_string())]
+        );
+        assert!(llm.requests.lock().unwrap()[0]
+            .transcript
+            .contains("Marketing is over by ten percent."));
+
+        let insights = storage.get_insights(meeting_id).await.unwrap();
+        assert_eq!(insights.len(), 2);
+        assert_eq!(
+            insights[0].content,
+            MockLlmService::content_for(&InsightType::Summary)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_transcription_keeps_existing_transcripts() {
+        use crate::domain::models::{Meeting, Platform};
+        use crate::ports::mocks::MockTranscriptionService;
+
+        let storage = SqliteStorage::new(PathBuf::from(":memory:")).unwrap();
+        storage.run_migrations().unwrap();
+        let meeting_id = storage
+            .create_meeting(&Meeting::new(Platform::Teams, None, None))
+            .await
+            .unwrap();
+        let first = MockTranscriptionService::new(vec![mock_segment("Speaker 0", 0, "Hello")]);
+        assert!(transcribe_and_store(
+            &storage,
+            &first,
+            meeting_id,
+            "meeting.wav",
+            &TranscriptionConfig::default(),
+            0,
+            false,
+        )
+        .await
+        .is_ok());
+
+        let failing = MockTranscriptionService::new(Vec::new()).failing("provider unavailable");
+        let result = transcribe_and_store(
+            &storage,
+            &failing,
+            meeting_id,
+            "meeting.wav",
+            &TranscriptionConfig::default(),
+            0,
+            true,
+        )
+        .await;
+        assert!(matches!(result, Err(TranscriptionFailure::Transcribe(_))));
+        assert_eq!(storage.get_transcripts(meeting_id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_rejects_recording_over_upload_limit() {
+        use crate::domain::models::{Meeting, Platform, ServiceConfig};
+        use crate::ports::mocks::MockTranscriptionService;
+        use crate::utils::keychain::MockKeychain;
+
+        let dir = tempfile::tempdir().unwrap();
+        let audio_path = dir.path().join("meeting_1.wav");
+        std::fs::write(&audio_path, vec![0u8; 2048]).unwrap();
+
+        let storage = SqliteStorage::new(PathBuf::from(":memory:")).unwrap();
+        storage.run_migrations().unwrap();
+        let keychain = MockKeychain::new();
+        keychain.save_api_key("asr", "deepgram", "asr-key").unwrap();
+        let config = ServiceConfig::new(ServiceType::Asr, "deepgram".to_string()).with_active(true);
+        storage.save_service_config(&config).await.unwrap();
+        let mut meeting = Meeting::new(Platform::Zoom, None, None);
+        meeting.audio_file_path = Some(audio_path.to_string_lossy().into_owned());
+        let meeting_id = storage.create_meeting(&meeting).await.unwrap();
+
+        let too_small = MockTranscriptionService::new(Vec::new()).with_max_upload_bytes(1000);
+        let error =
+            match prepare_transcription(&storage, &keychain, &too_small, meeting_id, None, None)
+                .await
+            {
+                Ok(_) => panic!("an oversized recording was accepted"),
+                Err(error) => error,
+            };
+        assert_eq!(error.code, "invalid_input");
+        assert!(error.message.contains("2.0 KB"));
+        assert!(error.message.contains("at most 1.0 KB"));
+        assert!(error.message.contains("Split the recording"));
+
+        let large_enough = MockTranscriptionService::new(Vec::new()).with_max_upload_bytes(2048);
+        assert!(
+            prepare_transcription(&storage, &keychain, &large_enough, meeting_id, None, None)
+                .await
+                .is_ok()
+        );
+        let unlimited = MockTranscriptionService::new(Vec::new());
+        assert!(
+            prepare_transcription(&storage, &keychain, &unlimited, meeting_id, None, None)
+                .await
+                .is_ok()
+        );
+    }
 }
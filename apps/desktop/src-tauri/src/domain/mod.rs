@@ -2,6 +2,7 @@
 ///
 /// These models are platform-agnostic and represent core business entities.
 pub mod models;
+pub mod pricing;
 pub mod prompts;
 
 pub use models::{
@@ -4,12 +4,16 @@
 use serde::{Deserialize, Serialize};
 
 /// Represents a meeting platform
+///
+/// Serialized as a plain string; anything other than the built-in platforms
+/// (e.g. "webex", "in-person") is kept verbatim in `Other`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+#[serde(from = "String", into = "String")]
 pub enum Platform {
     Teams,
     Zoom,
     Meet,
+    Other(String),
 }
 
 impl std::fmt::Display for Platform {
@@ -18,6 +22,100 @@ impl std::fmt::Display for Platform {
             Platform::Teams => write!(f, "teams"),
             Platform::Zoom => write!(f, "zoom"),
             Platform::Meet => write!(f, "meet"),
+            Platform::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl std::str::FromStr for Platform {
+    type Err = std::convert::Infallible;
+
+    /// Parse a stored platform name
+    ///
+    /// Never fails: unrecognized names become `Other` so they survive a round trip
+    /// instead of being relabeled as one of the built-in platforms.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "teams" => Platform::Teams,
+            "zoom" => Platform::Zoom,
+            "meet" => Platform::Meet,
+            other => Platform::Other(other.to_string()),
+        })
+    }
+}
+
+impl From<String> for Platform {
+    fn from(s: String) -> Self {
+        let Ok(platform) = s.parse();
+        platform
+    }
+}
+
+impl From<Platform> for String {
+    fn from(platform: Platform) -> Self {
+        platform.to_string()
+    }
+}
+
+/// Outcome of saving a meeting's recording when it stops
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingStatus {
+    /// The recording was saved to `audio_file_path`
+    Recorded,
+    /// Nothing (or too little) was captured to be worth transcribing
+    NoAudio,
+}
+
+impl std::fmt::Display for RecordingStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordingStatus::Recorded => write!(f, "recorded"),
+            RecordingStatus::NoAudio => write!(f, "no_audio"),
+        }
+    }
+}
+
+impl std::str::FromStr for RecordingStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "recorded" => Ok(RecordingStatus::Recorded),
+            "no_audio" => Ok(RecordingStatus::NoAudio),
+            _ => Err(format!("Invalid recording status: {}", s)),
+        }
+    }
+}
+
+/// How a transcript segment was produced
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptSource {
+    /// Transcribed from the saved recording
+    #[default]
+    Batch,
+    /// Live captions from a streaming session
+    Streaming,
+}
+
+impl std::fmt::Display for TranscriptSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscriptSource::Batch => write!(f, "batch"),
+            TranscriptSource::Streaming => write!(f, "streaming"),
+        }
+    }
+}
+
+impl std::str::FromStr for TranscriptSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "batch" => Ok(TranscriptSource::Batch),
+            "streaming" => Ok(TranscriptSource::Streaming),
+            _ => Err(format!("Invalid transcript source: {}", s)),
         }
     }
 }
@@ -33,7 +131,28 @@ pub struct Meeting {
     pub end_time: Option<i64>,
     pub participant_count: Option<i32>,
     pub audio_file_path: Option<String>, // Path to recorded audio file
+    pub transcripts_redacted: bool,      // Whether stored transcripts had PII redacted
+    pub detected_language: Option<String>, // Language detected by the ASR provider
+    pub notes: Option<String>,           // User-authored notes, separate from insights
+    pub audio_offset_ms: i64,            // Leading silence trimmed from the saved recording
+    pub audio_normalized: bool,          // Whether the saved recording was amplified
     pub created_at: i64,
+    pub transcript_confidence: Option<f32>, // Overall ASR confidence (0.0 to 1.0), if reported
+    pub recording_status: Option<RecordingStatus>, // Set once the recording has been saved
+    pub audio_sample_rate: Option<u32>,     // Format of the saved recording file
+    pub audio_channels: Option<u16>,
+    pub audio_bits_per_sample: Option<u16>,
+}
+
+/// A meeting's saved recording file and the format it was written in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedRecording {
+    pub audio_file_path: String,
+    pub audio_offset_ms: i64, // Leading silence trimmed from the recording
+    pub audio_normalized: bool,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits_per_sample: u16,
 }
 
 impl Meeting {
@@ -49,16 +168,71 @@ impl Meeting {
             end_time: None,
             participant_count: None,
             audio_file_path: None,
+            transcripts_redacted: false,
+            detected_language: None,
+            notes: None,
+            audio_offset_ms: 0,
+            audio_normalized: false,
             created_at: now,
+            transcript_confidence: None,
+            recording_status: None,
+            audio_sample_rate: None,
+            audio_channels: None,
+            audio_bits_per_sample: None,
         }
     }
 
-    /// Marks the meeting as ended
-    pub fn end(&mut self) {
-        self.end_time = Some(chrono::Utc::now().timestamp());
+    /// Title for rendering in exports, on one line and without control characters
+    ///
+    /// The stored title is left as the user entered it.
+    pub fn display_title(&self) -> String {
+        let title = self
+            .title
+            .as_deref()
+            .map(|title| collapse_whitespace(&title.replace(char::is_control, " ")))
+            .unwrap_or_default();
+        if title.is_empty() {
+            "Untitled Meeting".to_string()
+        } else {
+            title
+        }
+    }
+
+    /// Title reduced to a fragment that is safe to put in a file name, if anything is left
+    ///
+    /// Path separators, control characters and characters Windows reserves become
+    /// underscores, and leading dots are dropped, so the result can never name a
+    /// directory or escape the one it is joined to.
+    pub fn file_name_title(&self) -> Option<String> {
+        const MAX_CHARS: usize = 60;
+        const RESERVED: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+        let title = self.title.as_deref()?;
+        let cleaned: String = collapse_whitespace(title)
+            .chars()
+            .map(|c| {
+                if c.is_control() || RESERVED.contains(&c) {
+                    '_'
+                } else if c == ' ' {
+                    '-'
+                } else {
+                    c
+                }
+            })
+            .take(MAX_CHARS)
+            .collect();
+        let cleaned = cleaned
+            .trim_start_matches(['.', '-', '_'])
+            .trim_end_matches(['.', '-']);
+        (!cleaned.is_empty()).then(|| cleaned.to_string())
     }
 }
 
+/// Trim and join words with single spaces
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 /// Represents a meeting participant
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Participant {
@@ -80,6 +254,41 @@ impl Participant {
             speaker_label: None,
         }
     }
+
+    /// Match roster entries to a meeting's existing participants
+    ///
+    /// Each entry matches the first unclaimed participant with the same email, or failing
+    /// that the same name (both case-insensitive). Returns the matched participant ID for
+    /// each entry, in order; `existing` should be sorted by ID for stable results.
+    pub fn match_roster(existing: &[Participant], roster: &[Participant]) -> Vec<Option<i64>> {
+        let mut claimed: Vec<i64> = Vec::new();
+        roster
+            .iter()
+            .map(|entry| {
+                let unclaimed = || {
+                    existing
+                        .iter()
+                        .filter(|p| p.id.is_some_and(|id| !claimed.contains(&id)))
+                };
+                let by_email = entry.email.as_deref().and_then(|email| {
+                    unclaimed().find(|p| {
+                        p.email
+                            .as_deref()
+                            .is_some_and(|e| e.eq_ignore_ascii_case(email))
+                    })
+                });
+                let matched = by_email
+                    .or_else(|| {
+                        unclaimed().find(|p| p.name.to_lowercase() == entry.name.to_lowercase())
+                    })
+                    .and_then(|p| p.id);
+                if let Some(id) = matched {
+                    claimed.push(id);
+                }
+                matched
+            })
+            .collect()
+    }
 }
 
 /// Represents a transcript segment
@@ -91,9 +300,14 @@ pub struct Transcript {
     pub participant_name: Option<String>, // Actual participant name if linked
     pub speaker_label: Option<String>,    // "Speaker 1", "Speaker 2", etc. from diarization
     pub timestamp_ms: i64,                // Milliseconds into meeting
+    pub end_timestamp_ms: Option<i64>,    // End of the segment; None for older rows
     pub text: String,
-    pub confidence: Option<f32>, // 0.0 to 1.0
+    pub confidence: Option<f32>,  // 0.0 to 1.0
+    pub language: Option<String>, // Segment language from the provider, e.g. "en"
+    #[serde(default)]
+    pub source: TranscriptSource, // Batch transcription or live streaming
     pub created_at: i64,
+    pub updated_at: Option<i64>, // Set when the text is edited by hand
 }
 
 impl Transcript {
@@ -106,9 +320,13 @@ impl Transcript {
             participant_name: None,
             speaker_label: None,
             timestamp_ms,
+            end_timestamp_ms: None,
             text,
             confidence,
+            language: None,
+            source: TranscriptSource::Batch,
             created_at: chrono::Utc::now().timestamp(),
+            updated_at: None,
         }
     }
 
@@ -127,11 +345,159 @@ impl Transcript {
             participant_name: None,
             speaker_label,
             timestamp_ms,
+            end_timestamp_ms: None,
             text,
             confidence,
+            language: None,
+            source: TranscriptSource::Batch,
             created_at: chrono::Utc::now().timestamp(),
+            updated_at: None,
         }
     }
+
+    /// Whether the segment's `[timestamp_ms, end_timestamp_ms)` range overlaps `[start_ms, end_ms)`
+    ///
+    /// A segment without an end time covers just its start. Segments starting at the same
+    /// time always overlap, so zero-length ranges still match. Mirrors the SQL that
+    /// `upsert_transcript_segment` uses, for the mock storage.
+    #[cfg(test)]
+    pub fn overlaps(&self, start_ms: i64, end_ms: i64) -> bool {
+        let end = self.end_timestamp_ms.unwrap_or(self.timestamp_ms);
+        (self.timestamp_ms < end_ms && end > start_ms) || self.timestamp_ms == start_ms
+    }
+
+    /// Name shown for the segment's speaker: the linked participant, else the diarization label
+    pub fn speaker(&self) -> Option<&str> {
+        self.participant_name
+            .as_deref()
+            .or(self.speaker_label.as_deref())
+    }
+
+    /// Join segments into one text, one line per segment as `[speaker]: text`
+    ///
+    /// Segments without a speaker are emitted as bare text. With `include_timestamps`,
+    /// each line starts with the segment's offset as `[mm:ss]` (`[h:mm:ss]` past an hour).
+    pub fn reconstruct(transcripts: &[Transcript], include_timestamps: bool) -> String {
+        transcripts
+            .iter()
+            .map(|t| {
+                let line = match t.speaker() {
+                    Some(speaker) => format!("[{}]: {}", speaker, t.text),
+                    None => t.text.clone(),
+                };
+                if include_timestamps {
+                    format!("[{}] {}", format_offset(t.timestamp_ms), line)
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Overall confidence below which a transcript is flagged as low quality
+pub const LOW_CONFIDENCE_THRESHOLD: f32 = 0.7;
+
+/// Average ASR confidence of one speaker's segments
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpeakerConfidence {
+    /// None for segments without a speaker
+    pub speaker: Option<String>,
+    /// None when none of the speaker's segments has a score
+    pub average: Option<f32>,
+    pub segment_count: usize,
+}
+
+/// Confidence summary of a meeting's transcript
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptQuality {
+    /// Mean segment confidence; None when no segment has a score
+    pub overall: Option<f32>,
+    /// Overall score reported by the ASR provider, if it gave one
+    pub provider_confidence: Option<f32>,
+    /// Speakers in order of first appearance
+    pub speakers: Vec<SpeakerConfidence>,
+    /// Segments with a confidence score, out of `segment_count`
+    pub scored_segments: usize,
+    pub segment_count: usize,
+    /// Whether the score is low enough to suggest re-recording or another model
+    pub is_low: bool,
+}
+
+impl TranscriptQuality {
+    /// Average the segments' confidence overall and per speaker
+    ///
+    /// Segments without a score (some providers omit it) are left out of the averages
+    /// but still counted. The provider's own score is used when no segment has one.
+    pub fn from_segments(transcripts: &[Transcript], provider_confidence: Option<f32>) -> Self {
+        fn average(scores: impl Iterator<Item = f32>) -> Option<f32> {
+            let (sum, count) = scores.fold((0.0, 0usize), |(sum, n), c| (sum + c, n + 1));
+            (count > 0).then(|| sum / count as f32)
+        }
+
+        let mut speakers: Vec<(Option<&str>, Vec<&Transcript>)> = Vec::new();
+        for transcript in transcripts {
+            let speaker = transcript.speaker();
+            match speakers.iter_mut().find(|(s, _)| *s == speaker) {
+                Some((_, segments)) => segments.push(transcript),
+                None => speakers.push((speaker, vec![transcript])),
+            }
+        }
+
+        let overall = average(transcripts.iter().filter_map(|t| t.confidence));
+        let score = overall.or(provider_confidence);
+        Self {
+            overall,
+            provider_confidence,
+            speakers: speakers
+                .into_iter()
+                .map(|(speaker, segments)| SpeakerConfidence {
+                    speaker: speaker.map(str::to_string),
+                    average: average(segments.iter().filter_map(|t| t.confidence)),
+                    segment_count: segments.len(),
+                })
+                .collect(),
+            scored_segments: transcripts
+                .iter()
+                .filter(|t| t.confidence.is_some())
+                .count(),
+            segment_count: transcripts.len(),
+            is_low: score.is_some_and(|s| s < LOW_CONFIDENCE_THRESHOLD),
+        }
+    }
+}
+
+/// Format a meeting offset in milliseconds as `mm:ss`, or `h:mm:ss` from one hour on
+pub fn format_offset(timestamp_ms: i64) -> String {
+    let total_secs = timestamp_ms.max(0) / 1000;
+    let (hours, minutes, seconds) = (total_secs / 3600, (total_secs % 3600) / 60, total_secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+/// Parse an offset written by [`format_offset`] back to milliseconds
+///
+/// Accepts `mm:ss` and `h:mm:ss`; anything else, such as `Owner` or `12:75`, is None.
+pub fn parse_offset(text: &str) -> Option<i64> {
+    let parts: Vec<&str> = text.trim().split(':').collect();
+    let numbers = parts
+        .iter()
+        .map(|part| {
+            (!part.is_empty() && part.len() <= 2 && part.chars().all(|c| c.is_ascii_digit()))
+                .then(|| part.parse::<i64>().ok())
+                .flatten()
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let (hours, minutes, seconds) = match numbers[..] {
+        [minutes, seconds] => (0, minutes, seconds),
+        [hours, minutes, seconds] if minutes < 60 => (hours, minutes, seconds),
+        _ => return None,
+    };
+    (seconds < 60).then_some(((hours * 60 + minutes) * 60 + seconds) * 1000)
 }
 
 /// Type of insight generated from meeting
@@ -164,6 +530,7 @@ pub struct Insight {
     pub content: String,
     pub metadata: Option<String>, // JSON string for additional data
     pub created_at: i64,
+    pub feedback: Option<bool>, // Accepted or rejected by the user, None until rated
 }
 
 impl Insight {
@@ -176,6 +543,7 @@ impl Insight {
             content,
             metadata: None,
             created_at: chrono::Utc::now().timestamp(),
+            feedback: None,
         }
     }
 }
@@ -237,15 +605,20 @@ impl ServiceConfig {
     }
 }
 
+/// Key in an LLM service config's `settings` JSON holding its model overrides
+pub const MODEL_OVERRIDES_SETTINGS_KEY: &str = "model_overrides";
+
 /// Model configuration override
-/// Allows users to customize model-specific settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Allows users to customize model-specific settings, such as the context window of a
+/// model the provider adapter doesn't know yet. Stored per provider in the LLM service
+/// config's `settings` under [`MODEL_OVERRIDES_SETTINGS_KEY`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ModelOverride {
     pub id: Option<i64>,
-    pub provider: String,              // "openai", "anthropic", "google", "groq"
-    pub model_id: String,              // Model identifier (e.g., "gpt-5", "claude-4")
+    pub provider: String, // "openai", "anthropic", "google", "groq", "mistral"
+    pub model_id: String, // Model identifier (e.g., "gpt-5", "claude-4")
     pub context_window: Option<usize>, // User-configured context window
-    pub notes: Option<String>,         // User notes about this model
+    pub notes: Option<String>, // User notes about this model
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -276,6 +649,139 @@ impl ModelOverride {
         self.notes = Some(notes);
         self
     }
+
+    /// Read a provider's overrides from its service config `settings` JSON
+    ///
+    /// Missing or malformed settings yield no overrides.
+    pub fn list_from_settings(provider: &str, settings: Option<&str>) -> Vec<ModelOverride> {
+        settings
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+            .map(|settings| Self::overrides_in(&settings))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|o| o.provider == provider)
+            .collect()
+    }
+
+    fn overrides_in(settings: &serde_json::Value) -> Vec<ModelOverride> {
+        settings
+            .get(MODEL_OVERRIDES_SETTINGS_KEY)
+            .and_then(|o| serde_json::from_value(o.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Store this override in a service config's `settings` JSON, replacing any previous
+    /// override for the same model, and return the updated settings
+    ///
+    /// Other settings are kept as they are.
+    pub fn save_to_settings(&self, settings: Option<&str>) -> serde_json::Result<String> {
+        let mut settings = match settings {
+            Some(s) => serde_json::from_str::<serde_json::Value>(s)?,
+            None => serde_json::json!({}),
+        };
+        if !settings.is_object() {
+            settings = serde_json::json!({});
+        }
+
+        let mut overrides = Self::overrides_in(&settings);
+        let mut updated = self.clone();
+        updated.updated_at = chrono::Utc::now().timestamp();
+        match overrides
+            .iter_mut()
+            .find(|o| o.provider == self.provider && o.model_id == self.model_id)
+        {
+            Some(existing) => {
+                updated.created_at = existing.created_at;
+                *existing = updated;
+            }
+            None => overrides.push(updated),
+        }
+
+        settings[MODEL_OVERRIDES_SETTINGS_KEY] = serde_json::to_value(overrides)?;
+        serde_json::to_string(&settings)
+    }
+}
+
+impl ServiceConfig {
+    /// Model saved in the `settings` JSON, if any
+    pub fn model(&self) -> Option<String> {
+        let settings: serde_json::Value = serde_json::from_str(self.settings.as_deref()?).ok()?;
+        settings
+            .get("model")
+            .and_then(|m| m.as_str())
+            .filter(|m| !m.is_empty())
+            .map(str::to_string)
+    }
+}
+
+/// ASR and LLM choices for a single meeting that take precedence over the active
+/// service configs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MeetingModelOverride {
+    pub meeting_id: i64,
+    pub asr_provider: Option<String>,
+    pub asr_model: Option<String>,
+    pub llm_provider: Option<String>,
+    pub llm_model: Option<String>,
+}
+
+/// Provider and model chosen for a service, after applying a meeting's override
+#[derive(Debug, Clone)]
+pub struct ModelSelection {
+    pub provider: String,
+    /// None lets the provider adapter pick its default model
+    pub model: Option<String>,
+    /// Saved config of the selected provider, for its other settings
+    pub config: Option<ServiceConfig>,
+}
+
+impl MeetingModelOverride {
+    /// Whether no provider or model is overridden
+    pub fn is_empty(&self) -> bool {
+        self.asr_provider.is_none()
+            && self.asr_model.is_none()
+            && self.llm_provider.is_none()
+            && self.llm_model.is_none()
+    }
+
+    fn for_service(&self, service_type: &ServiceType) -> (Option<&str>, Option<&str>) {
+        match service_type {
+            ServiceType::Asr => (self.asr_provider.as_deref(), self.asr_model.as_deref()),
+            ServiceType::Llm => (self.llm_provider.as_deref(), self.llm_model.as_deref()),
+        }
+    }
+
+    /// Pick the provider and model for a service
+    ///
+    /// The meeting's provider wins over the active config's. The model is taken from,
+    /// in order: the meeting's override, the saved config of the chosen provider, and
+    /// finally left to the adapter default. Returns None when neither the meeting nor
+    /// an active config names a provider.
+    pub fn resolve(
+        meeting_override: Option<&Self>,
+        service_type: &ServiceType,
+        configs: &[ServiceConfig],
+    ) -> Option<ModelSelection> {
+        let (override_provider, override_model) = meeting_override
+            .map(|o| o.for_service(service_type))
+            .unwrap_or_default();
+        let of_type = || configs.iter().filter(|c| &c.service_type == service_type);
+
+        let provider = match override_provider {
+            Some(provider) => provider.to_string(),
+            None => of_type().find(|c| c.is_active)?.provider.clone(),
+        };
+        let config = of_type().find(|c| c.provider == provider).cloned();
+        let model = override_model
+            .map(str::to_string)
+            .or_else(|| config.as_ref().and_then(ServiceConfig::model));
+
+        Some(ModelSelection {
+            provider,
+            model,
+            config,
+        })
+    }
 }
 
 /// Search result for transcripts with meeting context
@@ -303,3 +809,330 @@ pub struct SearchResults {
     pub insights: Vec<InsightSearchResult>,
     pub meetings: Vec<Meeting>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(timestamp_ms: i64, text: &str) -> Transcript {
+        Transcript::new(1, timestamp_ms, text.to_string(), None)
+    }
+
+    fn titled(title: &str) -> Meeting {
+        Meeting::new(Platform::Zoom, Some(title.to_string()), None)
+    }
+
+    #[test]
+    fn test_file_name_title_is_neutralized() {
+        assert_eq!(
+            titled("../../evil").file_name_title().as_deref(),
+            Some("evil")
+        );
+        assert_eq!(
+            titled("Q3 review\nrm -rf /").file_name_title().as_deref(),
+            Some("Q3-review-rm--rf-_")
+        );
+        assert_eq!(
+            titled("Design: <draft>?").file_name_title().as_deref(),
+            Some("Design_-_draft__")
+        );
+        assert_eq!(titled("..").file_name_title(), None);
+        assert_eq!(titled(" \t ").file_name_title(), None);
+        assert_eq!(
+            Meeting::new(Platform::Zoom, None, None).file_name_title(),
+            None
+        );
+
+        for title in ["../../evil", "a/b\\c", "line\r\nbreak", "..\\..\\evil"] {
+            let name = titled(title).file_name_title().unwrap();
+            assert!(!name.contains(['/', '\\', '\n', '\r']), "{:?}", name);
+            assert!(!name.starts_with('.'), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_display_title_is_single_line() {
+        let meeting = titled("Weekly sync\r\n\u{1b}[31mred\u{7}");
+        assert_eq!(meeting.display_title(), "Weekly sync [31mred");
+        // The stored title is untouched
+        assert_eq!(
+            meeting.title.as_deref(),
+            Some("Weekly sync\r\n\u{1b}[31mred\u{7}")
+        );
+
+        assert_eq!(titled("\n\n").display_title(), "Untitled Meeting");
+        assert_eq!(
+            Meeting::new(Platform::Zoom, None, None).display_title(),
+            "Untitled Meeting"
+        );
+    }
+
+    #[test]
+    fn test_transcript_overlaps() {
+        let mut t = segment(1000, "hello");
+        t.end_timestamp_ms = Some(2000);
+        assert!(t.overlaps(1500, 2500));
+        assert!(t.overlaps(0, 1001));
+        assert!(!t.overlaps(2000, 3000), "touching ranges don't overlap");
+        assert!(!t.overlaps(0, 1000));
+
+        // Without an end time the segment covers only its start
+        let point = segment(1000, "hi");
+        assert!(point.overlaps(1000, 1000));
+        assert!(point.overlaps(500, 1500));
+        assert!(!point.overlaps(1001, 1500));
+    }
+
+    #[test]
+    fn test_platform_from_str_round_trips() {
+        for name in ["teams", "zoom", "meet", "webex", "in-person"] {
+            let platform: Platform = name.parse().unwrap();
+            assert_eq!(platform.to_string(), name);
+        }
+        assert_eq!("zoom".parse(), Ok(Platform::Zoom));
+        assert_eq!("Zoom".parse(), Ok(Platform::Other("Zoom".to_string())));
+    }
+
+    #[test]
+    fn test_platform_serializes_as_plain_string() {
+        assert_eq!(serde_json::to_string(&Platform::Zoom).unwrap(), "\"zoom\"");
+        assert_eq!(
+            serde_json::to_string(&Platform::Other("webex".to_string())).unwrap(),
+            "\"webex\""
+        );
+
+        let parsed: Platform = serde_json::from_str("\"in-person\"").unwrap();
+        assert_eq!(parsed, Platform::Other("in-person".to_string()));
+        let parsed: Platform = serde_json::from_str("\"teams\"").unwrap();
+        assert_eq!(parsed, Platform::Teams);
+    }
+
+    #[test]
+    fn test_reconstruct_prefers_participant_name() {
+        let mut t = segment(0, "Hello");
+        t.speaker_label = Some("Speaker 0".to_string());
+        t.participant_name = Some("Alice".to_string());
+
+        assert_eq!(Transcript::reconstruct(&[t], false), "[Alice]: Hello");
+    }
+
+    #[test]
+    fn test_reconstruct_falls_back_to_speaker_label() {
+        let mut t = segment(0, "Hi there");
+        t.speaker_label = Some("Speaker 1".to_string());
+
+        assert_eq!(
+            Transcript::reconstruct(&[t], false),
+            "[Speaker 1]: Hi there"
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_bare_text_without_speaker() {
+        let transcripts = vec![segment(0, "First"), segment(1000, "Second")];
+        assert_eq!(
+            Transcript::reconstruct(&transcripts, false),
+            "First\nSecond"
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_with_timestamps() {
+        let mut first = segment(65_000, "Let's start");
+        first.speaker_label = Some("Speaker 0".to_string());
+        let second = segment(3_725_000, "Wrapping up");
+
+        assert_eq!(
+            Transcript::reconstruct(&[first, second], true),
+            "[01:05] [Speaker 0]: Let's start\n[1:02:05] Wrapping up"
+        );
+    }
+
+    #[test]
+    fn test_parse_offset_round_trips() {
+        for offset_ms in [0, 65_000, 3_599_000, 3_725_000] {
+            assert_eq!(parse_offset(&format_offset(offset_ms)), Some(offset_ms));
+        }
+        assert_eq!(parse_offset("00:12:30"), Some(750_000));
+        assert_eq!(parse_offset("12:75"), None);
+        assert_eq!(parse_offset("1:60:00"), None);
+        assert_eq!(parse_offset("Owner"), None);
+        assert_eq!(parse_offset("12:30:00:00"), None);
+    }
+
+    #[test]
+    fn test_transcript_quality_averages_scored_segments() {
+        let segment = |speaker: Option<&str>, confidence: Option<f32>| {
+            Transcript::with_speaker(
+                1,
+                0,
+                "text".to_string(),
+                confidence,
+                speaker.map(str::to_string),
+            )
+        };
+        let transcripts = vec![
+            segment(Some("Speaker 0"), Some(0.9)),
+            segment(Some("Speaker 1"), Some(0.5)),
+            segment(Some("Speaker 0"), None),
+            segment(Some("Speaker 0"), Some(0.7)),
+            segment(Some("Speaker 2"), None),
+            segment(None, Some(0.6)),
+        ];
+
+        let quality = TranscriptQuality::from_segments(&transcripts, Some(0.95));
+        // (0.9 + 0.5 + 0.7 + 0.6) / 4; unscored segments don't pull the average down
+        assert!((quality.overall.unwrap() - 0.675).abs() < 1e-6);
+        assert_eq!(quality.scored_segments, 4);
+        assert_eq!(quality.segment_count, 6);
+        assert!(quality.is_low);
+
+        let speakers: Vec<_> = quality
+            .speakers
+            .iter()
+            .map(|s| (s.speaker.as_deref(), s.average, s.segment_count))
+            .collect();
+        assert_eq!(speakers.len(), 4);
+        assert_eq!(speakers[0].0, Some("Speaker 0"));
+        assert!((speakers[0].1.unwrap() - 0.8).abs() < 1e-6);
+        assert_eq!(speakers[0].2, 3);
+        assert_eq!(speakers[1], (Some("Speaker 1"), Some(0.5), 1));
+        assert_eq!(speakers[2], (Some("Speaker 2"), None, 1));
+        assert_eq!(speakers[3], (None, Some(0.6), 1));
+    }
+
+    #[test]
+    fn test_transcript_quality_without_scores() {
+        let transcripts = vec![Transcript::new(1, 0, "text".to_string(), None)];
+
+        // Only the provider's score is available
+        let quality = TranscriptQuality::from_segments(&transcripts, Some(0.92));
+        assert_eq!(quality.overall, None);
+        assert!(!quality.is_low);
+
+        let quality = TranscriptQuality::from_segments(&transcripts, Some(0.4));
+        assert!(quality.is_low);
+
+        // No score at all is not flagged
+        let quality = TranscriptQuality::from_segments(&transcripts, None);
+        assert_eq!(quality.overall, None);
+        assert_eq!(quality.scored_segments, 0);
+        assert!(!quality.is_low);
+
+        let quality = TranscriptQuality::from_segments(&[], None);
+        assert!(quality.speakers.is_empty());
+    }
+
+    #[test]
+    fn test_model_overrides_in_settings() {
+        assert!(ModelOverride::list_from_settings("openai", None).is_empty());
+        assert!(ModelOverride::list_from_settings("openai", Some("not json")).is_empty());
+
+        let settings = Some(r#"{"model": "gpt-4o"}"#);
+        let first = ModelOverride::new("openai".to_string(), "gpt-5".to_string())
+            .with_context_window(400_000);
+        let settings = first.save_to_settings(settings).unwrap();
+        let second = ModelOverride::new("openai".to_string(), "o4-mini".to_string())
+            .with_context_window(200_000);
+        let settings = second.save_to_settings(Some(&settings)).unwrap();
+
+        // Saving the same model again replaces its override
+        let replaced = ModelOverride::new("openai".to_string(), "gpt-5".to_string())
+            .with_context_window(272_000)
+            .with_notes("Input limit from the docs".to_string());
+        let settings = replaced.save_to_settings(Some(&settings)).unwrap();
+
+        let overrides = ModelOverride::list_from_settings("openai", Some(&settings));
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides[0].context_window, Some(272_000));
+        assert_eq!(
+            overrides[0].notes.as_deref(),
+            Some("Input limit from the docs")
+        );
+        assert_eq!(overrides[1].model_id, "o4-mini");
+
+        // Other settings survive
+        let config = ServiceConfig::new(ServiceType::Llm, "openai".to_string())
+            .with_settings(Some(settings.clone()));
+        assert_eq!(config.model().as_deref(), Some("gpt-4o"));
+
+        // Overrides belong to one provider
+        assert!(ModelOverride::list_from_settings("groq", Some(&settings)).is_empty());
+    }
+
+    fn config(
+        service_type: ServiceType,
+        provider: &str,
+        model: &str,
+        active: bool,
+    ) -> ServiceConfig {
+        ServiceConfig::new(service_type, provider.to_string())
+            .with_active(active)
+            .with_settings(Some(format!(r#"{{"model":"{}"}}"#, model)))
+    }
+
+    #[test]
+    fn test_model_override_precedence() {
+        let configs = vec![
+            config(ServiceType::Asr, "deepgram", "nova-3", true),
+            config(ServiceType::Asr, "assemblyai", "universal", false),
+            config(ServiceType::Llm, "openai", "gpt-4o", true),
+        ];
+
+        // No override: the active config
+        let selection = MeetingModelOverride::resolve(None, &ServiceType::Asr, &configs).unwrap();
+        assert_eq!(selection.provider, "deepgram");
+        assert_eq!(selection.model.as_deref(), Some("nova-3"));
+
+        // Overriding only the model keeps the active provider
+        let only_model = MeetingModelOverride {
+            meeting_id: 1,
+            llm_model: Some("gpt-4o-mini".to_string()),
+            ..Default::default()
+        };
+        let selection =
+            MeetingModelOverride::resolve(Some(&only_model), &ServiceType::Llm, &configs).unwrap();
+        assert_eq!(selection.provider, "openai");
+        assert_eq!(selection.model.as_deref(), Some("gpt-4o-mini"));
+        // ...and leaves the other service alone
+        let selection =
+            MeetingModelOverride::resolve(Some(&only_model), &ServiceType::Asr, &configs).unwrap();
+        assert_eq!(selection.model.as_deref(), Some("nova-3"));
+
+        // Overriding the provider uses that provider's saved model and settings
+        let provider = MeetingModelOverride {
+            meeting_id: 1,
+            asr_provider: Some("assemblyai".to_string()),
+            ..Default::default()
+        };
+        let selection =
+            MeetingModelOverride::resolve(Some(&provider), &ServiceType::Asr, &configs).unwrap();
+        assert_eq!(selection.provider, "assemblyai");
+        assert_eq!(selection.model.as_deref(), Some("universal"));
+        assert_eq!(selection.config.unwrap().provider, "assemblyai");
+
+        // The meeting's model wins over the provider's saved one
+        let both = MeetingModelOverride {
+            asr_model: Some("slam-1".to_string()),
+            ..provider
+        };
+        let selection =
+            MeetingModelOverride::resolve(Some(&both), &ServiceType::Asr, &configs).unwrap();
+        assert_eq!(selection.model.as_deref(), Some("slam-1"));
+
+        // A provider without a saved config falls back to the adapter default model
+        let unsaved = MeetingModelOverride {
+            meeting_id: 1,
+            llm_provider: Some("groq".to_string()),
+            ..Default::default()
+        };
+        let selection =
+            MeetingModelOverride::resolve(Some(&unsaved), &ServiceType::Llm, &configs).unwrap();
+        assert_eq!(selection.provider, "groq");
+        assert!(selection.model.is_none());
+        assert!(selection.config.is_none());
+
+        // Nothing active and nothing overridden
+        assert!(MeetingModelOverride::resolve(None, &ServiceType::Llm, &configs[..2]).is_none());
+    }
+}
@@ -0,0 +1,146 @@
+//! ASR cost and processing time estimates
+//!
+//! Prices are the providers' published pay-as-you-go rates for pre-recorded audio and
+//! are only meant for rough comparisons before starting a transcription.
+
+/// Per-minute list price of an ASR model family
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AsrPrice {
+    pub provider: &'static str,
+    /// Model name prefix; the longest prefix matching the configured model wins
+    pub model: &'static str,
+    pub usd_per_minute: f64,
+}
+
+/// Known ASR prices in US dollars per minute of audio
+pub const ASR_PRICES: &[AsrPrice] = &[
+    AsrPrice {
+        provider: "deepgram",
+        model: "nova-3",
+        usd_per_minute: 0.0043,
+    },
+    AsrPrice {
+        provider: "deepgram",
+        model: "nova-2",
+        usd_per_minute: 0.0043,
+    },
+    AsrPrice {
+        provider: "deepgram",
+        model: "nova",
+        usd_per_minute: 0.0043,
+    },
+    AsrPrice {
+        provider: "deepgram",
+        model: "enhanced",
+        usd_per_minute: 0.0145,
+    },
+    AsrPrice {
+        provider: "deepgram",
+        model: "base",
+        usd_per_minute: 0.0125,
+    },
+    AsrPrice {
+        provider: "deepgram",
+        model: "whisper",
+        usd_per_minute: 0.0048,
+    },
+    AsrPrice {
+        provider: "assemblyai",
+        model: "best",
+        usd_per_minute: 0.0062, // $0.37/hour
+    },
+    AsrPrice {
+        provider: "assemblyai",
+        model: "universal",
+        usd_per_minute: 0.0025, // $0.15/hour
+    },
+    AsrPrice {
+        provider: "assemblyai",
+        model: "slam-1",
+        usd_per_minute: 0.0045, // $0.27/hour
+    },
+    AsrPrice {
+        provider: "assemblyai",
+        model: "nano",
+        usd_per_minute: 0.002, // $0.12/hour
+    },
+];
+
+/// Model each provider adapter uses when none is configured
+pub fn default_asr_model(provider: &str) -> Option<&'static str> {
+    match provider {
+        "deepgram" => Some("nova-2-meeting"),
+        "assemblyai" => Some("best"),
+        _ => None,
+    }
+}
+
+/// Look up the price for a provider's model, falling back to the provider's default model
+pub fn asr_price(provider: &str, model: Option<&str>) -> Option<&'static AsrPrice> {
+    let model = model.or_else(|| default_asr_model(provider))?;
+    ASR_PRICES
+        .iter()
+        .filter(|p| p.provider == provider && model.starts_with(p.model))
+        .max_by_key(|p| p.model.len())
+}
+
+/// Cost of transcribing `duration_ms` of audio at a per-minute rate
+pub fn estimate_cost_usd(duration_ms: i64, usd_per_minute: f64) -> f64 {
+    duration_ms.max(0) as f64 / 60_000.0 * usd_per_minute
+}
+
+/// Rough wall-clock time for a provider to transcribe `duration_ms` of audio, in seconds
+///
+/// Covers queueing and processing once the upload has finished; upload time depends on
+/// the connection and isn't included.
+pub fn estimate_processing_secs(provider: &str, duration_ms: i64) -> Option<u64> {
+    // (fixed overhead in seconds, processing time as a fraction of audio duration)
+    let (overhead_secs, realtime_fraction) = match provider {
+        "deepgram" => (2.0, 0.01),
+        "assemblyai" => (15.0, 0.25),
+        _ => return None,
+    };
+    let audio_secs = duration_ms.max(0) as f64 / 1000.0;
+    Some((overhead_secs + audio_secs * realtime_fraction).ceil() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asr_price_matches_longest_model_prefix() {
+        let price = asr_price("deepgram", Some("nova-2-meeting")).unwrap();
+        assert_eq!(price.model, "nova-2");
+
+        let price = asr_price("deepgram", Some("enhanced-phonecall")).unwrap();
+        assert_eq!(price.usd_per_minute, 0.0145);
+
+        // Default model when none is configured
+        assert_eq!(asr_price("assemblyai", None).unwrap().model, "best");
+
+        assert!(asr_price("deepgram", Some("custom-model")).is_none());
+        assert!(asr_price("unknown", None).is_none());
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        // One hour of audio
+        let cost = estimate_cost_usd(3_600_000, 0.0043);
+        assert!((cost - 0.258).abs() < 1e-9);
+
+        // 90 seconds is billed as 1.5 minutes
+        let cost = estimate_cost_usd(90_000, 0.0062);
+        assert!((cost - 0.0093).abs() < 1e-9);
+
+        assert_eq!(estimate_cost_usd(0, 0.0043), 0.0);
+        assert_eq!(estimate_cost_usd(-5, 0.0043), 0.0);
+    }
+
+    #[test]
+    fn test_estimate_processing_time() {
+        assert_eq!(estimate_processing_secs("deepgram", 3_600_000), Some(38));
+        assert_eq!(estimate_processing_secs("assemblyai", 600_000), Some(165));
+        assert_eq!(estimate_processing_secs("unknown", 600_000), None);
+    }
+}
@@ -4,6 +4,11 @@
 //! for prompt management.
 
 use crate::domain::models::InsightType;
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Placeholders the LLM adapters substitute into a prompt
+pub const PROMPT_PLACEHOLDERS: &[&str] = &["transcript", "context"];
 
 /// Default prompt templates for each insight type
 pub struct PromptTemplates;
@@ -58,18 +63,18 @@ For each action item, format as follows:
 - **[Owner Name]** - [Clear description of task] - **Due: [Date/Timeframe]** `[Timestamp if mentioned]`
 - If no owner is mentioned, use **[Unassigned]**
 - If no deadline is mentioned, use **Due: TBD** or **Due: ASAP** if urgent
-- Include relevant timestamp where the action item was discussed
+- Include the `[mm:ss]` time where the action item was discussed, when the transcript shows times
 
 Group action items by category if applicable (e.g., Technical, Marketing, Operations)
 
 Example format:
 
 ### Technical Tasks
-- **John** - Complete **API integration** with payment gateway - **Due: Friday, Dec 15** `[00:12:30]`
-- **Sarah** - Review and merge **authentication PR** - **Due: End of week** `[00:18:45]`
+- **John** - Complete **API integration** with payment gateway - **Due: Friday, Dec 15** `[12:30]`
+- **Sarah** - Review and merge **authentication PR** - **Due: End of week** `[18:45]`
 
 ### Marketing Tasks
-- **Michael** - Prepare **Q1 campaign proposal** with budget breakdown - **Due: Next Monday** `[00:25:10]`
+- **Michael** - Prepare **Q1 campaign proposal** with budget breakdown - **Due: Next Monday** `[25:10]`
 - **Unassigned** - Schedule meeting with design team - **Due: This week**
 
 ### Follow-ups
@@ -173,6 +178,19 @@ Example format:
 Focus on concrete, actionable decisions rather than ongoing discussions."#
     }
 
+    /// Get prompt for naming a meeting from its summary or transcript
+    ///
+    /// Not an insight type, so it is not listed in [`PromptTemplates::all`].
+    pub fn title() -> &'static str {
+        r#"Write a concise title for the meeting described below, at most 8 words, naming its main topic.
+
+{transcript}
+
+{context}
+
+Reply with the title only: no quotes, no "Title:" label and no closing punctuation."#
+    }
+
     /// Get all default templates
     pub fn all() -> Vec<(InsightType, &'static str)> {
         vec![
@@ -194,6 +212,119 @@ Focus on concrete, actionable decisions rather than ongoing discussions."#
     }
 }
 
+/// Check that a user-supplied prompt template can be filled in
+///
+/// The template must contain `{transcript}`, and any other `{name}` token must
+/// be one of [`PROMPT_PLACEHOLDERS`]. Braces that don't wrap a plain identifier
+/// (e.g. JSON examples) are left alone.
+pub fn validate_template(template: &str) -> Result<()> {
+    if !template.contains("{transcript}") {
+        return Err(AppError::InvalidInput(
+            "Prompt template must contain the {transcript} placeholder".to_string(),
+        ));
+    }
+
+    let unknown: Vec<&str> = placeholders(template)
+        .filter(|name| !PROMPT_PLACEHOLDERS.contains(name))
+        .collect();
+    if !unknown.is_empty() {
+        let unknown = unknown
+            .iter()
+            .map(|name| format!("{{{}}}", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(AppError::InvalidInput(format!(
+            "Prompt template contains unknown placeholder(s) {}; supported placeholders are {{transcript}} and {{context}}",
+            unknown
+        )));
+    }
+
+    Ok(())
+}
+
+/// Ask for a prompt's response in `target_language` (e.g. "Spanish" or "es")
+///
+/// The instruction is appended, so it applies to custom templates and every provider
+/// alike.
+pub fn with_target_language(prompt: &str, target_language: &str) -> String {
+    format!(
+        "{}\n\nWrite your entire response in {}, even if the transcript is in another language.",
+        prompt.trim_end(),
+        target_language
+    )
+}
+
+/// Ask for each action item to cite when it was discussed
+///
+/// For transcripts reconstructed with timestamps, whose lines start with `[mm:ss]`.
+pub fn with_time_references(prompt: &str) -> String {
+    format!(
+        "{}\n\nEach transcript line starts with the time it was said, as [mm:ss] or [h:mm:ss]. End every action item with the time it was discussed in the same format, e.g. `[12:30]`.",
+        prompt.trim_end()
+    )
+}
+
+/// How long and thorough generated insights should be
+///
+/// A standup needs a few lines where a planning session needs a full write-up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetailLevel {
+    Brief,
+    #[default]
+    Standard,
+    Detailed,
+}
+
+impl DetailLevel {
+    /// Output token budget for one insight at this level
+    pub fn max_tokens(self) -> u32 {
+        match self {
+            DetailLevel::Brief => 1024,
+            DetailLevel::Standard => 2048,
+            DetailLevel::Detailed => 4096,
+        }
+    }
+
+    /// Instruction appended to the prompt; None at the standard level the templates
+    /// are written for
+    pub fn instruction(self) -> Option<&'static str> {
+        match self {
+            DetailLevel::Brief => Some(
+                "Keep your response brief: include only the most important points, in a few short bullets or sentences.",
+            ),
+            DetailLevel::Standard => None,
+            DetailLevel::Detailed => Some(
+                "Be thorough: cover every relevant point in detail, including the context, reasoning and owners the transcript mentions.",
+            ),
+        }
+    }
+}
+
+/// Adjust a prompt's requested length to `level`
+///
+/// Like [`with_target_language`], the instruction is appended so it also applies to
+/// custom templates.
+pub fn with_detail_level(prompt: &str, level: DetailLevel) -> String {
+    match level.instruction() {
+        Some(instruction) => format!("{}\n\n{}", prompt.trim_end(), instruction),
+        None => prompt.to_string(),
+    }
+}
+
+/// Iterate over the `{identifier}` tokens in a template
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template.split('{').skip(1).filter_map(|rest| {
+        let name = &rest[..rest.find('}')?];
+        let is_identifier = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        is_identifier.then_some(name)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,4 +347,65 @@ mod tests {
         let summary = PromptTemplates::for_type(&InsightType::Summary);
         assert_eq!(summary, PromptTemplates::summary());
     }
+
+    #[test]
+    fn test_default_templates_are_valid() {
+        for (_, template) in PromptTemplates::all() {
+            assert!(validate_template(template).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_validate_template_requires_transcript() {
+        let err = validate_template("Summarize this meeting. {context}").unwrap_err();
+        assert!(err.to_string().contains("{transcript}"));
+    }
+
+    #[test]
+    fn test_validate_template_flags_unknown_placeholders() {
+        let err = validate_template("{transcript}\n{speakers} {Title}").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("{speakers}"));
+        assert!(message.contains("{Title}"));
+    }
+
+    #[test]
+    fn test_validate_template_ignores_non_placeholder_braces() {
+        let template = r#"{transcript}
+Respond as JSON: {"items": [{ "owner": "..." }]} or {}"#;
+        assert!(validate_template(template).is_ok());
+    }
+
+    #[test]
+    fn test_with_target_language() {
+        let prompt = with_target_language(PromptTemplates::summary(), "Spanish");
+        assert!(prompt.starts_with(PromptTemplates::summary().trim_end()));
+        assert!(prompt.ends_with(
+            "Write your entire response in Spanish, even if the transcript is in another language."
+        ));
+        assert!(validate_template(&prompt).is_ok());
+    }
+
+    #[test]
+    fn test_detail_level_budget_and_instruction() {
+        assert_eq!(DetailLevel::default(), DetailLevel::Standard);
+        assert_eq!(DetailLevel::Brief.max_tokens(), 1024);
+        assert_eq!(DetailLevel::Standard.max_tokens(), 2048);
+        assert_eq!(DetailLevel::Detailed.max_tokens(), 4096);
+
+        let summary = PromptTemplates::summary();
+        assert_eq!(with_detail_level(summary, DetailLevel::Standard), summary);
+
+        let brief = with_detail_level(summary, DetailLevel::Brief);
+        assert!(brief.starts_with(summary.trim_end()));
+        assert!(brief.ends_with(DetailLevel::Brief.instruction().unwrap()));
+        assert!(validate_template(&brief).is_ok());
+
+        let detailed = with_detail_level(summary, DetailLevel::Detailed);
+        assert!(detailed
+            .ends_with("including the context, reasoning and owners the transcript mentions."));
+
+        let level: DetailLevel = serde_json::from_str(r#""detailed""#).unwrap();
+        assert_eq!(level, DetailLevel::Detailed);
+    }
 }
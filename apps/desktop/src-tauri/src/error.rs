@@ -1,6 +1,7 @@
 /// Error types for Meet Scribe
 ///
 /// Uses thiserror for ergonomic error handling with proper Display implementations.
+use serde::Serialize;
 use thiserror::Error;
 
 /// Main error type for the application
@@ -52,6 +53,27 @@ pub enum AppError {
     Other(String),
 }
 
+impl AppError {
+    /// Stable machine-readable code for the error kind
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Database(_) => "database",
+            AppError::Io(_) => "io",
+            AppError::Serialization(_) => "serialization",
+            AppError::Http(_) => "network",
+            AppError::Keychain(_) | AppError::KeychainError(_) => "keychain",
+            AppError::AudioCapture(_) => "audio_capture",
+            AppError::Detection(_) => "detection",
+            AppError::Transcription(_) => "transcription",
+            AppError::Llm(_) | AppError::LlmService(_) => "llm",
+            AppError::Config(_) => "config",
+            AppError::NotFound(_) => "not_found",
+            AppError::InvalidInput(_) => "invalid_input",
+            AppError::Other(_) => "internal",
+        }
+    }
+}
+
 /// Result type alias for convenience
 pub type Result<T> = std::result::Result<T, AppError>;
 
@@ -61,3 +83,93 @@ impl From<AppError> for String {
         error.to_string()
     }
 }
+
+/// Error returned from Tauri commands
+///
+/// Serialized as `{ code, message }` so the frontend can choose a recovery action
+/// from `code` while still showing `message` to the user.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommandError {
+    pub code: String,
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new("invalid_input", message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new("not_found", message)
+    }
+
+    pub fn config(message: impl Into<String>) -> Self {
+        Self::new("config", message)
+    }
+
+    /// The command can't run in the current state (e.g. nothing active to stop)
+    pub fn invalid_state(message: impl Into<String>) -> Self {
+        Self::new("invalid_state", message)
+    }
+
+    /// The command isn't available on this platform or for this provider
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        Self::new("unsupported", message)
+    }
+
+    /// Prefix the message with what was being attempted, keeping the code
+    pub fn context(mut self, context: &str) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(error: AppError) -> Self {
+        Self::new(error.code(), error.to_string())
+    }
+}
+
+/// Result type for Tauri command handlers
+pub type CommandResult<T> = std::result::Result<T, CommandError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_error_from_app_error() {
+        let error = CommandError::from(AppError::Config("No API key".to_string()));
+        assert_eq!(error.code, "config");
+        assert_eq!(error.message, "Configuration error: No API key");
+
+        let error = CommandError::from(AppError::LlmService("rate limited".to_string()));
+        assert_eq!(error.code, "llm");
+    }
+
+    #[test]
+    fn test_command_error_serialization() {
+        let error = CommandError::not_found("Meeting not found: 7").context("Failed to export");
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "not_found",
+                "message": "Failed to export: Meeting not found: 7"
+            })
+        );
+    }
+}
@@ -9,13 +9,13 @@ mod ports;
 mod utils;
 
 use adapters::storage::SqliteStorage;
-use error::Result;
+use error::{CommandError, CommandResult, Result};
 use ports::storage::StoragePort;
 use std::sync::Arc;
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconEvent},
-    Manager, Runtime,
+    Manager,
 };
 use tokio::sync::Mutex;
 use utils::keychain::KeychainManager;
@@ -36,6 +36,7 @@ pub struct AppState {
     pub keychain: Arc<KeychainManager>,
     pub audio_capture: Arc<Mutex<AudioCapture>>,
     pub current_meeting_id: Arc<Mutex<Option<i64>>>,
+    pub tray_ticker: Arc<utils::tray::TrayTicker>,
 }
 
 /// Initialize the application
@@ -64,6 +65,23 @@ fn initialize_app(
     // Run migrations
     storage.run_migrations()?;
 
+    // Route provider requests through the saved proxy, if any
+    match tauri::async_runtime::block_on(commands::config::saved_proxy_config(&storage)) {
+        Ok(proxy) => {
+            if proxy.url.is_some() {
+                log::info!("Using the proxy from settings for provider requests");
+            }
+            utils::http::set_proxy_config(proxy);
+        }
+        Err(e) => log::warn!("Failed to load proxy settings: {}", e),
+    }
+
+    // Hold provider requests to the saved per-provider limits
+    match tauri::async_runtime::block_on(commands::config::saved_rate_limits(&storage)) {
+        Ok(limits) => utils::rate_limit::set_rate_limits(limits),
+        Err(e) => log::warn!("Failed to load rate limit settings: {}", e),
+    }
+
     let storage_arc = Arc::new(storage);
     let keychain_arc = Arc::new(KeychainManager::new());
 
@@ -72,12 +90,13 @@ fn initialize_app(
         keychain: Arc::clone(&keychain_arc),
         audio_capture: Arc::new(Mutex::new(AudioCapture::new())),
         current_meeting_id: Arc::new(Mutex::new(None)),
+        tray_ticker: Arc::new(Mutex::new(None)),
     };
 
     let transcription_state = commands::transcription::TranscriptionState {
         storage: Arc::clone(&storage_arc),
         keychain: Arc::clone(&keychain_arc),
-        current_transcription: Arc::new(Mutex::new(None)),
+        active_transcriptions: Default::default(),
     };
 
     let streaming_state = commands::streaming::StreamingTranscriptionState::new();
@@ -93,33 +112,48 @@ fn get_version() -> String {
 
 /// Example Tauri command - checks database health
 #[tauri::command]
-async fn check_db_health(state: tauri::State<'_, AppState>) -> std::result::Result<String, String> {
+async fn check_db_health(state: tauri::State<'_, AppState>) -> CommandResult<String> {
     // Simple health check - try to list meetings
-    match state.storage.list_meetings(Some(1), Some(0)).await {
-        Ok(_) => Ok("Database is healthy".to_string()),
-        Err(e) => Err(e.to_string()),
-    }
+    state
+        .storage
+        .list_meetings(Some(1), Some(0))
+        .await
+        .map_err(|e| CommandError::from(e).context("Database health check failed"))?;
+    Ok("Database is healthy".to_string())
 }
 
 /// Update the tray icon tooltip with recording status
+///
+/// While recording, the tooltip shows the elapsed time of the active meeting.
 #[tauri::command]
 async fn update_tray_status(
     app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
     is_recording: bool,
-) -> std::result::Result<(), String> {
-    if let Some(tray) = app.tray_by_id("main") {
-        let tooltip = if is_recording {
-            "Meet Scribe - Recording..."
-        } else {
-            "Meet Scribe - Idle"
-        };
-        tray.set_tooltip(Some(tooltip)).map_err(|e| e.to_string())?;
+) -> CommandResult<()> {
+    if !is_recording {
+        utils::tray::stop_recording_ticker(&app, &state.tray_ticker).await;
+        return Ok(());
+    }
+
+    let current_meeting_id = *state.current_meeting_id.lock().await;
+    if let Some(meeting_id) = current_meeting_id {
+        let meeting = state
+            .storage
+            .get_meeting(meeting_id)
+            .await
+            .map_err(|e| CommandError::from(e).context("Failed to get meeting"))?
+            .ok_or_else(|| CommandError::not_found(format!("Meeting not found: {}", meeting_id)))?;
+        utils::tray::start_recording_ticker(app, &state.tray_ticker, meeting.start_time).await;
+    } else if let Some(tray) = app.tray_by_id("main") {
+        tray.set_tooltip(Some("Meet Scribe - Recording..."))
+            .map_err(|e| CommandError::from(error::AppError::Other(e.to_string())))?;
     }
     Ok(())
 }
 
 /// Setup system tray menu
-fn setup_tray_menu<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
+fn setup_tray_menu(app: &tauri::AppHandle) -> tauri::Result<()> {
     let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
     let hide_item = MenuItem::with_id(app, "hide", "Hide Window", true, None::<&str>)?;
     let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
@@ -159,7 +193,14 @@ fn setup_tray_menu<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
             }
         }
         "quit" => {
-            app.exit(0);
+            // Save any recording in progress before exiting
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = commands::meeting::shutdown(&app).await {
+                    log::error!("Failed to stop recording before quitting: {}", e);
+                }
+                app.exit(0);
+            });
         }
         _ => {}
     });
@@ -168,13 +209,13 @@ fn setup_tray_menu<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
 }
 
 fn main() {
-    // Initialize logger
-    env_logger::init();
-
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
         .setup(|app| {
+            // Log to a rotating file next to the database (and stderr in debug builds)
+            utils::logging::init(&app.path().app_data_dir()?);
+
             // Initialize app state
             let (app_state, transcription_state, streaming_state) = initialize_app(app.handle())?;
             app.manage(app_state);
@@ -198,18 +239,29 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             get_version,
             check_db_health,
+            commands::diagnostics::run_diagnostics,
+            commands::diagnostics::get_log_path,
             update_tray_status,
             // Config commands
             commands::config::save_api_key,
             commands::config::get_api_key_status,
+            commands::config::keychain_status,
             commands::config::delete_api_key,
+            commands::config::test_api_key,
             commands::config::save_service_config,
             commands::config::get_service_config,
             commands::config::get_active_service_config,
             commands::config::list_service_configs,
+            commands::config::list_service_configs_with_keys,
             commands::config::activate_service,
+            commands::config::get_proxy_config,
+            commands::config::save_proxy_config,
+            commands::config::get_rate_limits,
+            commands::config::save_rate_limits,
             // Export commands
             commands::export::export_meeting,
+            commands::export::export_meeting_json,
+            commands::export::import_meeting_json,
             // Search commands
             commands::search::search_all,
             commands::search::search_transcripts,
@@ -218,13 +270,21 @@ fn main() {
             // Meeting commands
             commands::meeting::start_meeting,
             commands::meeting::stop_meeting,
+            commands::meeting::stop_all,
             commands::meeting::get_meeting_status,
             commands::meeting::get_audio_capture_status,
             commands::meeting::list_audio_devices,
             commands::meeting::list_speaker_devices,
             commands::meeting::list_microphone_devices,
+            commands::meeting::list_audio_devices_detailed,
+            commands::meeting::refresh_audio_devices,
             commands::meeting::get_meeting_history,
             commands::meeting::get_meeting,
+            commands::meeting::get_audio_info,
+            commands::meeting::import_meeting,
+            commands::meeting::update_meeting_notes,
+            commands::meeting::get_meeting_model_override,
+            commands::meeting::set_meeting_model_override,
             commands::meeting::delete_meeting,
             commands::meeting::test_speaker_capture,
             commands::meeting::test_microphone_capture,
@@ -233,11 +293,21 @@ fn main() {
             commands::meeting::play_test_tone,
             // Transcription commands (batch)
             commands::transcription::start_transcription,
+            commands::transcription::retranscribe,
+            commands::transcription::estimate_transcription,
             commands::transcription::get_transcription_status,
+            commands::transcription::cancel_transcription,
             commands::transcription::get_transcripts,
+            commands::transcription::get_transcript_quality,
+            commands::transcription::edit_transcript,
+            commands::transcription::delete_transcript,
             commands::transcription::is_transcription_available,
+            commands::transcription::get_raw_response_path,
             commands::transcription::delete_transcripts,
             commands::transcription::fetch_asr_models,
+            commands::transcription::get_asr_capabilities,
+            // Transcribe and generate insights in one go
+            commands::processing::process_meeting,
             // Streaming transcription commands (real-time)
             commands::streaming::start_streaming_transcription,
             commands::streaming::stop_streaming_transcription,
@@ -245,20 +315,34 @@ fn main() {
             commands::streaming::get_streaming_transcription_status,
             // LLM commands
             commands::llm::fetch_llm_models,
+            commands::llm::get_model_overrides,
+            commands::llm::save_model_override,
             commands::llm::save_llm_api_key,
             commands::llm::check_llm_api_key,
             commands::llm::delete_llm_api_key,
             commands::llm::generate_insights,
             commands::llm::get_default_prompts,
+            commands::llm::get_prompt_template,
+            commands::llm::save_prompt_template,
+            commands::llm::reset_prompt_template,
             commands::llm::list_llm_providers,
             commands::llm::generate_meeting_insights,
+            commands::llm::regenerate_insight_types,
+            commands::llm::generate_meeting_summary,
+            commands::llm::generate_meeting_title,
             commands::llm::get_meeting_insights,
             commands::llm::update_insight,
+            commands::llm::set_insight_feedback,
+            commands::llm::get_insight,
             commands::llm::delete_meeting_insights,
             // Participant commands
             commands::participant::get_speaker_summary,
+            commands::participant::get_talk_time,
             commands::participant::link_speaker_to_participant,
+            commands::participant::assign_speakers,
+            commands::participant::set_participants,
             commands::participant::unlink_speaker,
+            commands::participant::merge_speakers,
             commands::participant::delete_meeting_participants,
             // Detection commands (UI Automation / AT-SPI)
             commands::detection::list_active_meetings,
@@ -268,7 +352,18 @@ fn main() {
             commands::detection::get_detection_info,
             commands::detection::import_detected_participants,
             commands::detection::detect_and_import_participants,
+            // Storage commands
+            commands::storage::get_storage_usage,
+            commands::storage::cleanup_recordings,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Covers exits that don't go through the tray's Quit item; a no-op otherwise
+            if let tauri::RunEvent::Exit = event {
+                if let Err(e) = tauri::async_runtime::block_on(commands::meeting::shutdown(app)) {
+                    log::error!("Failed to stop recording on exit: {}", e);
+                }
+            }
+        });
 }
@@ -2,8 +2,11 @@
 ///
 /// Defines the interface for capturing system audio streams.
 /// Platform-specific implementations in adapters/audio/
-use crate::error::Result;
+use crate::error::{AppError, Result};
 use async_trait::async_trait;
+use serde::Serialize;
+#[cfg(any(target_os = "windows", test))]
+use std::time::Duration;
 
 /// Represents audio format specifications
 ///
@@ -12,13 +15,28 @@ use async_trait::async_trait;
 /// - **Linux PulseAudio**: Fixed at 44100 Hz, stereo, 16-bit signed
 ///
 /// The default values are placeholder values used before audio capture starts.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct AudioFormat {
     pub sample_rate: u32,
     pub channels: u16,
     pub bits_per_sample: u16,
 }
 
+impl std::fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let channels = match self.channels {
+            1 => "mono".to_string(),
+            2 => "stereo".to_string(),
+            n => format!("{} channels", n),
+        };
+        write!(
+            f,
+            "{} Hz {} {}-bit",
+            self.sample_rate, channels, self.bits_per_sample
+        )
+    }
+}
+
 impl Default for AudioFormat {
     fn default() -> Self {
         Self {
@@ -29,6 +47,78 @@ impl Default for AudioFormat {
     }
 }
 
+/// Sample bit depths the capture adapters can convert
+#[cfg(any(target_os = "windows", test))]
+pub const SUPPORTED_BITS_PER_SAMPLE: [u16; 3] = [16, 24, 32];
+
+/// Reject a device format with a bit depth the capture adapters can't convert
+///
+/// Capturing it anyway would save a meeting with no samples.
+#[cfg(any(target_os = "windows", test))]
+pub fn check_bit_depth(bits_per_sample: u16) -> Result<()> {
+    if SUPPORTED_BITS_PER_SAMPLE.contains(&bits_per_sample) {
+        Ok(())
+    } else {
+        Err(AppError::AudioCapture(format!(
+            "Unsupported audio format: the device uses {}-bit samples, only 16, 24 and 32-bit are supported",
+            bits_per_sample
+        )))
+    }
+}
+
+/// Whether an audio device plays or records sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceKind {
+    Speaker,
+    Microphone,
+}
+
+/// ID of the default speaker entry in detailed device lists
+pub const DEFAULT_DEVICE_ID: &str = "default";
+
+/// An audio device along with its stable OS identifier
+///
+/// `index` is the number in the "N: Name" entries of `list_speaker_devices` and
+/// `list_microphone_devices`, which shifts as devices are plugged in or removed. `id`
+/// doesn't: it is the WASAPI endpoint ID on Windows and the sink or source name on
+/// Linux.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AudioDeviceInfo {
+    pub index: usize,
+    pub id: String,
+    pub name: String,
+    pub kind: DeviceKind,
+}
+
+/// A device chosen for capture
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceSelection {
+    /// The system default device
+    Default,
+    /// Position in the current device list, from an "N: Name" entry
+    Index(usize),
+    /// Stable device ID from `list_devices_detailed`, resolved when capture starts
+    Id(String),
+}
+
+impl DeviceSelection {
+    /// Parse a device string sent by the frontend
+    ///
+    /// "N: Name" entries select by index and anything else is taken as a device ID.
+    /// A blank string or [`DEFAULT_DEVICE_ID`] selects the default device.
+    pub fn parse(device: &str) -> Self {
+        let device = device.trim();
+        if device.is_empty() || device == DEFAULT_DEVICE_ID {
+            return Self::Default;
+        }
+        match device.split(':').next().map(|index| index.trim().parse()) {
+            Some(Ok(index)) => Self::Index(index),
+            _ => Self::Id(device.to_string()),
+        }
+    }
+}
+
 /// Audio buffer containing captured audio samples
 #[derive(Debug)]
 pub struct AudioBuffer {
@@ -36,6 +126,135 @@ pub struct AudioBuffer {
     pub format: AudioFormat,
 }
 
+/// Smallest capture buffer accepted; shorter buffers overrun between polls
+pub const MIN_CAPTURE_BUFFER_MS: u32 = 20;
+/// Largest capture buffer accepted
+pub const MAX_CAPTURE_BUFFER_MS: u32 = 2000;
+/// Longest sleep accepted between capture buffer reads
+pub const MAX_CAPTURE_POLL_MS: u32 = 100;
+
+/// Kind of capture stream being opened, which decides how it is opened
+#[cfg(any(target_os = "windows", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureStreamKind {
+    /// What an output device plays, in the device's mix format
+    Loopback,
+    /// What an output device (or app) plays, converted to a requested format
+    ConvertedLoopback,
+    /// An input device, in the device's mix format
+    Microphone,
+    /// An input device, converted to a requested format
+    ConvertedMicrophone,
+}
+
+/// How a capture stream is opened with the audio API
+#[cfg(any(target_os = "windows", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureStreamParams {
+    /// Buffer duration in the 100-nanosecond units WASAPI uses
+    pub buffer_duration_hns: i64,
+    /// Capture what an output device plays rather than an input device
+    pub loopback: bool,
+    /// Have the audio API convert to the requested format
+    pub auto_convert: bool,
+}
+
+/// Capture buffer size and polling interval
+///
+/// A smaller buffer lowers the latency of live transcription at the cost of more
+/// frequent reads. The defaults (1 s buffer, 10 ms polling) suit recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureTiming {
+    /// Length of the device buffer requested from the audio API
+    pub buffer_duration_ms: u32,
+    /// Sleep between reads in the capture loop
+    pub poll_interval_ms: u32,
+}
+
+impl Default for CaptureTiming {
+    fn default() -> Self {
+        Self {
+            buffer_duration_ms: 1000,
+            poll_interval_ms: 10,
+        }
+    }
+}
+
+impl CaptureTiming {
+    /// Create a timing, rejecting values that would make the capture glitch
+    ///
+    /// The loop must poll at least twice per buffer length, or the buffer fills up
+    /// between reads and audio is dropped.
+    pub fn new(buffer_duration_ms: u32, poll_interval_ms: u32) -> Result<Self> {
+        if !(MIN_CAPTURE_BUFFER_MS..=MAX_CAPTURE_BUFFER_MS).contains(&buffer_duration_ms) {
+            return Err(AppError::InvalidInput(format!(
+                "Capture buffer must be between {} and {} ms, got {}",
+                MIN_CAPTURE_BUFFER_MS, MAX_CAPTURE_BUFFER_MS, buffer_duration_ms
+            )));
+        }
+        if !(1..=MAX_CAPTURE_POLL_MS).contains(&poll_interval_ms) {
+            return Err(AppError::InvalidInput(format!(
+                "Capture polling interval must be between 1 and {} ms, got {}",
+                MAX_CAPTURE_POLL_MS, poll_interval_ms
+            )));
+        }
+        if poll_interval_ms * 2 > buffer_duration_ms {
+            return Err(AppError::InvalidInput(format!(
+                "Capture polling interval ({} ms) must be at most half the buffer ({} ms)",
+                poll_interval_ms, buffer_duration_ms
+            )));
+        }
+        Ok(Self {
+            buffer_duration_ms,
+            poll_interval_ms,
+        })
+    }
+
+    /// Read `capture_buffer_ms` / `capture_poll_ms` from a settings object
+    ///
+    /// Missing keys keep their defaults.
+    pub fn from_settings(settings: &serde_json::Value) -> Result<Self> {
+        let defaults = Self::default();
+        let value = |key: &str, default: u32| {
+            settings
+                .get(key)
+                .and_then(|v| v.as_u64())
+                .map_or(default, |v| v.min(u32::MAX as u64) as u32)
+        };
+        Self::new(
+            value("capture_buffer_ms", defaults.buffer_duration_ms),
+            value("capture_poll_ms", defaults.poll_interval_ms),
+        )
+    }
+
+    /// Buffer duration in the 100-nanosecond units WASAPI uses
+    #[cfg(any(target_os = "windows", test))]
+    pub fn buffer_duration_hns(&self) -> i64 {
+        self.buffer_duration_ms as i64 * 10_000
+    }
+
+    #[cfg(any(target_os = "windows", test))]
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms as u64)
+    }
+
+    /// Parameters for opening a stream of `kind` with this buffer size
+    #[cfg(any(target_os = "windows", test))]
+    pub fn stream_params(&self, kind: CaptureStreamKind) -> CaptureStreamParams {
+        CaptureStreamParams {
+            buffer_duration_hns: self.buffer_duration_hns(),
+            loopback: matches!(
+                kind,
+                CaptureStreamKind::Loopback | CaptureStreamKind::ConvertedLoopback
+            ),
+            auto_convert: matches!(
+                kind,
+                CaptureStreamKind::ConvertedLoopback | CaptureStreamKind::ConvertedMicrophone
+            ),
+        }
+    }
+}
+
 /// Port trait for audio capture functionality
 #[async_trait]
 pub trait AudioCapturePort: Send + Sync {
@@ -48,17 +267,36 @@ pub trait AudioCapturePort: Send + Sync {
     /// Lists available microphone devices
     async fn list_microphone_devices(&self) -> Result<Vec<String>>;
 
+    /// Lists speaker and microphone devices with their stable OS device IDs
+    ///
+    /// A device selected by ID stays selected when other devices come and go.
+    async fn list_devices_detailed(&self) -> Result<Vec<AudioDeviceInfo>>;
+
     /// Starts capturing audio from the specified device
+    /// The device is an "N: Name" entry or a device ID (see [`DeviceSelection::parse`])
+    /// `preferred_format` requests audio in that format (e.g. the streaming format) so it
+    /// needn't be resampled later. Adapters fall back to the device's own format when the
+    /// platform can't deliver it; `get_format` reports the format in use.
     /// Returns immediately, audio is captured in background
-    async fn start_capture(&mut self, device_name: Option<String>) -> Result<()>;
+    async fn start_capture(
+        &mut self,
+        device_name: Option<String>,
+        preferred_format: Option<AudioFormat>,
+    ) -> Result<()>;
 
     /// Starts dual-capture from both speaker and microphone devices
     /// Captures audio from both devices simultaneously and mixes them together
+    /// `process_name` limits the speaker side to that app's audio (e.g. "zoom") where the
+    /// platform supports it; otherwise, or when the app isn't running, the speaker
+    /// (the default one if none is given) is captured.
+    /// `preferred_format` works as in [`start_capture`](Self::start_capture), for both devices.
     /// Returns immediately, audio is captured in background
     async fn start_dual_capture(
         &mut self,
         speaker_device: Option<String>,
         microphone_device: Option<String>,
+        process_name: Option<String>,
+        preferred_format: Option<AudioFormat>,
     ) -> Result<()>;
 
     /// Stops audio capture
@@ -77,4 +315,111 @@ pub trait AudioCapturePort: Send + Sync {
     /// Gets the current audio level (0.0 to 1.0) without consuming the buffer
     /// Used for visual feedback during audio testing
     fn get_current_level(&self) -> f32;
+
+    /// Sets the buffer size and polling interval used by the next capture
+    ///
+    /// Backends without a configurable buffer ignore this.
+    fn set_capture_timing(&mut self, _timing: CaptureTiming) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_capture_timing_matches_one_second_buffer() {
+        let timing = CaptureTiming::default();
+        assert_eq!(timing.buffer_duration_hns(), 10_000_000);
+        assert_eq!(timing.poll_interval(), Duration::from_millis(10));
+        assert_eq!(
+            CaptureTiming::from_settings(&serde_json::json!({})).unwrap(),
+            timing
+        );
+    }
+
+    #[test]
+    fn test_capture_timing_from_settings() {
+        let timing = CaptureTiming::from_settings(&serde_json::json!({
+            "capture_buffer_ms": 100,
+            "capture_poll_ms": 5
+        }))
+        .unwrap();
+        assert_eq!(timing.buffer_duration_hns(), 1_000_000);
+        assert_eq!(timing.poll_interval_ms, 5);
+    }
+
+    #[test]
+    fn test_stream_params_use_configured_buffer() {
+        let timing = CaptureTiming::new(100, 5).unwrap();
+        for kind in [
+            CaptureStreamKind::Loopback,
+            CaptureStreamKind::ConvertedLoopback,
+            CaptureStreamKind::Microphone,
+            CaptureStreamKind::ConvertedMicrophone,
+        ] {
+            assert_eq!(timing.stream_params(kind).buffer_duration_hns, 1_000_000);
+        }
+
+        let loopback = timing.stream_params(CaptureStreamKind::Loopback);
+        assert!(loopback.loopback && !loopback.auto_convert);
+        let converted = timing.stream_params(CaptureStreamKind::ConvertedLoopback);
+        assert!(converted.loopback && converted.auto_convert);
+        let microphone = timing.stream_params(CaptureStreamKind::Microphone);
+        assert!(!microphone.loopback && !microphone.auto_convert);
+        let converted_microphone = timing.stream_params(CaptureStreamKind::ConvertedMicrophone);
+        assert!(!converted_microphone.loopback && converted_microphone.auto_convert);
+    }
+
+    #[test]
+    fn test_capture_timing_bounds() {
+        assert!(CaptureTiming::new(MIN_CAPTURE_BUFFER_MS, 10).is_ok());
+        assert!(CaptureTiming::new(MIN_CAPTURE_BUFFER_MS - 1, 5).is_err());
+        assert!(CaptureTiming::new(MAX_CAPTURE_BUFFER_MS + 1, 10).is_err());
+        assert!(CaptureTiming::new(1000, 0).is_err());
+        assert!(CaptureTiming::new(1000, MAX_CAPTURE_POLL_MS + 1).is_err());
+        // Polling must keep up with the buffer
+        assert!(CaptureTiming::new(30, 20).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_bit_depth_rejected() {
+        for bits in SUPPORTED_BITS_PER_SAMPLE {
+            assert!(check_bit_depth(bits).is_ok());
+        }
+        for bits in [0, 8, 20, 64] {
+            match check_bit_depth(bits) {
+                Err(AppError::AudioCapture(message)) => {
+                    assert!(message.contains(&format!("{}-bit", bits)))
+                }
+                other => panic!("{}-bit samples should be rejected, got {:?}", bits, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_device_selection_parse() {
+        assert_eq!(DeviceSelection::parse(""), DeviceSelection::Default);
+        assert_eq!(DeviceSelection::parse("default"), DeviceSelection::Default);
+        assert_eq!(
+            DeviceSelection::parse("1: Realtek Audio (Speaker)"),
+            DeviceSelection::Index(1)
+        );
+        assert_eq!(DeviceSelection::parse("0"), DeviceSelection::Index(0));
+
+        // WASAPI endpoint ID
+        let wasapi_id = "{0.0.0.00000000}.{8f2f1c0e-5b7a-4b7e-9a55-3d7b3c2e1a90}";
+        assert_eq!(
+            DeviceSelection::parse(wasapi_id),
+            DeviceSelection::Id(wasapi_id.to_string())
+        );
+        // PulseAudio sink and source names
+        assert_eq!(
+            DeviceSelection::parse(" alsa_output.pci-0000_00_1f.3.analog-stereo "),
+            DeviceSelection::Id("alsa_output.pci-0000_00_1f.3.analog-stereo".to_string())
+        );
+        assert_eq!(
+            DeviceSelection::parse("bluez_input.00_1B_66_AB_CD_EF.0"),
+            DeviceSelection::Id("bluez_input.00_1B_66_AB_CD_EF.0".to_string())
+        );
+    }
 }
@@ -42,6 +42,12 @@ pub struct LlmConfig {
 
     /// Provider-specific settings as JSON
     pub additional_settings: Option<serde_json::Value>,
+
+    /// Ask the provider to respond with a single JSON object
+    /// Only enable for models that support structured output; when off, responses are
+    /// free text.
+    #[serde(default)]
+    pub json_mode: bool,
 }
 
 impl Default for LlmConfig {
@@ -51,6 +57,7 @@ impl Default for LlmConfig {
             temperature: Some(0.3), // Lower temperature for more focused outputs
             max_tokens: Some(2000),
             additional_settings: None,
+            json_mode: false,
         }
     }
 }
@@ -95,3 +102,17 @@ pub trait LlmServicePort: Send + Sync {
     /// Check if the service is configured (has API key)
     fn is_configured(&self) -> bool;
 }
+
+/// Builds LLM services for a provider, so tests can substitute canned services
+pub trait LlmServiceFactory: Send + Sync {
+    /// Create the service for `provider`
+    ///
+    /// `settings` is the provider's saved settings, for providers that need more than a
+    /// key (Azure OpenAI's endpoint and deployment).
+    fn create(
+        &self,
+        provider: &str,
+        api_key: String,
+        settings: Option<&serde_json::Value>,
+    ) -> Result<Box<dyn LlmServicePort>>;
+}
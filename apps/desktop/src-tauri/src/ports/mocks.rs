@@ -1,13 +1,24 @@
 //! Mock implementations for testing
 
 use crate::domain::models::{
-    Insight, InsightSearchResult, Meeting, Participant, SearchResults, ServiceConfig, Transcript,
-    TranscriptSearchResult,
+    Insight, InsightSearchResult, InsightType, Meeting, MeetingModelOverride, ModelOverride,
+    Participant, RecordingStatus, SavedRecording, SearchResults, ServiceConfig, ServiceType,
+    Transcript, TranscriptSearchResult, TranscriptSource,
+};
+use crate::error::{AppError, Result};
+use crate::ports::audio::{
+    AudioBuffer, AudioCapturePort, AudioDeviceInfo, AudioFormat, DeviceKind,
+};
+use crate::ports::llm::{
+    GeneratedInsight, InsightRequest, LlmConfig, LlmServiceFactory, LlmServicePort, ModelInfo,
 };
-use crate::error::Result;
 use crate::ports::storage::StoragePort;
+use crate::ports::transcription::{
+    StreamingSession, StreamingTranscriptionCallback, TranscriptionConfig, TranscriptionResult,
+    TranscriptionSegment, TranscriptionServiceFactory, TranscriptionServicePort,
+};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 /// Mock storage implementation for testing
@@ -16,8 +27,13 @@ pub struct MockStorage {
     meetings: Arc<Mutex<HashMap<i64, Meeting>>>,
     participants: Arc<Mutex<HashMap<i64, Participant>>>,
     transcripts: Arc<Mutex<Vec<Transcript>>>,
+    /// IDs of transcripts stored as interim streaming segments
+    interim_transcripts: Arc<Mutex<HashSet<i64>>>,
     insights: Arc<Mutex<Vec<Insight>>>,
     service_configs: Arc<Mutex<Vec<ServiceConfig>>>,
+    prompt_templates: Arc<Mutex<Vec<(InsightType, String)>>>,
+    app_settings: Arc<Mutex<HashMap<String, String>>>,
+    model_overrides: Arc<Mutex<HashMap<i64, MeetingModelOverride>>>,
     next_id: Arc<Mutex<i64>>,
 }
 
@@ -70,8 +86,87 @@ impl StoragePort for MockStorage {
         Ok(())
     }
 
+    async fn update_meeting_notes(&self, id: i64, notes: Option<&str>) -> Result<()> {
+        match self.meetings.lock().unwrap().get_mut(&id) {
+            Some(meeting) => {
+                meeting.notes = notes.map(str::to_string);
+                Ok(())
+            }
+            None => Err(AppError::NotFound(format!("Meeting {} not found", id))),
+        }
+    }
+
+    async fn set_meeting_end_time(&self, id: i64, end_time: i64) -> Result<()> {
+        match self.meetings.lock().unwrap().get_mut(&id) {
+            Some(meeting) => {
+                meeting.end_time = Some(end_time);
+                Ok(())
+            }
+            None => Err(AppError::NotFound(format!("Meeting {} not found", id))),
+        }
+    }
+
+    async fn set_meeting_recording(&self, id: i64, recording: &SavedRecording) -> Result<()> {
+        match self.meetings.lock().unwrap().get_mut(&id) {
+            Some(meeting) => {
+                meeting.audio_file_path = Some(recording.audio_file_path.clone());
+                meeting.audio_offset_ms = recording.audio_offset_ms;
+                meeting.audio_normalized = recording.audio_normalized;
+                meeting.recording_status = Some(RecordingStatus::Recorded);
+                meeting.audio_sample_rate = Some(recording.sample_rate);
+                meeting.audio_channels = Some(recording.channels);
+                meeting.audio_bits_per_sample = Some(recording.bits_per_sample);
+                Ok(())
+            }
+            None => Err(AppError::NotFound(format!("Meeting {} not found", id))),
+        }
+    }
+
+    async fn set_recording_status(&self, id: i64, status: RecordingStatus) -> Result<()> {
+        match self.meetings.lock().unwrap().get_mut(&id) {
+            Some(meeting) => {
+                meeting.recording_status = Some(status);
+                Ok(())
+            }
+            None => Err(AppError::NotFound(format!("Meeting {} not found", id))),
+        }
+    }
+
     async fn delete_meeting(&self, id: i64) -> Result<()> {
         self.meetings.lock().unwrap().remove(&id);
+        self.model_overrides.lock().unwrap().remove(&id);
+        Ok(())
+    }
+
+    async fn get_meeting_model_override(
+        &self,
+        meeting_id: i64,
+    ) -> Result<Option<MeetingModelOverride>> {
+        Ok(self
+            .model_overrides
+            .lock()
+            .unwrap()
+            .get(&meeting_id)
+            .cloned())
+    }
+
+    async fn set_meeting_model_override(
+        &self,
+        model_override: &MeetingModelOverride,
+    ) -> Result<()> {
+        let meeting_id = model_override.meeting_id;
+        if !self.meetings.lock().unwrap().contains_key(&meeting_id) {
+            return Err(AppError::NotFound(format!(
+                "Meeting {} not found",
+                meeting_id
+            )));
+        }
+        let mut overrides = self.model_overrides.lock().unwrap();
+        if model_override.is_empty() {
+            overrides.remove(&meeting_id);
+        } else {
+            overrides.insert(meeting_id, model_override.clone());
+        }
         Ok(())
     }
 
@@ -83,6 +178,15 @@ impl StoragePort for MockStorage {
         Ok(id)
     }
 
+    async fn create_participants_batch(&self, participants: &[Participant]) -> Result<Vec<i64>> {
+        let mut ids = Vec::new();
+        for participant in participants {
+            let id = self.create_participant(participant).await?;
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+
     async fn get_participants(&self, meeting_id: i64) -> Result<Vec<Participant>> {
         Ok(self
             .participants
@@ -109,6 +213,45 @@ impl StoragePort for MockStorage {
         Ok(())
     }
 
+    async fn set_participants(&self, meeting_id: i64, roster: &[Participant]) -> Result<Vec<i64>> {
+        let mut participants = self.participants.lock().unwrap();
+        let mut existing: Vec<Participant> = participants
+            .values()
+            .filter(|p| p.meeting_id == meeting_id)
+            .cloned()
+            .collect();
+        existing.sort_by_key(|p| p.id);
+
+        let mut ids = Vec::with_capacity(roster.len());
+        for (entry, matched) in roster
+            .iter()
+            .zip(Participant::match_roster(&existing, roster))
+        {
+            match matched.and_then(|id| participants.get_mut(&id)) {
+                Some(participant) => {
+                    participant.name = entry.name.clone();
+                    if entry.email.is_some() {
+                        participant.email = entry.email.clone();
+                    }
+                    ids.push(participant.id.unwrap_or(0));
+                }
+                None => {
+                    let id = self.next_id();
+                    let mut participant =
+                        Participant::new(meeting_id, entry.name.clone(), entry.email.clone());
+                    participant.id = Some(id);
+                    participants.insert(id, participant);
+                    ids.push(id);
+                }
+            }
+        }
+
+        participants.retain(|id, p| {
+            p.meeting_id != meeting_id || p.speaker_label.is_some() || ids.contains(id)
+        });
+        Ok(ids)
+    }
+
     async fn create_transcript(&self, transcript: &Transcript) -> Result<i64> {
         let id = self.next_id();
         let mut t = transcript.clone();
@@ -128,6 +271,85 @@ impl StoragePort for MockStorage {
             .collect())
     }
 
+    async fn get_transcripts_by_source(
+        &self,
+        meeting_id: i64,
+        source: TranscriptSource,
+    ) -> Result<Vec<Transcript>> {
+        Ok(self
+            .get_transcripts(meeting_id)
+            .await?
+            .into_iter()
+            .filter(|t| t.source == source)
+            .collect())
+    }
+
+    async fn get_confident_transcripts(
+        &self,
+        meeting_id: i64,
+        min_confidence: f32,
+    ) -> Result<Vec<Transcript>> {
+        Ok(self
+            .get_transcripts(meeting_id)
+            .await?
+            .into_iter()
+            .filter(|t| t.confidence.is_none_or(|c| c >= min_confidence))
+            .collect())
+    }
+
+    async fn upsert_transcript_segment(
+        &self,
+        transcript: &Transcript,
+        is_final: bool,
+    ) -> Result<i64> {
+        let start = transcript.timestamp_ms;
+        let end = transcript.end_timestamp_ms.unwrap_or(start);
+
+        let mut transcripts = self.transcripts.lock().unwrap();
+        let mut interim = self.interim_transcripts.lock().unwrap();
+
+        let mut overlapping: Vec<i64> = transcripts
+            .iter()
+            .filter(|t| {
+                t.meeting_id == transcript.meeting_id
+                    && t.source == transcript.source
+                    && t.overlaps(start, end)
+            })
+            .filter_map(|t| t.id.filter(|id| interim.contains(id)))
+            .collect();
+        overlapping.sort_unstable();
+
+        let id = match overlapping.split_first() {
+            Some((&id, stale)) => {
+                transcripts.retain(|t| !t.id.is_some_and(|tid| stale.contains(&tid)));
+                for stale_id in stale {
+                    interim.remove(stale_id);
+                }
+                let existing = transcripts.iter_mut().find(|t| t.id == Some(id)).unwrap();
+                *existing = Transcript {
+                    id: Some(id),
+                    created_at: existing.created_at,
+                    ..transcript.clone()
+                };
+                id
+            }
+            None => {
+                let id = self.next_id();
+                let mut t = transcript.clone();
+                t.id = Some(id);
+                transcripts.push(t);
+                id
+            }
+        };
+
+        if is_final {
+            interim.remove(&id);
+        } else {
+            interim.insert(id);
+        }
+        Ok(id)
+    }
+
     async fn create_transcripts_batch(&self, transcripts: &[Transcript]) -> Result<Vec<i64>> {
         let mut ids = Vec::new();
         for transcript in transcripts {
@@ -137,6 +359,31 @@ impl StoragePort for MockStorage {
         Ok(ids)
     }
 
+    async fn replace_transcripts(
+        &self,
+        meeting_id: i64,
+        transcripts: &[Transcript],
+    ) -> Result<Vec<i64>> {
+        self.transcripts
+            .lock()
+            .unwrap()
+            .retain(|t| t.meeting_id != meeting_id || t.source != TranscriptSource::Batch);
+        self.create_transcripts_batch(transcripts).await
+    }
+
+    async fn delete_transcript(&self, transcript_id: i64) -> Result<()> {
+        let mut transcripts = self.transcripts.lock().unwrap();
+        let before = transcripts.len();
+        transcripts.retain(|t| t.id != Some(transcript_id));
+        if transcripts.len() == before {
+            return Err(AppError::NotFound(format!(
+                "Transcript {} not found",
+                transcript_id
+            )));
+        }
+        Ok(())
+    }
+
     async fn delete_transcripts(&self, meeting_id: i64) -> Result<()> {
         self.transcripts
             .lock()
@@ -155,6 +402,21 @@ impl StoragePort for MockStorage {
         Ok(())
     }
 
+    async fn update_transcript_text(&self, transcript_id: i64, text: &str) -> Result<()> {
+        let mut transcripts = self.transcripts.lock().unwrap();
+        match transcripts.iter_mut().find(|t| t.id == Some(transcript_id)) {
+            Some(transcript) => {
+                transcript.text = text.to_string();
+                transcript.updated_at = Some(chrono::Utc::now().timestamp());
+                Ok(())
+            }
+            None => Err(AppError::NotFound(format!(
+                "Transcript {} not found",
+                transcript_id
+            ))),
+        }
+    }
+
     async fn update_transcripts_by_speaker_label(
         &self,
         meeting_id: i64,
@@ -174,6 +436,135 @@ impl StoragePort for MockStorage {
         Ok(count)
     }
 
+    async fn merge_speaker_labels(
+        &self,
+        meeting_id: i64,
+        from_label: &str,
+        into_label: &str,
+    ) -> Result<usize> {
+        let mut participants = self.participants.lock().unwrap();
+        let find = |participants: &HashMap<i64, Participant>, label: &str| {
+            participants
+                .values()
+                .find(|p| p.meeting_id == meeting_id && p.speaker_label.as_deref() == Some(label))
+                .and_then(|p| p.id)
+        };
+        let from_participant = find(&participants, from_label);
+        let into_participant = find(&participants, into_label);
+
+        let target_participant = match (from_participant, into_participant) {
+            (from_id, Some(into_id)) => {
+                if let Some(from_id) = from_id {
+                    participants.remove(&from_id);
+                }
+                Some(into_id)
+            }
+            (Some(from_id), None) => {
+                if let Some(p) = participants.get_mut(&from_id) {
+                    p.speaker_label = Some(into_label.to_string());
+                }
+                Some(from_id)
+            }
+            (None, None) => None,
+        };
+
+        let mut transcripts = self.transcripts.lock().unwrap();
+        let mut count = 0;
+        for transcript in transcripts.iter_mut() {
+            if transcript.meeting_id != meeting_id {
+                continue;
+            }
+            let label = transcript.speaker_label.as_deref();
+            if label == Some(from_label) {
+                transcript.speaker_label = Some(into_label.to_string());
+                count += 1;
+            } else if label != Some(into_label) {
+                continue;
+            }
+            if target_participant.is_some() {
+                transcript.participant_id = target_participant;
+            }
+        }
+        Ok(count)
+    }
+
+    async fn assign_speakers(
+        &self,
+        meeting_id: i64,
+        assignments: &[(String, String)],
+    ) -> Result<usize> {
+        let mut transcripts = self.transcripts.lock().unwrap();
+        let has_label = |label: &str| {
+            transcripts
+                .iter()
+                .any(|t| t.meeting_id == meeting_id && t.speaker_label.as_deref() == Some(label))
+        };
+        let missing: Vec<&str> = assignments
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .filter(|label| !has_label(label))
+            .collect();
+        if !missing.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Speaker labels not found in meeting {}: {}",
+                meeting_id,
+                missing.join(", ")
+            )));
+        }
+
+        let mut participants = self.participants.lock().unwrap();
+        let mut linked = 0;
+        for (speaker_label, participant_name) in assignments {
+            let mut existing_ids: Vec<i64> = participants.keys().copied().collect();
+            existing_ids.sort();
+            // Prefer the speaker's participant, then an unlinked roster entry with the same name
+            let linked_id = existing_ids.iter().copied().find(|id| {
+                let p = &participants[id];
+                p.meeting_id == meeting_id && p.speaker_label.as_ref() == Some(speaker_label)
+            });
+            let roster_id = existing_ids.iter().copied().find(|id| {
+                let p = &participants[id];
+                p.meeting_id == meeting_id
+                    && p.speaker_label.is_none()
+                    && p.name.to_lowercase() == participant_name.to_lowercase()
+            });
+            if let (None, Some(id)) = (linked_id, roster_id) {
+                if let Some(p) = participants.get_mut(&id) {
+                    p.speaker_label = Some(speaker_label.clone());
+                }
+            }
+            let existing = linked_id
+                .or(roster_id)
+                .and_then(|id| participants.get_mut(&id));
+            let participant_id = match existing {
+                Some(participant) => {
+                    participant.name = participant_name.clone();
+                    participant.id.unwrap_or(0)
+                }
+                None => {
+                    let id = self.next_id();
+                    let mut participant =
+                        Participant::new(meeting_id, participant_name.clone(), None);
+                    participant.id = Some(id);
+                    participant.speaker_label = Some(speaker_label.clone());
+                    participants.insert(id, participant);
+                    id
+                }
+            };
+
+            for transcript in transcripts.iter_mut() {
+                if transcript.meeting_id == meeting_id
+                    && transcript.speaker_label.as_ref() == Some(speaker_label)
+                {
+                    transcript.participant_id = Some(participant_id);
+                    transcript.participant_name = Some(participant_name.clone());
+                    linked += 1;
+                }
+            }
+        }
+        Ok(linked)
+    }
+
     async fn create_insight(&self, insight: &Insight) -> Result<i64> {
         let id = self.next_id();
         let mut i = insight.clone();
@@ -193,6 +584,16 @@ impl StoragePort for MockStorage {
             .collect())
     }
 
+    async fn get_insight(&self, insight_id: i64) -> Result<Option<Insight>> {
+        Ok(self
+            .insights
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|i| i.id == Some(insight_id))
+            .cloned())
+    }
+
     async fn update_insight_content(&self, insight_id: i64, content: &str) -> Result<()> {
         let mut insights = self.insights.lock().unwrap();
         if let Some(insight) = insights.iter_mut().find(|i| i.id == Some(insight_id)) {
@@ -201,6 +602,25 @@ impl StoragePort for MockStorage {
         Ok(())
     }
 
+    async fn set_insight_feedback(&self, insight_id: i64, feedback: Option<bool>) -> Result<()> {
+        match self
+            .insights
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|i| i.id == Some(insight_id))
+        {
+            Some(insight) => {
+                insight.feedback = feedback;
+                Ok(())
+            }
+            None => Err(AppError::NotFound(format!(
+                "Insight {} not found",
+                insight_id
+            ))),
+        }
+    }
+
     async fn delete_insights(&self, meeting_id: i64) -> Result<()> {
         self.insights
             .lock()
@@ -209,9 +629,55 @@ impl StoragePort for MockStorage {
         Ok(())
     }
 
+    async fn save_prompt_template(&self, insight_type: &InsightType, template: &str) -> Result<()> {
+        let mut templates = self.prompt_templates.lock().unwrap();
+        templates.retain(|(t, _)| t != insight_type);
+        templates.push((insight_type.clone(), template.to_string()));
+        Ok(())
+    }
+
+    async fn get_prompt_template(&self, insight_type: &InsightType) -> Result<Option<String>> {
+        Ok(self
+            .prompt_templates
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(t, _)| t == insight_type)
+            .map(|(_, template)| template.clone()))
+    }
+
+    async fn reset_prompt_template(&self, insight_type: &InsightType) -> Result<()> {
+        self.prompt_templates
+            .lock()
+            .unwrap()
+            .retain(|(t, _)| t != insight_type);
+        Ok(())
+    }
+
+    async fn get_app_setting(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.app_settings.lock().unwrap().get(key).cloned())
+    }
+
+    async fn set_app_setting(&self, key: &str, value: Option<&str>) -> Result<()> {
+        let mut settings = self.app_settings.lock().unwrap();
+        match value {
+            Some(value) => settings.insert(key.to_string(), value.to_string()),
+            None => settings.remove(key),
+        };
+        Ok(())
+    }
+
     async fn save_service_config(&self, config: &ServiceConfig) -> Result<i64> {
         let mut configs = self.service_configs.lock().unwrap();
 
+        if config.is_active {
+            for other in configs.iter_mut() {
+                if other.service_type == config.service_type && other.provider != config.provider {
+                    other.is_active = false;
+                }
+            }
+        }
+
         // Find existing config
         if let Some(existing) = configs.iter_mut().find(|c| {
             format!("{:?}", c.service_type).to_lowercase()
@@ -267,6 +733,26 @@ impl StoragePort for MockStorage {
         Ok(self.service_configs.lock().unwrap().clone())
     }
 
+    async fn get_model_overrides(&self, provider: &str) -> Result<Vec<ModelOverride>> {
+        let config = self.get_service_config("llm", provider).await?;
+        Ok(ModelOverride::list_from_settings(
+            provider,
+            config.as_ref().and_then(|c| c.settings.as_deref()),
+        ))
+    }
+
+    async fn save_model_override(&self, model_override: &ModelOverride) -> Result<()> {
+        let mut config = self
+            .get_service_config("llm", &model_override.provider)
+            .await?
+            .unwrap_or_else(|| {
+                ServiceConfig::new(ServiceType::Llm, model_override.provider.clone())
+            });
+        config.settings = Some(model_override.save_to_settings(config.settings.as_deref())?);
+        self.save_service_config(&config).await?;
+        Ok(())
+    }
+
     async fn search_transcripts(
         &self,
         query: &str,
@@ -370,3 +856,337 @@ impl StoragePort for MockStorage {
         })
     }
 }
+
+/// Mock audio capture with a device list tests can change, and no real devices
+///
+/// Clones share the device list, so devices can be plugged in after the mock is
+/// handed out.
+#[derive(Clone, Default)]
+pub struct MockAudioCapture {
+    pub devices: Arc<Mutex<Vec<AudioDeviceInfo>>>,
+    capturing: bool,
+}
+
+impl MockAudioCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a device to the end of the list, as if it was just connected
+    pub fn plug_in(&self, id: &str, name: &str, kind: DeviceKind) {
+        let mut devices = self.devices.lock().unwrap();
+        let index = devices.len();
+        devices.push(AudioDeviceInfo {
+            index,
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+        });
+    }
+
+    fn names(&self, kind: Option<DeviceKind>) -> Vec<String> {
+        self.devices
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|d| kind.is_none_or(|kind| d.kind == kind))
+            .map(|d| format!("{}: {}", d.index, d.name))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl AudioCapturePort for MockAudioCapture {
+    async fn list_devices(&self) -> Result<Vec<String>> {
+        Ok(self.names(None))
+    }
+
+    async fn list_speaker_devices(&self) -> Result<Vec<String>> {
+        Ok(self.names(Some(DeviceKind::Speaker)))
+    }
+
+    async fn list_microphone_devices(&self) -> Result<Vec<String>> {
+        Ok(self.names(Some(DeviceKind::Microphone)))
+    }
+
+    async fn list_devices_detailed(&self) -> Result<Vec<AudioDeviceInfo>> {
+        Ok(self.devices.lock().unwrap().clone())
+    }
+
+    async fn start_capture(
+        &mut self,
+        _device_name: Option<String>,
+        _preferred_format: Option<AudioFormat>,
+    ) -> Result<()> {
+        self.capturing = true;
+        Ok(())
+    }
+
+    async fn start_dual_capture(
+        &mut self,
+        _speaker_device: Option<String>,
+        _microphone_device: Option<String>,
+        _process_name: Option<String>,
+        _preferred_format: Option<AudioFormat>,
+    ) -> Result<()> {
+        self.capturing = true;
+        Ok(())
+    }
+
+    async fn stop_capture(&mut self) -> Result<()> {
+        self.capturing = false;
+        Ok(())
+    }
+
+    async fn get_audio_buffer(&mut self) -> Result<Option<AudioBuffer>> {
+        Ok(None)
+    }
+
+    fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    fn get_format(&self) -> AudioFormat {
+        AudioFormat::default()
+    }
+
+    fn get_current_level(&self) -> f32 {
+        0.0
+    }
+}
+
+/// Mock ASR service that returns a canned transcript without touching the audio
+///
+/// Clones share their state, so a clone handed out by the factory records the calls
+/// made on it.
+#[derive(Clone)]
+pub struct MockTranscriptionService {
+    result: TranscriptionResult,
+    fail_with: Option<String>,
+    max_upload_bytes: Option<u64>,
+    streaming: bool,
+    /// Audio paths passed to `transcribe_file`, in call order
+    pub transcribed_files: Arc<Mutex<Vec<String>>>,
+    /// Provider and API key of each service created through the factory
+    pub created: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl MockTranscriptionService {
+    /// A service that transcribes every file as `segments`
+    pub fn new(segments: Vec<TranscriptionSegment>) -> Self {
+        let text = segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            result: TranscriptionResult {
+                text,
+                segments,
+                confidence: None,
+                detected_language: None,
+                model_info: None,
+                request_id: None,
+            },
+            fail_with: None,
+            max_upload_bytes: None,
+            streaming: false,
+            transcribed_files: Arc::default(),
+            created: Arc::default(),
+        }
+    }
+
+    /// Report this language and overall confidence with the transcript
+    pub fn with_detection(mut self, language: &str, confidence: f32) -> Self {
+        self.result.detected_language = Some(language.to_string());
+        self.result.confidence = Some(confidence);
+        self
+    }
+
+    /// Fail every transcription with this message
+    pub fn failing(mut self, message: &str) -> Self {
+        self.fail_with = Some(message.to_string());
+        self
+    }
+
+    /// Report this upload size limit
+    pub fn with_max_upload_bytes(mut self, limit: u64) -> Self {
+        self.max_upload_bytes = Some(limit);
+        self
+    }
+
+    /// Report streaming support (sessions still can't be started)
+    pub fn with_streaming(mut self) -> Self {
+        self.streaming = true;
+        self
+    }
+}
+
+#[async_trait]
+impl TranscriptionServicePort for MockTranscriptionService {
+    async fn transcribe_file(
+        &self,
+        audio_path: &str,
+        _config: &TranscriptionConfig,
+    ) -> Result<TranscriptionResult> {
+        self.transcribed_files
+            .lock()
+            .unwrap()
+            .push(audio_path.to_string());
+        match &self.fail_with {
+            Some(message) => Err(AppError::Transcription(message.clone())),
+            None => Ok(self.result.clone()),
+        }
+    }
+
+    async fn transcribe_bytes(
+        &self,
+        _audio_data: &[u8],
+        _format: &str,
+        config: &TranscriptionConfig,
+    ) -> Result<TranscriptionResult> {
+        self.transcribe_file("<bytes>", config).await
+    }
+
+    async fn start_streaming(
+        &self,
+        _config: &TranscriptionConfig,
+        _callback: Box<dyn StreamingTranscriptionCallback>,
+    ) -> Result<Box<dyn StreamingSession>> {
+        Err(AppError::Transcription(
+            "Streaming is not supported by the mock service".to_string(),
+        ))
+    }
+
+    fn provider_name(&self) -> &str {
+        "Mock"
+    }
+
+    fn is_configured(&self) -> bool {
+        true
+    }
+
+    fn max_upload_bytes(&self) -> Option<u64> {
+        self.max_upload_bytes
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.streaming
+    }
+}
+
+impl TranscriptionServiceFactory for MockTranscriptionService {
+    fn create(
+        &self,
+        provider: &str,
+        api_key: String,
+        _settings: Option<&str>,
+    ) -> Result<Box<dyn TranscriptionServicePort>> {
+        self.created
+            .lock()
+            .unwrap()
+            .push((provider.to_string(), api_key));
+        Ok(Box::new(self.clone()))
+    }
+}
+
+/// Mock LLM service that answers every insight type with canned content
+///
+/// Like [`MockTranscriptionService`], clones share the recorded requests.
+#[derive(Clone, Default)]
+pub struct MockLlmService {
+    /// Insight requests received, in call order
+    pub requests: Arc<Mutex<Vec<InsightRequest>>>,
+    /// Provider and API key of each service created through the factory
+    pub created: Arc<Mutex<Vec<(String, String)>>>,
+    /// Insight types that fail with an LLM error
+    fail_types: Vec<InsightType>,
+}
+
+impl MockLlmService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail requests for any of these insight types
+    pub fn failing_for(mut self, insight_types: &[InsightType]) -> Self {
+        self.fail_types = insight_types.to_vec();
+        self
+    }
+
+    /// The canned content generated for an insight type
+    pub fn content_for(insight_type: &InsightType) -> String {
+        format!("Mock {}", insight_type)
+    }
+}
+
+#[async_trait]
+impl LlmServicePort for MockLlmService {
+    async fn generate_insights(
+        &self,
+        request: &InsightRequest,
+        _config: &LlmConfig,
+        _prompt_template: Option<&str>,
+    ) -> Result<Vec<GeneratedInsight>> {
+        self.requests.lock().unwrap().push(request.clone());
+        if let Some(insight_type) = request
+            .insight_types
+            .iter()
+            .find(|insight_type| self.fail_types.contains(insight_type))
+        {
+            return Err(AppError::Llm(format!("Mock failure for {}", insight_type)));
+        }
+        Ok(request
+            .insight_types
+            .iter()
+            .map(|insight_type| GeneratedInsight {
+                insight_type: insight_type.clone(),
+                content: Self::content_for(insight_type),
+                metadata: None,
+            })
+            .collect())
+    }
+
+    async fn generate_summary(
+        &self,
+        _transcript: &str,
+        _context: Option<&str>,
+        _config: &LlmConfig,
+        _prompt_template: Option<&str>,
+    ) -> Result<String> {
+        Ok(Self::content_for(&InsightType::Summary))
+    }
+
+    async fn fetch_available_models(&self) -> Result<Vec<ModelInfo>> {
+        Ok(vec![ModelInfo {
+            id: "mock-model".to_string(),
+            name: "Mock Model".to_string(),
+            provider: "mock".to_string(),
+            context_window: 8192,
+            is_fallback_context_window: None,
+        }])
+    }
+
+    fn provider_name(&self) -> &str {
+        "Mock"
+    }
+
+    fn is_configured(&self) -> bool {
+        true
+    }
+}
+
+impl LlmServiceFactory for MockLlmService {
+    fn create(
+        &self,
+        provider: &str,
+        api_key: String,
+        _settings: Option<&serde_json::Value>,
+    ) -> Result<Box<dyn LlmServicePort>> {
+        self.created
+            .lock()
+            .unwrap()
+            .push((provider.to_string(), api_key));
+        Ok(Box::new(self.clone()))
+    }
+}
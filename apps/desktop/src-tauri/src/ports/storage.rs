@@ -3,8 +3,9 @@
 /// Defines the interface for database operations.
 /// Implementation: SQLite adapter
 use crate::domain::models::{
-    Insight, InsightSearchResult, Meeting, Participant, SearchResults, ServiceConfig, Transcript,
-    TranscriptSearchResult,
+    Insight, InsightSearchResult, InsightType, Meeting, MeetingModelOverride, ModelOverride,
+    Participant, RecordingStatus, SavedRecording, SearchResults, ServiceConfig, Transcript,
+    TranscriptSearchResult, TranscriptSource,
 };
 use crate::error::Result;
 use async_trait::async_trait;
@@ -25,13 +26,41 @@ pub trait StoragePort: Send + Sync {
     /// Update a meeting
     async fn update_meeting(&self, meeting: &Meeting) -> Result<()>;
 
+    /// Set or clear the user-authored notes of a meeting
+    async fn update_meeting_notes(&self, id: i64, notes: Option<&str>) -> Result<()>;
+
+    /// Set when a meeting ended, leaving its other columns alone
+    async fn set_meeting_end_time(&self, id: i64, end_time: i64) -> Result<()>;
+
+    /// Attach a saved recording to a meeting and mark it recorded
+    ///
+    /// Only the recording columns are written, so this can run while the meeting is
+    /// being ended.
+    async fn set_meeting_recording(&self, id: i64, recording: &SavedRecording) -> Result<()>;
+
+    /// Set a meeting's recording status, leaving its other columns alone
+    async fn set_recording_status(&self, id: i64, status: RecordingStatus) -> Result<()>;
+
     /// Delete a meeting and all related data
     async fn delete_meeting(&self, id: i64) -> Result<()>;
 
+    /// Get the meeting's ASR/LLM override, if one was set
+    async fn get_meeting_model_override(
+        &self,
+        meeting_id: i64,
+    ) -> Result<Option<MeetingModelOverride>>;
+
+    /// Set or replace a meeting's ASR/LLM override; an empty override removes it
+    async fn set_meeting_model_override(&self, model_override: &MeetingModelOverride)
+        -> Result<()>;
+
     // Participant operations
     /// Create a new participant
     async fn create_participant(&self, participant: &Participant) -> Result<i64>;
 
+    /// Batch insert participants in a single transaction, returning IDs in input order
+    async fn create_participants_batch(&self, participants: &[Participant]) -> Result<Vec<i64>>;
+
     /// Get participants for a meeting
     async fn get_participants(&self, meeting_id: i64) -> Result<Vec<Participant>>;
 
@@ -41,6 +70,12 @@ pub trait StoragePort: Send + Sync {
     /// Delete a participant by ID
     async fn delete_participant(&self, id: i64) -> Result<()>;
 
+    /// Replace a meeting's roster of known attendees in a single transaction
+    /// Entries matching an existing participant by email or name update it (keeping any
+    /// speaker link); the rest are added without a speaker label. Participants not in the
+    /// roster are removed unless they are linked to a speaker. Returns IDs in input order.
+    async fn set_participants(&self, meeting_id: i64, roster: &[Participant]) -> Result<Vec<i64>>;
+
     // Transcript operations
     /// Create a new transcript segment
     async fn create_transcript(&self, transcript: &Transcript) -> Result<i64>;
@@ -48,12 +83,51 @@ pub trait StoragePort: Send + Sync {
     /// Get transcripts for a meeting
     async fn get_transcripts(&self, meeting_id: i64) -> Result<Vec<Transcript>>;
 
+    /// Get a meeting's transcripts produced one way, batch or streaming
+    async fn get_transcripts_by_source(
+        &self,
+        meeting_id: i64,
+        source: TranscriptSource,
+    ) -> Result<Vec<Transcript>>;
+
+    /// Get transcripts for a meeting, excluding segments below `min_confidence`
+    /// Segments without a confidence score are included
+    async fn get_confident_transcripts(
+        &self,
+        meeting_id: i64,
+        min_confidence: f32,
+    ) -> Result<Vec<Transcript>>;
+
     /// Batch insert transcripts (more efficient for large meetings)
     async fn create_transcripts_batch(&self, transcripts: &[Transcript]) -> Result<Vec<i64>>;
 
+    /// Store a streaming segment, replacing stored interim segments it overlaps
+    ///
+    /// Interim rows of the meeting whose `[timestamp_ms, end_timestamp_ms)` range overlaps
+    /// the segment are collapsed into one row updated with the segment, so an interim that
+    /// later becomes final is stored once. Rows stored with `is_final` false stay
+    /// replaceable; final rows are never replaced. Returns the stored row's ID.
+    async fn upsert_transcript_segment(
+        &self,
+        transcript: &Transcript,
+        is_final: bool,
+    ) -> Result<i64>;
+
+    /// Replace the batch transcripts of a meeting in a single transaction
+    /// The old batch transcripts are deleted only if every new one is inserted; streaming
+    /// transcripts are kept.
+    async fn replace_transcripts(
+        &self,
+        meeting_id: i64,
+        transcripts: &[Transcript],
+    ) -> Result<Vec<i64>>;
+
     /// Update a transcript
     async fn update_transcript(&self, transcript: &Transcript) -> Result<()>;
 
+    /// Replace a transcript's text with a manual correction and record when it was edited
+    async fn update_transcript_text(&self, transcript_id: i64, text: &str) -> Result<()>;
+
     /// Batch update transcripts by speaker label (more efficient for participant linking)
     async fn update_transcripts_by_speaker_label(
         &self,
@@ -62,6 +136,29 @@ pub trait StoragePort: Send + Sync {
         participant_id: i64,
     ) -> Result<usize>;
 
+    /// Merge one speaker label into another within a meeting (atomic)
+    /// Re-links participant associations to the surviving label and returns the number of
+    /// transcripts relabeled
+    async fn merge_speaker_labels(
+        &self,
+        meeting_id: i64,
+        from_label: &str,
+        into_label: &str,
+    ) -> Result<usize>;
+
+    /// Assign participant names to speaker labels in a single transaction
+    /// Creates or renames the participant for each `(speaker_label, participant_name)` pair,
+    /// links all matching transcripts and returns the number of transcripts linked.
+    /// Fails without changes if any label does not appear in the meeting's transcripts.
+    async fn assign_speakers(
+        &self,
+        meeting_id: i64,
+        assignments: &[(String, String)],
+    ) -> Result<usize>;
+
+    /// Delete a single transcript segment, leaving the rest of the meeting's transcript
+    async fn delete_transcript(&self, transcript_id: i64) -> Result<()>;
+
     /// Delete all transcripts for a meeting
     async fn delete_transcripts(&self, meeting_id: i64) -> Result<()>;
 
@@ -72,14 +169,40 @@ pub trait StoragePort: Send + Sync {
     /// Get insights for a meeting
     async fn get_insights(&self, meeting_id: i64) -> Result<Vec<Insight>>;
 
+    /// Get an insight by ID
+    async fn get_insight(&self, insight_id: i64) -> Result<Option<Insight>>;
+
     /// Update an insight's content
     async fn update_insight_content(&self, insight_id: i64, content: &str) -> Result<()>;
 
+    /// Mark an insight as accepted or rejected, or clear the rating with None
+    async fn set_insight_feedback(&self, insight_id: i64, feedback: Option<bool>) -> Result<()>;
+
     /// Delete all insights for a meeting
     async fn delete_insights(&self, meeting_id: i64) -> Result<()>;
 
+    // Prompt template operations
+    /// Save a custom prompt template for an insight type, replacing any previous one
+    async fn save_prompt_template(&self, insight_type: &InsightType, template: &str) -> Result<()>;
+
+    /// Get the custom prompt template for an insight type, if one was saved
+    async fn get_prompt_template(&self, insight_type: &InsightType) -> Result<Option<String>>;
+
+    /// Remove the custom prompt template so the built-in default is used again
+    async fn reset_prompt_template(&self, insight_type: &InsightType) -> Result<()>;
+
+    // App setting operations
+    /// Get an application-wide setting
+    async fn get_app_setting(&self, key: &str) -> Result<Option<String>>;
+
+    /// Set an application-wide setting; None removes it
+    async fn set_app_setting(&self, key: &str, value: Option<&str>) -> Result<()>;
+
     // Service config operations
     /// Save or update service configuration
+    ///
+    /// Saving an active config deactivates the other configs of its service type, so at
+    /// most one provider per type is active.
     async fn save_service_config(&self, config: &ServiceConfig) -> Result<i64>;
 
     /// Get service configuration
@@ -95,6 +218,14 @@ pub trait StoragePort: Send + Sync {
     /// List all service configurations
     async fn list_service_configs(&self) -> Result<Vec<ServiceConfig>>;
 
+    // Model override operations
+    /// Get the model overrides saved for an LLM provider
+    async fn get_model_overrides(&self, provider: &str) -> Result<Vec<ModelOverride>>;
+
+    /// Save a model override in its provider's LLM service config, replacing any
+    /// previous override for the same model
+    async fn save_model_override(&self, model_override: &ModelOverride) -> Result<()>;
+
     // Search operations (FTS5 full-text search)
     /// Search across all searchable entities (transcripts, insights, meetings)
     async fn search_all(&self, query: &str, limit: Option<i32>) -> Result<SearchResults>;
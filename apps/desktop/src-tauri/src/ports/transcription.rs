@@ -3,6 +3,7 @@
 /// Defines the interface for ASR (Automatic Speech Recognition) services.
 /// Implementations: AssemblyAI, Deepgram
 use crate::error::Result;
+use crate::ports::audio::AudioFormat;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +18,30 @@ pub struct TranscriptionResult {
 
     /// Overall confidence score (0.0 to 1.0)
     pub confidence: Option<f32>,
+
+    /// Language detected by the provider (when auto-detection was used)
+    pub detected_language: Option<String>,
+
+    /// Model that produced the transcript, as reported by the provider
+    #[serde(default)]
+    pub model_info: Option<ModelInfo>,
+
+    /// Provider's ID for the request, to quote when reporting a bad transcription
+    #[serde(default)]
+    pub request_id: Option<String>,
+}
+
+/// Model details reported by an ASR provider
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModelInfo {
+    /// Model name (e.g., "general-nova-3")
+    pub name: String,
+
+    /// Model version, if reported
+    pub version: Option<String>,
+
+    /// Model architecture or family (e.g., "nova-3"), if reported
+    pub arch: Option<String>,
 }
 
 /// Represents a segment of transcription with timing and speaker info
@@ -36,6 +61,11 @@ pub struct TranscriptionSegment {
 
     /// Confidence score for this segment (0.0 to 1.0)
     pub confidence: Option<f32>,
+
+    /// Language of this segment (e.g. "es"), when the provider detects it per segment
+    /// Lets mixed-language meetings be filtered or translated by language.
+    #[serde(default)]
+    pub language: Option<String>,
 }
 
 /// Configuration for transcription request
@@ -48,6 +78,7 @@ pub struct TranscriptionConfig {
     pub num_speakers: Option<u32>,
 
     /// Language code (e.g., "en", "es", "fr")
+    /// None enables the provider's automatic language detection
     pub language: Option<String>,
 
     /// Model to use for transcription (provider-specific)
@@ -55,6 +86,42 @@ pub struct TranscriptionConfig {
 
     /// Provider-specific settings as JSON
     pub additional_settings: Option<serde_json::Value>,
+
+    /// Redact PII (names, emails, phone numbers, etc.) from transcripts
+    #[serde(default)]
+    pub enable_pii_redaction: bool,
+
+    /// PII categories to redact: "person_name", "email_address", "phone_number",
+    /// "us_social_security_number" or "credit_card_number". Each provider maps them to its
+    /// own names. Uses the provider's default policy set if None or if none of them map
+    #[serde(default)]
+    pub pii_policies: Option<Vec<String>>,
+
+    /// Transcribe each audio channel separately
+    /// Speakers are labeled "Channel 1", "Channel 2", ... instead of by diarization clustering
+    #[serde(default)]
+    pub multichannel: bool,
+
+    /// Drop segments whose confidence is below this (0.0 to 1.0)
+    /// Segments without a confidence score are kept; None disables filtering
+    #[serde(default)]
+    pub min_confidence: Option<f32>,
+
+    /// Renumber diarized speakers by first appearance, so "Speaker 0" is whoever talks first
+    /// Provider numbering is arbitrary and can change when the same audio is re-transcribed
+    #[serde(default)]
+    pub normalize_speaker_labels: bool,
+
+    /// Split a transcript the provider returned as one block into sentence segments
+    /// Only applies when there are no utterances or word timings; times are estimated.
+    #[serde(default)]
+    pub sentence_segmentation: bool,
+
+    /// Debug mode: write the provider's raw JSON response to this file before parsing
+    /// None (the default) keeps nothing, since responses contain the full transcript.
+    /// Only set by the backend, never deserialized from a request.
+    #[serde(skip)]
+    pub raw_response_path: Option<String>,
 }
 
 impl Default for TranscriptionConfig {
@@ -65,6 +132,13 @@ impl Default for TranscriptionConfig {
             language: Some("en".to_string()),
             model: None,
             additional_settings: None,
+            enable_pii_redaction: false,
+            pii_policies: None,
+            multichannel: false,
+            min_confidence: None,
+            normalize_speaker_labels: false,
+            sentence_segmentation: false,
+            raw_response_path: None,
         }
     }
 }
@@ -122,8 +196,87 @@ pub trait TranscriptionServicePort: Send + Sync {
     fn supports_streaming(&self) -> bool {
         false // Default: not supported (backward compatibility)
     }
+
+    /// Check if the provider redacts PII natively
+    /// Transcripts from providers without native redaction are redacted locally before storage
+    fn supports_pii_redaction(&self) -> bool {
+        false
+    }
+
+    /// Check if the provider can label segments by speaker
+    fn supports_diarization(&self) -> bool {
+        false
+    }
+
+    /// Check if the provider detects the spoken language when none is given
+    fn supports_language_detection(&self) -> bool {
+        false
+    }
+
+    /// Audio formats accepted for batch transcription, as file extensions
+    fn supported_audio_formats(&self) -> &'static [&'static str] {
+        &["wav"]
+    }
+
+    /// Largest recording the provider accepts in one upload, in bytes; None if unknown
+    fn max_upload_bytes(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Builds transcription services for a provider
+///
+/// Commands take a factory rather than naming adapters, so tests can substitute
+/// canned services for the real providers.
+pub trait TranscriptionServiceFactory: Send + Sync {
+    /// Create the service for `provider`; `settings` is its saved settings JSON, if any
+    fn create(
+        &self,
+        provider: &str,
+        api_key: String,
+        settings: Option<&str>,
+    ) -> Result<Box<dyn TranscriptionServicePort>>;
 }
 
+/// What an ASR provider supports, for the frontend to adapt its options
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AsrCapabilities {
+    pub provider: String,
+    pub supports_streaming: bool,
+    pub supports_diarization: bool,
+    pub supports_language_detection: bool,
+    pub supports_pii_redaction: bool,
+    pub supported_audio_formats: Vec<String>,
+    /// Largest recording that can be transcribed in one go, in bytes
+    pub max_upload_bytes: Option<u64>,
+}
+
+impl AsrCapabilities {
+    /// Describe a service from what it reports about itself
+    pub fn of(provider: &str, service: &dyn TranscriptionServicePort) -> Self {
+        Self {
+            provider: provider.to_string(),
+            supports_streaming: service.supports_streaming(),
+            supports_diarization: service.supports_diarization(),
+            supports_language_detection: service.supports_language_detection(),
+            supports_pii_redaction: service.supports_pii_redaction(),
+            supported_audio_formats: service
+                .supported_audio_formats()
+                .iter()
+                .map(|f| f.to_string())
+                .collect(),
+            max_upload_bytes: service.max_upload_bytes(),
+        }
+    }
+}
+
+/// Audio format streaming sessions expect: 16 kHz mono 16-bit PCM (linear16)
+pub const STREAMING_AUDIO_FORMAT: AudioFormat = AudioFormat {
+    sample_rate: 16000,
+    channels: 1,
+    bits_per_sample: 16,
+};
+
 /// Handle for an active streaming transcription session
 #[async_trait]
 pub trait StreamingSession: Send + Sync {
@@ -139,4 +292,9 @@ pub trait StreamingSession: Send + Sync {
 
     /// Check if the session is still active
     fn is_active(&self) -> bool;
+
+    /// Number of audio chunks queued while the connection is unavailable
+    fn pending_chunks(&self) -> usize {
+        0
+    }
 }
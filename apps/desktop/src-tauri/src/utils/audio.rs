@@ -0,0 +1,409 @@
+//! Audio buffer processing
+//!
+//! Transformations applied to a captured `AudioBuffer` before it is saved.
+
+use crate::ports::audio::{AudioBuffer, AudioFormat};
+
+/// Settings for trimming silence from the start and end of a recording
+#[derive(Debug, Clone)]
+pub struct SilenceTrimConfig {
+    /// RMS level (0.0 to 1.0) below which an analysis window counts as silent
+    pub threshold: f32,
+    /// Length of each analysis window in milliseconds
+    pub window_ms: u32,
+    /// Shortest run of silence worth trimming; shorter gaps are left alone
+    pub min_silence_ms: u32,
+    /// Silence kept next to the first and last sound so speech onsets aren't clipped
+    pub padding_ms: u32,
+}
+
+impl Default for SilenceTrimConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.01, // About -40 dBFS
+            window_ms: 20,
+            min_silence_ms: 1000,
+            padding_ms: 250,
+        }
+    }
+}
+
+/// Amount of audio removed from each end of a buffer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrimResult {
+    pub leading_ms: i64,
+    pub trailing_ms: i64,
+}
+
+/// Trim leading and trailing silence from an audio buffer in place
+///
+/// The buffer is split into windows of `window_ms`; the first and last windows whose
+/// RMS reaches `threshold` mark where sound starts and ends. A silent run at either end
+/// is only removed when it is at least `min_silence_ms` long, and `padding_ms` of it is
+/// kept. A buffer that is silent throughout is left untouched.
+///
+/// Returns how much was removed so callers can shift timestamps back onto the
+/// original recording timeline.
+pub fn trim_silence(buffer: &mut AudioBuffer, config: &SilenceTrimConfig) -> TrimResult {
+    let channels = usize::from(buffer.format.channels.max(1));
+    let sample_rate = buffer.format.sample_rate as usize;
+    let total_frames = buffer.samples.len() / channels;
+    if sample_rate == 0 || total_frames == 0 {
+        return TrimResult::default();
+    }
+
+    let ms_to_frames = |ms: u32| sample_rate * ms as usize / 1000;
+    let window_frames = ms_to_frames(config.window_ms).max(1);
+    let min_silence_frames = ms_to_frames(config.min_silence_ms);
+    let padding_frames = ms_to_frames(config.padding_ms);
+
+    let loud: Vec<bool> = buffer.samples[..total_frames * channels]
+        .chunks(window_frames * channels)
+        .map(|window| rms(window) >= config.threshold)
+        .collect();
+
+    let (Some(first), Some(last)) = (loud.iter().position(|&l| l), loud.iter().rposition(|&l| l))
+    else {
+        return TrimResult::default();
+    };
+
+    let leading_silence = first * window_frames;
+    let trailing_silence = total_frames - ((last + 1) * window_frames).min(total_frames);
+
+    let trim = |silence: usize| {
+        if silence >= min_silence_frames {
+            silence.saturating_sub(padding_frames)
+        } else {
+            0
+        }
+    };
+    let leading_frames = trim(leading_silence);
+    let trailing_frames = trim(trailing_silence);
+
+    buffer
+        .samples
+        .truncate((total_frames - trailing_frames) * channels);
+    buffer.samples.drain(..leading_frames * channels);
+
+    let frames_to_ms = |frames: usize| (frames * 1000 / sample_rate) as i64;
+    TrimResult {
+        leading_ms: frames_to_ms(leading_frames),
+        trailing_ms: frames_to_ms(trailing_frames),
+    }
+}
+
+/// Settings for raising the level of quiet recordings
+#[derive(Debug, Clone)]
+pub struct NormalizeConfig {
+    /// RMS level (0.0 to 1.0) the recording is scaled towards
+    pub target_rms: f32,
+    /// Highest absolute sample value allowed after scaling
+    pub peak_ceiling: f32,
+    /// Recordings with an RMS below this are treated as silence and not amplified
+    pub silence_floor: f32,
+}
+
+impl Default for NormalizeConfig {
+    fn default() -> Self {
+        Self {
+            target_rms: 0.1,      // About -20 dBFS
+            peak_ceiling: 0.98,   // Just under full scale
+            silence_floor: 0.001, // About -60 dBFS
+        }
+    }
+}
+
+/// Raise the level of a quiet audio buffer in place
+///
+/// Applies a single gain that brings the buffer's RMS up to `target_rms`, reduced as
+/// needed so the loudest sample stays within `peak_ceiling`. Buffers that are already
+/// at or above the target, or quieter than `silence_floor`, are left untouched.
+///
+/// Returns the gain applied, or `None` if the buffer was not changed.
+pub fn normalize_loudness(buffer: &mut AudioBuffer, config: &NormalizeConfig) -> Option<f32> {
+    let level = rms(&buffer.samples);
+    if level < config.silence_floor {
+        return None;
+    }
+
+    let peak = buffer
+        .samples
+        .iter()
+        .fold(0.0_f32, |peak, s| peak.max(s.abs()));
+    let gain = (config.target_rms / level).min(config.peak_ceiling / peak);
+    if gain <= 1.0 {
+        return None;
+    }
+
+    for sample in &mut buffer.samples {
+        *sample = (*sample * gain).clamp(-config.peak_ceiling, config.peak_ceiling);
+    }
+    Some(gain)
+}
+
+/// Downmix and resample a buffer to `sample_rate` and `channels`
+///
+/// Several channels going to mono are averaged, and mono going to several channels is
+/// copied to each; any other channel change goes through mono. Downsampling averages
+/// the source frames each output frame covers, which keeps most of what would alias out
+/// of the result, and upsampling interpolates linearly. The output depends only on the
+/// input, so a recording always saves to the same file.
+pub fn convert_format(buffer: &AudioBuffer, sample_rate: u32, channels: u16) -> AudioBuffer {
+    let source = &buffer.format;
+    let format = AudioFormat {
+        sample_rate,
+        channels,
+        bits_per_sample: source.bits_per_sample,
+    };
+    if source.sample_rate == sample_rate && source.channels == channels {
+        return AudioBuffer {
+            samples: buffer.samples.clone(),
+            format,
+        };
+    }
+
+    let source_channels = usize::from(source.channels.max(1));
+    let target_channels = usize::from(channels.max(1));
+    let frames = buffer.samples.chunks_exact(source_channels);
+
+    // One sample sequence per output channel, at the source rate
+    let planes: Vec<Vec<f32>> = if source_channels == target_channels {
+        (0..target_channels)
+            .map(|c| frames.clone().map(|frame| frame[c]).collect())
+            .collect()
+    } else {
+        let mono: Vec<f32> = frames
+            .map(|frame| frame.iter().sum::<f32>() / source_channels as f32)
+            .collect();
+        vec![mono; target_channels]
+    };
+
+    let planes: Vec<Vec<f32>> = planes
+        .iter()
+        .map(|plane| resample(plane, source.sample_rate, sample_rate))
+        .collect();
+    let output_frames = planes.first().map_or(0, Vec::len);
+    let mut samples = Vec::with_capacity(output_frames * target_channels);
+    for i in 0..output_frames {
+        samples.extend(planes.iter().map(|plane| plane[i]));
+    }
+
+    AudioBuffer { samples, format }
+}
+
+/// Resample one channel from `from` Hz to `to` Hz
+fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || from == 0 || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let (from, to) = (u64::from(from), u64::from(to));
+    let len = samples.len() as u64;
+    let output_len = (len * to / from) as usize;
+
+    if to < from {
+        (0..output_len as u64)
+            .map(|i| {
+                let start = (i * from / to) as usize;
+                let end = (((i + 1) * from / to).min(len) as usize).max(start + 1);
+                let window = &samples[start..end];
+                window.iter().sum::<f32>() / window.len() as f32
+            })
+            .collect()
+    } else {
+        (0..output_len as u64)
+            .map(|i| {
+                let position = (i * from) as f64 / to as f64;
+                let index = position as usize;
+                let next = samples[(index + 1).min(samples.len() - 1)];
+                let fraction = (position - index as f64) as f32;
+                samples[index] + (next - samples[index]) * fraction
+            })
+            .collect()
+    }
+}
+
+/// Root mean square of a run of samples
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_squares: f32 = samples.iter().map(|s| s * s).sum();
+    (sum_squares / samples.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 16000;
+
+    fn silence(ms: u32, channels: u16) -> Vec<f32> {
+        vec![0.0; (SAMPLE_RATE * ms / 1000) as usize * channels as usize]
+    }
+
+    fn tone(ms: u32, channels: u16) -> Vec<f32> {
+        let frames = (SAMPLE_RATE * ms / 1000) as usize;
+        (0..frames)
+            .flat_map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                let sample = 0.5 * (2.0 * std::f32::consts::PI * 440.0 * t).sin();
+                std::iter::repeat_n(sample, channels as usize)
+            })
+            .collect()
+    }
+
+    fn buffer(parts: &[Vec<f32>], channels: u16) -> AudioBuffer {
+        AudioBuffer {
+            samples: parts.concat(),
+            format: AudioFormat {
+                sample_rate: SAMPLE_RATE,
+                channels,
+                bits_per_sample: 16,
+            },
+        }
+    }
+
+    #[test]
+    fn test_trims_silent_prefix_and_suffix() {
+        let mut buf = buffer(&[silence(2000, 1), tone(1000, 1), silence(3000, 1)], 1);
+
+        let result = trim_silence(&mut buf, &SilenceTrimConfig::default());
+
+        assert_eq!(
+            result,
+            TrimResult {
+                leading_ms: 1750,
+                trailing_ms: 2750,
+            }
+        );
+        // 1s of tone plus 250ms padding on each side
+        assert_eq!(buf.samples.len(), silence(1500, 1).len());
+        assert!(rms(&buf.samples[..4000]) < 0.01);
+        assert!(rms(&buf.samples[4000..4320]) > 0.1);
+    }
+
+    #[test]
+    fn test_trims_interleaved_stereo_by_frame() {
+        let mut buf = buffer(&[silence(2000, 2), tone(1000, 2), silence(2000, 2)], 2);
+
+        let result = trim_silence(&mut buf, &SilenceTrimConfig::default());
+
+        assert_eq!(result.leading_ms, 1750);
+        assert_eq!(result.trailing_ms, 1750);
+        assert_eq!(buf.samples.len(), silence(1500, 2).len());
+    }
+
+    #[test]
+    fn test_keeps_short_gaps() {
+        let mut buf = buffer(&[silence(500, 1), tone(1000, 1), silence(3000, 1)], 1);
+
+        let result = trim_silence(&mut buf, &SilenceTrimConfig::default());
+
+        assert_eq!(result.leading_ms, 0);
+        assert_eq!(result.trailing_ms, 2750);
+        assert_eq!(buf.samples.len(), silence(1750, 1).len());
+    }
+
+    #[test]
+    fn test_leaves_silent_buffer_untouched() {
+        let mut buf = buffer(&[silence(3000, 1)], 1);
+
+        let result = trim_silence(&mut buf, &SilenceTrimConfig::default());
+
+        assert_eq!(result, TrimResult::default());
+        assert_eq!(buf.samples.len(), silence(3000, 1).len());
+    }
+
+    #[test]
+    fn test_normalize_scales_quiet_buffer_to_target() {
+        let quiet: Vec<f32> = tone(1000, 1).iter().map(|s| s * 0.1).collect();
+        let mut buf = buffer(&[quiet], 1);
+        // A 0.05 amplitude sine has an RMS of 0.05 / sqrt(2)
+        assert!((rms(&buf.samples) - 0.0354).abs() < 0.001);
+
+        let gain = normalize_loudness(&mut buf, &NormalizeConfig::default()).unwrap();
+
+        assert!((gain - 2.83).abs() < 0.01);
+        assert!((rms(&buf.samples) - 0.1).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_normalize_limits_gain_to_avoid_clipping() {
+        let mut samples = vec![0.01_f32; 16000];
+        samples[100] = 0.49;
+        let mut buf = buffer(&[samples], 1);
+
+        let gain = normalize_loudness(&mut buf, &NormalizeConfig::default()).unwrap();
+
+        assert!((gain - 2.0).abs() < 0.001);
+        let peak = buf.samples.iter().fold(0.0_f32, |p, s| p.max(s.abs()));
+        assert!(peak <= 0.98);
+    }
+
+    #[test]
+    fn test_normalize_leaves_silent_and_loud_buffers_untouched() {
+        let mut silent = buffer(&[silence(1000, 1)], 1);
+        assert_eq!(
+            normalize_loudness(&mut silent, &NormalizeConfig::default()),
+            None
+        );
+        assert!(silent.samples.iter().all(|&s| s == 0.0));
+
+        let mut loud = buffer(&[tone(1000, 1)], 1);
+        let before = loud.samples.clone();
+        assert_eq!(
+            normalize_loudness(&mut loud, &NormalizeConfig::default()),
+            None
+        );
+        assert_eq!(loud.samples, before);
+    }
+
+    #[test]
+    fn test_convert_format_downmixes_and_downsamples() {
+        // One second of 48 kHz stereo: left and right cancel out in the first half
+        let mut samples = Vec::new();
+        for i in 0..48000 {
+            let (left, right) = if i < 24000 { (0.5, -0.5) } else { (0.25, 0.25) };
+            samples.extend([left, right]);
+        }
+        let buffer = AudioBuffer {
+            samples,
+            format: AudioFormat {
+                sample_rate: 48000,
+                channels: 2,
+                bits_per_sample: 32,
+            },
+        };
+
+        let converted = convert_format(&buffer, 16000, 1);
+        assert_eq!(converted.format.sample_rate, 16000);
+        assert_eq!(converted.format.channels, 1);
+        assert_eq!(converted.samples.len(), 16000);
+        assert_eq!(converted.samples[0], 0.0);
+        assert_eq!(converted.samples[15999], 0.25);
+
+        // The same input always converts to the same output
+        assert_eq!(convert_format(&buffer, 16000, 1).samples, converted.samples);
+    }
+
+    #[test]
+    fn test_convert_format_upsamples_and_upmixes() {
+        let buffer = AudioBuffer {
+            samples: vec![0.0, 1.0],
+            format: AudioFormat {
+                sample_rate: 8000,
+                channels: 1,
+                bits_per_sample: 16,
+            },
+        };
+
+        let converted = convert_format(&buffer, 16000, 2);
+        assert_eq!(
+            converted.samples,
+            vec![0.0, 0.0, 0.5, 0.5, 1.0, 1.0, 1.0, 1.0]
+        );
+
+        let unchanged = convert_format(&buffer, 8000, 1);
+        assert_eq!(unchanged.samples, buffer.samples);
+    }
+}
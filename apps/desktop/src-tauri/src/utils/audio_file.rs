@@ -1,12 +1,65 @@
 //! Audio file utilities for saving captured audio
 //!
-//! Provides functions to save audio buffers to WAV files using the hound crate.
+//! Provides functions to save audio buffers to WAV files using the hound crate, or to
+//! Ogg Opus files for much smaller uploads.
 
 use crate::error::{AppError, Result};
-use crate::ports::audio::AudioBuffer;
+use crate::ports::audio::{AudioBuffer, AudioFormat};
+use crate::utils::audio::convert_format;
+use crate::utils::opus::{OggOpusEncoder, OPUS_SAMPLE_RATES};
 use hound::{WavSpec, WavWriter};
 use std::path::Path;
 
+/// Smallest format ASR services transcribe without losing accuracy: 16 kHz mono 16-bit
+///
+/// A third of the size of 48 kHz stereo, so it uploads and transcribes faster.
+pub const TRANSCRIPTION_WAV_FORMAT: AudioFormat = AudioFormat {
+    sample_rate: 16000,
+    channels: 1,
+    bits_per_sample: 16,
+};
+
+/// File format recordings are saved in
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingFormat {
+    /// 16-bit PCM WAV, lossless
+    #[default]
+    Wav,
+    /// Opus in an Ogg container, about a tenth of the size of WAV
+    Opus,
+}
+
+impl RecordingFormat {
+    /// Parse the `recording_format` setting value
+    pub fn from_setting(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "wav" => Some(Self::Wav),
+            "opus" => Some(Self::Opus),
+            _ => None,
+        }
+    }
+
+    /// File extension for recordings in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Opus => "opus",
+        }
+    }
+}
+
+/// Convert an f32 sample to 16-bit PCM
+fn to_i16(sample: f32) -> i16 {
+    // Clamp to [-1.0, 1.0] range
+    let clamped = sample.max(-1.0).min(1.0);
+
+    // Convert to i16 range using 32768.0 to properly handle the full asymmetric range
+    // i16 range is -32768 to 32767, so:
+    // - Negative: -1.0 * 32768.0 = -32768 ✓
+    // - Positive: 1.0 * 32768.0 = 32768, clamped to 32767 when cast to i16 ✓
+    (clamped * 32768.0) as i16
+}
+
 /// Save an audio buffer to a WAV file
 ///
 /// Always saves as 16-bit PCM format for maximum compatibility with ASR services.
@@ -34,17 +87,8 @@ pub fn save_wav_file<P: AsRef<Path>>(buffer: &AudioBuffer, path: P) -> Result<us
     // Convert f32 samples to i16 and write
     let mut samples_written = 0;
     for &sample in &buffer.samples {
-        // Clamp to [-1.0, 1.0] range
-        let clamped = sample.max(-1.0).min(1.0);
-
-        // Convert to i16 range using 32768.0 to properly handle the full asymmetric range
-        // i16 range is -32768 to 32767, so:
-        // - Negative: -1.0 * 32768.0 = -32768 ✓
-        // - Positive: 1.0 * 32768.0 = 32768, clamped to 32767 when cast to i16 ✓
-        let i16_sample = (clamped * 32768.0) as i16;
-
         writer
-            .write_sample(i16_sample)
+            .write_sample(to_i16(sample))
             .map_err(|e| AppError::AudioCapture(format!("Failed to write sample: {}", e)))?;
 
         samples_written += 1;
@@ -59,6 +103,67 @@ pub fn save_wav_file<P: AsRef<Path>>(buffer: &AudioBuffer, path: P) -> Result<us
     Ok(samples_written)
 }
 
+/// Save an audio buffer to a WAV file in `format`, downmixing and resampling as needed
+///
+/// Only 16-bit output is supported, like `save_wav_file`.
+///
+/// # Returns
+/// The number of samples written
+pub fn save_wav_file_as<P: AsRef<Path>>(
+    buffer: &AudioBuffer,
+    path: P,
+    format: &AudioFormat,
+) -> Result<usize> {
+    if format.bits_per_sample != 16 || format.sample_rate == 0 || format.channels == 0 {
+        return Err(AppError::InvalidInput(format!(
+            "Can't save a WAV file as {}",
+            format
+        )));
+    }
+    let converted = convert_format(buffer, format.sample_rate, format.channels);
+    save_wav_file(&converted, path)
+}
+
+/// Format an Opus file will be encoded in for audio in `format`
+///
+/// Opus only supports a few sample rates and at most two channels; other rates are
+/// encoded at 48 kHz and extra channels are downmixed to stereo. Opus has no bit
+/// depth, so `bits_per_sample` is the 16 bits decoders produce.
+pub fn opus_format(format: &AudioFormat) -> AudioFormat {
+    let sample_rate = if OPUS_SAMPLE_RATES.contains(&format.sample_rate) {
+        format.sample_rate
+    } else {
+        48000
+    };
+    AudioFormat {
+        sample_rate,
+        channels: format.channels.clamp(1, 2),
+        bits_per_sample: 16,
+    }
+}
+
+/// Save an audio buffer to an Ogg Opus file in `opus_format(format)`
+///
+/// # Returns
+/// The number of samples encoded
+pub fn save_opus_file_as<P: AsRef<Path>>(
+    buffer: &AudioBuffer,
+    path: P,
+    format: &AudioFormat,
+) -> Result<usize> {
+    let target = opus_format(format);
+    let converted = convert_format(buffer, target.sample_rate, target.channels);
+    let samples: Vec<i16> = converted.samples.iter().map(|&s| to_i16(s)).collect();
+
+    let mut encoder = OggOpusEncoder::new(target.sample_rate, target.channels)?;
+    let mut bytes = encoder.encode(&samples)?;
+    bytes.extend(encoder.finish()?);
+    std::fs::write(path, bytes)?;
+
+    log::info!("Saved {} samples to Opus file as {}", samples.len(), target);
+    Ok(samples.len())
+}
+
 /// Save audio buffer as chunks to multiple WAV files
 ///
 /// Useful for long recordings that need to be split into manageable chunks
@@ -107,6 +212,25 @@ pub fn save_wav_chunks<P: AsRef<Path>>(
     Ok(created_files)
 }
 
+/// Delete an audio file from disk, returning how many bytes were freed
+///
+/// A file that is already gone is not an error and frees 0 bytes.
+pub fn remove_audio_file<P: AsRef<Path>>(path: P) -> Result<u64> {
+    let path = path.as_ref();
+
+    let size = match std::fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(size),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// Get the duration of an audio buffer in seconds
 pub fn get_duration_seconds(buffer: &AudioBuffer) -> f64 {
     let total_frames = buffer.samples.len() / buffer.format.channels as usize;
@@ -128,10 +252,22 @@ pub fn format_audio_info(buffer: &AudioBuffer) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ports::audio::AudioFormat;
     use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_remove_audio_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("meeting_1.wav");
+        fs::write(&path, [0u8; 128]).unwrap();
+
+        assert_eq!(remove_audio_file(&path).unwrap(), 128);
+        assert!(!path.exists());
+
+        // Already deleted
+        assert_eq!(remove_audio_file(&path).unwrap(), 0);
+    }
+
     #[test]
     fn test_get_duration_seconds() {
         let buffer = AudioBuffer {
@@ -260,4 +396,102 @@ mod tests {
         assert_eq!(result.unwrap(), 3); // Should write all 3 samples
         assert!(file_path.exists());
     }
+
+    #[test]
+    fn test_save_wav_file_as_transcription_format() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("optimized.wav");
+
+        // Two seconds of 48 kHz stereo
+        let buffer = AudioBuffer {
+            samples: vec![0.25; 48000 * 2 * 2],
+            format: AudioFormat {
+                sample_rate: 48000,
+                channels: 2,
+                bits_per_sample: 32,
+            },
+        };
+
+        let written = save_wav_file_as(&buffer, &file_path, &TRANSCRIPTION_WAV_FORMAT).unwrap();
+        assert_eq!(written, 32000);
+
+        let header = crate::utils::wav::read_wav_file_header(&file_path).unwrap();
+        assert_eq!(header.sample_rate, 16000);
+        assert_eq!(header.channels, 1);
+        assert_eq!(header.bits_per_sample, 16);
+        assert_eq!(header.data_size, Some(32000 * 2));
+        assert_eq!(header.duration_ms(), Some(2000));
+
+        let unsupported = AudioFormat {
+            bits_per_sample: 24,
+            ..TRANSCRIPTION_WAV_FORMAT
+        };
+        assert!(save_wav_file_as(&buffer, &file_path, &unsupported).is_err());
+    }
+
+    #[test]
+    fn test_save_opus_file_as() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("meeting.opus");
+
+        // Two seconds of 44.1 kHz stereo, a rate Opus can't encode at
+        let buffer = AudioBuffer {
+            samples: vec![0.25; 44100 * 2 * 2],
+            format: AudioFormat {
+                sample_rate: 44100,
+                channels: 2,
+                bits_per_sample: 32,
+            },
+        };
+
+        let written = save_opus_file_as(&buffer, &file_path, &buffer.format).unwrap();
+        assert_eq!(written, 48000 * 2 * 2);
+
+        let info = crate::utils::opus::read_opus_file_info(&file_path).unwrap();
+        assert_eq!(info.input_sample_rate, 48000);
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.duration_ms(), Some(2000));
+
+        // The transcription format is encoded as is
+        save_opus_file_as(&buffer, &file_path, &TRANSCRIPTION_WAV_FORMAT).unwrap();
+        let info = crate::utils::opus::read_opus_file_info(&file_path).unwrap();
+        assert_eq!(info.input_sample_rate, 16000);
+        assert_eq!(info.channels, 1);
+    }
+
+    #[test]
+    fn test_opus_format() {
+        let surround = AudioFormat {
+            sample_rate: 96000,
+            channels: 6,
+            bits_per_sample: 24,
+        };
+        assert_eq!(
+            opus_format(&surround),
+            AudioFormat {
+                sample_rate: 48000,
+                channels: 2,
+                bits_per_sample: 16,
+            }
+        );
+        assert_eq!(
+            opus_format(&TRANSCRIPTION_WAV_FORMAT),
+            TRANSCRIPTION_WAV_FORMAT
+        );
+    }
+
+    #[test]
+    fn test_recording_format_setting() {
+        assert_eq!(
+            RecordingFormat::from_setting("Opus"),
+            Some(RecordingFormat::Opus)
+        );
+        assert_eq!(
+            RecordingFormat::from_setting("wav"),
+            Some(RecordingFormat::Wav)
+        );
+        assert_eq!(RecordingFormat::from_setting("mp3"), None);
+        assert_eq!(RecordingFormat::default().extension(), "wav");
+        assert_eq!(RecordingFormat::Opus.extension(), "opus");
+    }
 }
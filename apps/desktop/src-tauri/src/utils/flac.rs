@@ -0,0 +1,127 @@
+//! FLAC header parsing
+//!
+//! Reads the STREAMINFO block at the start of a FLAC file, which carries the stream's
+//! format and length, without decoding any audio.
+
+use crate::error::{AppError, Result};
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes up to the end of STREAMINFO: `fLaC` marker, block header and 34-byte body
+const STREAMINFO_END: usize = 4 + 4 + 34;
+
+/// Format information from a FLAC STREAMINFO block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlacStreamInfo {
+    pub sample_rate: u32,
+    pub channels: u8,
+    pub bits_per_sample: u8,
+    /// Samples per channel; None when the encoder didn't know the length
+    pub total_samples: Option<u64>,
+}
+
+impl FlacStreamInfo {
+    /// Duration of the stream in milliseconds, if its length is known
+    pub fn duration_ms(&self) -> Option<i64> {
+        Some((self.total_samples? * 1000 / u64::from(self.sample_rate)) as i64)
+    }
+}
+
+/// Parse the STREAMINFO block at the start of `bytes`
+///
+/// STREAMINFO is always the first metadata block, so only the leading 42 bytes are
+/// needed.
+pub fn read_flac_header(bytes: &[u8]) -> Result<FlacStreamInfo> {
+    if bytes.len() < 4 || &bytes[0..4] != b"fLaC" {
+        return Err(invalid("missing fLaC signature"));
+    }
+    if bytes.len() < STREAMINFO_END {
+        return Err(invalid("STREAMINFO block is truncated"));
+    }
+    // Low 7 bits of the block header's first byte are the block type; 0 is STREAMINFO
+    if bytes[4] & 0x7F != 0 {
+        return Err(invalid("first metadata block is not STREAMINFO"));
+    }
+
+    // After the block and frame sizes: 20 bits sample rate, 3 bits channels - 1,
+    // 5 bits bits-per-sample - 1, then 36 bits total samples
+    let fields = u64::from_be_bytes(bytes[18..26].try_into().expect("8 bytes"));
+    let sample_rate = (fields >> 44) as u32;
+    let channels = ((fields >> 41) & 0x7) as u8 + 1;
+    let bits_per_sample = ((fields >> 36) & 0x1F) as u8 + 1;
+    let total_samples = fields & 0xF_FFFF_FFFF;
+
+    if sample_rate == 0 {
+        return Err(invalid("sample rate is 0"));
+    }
+    Ok(FlacStreamInfo {
+        sample_rate,
+        channels,
+        bits_per_sample,
+        total_samples: (total_samples > 0).then_some(total_samples),
+    })
+}
+
+/// Read the STREAMINFO of a FLAC file on disk
+pub fn read_flac_file_header(path: &Path) -> Result<FlacStreamInfo> {
+    let mut bytes = Vec::with_capacity(STREAMINFO_END);
+    std::fs::File::open(path)?
+        .take(STREAMINFO_END as u64)
+        .read_to_end(&mut bytes)?;
+    read_flac_header(&bytes)
+}
+
+fn invalid(reason: &str) -> AppError {
+    AppError::InvalidInput(format!("Invalid FLAC header: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Marker and STREAMINFO for a stream with the given format
+    fn streaminfo(sample_rate: u32, channels: u8, bits: u8, total_samples: u64) -> Vec<u8> {
+        let mut bytes = b"fLaC".to_vec();
+        // Last metadata block, type 0, 34 bytes long
+        bytes.extend_from_slice(&[0x80, 0, 0, 34]);
+        // Min/max block size and min/max frame size
+        bytes.extend_from_slice(&[0x10, 0x00, 0x10, 0x00, 0, 0, 0, 0, 0, 0]);
+        let fields = (u64::from(sample_rate) << 44)
+            | (u64::from(channels - 1) << 41)
+            | (u64::from(bits - 1) << 36)
+            | total_samples;
+        bytes.extend_from_slice(&fields.to_be_bytes());
+        // MD5 of the audio
+        bytes.extend_from_slice(&[0; 16]);
+        bytes
+    }
+
+    #[test]
+    fn test_read_streaminfo() {
+        let info = read_flac_header(&streaminfo(48000, 2, 24, 144_000)).unwrap();
+        assert_eq!(
+            info,
+            FlacStreamInfo {
+                sample_rate: 48000,
+                channels: 2,
+                bits_per_sample: 24,
+                total_samples: Some(144_000),
+            }
+        );
+        assert_eq!(info.duration_ms(), Some(3000));
+
+        // An unknown length is reported as such
+        let unknown = read_flac_header(&streaminfo(16000, 1, 16, 0)).unwrap();
+        assert_eq!(unknown.duration_ms(), None);
+    }
+
+    #[test]
+    fn test_rejects_invalid_flac() {
+        assert!(read_flac_header(b"RIFF0000WAVE").is_err());
+        assert!(read_flac_header(&streaminfo(16000, 1, 16, 0)[..20]).is_err());
+
+        let mut not_streaminfo = streaminfo(16000, 1, 16, 0);
+        not_streaminfo[4] = 0x84;
+        assert!(read_flac_header(&not_streaminfo).is_err());
+    }
+}
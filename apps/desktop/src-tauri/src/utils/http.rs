@@ -0,0 +1,322 @@
+//! HTTP client construction for the provider adapters
+//!
+//! Adapters use [`shared_client`], which hands out one client per timeout so services
+//! constructed per command reuse open connections instead of each starting a new pool.
+//! Every client is built with [`client_builder`] so they share the proxy settings. A proxy
+//! saved in settings ([`ProxyConfig`]) is used for all requests except the `no_proxy`
+//! exceptions. Without one, reqwest reads `HTTPS_PROXY`/`HTTP_PROXY`/`ALL_PROXY` and
+//! `NO_PROXY` (or their lowercase forms) from the environment.
+
+use crate::error::{AppError, Result};
+use reqwest::{ClientBuilder, NoProxy, Proxy};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::Duration;
+
+/// App setting key the proxy config is saved under, as JSON
+pub const PROXY_SETTING_KEY: &str = "proxy";
+
+/// Explicit proxy for provider requests
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    /// Proxy URL, e.g. "http://proxy.corp.example:8080"; None uses the environment
+    pub url: Option<String>,
+    /// Comma-separated hosts, domains and IP ranges to reach directly,
+    /// e.g. "localhost,.corp.example,10.0.0.0/8"
+    pub no_proxy: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Trim both fields, treating blank ones as unset, and check the proxy URL
+    pub fn normalized(self) -> Result<Self> {
+        let clean = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let config = Self {
+            url: clean(self.url),
+            no_proxy: clean(self.no_proxy),
+        };
+        config.proxy()?;
+        Ok(config)
+    }
+
+    /// Whether nothing is configured, so the environment decides
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none() && self.no_proxy.is_none()
+    }
+
+    /// The reqwest proxy for `url`, with the `no_proxy` exceptions
+    fn proxy(&self) -> Result<Option<Proxy>> {
+        let Some(url) = self.url.as_deref() else {
+            return Ok(None);
+        };
+        let proxy = Proxy::all(url)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid proxy URL {}: {}", url, e)))?;
+        let no_proxy = self.no_proxy.as_deref().and_then(NoProxy::from_string);
+        Ok(Some(proxy.no_proxy(no_proxy)))
+    }
+}
+
+/// Proxy used by clients built from now on
+static PROXY_CONFIG: RwLock<ProxyConfig> = RwLock::new(ProxyConfig {
+    url: None,
+    no_proxy: None,
+});
+
+/// Use `config` for clients built from now on
+///
+/// Services create their client when they are constructed, which happens per command,
+/// so a change applies from the next provider call.
+pub fn set_proxy_config(config: ProxyConfig) {
+    *PROXY_CONFIG.write().unwrap() = config;
+    // Shared clients were built with the old proxy
+    shared_clients().lock().unwrap().clear();
+}
+
+/// The proxy config clients are currently built with
+pub fn proxy_config() -> ProxyConfig {
+    PROXY_CONFIG.read().unwrap().clone()
+}
+
+/// Start building a client with the given request timeout and the current proxy settings
+pub fn client_builder(timeout: Duration) -> ClientBuilder {
+    apply_proxy(reqwest::Client::builder().timeout(timeout), &proxy_config())
+}
+
+/// Clients handed out by [`shared_client`], by request timeout
+fn shared_clients() -> &'static Mutex<HashMap<Duration, reqwest::Client>> {
+    static CLIENTS: OnceLock<Mutex<HashMap<Duration, reqwest::Client>>> = OnceLock::new();
+    CLIENTS.get_or_init(Default::default)
+}
+
+/// The shared client for requests with the given timeout, built on first use
+///
+/// `reqwest::Client` is a handle to a reference-counted pool, so clones are cheap and
+/// can be used from any thread. Changing the proxy config rebuilds the clients.
+pub fn shared_client(timeout: Duration) -> reqwest::Client {
+    shared_clients()
+        .lock()
+        .unwrap()
+        .entry(timeout)
+        .or_insert_with(|| {
+            client_builder(timeout)
+                .build()
+                .expect("Failed to create HTTP client")
+        })
+        .clone()
+}
+
+/// Send an adapter's API key check, failing unless the provider accepts the key
+///
+/// `to_error` wraps the message in the adapter's error variant, e.g.
+/// `AppError::Transcription`. A rejected key reports the status and response body.
+pub async fn check_api_key(
+    request: reqwest::RequestBuilder,
+    provider: &str,
+    to_error: fn(String) -> AppError,
+) -> Result<()> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| to_error(format!("Failed to reach {}: {}", provider, e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(to_error(format!(
+            "{} API error ({}): {}",
+            provider, status, error_text
+        )));
+    }
+
+    Ok(())
+}
+
+/// Route a client through `config`'s proxy, if one is set
+///
+/// Configs are validated when saved, so an invalid one is logged and skipped here
+/// rather than failing every client.
+fn apply_proxy(builder: ClientBuilder, config: &ProxyConfig) -> ClientBuilder {
+    match config.proxy() {
+        Ok(Some(proxy)) => builder.proxy(proxy),
+        // reqwest falls back to the proxy environment variables
+        Ok(None) => builder,
+        Err(e) => {
+            log::warn!("Ignoring proxy setting: {}", e);
+            builder
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Answer one request with `200 OK`, resolving to the request line it received
+    async fn listen_once() -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before headers were complete");
+                head.extend_from_slice(&buf[..n]);
+            }
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+            let head = String::from_utf8_lossy(&head).into_owned();
+            head.lines().next().unwrap_or_default().to_string()
+        });
+
+        (address, handle)
+    }
+
+    fn proxied_client(config: &ProxyConfig) -> reqwest::Client {
+        apply_proxy(
+            reqwest::Client::builder().timeout(Duration::from_secs(5)),
+            config,
+        )
+        .build()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_configured_proxy_is_used() {
+        let (proxy_address, proxy) = listen_once().await;
+        let config = ProxyConfig {
+            url: Some(format!("http://{}", proxy_address)),
+            no_proxy: None,
+        };
+
+        // The host doesn't resolve, so the request only succeeds through the proxy
+        let response = proxied_client(&config)
+            .get("http://api.meet-scribe.invalid/v1/models")
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(
+            proxy.await.unwrap(),
+            "GET http://api.meet-scribe.invalid/v1/models HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_proxy_hosts_bypass_the_proxy() {
+        let (proxy_address, proxy) = listen_once().await;
+        let (direct_address, direct) = listen_once().await;
+        let config = ProxyConfig {
+            url: Some(format!("http://{}", proxy_address)),
+            no_proxy: Some("localhost, 127.0.0.1".to_string()),
+        };
+
+        let response = proxied_client(&config)
+            .get(format!("http://{}/v1/models", direct_address))
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(direct.await.unwrap(), "GET /v1/models HTTP/1.1");
+        assert!(!proxy.is_finished());
+        proxy.abort();
+    }
+
+    #[tokio::test]
+    async fn test_check_api_key() {
+        let (address, server) = listen_once().await;
+        let client = reqwest::Client::new();
+        check_api_key(
+            client.get(format!("http://{}/v1/models", address)),
+            "Example",
+            AppError::Transcription,
+        )
+        .await
+        .unwrap();
+        assert_eq!(server.await.unwrap(), "GET /v1/models HTTP/1.1");
+
+        // A rejected key reports the status and body in the adapter's error variant
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 401 Unauthorized\r\ncontent-length: 7\r\nconnection: close\r\n\r\nbad key",
+                )
+                .await
+                .unwrap();
+        });
+        let error = check_api_key(
+            client.get(format!("http://{}/v1/models", address)),
+            "Example",
+            AppError::LlmService,
+        )
+        .await
+        .unwrap_err();
+        server.await.unwrap();
+        match error {
+            AppError::LlmService(message) => {
+                assert_eq!(message, "Example API error (401 Unauthorized): bad key")
+            }
+            other => panic!("expected an LLM service error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_shared_client_reused_per_timeout() {
+        fn assert_shareable<T: Clone + Send + Sync>(_: &T) {}
+
+        // A timeout no other test uses
+        let timeout = Duration::from_millis(12_345);
+        let client = shared_client(timeout);
+        assert_shareable(&client);
+        shared_client(timeout);
+        shared_client(Duration::from_millis(12_346));
+
+        let clients = shared_clients().lock().unwrap();
+        assert!(clients.contains_key(&timeout));
+        assert!(clients.contains_key(&Duration::from_millis(12_346)));
+    }
+
+    #[test]
+    fn test_proxy_config_normalized() {
+        let config = ProxyConfig {
+            url: Some("  http://proxy.corp.example:8080 ".to_string()),
+            no_proxy: Some("   ".to_string()),
+        }
+        .normalized()
+        .unwrap();
+        assert_eq!(
+            config.url.as_deref(),
+            Some("http://proxy.corp.example:8080")
+        );
+        assert_eq!(config.no_proxy, None);
+
+        assert!(ProxyConfig::default().normalized().unwrap().is_empty());
+
+        let invalid = ProxyConfig {
+            url: Some("http://proxy:not-a-port".to_string()),
+            no_proxy: None,
+        };
+        assert!(matches!(
+            invalid.normalized(),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+}
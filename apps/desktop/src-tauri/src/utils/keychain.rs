@@ -3,22 +3,50 @@
 //! This module provides secure storage for API keys using:
 //! - Windows: Windows Credential Manager
 //! - Linux: Secret Service (GNOME Keyring, KWallet)
-//! - macOS: macOS Keychain (future support)
+//! - macOS: macOS Keychain (login keychain, generic password items)
+//!
+//! Every key is stored under the `com.srprasanna.meet-scribe` service with an
+//! account of `{service_type}_{provider}` (e.g. `asr_deepgram`). All lookups
+//! should go through [`KeychainPort`] so the naming stays consistent.
 
 use crate::error::{AppError, Result};
 use keyring::Entry;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 /// Keychain service name for Meet Scribe
 const SERVICE_NAME: &str = "com.srprasanna.meet-scribe";
 
+/// State of a stored API key, distinguishing a missing key from an unreadable keychain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "message", rename_all = "snake_case")]
+pub enum KeyStatus {
+    Present,
+    Missing,
+    /// The keychain exists but can't be read until the user unlocks it
+    Locked,
+    Error(String),
+}
+
+impl KeyStatus {
+    /// Classify a keyring error from a lookup
+    fn from_keyring_error(error: &keyring::Error) -> Self {
+        match error {
+            keyring::Error::NoEntry => KeyStatus::Missing,
+            keyring::Error::NoStorageAccess(_) => KeyStatus::Locked,
+            other => KeyStatus::Error(other.to_string()),
+        }
+    }
+}
+
 /// Trait for keychain operations - allows for mocking in tests
 pub trait KeychainPort: Send + Sync {
     fn save_api_key(&self, service_type: &str, provider: &str, api_key: &str) -> Result<()>;
     fn get_api_key(&self, service_type: &str, provider: &str) -> Result<String>;
     fn delete_api_key(&self, service_type: &str, provider: &str) -> Result<()>;
     fn has_api_key(&self, service_type: &str, provider: &str) -> bool;
+    fn key_status(&self, service_type: &str, provider: &str) -> KeyStatus;
 }
 
 /// Keychain manager for secure API key storage using OS keychain
@@ -64,6 +92,19 @@ impl KeychainPort for KeychainManager {
     fn has_api_key(&self, service_type: &str, provider: &str) -> bool {
         self.get_api_key(service_type, provider).is_ok()
     }
+
+    fn key_status(&self, service_type: &str, provider: &str) -> KeyStatus {
+        let account = format!("{}_{}", service_type, provider);
+        let entry = match Entry::new(SERVICE_NAME, &account) {
+            Ok(entry) => entry,
+            Err(e) => return KeyStatus::from_keyring_error(&e),
+        };
+
+        match entry.get_password() {
+            Ok(_) => KeyStatus::Present,
+            Err(e) => KeyStatus::from_keyring_error(&e),
+        }
+    }
 }
 
 impl KeychainManager {
@@ -123,6 +164,14 @@ impl KeychainPort for MockKeychain {
         let key = format!("{}_{}", service_type, provider);
         self.storage.lock().unwrap().contains_key(&key)
     }
+
+    fn key_status(&self, service_type: &str, provider: &str) -> KeyStatus {
+        if self.has_api_key(service_type, provider) {
+            KeyStatus::Present
+        } else {
+            KeyStatus::Missing
+        }
+    }
 }
 
 #[cfg(test)]
@@ -278,6 +327,31 @@ mod tests {
         let _ = manager.delete_api_key(service_type, provider2);
     }
 
+    #[test]
+    #[cfg(target_os = "macos")]
+    #[ignore] // Requires macOS Keychain access - run with `cargo test -- --ignored`
+    fn test_macos_keychain_round_trip() {
+        let manager = KeychainManager::new();
+        let service_type = "asr";
+        let provider = "test_provider_macos";
+        let api_key = "macos_test_key";
+
+        manager
+            .save_api_key(service_type, provider, api_key)
+            .expect("Should save API key to the macOS Keychain");
+
+        // The item must be readable through the same service/account pair used for lookups
+        let entry = Entry::new(SERVICE_NAME, &format!("{}_{}", service_type, provider)).unwrap();
+        assert_eq!(entry.get_password().unwrap(), api_key);
+        assert_eq!(
+            manager.get_api_key(service_type, provider).unwrap(),
+            api_key
+        );
+
+        manager.delete_api_key(service_type, provider).unwrap();
+        assert!(!manager.has_api_key(service_type, provider));
+    }
+
     #[test]
     #[ignore] // Requires OS keychain access - skip in CI
     fn test_get_nonexistent_key() {
@@ -425,4 +499,36 @@ mod tests {
         let result = mock.get_api_key("nonexistent_service", "nonexistent_provider");
         assert!(result.is_err(), "Should return error for nonexistent key");
     }
+
+    #[test]
+    fn test_mock_key_status() {
+        let mock = MockKeychain::new();
+        assert_eq!(mock.key_status("asr", "deepgram"), KeyStatus::Missing);
+
+        mock.save_api_key("asr", "deepgram", "dg-key").unwrap();
+        assert_eq!(mock.key_status("asr", "deepgram"), KeyStatus::Present);
+        assert_eq!(mock.key_status("llm", "deepgram"), KeyStatus::Missing);
+    }
+
+    #[test]
+    fn test_key_status_from_keyring_error() {
+        assert_eq!(
+            KeyStatus::from_keyring_error(&keyring::Error::NoEntry),
+            KeyStatus::Missing
+        );
+        let denied = keyring::Error::NoStorageAccess("collection is locked".into());
+        assert_eq!(KeyStatus::from_keyring_error(&denied), KeyStatus::Locked);
+        assert!(matches!(
+            KeyStatus::from_keyring_error(&keyring::Error::BadEncoding(vec![0xff])),
+            KeyStatus::Error(_)
+        ));
+
+        let json = serde_json::to_value(KeyStatus::Error("boom".to_string())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "status": "error", "message": "boom" })
+        );
+        let json = serde_json::to_value(KeyStatus::Locked).unwrap();
+        assert_eq!(json, serde_json::json!({ "status": "locked" }));
+    }
 }
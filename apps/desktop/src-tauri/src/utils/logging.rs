@@ -0,0 +1,230 @@
+//! Persistent application log
+//!
+//! Log records go to `meet-scribe.log` in the app data directory, next to the database,
+//! so intermittent failures can be diagnosed after the fact. The file is rotated once it
+//! reaches [`MAX_LOG_FILE_BYTES`], keeping [`MAX_ROTATED_LOGS`] older files
+//! (`meet-scribe.log.1` is the newest), which caps the total size on disk.
+//!
+//! The level comes from `RUST_LOG` (e.g. `RUST_LOG=debug`), defaulting to
+//! [`DEFAULT_LOG_LEVEL`]. Debug builds also echo records to stderr.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Log file name inside the app data directory
+pub const LOG_FILE_NAME: &str = "meet-scribe.log";
+
+/// Level used when `RUST_LOG` is not set
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+
+/// Size at which the log file is rotated
+pub const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated log files kept besides the current one
+pub const MAX_ROTATED_LOGS: usize = 3;
+
+/// Path of the log file, once file logging is running
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Log file writer that rotates by size
+///
+/// When a write would take the file past `max_bytes`, `{name}.1` becomes `{name}.2` and
+/// so on, the oldest file beyond `max_rotated` is deleted, and the current file becomes
+/// `{name}.1`.
+pub struct RotatingFileWriter {
+    path: PathBuf,
+    /// None only while rotating; the file is closed before renaming (required on Windows)
+    file: Option<File>,
+    size: u64,
+    max_bytes: u64,
+    max_rotated: usize,
+    echo_stderr: bool,
+}
+
+impl RotatingFileWriter {
+    /// Open `path` for appending, creating it if needed
+    pub fn open(path: PathBuf, max_bytes: u64, max_rotated: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file: Some(file),
+            size,
+            max_bytes,
+            max_rotated,
+            echo_stderr: false,
+        })
+    }
+
+    /// Also write every record to stderr
+    pub fn with_stderr(mut self, echo_stderr: bool) -> Self {
+        self.echo_stderr = echo_stderr;
+        self
+    }
+
+    /// Path of the `index`th rotated file (1 is the newest)
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+
+        if self.max_rotated == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let oldest = self.rotated_path(self.max_rotated);
+            if oldest.exists() {
+                fs::remove_file(&oldest)?;
+            }
+            for index in (1..self.max_rotated).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        self.file = Some(File::create(&self.path)?);
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            // A failed rotation left no file open; try again from scratch
+            None => self.file.insert(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?,
+            ),
+        };
+
+        let written = file.write(buf)?;
+        self.size += written as u64;
+        if self.echo_stderr {
+            let _ = io::stderr().write_all(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Start logging to the rotating log file in `log_dir`
+///
+/// Falls back to stderr-only logging if the file can't be opened. Returns the log file
+/// path when file logging is running.
+pub fn init(log_dir: &Path) -> Option<PathBuf> {
+    let mut builder = env_logger::Builder::from_env(
+        env_logger::Env::default().default_filter_or(DEFAULT_LOG_LEVEL),
+    );
+
+    let path = log_dir.join(LOG_FILE_NAME);
+    let opened = fs::create_dir_all(log_dir)
+        .and_then(|_| RotatingFileWriter::open(path.clone(), MAX_LOG_FILE_BYTES, MAX_ROTATED_LOGS));
+    let result = opened.map(|writer| {
+        builder
+            .target(env_logger::Target::Pipe(Box::new(
+                writer.with_stderr(cfg!(debug_assertions)),
+            )))
+            .write_style(env_logger::WriteStyle::Never);
+    });
+
+    if builder.try_init().is_err() {
+        return None;
+    }
+
+    match result {
+        Ok(()) => {
+            log::info!("Logging to {}", path.display());
+            let _ = LOG_PATH.set(path.clone());
+            Some(path)
+        }
+        Err(e) => {
+            log::error!("Failed to open log file {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Path of the log file, if file logging is running
+pub fn log_path() -> Option<&'static Path> {
+    LOG_PATH.get().map(PathBuf::as_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_caps_file_count_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOG_FILE_NAME);
+        let mut writer = RotatingFileWriter::open(path.clone(), 100, 2).unwrap();
+
+        // One write per record, as env_logger does
+        for i in 0..20 {
+            let record = format!("record {:02} {}\n", i, "x".repeat(30));
+            writer.write_all(record.as_bytes()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let mut files: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec!["meet-scribe.log", "meet-scribe.log.1", "meet-scribe.log.2"]
+        );
+        for file in &files {
+            assert!(fs::metadata(dir.path().join(file)).unwrap().len() <= 100);
+        }
+
+        // The newest records are in the current file, older ones in `.1`
+        let current = fs::read_to_string(&path).unwrap();
+        assert!(current.contains("record 19"));
+        let previous = fs::read_to_string(dir.path().join("meet-scribe.log.1")).unwrap();
+        assert!(!previous.contains("record 19"));
+        assert!(!current.contains("record 00"));
+    }
+
+    #[test]
+    fn test_reopen_appends_to_existing_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOG_FILE_NAME);
+        writeln!(
+            RotatingFileWriter::open(path.clone(), 1024, 1).unwrap(),
+            "first run"
+        )
+        .unwrap();
+
+        let mut writer = RotatingFileWriter::open(path.clone(), 1024, 1).unwrap();
+        assert_eq!(writer.size, "first run\n".len() as u64);
+        writeln!(writer, "second run").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "first run\nsecond run\n"
+        );
+    }
+}
@@ -1,3 +1,13 @@
 /// Utility modules for Meet Scribe
+pub mod audio;
 pub mod audio_file;
+pub mod flac;
+pub mod http;
 pub mod keychain;
+pub mod logging;
+pub mod notification;
+pub mod opus;
+pub mod rate_limit;
+pub mod redaction;
+pub mod tray;
+pub mod wav;
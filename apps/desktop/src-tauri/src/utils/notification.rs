@@ -0,0 +1,60 @@
+//! Desktop notifications for long-running background work
+//!
+//! Uses `tauri_plugin_notification` so the user can switch away while a
+//! transcription or insight generation job runs and still hear when it's done.
+
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_notification::NotificationExt;
+
+/// Maximum length of an error message shown in a notification body
+const MAX_ERROR_SUMMARY_LEN: usize = 120;
+
+/// Human-readable name for a meeting in notification text
+pub fn meeting_display_name(title: Option<&str>, meeting_id: i64) -> String {
+    match title.map(str::trim) {
+        Some(title) if !title.is_empty() => format!("'{}'", title),
+        _ => format!("meeting #{}", meeting_id),
+    }
+}
+
+/// Shorten an error message to its first line, truncated for display
+pub fn summarize_error(error: &str) -> String {
+    let first_line = error.lines().next().unwrap_or_default().trim();
+    if first_line.chars().count() <= MAX_ERROR_SUMMARY_LEN {
+        return first_line.to_string();
+    }
+    let truncated: String = first_line.chars().take(MAX_ERROR_SUMMARY_LEN).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+/// Show a desktop notification, logging (not failing) if it cannot be displayed
+pub fn notify<R: Runtime>(app: &AppHandle<R>, title: &str, body: &str) {
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("Failed to show notification '{}': {}", title, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meeting_display_name() {
+        assert_eq!(
+            meeting_display_name(Some("Weekly Sync"), 7),
+            "'Weekly Sync'"
+        );
+        assert_eq!(meeting_display_name(Some("  "), 7), "meeting #7");
+        assert_eq!(meeting_display_name(None, 7), "meeting #7");
+    }
+
+    #[test]
+    fn test_summarize_error() {
+        assert_eq!(summarize_error("Timeout\nstack trace"), "Timeout");
+
+        let long = "x".repeat(200);
+        let summary = summarize_error(&long);
+        assert_eq!(summary.chars().count(), MAX_ERROR_SUMMARY_LEN + 1);
+        assert!(summary.ends_with('…'));
+    }
+}
@@ -0,0 +1,420 @@
+//! Ogg/Opus encoding
+//!
+//! Encodes 16-bit PCM into an Ogg Opus stream (RFC 7845), a fraction of the size of
+//! the same audio as WAV. The stream can be written to a file in one go or sent
+//! incrementally, page by page, to a live transcription service.
+
+use crate::error::{AppError, Result};
+use ogg::writing::PacketWriteEndInfo;
+use ogg::{PacketReader, PacketWriter};
+use ropus::{Application, Bitrate, Channels, Encoder};
+use std::path::Path;
+
+/// Sample rates the Opus encoder accepts; anything else has to be resampled first
+pub const OPUS_SAMPLE_RATES: [u32; 5] = [8000, 12000, 16000, 24000, 48000];
+
+/// Granule positions and pre-skip are always counted at 48 kHz (RFC 7845 §4)
+const GRANULE_RATE: u32 = 48000;
+
+/// Duration of each encoded packet
+const FRAME_MS: u32 = 20;
+
+/// Target bitrate per channel; plenty for speech
+const BITRATE_PER_CHANNEL: u32 = 24_000;
+
+/// Largest packet the encoder may produce (the libopus recommendation)
+const MAX_PACKET_LEN: usize = 4000;
+
+/// Serial number of the single logical stream in each file
+const STREAM_SERIAL: u32 = 0x4D53_4F50;
+
+/// Vendor string written to the OpusTags header
+const VENDOR: &str = "meet-scribe";
+
+/// Incremental encoder producing an Ogg Opus byte stream
+///
+/// The first bytes returned contain the OpusHead and OpusTags headers. Audio is
+/// buffered until a full 20 ms frame is available, so a call to `encode` may return
+/// no bytes at all.
+pub struct OggOpusEncoder {
+    encoder: Encoder,
+    writer: PacketWriter<'static, Vec<u8>>,
+    channels: usize,
+    /// Samples per channel in one frame
+    frame_len: usize,
+    /// 48 kHz samples per sample at the encoder's rate
+    granule_scale: u64,
+    /// Encoder delay at the encoder's rate, flushed with silence by `finish`
+    lookahead: usize,
+    pre_skip: u64,
+    /// Interleaved samples waiting for a full frame
+    pending: Vec<i16>,
+    /// Samples per channel passed to `encode`
+    input_len: u64,
+    /// Samples per channel encoded so far, including padding
+    encoded_len: u64,
+}
+
+impl OggOpusEncoder {
+    /// Create an encoder for `channels` (1 or 2) at one of `OPUS_SAMPLE_RATES`
+    pub fn new(sample_rate: u32, channels: u16) -> Result<Self> {
+        if !OPUS_SAMPLE_RATES.contains(&sample_rate) {
+            return Err(AppError::InvalidInput(format!(
+                "Opus can't encode at {} Hz",
+                sample_rate
+            )));
+        }
+        let layout = match channels {
+            1 => Channels::Mono,
+            2 => Channels::Stereo,
+            n => {
+                return Err(AppError::InvalidInput(format!(
+                    "Opus can't encode {} channels",
+                    n
+                )))
+            }
+        };
+
+        let encoder = Encoder::builder(sample_rate, layout, Application::Voip)
+            .bitrate(Bitrate::Bits(BITRATE_PER_CHANNEL * u32::from(channels)))
+            .build()
+            .map_err(|e| AppError::AudioCapture(format!("Failed to create Opus encoder: {}", e)))?;
+
+        let granule_scale = u64::from(GRANULE_RATE / sample_rate);
+        // The encoder reports its delay at its own rate
+        let lookahead = encoder.lookahead() as usize;
+        let pre_skip = lookahead as u64 * granule_scale;
+
+        let mut writer = PacketWriter::new(Vec::new());
+        // Each header gets a page of its own
+        writer.write_packet(
+            opus_head(channels as u8, pre_skip as u16, sample_rate),
+            STREAM_SERIAL,
+            PacketWriteEndInfo::EndPage,
+            0,
+        )?;
+        writer.write_packet(
+            opus_tags(VENDOR),
+            STREAM_SERIAL,
+            PacketWriteEndInfo::EndPage,
+            0,
+        )?;
+
+        Ok(Self {
+            encoder,
+            writer,
+            channels: usize::from(channels),
+            frame_len: (sample_rate * FRAME_MS / 1000) as usize,
+            granule_scale,
+            lookahead,
+            pre_skip,
+            pending: Vec::new(),
+            input_len: 0,
+            encoded_len: 0,
+        })
+    }
+
+    /// Encode interleaved samples, returning the Ogg pages completed so far
+    ///
+    /// Every call ends a page, so the bytes returned can be sent on their own.
+    pub fn encode(&mut self, samples: &[i16]) -> Result<Vec<u8>> {
+        self.pending.extend_from_slice(samples);
+        self.input_len += (samples.len() / self.channels) as u64;
+        self.write_frames(PacketWriteEndInfo::EndPage, None)?;
+        Ok(std::mem::take(self.writer.inner_mut()))
+    }
+
+    /// Encode whatever is still buffered and end the stream, returning the last pages
+    ///
+    /// The final frame is padded with silence; the end granule position tells
+    /// decoders to drop the padding.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        // Push the encoder's delay out, then fill the last frame
+        let flushed_len = self.pending.len() + self.lookahead * self.channels;
+        let frame_samples = self.frame_len * self.channels;
+        self.pending
+            .resize(flushed_len.div_ceil(frame_samples) * frame_samples, 0);
+
+        let end_granule = self.pre_skip + self.input_len * self.granule_scale;
+        self.write_frames(PacketWriteEndInfo::EndStream, Some(end_granule))?;
+        Ok(self.writer.into_inner())
+    }
+
+    /// Encode every full frame in `pending`, ending the last packet with `end`
+    ///
+    /// `end_granule` overrides the granule position of the last packet.
+    fn write_frames(&mut self, end: PacketWriteEndInfo, end_granule: Option<u64>) -> Result<()> {
+        let frame_samples = self.frame_len * self.channels;
+        let frames = self.pending.len() / frame_samples;
+        if frames == 0 {
+            return Ok(());
+        }
+
+        let mut packet = [0u8; MAX_PACKET_LEN];
+        for (index, frame) in self
+            .pending
+            .chunks_exact(frame_samples)
+            .take(frames)
+            .enumerate()
+        {
+            let len = self
+                .encoder
+                .encode(frame, &mut packet)
+                .map_err(|e| AppError::AudioCapture(format!("Failed to encode Opus: {}", e)))?;
+            self.encoded_len += self.frame_len as u64;
+
+            let last = index + 1 == frames;
+            let granule = match end_granule {
+                Some(granule) if last => granule,
+                _ => self.encoded_len * self.granule_scale,
+            };
+            let info = if last {
+                end
+            } else {
+                PacketWriteEndInfo::NormalPacket
+            };
+            self.writer
+                .write_packet(packet[..len].to_vec(), STREAM_SERIAL, info, granule)?;
+        }
+        self.pending.drain(..frames * frame_samples);
+        Ok(())
+    }
+}
+
+/// Build an OpusHead identification header (RFC 7845 §5.1) for channel mapping family 0
+pub fn opus_head(channels: u8, pre_skip: u16, input_sample_rate: u32) -> Vec<u8> {
+    let mut head = Vec::with_capacity(19);
+    head.extend_from_slice(b"OpusHead");
+    head.push(1); // version
+    head.push(channels);
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&input_sample_rate.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // channel mapping family
+    head
+}
+
+/// Build an OpusTags comment header (RFC 7845 §5.2) with no user comments
+pub fn opus_tags(vendor: &str) -> Vec<u8> {
+    let mut tags = Vec::with_capacity(16 + vendor.len());
+    tags.extend_from_slice(b"OpusTags");
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor.as_bytes());
+    tags.extend_from_slice(&0u32.to_le_bytes());
+    tags
+}
+
+/// Format information from an Ogg Opus stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpusStreamInfo {
+    /// Rate of the audio before it was encoded; playback is always at 48 kHz
+    pub input_sample_rate: u32,
+    pub channels: u8,
+    /// 48 kHz samples to drop from the start of the decoded stream
+    pub pre_skip: u16,
+    /// Granule position of the last page, if the stream has any audio
+    pub end_granule: Option<u64>,
+}
+
+impl OpusStreamInfo {
+    /// Duration of the stream in milliseconds, if it has any audio
+    pub fn duration_ms(&self) -> Option<i64> {
+        let samples = self.end_granule?.saturating_sub(u64::from(self.pre_skip));
+        Some((samples * 1000 / u64::from(GRANULE_RATE)) as i64)
+    }
+}
+
+/// Parse an OpusHead identification header
+pub fn read_opus_head(packet: &[u8]) -> Result<OpusStreamInfo> {
+    if packet.len() < 19 || &packet[0..8] != b"OpusHead" {
+        return Err(AppError::InvalidInput(
+            "Invalid Ogg Opus stream: missing OpusHead".to_string(),
+        ));
+    }
+    Ok(OpusStreamInfo {
+        input_sample_rate: u32::from_le_bytes(packet[12..16].try_into().expect("4 bytes")),
+        channels: packet[9],
+        pre_skip: u16::from_le_bytes([packet[10], packet[11]]),
+        end_granule: None,
+    })
+}
+
+/// Read the format and length of an Ogg Opus file
+///
+/// The length comes from the last page, so the whole file is scanned.
+pub fn read_opus_file_info(path: &Path) -> Result<OpusStreamInfo> {
+    let invalid = |e: ogg::OggReadError| {
+        AppError::InvalidInput(format!("Invalid Ogg Opus file {}: {}", path.display(), e))
+    };
+    let mut reader = PacketReader::new(std::io::BufReader::new(std::fs::File::open(path)?));
+
+    let head = reader.read_packet_expected().map_err(invalid)?;
+    let mut info = read_opus_head(&head.data)?;
+    // The OpusTags page has granule position 0; audio pages follow
+    while let Some(packet) = reader.read_packet().map_err(invalid)? {
+        if packet.last_in_page() && packet.absgp_page() > 0 {
+            info.end_granule = Some(packet.absgp_page());
+        }
+    }
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ropus::{DecodeMode, Decoder};
+    use std::io::Cursor;
+
+    /// `seconds` of a 440 Hz tone at half scale
+    fn tone(sample_rate: u32, channels: u16, seconds: f32) -> Vec<i16> {
+        let frames = (sample_rate as f32 * seconds) as usize;
+        (0..frames)
+            .flat_map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                let sample = ((t * 440.0 * std::f32::consts::TAU).sin() * 16384.0) as i16;
+                std::iter::repeat_n(sample, usize::from(channels))
+            })
+            .collect()
+    }
+
+    /// Decode an Ogg Opus stream, returning its header and the PCM after pre-skip
+    fn decode(bytes: &[u8]) -> (OpusStreamInfo, Vec<i16>) {
+        let mut reader = PacketReader::new(Cursor::new(bytes));
+        let mut info = read_opus_head(&reader.read_packet_expected().unwrap().data).unwrap();
+        let tags = reader.read_packet_expected().unwrap();
+        assert!(tags.data.starts_with(b"OpusTags"));
+
+        let channels = match info.channels {
+            1 => Channels::Mono,
+            _ => Channels::Stereo,
+        };
+        let mut decoder = Decoder::new(GRANULE_RATE, channels).unwrap();
+        let mut frame = vec![0i16; decoder.max_frame_samples_per_channel() * channels.count()];
+        let mut pcm = Vec::new();
+        while let Some(packet) = reader.read_packet().unwrap() {
+            let len = decoder
+                .decode(&packet.data, &mut frame, DecodeMode::Normal)
+                .unwrap();
+            pcm.extend_from_slice(&frame[..len * channels.count()]);
+            info.end_granule = Some(packet.absgp_page());
+        }
+
+        let skip = usize::from(info.pre_skip) * channels.count();
+        let end = info.end_granule.unwrap() as usize * channels.count();
+        (info, pcm[skip..end].to_vec())
+    }
+
+    fn rms(samples: &[i16]) -> f64 {
+        let sum: f64 = samples.iter().map(|&s| f64::from(s) * f64::from(s)).sum();
+        (sum / samples.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn test_round_trip_at_capture_rate() {
+        let input = tone(48000, 2, 1.0);
+        let mut encoder = OggOpusEncoder::new(48000, 2).unwrap();
+        // Uneven chunks, as capture delivers them
+        let mut bytes = Vec::new();
+        for chunk in input.chunks(1234) {
+            bytes.extend(encoder.encode(chunk).unwrap());
+        }
+        bytes.extend(encoder.finish().unwrap());
+
+        let (info, output) = decode(&bytes);
+        assert_eq!(info.input_sample_rate, 48000);
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.duration_ms(), Some(1000));
+        // Padding is trimmed: exactly the input's length comes back
+        assert_eq!(output.len(), input.len());
+
+        // Lossy, but the tone survives at about the same level
+        let ratio = rms(&output) / rms(&input);
+        assert!((0.8..1.2).contains(&ratio), "level ratio {}", ratio);
+    }
+
+    #[test]
+    fn test_round_trip_at_transcription_rate() {
+        let input = tone(16000, 1, 0.5);
+        let mut encoder = OggOpusEncoder::new(16000, 1).unwrap();
+        let mut bytes = encoder.encode(&input).unwrap();
+        bytes.extend(encoder.finish().unwrap());
+
+        let (info, output) = decode(&bytes);
+        assert_eq!(info.input_sample_rate, 16000);
+        assert_eq!(info.duration_ms(), Some(500));
+        // Decoded at 48 kHz
+        assert_eq!(output.len(), input.len() * 3);
+        // Far smaller than the 16 kB of PCM
+        assert!(bytes.len() < input.len() * 2 / 4);
+    }
+
+    #[test]
+    fn test_encode_returns_headers_first_and_whole_pages() {
+        let mut encoder = OggOpusEncoder::new(16000, 1).unwrap();
+        // Less than a frame: only the header pages come out
+        let first = encoder.encode(&[0; 100]).unwrap();
+        assert!(first.starts_with(b"OggS"));
+        assert_eq!(first.windows(8).filter(|w| w == b"OpusHead").count(), 1);
+        assert_eq!(first.windows(8).filter(|w| w == b"OpusTags").count(), 1);
+        assert!(encoder.encode(&[0; 100]).unwrap().is_empty());
+
+        // Completing the frame produces a page of audio
+        let page = encoder.encode(&[0; 120]).unwrap();
+        assert!(page.starts_with(b"OggS"));
+    }
+
+    #[test]
+    fn test_opus_headers() {
+        let head = opus_head(2, 312, 48000);
+        assert_eq!(
+            head,
+            [
+                b'O', b'p', b'u', b's', b'H', b'e', b'a', b'd', 1, 2, 0x38, 0x01, 0x80, 0xBB, 0, 0,
+                0, 0, 0
+            ]
+        );
+        assert_eq!(
+            read_opus_head(&head).unwrap(),
+            OpusStreamInfo {
+                input_sample_rate: 48000,
+                channels: 2,
+                pre_skip: 312,
+                end_granule: None,
+            }
+        );
+        assert!(read_opus_head(b"OpusTags").is_err());
+
+        let tags = opus_tags("meet-scribe");
+        assert_eq!(&tags[0..8], b"OpusTags");
+        assert_eq!(u32::from_le_bytes(tags[8..12].try_into().unwrap()), 11);
+        assert_eq!(&tags[12..23], b"meet-scribe");
+        assert_eq!(&tags[23..], &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_formats() {
+        assert!(matches!(
+            OggOpusEncoder::new(44100, 1),
+            Err(AppError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            OggOpusEncoder::new(48000, 6),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_opus_file_info() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("meeting.opus");
+        let mut encoder = OggOpusEncoder::new(48000, 1).unwrap();
+        let mut bytes = encoder.encode(&tone(48000, 1, 2.5)).unwrap();
+        bytes.extend(encoder.finish().unwrap());
+        std::fs::write(&path, bytes).unwrap();
+
+        let info = read_opus_file_info(&path).unwrap();
+        assert_eq!(info.input_sample_rate, 48000);
+        assert_eq!(info.channels, 1);
+        assert_eq!(info.duration_ms(), Some(2500));
+    }
+}
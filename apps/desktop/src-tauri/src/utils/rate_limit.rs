@@ -0,0 +1,263 @@
+//! Client-side throttling of provider requests
+//!
+//! Each provider gets one [`RateLimiter`], shared by every service instance built for it,
+//! so parallel insight generation or several meetings transcribing at once stay under the
+//! provider's limits instead of running into 429 responses. Limits are saved per provider
+//! in settings ([`RateLimits`]); providers without an entry use [`RateLimitConfig::default`].
+
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// App setting key the per-provider limits are saved under, as JSON
+pub const RATE_LIMITS_SETTING_KEY: &str = "rate_limits";
+
+/// Highest concurrency a provider can be configured with
+const MAX_CONCURRENT_LIMIT: usize = 32;
+
+/// Span `requests_per_minute` is counted over
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Request limits for one provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Requests allowed in flight at once
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Requests allowed to start in any 60 second span; None for no limit
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+}
+
+fn default_max_concurrent() -> usize {
+    4
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: default_max_concurrent(),
+            requests_per_minute: None,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Check the limits are usable, naming `provider` in the error
+    pub fn validate(&self, provider: &str) -> Result<()> {
+        if !(1..=MAX_CONCURRENT_LIMIT).contains(&self.max_concurrent) {
+            return Err(AppError::InvalidInput(format!(
+                "max_concurrent for {} must be between 1 and {}",
+                provider, MAX_CONCURRENT_LIMIT
+            )));
+        }
+        if self.requests_per_minute == Some(0) {
+            return Err(AppError::InvalidInput(format!(
+                "requests_per_minute for {} must be at least 1",
+                provider
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Limits by provider name, e.g. "deepgram" or "openai"
+pub type RateLimits = HashMap<String, RateLimitConfig>;
+
+/// Caps concurrent and per-minute requests to one provider
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    in_flight: Arc<Semaphore>,
+    /// Start times of requests inside the current window, oldest first
+    started: Mutex<VecDeque<Instant>>,
+}
+
+/// Held for the duration of a request; dropping it lets the next request start
+#[derive(Debug)]
+pub struct RateLimitPermit {
+    _in_flight: OwnedSemaphorePermit,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            in_flight: Arc::new(Semaphore::new(config.max_concurrent.max(1))),
+            started: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Wait until a request may start under both limits
+    pub async fn acquire(&self) -> RateLimitPermit {
+        let permit = Arc::clone(&self.in_flight)
+            .acquire_owned()
+            .await
+            .expect("rate limit semaphore is never closed");
+
+        if let Some(per_minute) = self.config.requests_per_minute {
+            loop {
+                let wait = {
+                    let mut started = self.started.lock().unwrap();
+                    reserve_slot(&mut started, Instant::now(), per_minute)
+                };
+                match wait {
+                    Some(wait) => tokio::time::sleep(wait).await,
+                    None => break,
+                }
+            }
+        }
+
+        RateLimitPermit { _in_flight: permit }
+    }
+}
+
+/// Record a request starting at `now` if the window has room, otherwise return how
+/// long until the oldest request leaves it
+fn reserve_slot(
+    started: &mut VecDeque<Instant>,
+    now: Instant,
+    per_minute: u32,
+) -> Option<Duration> {
+    while started
+        .front()
+        .is_some_and(|&start| now.duration_since(start) >= WINDOW)
+    {
+        started.pop_front();
+    }
+
+    if started.len() < per_minute as usize {
+        started.push_back(now);
+        None
+    } else {
+        started
+            .front()
+            .map(|&oldest| WINDOW - now.duration_since(oldest))
+    }
+}
+
+/// Saved limits and the limiters built from them
+#[derive(Default)]
+struct Registry {
+    limits: RateLimits,
+    limiters: HashMap<String, Arc<RateLimiter>>,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Use `limits` for services built from now on
+///
+/// Requests already holding a permit finish under the old limits.
+pub fn set_rate_limits(limits: RateLimits) {
+    let mut registry = registry().lock().unwrap();
+    registry.limits = limits;
+    registry.limiters.clear();
+}
+
+/// The limits currently configured, by provider
+pub fn rate_limits() -> RateLimits {
+    registry().lock().unwrap().limits.clone()
+}
+
+/// The limiter shared by all requests to `provider`
+pub fn limiter(provider: &str) -> Arc<RateLimiter> {
+    let mut registry = registry().lock().unwrap();
+    let config = registry.limits.get(provider).copied().unwrap_or_default();
+    Arc::clone(
+        registry
+            .limiters
+            .entry(provider.to_string())
+            .or_insert_with(|| Arc::new(RateLimiter::new(config))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_limiter_caps_concurrency() {
+        let limiter = Arc::new(RateLimiter::new(RateLimitConfig {
+            max_concurrent: 2,
+            requests_per_minute: None,
+        }));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let (limiter, running, peak) = (
+                    Arc::clone(&limiter),
+                    Arc::clone(&running),
+                    Arc::clone(&peak),
+                );
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire().await;
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_reserve_slot_waits_for_oldest_request() {
+        let start = Instant::now();
+        let mut started = VecDeque::new();
+
+        assert_eq!(reserve_slot(&mut started, start, 2), None);
+        let second = start + Duration::from_secs(10);
+        assert_eq!(reserve_slot(&mut started, second, 2), None);
+
+        // Full until the first request is a minute old
+        let third = start + Duration::from_secs(15);
+        assert_eq!(
+            reserve_slot(&mut started, third, 2),
+            Some(Duration::from_secs(45))
+        );
+        assert_eq!(started.len(), 2);
+
+        assert_eq!(reserve_slot(&mut started, start + WINDOW, 2), None);
+        assert_eq!(started, VecDeque::from([second, start + WINDOW]));
+    }
+
+    #[test]
+    fn test_rate_limit_config_validation() {
+        assert!(RateLimitConfig::default().validate("openai").is_ok());
+
+        let zero = RateLimitConfig {
+            max_concurrent: 0,
+            requests_per_minute: None,
+        };
+        assert!(matches!(
+            zero.validate("openai"),
+            Err(AppError::InvalidInput(_))
+        ));
+
+        let no_requests = RateLimitConfig {
+            max_concurrent: 1,
+            requests_per_minute: Some(0),
+        };
+        assert!(no_requests.validate("openai").is_err());
+
+        // Missing fields take their defaults
+        let partial: RateLimitConfig =
+            serde_json::from_str(r#"{"requests_per_minute": 30}"#).unwrap();
+        assert_eq!(partial.max_concurrent, default_max_concurrent());
+        assert_eq!(partial.requests_per_minute, Some(30));
+    }
+}
@@ -0,0 +1,87 @@
+//! PII redaction utilities
+//!
+//! Regex-based fallback redaction for ASR providers that do not offer native
+//! PII redaction. Detects email addresses, phone numbers and US social security
+//! numbers and replaces them with a bracketed placeholder.
+//!
+//! Names cannot be detected reliably with regular expressions, so providers with
+//! native redaction (AssemblyAI, Deepgram) should be preferred when names must be removed.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Placeholder for redacted email addresses
+pub const EMAIL_PLACEHOLDER: &str = "[EMAIL]";
+
+/// Placeholder for redacted phone numbers
+pub const PHONE_PLACEHOLDER: &str = "[PHONE]";
+
+/// Placeholder for redacted social security numbers
+pub const SSN_PLACEHOLDER: &str = "[SSN]";
+
+fn email_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)\b[A-Z0-9._%+-]+@[A-Z0-9.-]+\.[A-Z]{2,}\b").expect("valid email regex")
+    })
+}
+
+fn ssn_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").expect("valid SSN regex"))
+}
+
+fn phone_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        // Optional country code, optional area code parentheses, common separators
+        Regex::new(r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)\s?|\b\d{3}[\s.-])\d{3}[\s.-]\d{4}\b")
+            .expect("valid phone regex")
+    })
+}
+
+/// Redact emails, phone numbers and SSNs from the given text
+///
+/// SSNs are replaced before phone numbers so that `123-45-6789` is not
+/// partially matched as a phone number.
+pub fn redact_pii(text: &str) -> String {
+    let redacted = email_regex().replace_all(text, EMAIL_PLACEHOLDER);
+    let redacted = ssn_regex().replace_all(&redacted, SSN_PLACEHOLDER);
+    let redacted = phone_regex().replace_all(&redacted, PHONE_PLACEHOLDER);
+    redacted.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_email() {
+        let text = "Send it to jane.doe+notes@example.co.uk please";
+        assert_eq!(redact_pii(text), "Send it to [EMAIL] please");
+    }
+
+    #[test]
+    fn test_redact_phone_numbers() {
+        assert_eq!(redact_pii("Call 555-123-4567 today"), "Call [PHONE] today");
+        assert_eq!(redact_pii("Call (555) 123-4567"), "Call [PHONE]");
+        assert_eq!(redact_pii("Call +1 555.123.4567"), "Call [PHONE]");
+    }
+
+    #[test]
+    fn test_redact_ssn() {
+        assert_eq!(redact_pii("My SSN is 123-45-6789."), "My SSN is [SSN].");
+    }
+
+    #[test]
+    fn test_redact_multiple() {
+        let text = "Email bob@corp.com or call 555 123 4567, SSN 987-65-4321";
+        assert_eq!(redact_pii(text), "Email [EMAIL] or call [PHONE], SSN [SSN]");
+    }
+
+    #[test]
+    fn test_no_pii_unchanged() {
+        let text = "We shipped version 2.1 on 2024-03-15 with 42 fixes";
+        assert_eq!(redact_pii(text), text);
+    }
+}
@@ -0,0 +1,90 @@
+//! System tray status helpers
+//!
+//! While a meeting is being recorded, a background ticker refreshes the tray
+//! tooltip every second with the elapsed recording time.
+
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// ID of the tray icon declared in tauri.conf.json
+const TRAY_ID: &str = "main";
+
+/// Tooltip shown when no meeting is being recorded
+pub const IDLE_TOOLTIP: &str = "Meet Scribe - Idle";
+
+/// Handle to the running tooltip ticker, if any
+pub type TrayTicker = Mutex<Option<JoinHandle<()>>>;
+
+/// Format a duration in seconds as `HH:MM:SS`
+pub fn format_elapsed(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+fn set_tooltip<R: Runtime>(app: &AppHandle<R>, tooltip: &str) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+            log::warn!("Failed to update tray tooltip: {}", e);
+        }
+    }
+}
+
+/// Start updating the tray tooltip with the time elapsed since `start_time` (Unix seconds)
+///
+/// Any ticker that is already running is cancelled first, so calling this twice
+/// never leaves two tasks fighting over the tooltip.
+pub async fn start_recording_ticker<R: Runtime>(
+    app: AppHandle<R>,
+    ticker: &TrayTicker,
+    start_time: i64,
+) {
+    let mut current = ticker.lock().await;
+    if let Some(handle) = current.take() {
+        handle.abort();
+    }
+
+    *current = Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            let elapsed = chrono::Utc::now().timestamp() - start_time;
+            set_tooltip(
+                &app,
+                &format!("Meet Scribe - Recording {}", format_elapsed(elapsed)),
+            );
+        }
+    }));
+}
+
+/// Cancel the recording ticker (if running) and reset the tooltip to idle
+pub async fn stop_recording_ticker<R: Runtime>(app: &AppHandle<R>, ticker: &TrayTicker) {
+    if let Some(handle) = ticker.lock().await.take() {
+        handle.abort();
+    }
+    set_tooltip(app, IDLE_TOOLTIP);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(0), "00:00:00");
+        assert_eq!(format_elapsed(754), "00:12:34");
+        assert_eq!(format_elapsed(3 * 3600 + 5), "03:00:05");
+    }
+
+    #[test]
+    fn test_format_elapsed_clamps_negative() {
+        // Clock skew between meeting creation and now should never show a negative time
+        assert_eq!(format_elapsed(-5), "00:00:00");
+    }
+}
@@ -0,0 +1,268 @@
+//! WAV header parsing
+//!
+//! Reads the format of a RIFF/WAVE file from its leading bytes without loading the
+//! rest of the file.
+
+use crate::error::{AppError, Result};
+use std::io::Read;
+use std::path::Path;
+
+/// Length of a canonical WAV header (RIFF header, 16-byte `fmt ` chunk, `data` chunk header)
+pub const WAV_HEADER_LEN: usize = 44;
+
+/// Minimum size of a `fmt ` chunk body
+const FMT_CHUNK_MIN_LEN: u32 = 16;
+
+/// Bytes read from the start of a file when parsing its header, leaving room for
+/// metadata chunks before `data`
+const FILE_HEADER_READ_LEN: u64 = 4096;
+
+/// Format information from a WAV header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WavHeader {
+    /// Format tag from the `fmt ` chunk (1 = PCM, 3 = IEEE float, 0xFFFE = extensible)
+    pub audio_format: u16,
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    /// Size of the sample data in bytes, if the `data` chunk header was within the bytes read
+    pub data_size: Option<u32>,
+}
+
+impl WavHeader {
+    /// Duration of the sample data in milliseconds, if the data size is known
+    pub fn duration_ms(&self) -> Option<i64> {
+        let bytes_per_second = u64::from(self.sample_rate)
+            * u64::from(self.channels)
+            * u64::from(self.bits_per_sample / 8);
+        Some((u64::from(self.data_size?) * 1000 / bytes_per_second) as i64)
+    }
+}
+
+/// Parse the WAV header at the start of `bytes`
+///
+/// Walks the RIFF chunks up to the `fmt ` chunk (and the `data` chunk header if
+/// present), so files with extra chunks such as `LIST` before the samples are
+/// handled. Fails with a descriptive error for non-WAV input, truncated headers
+/// and chunk sizes that don't fit.
+pub fn read_wav_header(bytes: &[u8]) -> Result<WavHeader> {
+    if bytes.len() < 12 {
+        return Err(invalid(format!(
+            "expected at least 12 bytes, got {}",
+            bytes.len()
+        )));
+    }
+    if &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(invalid("missing RIFF/WAVE signature".to_string()));
+    }
+    if read_u32(bytes, 4) < 4 {
+        return Err(invalid("RIFF chunk size is too small".to_string()));
+    }
+
+    let mut format: Option<WavHeader> = None;
+    let mut offset = 12;
+
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_size = read_u32(bytes, offset + 4);
+        let body = offset + 8;
+
+        match chunk_id {
+            b"fmt " => {
+                if chunk_size < FMT_CHUNK_MIN_LEN {
+                    return Err(invalid(format!(
+                        "fmt chunk is {} bytes, expected at least {}",
+                        chunk_size, FMT_CHUNK_MIN_LEN
+                    )));
+                }
+                if body + FMT_CHUNK_MIN_LEN as usize > bytes.len() {
+                    return Err(invalid("fmt chunk is truncated".to_string()));
+                }
+                format = Some(WavHeader {
+                    audio_format: read_u16(bytes, body),
+                    channels: read_u16(bytes, body + 2),
+                    sample_rate: read_u32(bytes, body + 4),
+                    bits_per_sample: read_u16(bytes, body + 14),
+                    data_size: None,
+                });
+            }
+            b"data" => {
+                let mut header = format.ok_or_else(|| {
+                    invalid("data chunk appears before the fmt chunk".to_string())
+                })?;
+                header.data_size = Some(chunk_size);
+                return validate(header);
+            }
+            _ => {}
+        }
+
+        // Chunks are padded to an even length
+        offset = body + chunk_size as usize + (chunk_size as usize & 1);
+    }
+
+    match format {
+        Some(header) => validate(header),
+        None => Err(invalid("fmt chunk not found".to_string())),
+    }
+}
+
+/// Read the WAV header of a file on disk without loading the samples
+pub fn read_wav_file_header(path: &Path) -> Result<WavHeader> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path)?
+        .take(FILE_HEADER_READ_LEN)
+        .read_to_end(&mut bytes)?;
+    read_wav_header(&bytes)
+}
+
+/// Reject formats that can't describe real audio
+fn validate(header: WavHeader) -> Result<WavHeader> {
+    if header.channels == 0 {
+        return Err(invalid("channel count is 0".to_string()));
+    }
+    if header.sample_rate == 0 {
+        return Err(invalid("sample rate is 0".to_string()));
+    }
+    if header.bits_per_sample == 0 || !header.bits_per_sample.is_multiple_of(8) {
+        return Err(invalid(format!(
+            "unsupported bits per sample: {}",
+            header.bits_per_sample
+        )));
+    }
+    Ok(header)
+}
+
+fn invalid(reason: String) -> AppError {
+    AppError::InvalidInput(format!("Invalid WAV header: {}", reason))
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WAVE_FORMAT_PCM: u16 = 1;
+
+    /// Canonical 44-byte header for 16-bit PCM
+    fn pcm_header(channels: u16, sample_rate: u32, data_size: u32) -> Vec<u8> {
+        let block_align = channels * 2;
+        let mut bytes = Vec::with_capacity(WAV_HEADER_LEN);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&WAVE_FORMAT_PCM.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+        bytes.extend_from_slice(&block_align.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_read_valid_header() {
+        let header = read_wav_header(&pcm_header(2, 48000, 192_000)).unwrap();
+        assert_eq!(
+            header,
+            WavHeader {
+                audio_format: WAVE_FORMAT_PCM,
+                channels: 2,
+                sample_rate: 48000,
+                bits_per_sample: 16,
+                data_size: Some(192_000),
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_header_written_by_hound() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(file.path(), spec).unwrap();
+        for _ in 0..1600 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let bytes = std::fs::read(file.path()).unwrap();
+        let header = read_wav_header(&bytes).unwrap();
+        assert_eq!(header.channels, 1);
+        assert_eq!(header.sample_rate, 16000);
+        assert_eq!(header.data_size, Some(3200));
+
+        let header = read_wav_file_header(file.path()).unwrap();
+        assert_eq!(header.duration_ms(), Some(100));
+    }
+
+    #[test]
+    fn test_duration() {
+        let header = read_wav_header(&pcm_header(2, 48000, 192_000)).unwrap();
+        assert_eq!(header.duration_ms(), Some(1000));
+
+        let header = read_wav_header(&pcm_header(1, 16000, 0)[..36]).unwrap();
+        assert_eq!(header.duration_ms(), None);
+    }
+
+    #[test]
+    fn test_skips_chunks_before_data() {
+        let mut bytes = pcm_header(1, 16000, 0);
+        // Insert an odd-sized LIST chunk (padded to even) between fmt and data
+        let list = [b"LIST".as_slice(), &3u32.to_le_bytes(), b"abc\0"].concat();
+        bytes.splice(36..36, list);
+
+        let header = read_wav_header(&bytes).unwrap();
+        assert_eq!(header.sample_rate, 16000);
+        assert_eq!(header.data_size, Some(0));
+    }
+
+    #[test]
+    fn test_truncated_header() {
+        let bytes = pcm_header(2, 48000, 0);
+
+        let err = read_wav_header(&bytes[..8]).unwrap_err();
+        assert!(err.to_string().contains("at least 12 bytes"));
+
+        let err = read_wav_header(&bytes[..30]).unwrap_err();
+        assert!(err.to_string().contains("fmt chunk is truncated"));
+
+        // Without the data chunk header the format is still known
+        let header = read_wav_header(&bytes[..36]).unwrap();
+        assert_eq!(header.data_size, None);
+    }
+
+    #[test]
+    fn test_rejects_non_wav() {
+        let err = read_wav_header(b"ID3\x04\x00\x00\x00\x00\x00\x00\x00\x00mp3 data").unwrap_err();
+        assert!(err.to_string().contains("RIFF/WAVE signature"));
+
+        let mut bytes = pcm_header(1, 16000, 0);
+        bytes[16..20].copy_from_slice(&8u32.to_le_bytes());
+        let err = read_wav_header(&bytes).unwrap_err();
+        assert!(err.to_string().contains("fmt chunk is 8 bytes"));
+
+        let mut bytes = pcm_header(1, 16000, 0);
+        bytes[22..24].copy_from_slice(&0u16.to_le_bytes());
+        assert!(read_wav_header(&bytes).is_err());
+    }
+}
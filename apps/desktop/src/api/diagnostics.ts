@@ -0,0 +1,41 @@
+/**
+ * Diagnostics API - Frontend bindings for the self-check Tauri command
+ */
+
+import { invoke } from "./invoke";
+
+export type CheckStatus = "ok" | "warning" | "failed";
+
+/**
+ * Result of one diagnostic check
+ */
+export interface DiagnosticCheck {
+  name: string;
+  status: CheckStatus;
+  message: string;
+}
+
+/**
+ * Health report covering the database, recordings directory, API keys,
+ * audio devices and provider connectivity
+ */
+export interface DiagnosticsReport {
+  healthy: boolean;
+  checks: DiagnosticCheck[];
+}
+
+/**
+ * Run all diagnostic checks
+ */
+export async function runDiagnostics(): Promise<DiagnosticsReport> {
+  return invoke("run_diagnostics");
+}
+
+/**
+ * Get the path of the application log file, for attaching to bug reports
+ *
+ * @returns Promise that resolves to the file path, or null if logs only go to stderr
+ */
+export async function getLogPath(): Promise<string | null> {
+  return invoke("get_log_path");
+}
@@ -2,7 +2,7 @@
  * Insights API - Frontend bindings for LLM insight generation Tauri commands
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "./invoke";
 import type { InsightType } from "../types";
 
 /**
@@ -13,7 +13,20 @@ export interface StoredInsight {
   meeting_id: number;
   insight_type: InsightType;
   content: string;
+  // { truncated: true } when the response hit the token limit; retry with a higher max_tokens
+  // language is set when the insight was requested in a target language
+  metadata?: { truncated?: boolean; language?: string };
   created_at: number;
+  // true when accepted, false when rejected, null until the user rates it
+  feedback: boolean | null;
+}
+
+/**
+ * An insight type that could not be generated
+ */
+export interface FailedInsight {
+  insight_type: InsightType;
+  error: { code: string; message: string };
 }
 
 /**
@@ -21,6 +34,8 @@ export interface StoredInsight {
  */
 export interface MeetingInsightsResponse {
   insights: StoredInsight[];
+  // Types that failed or came back empty; retry them with regenerateInsightTypes
+  failed: FailedInsight[];
 }
 
 /**
@@ -28,13 +43,30 @@ export interface MeetingInsightsResponse {
  */
 export interface GenerateMeetingInsightsRequest {
   meeting_id: number;
-  provider: string;
-  model: string;
+  // Omit to use the meeting's override, then the active LLM service
+  provider?: string;
+  model?: string;
   insight_types: InsightType[];
   temperature?: number;
   max_tokens?: number;
+  // Ask for JSON output; only for models that support it
+  json_mode?: boolean;
+  // Agenda, prior-meeting summary, etc.; defaults to the meeting's notes
+  context?: string;
+  // Language to write the insights in, e.g. "Spanish"
+  target_language?: string;
+  // Length of the insights; sets max_tokens unless it is given. Defaults to "standard"
+  detail_level?: DetailLevel;
+  // Prefix transcript lines with [mm:ss] so action items cite when they came up;
+  // cited times are stored in the insight's metadata as time_references_ms
+  include_timestamps?: boolean;
 }
 
+/**
+ * How long and thorough generated insights are
+ */
+export type DetailLevel = "brief" | "standard" | "detailed";
+
 /**
  * Generate insights for a meeting using the configured LLM
  *
@@ -47,6 +79,61 @@ export async function generateMeetingInsights(
   return invoke("generate_meeting_insights", { request });
 }
 
+/**
+ * Generate and store only the given insight types, e.g. the ones that failed
+ *
+ * The retry keeps the original request's options; only its insight types are replaced.
+ *
+ * @param request - The request the insights were first generated with
+ * @param insightTypes - The insight types to generate
+ * @returns Promise that resolves to the newly stored insights and any that failed again
+ */
+export async function regenerateInsightTypes(
+  request: GenerateMeetingInsightsRequest,
+  insightTypes: InsightType[]
+): Promise<MeetingInsightsResponse> {
+  return invoke("regenerate_insight_types", { request, insightTypes });
+}
+
+/**
+ * Generate and store only a summary for a meeting
+ *
+ * @param meetingId - The ID of the meeting
+ * @param provider - The LLM provider to use
+ * @param model - The model to use
+ * @param targetLanguage - Optional language to write the summary in, e.g. "Spanish"
+ * @param detailLevel - Optional summary length; defaults to "standard"
+ * @returns Promise that resolves to the stored summary insight
+ */
+export async function generateMeetingSummary(
+  meetingId: number,
+  provider: string,
+  model: string,
+  targetLanguage?: string,
+  detailLevel?: DetailLevel
+): Promise<StoredInsight> {
+  return invoke("generate_meeting_summary", {
+    meetingId,
+    provider,
+    model,
+    targetLanguage,
+    detailLevel,
+  });
+}
+
+/**
+ * Name an untitled meeting from its summary or transcript with the active LLM
+ *
+ * @param meetingId - The ID of the meeting
+ * @returns Promise that resolves to the new title, or null if the meeting
+ * already has a title or has no transcript
+ */
+export async function generateMeetingTitle(
+  meetingId: number
+): Promise<string | null> {
+  return invoke("generate_meeting_title", { meetingId });
+}
+
 /**
  * Get stored insights for a meeting
  *
@@ -81,6 +168,55 @@ export async function getDefaultPrompts(
   });
 }
 
+/**
+ * Prompt template in use for an insight type
+ */
+export interface PromptTemplateResponse {
+  insight_type: InsightType;
+  prompt: string;
+  is_custom: boolean;
+}
+
+/**
+ * Get the prompt template for an insight type (custom if saved, otherwise the default)
+ *
+ * @param insightType - The insight type
+ * @returns Promise that resolves to the prompt template
+ */
+export async function getPromptTemplate(
+  insightType: InsightType
+): Promise<PromptTemplateResponse> {
+  return invoke("get_prompt_template", { insightType });
+}
+
+/**
+ * Save a custom prompt template for an insight type
+ *
+ * The template must contain the {transcript} placeholder.
+ *
+ * @param insightType - The insight type
+ * @param template - The prompt template text
+ * @returns Promise that resolves when the template is saved
+ */
+export async function savePromptTemplate(
+  insightType: InsightType,
+  template: string
+): Promise<void> {
+  return invoke("save_prompt_template", { insightType, template });
+}
+
+/**
+ * Discard the custom prompt template for an insight type
+ *
+ * @param insightType - The insight type
+ * @returns Promise that resolves to the default prompt template
+ */
+export async function resetPromptTemplate(
+  insightType: InsightType
+): Promise<PromptTemplateResponse> {
+  return invoke("reset_prompt_template", { insightType });
+}
+
 /**
  * List all supported LLM providers
  *
@@ -90,6 +226,16 @@ export async function listLlmProviders(): Promise<string[]> {
   return invoke("list_llm_providers");
 }
 
+/**
+ * Get a single stored insight
+ *
+ * @param insightId - The ID of the insight
+ * @returns Promise that resolves to the insight; rejects with a not_found error if it doesn't exist
+ */
+export async function getInsight(insightId: number): Promise<StoredInsight> {
+  return invoke("get_insight", { insightId });
+}
+
 /**
  * Update an existing insight's content
  *
@@ -101,6 +247,20 @@ export async function updateInsight(insightId: number, content: string): Promise
   return invoke("update_insight", { insightId, content });
 }
 
+/**
+ * Mark an insight as accepted or rejected
+ *
+ * @param insightId - The ID of the insight to rate
+ * @param accepted - true to accept, false to reject, null to clear the rating
+ * @returns Promise that resolves to the rated insight
+ */
+export async function setInsightFeedback(
+  insightId: number,
+  accepted: boolean | null
+): Promise<StoredInsight> {
+  return invoke("set_insight_feedback", { insightId, accepted });
+}
+
 /**
  * Delete all insights for a meeting
  *
@@ -0,0 +1,75 @@
+/**
+ * Invoke wrapper - Converts structured backend errors into CommandError instances
+ */
+
+import { invoke as tauriInvoke, type InvokeArgs } from "@tauri-apps/api/core";
+
+/**
+ * Error codes returned by backend commands
+ */
+export type CommandErrorCode =
+  | "database"
+  | "io"
+  | "serialization"
+  | "network"
+  | "keychain"
+  | "keychain_locked"
+  | "audio_capture"
+  | "detection"
+  | "transcription"
+  | "llm"
+  | "config"
+  | "not_found"
+  | "invalid_input"
+  | "invalid_state"
+  | "unsupported"
+  | "cancelled"
+  | "internal";
+
+/**
+ * Error thrown when a backend command fails
+ *
+ * Stringifies to the human-readable message, so existing `${err}` usage keeps working.
+ */
+export class CommandError extends Error {
+  code: CommandErrorCode;
+
+  constructor(code: CommandErrorCode, message: string) {
+    super(message);
+    this.name = "CommandError";
+    this.code = code;
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+function isCommandErrorPayload(
+  value: unknown
+): value is { code: CommandErrorCode; message: string } {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    typeof (value as { code?: unknown }).code === "string" &&
+    typeof (value as { message?: unknown }).message === "string"
+  );
+}
+
+/**
+ * Invoke a Tauri command, rethrowing `{ code, message }` errors as CommandError
+ *
+ * @param cmd - The command name
+ * @param args - Command arguments
+ * @returns Promise that resolves to the command result
+ */
+export async function invoke<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+  try {
+    return await tauriInvoke<T>(cmd, args);
+  } catch (err) {
+    if (isCommandErrorPayload(err)) {
+      throw new CommandError(err.code, err.message);
+    }
+    throw err;
+  }
+}
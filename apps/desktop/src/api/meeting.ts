@@ -0,0 +1,154 @@
+/**
+ * Meeting API - Frontend bindings for meeting Tauri commands
+ */
+
+import { invoke } from "./invoke";
+import type {
+  AudioDeviceInfo,
+  AudioInfo,
+  Meeting,
+  MeetingExport,
+  MeetingModelOverride,
+} from "../types";
+
+/**
+ * Set the user-authored notes of a meeting
+ *
+ * @param meetingId - The ID of the meeting
+ * @param notes - The notes text; blank or null clears them
+ * @returns Promise that resolves when the notes are saved
+ */
+export async function updateMeetingNotes(
+  meetingId: number,
+  notes: string | null
+): Promise<void> {
+  return invoke("update_meeting_notes", { meetingId, notes });
+}
+
+/**
+ * Get the duration and format of a meeting's recording
+ *
+ * @param meetingId - The ID of the meeting
+ * @returns Promise that resolves to the recording's header information
+ */
+export async function getAudioInfo(meetingId: number): Promise<AudioInfo> {
+  return invoke("get_audio_info", { meetingId });
+}
+
+/**
+ * Get the ASR/LLM provider and model chosen for a meeting
+ *
+ * @param meetingId - The ID of the meeting
+ * @returns Promise that resolves to the override, or null if the active services are used
+ */
+export async function getMeetingModelOverride(
+  meetingId: number
+): Promise<MeetingModelOverride | null> {
+  return invoke("get_meeting_model_override", { meetingId });
+}
+
+/**
+ * Choose the ASR/LLM provider and model for a single meeting
+ *
+ * Unset fields fall back to the active service configuration; leaving all of them
+ * unset removes the override.
+ *
+ * @param override - Provider and model choices for the meeting
+ * @returns Promise that resolves when the override is saved
+ */
+export async function setMeetingModelOverride(
+  override: MeetingModelOverride
+): Promise<void> {
+  return invoke("set_meeting_model_override", {
+    meetingId: override.meeting_id,
+    asrProvider: override.asr_provider ?? null,
+    asrModel: override.asr_model ?? null,
+    llmProvider: override.llm_provider ?? null,
+    llmModel: override.llm_model ?? null,
+  });
+}
+
+/**
+ * Import an audio file recorded elsewhere as a finished meeting
+ *
+ * The file is copied into the recordings directory and can then be transcribed and
+ * summarized like a captured meeting. Only WAV files are supported.
+ *
+ * @param path - Path of the WAV file to import
+ * @param platform - Platform name, e.g. "zoom" or "in-person"
+ * @param title - Optional meeting title
+ * @returns Promise that resolves to the created meeting
+ */
+export async function importMeeting(
+  path: string,
+  platform: string,
+  title?: string
+): Promise<Meeting> {
+  return invoke("import_meeting", { path, platform, title });
+}
+
+/**
+ * Export a meeting with its participants, transcripts and insights as one JSON document
+ *
+ * @param meetingId - The ID of the meeting
+ * @returns Promise that resolves to the document, including its schema version
+ */
+export async function exportMeetingJson(meetingId: number): Promise<MeetingExport> {
+  return invoke("export_meeting_json", { meetingId });
+}
+
+/**
+ * Recreate a meeting from a document made by exportMeetingJson
+ *
+ * The meeting is always added as a new meeting, without a recording. Documents
+ * from a newer schema version are rejected.
+ *
+ * @param document - The parsed export document
+ * @returns Promise that resolves to the imported meeting
+ */
+export async function importMeetingJson(document: MeetingExport): Promise<Meeting> {
+  return invoke("import_meeting_json", { document });
+}
+
+/**
+ * What stopAll shut down
+ */
+export interface StopAllResult {
+  meeting_id?: number;
+  streaming_closed: boolean;
+}
+
+/**
+ * Stop the active meeting, audio capture and any streaming session
+ *
+ * The recording is saved before the promise resolves. Safe to call when nothing is
+ * running.
+ *
+ * @returns Promise that resolves to what was stopped
+ */
+export async function stopAll(): Promise<StopAllResult> {
+  return invoke("stop_all");
+}
+
+/**
+ * List speaker and microphone devices with their stable device IDs
+ *
+ * Pass a device's `id` as `speaker_device` / `microphone_device` to `start_meeting`;
+ * unlike list indices, IDs keep pointing at the same device when others are plugged in.
+ *
+ * @returns Promise that resolves to the devices, speakers first
+ */
+export async function listAudioDevicesDetailed(): Promise<AudioDeviceInfo[]> {
+  return invoke("list_audio_devices_detailed");
+}
+
+/**
+ * Re-enumerate the audio devices, e.g. after a headset is plugged in
+ *
+ * The new list is also sent to every view as a "devices-changed" event.
+ *
+ * @returns Promise that resolves to the devices, speakers first
+ */
+export async function refreshAudioDevices(): Promise<AudioDeviceInfo[]> {
+  return invoke("refresh_audio_devices");
+}
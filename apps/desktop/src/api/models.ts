@@ -0,0 +1,43 @@
+/**
+ * Model overrides API - Frontend bindings for per-model LLM settings
+ */
+
+import { invoke } from "./invoke";
+import type { ModelOverride } from "../types";
+
+/**
+ * Get the user's model overrides for an LLM provider
+ *
+ * @param provider - The LLM provider (e.g. "openai", "anthropic")
+ * @returns Promise that resolves to the provider's overrides
+ */
+export async function getModelOverrides(
+  provider: string
+): Promise<ModelOverride[]> {
+  return invoke("get_model_overrides", { provider });
+}
+
+/**
+ * Set the context window of a model the app can only estimate
+ *
+ * The value replaces the estimate the next time the provider's models are fetched.
+ *
+ * @param provider - The LLM provider
+ * @param modelId - The model identifier as listed by the provider
+ * @param contextWindow - Context window in tokens
+ * @param notes - Optional note, e.g. where the value came from
+ * @returns Promise that resolves when the override is saved
+ */
+export async function saveModelOverride(
+  provider: string,
+  modelId: string,
+  contextWindow?: number,
+  notes?: string
+): Promise<void> {
+  return invoke("save_model_override", {
+    provider,
+    modelId,
+    contextWindow: contextWindow ?? null,
+    notes: notes ?? null,
+  });
+}
@@ -0,0 +1,68 @@
+/**
+ * Network API - Frontend bindings for the proxy and rate limit settings Tauri commands
+ */
+
+import { invoke } from "./invoke";
+
+/**
+ * Proxy for provider requests
+ *
+ * With no `url`, the HTTPS_PROXY / HTTP_PROXY / NO_PROXY environment variables apply.
+ */
+export interface ProxyConfig {
+  /** Proxy URL, e.g. "http://proxy.corp.example:8080" */
+  url?: string;
+  /** Comma-separated hosts, domains and IP ranges reached directly, e.g. "localhost,.corp.example" */
+  no_proxy?: string;
+}
+
+/**
+ * Get the proxy used for provider requests
+ *
+ * @returns Promise that resolves to the saved proxy config (empty if none)
+ */
+export async function getProxyConfig(): Promise<ProxyConfig> {
+  return invoke("get_proxy_config");
+}
+
+/**
+ * Save the proxy used for provider requests; applies from the next request
+ *
+ * @param config - The proxy config; blank fields are cleared
+ * @returns Promise that resolves to the saved, normalized config
+ */
+export async function saveProxyConfig(config: ProxyConfig): Promise<ProxyConfig> {
+  return invoke("save_proxy_config", { config });
+}
+
+/**
+ * Request limits for one provider
+ */
+export interface RateLimitConfig {
+  /** Requests allowed in flight at once (1-32) */
+  max_concurrent: number;
+  /** Requests allowed to start in any 60 second span; unset for no limit */
+  requests_per_minute?: number;
+}
+
+/** Limits by provider name, e.g. "deepgram" or "openai" */
+export type RateLimits = Record<string, RateLimitConfig>;
+
+/**
+ * Get the request limits applied to each provider
+ *
+ * @returns Promise that resolves to the saved limits; providers not listed allow 4 concurrent requests
+ */
+export async function getRateLimits(): Promise<RateLimits> {
+  return invoke("get_rate_limits");
+}
+
+/**
+ * Save the request limits for each provider; applies from the next provider call
+ *
+ * @param limits - Limits by provider, replacing any saved before
+ * @returns Promise that resolves to the saved limits
+ */
+export async function saveRateLimits(limits: RateLimits): Promise<RateLimits> {
+  return invoke("save_rate_limits", { limits });
+}
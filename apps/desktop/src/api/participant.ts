@@ -2,7 +2,8 @@
  * Participant API - Frontend bindings for participant mapping Tauri commands
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "./invoke";
+import type { Participant } from "../types";
 
 /**
  * Participant information
@@ -23,6 +24,19 @@ export interface SpeakerSummary {
   participant?: ParticipantInfo;
 }
 
+/**
+ * Talk-time analytics for a single speaker
+ */
+export interface SpeakerTalkTime {
+  speaker_label: string;
+  participant_id?: number;
+  participant_name?: string;
+  duration_ms: number;
+  word_count: number;
+  /** Share of the meeting's duration spent speaking (0-100) */
+  percentage: number;
+}
+
 /**
  * Request to link a speaker to a participant
  */
@@ -45,6 +59,18 @@ export async function getSpeakerSummary(
   return invoke("get_speaker_summary", { meetingId });
 }
 
+/**
+ * Get talk-time analytics for each speaker in a meeting
+ *
+ * @param meetingId - The ID of the meeting
+ * @returns Promise that resolves to speakers sorted by spoken duration
+ */
+export async function getTalkTime(
+  meetingId: number
+): Promise<SpeakerTalkTime[]> {
+  return invoke("get_talk_time", { meetingId });
+}
+
 /**
  * Link a speaker label to a participant (create or update)
  *
@@ -57,6 +83,43 @@ export async function linkSpeakerToParticipant(
   return invoke("link_speaker_to_participant", { request });
 }
 
+/**
+ * Assign participant names to several speaker labels at once
+ *
+ * @param meetingId - The ID of the meeting
+ * @param mapping - Pairs of [speakerLabel, participantName]
+ * @returns Promise that resolves to the number of transcripts linked
+ */
+export async function assignSpeakers(
+  meetingId: number,
+  mapping: [string, string][]
+): Promise<number> {
+  return invoke("assign_speakers", { meetingId, mapping });
+}
+
+/**
+ * A known attendee, e.g. from a calendar invite
+ */
+export interface RosterEntry {
+  name: string;
+  email?: string;
+}
+
+/**
+ * Set the roster of known attendees for a meeting
+ * Attendees stay unlinked until a speaker is assigned to them by name
+ *
+ * @param meetingId - The ID of the meeting
+ * @param participants - Attendee names and optional emails
+ * @returns Promise that resolves to the meeting's participants after the update
+ */
+export async function setParticipants(
+  meetingId: number,
+  participants: RosterEntry[]
+): Promise<Participant[]> {
+  return invoke("set_participants", { meetingId, participants });
+}
+
 /**
  * Unlink a speaker from a participant (remove mapping)
  *
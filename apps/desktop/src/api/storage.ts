@@ -0,0 +1,65 @@
+/**
+ * Storage API - Frontend bindings for disk usage and recording cleanup Tauri commands
+ */
+
+import { invoke } from "./invoke";
+
+/**
+ * Disk space used by one meeting's recordings
+ */
+export interface MeetingStorageUsage {
+  meeting_id: number;
+  title?: string;
+  bytes: number;
+  files: string[];
+}
+
+/**
+ * Disk usage report for the database and recordings
+ */
+export interface StorageUsage {
+  database_bytes: number;
+  recordings_bytes: number;
+  unassigned_recordings_bytes: number;
+  total_bytes: number;
+  meetings: MeetingStorageUsage[];
+}
+
+/**
+ * A recording removed by cleanup
+ */
+export interface FreedRecording {
+  meeting_id: number;
+  path: string;
+  bytes: number;
+}
+
+/**
+ * Result of a recordings cleanup
+ */
+export interface CleanupResult {
+  freed_files: FreedRecording[];
+  bytes_freed: number;
+  skipped_meeting_ids: number[];
+}
+
+/**
+ * Get disk usage of the database and recordings
+ *
+ * @returns Promise that resolves to the usage report, meetings sorted largest first
+ */
+export async function getStorageUsage(): Promise<StorageUsage> {
+  return invoke("get_storage_usage");
+}
+
+/**
+ * Delete recordings of meetings older than a threshold, keeping their transcripts
+ *
+ * @param olderThanDays - Only meetings that started more than this many days ago are cleaned up
+ * @returns Promise that resolves to the freed files
+ */
+export async function cleanupRecordings(
+  olderThanDays: number
+): Promise<CleanupResult> {
+  return invoke("cleanup_recordings", { olderThanDays });
+}
@@ -2,19 +2,57 @@
  * Transcription API - Frontend bindings for transcription Tauri commands
  */
 
-import { invoke } from "@tauri-apps/api/core";
-import type { Transcript, TranscriptionConfig } from "../types";
+import { invoke } from "./invoke";
+import type {
+  GenerateMeetingInsightsRequest,
+  MeetingInsightsResponse,
+} from "./insights";
+import type {
+  Transcript,
+  TranscriptionConfig,
+  TranscriptQuality,
+  TranscriptSource,
+} from "../types";
+
+/**
+ * What an ASR provider supports
+ */
+export interface AsrCapabilities {
+  provider: string;
+  supports_streaming: boolean;
+  supports_diarization: boolean;
+  supports_language_detection: boolean;
+  supports_pii_redaction: boolean;
+  supported_audio_formats: string[];
+  // Largest recording that can be transcribed in one go, in bytes
+  max_upload_bytes?: number;
+}
+
+/**
+ * Estimated cost and time of transcribing a meeting
+ */
+export interface TranscriptionEstimate {
+  provider: string;
+  model?: string;
+  duration_ms: number;
+  duration_source: "audio_file" | "meeting_times";
+  usd_per_minute?: number;
+  estimated_cost_usd?: number;
+  estimated_processing_secs?: number;
+}
 
 /**
  * Start transcription for a completed meeting
  *
  * @param meetingId - The ID of the meeting to transcribe
  * @param config - Optional transcription configuration
+ * @param numSpeakers - Optional expected number of speakers (improves diarization)
  * @returns Promise that resolves when transcription starts
  */
 export async function startTranscription(
   meetingId: number,
-  config?: TranscriptionConfig
+  config?: TranscriptionConfig,
+  numSpeakers?: number
 ): Promise<void> {
   console.log(">>> FRONTEND: Calling start_transcription for meeting", meetingId);
   console.log(">>> FRONTEND: Config:", config || "undefined (will load from service config)");
@@ -22,28 +60,129 @@ export async function startTranscription(
   await invoke("start_transcription", {
     meetingId,
     config: config, // Pass undefined to let backend load from service config
+    numSpeakers,
   });
 
   console.log(">>> FRONTEND: start_transcription returned successfully");
 }
 
+/**
+ * Re-transcribe a meeting, replacing its existing transcripts
+ *
+ * The old transcripts are kept until the new ones are ready, and left intact if
+ * transcription fails.
+ *
+ * @param meetingId - The ID of the meeting to re-transcribe
+ * @param config - Optional transcription configuration, e.g. with a different model
+ * @param numSpeakers - Optional expected number of speakers (improves diarization)
+ * @returns Promise that resolves when re-transcription starts
+ */
+export async function retranscribe(
+  meetingId: number,
+  config?: TranscriptionConfig,
+  numSpeakers?: number
+): Promise<void> {
+  await invoke("retranscribe", { meetingId, config, numSpeakers });
+}
+
+/**
+ * Transcribe a meeting and then generate its insights, in one call
+ *
+ * Progress is reported through "meeting-processing-progress" events. Insights are
+ * only requested once a transcript is stored; a failure's message names the stage.
+ * cancelTranscription(meetingId) stops it, rejecting with a "cancelled" error.
+ *
+ * @param meetingId - The ID of the meeting to process
+ * @param asrConfig - Optional transcription configuration
+ * @param llmRequest - Insights to generate; its meeting_id must be meetingId
+ * @returns Promise that resolves to the stored insights
+ */
+export async function processMeeting(
+  meetingId: number,
+  asrConfig: TranscriptionConfig | undefined,
+  llmRequest: GenerateMeetingInsightsRequest
+): Promise<MeetingInsightsResponse> {
+  return invoke("process_meeting", { meetingId, asrConfig, llmRequest });
+}
+
 /**
  * Get the current transcription status
  *
- * @returns Promise that resolves to the meeting ID being transcribed, or null if none
+ * @returns Promise that resolves to the IDs of meetings being transcribed (empty if none)
  */
-export async function getTranscriptionStatus(): Promise<number | null> {
+export async function getTranscriptionStatus(): Promise<number[]> {
   return invoke("get_transcription_status");
 }
 
+/**
+ * Cancel an in-progress transcription
+ *
+ * @param meetingId - The ID of the meeting being transcribed
+ * @returns Promise that resolves once the transcription has been cancelled
+ */
+export async function cancelTranscription(meetingId: number): Promise<void> {
+  return invoke("cancel_transcription", { meetingId });
+}
+
+/**
+ * Estimate the cost and time of transcribing a meeting with the active ASR service
+ *
+ * No request is sent to the provider.
+ *
+ * @param meetingId - The ID of the meeting
+ * @returns Promise that resolves to the estimate
+ */
+export async function estimateTranscription(
+  meetingId: number
+): Promise<TranscriptionEstimate> {
+  return invoke("estimate_transcription", { meetingId });
+}
+
 /**
  * Get transcripts for a meeting
  *
  * @param meetingId - The ID of the meeting
+ * @param minConfidence - Optional threshold (0-1); lower-confidence segments are left out
+ * @param source - Optional; only batch transcription or only live caption segments
  * @returns Promise that resolves to array of transcript segments
  */
-export async function getTranscripts(meetingId: number): Promise<Transcript[]> {
-  return invoke("get_transcripts", { meetingId });
+export async function getTranscripts(
+  meetingId: number,
+  minConfidence?: number,
+  source?: TranscriptSource
+): Promise<Transcript[]> {
+  return invoke("get_transcripts", { meetingId, minConfidence, source });
+}
+
+/**
+ * Get the overall and per-speaker confidence of a meeting's transcript
+ *
+ * @param meetingId - The ID of the meeting
+ * @returns Promise that resolves to the quality summary; `is_low` flags a poor transcript
+ */
+export async function getTranscriptQuality(meetingId: number): Promise<TranscriptQuality> {
+  return invoke("get_transcript_quality", { meetingId });
+}
+
+/**
+ * Correct the text of a transcript segment
+ *
+ * @param transcriptId - The ID of the transcript segment
+ * @param text - The corrected text; must not be blank
+ * @returns Promise that resolves when the edit is saved
+ */
+export async function editTranscript(transcriptId: number, text: string): Promise<void> {
+  return invoke("edit_transcript", { transcriptId, text });
+}
+
+/**
+ * Delete a single transcript segment, keeping the rest of the transcript
+ *
+ * @param transcriptId - The ID of the transcript segment
+ * @returns Promise that resolves when the segment is deleted; rejects with not_found if it doesn't exist
+ */
+export async function deleteTranscript(transcriptId: number): Promise<void> {
+  return invoke("delete_transcript", { transcriptId });
 }
 
 /**
@@ -57,6 +196,22 @@ export async function isTranscriptionAvailable(): Promise<boolean> {
   return invoke("is_transcription_available");
 }
 
+/**
+ * Get the path of a meeting's saved raw ASR response, for attaching to bug reports
+ *
+ * Raw responses are only saved while the ASR service's `save_raw_response` setting is on.
+ *
+ * @param meetingId - The ID of the meeting
+ * @param provider - The ASR provider (e.g. "deepgram", "assemblyai")
+ * @returns Promise that resolves to the file path, or null if none was saved
+ */
+export async function getRawResponsePath(
+  meetingId: number,
+  provider: string
+): Promise<string | null> {
+  return invoke("get_raw_response_path", { meetingId, provider });
+}
+
 /**
  * Delete all transcripts for a meeting
  *
@@ -68,3 +223,13 @@ export async function isTranscriptionAvailable(): Promise<boolean> {
 export async function deleteTranscripts(meetingId: number): Promise<void> {
   return invoke("delete_transcripts", { meetingId });
 }
+
+/**
+ * Get what an ASR provider supports; no API key is needed
+ *
+ * @param provider - The ASR provider ("deepgram" or "assemblyai")
+ * @returns Promise that resolves to the provider's capabilities
+ */
+export async function getAsrCapabilities(provider: string): Promise<AsrCapabilities> {
+  return invoke("get_asr_capabilities", { provider });
+}
@@ -1,5 +1,5 @@
 import { useState, useEffect, useRef } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../api/invoke';
 import { Box, Button, HStack, VStack, Text } from '@chakra-ui/react';
 import { AudioDeviceSelector } from './AudioDeviceSelector';
 
@@ -1,5 +1,18 @@
 import { useState, useEffect } from "react";
-import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "../api/invoke";
+import { listAudioDevicesDetailed } from "../api/meeting";
+import type { AudioDeviceInfo, MeetingNoAudio } from "../types";
+
+// localStorage keys of the last selected device IDs
+const SPEAKER_DEVICE_KEY = "meet-scribe.speakerDeviceId";
+const MICROPHONE_DEVICE_KEY = "meet-scribe.microphoneDeviceId";
+
+// The saved device if it is still connected, otherwise the first one
+function pickDevice(devices: AudioDeviceInfo[], savedId: string | null): string {
+  const saved = devices.find((device) => device.id === savedId);
+  return saved ? saved.id : devices.length > 0 ? devices[0].id : "";
+}
 
 interface MeetingStatus {
   meeting_id: number | null;
@@ -44,6 +57,7 @@ const PLATFORMS = [
 
 // Common languages supported by AssemblyAI and Deepgram
 const LANGUAGES = [
+  { value: "auto", label: "Auto-detect" },
   { value: "en", label: "English" },
   { value: "es", label: "Spanish" },
   { value: "fr", label: "French" },
@@ -66,10 +80,11 @@ function ActiveMeeting() {
   const [selectedPlatform, setSelectedPlatform] = useState<string>("teams");
   const [meetingTitle, setMeetingTitle] = useState<string>("");
   const [selectedLanguage, setSelectedLanguage] = useState<string>("en");
-  const [selectedSpeakerDevice, setSelectedSpeakerDevice] = useState<string>("0: Default Communication Device");
+  // Selections are stable device IDs ("default" for the default speaker)
+  const [selectedSpeakerDevice, setSelectedSpeakerDevice] = useState<string>("default");
   const [selectedMicrophoneDevice, setSelectedMicrophoneDevice] = useState<string>("");
-  const [speakerDevices, setSpeakerDevices] = useState<string[]>([]);
-  const [microphoneDevices, setMicrophoneDevices] = useState<string[]>([]);
+  const [speakerDevices, setSpeakerDevices] = useState<AudioDeviceInfo[]>([]);
+  const [microphoneDevices, setMicrophoneDevices] = useState<AudioDeviceInfo[]>([]);
   const [loadingDevices, setLoadingDevices] = useState<boolean>(false);
   const [meetingStatus, setMeetingStatus] = useState<MeetingStatus>({
     meeting_id: null,
@@ -98,17 +113,21 @@ function ActiveMeeting() {
     const loadAudioDevices = async () => {
       setLoadingDevices(true);
       try {
-        const speakers = await invoke<string[]>("list_speaker_devices");
-        const microphones = await invoke<string[]>("list_microphone_devices");
+        const devices = await listAudioDevicesDetailed();
+        const speakers = devices.filter((device) => device.kind === "speaker");
+        const microphones = devices.filter((device) => device.kind === "microphone");
 
         setSpeakerDevices(speakers);
         setMicrophoneDevices(microphones);
 
+        // Reselect the last used devices if they are still connected
         if (speakers.length > 0) {
-          setSelectedSpeakerDevice(speakers[0]); // Default to first speaker device
+          setSelectedSpeakerDevice(pickDevice(speakers, localStorage.getItem(SPEAKER_DEVICE_KEY)));
         }
         if (microphones.length > 0) {
-          setSelectedMicrophoneDevice(microphones[0]); // Default to first microphone device
+          setSelectedMicrophoneDevice(
+            pickDevice(microphones, localStorage.getItem(MICROPHONE_DEVICE_KEY))
+          );
         }
       } catch (err) {
         console.error("Failed to load audio devices:", err);
@@ -121,6 +140,17 @@ function ActiveMeeting() {
     loadAudioDevices();
   }, []);
 
+  // The recording is saved after stop_meeting returns, so an empty capture is reported by event
+  useEffect(() => {
+    const unlisten = listen<MeetingNoAudio>("meeting-no-audio", (event) => {
+      setError(event.payload.message);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // Poll meeting status periodically
   useEffect(() => {
     const pollStatus = async () => {
@@ -380,7 +410,10 @@ function ActiveMeeting() {
               <select
                 id="speakerDevice"
                 value={selectedSpeakerDevice}
-                onChange={(e) => setSelectedSpeakerDevice(e.target.value)}
+                onChange={(e) => {
+                  setSelectedSpeakerDevice(e.target.value);
+                  localStorage.setItem(SPEAKER_DEVICE_KEY, e.target.value);
+                }}
                 disabled={loadingDevices}
                 style={{
                   width: "100%",
@@ -398,8 +431,8 @@ function ActiveMeeting() {
                   <option>No speaker devices found</option>
                 ) : (
                   speakerDevices.map((device) => (
-                    <option key={device} value={device}>
-                      {device}
+                    <option key={device.id} value={device.id}>
+                      {device.name}
                     </option>
                   ))
                 )}
@@ -424,7 +457,10 @@ function ActiveMeeting() {
               <select
                 id="microphoneDevice"
                 value={selectedMicrophoneDevice}
-                onChange={(e) => setSelectedMicrophoneDevice(e.target.value)}
+                onChange={(e) => {
+                  setSelectedMicrophoneDevice(e.target.value);
+                  localStorage.setItem(MICROPHONE_DEVICE_KEY, e.target.value);
+                }}
                 disabled={loadingDevices}
                 style={{
                   width: "100%",
@@ -442,8 +478,8 @@ function ActiveMeeting() {
                   <option>No microphone devices found</option>
                 ) : (
                   microphoneDevices.map((device) => (
-                    <option key={device} value={device}>
-                      {device}
+                    <option key={device.id} value={device.id}>
+                      {device.name}
                     </option>
                   ))
                 )}
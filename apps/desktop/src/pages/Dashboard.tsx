@@ -1,6 +1,6 @@
 /// Dashboard page - main landing page
 import { useEffect, useState } from "react";
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "../api/invoke";
 
 function Dashboard() {
   const [version, setVersion] = useState<string>("");
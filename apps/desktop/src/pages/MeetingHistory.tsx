@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
-import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { invoke, CommandError } from "../api/invoke";
 import { MarkdownContent } from "../components/MarkdownContent";
 import { MarkdownEditor } from "../components/MarkdownEditor";
 import { AudioPlayer } from "../components/AudioPlayer";
@@ -8,6 +9,7 @@ import {
   startTranscription,
   getTranscriptionStatus,
   getTranscripts,
+  getTranscriptQuality,
   isTranscriptionAvailable,
   deleteTranscripts,
 } from "../api/transcription";
@@ -25,7 +27,16 @@ import {
   deleteMeetingParticipants,
   type SpeakerSummary,
 } from "../api/participant";
-import type { Transcript, InsightType, ServiceConfig } from "../types";
+import type {
+  Transcript,
+  InsightType,
+  ServiceConfig,
+  TranscriptQuality,
+  RecordingStatus,
+  MeetingAudioReady,
+  MeetingAudioFailed,
+  MeetingNoAudio,
+} from "../types";
 import {
   DialogRoot,
   DialogContent,
@@ -49,6 +60,7 @@ interface Meeting {
   participant_count?: number;
   audio_file_path?: string;
   created_at: number;
+  recording_status?: RecordingStatus;
 }
 
 const PLATFORMS = {
@@ -71,9 +83,14 @@ function MeetingHistory() {
   const [successMessage, setSuccessMessage] = useState<string | null>(null);
   const [selectedMeeting, setSelectedMeeting] = useState<Meeting | null>(null);
   const [transcriptionAvailable, setTranscriptionAvailable] = useState<boolean>(false);
-  const [transcribingMeetingId, setTranscribingMeetingId] = useState<number | null>(null);
+  const [transcribingMeetingIds, setTranscribingMeetingIds] = useState<number[]>([]);
+  // Meetings whose recording failed to save after they were stopped
+  const [failedRecordingIds, setFailedRecordingIds] = useState<number[]>([]);
+  // Expected speaker count entered before transcribing, keyed by meeting ID
+  const [expectedSpeakers, setExpectedSpeakers] = useState<{ [meetingId: number]: string }>({});
   const [transcripts, setTranscripts] = useState<{ [meetingId: number]: Transcript[] }>({});
   const [loadingTranscripts, setLoadingTranscripts] = useState<{ [meetingId: number]: boolean }>({});
+  const [transcriptQuality, setTranscriptQuality] = useState<{ [meetingId: number]: TranscriptQuality }>({});
 
   // Insights state
   const [insights, setInsights] = useState<{ [meetingId: number]: StoredInsight[] }>({});
@@ -137,16 +154,49 @@ function MeetingHistory() {
     }
   }, [selectedMeeting]);
 
+  // Recordings are saved after stop_meeting returns, so a just-stopped meeting
+  // can't be transcribed until its audio is reported ready
+  useEffect(() => {
+    const updateMeeting = (meetingId: number, changes: Partial<Meeting>) => {
+      setMeetings((prev) => prev.map((m) => (m.id === meetingId ? { ...m, ...changes } : m)));
+      setSelectedMeeting((prev) => (prev?.id === meetingId ? { ...prev, ...changes } : prev));
+    };
+
+    const unlistenReady = listen<MeetingAudioReady>("meeting-audio-ready", (event) => {
+      updateMeeting(event.payload.meeting_id, {
+        audio_file_path: event.payload.path,
+        recording_status: "recorded",
+      });
+    });
+    const unlistenFailed = listen<MeetingAudioFailed>("meeting-audio-failed", (event) => {
+      setFailedRecordingIds((prev) =>
+        prev.includes(event.payload.meeting_id) ? prev : [...prev, event.payload.meeting_id]
+      );
+      setError(event.payload.message);
+    });
+    const unlistenNoAudio = listen<MeetingNoAudio>("meeting-no-audio", (event) => {
+      updateMeeting(event.payload.meeting_id, { recording_status: "no_audio" });
+    });
+
+    return () => {
+      unlistenReady.then((fn) => fn());
+      unlistenFailed.then((fn) => fn());
+      unlistenNoAudio.then((fn) => fn());
+    };
+  }, []);
+
   // Poll for transcription status every 3 seconds
   useEffect(() => {
     const interval = setInterval(async () => {
       try {
         const status = await getTranscriptionStatus();
-        setTranscribingMeetingId(status);
+        setTranscribingMeetingIds(status);
 
-        // If transcription just completed, reload transcripts for that meeting
-        if (transcribingMeetingId !== null && status === null) {
-          await loadTranscriptsForMeeting(transcribingMeetingId);
+        // Reload transcripts for any meeting whose transcription just completed
+        for (const meetingId of transcribingMeetingIds) {
+          if (!status.includes(meetingId)) {
+            await loadTranscriptsForMeeting(meetingId);
+          }
         }
       } catch (err) {
         console.error("Failed to check transcription status:", err);
@@ -154,7 +204,7 @@ function MeetingHistory() {
     }, 3000);
 
     return () => clearInterval(interval);
-  }, [transcribingMeetingId]);
+  }, [transcribingMeetingIds]);
 
   const checkTranscriptionAvailability = async () => {
     try {
@@ -162,6 +212,10 @@ function MeetingHistory() {
       setTranscriptionAvailable(available);
     } catch (err) {
       console.error("Failed to check transcription availability:", err);
+      if (err instanceof CommandError && err.code === "keychain_locked") {
+        setTranscriptionAvailable(false);
+        setError(err.message);
+      }
     }
   };
 
@@ -223,7 +277,7 @@ function MeetingHistory() {
       await deleteMeetingParticipants(meetingId);
       // Start new transcription
       await startTranscription(meetingId);
-      setTranscribingMeetingId(meetingId);
+      setTranscribingMeetingIds((prev) => (prev.includes(meetingId) ? prev : [...prev, meetingId]));
     } catch (err) {
       setError(`Failed to regenerate transcript: ${err}`);
       console.error(err);
@@ -265,10 +319,9 @@ function MeetingHistory() {
       setSuccessMessage(null);
       setGeneratingInsights(meetingId);
 
+      // Provider and model are resolved by the backend so a meeting's override applies
       const response = await generateMeetingInsights({
         meeting_id: meetingId,
-        provider: llmConfig.provider,
-        model: llmConfig.model,
         insight_types: ["summary", "action_item", "key_point", "decision"],
       });
 
@@ -332,6 +385,10 @@ function MeetingHistory() {
     try {
       const transcriptList = await getTranscripts(meetingId);
       setTranscripts((prev) => ({ ...prev, [meetingId]: transcriptList }));
+      if (transcriptList.length > 0) {
+        const quality = await getTranscriptQuality(meetingId);
+        setTranscriptQuality((prev) => ({ ...prev, [meetingId]: quality }));
+      }
     } catch (err) {
       console.error(`Failed to load transcripts for meeting ${meetingId}:`, err);
     } finally {
@@ -345,11 +402,18 @@ function MeetingHistory() {
       return;
     }
 
+    const speakersInput = expectedSpeakers[meetingId]?.trim();
+    const numSpeakers = speakersInput ? Number(speakersInput) : undefined;
+    if (numSpeakers !== undefined && (!Number.isInteger(numSpeakers) || numSpeakers < 1)) {
+      setError("Number of speakers must be a whole number of at least 1.");
+      return;
+    }
+
     try {
       setError(null);
       setSuccessMessage(null);
-      await startTranscription(meetingId);
-      setTranscribingMeetingId(meetingId);
+      await startTranscription(meetingId, undefined, numSpeakers);
+      setTranscribingMeetingIds((prev) => (prev.includes(meetingId) ? prev : [...prev, meetingId]));
     } catch (err) {
       setError(`Failed to start transcription: ${err}`);
       console.error(err);
@@ -647,11 +711,53 @@ function MeetingHistory() {
 
                   <div style={{ display: "flex", gap: "8px" }}>
                     {/* Transcription button - only show for completed meetings */}
-                    {meeting.end_time && (() => {
+                    {meeting.end_time && meeting.recording_status === "no_audio" && (
+                      <span
+                        title="Nothing was captured while this meeting was recording"
+                        style={{ padding: "8px 12px", color: "#999", fontSize: "13px" }}
+                      >
+                        🔇 No audio recorded
+                      </span>
+                    )}
+                    {meeting.end_time && meeting.recording_status !== "no_audio" && (() => {
                       const hasTranscripts = transcripts[meeting.id]?.length > 0;
-                      const isTranscribing = transcribingMeetingId === meeting.id;
+                      const isTranscribing = transcribingMeetingIds.includes(meeting.id!);
+                      const recordingFailed = failedRecordingIds.includes(meeting.id!);
+                      const audioReady = !!meeting.audio_file_path;
+
+                      if (!audioReady && !hasTranscripts) {
+                        return (
+                          <span
+                            style={{ padding: "8px 12px", color: "#999", fontSize: "13px" }}
+                            title={recordingFailed ? "The recording could not be saved" : "Waiting for the recording to be saved"}
+                          >
+                            {recordingFailed ? "⚠️ Recording not saved" : "⏳ Saving audio..."}
+                          </span>
+                        );
+                      }
 
                       return (
+                        <>
+                        {transcriptionAvailable && !hasTranscripts && !isTranscribing && (
+                          <input
+                            type="number"
+                            min={1}
+                            placeholder="Speakers"
+                            title="Expected number of speakers (optional, improves speaker detection)"
+                            value={expectedSpeakers[meeting.id] ?? ""}
+                            onClick={(e) => e.stopPropagation()}
+                            onChange={(e) =>
+                              setExpectedSpeakers((prev) => ({ ...prev, [meeting.id]: e.target.value }))
+                            }
+                            style={{
+                              width: "90px",
+                              padding: "6px 8px",
+                              border: "1px solid #ccc",
+                              borderRadius: "6px",
+                              fontSize: "13px",
+                            }}
+                          />
+                        )}
                         <button
                           onClick={(e) => {
                             e.stopPropagation();
@@ -688,6 +794,7 @@ function MeetingHistory() {
                         >
                           {isTranscribing ? "⏳ Transcribing..." : hasTranscripts ? "✓ Transcribed" : "📝 Transcribe"}
                         </button>
+                        </>
                       );
                     })()}
                     <button
@@ -979,6 +1086,11 @@ function MeetingHistory() {
               <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginTop: "24px", marginBottom: "12px" }}>
                 <h3 style={{ margin: 0 }}>
                   Transcript ({transcripts[selectedMeeting.id].length} segments)
+                  {transcriptQuality[selectedMeeting.id]?.overall != null && (
+                    <span style={{ marginLeft: "8px", fontSize: "12px", fontWeight: "normal", color: "#666" }}>
+                      {Math.round(transcriptQuality[selectedMeeting.id].overall! * 100)}% confidence
+                    </span>
+                  )}
                 </h3>
                 <div style={{ display: "flex", gap: "8px" }}>
                   <button
@@ -998,14 +1110,14 @@ function MeetingHistory() {
                   </button>
                   <button
                     onClick={() => openRegenerateTranscriptDialog(selectedMeeting.id)}
-                    disabled={transcribingMeetingId === selectedMeeting.id}
+                    disabled={transcribingMeetingIds.includes(selectedMeeting.id!)}
                     style={{
                       padding: "6px 12px",
                       background: "#6c757d",
                       color: "white",
                       border: "none",
                       borderRadius: "4px",
-                      cursor: transcribingMeetingId === selectedMeeting.id ? "not-allowed" : "pointer",
+                      cursor: transcribingMeetingIds.includes(selectedMeeting.id!) ? "not-allowed" : "pointer",
                       fontSize: "12px",
                     }}
                     title="Re-transcribe with different settings or ASR provider"
@@ -1014,6 +1126,21 @@ function MeetingHistory() {
                   </button>
                 </div>
               </div>
+              {transcriptQuality[selectedMeeting.id]?.is_low && (
+                <div
+                  style={{
+                    padding: "8px 12px",
+                    marginBottom: "12px",
+                    background: "#fff4ce",
+                    border: "1px solid #f0c36d",
+                    borderRadius: "4px",
+                    fontSize: "13px",
+                  }}
+                >
+                  ⚠️ The speech recognizer was unsure about much of this transcript. Consider
+                  regenerating it with a different model, or improving the audio for future recordings.
+                </div>
+              )}
               <div
                 style={{
                   maxHeight: "400px",
@@ -1087,7 +1214,7 @@ function MeetingHistory() {
                 })}
               </div>
             </div>
-          ) : transcribingMeetingId === selectedMeeting.id ? (
+          ) : transcribingMeetingIds.includes(selectedMeeting.id!) ? (
             <div
               style={{
                 padding: "24px",
@@ -1,5 +1,5 @@
 import { useState, useEffect } from "react";
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "../api/invoke";
 import {
   Box,
   Container,
@@ -38,8 +38,16 @@ const toaster = createToaster({
 interface ApiKeyStatus {
   has_key: boolean;
   masked_key?: string;
+  /** Set when no key could be read because the keychain is locked */
+  locked?: boolean;
 }
 
+type KeychainStatus =
+  | { status: "present" }
+  | { status: "missing" }
+  | { status: "locked" }
+  | { status: "error"; message: string };
+
 interface ServiceConfig {
   id?: number;
   service_type: string;
@@ -67,6 +75,10 @@ const LLM_PROVIDERS = {
     name: "OpenAI",
     signupUrl: "https://platform.openai.com/",
   },
+  "azure-openai": {
+    name: "Azure OpenAI",
+    signupUrl: "https://portal.azure.com/",
+  },
   anthropic: {
     name: "Anthropic (Claude)",
     signupUrl: "https://console.anthropic.com/",
@@ -79,8 +91,21 @@ const LLM_PROVIDERS = {
     name: "Groq",
     signupUrl: "https://console.groq.com/",
   },
+  mistral: {
+    name: "Mistral",
+    signupUrl: "https://console.mistral.ai/",
+  },
 };
 
+// Azure OpenAI routes requests to a deployment on the customer's resource
+const AZURE_OPENAI_PROVIDER = "azure-openai";
+
+interface AzureOpenAISettings {
+  endpoint: string;
+  deployment: string;
+  api_version: string;
+}
+
 interface ModelInfo {
   id: string;
   name: string;
@@ -107,6 +132,11 @@ function Settings() {
   const [llmAvailableModels, setLlmAvailableModels] = useState<Record<string, ModelInfo[]>>({});
   const [llmModelsLoading, setLlmModelsLoading] = useState<Record<string, boolean>>({});
   const [llmModelChanged, setLlmModelChanged] = useState<Record<string, boolean>>({});
+  const [azureSettings, setAzureSettings] = useState<AzureOpenAISettings>({
+    endpoint: "",
+    deployment: "",
+    api_version: "",
+  });
 
   // Loading states
   const [loading, setLoading] = useState<Record<string, boolean>>({});
@@ -184,6 +214,14 @@ function Settings() {
       const keyStatus = await invoke<ApiKeyStatus>("get_api_key_status", {
         request: { service_type: serviceType, provider },
       });
+      if (!keyStatus.has_key) {
+        // A locked keychain looks like a missing key; check which one it is
+        const keychain = await invoke<KeychainStatus>("keychain_status", {
+          serviceType,
+          provider,
+        });
+        keyStatus.locked = keychain.status === "locked";
+      }
 
       // Load service configuration
       const config = await invoke<ServiceConfig | null>("get_service_config", {
@@ -197,6 +235,13 @@ function Settings() {
         try {
           const settings = JSON.parse(config.settings);
           model = settings.model || "";
+          if (provider === AZURE_OPENAI_PROVIDER) {
+            setAzureSettings({
+              endpoint: settings.endpoint || "",
+              deployment: settings.deployment || "",
+              api_version: settings.api_version || "",
+            });
+          }
         } catch (e) {
           console.error("Error parsing settings:", e);
         }
@@ -246,7 +291,7 @@ function Settings() {
 
     try {
       // Save API key to keychain
-      await invoke("save_api_key", {
+      const result = await invoke<{ format_warning?: string }>("save_api_key", {
         request: {
           service_type: serviceType,
           provider,
@@ -270,6 +315,15 @@ function Settings() {
         type: "success",
         duration: 3000,
       });
+
+      if (result.format_warning) {
+        toaster.create({
+          title: "Check your API key",
+          description: result.format_warning,
+          type: "warning",
+          duration: 6000,
+        });
+      }
     } catch (err) {
       toaster.create({
         title: "Error",
@@ -288,8 +342,17 @@ function Settings() {
     setLoading((prev) => ({ ...prev, [`model_${serviceType}_${provider}`]: true }));
 
     try {
-      // Create settings JSON
-      const settings = JSON.stringify({ model });
+      // Create settings JSON, keeping provider-specific settings (e.g. Azure endpoint)
+      const configs = serviceType === "asr" ? asrConfigs : llmConfigs;
+      let existingSettings = {};
+      if (configs[provider]?.settings) {
+        try {
+          existingSettings = JSON.parse(configs[provider].settings!);
+        } catch (e) {
+          console.error("Error parsing settings:", e);
+        }
+      }
+      const settings = JSON.stringify({ ...existingSettings, model });
 
       // Save configuration with is_active = true to automatically activate when model is selected
       await invoke("save_service_config", {
@@ -332,6 +395,64 @@ function Settings() {
     }
   };
 
+  const handleSaveAzureSettings = async () => {
+    if (!azureSettings.endpoint.trim()) {
+      toaster.create({
+        title: "Error",
+        description: "Please enter the Azure OpenAI endpoint",
+        type: "error",
+        duration: 3000,
+      });
+      return;
+    }
+
+    setLoading((prev) => ({ ...prev, [`azure_${AZURE_OPENAI_PROVIDER}`]: true }));
+
+    try {
+      const existingConfig = llmConfigs[AZURE_OPENAI_PROVIDER];
+      let existingSettings = {};
+      if (existingConfig?.settings) {
+        try {
+          existingSettings = JSON.parse(existingConfig.settings);
+        } catch (e) {
+          console.error("Error parsing settings:", e);
+        }
+      }
+
+      await invoke("save_service_config", {
+        request: {
+          service_type: "llm",
+          provider: AZURE_OPENAI_PROVIDER,
+          is_active: existingConfig?.is_active || false,
+          settings: JSON.stringify({
+            ...existingSettings,
+            endpoint: azureSettings.endpoint.trim(),
+            deployment: azureSettings.deployment.trim() || undefined,
+            api_version: azureSettings.api_version.trim() || undefined,
+          }),
+        },
+      });
+
+      await loadConfig("llm", AZURE_OPENAI_PROVIDER);
+
+      toaster.create({
+        title: "Success",
+        description: "Azure OpenAI connection saved",
+        type: "success",
+        duration: 3000,
+      });
+    } catch (err) {
+      toaster.create({
+        title: "Error",
+        description: `Failed to save Azure OpenAI settings: ${err}`,
+        type: "error",
+        duration: 5000,
+      });
+    } finally {
+      setLoading((prev) => ({ ...prev, [`azure_${AZURE_OPENAI_PROVIDER}`]: false }));
+    }
+  };
+
   const handleToggleService = async (serviceType: string, provider: string, activate: boolean) => {
     const loadingKey = activate ? `activate_${serviceType}_${provider}` : `deactivate_${serviceType}_${provider}`;
     setLoading((prev) => ({ ...prev, [loadingKey]: true }));
@@ -395,6 +516,33 @@ function Settings() {
     setDeleteDialog({ open: false, serviceType: "", provider: "" });
   };
 
+  const handleTestApiKey = async (serviceType: string, provider: string) => {
+    setLoading((prev) => ({ ...prev, [`test_${serviceType}_${provider}`]: true }));
+
+    try {
+      const result = await invoke<{ ok: boolean; message: string }>("test_api_key", {
+        serviceType,
+        provider,
+      });
+
+      toaster.create({
+        title: result.ok ? "API key works" : "API key test failed",
+        description: result.message,
+        type: result.ok ? "success" : "error",
+        duration: result.ok ? 3000 : 6000,
+      });
+    } catch (err) {
+      toaster.create({
+        title: "Error",
+        description: `Failed to test API key: ${err}`,
+        type: "error",
+        duration: 5000,
+      });
+    } finally {
+      setLoading((prev) => ({ ...prev, [`test_${serviceType}_${provider}`]: false }));
+    }
+  };
+
   const handleDeleteApiKey = async () => {
     const { serviceType, provider } = deleteDialog;
     closeDeleteDialog();
@@ -527,6 +675,16 @@ function Settings() {
                 <Box flex={1} p={2} bg="gray.100" borderRadius="md" fontFamily="monospace" fontSize="sm">
                   {keyStatus.masked_key}
                 </Box>
+                <Button
+                  size="sm"
+                  variant="outline"
+                  onClick={() => handleTestApiKey(serviceType, provider)}
+                  loading={loading[`test_${serviceType}_${provider}`]}
+                  px={4}
+                  py={2}
+                >
+                  Test
+                </Button>
                 <Button
                   size="sm"
                   colorScheme="red"
@@ -539,31 +697,72 @@ function Settings() {
                 </Button>
               </HStack>
             ) : (
-              <HStack gap={2}>
+              <VStack align="stretch" gap={2}>
+                {keyStatus?.locked && (
+                  <Text fontSize="sm" color="orange.600">
+                    The system keychain is locked, please unlock it to use the saved API key.
+                  </Text>
+                )}
+                <HStack gap={2}>
+                  <Input
+                    type="password"
+                    placeholder="Enter API Key"
+                    value={apiKey}
+                    onChange={(e) => {
+                      if (serviceType === "asr") {
+                        setAsrApiKeys((prev) => ({ ...prev, [provider]: e.target.value }));
+                      } else {
+                        setLlmApiKeys((prev) => ({ ...prev, [provider]: e.target.value }));
+                      }
+                    }}
+                  />
+                  <Button
+                    colorScheme="blue"
+                    onClick={() => handleSaveApiKey(serviceType, provider)}
+                    loading={isLoading}
+                    px={4}
+                    py={2}
+                  >
+                    Save
+                  </Button>
+                </HStack>
+              </VStack>
+            )}
+          </Box>
+
+          {/* Azure OpenAI connection (endpoint, deployment, API version) */}
+          {provider === AZURE_OPENAI_PROVIDER && (
+            <Box>
+              <Text fontWeight="bold" mb={2}>Connection</Text>
+              <VStack align="stretch" gap={2}>
                 <Input
-                  type="password"
-                  placeholder="Enter API Key"
-                  value={apiKey}
-                  onChange={(e) => {
-                    if (serviceType === "asr") {
-                      setAsrApiKeys((prev) => ({ ...prev, [provider]: e.target.value }));
-                    } else {
-                      setLlmApiKeys((prev) => ({ ...prev, [provider]: e.target.value }));
-                    }
-                  }}
+                  placeholder="Endpoint (https://my-resource.openai.azure.com)"
+                  value={azureSettings.endpoint}
+                  onChange={(e) => setAzureSettings((prev) => ({ ...prev, endpoint: e.target.value }))}
+                />
+                <Input
+                  placeholder="Deployment name"
+                  value={azureSettings.deployment}
+                  onChange={(e) => setAzureSettings((prev) => ({ ...prev, deployment: e.target.value }))}
+                />
+                <Input
+                  placeholder="API version (default 2024-06-01)"
+                  value={azureSettings.api_version}
+                  onChange={(e) => setAzureSettings((prev) => ({ ...prev, api_version: e.target.value }))}
                 />
                 <Button
+                  size="sm"
                   colorScheme="blue"
-                  onClick={() => handleSaveApiKey(serviceType, provider)}
-                  loading={isLoading}
+                  onClick={handleSaveAzureSettings}
+                  loading={loading[`azure_${AZURE_OPENAI_PROVIDER}`]}
                   px={4}
                   py={2}
                 >
-                  Save
+                  Save Connection
                 </Button>
-              </HStack>
-            )}
-          </Box>
+              </VStack>
+            </Box>
+          )}
 
           {/* Model Selection */}
           {hasKey && (
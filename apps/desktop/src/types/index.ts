@@ -1,6 +1,7 @@
 /// TypeScript type definitions for Meet Scribe
 
-export type Platform = "teams" | "zoom" | "meet";
+// Any other platform name (e.g. "webex") is passed through as-is
+export type Platform = "teams" | "zoom" | "meet" | (string & {});
 
 export interface Meeting {
   id?: number;
@@ -9,7 +10,77 @@ export interface Meeting {
   start_time: number;
   end_time?: number;
   participant_count?: number;
+  transcripts_redacted?: boolean;
+  detected_language?: string;
+  notes?: string;
+  audio_offset_ms: number;
+  audio_normalized: boolean;
   created_at: number;
+  transcript_confidence?: number;
+  recording_status?: RecordingStatus;
+  /** Format of the saved recording file */
+  audio_sample_rate?: number;
+  audio_channels?: number;
+  audio_bits_per_sample?: number;
+}
+
+export type RecordingStatus = "recorded" | "no_audio";
+
+export interface MeetingNoAudio {
+  meeting_id: number;
+  message: string;
+}
+
+export interface MeetingAudioReady {
+  meeting_id: number;
+  path: string;
+}
+
+export interface MeetingAudioFailed {
+  meeting_id: number;
+  message: string;
+}
+
+// Payload of the "meeting-processing-progress" event sent by process_meeting
+export interface ProcessingProgress {
+  meeting_id: number;
+  stage: "transcription" | "insights";
+  status: "started" | "completed" | "failed";
+  message?: string;
+}
+
+export interface SpeakerConfidence {
+  speaker?: string;
+  average?: number;
+  segment_count: number;
+}
+
+export interface TranscriptQuality {
+  overall?: number;
+  provider_confidence?: number;
+  speakers: SpeakerConfidence[];
+  scored_segments: number;
+  segment_count: number;
+  is_low: boolean;
+}
+
+export interface MeetingModelOverride {
+  meeting_id: number;
+  asr_provider?: string;
+  asr_model?: string;
+  llm_provider?: string;
+  llm_model?: string;
+}
+
+export interface AudioInfo {
+  path: string;
+  format: "wav" | "flac";
+  /** Null when the file's header doesn't record its length */
+  duration_ms: number | null;
+  sample_rate: number;
+  channels: number;
+  bits_per_sample: number;
+  file_size: number;
 }
 
 export interface Participant {
@@ -27,9 +98,66 @@ export interface Transcript {
   participant_name?: string;
   speaker_label?: string;
   timestamp_ms: number;
+  end_timestamp_ms?: number;
   text: string;
   confidence?: number;
+  /** Segment language reported by the provider (e.g. "es"), in mixed-language meetings */
+  language?: string;
+  /** Whether the segment came from batch transcription or live captions */
+  source: TranscriptSource;
   created_at: number;
+  updated_at?: number;
+}
+
+export type TranscriptSource = "batch" | "streaming";
+
+export interface TranscriptionSegment {
+  text: string;
+  start_ms: number;
+  end_ms: number;
+  speaker_label?: string;
+  confidence?: number;
+  language?: string;
+}
+
+// Payload of the "streaming-interim" and "streaming-final" events.
+// Replace the caption with the same utterance_id instead of appending.
+// Events arrive in segment start order; `sequence` counts them per session.
+export interface StreamingSegmentEvent {
+  meeting_id: number;
+  sequence: number;
+  utterance_id: number;
+  transcript_id?: number;
+  segment: TranscriptionSegment;
+}
+
+export interface AudioFormat {
+  sample_rate: number;
+  channels: number;
+  bits_per_sample: number;
+}
+
+// Audio device from list_audio_devices_detailed. `id` is the stable OS device ID
+// ("default" for the default speaker) and is what should be remembered across sessions.
+export interface AudioDeviceInfo {
+  index: number;
+  id: string;
+  name: string;
+  kind: "speaker" | "microphone";
+}
+
+// Payload of the "devices-changed" event sent by refresh_audio_devices
+export interface DevicesChanged {
+  devices: AudioDeviceInfo[];
+}
+
+// Payload of the "streaming-format-warning" event, emitted when streaming starts while
+// audio is captured in a different format than the provider expects.
+export interface StreamingFormatWarning {
+  meeting_id: number;
+  capture_format: AudioFormat;
+  expected_format: AudioFormat;
+  message: string;
 }
 
 export type InsightType = "summary" | "action_item" | "key_point" | "decision";
@@ -41,10 +169,21 @@ export interface Insight {
   content: string;
   metadata?: string;
   created_at: number;
+  feedback?: boolean | null;
 }
 
 export type ServiceType = "asr" | "llm";
 
+// Whole-meeting JSON document from export_meeting_json, accepted by import_meeting_json
+export interface MeetingExport {
+  schema_version: number;
+  exported_at: number;
+  meeting: Meeting;
+  participants: Participant[];
+  transcripts: Transcript[];
+  insights: Insight[];
+}
+
 export interface ServiceConfig {
   id?: number;
   service_type: ServiceType;
@@ -62,6 +201,13 @@ export interface TranscriptionConfig {
   language?: string;
   model?: string;
   additional_settings?: Record<string, unknown>;
+  enable_pii_redaction?: boolean;
+  pii_policies?: string[];
+  multichannel?: boolean;
+  min_confidence?: number;
+  normalize_speaker_labels?: boolean;
+  // Split a transcript returned as one block into sentences with estimated times
+  sentence_segmentation?: boolean;
 }
 
 export interface TranscriptionSegment {
@@ -70,10 +216,30 @@ export interface TranscriptionSegment {
   end_ms: number;
   speaker_label?: string;
   confidence?: number;
+  language?: string;
+}
+
+export interface ModelInfo {
+  name: string;
+  version?: string;
+  arch?: string;
+}
+
+export interface ModelOverride {
+  id?: number;
+  provider: string;
+  model_id: string;
+  context_window?: number;
+  notes?: string;
+  created_at: number;
+  updated_at: number;
 }
 
 export interface TranscriptionResult {
   text: string;
   segments: TranscriptionSegment[];
   confidence?: number;
+  detected_language?: string;
+  model_info?: ModelInfo;
+  request_id?: string;
 }